@@ -8,20 +8,28 @@
 use std::default::Default;
 use std::fs::create_dir_all;
 use std::num::NonZeroU32;
-use std::path::PathBuf;
-use std::sync::atomic::AtomicBool;
+use std::path::{Path, PathBuf};
 use std::sync::Mutex;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
+use crate::oci::{sha256_digest, OciClient, OciReference};
+use crate::verification::{ArchiveVerification, SIGNATURE_EXTENSION};
 use crate::Error::GitError;
+use flate2::read::GzDecoder;
 use gix::clone::PrepareFetch;
 use gix::create::Kind;
+use gix::refs::transaction::PreviousValue;
 use gix::remote::fetch::Shallow;
 use gix::{create, open, progress};
 use miette::Diagnostic;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use tempdir::TempDir;
+use weaver_common::cancellation::CancellationToken;
 use weaver_common::diagnostic::{DiagnosticMessage, DiagnosticMessages};
 
+pub mod oci;
+pub mod verification;
+
 /// An error that can occur while creating or using a cache.
 #[derive(thiserror::Error, Debug, Clone, Serialize, Diagnostic)]
 #[non_exhaustive]
@@ -54,6 +62,40 @@ pub enum Error {
         /// The error message
         message: String,
     },
+
+    /// An error occurred while exchanging with an OCI registry.
+    #[error("OCI error occurred with `{reference}`: {message}")]
+    OciError {
+        /// The OCI reference
+        reference: String,
+        /// The error message
+        message: String,
+    },
+
+    /// A local registry archive can't be read or extracted.
+    #[error("Registry archive `{archive}` not extracted: {message}")]
+    ArchiveError {
+        /// The path of the archive
+        archive: String,
+        /// The error message
+        message: String,
+    },
+
+    /// The checksum or the signature of a registry archive doesn't match.
+    #[error("Verification of `{reference}` failed: {message}")]
+    VerificationFailed {
+        /// The reference of the registry archive
+        reference: String,
+        /// The error message
+        message: String,
+    },
+
+    /// The operation was cancelled or timed out.
+    #[error("Cloning `{repo_url}` was cancelled or timed out")]
+    Cancelled {
+        /// The git repo URL
+        repo_url: String,
+    },
 }
 
 impl From<Error> for DiagnosticMessages {
@@ -62,20 +104,112 @@ impl From<Error> for DiagnosticMessages {
     }
 }
 
+/// The default duration (in seconds) during which a cached registry fetched
+/// from a branch or a tag is used without being fetched again.
+pub const DEFAULT_CACHE_TTL_SECS: u64 = 3600;
+
+/// The freshness policy of the registries fetched into the cache.
+///
+/// The registries fetched from a branch (git) or a tag (OCI) are fetched
+/// again once their time-to-live has elapsed. The content-addressed ones
+/// (OCI references pinned by digest, local archives) never expire.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CachePolicy {
+    /// Fetch the registries again, regardless of their freshness.
+    pub refresh: bool,
+    /// The duration during which a registry fetched from a branch or a tag
+    /// is used without being fetched again.
+    pub ttl: Duration,
+}
+
+impl Default for CachePolicy {
+    fn default() -> Self {
+        Self {
+            refresh: false,
+            ttl: Duration::from_secs(DEFAULT_CACHE_TTL_SECS),
+        }
+    }
+}
+
+/// The freshness metadata of a cache entry, stored next to the entry.
+#[derive(Serialize, Deserialize)]
+struct CacheEntry {
+    /// The URL or the reference the entry was fetched from.
+    source: String,
+    /// The time the entry was fetched (seconds since the Unix epoch).
+    fetched_at: u64,
+    /// The digest the reference was resolved to, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    digest: Option<String>,
+    /// The name of the directory holding the current version of the entry,
+    /// if the entry is versioned.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    dir: Option<String>,
+}
+
+impl CacheEntry {
+    /// Creates the metadata of an entry fetched now.
+    fn new(source: &str, digest: Option<String>) -> Self {
+        Self {
+            source: source.to_owned(),
+            fetched_at: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |elapsed| elapsed.as_secs()),
+            digest,
+            dir: None,
+        }
+    }
+
+    /// Returns the path of the metadata file of the given source in a
+    /// directory of the cache.
+    fn path(dir: &Path, source: &str) -> PathBuf {
+        dir.join(format!("{}.json", entry_key(source)))
+    }
+
+    /// Reads the metadata of an entry, if any.
+    fn read(path: &Path) -> Option<Self> {
+        let content = std::fs::read(path).ok()?;
+        serde_json::from_slice(&content).ok()
+    }
+
+    /// Writes the metadata of an entry. The metadata is written into a
+    /// temporary file renamed once complete, so the readers of other
+    /// processes never read a partially written entry.
+    fn write(&self, path: &Path) -> std::io::Result<()> {
+        let temp_path = path.with_extension(format!("{}.tmp", std::process::id()));
+        std::fs::write(&temp_path, serde_json::to_vec_pretty(self)?)?;
+        std::fs::rename(&temp_path, path).inspect_err(|_| {
+            _ = std::fs::remove_file(&temp_path);
+        })
+    }
+
+    /// Returns the directory holding the current version of the entry in the
+    /// given directory of the cache. Unversioned entries are stored in the
+    /// directory named after the key of their source.
+    fn dir(&self, dir: &Path) -> PathBuf {
+        match &self.dir {
+            Some(name) => dir.join(name),
+            None => dir.join(entry_key(&self.source)),
+        }
+    }
+
+    /// Returns true if the entry is still fresh according to the policy.
+    fn is_fresh(&self, policy: &CachePolicy) -> bool {
+        let age = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |elapsed| elapsed.as_secs())
+            .saturating_sub(self.fetched_at);
+        !policy.refresh && Duration::from_secs(age) < policy.ttl
+    }
+}
+
 /// A cache system for OTel Weaver.
 #[derive(Default)]
 pub struct Cache {
     path: PathBuf,
-    git_repo_dirs: Mutex<std::collections::HashMap<String, GitRepo>>,
-}
-
-/// A git repo that is cloned into a tempdir.
-struct GitRepo {
-    /// Need to allow dead code because we need to keep the tempdir live
-    /// for the lifetime of the GitRepo.
-    #[allow(dead_code)]
-    temp_dir: TempDir,
-    path: PathBuf,
+    policy: CachePolicy,
+    /// The git repos already fetched by this process.
+    git_repo_dirs: Mutex<std::collections::HashMap<String, PathBuf>>,
 }
 
 impl Cache {
@@ -83,6 +217,11 @@ impl Cache {
     /// This directory is used to store the semantic conventions, schemas
     /// and other assets that are fetched from the network.
     pub fn try_new() -> Result<Self, Error> {
+        Self::try_new_with_policy(CachePolicy::default())
+    }
+
+    /// Same as [`Cache::try_new`], with the given freshness policy.
+    pub fn try_new_with_policy(policy: CachePolicy) -> Result<Self, Error> {
         let home = dirs::home_dir().ok_or(Error::HomeDirNotFound)?;
         let cache_path = home.join(".otel-weaver/cache");
 
@@ -92,6 +231,7 @@ impl Cache {
 
         Ok(Self {
             path: cache_path,
+            policy,
             ..Default::default()
         })
     }
@@ -103,37 +243,147 @@ impl Cache {
     /// path is checked to exist in the repo and an error is returned if it doesn't.
     /// If the path exists in the repo, the returned pathbuf is the path to the
     /// subdirectory in the git repo directory.
+    ///
+    /// The clone is kept in the cache and reused until its time-to-live has
+    /// elapsed (see [`CachePolicy`]). If the repo can't be cloned again once
+    /// expired, the expired clone is used, unless a refresh is requested.
     pub fn git_repo(&self, repo_url: String, path: Option<String>) -> Result<PathBuf, Error> {
-        // Checks if a tempdir already exists for this repo
-        if let Some(git_repo_dir) = self
-            .git_repo_dirs
-            .lock()
-            .expect("git_repo_dirs lock failed")
-            .get(&repo_url)
-        {
-            if let Some(subdir) = path {
-                return Ok(git_repo_dir.path.join(subdir));
-            } else {
-                return Ok(git_repo_dir.path.clone());
+        self.git_repo_with_cancellation(repo_url, path, &CancellationToken::default())
+    }
+
+    /// Same as [`Cache::git_repo`], but the clone is interrupted with
+    /// [`Error::Cancelled`] once the given token is cancelled or timed out.
+    pub fn git_repo_with_cancellation(
+        &self,
+        repo_url: String,
+        path: Option<String>,
+        cancellation: &CancellationToken,
+    ) -> Result<PathBuf, Error> {
+        self.git_repo_at_ref_with_cancellation(repo_url, None, path, cancellation)
+    }
+
+    /// Same as [`Cache::git_repo_with_cancellation`], but the repo is checked
+    /// out at the given ref (a tag, a branch, or a full ref name such as
+    /// `refs/tags/v1.26.0`) instead of the default branch of the remote.
+    /// The clones of the different refs of a repo are cached separately.
+    pub fn git_repo_at_ref_with_cancellation(
+        &self,
+        repo_url: String,
+        git_ref: Option<String>,
+        path: Option<String>,
+        cancellation: &CancellationToken,
+    ) -> Result<PathBuf, Error> {
+        let repo_dir = self.git_repo_root(&repo_url, git_ref.as_deref(), cancellation)?;
+
+        // Determines the path to the repo.
+        if let Some(path) = &path {
+            // Checks the existence of the path in the repo.
+            // If the path doesn't exist, returns an error.
+            if !repo_dir.join(path).exists() {
+                return Err(GitError {
+                    repo_url: repo_url.clone(),
+                    message: format!("Path `{}` not found in repo", path),
+                });
             }
+            Ok(repo_dir.join(path))
+        } else {
+            Ok(repo_dir)
+        }
+    }
+
+    /// Returns the root directory of the clone of the given repo, cloning it
+    /// if it is not in the cache or if it has expired. A repo is fetched at
+    /// most once per process.
+    fn git_repo_root(
+        &self,
+        repo_url: &str,
+        git_ref: Option<&str>,
+        cancellation: &CancellationToken,
+    ) -> Result<PathBuf, Error> {
+        // The clones of the different refs of a repo are distinct entries.
+        let source = match git_ref {
+            Some(git_ref) => format!("{}@{}", repo_url, git_ref),
+            None => repo_url.to_owned(),
+        };
+        let git_repo_dirs = || {
+            self.git_repo_dirs
+                .lock()
+                .expect("git_repo_dirs lock failed")
+        };
+        if let Some(repo_dir) = git_repo_dirs().get(&source) {
+            return Ok(repo_dir.clone());
         }
 
-        // Otherwise creates a tempdir for the repo and keeps track of it
-        // in the git_repo_dirs hashmap.
-        let git_repo_dir = TempDir::new_in(self.path.as_path(), "git-repo").map_err(|e| {
-            Error::GitRepoNotCreated {
-                repo_url: repo_url.clone(),
+        // The clones of a ref are kept apart, so the entries of the `git`
+        // directory are the repos the registries can be loaded from.
+        let repos_dir = match git_ref {
+            Some(_) => self.path.join("git").join("refs"),
+            None => self.path.join("git"),
+        };
+        let entry_path = CacheEntry::path(&repos_dir, &source);
+        let entry = CacheEntry::read(&entry_path);
+        let current_dir = entry
+            .as_ref()
+            .map(|entry| entry.dir(&repos_dir))
+            .filter(|dir| dir.is_dir());
+        let fresh = entry.is_some_and(|entry| entry.is_fresh(&self.policy));
+        let clone = match &current_dir {
+            Some(_) if fresh => None,
+            _ => match self.clone_git_repo(repo_url, git_ref, &repos_dir, cancellation) {
+                Ok(clone) => Some(clone),
+                // An expired clone is better than no clone at all.
+                Err(GitError { .. }) if !self.policy.refresh && current_dir.is_some() => None,
+                Err(e) => return Err(e),
+            },
+        };
+
+        // If the same repo has been fetched concurrently, the first clone is
+        // kept and this one is removed.
+        let mut git_repo_dirs = git_repo_dirs();
+        if let Some(repo_dir) = git_repo_dirs.get(&source) {
+            return Ok(repo_dir.clone());
+        }
+        let repo_dir = match clone {
+            Some(clone) => switch_version(
+                clone,
+                &repos_dir,
+                &source,
+                current_dir.as_deref(),
+                self.policy.ttl,
+            )
+            .map_err(|e| Error::GitRepoNotCreated {
+                repo_url: repo_url.to_owned(),
                 message: e.to_string(),
-            }
-        })?;
-        let git_repo_pathbuf = git_repo_dir.path().to_path_buf();
-        let git_repo_path = git_repo_pathbuf.as_path();
+            })?,
+            None => current_dir.expect("a repo is only not cloned if it is in the cache"),
+        };
+        _ = git_repo_dirs.insert(source, repo_dir.clone());
+        Ok(repo_dir)
+    }
+
+    /// Clones a repo into a temporary directory of the given directory. The
+    /// given ref, if any, is fetched and checked out in place of the default
+    /// branch of the remote.
+    fn clone_git_repo(
+        &self,
+        repo_url: &str,
+        git_ref: Option<&str>,
+        repos_dir: &Path,
+        cancellation: &CancellationToken,
+    ) -> Result<TempDir, Error> {
+        let not_created = |message: String| Error::GitRepoNotCreated {
+            repo_url: repo_url.to_owned(),
+            message,
+        };
+        create_dir_all(repos_dir).map_err(|e| not_created(e.to_string()))?;
+        let git_repo_dir =
+            TempDir::new_in(repos_dir, "clone").map_err(|e| not_created(e.to_string()))?;
 
         // Clones the repo into the tempdir.
         // Use shallow clone to save time and space.
         let mut fetch = PrepareFetch::new(
-            repo_url.as_str(),
-            git_repo_path,
+            repo_url,
+            git_repo_dir.path(),
             Kind::WithWorktree,
             create::Options {
                 destination_must_be_empty: true,
@@ -142,58 +392,367 @@ impl Cache {
             open::Options::isolated(),
         )
         .map_err(|e| GitError {
-            repo_url: repo_url.clone(),
+            repo_url: repo_url.to_owned(),
             message: e.to_string(),
         })?
         .with_shallow(Shallow::DepthAtRemote(
             NonZeroU32::new(1).expect("1 is not zero"),
         ));
-
-        let (mut prepare, _outcome) = fetch
-            .fetch_then_checkout(progress::Discard, &AtomicBool::new(false))
+        if let Some(git_ref) = git_ref {
+            let refspec = gix::refspec::parse(
+                format!("+{}:{}", git_ref, CHECKOUT_REF).as_str().into(),
+                gix::refspec::parse::Operation::Fetch,
+            )
             .map_err(|e| GitError {
-                repo_url: repo_url.clone(),
-                message: e.to_string(),
-            })?;
+                repo_url: repo_url.to_owned(),
+                message: format!("Invalid ref `{}`: {}", git_ref, e),
+            })?
+            .to_owned();
+            fetch = fetch.with_fetch_options(gix::remote::ref_map::Options {
+                extra_refspecs: vec![refspec],
+                ..Default::default()
+            });
+        }
 
-        let (_repo, _outcome) = prepare
-            .main_worktree(progress::Discard, &AtomicBool::new(false))
-            .map_err(|e| GitError {
-                repo_url: repo_url.clone(),
-                message: e.to_string(),
+        let cancelled = || Error::Cancelled {
+            repo_url: repo_url.to_owned(),
+        };
+        cancellation.check().map_err(|_| cancelled())?;
+        cancellation
+            .run_interruptible(|should_interrupt| {
+                let (mut prepare, _outcome) = fetch
+                    .fetch_then_checkout(progress::Discard, should_interrupt)
+                    .map_err(|e| GitError {
+                        repo_url: repo_url.to_owned(),
+                        message: e.to_string(),
+                    })?;
+                if let Some(git_ref) = git_ref {
+                    detach_head(prepare.repo(), git_ref).map_err(|message| GitError {
+                        repo_url: repo_url.to_owned(),
+                        message,
+                    })?;
+                }
+
+                let (_repo, _outcome) = prepare
+                    .main_worktree(progress::Discard, should_interrupt)
+                    .map_err(|e| GitError {
+                        repo_url: repo_url.to_owned(),
+                        message: e.to_string(),
+                    })?;
+                Ok(())
+            })
+            .map_err(|e| {
+                // An interrupted clone fails with a git error.
+                if cancellation.is_cancelled() {
+                    cancelled()
+                } else {
+                    e
+                }
             })?;
 
-        // Determines the path to the repo.
-        let git_repo_path = if let Some(path) = &path {
-            // Checks the existence of the path in the repo.
-            // If the path doesn't exist, returns an error.
-            if !git_repo_path.join(path).exists() {
-                return Err(GitError {
-                    repo_url: repo_url.clone(),
-                    message: format!("Path `{}` not found in repo", path),
-                });
+        Ok(git_repo_dir)
+    }
+
+    /// The registry archive of the given OCI artifact is pulled into the cache,
+    /// extracted, and the path to the extracted registry is returned.
+    ///
+    /// The archives and the extracted registries are stored by digest, so a
+    /// digest-pinned reference (e.g. `oci://ghcr.io/acme/semconv@sha256:...`)
+    /// already in the cache is used without any network access. For a tagged
+    /// reference, the manifest is pulled to resolve the tag once the previous
+    /// resolution has expired (see [`CachePolicy`]), and the archive is only
+    /// pulled if its digest is not in the cache.
+    ///
+    /// The checksum and the minisign signature of the archive are verified
+    /// if the fragment of the reference requests it (see
+    /// [`ArchiveVerification`]), in which case the manifest is always pulled.
+    pub fn oci_registry(&self, reference: &str) -> Result<PathBuf, Error> {
+        self.oci_registry_with_cancellation(reference, &CancellationToken::default())
+    }
+
+    /// Same as [`Cache::oci_registry`], but the pull is interrupted with
+    /// [`Error::Cancelled`] once the given token is cancelled or timed out.
+    pub fn oci_registry_with_cancellation(
+        &self,
+        reference: &str,
+        cancellation: &CancellationToken,
+    ) -> Result<PathBuf, Error> {
+        let (reference, verification) = ArchiveVerification::split_reference(reference)?;
+        let oci_reference: OciReference = reference.parse()?;
+        let oci_error = |message: String| Error::OciError {
+            reference: reference.to_owned(),
+            message,
+        };
+        let cancelled = || Error::Cancelled {
+            repo_url: reference.to_owned(),
+        };
+        let registries_dir = self.path.join("oci").join("registries");
+        let blobs_dir = self.path.join("oci").join("blobs");
+        let tags_dir = self.path.join("oci").join("tags");
+        let refresh = self.policy.refresh;
+        let use_cache = !refresh && !verification.is_required();
+
+        // A digest-pinned registry never expires, and a tagged registry is
+        // used without resolving its tag again until it expires.
+        let tag_entry_path = CacheEntry::path(&tags_dir, reference);
+        let cached_digest = match &oci_reference.digest {
+            Some(digest) => Some(digest.clone()),
+            None => CacheEntry::read(&tag_entry_path)
+                .filter(|entry| entry.is_fresh(&self.policy))
+                .and_then(|entry| entry.digest),
+        };
+        if let (Some(digest), true) = (cached_digest, use_cache) {
+            let registry_dir = digest_dir(&registries_dir, &digest);
+            if registry_dir.is_dir() {
+                return Ok(registry_dir);
+            }
+        }
+
+        cancellation.check().map_err(|_| cancelled())?;
+        let is_tagged = oci_reference.digest.is_none();
+        let client = OciClient::new(oci_reference)?;
+        let artifact = client.pull_registry_manifest()?;
+        if is_tagged {
+            // The freshness metadata is only an optimization.
+            _ = create_dir_all(&tags_dir).and_then(|()| {
+                CacheEntry::new(reference, Some(artifact.manifest_digest.clone()))
+                    .write(&tag_entry_path)
+            });
+        }
+        let registry_dir = digest_dir(&registries_dir, &artifact.manifest_digest);
+        if registry_dir.is_dir() && use_cache {
+            return Ok(registry_dir);
+        }
+
+        // The cached blobs are only used if their content matches their digest.
+        let pull_blob = |digest: &str| {
+            let blob_path = digest_dir(&blobs_dir, digest);
+            match std::fs::read(&blob_path) {
+                Ok(blob) if !refresh && sha256_digest(&blob) == digest => Ok(blob),
+                _ => {
+                    cancellation.check().map_err(|_| cancelled())?;
+                    let blob = client.pull_blob(digest)?;
+                    create_dir_all(&blobs_dir).map_err(|e| oci_error(e.to_string()))?;
+                    std::fs::write(&blob_path, &blob).map_err(|e| oci_error(e.to_string()))?;
+                    Ok(blob)
+                }
             }
+        };
+        let archive = pull_blob(&artifact.archive_digest)?;
+        let signature = match (
+            &verification.minisign_public_key,
+            &artifact.signature_digest,
+        ) {
+            (Some(_), Some(digest)) => Some(pull_blob(digest)?),
+            _ => None,
+        };
+        verification.verify(reference, &archive, signature.as_deref())?;
+        if registry_dir.is_dir() && !refresh {
+            return Ok(registry_dir);
+        }
+        extract_archive(&archive, &registries_dir, &registry_dir, refresh).map_err(oci_error)?;
+        Ok(registry_dir)
+    }
 
-            git_repo_path.join(path)
+    /// The given local registry archive (a `.tar.gz` file built by `weaver
+    /// registry package`) is extracted into the cache, and the path to the
+    /// extracted registry is returned. The extracted registries are stored by
+    /// digest of the archive.
+    ///
+    /// The checksum and the minisign signature of the archive are verified
+    /// if the fragment of the reference requests it (see
+    /// [`ArchiveVerification`]). The signature is read from the file
+    /// `<archive>.minisig` next to the archive.
+    pub fn archive_registry(&self, reference: &str) -> Result<PathBuf, Error> {
+        let (archive_path, verification) = ArchiveVerification::split_reference(reference)?;
+        let archive_error = |message: String| Error::ArchiveError {
+            archive: archive_path.to_owned(),
+            message,
+        };
+        let archive = std::fs::read(archive_path).map_err(|e| archive_error(e.to_string()))?;
+        let signature = std::fs::read(format!("{archive_path}.{SIGNATURE_EXTENSION}")).ok();
+        verification.verify(archive_path, &archive, signature.as_deref())?;
+
+        let registries_dir = self.path.join("archives");
+        let registry_dir = digest_dir(&registries_dir, &sha256_digest(&archive));
+        if !registry_dir.is_dir() {
+            extract_archive(&archive, &registries_dir, &registry_dir, false)
+                .map_err(archive_error)?;
+        }
+        Ok(registry_dir)
+    }
+
+    /// Returns the Git URLs and the tagged OCI references of the registries
+    /// in the cache, sorted.
+    #[must_use]
+    pub fn cached_registries(&self) -> Vec<String> {
+        let sources = |dir: PathBuf| {
+            std::fs::read_dir(dir)
+                .into_iter()
+                .flatten()
+                .filter_map(Result::ok)
+                .map(|dir_entry| dir_entry.path())
+                .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+                .filter_map(|path| CacheEntry::read(&path))
+                .map(|entry| entry.source)
+                .collect::<Vec<_>>()
+        };
+        let mut registries = sources(self.path.join("git"));
+        registries.extend(sources(self.path.join("oci").join("tags")));
+        registries.sort();
+        registries.dedup();
+        registries
+    }
+
+    /// Returns the path of the file used to cache the hashes of the artifacts
+    /// generated into the given output directory. The file is named after the
+    /// key of the absolute path of the output directory.
+    pub fn generation_cache_file(&self, output_dir: &Path) -> PathBuf {
+        let output_dir = if output_dir.is_relative() {
+            std::env::current_dir()
+                .map(|current_dir| current_dir.join(output_dir))
+                .unwrap_or_else(|_| output_dir.to_path_buf())
         } else {
-            git_repo_path.to_path_buf()
+            output_dir.to_path_buf()
         };
+        CacheEntry::path(&self.path.join("generate"), &output_dir.to_string_lossy())
+    }
+
+    /// Returns the path of the file used to cache the links found alive by
+    /// the link checker.
+    pub fn link_check_cache_file(&self) -> PathBuf {
+        self.path.join("links.json")
+    }
+}
 
-        // Adds the repo to the git_repo_dirs hashmap.
-        _ = self
-            .git_repo_dirs
-            .lock()
-            .expect("git_repo_dirs lock failed")
-            .insert(
-                repo_url.clone(),
-                GitRepo {
-                    temp_dir: git_repo_dir,
-                    path: git_repo_path.clone(),
-                },
-            );
+/// Name of the local ref the requested ref of a git repo is fetched into.
+const CHECKOUT_REF: &str = "refs/weaver/checkout";
 
-        Ok(git_repo_path)
+/// Points the HEAD of a freshly fetched repo to the commit of the requested
+/// ref, so this commit is the one checked out.
+fn detach_head(repo: &gix::Repository, git_ref: &str) -> Result<(), String> {
+    let mut reference = repo
+        .find_reference(CHECKOUT_REF)
+        .map_err(|_| format!("Ref `{}` not found in repo", git_ref))?;
+    let commit = reference.peel_to_id_in_place().map_err(|e| e.to_string())?;
+    _ = repo
+        .reference(
+            "HEAD",
+            commit,
+            PreviousValue::Any,
+            format!("checkout: moving to {}", git_ref),
+        )
+        .map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+/// Returns the key of a URL or a reference in the cache.
+fn entry_key(source: &str) -> String {
+    sha256_digest(source.as_bytes())
+        .trim_start_matches("sha256:")
+        .chars()
+        .take(16)
+        .collect()
+}
+
+/// Makes a temporary directory the current version of the entry of the given
+/// source, and returns the directory of this version.
+///
+/// Each version is stored in its own directory, and the entry (see
+/// [`CacheEntry`]) is atomically switched to the new version, so the other
+/// processes keep using the previous version of the entry while it is
+/// replaced. The superseded versions are removed once older than the given
+/// time-to-live, except the version being replaced, which may still be used.
+fn switch_version(
+    temp_dir: TempDir,
+    dir: &Path,
+    source: &str,
+    replaced_dir: Option<&Path>,
+    ttl: Duration,
+) -> std::io::Result<PathBuf> {
+    let key = entry_key(source);
+    let created_at = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_nanos());
+    let name = format!("{}.{}", key, created_at);
+    let version_dir = dir.join(&name);
+    let temp_path = temp_dir.into_path();
+    std::fs::rename(&temp_path, &version_dir).inspect_err(|_| {
+        _ = std::fs::remove_dir_all(&temp_path);
+    })?;
+    let mut entry = CacheEntry::new(source, None);
+    entry.dir = Some(name);
+    entry.write(&CacheEntry::path(dir, source))?;
+
+    // The removal of the superseded versions is only an optimization.
+    let superseded = std::fs::read_dir(dir)?
+        .filter_map(Result::ok)
+        .map(|dir_entry| dir_entry.path())
+        .filter(|path| {
+            path.is_dir()
+                && *path != version_dir
+                && Some(path.as_path()) != replaced_dir
+                && path
+                    .file_name()
+                    .and_then(|name| name.to_str())
+                    .is_some_and(|name| {
+                        name == key || name.strip_prefix(&key).is_some_and(|v| v.starts_with('.'))
+                    })
+        });
+    for path in superseded {
+        let expired = std::fs::metadata(&path)
+            .and_then(|metadata| metadata.modified())
+            .ok()
+            .and_then(|modified| modified.elapsed().ok())
+            .is_some_and(|age| age >= ttl);
+        if expired {
+            _ = std::fs::remove_dir_all(&path);
+        }
+    }
+    Ok(version_dir)
+}
+
+/// Replaces a directory with the content of a temporary directory.
+fn replace_dir(temp_dir: TempDir, dir: &Path) -> std::io::Result<()> {
+    let temp_path = temp_dir.into_path();
+    if dir.exists() {
+        std::fs::remove_dir_all(dir)?;
+    }
+    std::fs::rename(&temp_path, dir).inspect_err(|_| {
+        _ = std::fs::remove_dir_all(&temp_path);
+    })
+}
+
+/// Returns the directory storing the content with the given digest.
+fn digest_dir(dir: &Path, digest: &str) -> PathBuf {
+    dir.join(digest.replace(':', "-"))
+}
+
+/// Extracts a registry archive into the given directory. The archive is
+/// extracted into a temporary directory renamed once complete, so an
+/// interrupted extraction is never used. An existing directory is kept
+/// unless `replace` is set.
+fn extract_archive(
+    archive: &[u8],
+    parent_dir: &Path,
+    registry_dir: &Path,
+    replace: bool,
+) -> Result<(), String> {
+    create_dir_all(parent_dir).map_err(|e| e.to_string())?;
+    let temp_dir = TempDir::new_in(parent_dir, "extract").map_err(|e| e.to_string())?;
+    tar::Archive::new(GzDecoder::new(archive))
+        .unpack(temp_dir.path())
+        .map_err(|e| format!("Invalid registry archive: {e}"))?;
+    if replace {
+        return replace_dir(temp_dir, registry_dir).map_err(|e| e.to_string());
+    }
+    if std::fs::rename(temp_dir.path(), registry_dir).is_err() && !registry_dir.is_dir() {
+        return Err(format!(
+            "Failed to extract the registry archive into `{}`",
+            registry_dir.display()
+        ));
     }
+    Ok(())
 }
 
 #[cfg(test)]
@@ -213,4 +772,195 @@ mod tests {
         assert!(result.is_ok());
         assert!(result.unwrap().exists());
     }
+
+    #[test]
+    fn test_cancelled_git_repo() {
+        let cache_dir = TempDir::new("weaver-cache").unwrap();
+        let cache = Cache {
+            path: cache_dir.path().to_path_buf(),
+            ..Default::default()
+        };
+        let cancellation = CancellationToken::new();
+        cancellation.cancel();
+        let result = cache.git_repo_with_cancellation(
+            "https://github.com/open-telemetry/semantic-conventions.git".into(),
+            Some("model".into()),
+            &cancellation,
+        );
+        assert!(matches!(result, Err(Error::Cancelled { .. })));
+    }
+
+    #[test]
+    fn test_git_repo_at_ref() {
+        let repo_dir = TempDir::new("weaver-repo").unwrap();
+        let git = |args: &[&str]| {
+            let status = std::process::Command::new("git")
+                .args([
+                    "-c",
+                    "user.name=weaver",
+                    "-c",
+                    "user.email=weaver@example.com",
+                ])
+                .args(args)
+                .current_dir(repo_dir.path())
+                .status()
+                .unwrap();
+            assert!(status.success());
+        };
+        let commit = |content: &str| {
+            std::fs::write(repo_dir.path().join("registry.yaml"), content).unwrap();
+            git(&["add", "registry.yaml"]);
+            git(&["commit", "-q", "-m", content]);
+        };
+        git(&["init", "-q"]);
+        commit("v1");
+        git(&["tag", "-a", "v1.0.0", "-m", "v1.0.0"]);
+        commit("v2");
+
+        let cache_dir = TempDir::new("weaver-cache").unwrap();
+        let cache = Cache {
+            path: cache_dir.path().to_path_buf(),
+            ..Default::default()
+        };
+        let repo_url = format!("file://{}", repo_dir.path().display());
+        let clone = |git_ref: Option<&str>| {
+            let dir = cache
+                .git_repo_at_ref_with_cancellation(
+                    repo_url.clone(),
+                    git_ref.map(ToOwned::to_owned),
+                    None,
+                    &CancellationToken::default(),
+                )
+                .unwrap();
+            std::fs::read_to_string(dir.join("registry.yaml")).unwrap()
+        };
+        assert_eq!(clone(None), "v2");
+        assert_eq!(clone(Some("v1.0.0")), "v1");
+        assert_eq!(clone(Some("refs/tags/v1.0.0")), "v1");
+
+        // The clones of a ref are not listed as registries.
+        assert_eq!(cache.cached_registries(), vec![repo_url.clone()]);
+
+        let result = cache.git_repo_at_ref_with_cancellation(
+            repo_url.clone(),
+            Some("v9.9.9".to_owned()),
+            None,
+            &CancellationToken::default(),
+        );
+        assert!(matches!(result, Err(GitError { .. })));
+    }
+
+    #[test]
+    fn test_switch_version() {
+        let cache_dir = TempDir::new("weaver-cache").unwrap();
+        let dir = cache_dir.path();
+        let source = "https://github.com/acme/semconv.git";
+        let clone = |content: &str| {
+            let temp_dir = TempDir::new_in(dir, "clone").unwrap();
+            std::fs::write(temp_dir.path().join("registry.yaml"), content).unwrap();
+            temp_dir
+        };
+        let current_dir = || {
+            CacheEntry::read(&CacheEntry::path(dir, source))
+                .unwrap()
+                .dir(dir)
+        };
+
+        // The entry is switched to each new version, and the replaced version
+        // is kept for the processes still using it.
+        let first = switch_version(clone("v1"), dir, source, None, Duration::ZERO).unwrap();
+        assert_eq!(current_dir(), first);
+        let second =
+            switch_version(clone("v2"), dir, source, Some(&first), Duration::ZERO).unwrap();
+        assert_eq!(current_dir(), second);
+        assert_eq!(std::fs::read(first.join("registry.yaml")).unwrap(), b"v1");
+        assert_eq!(std::fs::read(second.join("registry.yaml")).unwrap(), b"v2");
+
+        // The superseded versions are removed once expired.
+        let third = switch_version(
+            clone("v3"),
+            dir,
+            source,
+            Some(&second),
+            Duration::from_secs(60),
+        )
+        .unwrap();
+        assert!(first.is_dir());
+        let fourth =
+            switch_version(clone("v4"), dir, source, Some(&third), Duration::ZERO).unwrap();
+        assert_eq!(current_dir(), fourth);
+        assert!(!first.exists());
+        assert!(!second.exists());
+        assert!(third.is_dir());
+    }
+
+    #[test]
+    fn test_generation_cache_file() {
+        let cache = Cache::try_new().unwrap();
+        let file = cache.generation_cache_file(Path::new("/tmp/my-project/output"));
+        assert_eq!(
+            file,
+            cache
+                .path
+                .join("generate")
+                .join(format!("{}.json", entry_key("/tmp/my-project/output")))
+        );
+        assert_ne!(
+            cache.generation_cache_file(Path::new("output")),
+            cache.generation_cache_file(Path::new("other/output"))
+        );
+        // The paths only differing by their non-alphanumeric characters don't
+        // share their cache file.
+        assert_ne!(
+            cache.generation_cache_file(Path::new("/tmp/my-project/output")),
+            cache.generation_cache_file(Path::new("/tmp/my_project/output"))
+        );
+    }
+
+    #[test]
+    fn test_archive_registry() {
+        let mut builder = tar::Builder::new(flate2::write::GzEncoder::new(
+            Vec::new(),
+            flate2::Compression::default(),
+        ));
+        let content = b"groups: []\n";
+        let mut header = tar::Header::new_gnu();
+        header.set_size(content.len() as u64);
+        header.set_mode(0o644);
+        header.set_cksum();
+        builder
+            .append_data(&mut header, "registry.yaml", content.as_slice())
+            .unwrap();
+        let archive = builder.into_inner().unwrap().finish().unwrap();
+        let temp_dir = TempDir::new("weaver-archive").unwrap();
+        let archive_path = temp_dir.path().join("semconv-1.0.0.tar.gz");
+        std::fs::write(&archive_path, &archive).unwrap();
+        let archive_path = archive_path.display().to_string();
+        let cache = Cache {
+            path: temp_dir.path().join("cache"),
+            ..Default::default()
+        };
+
+        let checksum = sha256_digest(&archive).replace("sha256:", "");
+        let registry_dir = cache
+            .archive_registry(&format!("{archive_path}#sha256={checksum}"))
+            .unwrap();
+        assert_eq!(
+            std::fs::read(registry_dir.join("registry.yaml")).unwrap(),
+            content
+        );
+        assert_eq!(cache.archive_registry(&archive_path).unwrap(), registry_dir);
+
+        // Mismatching checksum and missing signature.
+        let result = cache.archive_registry(&format!("{archive_path}#sha256={}", "0".repeat(64)));
+        assert!(matches!(result, Err(Error::VerificationFailed { .. })));
+        let result = cache.archive_registry(&format!(
+            "{archive_path}#minisign=RWQf6LRCGA9i53mlYecO4IzT51TGPpvWucNSCh1CBM0QTaLn73Y7GFO3"
+        ));
+        assert!(matches!(result, Err(Error::VerificationFailed { .. })));
+        assert!(matches!(
+            cache.archive_registry("missing.tar.gz"),
+            Err(Error::ArchiveError { .. })
+        ));
+    }
 }
@@ -0,0 +1,801 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! A minimal client of the OCI distribution API used to publish and pull
+//! registry archives as OCI artifacts (e.g. `oci://ghcr.io/acme/semconv:1.2.0`).
+//!
+//! Registries requiring authentication are supported through the token
+//! challenge of the distribution API. The credentials are read from the
+//! `WEAVER_REGISTRY_USERNAME` and `WEAVER_REGISTRY_PASSWORD` environment
+//! variables.
+
+use std::collections::BTreeMap;
+use std::fmt::Display;
+use std::io::Read;
+use std::str::FromStr;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use base64::Engine;
+use serde::Deserialize;
+use serde_json::json;
+use sha2::{Digest, Sha256};
+
+use crate::Error;
+
+/// The scheme of the OCI references.
+pub const OCI_SCHEME: &str = "oci://";
+
+/// The artifact type of the registry archives.
+pub const REGISTRY_ARTIFACT_TYPE: &str = "application/vnd.opentelemetry.weaver.registry.v1";
+
+/// The media type of the layer containing a registry archive.
+pub const REGISTRY_LAYER_MEDIA_TYPE: &str =
+    "application/vnd.opentelemetry.weaver.registry.layer.v1.tar+gzip";
+
+/// The media type of the layer containing the minisign signature of the
+/// registry archive.
+pub const SIGNATURE_LAYER_MEDIA_TYPE: &str =
+    "application/vnd.opentelemetry.weaver.registry.signature.v1+minisign";
+
+/// The environment variable containing the user name used to authenticate
+/// to the registries.
+pub const USERNAME_ENV: &str = "WEAVER_REGISTRY_USERNAME";
+
+/// The environment variable containing the password (or token) used to
+/// authenticate to the registries.
+pub const PASSWORD_ENV: &str = "WEAVER_REGISTRY_PASSWORD";
+
+/// The media type of the OCI image manifests.
+const MANIFEST_MEDIA_TYPE: &str = "application/vnd.oci.image.manifest.v1+json";
+
+/// The media type of the empty config of the OCI artifacts.
+const EMPTY_CONFIG_MEDIA_TYPE: &str = "application/vnd.oci.empty.v1+json";
+
+/// The annotation giving the file name of a layer.
+const TITLE_ANNOTATION: &str = "org.opencontainers.image.title";
+
+/// A reference to an OCI artifact, e.g. `oci://ghcr.io/acme/semconv:1.2.0` or
+/// `oci://ghcr.io/acme/semconv@sha256:...`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OciReference {
+    /// The host (and port) of the OCI registry.
+    pub registry: String,
+    /// The repository of the artifact.
+    pub repository: String,
+    /// The tag of the artifact, if any.
+    pub tag: Option<String>,
+    /// The digest pinning the artifact, if any.
+    pub digest: Option<String>,
+}
+
+impl OciReference {
+    /// Returns the tag or the digest identifying the manifest of the artifact
+    /// (`latest` if neither is specified).
+    #[must_use]
+    pub fn manifest_reference(&self) -> &str {
+        self.digest
+            .as_deref()
+            .or(self.tag.as_deref())
+            .unwrap_or("latest")
+    }
+
+    /// Returns the base URL of the repository in the distribution API. The
+    /// local registries are accessed over HTTP, the other ones over HTTPS.
+    fn base_url(&self) -> String {
+        let scheme = if self.registry.starts_with("localhost") || self.registry.starts_with("127.")
+        {
+            "http"
+        } else {
+            "https"
+        };
+        format!("{}://{}/v2/{}", scheme, self.registry, self.repository)
+    }
+}
+
+impl FromStr for OciReference {
+    type Err = Error;
+
+    fn from_str(reference: &str) -> Result<Self, Self::Err> {
+        let invalid = |message: &str| Error::OciError {
+            reference: reference.to_owned(),
+            message: message.to_owned(),
+        };
+        let rest = reference
+            .strip_prefix(OCI_SCHEME)
+            .ok_or_else(|| invalid("The reference must start with `oci://`"))?;
+        let (registry, rest) = rest
+            .split_once('/')
+            .ok_or_else(|| invalid("The reference must contain a registry and a repository"))?;
+        let (rest, digest) = match rest.split_once('@') {
+            Some((rest, digest)) => {
+                if !digest.starts_with("sha256:") {
+                    return Err(invalid("Only `sha256` digests are supported"));
+                }
+                (rest, Some(digest.to_owned()))
+            }
+            None => (rest, None),
+        };
+        let (repository, tag) = match rest.rsplit_once(':') {
+            Some((repository, tag)) if !tag.contains('/') => (repository, Some(tag.to_owned())),
+            _ => (rest, None),
+        };
+        if registry.is_empty() || repository.is_empty() {
+            return Err(invalid(
+                "The reference must contain a registry and a repository",
+            ));
+        }
+        Ok(OciReference {
+            registry: registry.to_owned(),
+            repository: repository.to_owned(),
+            tag,
+            digest,
+        })
+    }
+}
+
+impl Display for OciReference {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}{}/{}", OCI_SCHEME, self.registry, self.repository)?;
+        if let Some(tag) = &self.tag {
+            write!(f, ":{}", tag)?;
+        }
+        if let Some(digest) = &self.digest {
+            write!(f, "@{}", digest)?;
+        }
+        Ok(())
+    }
+}
+
+/// Returns the `sha256:<hex>` digest of the given content.
+#[must_use]
+pub fn sha256_digest(content: &[u8]) -> String {
+    let hash: String = Sha256::digest(content)
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect();
+    format!("sha256:{}", hash)
+}
+
+/// The token returned by the token endpoint of a registry.
+#[derive(Deserialize)]
+struct TokenResponse {
+    token: Option<String>,
+    access_token: Option<String>,
+}
+
+/// The manifest of an OCI artifact (only the fields used by weaver).
+#[derive(Deserialize)]
+struct OciManifest {
+    layers: Vec<OciDescriptor>,
+}
+
+/// The descriptor of a layer of an OCI artifact.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct OciDescriptor {
+    media_type: String,
+    digest: String,
+}
+
+/// The digests of a registry artifact.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RegistryArtifact {
+    /// The digest of the manifest of the artifact.
+    pub manifest_digest: String,
+    /// The digest of the registry archive.
+    pub archive_digest: String,
+    /// The digest of the minisign signature of the archive, if any.
+    pub signature_digest: Option<String>,
+}
+
+/// A client of the OCI distribution API for a repository.
+pub struct OciClient {
+    agent: ureq::Agent,
+    reference: OciReference,
+    /// The `Authorization` header obtained from the last authentication
+    /// challenge, if any.
+    authorization: Mutex<Option<String>>,
+}
+
+impl OciClient {
+    /// Creates a client of the repository of the given reference, using the
+    /// proxy and the CA certificates of the environment.
+    pub fn new(reference: OciReference) -> Result<Self, Error> {
+        let agent = weaver_common::http::agent_builder(&reference.base_url())
+            .map_err(|message| Error::OciError {
+                reference: reference.to_string(),
+                message,
+            })?
+            .timeout(Duration::from_secs(300))
+            .build();
+        Ok(Self {
+            agent,
+            reference,
+            authorization: Mutex::new(None),
+        })
+    }
+
+    /// Pushes a registry archive as an OCI artifact and returns the digest of
+    /// its manifest.
+    ///
+    /// # Arguments
+    ///
+    /// * `file_name` - The file name of the archive, stored as the title of the layer.
+    /// * `archive` - The content of the archive.
+    /// * `signature` - The minisign signature of the archive, if any, pushed as a second layer.
+    /// * `annotations` - The annotations of the manifest.
+    pub fn push_registry_archive(
+        &self,
+        file_name: &str,
+        archive: &[u8],
+        signature: Option<&[u8]>,
+        annotations: &BTreeMap<String, String>,
+    ) -> Result<String, Error> {
+        let config = b"{}";
+        let config_digest = self.push_blob(config)?;
+        let layer_digest = self.push_blob(archive)?;
+        let mut layers = vec![json!({
+            "mediaType": REGISTRY_LAYER_MEDIA_TYPE,
+            "digest": layer_digest,
+            "size": archive.len(),
+            "annotations": { TITLE_ANNOTATION: file_name },
+        })];
+        if let Some(signature) = signature {
+            layers.push(json!({
+                "mediaType": SIGNATURE_LAYER_MEDIA_TYPE,
+                "digest": self.push_blob(signature)?,
+                "size": signature.len(),
+                "annotations": { TITLE_ANNOTATION: format!("{file_name}.minisig") },
+            }));
+        }
+        let manifest = json!({
+            "schemaVersion": 2,
+            "mediaType": MANIFEST_MEDIA_TYPE,
+            "artifactType": REGISTRY_ARTIFACT_TYPE,
+            "config": {
+                "mediaType": EMPTY_CONFIG_MEDIA_TYPE,
+                "digest": config_digest,
+                "size": config.len(),
+            },
+            "layers": layers,
+            "annotations": annotations,
+        });
+        let manifest = serde_json::to_vec(&manifest).map_err(|e| self.error(e.to_string()))?;
+        let url = format!(
+            "{}/manifests/{}",
+            self.reference.base_url(),
+            self.reference.manifest_reference()
+        );
+        _ = self.call("PUT", &url, None, Some((MANIFEST_MEDIA_TYPE, &manifest)))?;
+        Ok(sha256_digest(&manifest))
+    }
+
+    /// Pushes a blob (unless the repository already contains it) and returns
+    /// its digest.
+    fn push_blob(&self, content: &[u8]) -> Result<String, Error> {
+        let digest = sha256_digest(content);
+        let base_url = self.reference.base_url();
+        if self
+            .call(
+                "HEAD",
+                &format!("{}/blobs/{}", base_url, digest),
+                None,
+                None,
+            )
+            .is_ok()
+        {
+            return Ok(digest);
+        }
+
+        let response = self.call("POST", &format!("{}/blobs/uploads/", base_url), None, None)?;
+        let location = response
+            .header("Location")
+            .ok_or_else(|| self.error("The upload location is missing".to_owned()))?;
+        let location = if location.starts_with('/') {
+            let origin = base_url
+                .find("/v2/")
+                .map(|index| &base_url[..index])
+                .unwrap_or(&base_url);
+            format!("{}{}", origin, location)
+        } else {
+            location.to_owned()
+        };
+        let separator = if location.contains('?') { '&' } else { '?' };
+        _ = self.call(
+            "PUT",
+            &format!("{}{}digest={}", location, separator, digest),
+            None,
+            Some(("application/octet-stream", content)),
+        )?;
+        Ok(digest)
+    }
+
+    /// Pulls the manifest of the artifact and returns its digest and the
+    /// digests of the layers containing the registry archive and its
+    /// signature. The digest of the manifest is checked against the digest
+    /// pinning the reference, if any.
+    pub fn pull_registry_manifest(&self) -> Result<RegistryArtifact, Error> {
+        let url = format!(
+            "{}/manifests/{}",
+            self.reference.base_url(),
+            self.reference.manifest_reference()
+        );
+        let response = self.call("GET", &url, Some(MANIFEST_MEDIA_TYPE), None)?;
+        let manifest = self.read(response)?;
+        let digest = sha256_digest(&manifest);
+        if let Some(expected) = &self.reference.digest {
+            if *expected != digest {
+                return Err(self.error(format!(
+                    "The digest of the manifest is `{digest}`, `{expected}` expected"
+                )));
+            }
+        }
+        let manifest: OciManifest = serde_json::from_slice(&manifest)
+            .map_err(|e| self.error(format!("Invalid manifest: {e}")))?;
+        let layer_digest = |media_type: &str| {
+            manifest
+                .layers
+                .iter()
+                .find(|layer| layer.media_type == media_type)
+                .map(|layer| layer.digest.clone())
+        };
+        let archive_digest = layer_digest(REGISTRY_LAYER_MEDIA_TYPE).ok_or_else(|| {
+            self.error("The artifact doesn't contain a registry archive".to_owned())
+        })?;
+        Ok(RegistryArtifact {
+            manifest_digest: digest,
+            archive_digest,
+            signature_digest: layer_digest(SIGNATURE_LAYER_MEDIA_TYPE),
+        })
+    }
+
+    /// Pulls a blob and checks its digest.
+    pub fn pull_blob(&self, digest: &str) -> Result<Vec<u8>, Error> {
+        let url = format!("{}/blobs/{}", self.reference.base_url(), digest);
+        let response = self.call("GET", &url, None, None)?;
+        let content = self.read(response)?;
+        let actual = sha256_digest(&content);
+        if actual != digest {
+            return Err(self.error(format!(
+                "The digest of the blob is `{actual}`, `{digest}` expected"
+            )));
+        }
+        Ok(content)
+    }
+
+    /// Reads the body of a response.
+    fn read(&self, response: ureq::Response) -> Result<Vec<u8>, Error> {
+        let mut content = Vec::new();
+        _ = response
+            .into_reader()
+            .read_to_end(&mut content)
+            .map_err(|e| self.error(e.to_string()))?;
+        Ok(content)
+    }
+
+    /// Sends a request to the registry, answering the authentication
+    /// challenge of the registry if needed.
+    fn call(
+        &self,
+        method: &str,
+        url: &str,
+        accept: Option<&str>,
+        body: Option<(&str, &[u8])>,
+    ) -> Result<ureq::Response, Error> {
+        let send = |authorization: Option<&str>| {
+            let mut request = self.agent.request(method, url);
+            if let Some(authorization) = authorization {
+                request = request.set("Authorization", authorization);
+            }
+            if let Some(accept) = accept {
+                request = request.set("Accept", accept);
+            }
+            match body {
+                Some((content_type, content)) => request
+                    .set("Content-Type", content_type)
+                    .send_bytes(content),
+                None => request.call(),
+            }
+            .map_err(Box::new)
+        };
+        let authorization = self
+            .authorization
+            .lock()
+            .expect("authorization lock failed")
+            .clone();
+        match send(authorization.as_deref()).map_err(|e| *e) {
+            Ok(response) => Ok(response),
+            Err(ureq::Error::Status(401, response)) => {
+                let challenge = response.header("WWW-Authenticate").unwrap_or_default();
+                let authorization = self.authenticate(challenge)?;
+                let response = send(Some(&authorization));
+                *self
+                    .authorization
+                    .lock()
+                    .expect("authorization lock failed") = Some(authorization);
+                response.map_err(|e| self.error(e.to_string()))
+            }
+            Err(e) => Err(self.error(e.to_string())),
+        }
+    }
+
+    /// Returns the `Authorization` header answering an authentication
+    /// challenge (`Basic` or `Bearer`).
+    fn authenticate(&self, challenge: &str) -> Result<String, Error> {
+        let credentials = match (std::env::var(USERNAME_ENV), std::env::var(PASSWORD_ENV)) {
+            (Ok(username), Ok(password)) => Some(format!(
+                "Basic {}",
+                base64::engine::general_purpose::STANDARD.encode(format!("{username}:{password}"))
+            )),
+            _ => None,
+        };
+        let (scheme, params) = challenge.split_once(' ').unwrap_or((challenge, ""));
+        if scheme.eq_ignore_ascii_case("basic") {
+            return credentials.ok_or_else(|| {
+                self.error(format!(
+                    "The registry requires credentials, set the `{USERNAME_ENV}` and `{PASSWORD_ENV}` environment variables"
+                ))
+            });
+        }
+        if !scheme.eq_ignore_ascii_case("bearer") {
+            return Err(self.error(format!(
+                "Unsupported authentication challenge `{challenge}`"
+            )));
+        }
+
+        let params = challenge_params(params);
+        let realm = params
+            .get("realm")
+            .ok_or_else(|| self.error("The authentication realm is missing".to_owned()))?;
+        let mut request = self.agent.get(realm);
+        if let Some(service) = params.get("service") {
+            request = request.query("service", service);
+        }
+        if let Some(scope) = params.get("scope") {
+            request = request.query("scope", scope);
+        }
+        if let Some(credentials) = &credentials {
+            request = request.set("Authorization", credentials);
+        }
+        let response = request
+            .call()
+            .map_err(|e| self.error(format!("Authentication failed: {e}")))?;
+        let token: TokenResponse = serde_json::from_reader(response.into_reader())
+            .map_err(|e| self.error(format!("Invalid authentication token: {e}")))?;
+        token
+            .token
+            .or(token.access_token)
+            .map(|token| format!("Bearer {token}"))
+            .ok_or_else(|| self.error("The authentication token is missing".to_owned()))
+    }
+
+    fn error(&self, message: String) -> Error {
+        Error::OciError {
+            reference: self.reference.to_string(),
+            message,
+        }
+    }
+}
+
+/// Parses the `key="value"` parameters of an authentication challenge.
+fn challenge_params(params: &str) -> BTreeMap<String, String> {
+    let mut result = BTreeMap::new();
+    let mut rest = params.trim();
+    while let Some((key, value)) = rest.split_once('=') {
+        let key = key.trim().trim_start_matches(',').trim().to_owned();
+        let (value, next) = if let Some(value) = value.strip_prefix('"') {
+            value.split_once('"').unwrap_or((value, ""))
+        } else {
+            value.split_once(',').unwrap_or((value, ""))
+        };
+        _ = result.insert(key, value.to_owned());
+        rest = next.trim();
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+    use std::sync::Arc;
+
+    use super::*;
+
+    #[test]
+    fn test_oci_reference() {
+        let reference: OciReference = "oci://ghcr.io/acme/semconv:1.2.0".parse().unwrap();
+        assert_eq!(reference.registry, "ghcr.io");
+        assert_eq!(reference.repository, "acme/semconv");
+        assert_eq!(reference.manifest_reference(), "1.2.0");
+        assert_eq!(reference.to_string(), "oci://ghcr.io/acme/semconv:1.2.0");
+
+        let reference: OciReference = "oci://localhost:5000/semconv@sha256:abc".parse().unwrap();
+        assert_eq!(reference.registry, "localhost:5000");
+        assert_eq!(reference.tag, None);
+        assert_eq!(reference.manifest_reference(), "sha256:abc");
+        assert_eq!(reference.base_url(), "http://localhost:5000/v2/semconv");
+
+        let reference: OciReference = "oci://ghcr.io/acme/semconv".parse().unwrap();
+        assert_eq!(reference.manifest_reference(), "latest");
+
+        assert!("ghcr.io/acme/semconv".parse::<OciReference>().is_err());
+        assert!("oci://ghcr.io".parse::<OciReference>().is_err());
+        assert!("oci://ghcr.io/acme@md5:abc"
+            .parse::<OciReference>()
+            .is_err());
+    }
+
+    #[test]
+    fn test_challenge_params() {
+        let params = challenge_params(
+            r#"realm="https://ghcr.io/token",service="ghcr.io",scope="repository:acme/semconv:pull""#,
+        );
+        assert_eq!(params["realm"], "https://ghcr.io/token");
+        assert_eq!(params["service"], "ghcr.io");
+        assert_eq!(params["scope"], "repository:acme/semconv:pull");
+    }
+
+    /// Starts a fake OCI registry requiring a bearer token and serving the
+    /// given content (by path), and returns its address and the list of the
+    /// requests it received.
+    fn start_registry(served: BTreeMap<String, Vec<u8>>) -> (String, Arc<Mutex<Vec<String>>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap().to_string();
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let received = requests.clone();
+        let realm = format!("http://{}/token", address);
+        _ = std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                let mut stream = stream.unwrap();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                loop {
+                    let mut request_line = String::new();
+                    if reader.read_line(&mut request_line).unwrap_or(0) == 0 {
+                        break;
+                    }
+                    let mut content_length = 0;
+                    let mut authorized = false;
+                    loop {
+                        let mut header = String::new();
+                        _ = reader.read_line(&mut header).unwrap();
+                        let header = header.trim_end();
+                        if header.is_empty() {
+                            break;
+                        }
+                        let (name, value) = header.split_once(": ").unwrap_or((header, ""));
+                        if name.eq_ignore_ascii_case("content-length") {
+                            content_length = value.parse().unwrap();
+                        }
+                        if name.eq_ignore_ascii_case("authorization") {
+                            authorized = value == "Bearer secret";
+                        }
+                    }
+                    let mut body = vec![0; content_length];
+                    reader.read_exact(&mut body).unwrap();
+
+                    let mut parts = request_line.split_whitespace();
+                    let method = parts.next().unwrap_or_default().to_owned();
+                    let path = parts.next().unwrap_or_default().to_owned();
+                    let (status, headers, content) = if path.starts_with("/token") {
+                        ("200 OK", String::new(), br#"{"token":"secret"}"#.to_vec())
+                    } else if !authorized {
+                        (
+                            "401 Unauthorized",
+                            format!(
+                                "WWW-Authenticate: Bearer realm=\"{realm}\",service=\"test\"\r\n"
+                            ),
+                            vec![],
+                        )
+                    } else if method == "GET" && served.contains_key(&path) {
+                        ("200 OK", String::new(), served[&path].clone())
+                    } else if method == "HEAD" || method == "GET" {
+                        ("404 Not Found", String::new(), vec![])
+                    } else if method == "POST" {
+                        (
+                            "202 Accepted",
+                            "Location: /v2/acme/semconv/blobs/uploads/1?state=x\r\n".to_owned(),
+                            vec![],
+                        )
+                    } else {
+                        ("201 Created", String::new(), vec![])
+                    };
+                    received
+                        .lock()
+                        .unwrap()
+                        .push(format!("{method} {path} {} {authorized}", body.len()));
+                    let response = format!(
+                        "HTTP/1.1 {status}\r\n{headers}Content-Length: {}\r\n\r\n",
+                        content.len()
+                    );
+                    stream.write_all(response.as_bytes()).unwrap();
+                    if method != "HEAD" {
+                        stream.write_all(&content).unwrap();
+                    }
+                }
+            }
+        });
+        (address, requests)
+    }
+
+    #[test]
+    fn test_push_registry_archive() {
+        let (address, requests) = start_registry(BTreeMap::new());
+        let reference: OciReference = format!("oci://{address}/acme/semconv:1.0.0")
+            .parse()
+            .unwrap();
+        let client = OciClient::new(reference).unwrap();
+        let annotations = BTreeMap::from([(
+            "org.opencontainers.image.version".to_owned(),
+            "1.0.0".to_owned(),
+        )]);
+        let digest = client
+            .push_registry_archive("semconv-1.0.0.tar.gz", b"archive", None, &annotations)
+            .unwrap();
+        assert!(digest.starts_with("sha256:"));
+
+        let requests = requests.lock().unwrap();
+        let config_digest = sha256_digest(b"{}");
+        let layer_digest = sha256_digest(b"archive");
+        assert_eq!(
+            *requests,
+            [
+                format!("HEAD /v2/acme/semconv/blobs/{config_digest} 0 false"),
+                "GET /token?service=test 0 false".to_owned(),
+                format!("HEAD /v2/acme/semconv/blobs/{config_digest} 0 true"),
+                "POST /v2/acme/semconv/blobs/uploads/ 0 true".to_owned(),
+                format!(
+                    "PUT /v2/acme/semconv/blobs/uploads/1?state=x&digest={config_digest} 2 true"
+                ),
+                format!("HEAD /v2/acme/semconv/blobs/{layer_digest} 0 true"),
+                "POST /v2/acme/semconv/blobs/uploads/ 0 true".to_owned(),
+                format!(
+                    "PUT /v2/acme/semconv/blobs/uploads/1?state=x&digest={layer_digest} 7 true"
+                ),
+                requests[8].clone(),
+            ]
+        );
+        assert!(requests[8].starts_with("PUT /v2/acme/semconv/manifests/1.0.0 "));
+    }
+
+    #[test]
+    fn test_pull_registry_archive() {
+        // A registry archive containing a single semantic convention file.
+        let mut builder = tar::Builder::new(flate2::write::GzEncoder::new(
+            Vec::new(),
+            flate2::Compression::default(),
+        ));
+        let content = b"groups: []\n";
+        let mut header = tar::Header::new_gnu();
+        header.set_size(content.len() as u64);
+        header.set_mode(0o644);
+        header.set_cksum();
+        builder
+            .append_data(&mut header, "registry.yaml", content.as_slice())
+            .unwrap();
+        let archive = builder.into_inner().unwrap().finish().unwrap();
+        let layer_digest = sha256_digest(&archive);
+        let manifest = serde_json::to_vec(&json!({
+            "schemaVersion": 2,
+            "mediaType": MANIFEST_MEDIA_TYPE,
+            "layers": [{
+                "mediaType": REGISTRY_LAYER_MEDIA_TYPE,
+                "digest": layer_digest,
+                "size": archive.len(),
+            }],
+        }))
+        .unwrap();
+        let manifest_digest = sha256_digest(&manifest);
+        let (address, requests) = start_registry(BTreeMap::from([
+            (
+                "/v2/acme/semconv/manifests/1.0.0".to_owned(),
+                manifest.clone(),
+            ),
+            (
+                format!("/v2/acme/semconv/manifests/{manifest_digest}"),
+                manifest,
+            ),
+            (format!("/v2/acme/semconv/blobs/{layer_digest}"), archive),
+        ]));
+
+        let cache_dir = tempdir::TempDir::new("weaver-cache").unwrap();
+        let cache = crate::Cache {
+            path: cache_dir.path().to_path_buf(),
+            ..Default::default()
+        };
+        let registry_dir = cache
+            .oci_registry(&format!("oci://{address}/acme/semconv:1.0.0"))
+            .unwrap();
+        assert_eq!(
+            std::fs::read(registry_dir.join("registry.yaml")).unwrap(),
+            content
+        );
+        assert!(cache_dir
+            .path()
+            .join("oci/blobs")
+            .join(layer_digest.replace(':', "-"))
+            .is_file());
+        let pulls = requests.lock().unwrap().len();
+
+        // The resolution of the tag is fresh.
+        assert_eq!(
+            cache
+                .oci_registry(&format!("oci://{address}/acme/semconv:1.0.0"))
+                .unwrap(),
+            registry_dir
+        );
+        assert_eq!(requests.lock().unwrap().len(), pulls);
+
+        // Once expired, the tag is resolved again but the archive is not
+        // pulled again, unless a refresh is requested.
+        let blob_pulls = || {
+            requests
+                .lock()
+                .unwrap()
+                .iter()
+                .filter(|request| request.starts_with("GET /v2/acme/semconv/blobs/"))
+                .count()
+        };
+        let expired_cache = crate::Cache {
+            path: cache_dir.path().to_path_buf(),
+            policy: crate::CachePolicy {
+                refresh: false,
+                ttl: Duration::ZERO,
+            },
+            ..Default::default()
+        };
+        let blobs = blob_pulls();
+        let _ = expired_cache
+            .oci_registry(&format!("oci://{address}/acme/semconv:1.0.0"))
+            .unwrap();
+        assert!(requests.lock().unwrap().len() > pulls);
+        assert_eq!(blob_pulls(), blobs);
+        let refreshed_cache = crate::Cache {
+            path: cache_dir.path().to_path_buf(),
+            policy: crate::CachePolicy {
+                refresh: true,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        assert_eq!(
+            refreshed_cache
+                .oci_registry(&format!("oci://{address}/acme/semconv@{manifest_digest}"))
+                .unwrap(),
+            registry_dir
+        );
+        assert_eq!(blob_pulls(), blobs + 1);
+        let pulls = requests.lock().unwrap().len();
+
+        // A digest-pinned reference in the cache is used without network access.
+        let pinned = cache
+            .oci_registry(&format!("oci://{address}/acme/semconv@{manifest_digest}"))
+            .unwrap();
+        assert_eq!(pinned, registry_dir);
+        assert_eq!(requests.lock().unwrap().len(), pulls);
+
+        // A digest pin not matching the manifest is rejected.
+        let result = cache.oci_registry(&format!(
+            "oci://{address}/acme/semconv:1.0.0@{}",
+            sha256_digest(b"other")
+        ));
+        assert!(matches!(result, Err(Error::OciError { .. })));
+
+        // The checksum of the archive is verified, even for a pinned
+        // reference in the cache, and a required signature must be present.
+        let pinned = format!("oci://{address}/acme/semconv@{manifest_digest}");
+        let checksum = layer_digest.replace("sha256:", "");
+        assert_eq!(
+            cache
+                .oci_registry(&format!("{pinned}#sha256={checksum}"))
+                .unwrap(),
+            registry_dir
+        );
+        let result = cache.oci_registry(&format!("{pinned}#sha256={}", "0".repeat(64)));
+        assert!(matches!(result, Err(Error::VerificationFailed { .. })));
+        let result = cache.oci_registry(&format!(
+            "{pinned}#minisign=RWQf6LRCGA9i53mlYecO4IzT51TGPpvWucNSCh1CBM0QTaLn73Y7GFO3"
+        ));
+        assert!(matches!(result, Err(Error::VerificationFailed { .. })));
+    }
+}
@@ -0,0 +1,193 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Verification of the registry archives (local archives and OCI artifacts)
+//! before their extraction.
+//!
+//! The expected checksum and the minisign public key of an archive are
+//! specified in the fragment of its reference, e.g.
+//! `./acme-semconv-1.2.0.tar.gz#sha256=<hex>` or
+//! `oci://ghcr.io/acme/semconv:1.2.0#minisign=acme.pub`. Several parameters
+//! are separated by `&`.
+
+use std::path::Path;
+
+use minisign_verify::{PublicKey, Signature};
+
+use crate::oci::sha256_digest;
+use crate::Error;
+
+/// The extension of the minisign signature files of local archives (e.g.
+/// `acme-semconv-1.2.0.tar.gz.minisig`).
+pub const SIGNATURE_EXTENSION: &str = "minisig";
+
+/// The checks to perform on a registry archive before its extraction.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ArchiveVerification {
+    /// The expected SHA-256 checksum of the archive (hexadecimal).
+    pub sha256: Option<String>,
+    /// The minisign public key (base64 or path of a public key file) the
+    /// archive must be signed with.
+    pub minisign_public_key: Option<String>,
+}
+
+impl ArchiveVerification {
+    /// Splits a registry reference into the reference itself and the checks
+    /// declared by its fragment.
+    pub fn split_reference(reference: &str) -> Result<(&str, Self), Error> {
+        let Some((reference, fragment)) = reference.split_once('#') else {
+            return Ok((reference, Self::default()));
+        };
+        let mut verification = Self::default();
+        for param in fragment.split('&').filter(|param| !param.is_empty()) {
+            match param.split_once('=') {
+                Some(("sha256", value)) => {
+                    let value = value.strip_prefix("sha256:").unwrap_or(value);
+                    if value.len() != 64 || !value.chars().all(|c| c.is_ascii_hexdigit()) {
+                        return Err(verification_error(
+                            reference,
+                            format!("Invalid SHA-256 checksum `{value}`"),
+                        ));
+                    }
+                    verification.sha256 = Some(value.to_ascii_lowercase());
+                }
+                Some(("minisign", value)) if !value.is_empty() => {
+                    verification.minisign_public_key = Some(value.to_owned());
+                }
+                _ => {
+                    return Err(verification_error(
+                        reference,
+                        format!(
+                            "Unsupported verification parameter `{param}` (expected `sha256=<hex>` or `minisign=<public key>`)"
+                        ),
+                    ));
+                }
+            }
+        }
+        Ok((reference, verification))
+    }
+
+    /// Returns true if the archive must be verified.
+    #[must_use]
+    pub fn is_required(&self) -> bool {
+        self.sha256.is_some() || self.minisign_public_key.is_some()
+    }
+
+    /// Verifies the checksum and the signature of an archive. A missing
+    /// signature is an error when a public key is specified.
+    ///
+    /// # Arguments
+    ///
+    /// * `reference` - The reference of the archive, used in the errors.
+    /// * `archive` - The content of the archive.
+    /// * `signature` - The content of the minisign signature of the archive, if any.
+    pub fn verify(
+        &self,
+        reference: &str,
+        archive: &[u8],
+        signature: Option<&[u8]>,
+    ) -> Result<(), Error> {
+        if let Some(expected) = &self.sha256 {
+            let actual = sha256_digest(archive);
+            if actual.strip_prefix("sha256:") != Some(expected.as_str()) {
+                return Err(verification_error(
+                    reference,
+                    format!(
+                        "The checksum of the archive is `{actual}`, `sha256:{expected}` expected"
+                    ),
+                ));
+            }
+        }
+        if let Some(public_key) = &self.minisign_public_key {
+            let public_key = if Path::new(public_key).is_file() {
+                PublicKey::from_file(public_key)
+            } else {
+                PublicKey::from_base64(public_key)
+            }
+            .map_err(|e| verification_error(reference, format!("Invalid public key: {e}")))?;
+            let signature = signature.ok_or_else(|| {
+                verification_error(reference, "The archive is not signed".to_owned())
+            })?;
+            let signature = std::str::from_utf8(signature)
+                .ok()
+                .and_then(|signature| Signature::decode(signature).ok())
+                .ok_or_else(|| verification_error(reference, "Invalid signature".to_owned()))?;
+            public_key.verify(archive, &signature, false).map_err(|e| {
+                verification_error(reference, format!("Invalid signature of the archive: {e}"))
+            })?;
+        }
+        Ok(())
+    }
+}
+
+fn verification_error(reference: &str, message: String) -> Error {
+    Error::VerificationFailed {
+        reference: reference.to_owned(),
+        message,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // A public key and a signature of `ARCHIVE` from the documentation of
+    // minisign-verify.
+    const ARCHIVE: &[u8] = b"test";
+    const PUBLIC_KEY: &str = "RWQf6LRCGA9i53mlYecO4IzT51TGPpvWucNSCh1CBM0QTaLn73Y7GFO3";
+    const SIGNATURE: &str = "untrusted comment: signature from minisign secret key
+RUQf6LRCGA9i559r3g7V1qNyJDApGip8MfqcadIgT9CuhV3EMhHoN1mGTkUidF/z7SrlQgXdy8ofjb7bNJJylDOocrCo8KLzZwo=
+trusted comment: timestamp:1633700835\tfile:test\tprehashed
+wLMDjy9FLAuxZ3q4NlEvkgtyhrr0gtTu6KC4KBJdITbbOeAi1zBIYo0v4iTgt8jJpIidRJnp94ABQkJAgAooBQ==";
+
+    #[test]
+    fn test_split_reference() {
+        let checksum = "a".repeat(64);
+        let reference =
+            format!("oci://ghcr.io/acme/semconv:1.2.0#sha256={checksum}&minisign=acme.pub");
+        let (reference, verification) =
+            ArchiveVerification::split_reference(&reference).expect("Valid reference");
+        assert_eq!(reference, "oci://ghcr.io/acme/semconv:1.2.0");
+        assert_eq!(verification.sha256.as_deref(), Some(checksum.as_str()));
+        assert_eq!(
+            verification.minisign_public_key.as_deref(),
+            Some("acme.pub")
+        );
+        assert!(verification.is_required());
+
+        let (reference, verification) =
+            ArchiveVerification::split_reference("registry.tar.gz").expect("Valid reference");
+        assert_eq!(reference, "registry.tar.gz");
+        assert!(!verification.is_required());
+
+        assert!(ArchiveVerification::split_reference("registry.tar.gz#sha256=1234").is_err());
+        assert!(ArchiveVerification::split_reference("registry.tar.gz#md5=1234").is_err());
+    }
+
+    #[test]
+    fn test_verify() {
+        let checksum = sha256_digest(ARCHIVE).replace("sha256:", "");
+        let verification = ArchiveVerification {
+            sha256: Some(checksum),
+            minisign_public_key: Some(PUBLIC_KEY.to_owned()),
+        };
+        verification
+            .verify("test", ARCHIVE, Some(SIGNATURE.as_bytes()))
+            .expect("Valid archive");
+
+        // Tampered archive, missing or invalid signature.
+        assert!(verification
+            .verify("test", b"tampered", Some(SIGNATURE.as_bytes()))
+            .is_err());
+        assert!(verification.verify("test", ARCHIVE, None).is_err());
+        let signature_only = ArchiveVerification {
+            sha256: None,
+            minisign_public_key: Some(PUBLIC_KEY.to_owned()),
+        };
+        assert!(signature_only
+            .verify("test", b"tampered", Some(SIGNATURE.as_bytes()))
+            .is_err());
+        assert!(signature_only
+            .verify("test", ARCHIVE, Some(b"not a signature"))
+            .is_err());
+    }
+}
@@ -0,0 +1,11 @@
+package before_resolution.attr_deprecated_without_reason
+
+# Deprecation: a deprecated attribute must explain what replaces it or why it
+# has been deprecated.
+deny[data.builtin.violations.attr_violation("attr_deprecated_without_reason", "deprecation", group.id, attr.id)] {
+    group := input.groups[_]
+    attr := group.attributes[_]
+    attr.id
+    is_string(attr.deprecated)
+    trim_space(attr.deprecated) == ""
+}
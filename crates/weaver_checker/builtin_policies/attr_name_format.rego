@@ -0,0 +1,15 @@
+package before_resolution.attr_name_format
+
+# Naming: attribute names are made of lowercase, dot-separated namespaces and
+# snake_case components (e.g. `http.request.method`).
+deny[data.builtin.violations.attr_violation("attr_name_format", "naming", group.id, name)] {
+    group := input.groups[_]
+    attr := group.attributes[_]
+    name := attr_full_name(group, attr)
+    not regex.match(`^[a-z][a-z0-9]*(_[a-z0-9]+)*(\.[a-z][a-z0-9]*(_[a-z0-9]+)*)*$`, name)
+}
+
+attr_full_name(group, attr) = name {
+    group.prefix != ""
+    name := concat(".", [group.prefix, attr.id])
+} else = attr.id
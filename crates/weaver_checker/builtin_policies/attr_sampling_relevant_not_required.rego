@@ -0,0 +1,21 @@
+package after_resolution.attr_sampling_relevant_not_required
+
+# Sampling: the sampling-relevant attributes of a span must be provided at span
+# creation, so they must be `required` or `conditionally_required`. The flag and
+# the requirement level are checked once the references and the `extends`
+# clauses are resolved.
+deny[data.builtin.violations.attr_violation("attr_sampling_relevant_not_required", "sampling", group.id, attr.name)] {
+    group := input.groups[_]
+    group.type == "span"
+    attr := group.attributes[_]
+    attr.sampling_relevant == true
+    not is_required(attr.requirement_level)
+}
+
+is_required(requirement_level) {
+    requirement_level == "required"
+}
+
+is_required(requirement_level) {
+    requirement_level.conditionally_required
+}
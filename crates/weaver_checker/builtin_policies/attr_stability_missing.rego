@@ -0,0 +1,11 @@
+package before_resolution.attr_stability_missing
+
+# Stability: attributes defined in the attribute registry must declare their
+# stability.
+deny[data.builtin.violations.attr_violation("attr_stability_missing", "stability", group.id, attr.id)] {
+    group := input.groups[_]
+    startswith(group.id, "registry.")
+    attr := group.attributes[_]
+    attr.id
+    not attr.stability
+}
@@ -0,0 +1,22 @@
+package builtin.violations
+
+# Helpers building the violations reported by the built-in policies. The
+# policies call them by their full path (e.g.
+# `data.builtin.violations.attr_violation(...)`), the functions of an imported
+# package can't be called through the import alias.
+
+# Violation of an attribute of a group.
+attr_violation(violation_id, category, group_id, attr_id) = violation {
+    violation := {
+        "id": violation_id,
+        "type": "semconv_attribute",
+        "category": category,
+        "group": group_id,
+        "attr": attr_id,
+    }
+}
+
+# Violation of a metric group, not related to a specific attribute.
+metric_violation(violation_id, category, group_id) = violation {
+    violation := attr_violation(violation_id, category, group_id, "")
+}
@@ -0,0 +1,11 @@
+package before_resolution.metric_counter_count_suffix
+
+# Instrument: the `.count` suffix names the number of items currently in use
+# (e.g. `jvm.thread.count`), which is measured by an `updowncounter`. A monotonic
+# `counter` ending with `.count` is most likely the wrong instrument.
+deny[data.builtin.violations.metric_violation("metric_counter_count_suffix", "instrument", group.id)] {
+    group := input.groups[_]
+    group.type == "metric"
+    group.instrument == "counter"
+    endswith(group.metric_name, ".count")
+}
@@ -0,0 +1,13 @@
+package before_resolution.metric_duration_unit
+
+# Unit: the histograms of durations (metric names ending with `.duration`) are
+# measured in seconds (`s`), or in milliseconds (`ms`) for the legacy metrics.
+deny[data.builtin.violations.metric_violation("metric_duration_unit", "unit", group.id)] {
+    group := input.groups[_]
+    group.type == "metric"
+    group.instrument == "histogram"
+    endswith(group.metric_name, ".duration")
+    not duration_units[group.unit]
+}
+
+duration_units := {"s", "ms"}
@@ -0,0 +1,19 @@
+package before_resolution.metric_updowncounter_total_suffix
+
+# Instrument: the `total` suffix (`.total`, or `_total` once exported to
+# Prometheus) is reserved to the monotonic counters. An `updowncounter` can
+# decrease, so its name must not end with it.
+deny[data.builtin.violations.metric_violation("metric_updowncounter_total_suffix", "instrument", group.id)] {
+    group := input.groups[_]
+    group.type == "metric"
+    group.instrument == "updowncounter"
+    total_suffix(group.metric_name)
+}
+
+total_suffix(metric_name) {
+    endswith(metric_name, ".total")
+}
+
+total_suffix(metric_name) {
+    endswith(metric_name, "_total")
+}
@@ -0,0 +1,20 @@
+package before_resolution.registry_attr_prefix
+
+# Prefix: attributes defined in a `registry.<namespace>` group must be prefixed
+# with the namespace of the group (e.g. `http.request.method` in `registry.http`).
+# Deprecated attributes keep their original name and are not checked.
+deny[data.builtin.violations.attr_violation("registry_attr_prefix", "prefix", group.id, name)] {
+    group := input.groups[_]
+    startswith(group.id, "registry.")
+    namespace := split(group.id, ".")[1]
+    attr := group.attributes[_]
+    attr.id
+    not attr.deprecated
+    name := attr_full_name(group, attr)
+    not startswith(name, concat("", [namespace, "."]))
+}
+
+attr_full_name(group, attr) = name {
+    group.prefix != ""
+    name := concat(".", [group.prefix, attr.id])
+} else = attr.id
@@ -0,0 +1,15 @@
+package before_resolution
+
+# Attribute names must be lowercase (used for test purposes only).
+deny[violation] {
+    group := input.groups[_]
+    attr := group.attributes[_]
+    lower(attr.id) != attr.id
+    violation := {
+        "id": "attr_name_lowercase",
+        "type": "semconv_attribute",
+        "category": "naming",
+        "group": group.id,
+        "attr": attr.id,
+    }
+}
@@ -0,0 +1,14 @@
+package before_resolution_test
+
+test_invalid_attr_name {
+    count(data.before_resolution.deny) == 1 with input as {"groups": [{"id": "registry.http", "attributes": [{"id": "http.Method"}]}]}
+}
+
+test_valid_attr_name {
+    count(data.before_resolution.deny) == 0 with input as {"groups": [{"id": "registry.http", "attributes": [{"id": "http.method"}]}]}
+}
+
+# A failing test (used to test the reporting of failures).
+test_failing {
+    count(data.before_resolution.deny) == 1 with input as {"groups": []}
+}
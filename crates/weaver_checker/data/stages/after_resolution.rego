@@ -0,0 +1,14 @@
+package after_resolution
+
+# Rules applied on the resolved registry (used for test purposes only).
+
+# An attribute must have the same type in all the groups referencing it.
+deny[data.helpers.attr_violation("attr_type_mismatch", group.id, attr.name)] {
+    group := input.groups[_]
+    attr := group.attributes[_]
+    other_group := input.groups[_]
+    other_group.id != group.id
+    other_attr := other_group.attributes[_]
+    other_attr.name == attr.name
+    other_attr.type != attr.type
+}
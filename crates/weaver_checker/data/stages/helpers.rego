@@ -0,0 +1,13 @@
+package helpers
+
+# A supporting module imported by the policies of a stage (used for test purposes only).
+
+attr_violation(violation_id, group_id, attr_id) = violation {
+    violation := {
+        "id": violation_id,
+        "type": "semconv_attribute",
+        "category": "attribute",
+        "group": group_id,
+        "attr": attr_id,
+    }
+}
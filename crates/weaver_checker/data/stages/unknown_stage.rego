@@ -0,0 +1,8 @@
+package otel
+
+# A policy package that doesn't declare any policy stage. It is loaded but its
+# rules are never evaluated directly (used for test purposes only).
+
+deny[violation] {
+    violation := input.groups[_]
+}
@@ -0,0 +1,22 @@
+;; A WASM policy reporting a fixed violation whatever the input (used for test purposes only).
+;; Compiled to `policy.wasm` with `wat2wasm policy.wat`.
+(module
+  (memory (export "memory") 1)
+  (global $next (mut i32) (i32.const 1024))
+  (data (i32.const 0) "[{\"type\":\"semconv_attribute\",\"id\":\"wasm_violation\",\"category\":\"wasm\",\"group\":\"registry.http\",\"attr\":\"http.method\"}]")
+  (func (export "alloc") (param $len i32) (result i32)
+    (local $ptr i32)
+    (local.set $ptr (global.get $next))
+    (global.set $next (i32.add (global.get $next) (local.get $len)))
+    ;; Grow the memory if the allocated bytes don't fit in it.
+    (if (i32.gt_u (global.get $next) (i32.mul (memory.size) (i32.const 65536)))
+      (then
+        (drop (memory.grow
+          (i32.add
+            (i32.div_u
+              (i32.sub (global.get $next) (i32.mul (memory.size) (i32.const 65536)))
+              (i32.const 65536))
+            (i32.const 1))))))
+    (local.get $ptr))
+  (func (export "check") (param $ptr i32) (param $len i32) (result i64)
+    (i64.const 115)))
@@ -3,11 +3,13 @@
 #![allow(rustdoc::broken_intra_doc_links)]
 #![doc = include_str!("../README.md")]
 
-use std::collections::HashSet;
+use std::collections::BTreeSet;
 use std::fmt::{Display, Formatter};
 use std::path::Path;
+use std::sync::Arc;
 
 use globset::Glob;
+use include_dir::{include_dir, Dir};
 use miette::Diagnostic;
 use serde::Serialize;
 use serde_json::to_value;
@@ -16,10 +18,20 @@ use walkdir::DirEntry;
 use weaver_common::diagnostic::{DiagnosticMessage, DiagnosticMessages};
 use weaver_common::error::{format_errors, handle_errors, WeaverError};
 
+use crate::policy_test::is_policy_test_file;
 use crate::violation::Violation;
+use crate::wasm::{WasmPolicy, DEFAULT_WASM_FUEL};
 use crate::Error::CompoundError;
 
+pub mod policy_test;
 pub mod violation;
+pub mod wasm;
+
+/// Built-in policies implementing the semantic convention authoring rules
+/// (naming, stability, deprecation, prefix). The id of a built-in policy is the
+/// name of its file without the `.rego` extension. The `lib` directory holds
+/// the helper packages shared by the built-in policies.
+static BUILTIN_POLICIES: Dir<'_> = include_dir!("$CARGO_MANIFEST_DIR/builtin_policies");
 
 /// An error that can occur while evaluating policies.
 #[derive(thiserror::Error, Debug, Serialize, Diagnostic, Clone)]
@@ -40,6 +52,40 @@ pub enum Error {
         error: String,
     },
 
+    /// An invalid WASM policy module.
+    #[error("Invalid WASM policy module '{file}', error: {error})")]
+    #[diagnostic(help(
+        "A WASM policy module must export `memory`, `alloc(len: i32) -> i32`, and `check(ptr: i32, len: i32) -> i64`."
+    ))]
+    InvalidWasmPolicy {
+        /// The file that caused the error.
+        file: String,
+        /// The error that occurred.
+        error: String,
+    },
+
+    /// A WASM policy that consumed its whole fuel budget.
+    #[error("WASM policy '{file}' exhausted its fuel budget ({fuel} units)")]
+    #[diagnostic(help(
+        "The policy doesn't terminate or its check is too expensive. The fuel budget can be increased with `Engine::set_wasm_fuel`."
+    ))]
+    WasmPolicyFuelExhausted {
+        /// The WASM policy module.
+        file: String,
+        /// The fuel budget of the check.
+        fuel: u64,
+    },
+
+    /// An unknown built-in policy.
+    #[error("Unknown built-in policy '{id}'")]
+    #[diagnostic(help("Known built-in policies are: {known}"))]
+    UnknownBuiltinPolicy {
+        /// The id of the built-in policy.
+        id: String,
+        /// The ids of the known built-in policies.
+        known: String,
+    },
+
     /// An invalid policy glob pattern.
     #[error("Invalid policy glob pattern '{pattern}', error: {error})")]
     #[diagnostic(
@@ -121,11 +167,40 @@ impl From<Error> for DiagnosticMessages {
 }
 
 /// A list of supported policy stages.
+///
+/// The stage of a policy is declared by the name of its package.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum PolicyStage {
-    /// Policies that are evaluated before resolution.
+    /// Policies that are evaluated before resolution, against each semantic
+    /// convention file (package `before_resolution`).
     BeforeResolution,
-    /// Policies that are evaluated after resolution.
+    /// Policies that are evaluated after resolution, against the whole resolved
+    /// registry (package `after_resolution`).
     AfterResolution,
+    /// Policies that are evaluated by the live check, against each observed
+    /// attribute and its definition in the registry (package `live_check_advice`).
+    LiveCheckAdvice,
+}
+
+impl PolicyStage {
+    /// Returns the policy stage corresponding to a policy package
+    /// (e.g. `data.before_resolution` or `data.before_resolution.naming`), or
+    /// `None` if the package doesn't match any stage.
+    #[must_use]
+    pub fn from_package(package: &str) -> Option<Self> {
+        [
+            PolicyStage::BeforeResolution,
+            PolicyStage::AfterResolution,
+            PolicyStage::LiveCheckAdvice,
+        ]
+        .into_iter()
+        .find(|stage| {
+            let stage_package = format!("data.{}", stage);
+            package
+                .strip_prefix(&stage_package)
+                .is_some_and(|rest| rest.is_empty() || rest.starts_with('.'))
+        })
+    }
 }
 
 impl Display for PolicyStage {
@@ -138,6 +213,9 @@ impl Display for PolicyStage {
             PolicyStage::AfterResolution => {
                 write!(f, "after_resolution")
             }
+            PolicyStage::LiveCheckAdvice => {
+                write!(f, "live_check_advice")
+            }
         }
     }
 }
@@ -153,7 +231,35 @@ pub struct Engine {
     policy_package_count: usize,
     // Policy packages loaded. This is used to check if a policy package has been imported
     // before evaluating it.
-    policy_packages: HashSet<String>,
+    policy_packages: BTreeSet<String>,
+    // Policies compiled to WASM modules, evaluated after resolution.
+    wasm_policies: Vec<Arc<WasmPolicy>>,
+    // Fuel budget of each WASM policy check (default: `DEFAULT_WASM_FUEL`).
+    wasm_fuel: Option<u64>,
+    // Current input, kept to evaluate the WASM policies.
+    input: Option<regorus::Value>,
+}
+
+/// Returns the ids of the built-in policies.
+#[must_use]
+pub fn builtin_policy_ids() -> Vec<String> {
+    builtin_policy_files().map(builtin_policy_id).collect()
+}
+
+fn builtin_policy_files() -> impl Iterator<Item = &'static include_dir::File<'static>> {
+    let mut files: Vec<_> = BUILTIN_POLICIES
+        .files()
+        .filter(|file| file.path().extension().is_some_and(|ext| ext == "rego"))
+        .collect();
+    files.sort_by_key(|file| file.path());
+    files.into_iter()
+}
+
+fn builtin_policy_id(file: &include_dir::File<'_>) -> String {
+    file.path()
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().to_string())
+        .unwrap_or_default()
 }
 
 impl Engine {
@@ -175,24 +281,100 @@ impl Engine {
     /// # Arguments
     ///
     /// * `policy_path` - The path to the policy file.
+    ///
+    /// The rules of the policy file are evaluated when its package matches one
+    /// of the policy stages (see [`PolicyStage`]). The other packages are
+    /// supporting modules, e.g. helper functions imported by the stage
+    /// policies.
     pub fn add_policy<P: AsRef<Path>>(&mut self, policy_path: P) -> Result<String, Error> {
         let policy_path_str = policy_path.as_ref().to_string_lossy().to_string();
 
-        let policy_package = self
-            .engine
-            .add_policy_from_file(policy_path)
-            .map_err(|e| Error::InvalidPolicyFile {
+        let policy_package = self.engine.add_policy_from_file(policy_path).map_err(|e| {
+            Error::InvalidPolicyFile {
                 file: policy_path_str.clone(),
                 error: e.to_string(),
+            }
+        })?;
+        Ok(self.register_policy_package(policy_package))
+    }
+
+    /// Adds the built-in policies implementing the semantic convention authoring
+    /// rules, except the ones whose id is listed in `disabled`.
+    ///
+    /// # Returns
+    ///
+    /// The number of built-in policies added.
+    pub fn add_builtin_policies(&mut self, disabled: &[String]) -> Result<usize, Error> {
+        let known = builtin_policy_ids();
+        if let Some(id) = disabled.iter().find(|id| !known.contains(id)) {
+            return Err(Error::UnknownBuiltinPolicy {
+                id: id.clone(),
+                known: known.join(", "),
+            });
+        }
+
+        // The helper packages are imported by the built-in policies, they
+        // don't define any policy stage.
+        for file in BUILTIN_POLICIES
+            .get_dir("lib")
+            .into_iter()
+            .flat_map(|dir| dir.files())
+        {
+            _ = self.add_builtin_policy_file(file)?;
+        }
+
+        let mut added_policy_count = 0;
+        for file in builtin_policy_files() {
+            if disabled.contains(&builtin_policy_id(file)) {
+                continue;
+            }
+            let policy_package = self.add_builtin_policy_file(file)?;
+            _ = self.register_policy_package(policy_package);
+            added_policy_count += 1;
+        }
+        Ok(added_policy_count)
+    }
+
+    /// Adds a file of the built-in policies to the engine, and returns its
+    /// package.
+    fn add_builtin_policy_file(&mut self, file: &include_dir::File<'_>) -> Result<String, Error> {
+        let path = format!("builtin:{}", file.path().display());
+        self.engine
+            .add_policy(
+                path.clone(),
+                file.contents_utf8().unwrap_or_default().to_owned(),
+            )
+            .map_err(|e| Error::InvalidPolicyFile {
+                file: path,
+                error: e.to_string(),
             })
-            .inspect(|_| {
-                self.policy_package_count += 1;
-            })?;
+    }
+
+    /// Adds a policy compiled to a WASM module (see the [`wasm`] module for the
+    /// interface the module must expose). WASM policies are evaluated against
+    /// the resolved registry, i.e. at the `after_resolution` stage.
+    pub fn add_wasm_policy<P: AsRef<Path>>(&mut self, policy_path: P) -> Result<(), Error> {
+        self.wasm_policies
+            .push(Arc::new(WasmPolicy::load(policy_path)?));
+        self.policy_package_count += 1;
+        Ok(())
+    }
+
+    /// Sets the maximum number of units of fuel consumed by each check of a
+    /// WASM policy (see [`wasm::DEFAULT_WASM_FUEL`]).
+    pub fn set_wasm_fuel(&mut self, fuel: u64) {
+        self.wasm_fuel = Some(fuel);
+    }
+
+    /// Registers a policy package. Only the packages declaring a policy stage
+    /// are evaluated (see [`Engine::check`]).
+    fn register_policy_package(&mut self, policy_package: String) -> String {
+        self.policy_package_count += 1;
         // Add the policy package defined in the imported policy file.
         // Nothing prevent multiple policy files to import the same policy package.
         // All the rules will be combined and evaluated together.
         _ = self.policy_packages.insert(policy_package.clone());
-        Ok(policy_package)
+        policy_package
     }
 
     /// Adds all the policy files present in the given directory that match the
@@ -236,7 +418,8 @@ impl Engine {
             if is_hidden(&entry) {
                 continue;
             }
-            if is_policy_file(&entry) {
+            // Policy test files are only evaluated by the policy test runner.
+            if is_policy_file(&entry) && !is_policy_test_file(entry.path()) {
                 if let Err(err) = self.add_policy(entry.path()) {
                     errors.push(err);
                 } else {
@@ -257,6 +440,20 @@ impl Engine {
         self.policy_package_count
     }
 
+    /// Returns true if at least one policy has been added for the given stage.
+    #[must_use]
+    pub fn has_policies(&self, stage: PolicyStage) -> bool {
+        self.stage_packages(stage).next().is_some()
+            || (stage == PolicyStage::AfterResolution && !self.wasm_policies.is_empty())
+    }
+
+    /// Returns the policy packages loaded for the given stage.
+    fn stage_packages(&self, stage: PolicyStage) -> impl Iterator<Item = &String> {
+        self.policy_packages
+            .iter()
+            .filter(move |package| PolicyStage::from_package(package) == Some(stage))
+    }
+
     /// Adds a data document to the policy engine.
     ///
     /// Data versus Input: In essence, data is about what the policy engine
@@ -299,11 +496,13 @@ impl Engine {
         let json_input = to_value(input).map_err(|e| Error::InvalidInput {
             error: e.to_string(),
         })?;
-
         let value: regorus::Value =
             serde_json::from_value(json_input).map_err(|e| Error::InvalidInput {
                 error: e.to_string(),
             })?;
+        // The values of `regorus` are reference counted, so keeping the input
+        // for the WASM policies is cheap.
+        self.input = Some(value.clone());
         self.engine.set_input(value);
         Ok(())
     }
@@ -312,22 +511,26 @@ impl Engine {
     /// input, and the given policy stage.
     #[allow(clippy::print_stdout)] // Used to display the coverage (debugging purposes only)
     pub fn check(&mut self, stage: PolicyStage) -> Result<Vec<Violation>, Error> {
+        // Evaluate the `deny` rule of every policy package that matches the stage.
         // If we don't have any policy package that matches the stage,
         // return an empty list of violations.
-        if !self.policy_packages.contains(&format!("data.{}", stage)) {
-            return Ok(vec![]);
+        let packages: Vec<String> = self.stage_packages(stage).cloned().collect();
+        let mut values = Vec::with_capacity(packages.len());
+        for package in &packages {
+            let value = self
+                .engine
+                .eval_rule(format!("{}.deny", package))
+                .map_err(|e| Error::ViolationEvaluationError {
+                    error: e.to_string(),
+                })?;
+            if value != regorus::Value::Undefined {
+                values.push(value);
+            }
         }
 
-        let value = self
-            .engine
-            .eval_rule(format!("data.{}.deny", stage))
-            .map_err(|e| Error::ViolationEvaluationError {
-                error: e.to_string(),
-            })?;
-
         // Print the coverage report if enabled
         // This is useful for debugging purposes
-        if self.coverage_enabled {
+        if self.coverage_enabled && !packages.is_empty() {
             let report =
                 self.engine
                     .get_coverage_report()
@@ -343,17 +546,36 @@ impl Engine {
             println!("{}", pretty_report);
         }
 
-        // convert `regorus` value to `serde_json` value
-        let json_value = to_value(&value).map_err(|e| Error::ViolationEvaluationError {
-            error: e.to_string(),
-        })?;
-
-        // convert json value into a vector of violations
-        let violations: Vec<Violation> =
-            serde_json::from_value(json_value).map_err(|e| Error::ViolationEvaluationError {
+        let mut violations = vec![];
+        for value in values {
+            // convert `regorus` value to `serde_json` value
+            let json_value = to_value(&value).map_err(|e| Error::ViolationEvaluationError {
                 error: e.to_string(),
             })?;
 
+            // convert json value into a vector of violations
+            let package_violations: Vec<Violation> =
+                serde_json::from_value(json_value).map_err(|e| {
+                    Error::ViolationEvaluationError {
+                        error: e.to_string(),
+                    }
+                })?;
+            violations.extend(package_violations);
+        }
+
+        // Evaluate the WASM policies against the resolved registry.
+        if stage == PolicyStage::AfterResolution && !self.wasm_policies.is_empty() {
+            if let Some(input) = &self.input {
+                let input = serde_json::to_string(input).map_err(|e| Error::InvalidInput {
+                    error: e.to_string(),
+                })?;
+                let fuel = self.wasm_fuel.unwrap_or(DEFAULT_WASM_FUEL);
+                for wasm_policy in &self.wasm_policies {
+                    violations.extend(wasm_policy.check_with_fuel(&input, fuel)?);
+                }
+            }
+        }
+
         Ok(violations)
     }
 }
@@ -362,12 +584,13 @@ impl Engine {
 mod tests {
     use std::collections::HashMap;
 
+    use serde_json::json;
     use serde_yaml::Value;
 
     use weaver_common::error::format_errors;
 
     use crate::violation::Violation;
-    use crate::{Engine, Error, PolicyStage};
+    use crate::{builtin_policy_ids, Engine, Error, PolicyStage};
 
     #[test]
     fn test_policy() -> Result<(), Box<dyn std::error::Error>> {
@@ -502,6 +725,202 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_policy_stages() -> Result<(), Box<dyn std::error::Error>> {
+        assert_eq!(
+            PolicyStage::from_package("data.before_resolution"),
+            Some(PolicyStage::BeforeResolution)
+        );
+        assert_eq!(
+            PolicyStage::from_package("data.after_resolution"),
+            Some(PolicyStage::AfterResolution)
+        );
+        assert_eq!(
+            PolicyStage::from_package("data.live_check_advice.naming"),
+            Some(PolicyStage::LiveCheckAdvice)
+        );
+        assert_eq!(PolicyStage::from_package("data.otel"), None);
+
+        let mut engine = Engine::new();
+        let policy_package = engine.add_policy("data/stages/after_resolution.rego")?;
+        assert_eq!(policy_package, "data.after_resolution");
+        assert!(engine.has_policies(PolicyStage::AfterResolution));
+        assert!(!engine.has_policies(PolicyStage::BeforeResolution));
+
+        // The packages that don't declare a policy stage are loaded as supporting
+        // modules, their rules are not evaluated directly.
+        let policy_package = engine.add_policy("data/stages/helpers.rego")?;
+        assert_eq!(policy_package, "data.helpers");
+        let policy_package = engine.add_policy("data/stages/unknown_stage.rego")?;
+        assert_eq!(policy_package, "data.otel");
+        assert_eq!(engine.policy_package_count(), 3);
+
+        let resolved_registry = json!({
+            "groups": [
+                {"id": "registry.network", "attributes": [{"name": "network.port", "type": "int"}]},
+                {"id": "span.server", "attributes": [{"name": "network.port", "type": "string"}]},
+                {"id": "span.client", "attributes": [{"name": "server.address", "type": "string"}]},
+            ]
+        });
+        engine.set_input(&resolved_registry)?;
+
+        // No policy registered for this stage.
+        assert!(engine.check(PolicyStage::BeforeResolution)?.is_empty());
+
+        let mut violations = engine.check(PolicyStage::AfterResolution)?;
+        violations.sort_by_key(|v| v.to_string());
+        assert_eq!(
+            violations,
+            vec![
+                Violation::SemconvAttribute {
+                    id: "attr_type_mismatch".to_owned(),
+                    category: "attribute".to_owned(),
+                    group: "registry.network".to_owned(),
+                    attr: "network.port".to_owned(),
+                },
+                Violation::SemconvAttribute {
+                    id: "attr_type_mismatch".to_owned(),
+                    category: "attribute".to_owned(),
+                    group: "span.server".to_owned(),
+                    attr: "network.port".to_owned(),
+                },
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_wasm_policies() -> Result<(), Box<dyn std::error::Error>> {
+        let mut engine = Engine::new();
+        // The input can be set before the WASM policies are added.
+        engine.set_input(&json!({"groups": []}))?;
+        engine.add_wasm_policy("data/wasm/policy.wasm")?;
+        assert!(engine.has_policies(PolicyStage::AfterResolution));
+        assert!(!engine.has_policies(PolicyStage::BeforeResolution));
+
+        assert!(engine.check(PolicyStage::BeforeResolution)?.is_empty());
+        let violations = engine.check(PolicyStage::AfterResolution)?;
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].id(), "wasm_violation");
+
+        assert!(matches!(
+            engine.add_wasm_policy("data/wasm/policy.wat"),
+            Err(Error::InvalidWasmPolicy { .. })
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_builtin_policies() -> Result<(), Box<dyn std::error::Error>> {
+        assert_eq!(
+            builtin_policy_ids(),
+            vec![
+                "attr_deprecated_without_reason",
+                "attr_name_format",
+                "attr_sampling_relevant_not_required",
+                "attr_stability_missing",
+                "metric_counter_count_suffix",
+                "metric_duration_unit",
+                "metric_updowncounter_total_suffix",
+                "registry_attr_prefix",
+            ]
+        );
+
+        let registry = std::fs::read_to_string("data/builtin/registry.yaml")?;
+        let registry: Value = serde_yaml::from_str(&registry)?;
+
+        let mut engine = Engine::new();
+        assert_eq!(engine.add_builtin_policies(&[])?, 8);
+        engine.set_input(&registry)?;
+        let mut violations: Vec<(String, String)> = engine
+            .check(PolicyStage::BeforeResolution)?
+            .into_iter()
+            .filter_map(|v| match v {
+                // The metric policies report the group of the metric.
+                Violation::SemconvAttribute {
+                    id, group, attr, ..
+                } if attr.is_empty() => Some((id, group)),
+                Violation::SemconvAttribute { id, attr, .. } => Some((id, attr)),
+                Violation::Advice { .. } => None,
+            })
+            .collect();
+        violations.sort();
+        assert_eq!(
+            violations,
+            vec![
+                (
+                    "attr_deprecated_without_reason".to_owned(),
+                    "http.method".to_owned()
+                ),
+                (
+                    "attr_name_format".to_owned(),
+                    "http.requestBodySize".to_owned()
+                ),
+                (
+                    "attr_stability_missing".to_owned(),
+                    "http.response.status".to_owned()
+                ),
+                (
+                    "metric_counter_count_suffix".to_owned(),
+                    "metric.http.server.request.count".to_owned()
+                ),
+                (
+                    "metric_duration_unit".to_owned(),
+                    "metric.http.client.request.duration".to_owned()
+                ),
+                (
+                    "metric_updowncounter_total_suffix".to_owned(),
+                    "metric.http.server.active_requests.total".to_owned()
+                ),
+                ("registry_attr_prefix".to_owned(), "server.port".to_owned()),
+            ]
+        );
+
+        // The sampling-relevant attributes of the spans are checked after the
+        // resolution.
+        let resolved_registry = std::fs::read_to_string("data/builtin/resolved_registry.yaml")?;
+        let resolved_registry: Value = serde_yaml::from_str(&resolved_registry)?;
+        engine.set_input(&resolved_registry)?;
+        let violations: Vec<(String, String, String)> = engine
+            .check(PolicyStage::AfterResolution)?
+            .into_iter()
+            .filter_map(|v| match v {
+                Violation::SemconvAttribute {
+                    id, group, attr, ..
+                } => Some((id, group, attr)),
+                Violation::Advice { .. } => None,
+            })
+            .collect();
+        assert_eq!(
+            violations,
+            vec![(
+                "attr_sampling_relevant_not_required".to_owned(),
+                "span.http.client".to_owned(),
+                "url.full".to_owned()
+            )]
+        );
+
+        // Disabled built-in policies are not evaluated.
+        let mut engine = Engine::new();
+        assert_eq!(
+            engine.add_builtin_policies(&[
+                "attr_name_format".to_owned(),
+                "metric_duration_unit".to_owned(),
+                "registry_attr_prefix".to_owned()
+            ])?,
+            5
+        );
+        engine.set_input(&registry)?;
+        assert_eq!(engine.check(PolicyStage::BeforeResolution)?.len(), 4);
+
+        // Unknown built-in policies are reported.
+        let result = Engine::new().add_builtin_policies(&["unknown".to_owned()]);
+        assert!(matches!(result, Err(Error::UnknownBuiltinPolicy { .. })));
+
+        Ok(())
+    }
+
     #[test]
     fn test_add_policies_with_invalid_policies() {
         let mut engine = Engine::new();
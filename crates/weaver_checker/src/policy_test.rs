@@ -0,0 +1,234 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Run OPA-style policy tests.
+//!
+//! A policy test file is a `*_test.rego` file located next to the policies it
+//! tests. Each rule whose name starts with `test_` is a test case that passes
+//! when it evaluates to `true`.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use walkdir::WalkDir;
+
+use weaver_common::error::handle_errors;
+
+use crate::Error;
+
+/// Suffix of the policy test files.
+pub const POLICY_TEST_FILE_SUFFIX: &str = "_test.rego";
+
+/// Prefix of the rules defining a test case.
+const TEST_RULE_PREFIX: &str = "test_";
+
+/// Outcome of a policy test case.
+#[derive(Debug, Clone, PartialEq)]
+pub enum PolicyTestOutcome {
+    /// The test rule evaluated to `true`.
+    Passed,
+    /// The test rule evaluated to `false` or is undefined.
+    Failed,
+    /// The evaluation of the test rule failed.
+    Error(String),
+}
+
+/// Result of a policy test case.
+#[derive(Debug, Clone)]
+pub struct PolicyTestResult {
+    /// The test file defining the test case.
+    pub file: PathBuf,
+    /// The package of the test file (e.g. `data.before_resolution_test`).
+    pub package: String,
+    /// The name of the test rule.
+    pub name: String,
+    /// The outcome of the test case.
+    pub outcome: PolicyTestOutcome,
+}
+
+/// Returns true if the given path is a policy test file.
+#[must_use]
+pub fn is_policy_test_file(path: &Path) -> bool {
+    path.file_name()
+        .and_then(|name| name.to_str())
+        .is_some_and(|name| name.ends_with(POLICY_TEST_FILE_SUFFIX))
+}
+
+/// Returns the policy test files present in the given files or directories
+/// (directories are visited recursively).
+#[must_use]
+pub fn discover_policy_tests(paths: &[PathBuf]) -> Vec<PathBuf> {
+    let mut test_files: Vec<PathBuf> = paths
+        .iter()
+        .flat_map(|path| {
+            WalkDir::new(path)
+                .into_iter()
+                .filter_entry(|entry| {
+                    entry.depth() == 0 || !entry.file_name().to_string_lossy().starts_with('.')
+                })
+                .flatten()
+                .filter(|entry| entry.file_type().is_file() && is_policy_test_file(entry.path()))
+                .map(|entry| entry.into_path())
+        })
+        .collect();
+    test_files.sort();
+    test_files.dedup();
+    test_files
+}
+
+/// Discovers and runs the policy tests present in the given files or directories.
+///
+/// The test files of a directory are evaluated together with all the policy
+/// files of this directory.
+pub fn run_policy_tests(paths: &[PathBuf]) -> Result<Vec<PolicyTestResult>, Error> {
+    let mut test_files_by_dir: BTreeMap<PathBuf, Vec<PathBuf>> = BTreeMap::new();
+    for test_file in discover_policy_tests(paths) {
+        let dir = test_file
+            .parent()
+            .map(Path::to_path_buf)
+            .unwrap_or_default();
+        test_files_by_dir.entry(dir).or_default().push(test_file);
+    }
+
+    let mut results = vec![];
+    let mut errors = vec![];
+    for (dir, test_files) in test_files_by_dir {
+        match run_dir_policy_tests(&dir, &test_files) {
+            Ok(dir_results) => results.extend(dir_results),
+            Err(e) => errors.push(e),
+        }
+    }
+    handle_errors(errors)?;
+    Ok(results)
+}
+
+/// Runs the tests of the given test files, loaded with all the policy files of
+/// their directory.
+fn run_dir_policy_tests(
+    dir: &Path,
+    test_files: &[PathBuf],
+) -> Result<Vec<PolicyTestResult>, Error> {
+    let invalid_policy_file = |file: &Path, error: String| Error::InvalidPolicyFile {
+        file: file.to_string_lossy().to_string(),
+        error,
+    };
+
+    let mut policy_files: Vec<PathBuf> = fs::read_dir(dir)
+        .map_err(|e| invalid_policy_file(dir, e.to_string()))?
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.is_file() && path.extension().is_some_and(|ext| ext == "rego"))
+        .collect();
+    policy_files.sort();
+
+    let mut engine = regorus::Engine::new();
+    let mut packages = BTreeMap::new();
+    let mut errors = vec![];
+    for policy_file in &policy_files {
+        match engine.add_policy_from_file(policy_file) {
+            Ok(package) => _ = packages.insert(policy_file.clone(), package),
+            Err(e) => errors.push(invalid_policy_file(policy_file, e.to_string())),
+        }
+    }
+    handle_errors(errors)?;
+
+    let test_rules = test_rule_names(&engine).map_err(|e| invalid_policy_file(dir, e))?;
+
+    let mut results = vec![];
+    for test_file in test_files {
+        let package = packages.get(test_file).cloned().unwrap_or_default();
+        let names = test_rules
+            .get(test_file.to_string_lossy().as_ref())
+            .cloned()
+            .unwrap_or_default();
+        for name in names {
+            let outcome = match engine.eval_rule(format!("{}.{}", package, name)) {
+                Ok(regorus::Value::Bool(true)) => PolicyTestOutcome::Passed,
+                Ok(_) => PolicyTestOutcome::Failed,
+                Err(e) => PolicyTestOutcome::Error(e.to_string()),
+            };
+            results.push(PolicyTestResult {
+                file: test_file.clone(),
+                package: package.clone(),
+                name,
+                outcome,
+            });
+        }
+    }
+    Ok(results)
+}
+
+/// Returns the names of the test rules defined in each policy file loaded in
+/// the engine, in order of definition. A test rule is a rule (not a function)
+/// whose name starts with `test_`.
+fn test_rule_names(engine: &regorus::Engine) -> Result<BTreeMap<String, Vec<String>>, String> {
+    let ast = engine.get_ast_as_json().map_err(|e| e.to_string())?;
+    let policies: Vec<serde_json::Value> = serde_json::from_str(&ast).map_err(|e| e.to_string())?;
+
+    let mut test_rules: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    for policy in &policies {
+        let file = policy["source"]["file"].as_str().unwrap_or_default();
+        let names = test_rules.entry(file.to_owned()).or_default();
+        for rule in policy["ast"]["rules"].as_array().into_iter().flatten() {
+            let Some(name) = rule["Spec"]["head"]["Compr"]["refr"]["Var"][1].as_str() else {
+                continue;
+            };
+            if name.starts_with(TEST_RULE_PREFIX) && !names.iter().any(|n| n == name) {
+                names.push(name.to_owned());
+            }
+        }
+    }
+    Ok(test_rules)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::*;
+
+    #[test]
+    fn test_run_policy_tests() {
+        let results =
+            run_policy_tests(&[PathBuf::from("data/policy_tests")]).expect("Failed to run tests");
+        let outcomes: Vec<(&str, PolicyTestOutcome)> = results
+            .iter()
+            .map(|r| (r.name.as_str(), r.outcome.clone()))
+            .collect();
+        assert_eq!(
+            outcomes,
+            vec![
+                ("test_invalid_attr_name", PolicyTestOutcome::Passed),
+                ("test_valid_attr_name", PolicyTestOutcome::Passed),
+                ("test_failing", PolicyTestOutcome::Failed),
+            ]
+        );
+        assert!(results
+            .iter()
+            .all(|r| r.package == "data.before_resolution_test"));
+    }
+
+    #[test]
+    fn test_test_rule_names() {
+        let content = "package x_test\n\
+            import future.keywords.if\n\
+            test_a {\n  true\n}\n\
+            test_b if { true }\n\
+            test_a { false }\n\
+            helper_test_c { true }\n  test_d { true }\n\
+            test_e(x) { x }\n";
+        let mut engine = regorus::Engine::new();
+        _ = engine
+            .add_policy("x_test.rego".to_owned(), content.to_owned())
+            .expect("Failed to add policy");
+        let test_rules = test_rule_names(&engine).expect("Failed to get the test rules");
+        assert_eq!(
+            test_rules.get("x_test.rego"),
+            Some(&vec![
+                "test_a".to_owned(),
+                "test_b".to_owned(),
+                "test_d".to_owned()
+            ])
+        );
+    }
+}
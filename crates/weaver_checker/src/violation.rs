@@ -21,6 +21,15 @@ pub enum Violation {
         /// The semconv attribute where the violation occurred.
         attr: String,
     },
+    /// An advice on the attribute observed by a live check.
+    Advice {
+        /// The ID of the advice.
+        id: String,
+        /// The level of the advice (`information`, `improvement`, or `violation`).
+        level: String,
+        /// The remediation advice.
+        message: String,
+    },
 }
 
 impl Display for Violation {
@@ -38,6 +47,9 @@ impl Display for Violation {
                     id, category, group, attr
                 )
             }
+            Violation::Advice { id, level, message } => {
+                write!(f, "id={}, level={}, message={}", id, level, message)
+            }
         }
     }
 }
@@ -47,7 +59,7 @@ impl Violation {
     #[must_use]
     pub fn id(&self) -> &str {
         match self {
-            Violation::SemconvAttribute { id, .. } => id,
+            Violation::SemconvAttribute { id, .. } | Violation::Advice { id, .. } => id,
         }
     }
 }
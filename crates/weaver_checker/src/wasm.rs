@@ -0,0 +1,207 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Policies compiled to WebAssembly.
+//!
+//! A WASM policy is a module, written in any language compiled to WebAssembly,
+//! that checks the resolved registry. The module must export:
+//! - `memory`: the linear memory of the module,
+//! - `alloc(len: i32) -> i32`: allocates `len` bytes in the linear memory and
+//!   returns a pointer to them,
+//! - `check(ptr: i32, len: i32) -> i64`: checks the resolved registry serialized
+//!   in JSON at `ptr` (`len` bytes) and returns the location of a JSON array of
+//!   violations packed in an `i64` (pointer in the high 32 bits, length in the
+//!   low 32 bits).
+//!
+//! The violations have the same format as the violations produced by the Rego
+//! policies.
+//!
+//! The execution of a module is bounded by a fuel budget (roughly one unit per
+//! executed instruction), so a module that doesn't terminate fails the check
+//! instead of blocking Weaver.
+
+use std::path::Path;
+
+use wasmi::core::TrapCode;
+use wasmi::{Config, Engine, Linker, Module, Store};
+
+use crate::violation::Violation;
+use crate::Error;
+
+/// Default fuel budget of a check, i.e. the maximum number of units of fuel
+/// consumed by the execution of a WASM policy.
+pub const DEFAULT_WASM_FUEL: u64 = 10_000_000_000;
+
+/// A policy compiled to a WebAssembly module.
+#[derive(Debug)]
+pub struct WasmPolicy {
+    /// Path of the WASM module (used as provenance in the error messages).
+    file: String,
+    /// The WASM engine used to compile the module.
+    engine: Engine,
+    /// The compiled module.
+    module: Module,
+}
+
+impl WasmPolicy {
+    /// Loads and compiles a WASM policy module.
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, Error> {
+        let file = path.as_ref().to_string_lossy().to_string();
+        let wasm = std::fs::read(path.as_ref()).map_err(|e| Error::InvalidWasmPolicy {
+            file: file.clone(),
+            error: e.to_string(),
+        })?;
+        Self::from_bytes(file, &wasm)
+    }
+
+    /// Compiles a WASM policy module from its binary representation.
+    pub fn from_bytes(file: String, wasm: &[u8]) -> Result<Self, Error> {
+        let mut config = Config::default();
+        _ = config.consume_fuel(true);
+        let engine = Engine::new(&config);
+        let module = Module::new(&engine, wasm).map_err(|e| Error::InvalidWasmPolicy {
+            file: file.clone(),
+            error: e.to_string(),
+        })?;
+        Ok(Self {
+            file,
+            engine,
+            module,
+        })
+    }
+
+    /// Returns the path of the WASM module.
+    #[must_use]
+    pub fn file(&self) -> &str {
+        &self.file
+    }
+
+    /// Checks the given input (usually the resolved registry serialized in JSON)
+    /// and returns the violations detected by the module.
+    ///
+    /// A new instance of the module is created for each check.
+    pub fn check(&self, input: &str) -> Result<Vec<Violation>, Error> {
+        self.check_with_fuel(input, DEFAULT_WASM_FUEL)
+    }
+
+    /// Checks the given input like [`WasmPolicy::check`], with at most `fuel`
+    /// units of fuel consumed by the module.
+    pub fn check_with_fuel(&self, input: &str, fuel: u64) -> Result<Vec<Violation>, Error> {
+        let evaluation_error = |error: wasmi::Error| match &error {
+            wasmi::Error::Trap(trap) if matches!(trap.trap_code(), Some(TrapCode::OutOfFuel)) => {
+                Error::WasmPolicyFuelExhausted {
+                    file: self.file.clone(),
+                    fuel,
+                }
+            }
+            _ => Error::ViolationEvaluationError {
+                error: format!("WASM policy '{}': {}", self.file, error),
+            },
+        };
+        let invalid_output = |error: String| Error::ViolationEvaluationError {
+            error: format!("WASM policy '{}': {}", self.file, error),
+        };
+
+        let mut store = Store::new(&self.engine, ());
+        store
+            .add_fuel(fuel)
+            .map_err(|e| evaluation_error(e.into()))?;
+        let linker = <Linker<()>>::new(&self.engine);
+        let instance = linker
+            .instantiate(&mut store, &self.module)
+            .and_then(|instance| instance.start(&mut store))
+            .map_err(evaluation_error)?;
+        let memory = instance
+            .get_memory(&store, "memory")
+            .ok_or_else(|| invalid_output("missing `memory` export".to_owned()))?;
+        let alloc = instance
+            .get_typed_func::<i32, i32>(&store, "alloc")
+            .map_err(|e| invalid_output(format!("invalid `alloc` export, {}", e)))?;
+        let check = instance
+            .get_typed_func::<(i32, i32), i64>(&store, "check")
+            .map_err(|e| invalid_output(format!("invalid `check` export, {}", e)))?;
+
+        let input_len =
+            i32::try_from(input.len()).map_err(|_| invalid_output("input too large".to_owned()))?;
+        let input_ptr = alloc
+            .call(&mut store, input_len)
+            .map_err(|e| evaluation_error(e.into()))?;
+        memory
+            .write(&mut store, input_ptr as u32 as usize, input.as_bytes())
+            .map_err(|e| invalid_output(e.to_string()))?;
+
+        let output = check
+            .call(&mut store, (input_ptr, input_len))
+            .map_err(|e| evaluation_error(e.into()))?;
+        let output_ptr = (output as u64 >> 32) as usize;
+        let output_len = (output as u64 & 0xFFFF_FFFF) as usize;
+        let mut violations = vec![0; output_len];
+        memory
+            .read(&store, output_ptr, &mut violations)
+            .map_err(|e| invalid_output(e.to_string()))?;
+
+        serde_json::from_slice(&violations).map_err(|e| invalid_output(e.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wasm_policy() {
+        // The checked-in module is compiled from its text representation.
+        assert_eq!(
+            wat::parse_file("data/wasm/policy.wat").expect("Failed to parse WAT"),
+            std::fs::read("data/wasm/policy.wasm").expect("Failed to read WASM module")
+        );
+
+        let policy =
+            WasmPolicy::load("data/wasm/policy.wasm").expect("Failed to load the WASM policy");
+        let violations = policy
+            .check(&format!(
+                r#"{{"groups": [], "padding": "{}"}}"#,
+                "x".repeat(100_000)
+            ))
+            .expect("Failed to check the input");
+        assert_eq!(
+            violations,
+            vec![Violation::SemconvAttribute {
+                id: "wasm_violation".to_owned(),
+                category: "wasm".to_owned(),
+                group: "registry.http".to_owned(),
+                attr: "http.method".to_owned(),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_invalid_wasm_policy() {
+        let result = WasmPolicy::from_bytes("policy.wasm".to_owned(), b"not a wasm module");
+        assert!(matches!(result, Err(Error::InvalidWasmPolicy { .. })));
+
+        // A module without the expected exports.
+        let wasm = wat::parse_str("(module)").expect("Failed to parse WAT");
+        let policy = WasmPolicy::from_bytes("policy.wasm".to_owned(), &wasm)
+            .expect("Failed to load the WASM policy");
+        assert!(policy.check("{}").is_err());
+    }
+
+    #[test]
+    fn test_wasm_policy_fuel_exhausted() {
+        let wasm = wat::parse_str(
+            r#"(module
+                (memory (export "memory") 1)
+                (func (export "alloc") (param i32) (result i32) (i32.const 0))
+                (func (export "check") (param i32 i32) (result i64)
+                    (loop $forever (br $forever))
+                    (i64.const 0)))"#,
+        )
+        .expect("Failed to parse WAT");
+        let policy = WasmPolicy::from_bytes("policy.wasm".to_owned(), &wasm)
+            .expect("Failed to load the WASM policy");
+        assert!(matches!(
+            policy.check_with_fuel("{}", 10_000),
+            Err(Error::WasmPolicyFuelExhausted { fuel: 10_000, .. })
+        ));
+    }
+}
@@ -0,0 +1,191 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Cooperative cancellation of long-running operations.
+//!
+//! A [`CancellationToken`] is shared between the caller of an operation (e.g.
+//! a server or a language server aborting a stale request) and the operation
+//! itself, which checks the token at regular intervals and stops with a
+//! `Cancelled` error once the token is cancelled or its deadline is reached.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{channel, RecvTimeoutError};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// A token used to cancel an operation, optionally after a timeout.
+///
+/// Clones of a token share the same cancellation state. A default token is
+/// never cancelled unless [`CancellationToken::cancel`] is called. A child
+/// token created by [`CancellationToken::with_timeout`] is cancelled with its
+/// parent, but its own timeout or cancellation doesn't affect the parent.
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken {
+    /// Set once the token is cancelled or its deadline is reached.
+    cancelled: Arc<AtomicBool>,
+    /// The instant after which the token is considered cancelled.
+    deadline: Option<Instant>,
+    /// The token this token has been derived from, if any.
+    parent: Option<Box<CancellationToken>>,
+}
+
+/// The interval at which the cancellation of the parent of a token is checked
+/// while running an interruptible function.
+const WATCH_INTERVAL: Duration = Duration::from_millis(50);
+
+/// The error returned by [`CancellationToken::check`] when the token is
+/// cancelled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cancelled;
+
+impl CancellationToken {
+    /// Creates a token that is only cancelled by [`CancellationToken::cancel`].
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns a child of this token with a deadline. The child is cancelled
+    /// once the given timeout has elapsed or this token is cancelled, its
+    /// timeout doesn't cancel this token.
+    #[must_use]
+    pub fn with_timeout(&self, timeout: Duration) -> Self {
+        let deadline = Instant::now() + timeout;
+        Self {
+            cancelled: Arc::default(),
+            deadline: Some(self.deadline.map_or(deadline, |d| d.min(deadline))),
+            parent: Some(Box::new(self.clone())),
+        }
+    }
+
+    /// Cancels the token, all its clones, and its children.
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::SeqCst);
+    }
+
+    /// Returns the deadline of the token, if any.
+    #[must_use]
+    pub fn deadline(&self) -> Option<Instant> {
+        self.deadline
+    }
+
+    /// Returns true if the token has been cancelled or its deadline has been
+    /// reached.
+    #[must_use]
+    pub fn is_cancelled(&self) -> bool {
+        if self.cancelled.load(Ordering::SeqCst) {
+            return true;
+        }
+        if self.deadline.is_some_and(|d| Instant::now() >= d)
+            || self.parent.as_ref().is_some_and(|p| p.is_cancelled())
+        {
+            self.cancel();
+            return true;
+        }
+        false
+    }
+
+    /// Returns an error if the token has been cancelled or its deadline has
+    /// been reached.
+    pub fn check(&self) -> Result<(), Cancelled> {
+        if self.is_cancelled() {
+            Err(Cancelled)
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Runs the given function with an interrupt flag set as soon as the
+    /// token is cancelled or its deadline is reached. This is intended for
+    /// libraries polling an `AtomicBool` (e.g. `gix`) instead of a token.
+    pub fn run_interruptible<R>(&self, f: impl FnOnce(&AtomicBool) -> R) -> R {
+        if self.parent.is_none() {
+            return f(&self.cancelled);
+        }
+        // A watcher thread sets the flag when the deadline is reached or the
+        // parent is cancelled, unless the function completes first (the
+        // channel is then disconnected).
+        let (done, wait) = channel::<()>();
+        std::thread::scope(|scope| {
+            _ = scope.spawn(move || {
+                while !self.is_cancelled() {
+                    let timeout = self.deadline.map_or(WATCH_INTERVAL, |d| {
+                        d.saturating_duration_since(Instant::now())
+                            .min(WATCH_INTERVAL)
+                    });
+                    if let Err(RecvTimeoutError::Disconnected) = wait.recv_timeout(timeout) {
+                        return;
+                    }
+                }
+            });
+            let result = f(&self.cancelled);
+            drop(done);
+            result
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cancel() {
+        let token = CancellationToken::new();
+        let clone = token.clone();
+        assert!(!token.is_cancelled());
+        assert_eq!(token.check(), Ok(()));
+
+        clone.cancel();
+        assert!(token.is_cancelled());
+        assert_eq!(token.check(), Err(Cancelled));
+    }
+
+    #[test]
+    fn test_timeout() {
+        let token = CancellationToken::new();
+        let with_timeout = token.with_timeout(Duration::from_millis(200));
+        assert!(!with_timeout.is_cancelled());
+        assert!(with_timeout.deadline().is_some());
+
+        let interrupted = with_timeout.run_interruptible(|flag| {
+            let start = Instant::now();
+            while !flag.load(Ordering::SeqCst) {
+                if start.elapsed() > Duration::from_secs(10) {
+                    return false;
+                }
+                std::thread::sleep(Duration::from_millis(5));
+            }
+            true
+        });
+        assert!(interrupted);
+        assert!(with_timeout.is_cancelled());
+        // The timeout of a child doesn't cancel its parent or its clones.
+        assert!(!token.is_cancelled());
+        assert!(!token.clone().is_cancelled());
+
+        // Completing before the deadline doesn't cancel the token.
+        let token = CancellationToken::new().with_timeout(Duration::from_secs(60));
+        assert_eq!(token.run_interruptible(|_| 42), 42);
+        assert!(!token.is_cancelled());
+    }
+
+    #[test]
+    fn test_parent_cancellation() {
+        let parent = CancellationToken::new();
+        let child = parent.with_timeout(Duration::from_secs(60));
+        let interrupted = child.run_interruptible(|flag| {
+            parent.cancel();
+            let start = Instant::now();
+            while !flag.load(Ordering::SeqCst) {
+                if start.elapsed() > Duration::from_secs(10) {
+                    return false;
+                }
+                std::thread::sleep(Duration::from_millis(5));
+            }
+            true
+        });
+        assert!(interrupted);
+        assert!(child.is_cancelled());
+        assert_eq!(child.check(), Err(Cancelled));
+    }
+}
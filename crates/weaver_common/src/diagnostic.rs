@@ -38,6 +38,71 @@ pub struct MietteDiagnosticExt {
     pub labels: Option<Vec<LabeledSpan>>,
 }
 
+/// The fields of the serialized errors naming the file a diagnostic message
+/// relates to, by order of precedence.
+const FILE_FIELDS: [&str; 5] = ["provenance", "path_or_url", "source_file", "path", "file"];
+
+/// The fields of the serialized errors naming the other files a diagnostic
+/// message relates to (e.g. the file defining the item a definition collides
+/// with).
+const RELATED_FILE_FIELDS: [&str; 1] = ["other_provenance"];
+
+/// The location, in a file, of the item a diagnostic message relates to.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DiagnosticLocation {
+    /// The path or URL of the file
+    pub file: String,
+    /// The line in the file (starting at 1), if known
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub line: Option<usize>,
+    /// The column in the line (starting at 1), if known
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub column: Option<usize>,
+}
+
+impl DiagnosticLocation {
+    /// Returns the location found in the fields of a serialized error, looked
+    /// up in the error and then in its nested errors.
+    fn from_error(error: &serde_json::Value) -> Option<Self> {
+        let serde_json::Value::Object(object) = error else {
+            return None;
+        };
+        let position = |field: &str| {
+            object
+                .get(field)
+                .and_then(serde_json::Value::as_u64)
+                .and_then(|n| usize::try_from(n).ok())
+        };
+        FILE_FIELDS
+            .iter()
+            .find_map(|field| object.get(*field)?.as_str().filter(|s| !s.is_empty()))
+            .map(|file| Self {
+                file: file.to_owned(),
+                line: position("line"),
+                column: position("column"),
+            })
+            .or_else(|| object.values().find_map(Self::from_error))
+    }
+
+    /// Returns the related locations found in the fields of a serialized
+    /// error and of its nested errors.
+    fn related_from_error(error: &serde_json::Value) -> Vec<Self> {
+        let serde_json::Value::Object(object) = error else {
+            return vec![];
+        };
+        RELATED_FILE_FIELDS
+            .iter()
+            .filter_map(|field| object.get(*field)?.as_str().filter(|s| !s.is_empty()))
+            .map(|file| Self {
+                file: file.to_owned(),
+                line: None,
+                column: None,
+            })
+            .chain(object.values().flat_map(Self::related_from_error))
+            .collect()
+    }
+}
+
 /// A generic and serializable representation of a diagnostic message
 #[derive(Debug, serde::Serialize, Clone)]
 pub struct DiagnosticMessage {
@@ -45,6 +110,15 @@ pub struct DiagnosticMessage {
     pub(crate) error: serde_json::Value,
     /// The diagnostic message
     pub(crate) diagnostic: MietteDiagnosticExt,
+    /// The location of the item the diagnostic message relates to, if known
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) location: Option<DiagnosticLocation>,
+    /// The locations of the other items the diagnostic message relates to
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub(crate) related_locations: Vec<DiagnosticLocation>,
+    /// The owners of the item the diagnostic message relates to, if known
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub(crate) owners: Vec<String>,
 }
 
 /// A list of diagnostic messages
@@ -73,10 +147,38 @@ impl DiagnosticMessage {
             labels,
         };
         Self {
+            location: DiagnosticLocation::from_error(&json_error),
+            related_locations: DiagnosticLocation::related_from_error(&json_error),
             error: json_error,
             diagnostic,
+            owners: Vec::new(),
         }
     }
+
+    /// Returns the serialized error
+    #[must_use]
+    pub fn error(&self) -> &serde_json::Value {
+        &self.error
+    }
+
+    /// Returns the location of the item the diagnostic message relates to
+    #[must_use]
+    pub fn location(&self) -> Option<&DiagnosticLocation> {
+        self.location.as_ref()
+    }
+
+    /// Returns the locations of the other items the diagnostic message
+    /// relates to
+    #[must_use]
+    pub fn related_locations(&self) -> &[DiagnosticLocation] {
+        &self.related_locations
+    }
+
+    /// Returns the owners of the item the diagnostic message relates to
+    #[must_use]
+    pub fn owners(&self) -> &[String] {
+        &self.owners
+    }
 }
 
 impl DiagnosticMessages {
@@ -105,6 +207,19 @@ impl DiagnosticMessages {
             .for_each(|msg| logger.error(&msg.diagnostic.message));
     }
 
+    /// Sets the owners of each diagnostic message, computed from its serialized
+    /// error
+    pub fn assign_owners(&mut self, owners_of: impl Fn(&serde_json::Value) -> Vec<String>) {
+        for msg in &mut self.0 {
+            msg.owners = owners_of(&msg.error);
+        }
+    }
+
+    /// Retains only the diagnostic messages matching the predicate
+    pub fn retain(&mut self, predicate: impl FnMut(&DiagnosticMessage) -> bool) {
+        self.0.retain(predicate);
+    }
+
     /// Returns the number of diagnostic messages
     #[must_use]
     pub fn len(&self) -> usize {
@@ -191,7 +306,7 @@ where
 #[cfg(test)]
 mod tests {
     use super::*;
-    use miette::{diagnostic, Diagnostic};
+    use miette::Diagnostic;
 
     #[derive(thiserror::Error, Debug, Clone, Diagnostic, Serialize)]
     #[error("This is a test error")]
@@ -284,4 +399,99 @@ mod tests {
             Some("https://example.com".to_owned())
         );
     }
+
+    #[derive(thiserror::Error, Debug, Diagnostic, Serialize)]
+    enum LocatedError {
+        #[error("Invalid spec")]
+        InvalidSpec {
+            path_or_url: String,
+            line: Option<usize>,
+            column: Option<usize>,
+        },
+        #[error("Policy violation")]
+        PolicyViolation {
+            provenance: String,
+            violation: serde_json::Value,
+        },
+        #[error("Collision")]
+        Collision {
+            provenance: String,
+            other_provenance: String,
+        },
+        #[error("Nested error")]
+        Nested { error: Box<LocatedError> },
+    }
+
+    #[test]
+    fn test_diagnostic_message_location() {
+        let message = DiagnosticMessage::new(LocatedError::InvalidSpec {
+            path_or_url: "model/http.yaml".to_owned(),
+            line: Some(12),
+            column: Some(5),
+        });
+        assert_eq!(
+            message.location(),
+            Some(&DiagnosticLocation {
+                file: "model/http.yaml".to_owned(),
+                line: Some(12),
+                column: Some(5),
+            })
+        );
+
+        let message = DiagnosticMessage::new(LocatedError::Nested {
+            error: Box::new(LocatedError::PolicyViolation {
+                provenance: "model/db.yaml".to_owned(),
+                violation: serde_json::json!({"id": "attr_name", "line": "not a number"}),
+            }),
+        });
+        let location = message.location().expect("A location is expected");
+        assert_eq!(location.file, "model/db.yaml");
+        assert_eq!(location.line, None);
+
+        let message = DiagnosticMessage::new(TestError {
+            message: "No location".to_owned(),
+        });
+        assert_eq!(message.location(), None);
+        let json = serde_json::to_value(&message).expect("Failed to serialize");
+        assert!(json.get("location").is_none());
+        assert!(json.get("related_locations").is_none());
+
+        let message = DiagnosticMessage::new(LocatedError::Nested {
+            error: Box::new(LocatedError::Collision {
+                provenance: "model/legacy.yaml".to_owned(),
+                other_provenance: "model/http.yaml".to_owned(),
+            }),
+        });
+        assert_eq!(
+            message.location().map(|location| location.file.as_str()),
+            Some("model/legacy.yaml")
+        );
+        let related: Vec<_> = message
+            .related_locations()
+            .iter()
+            .map(|location| location.file.as_str())
+            .collect();
+        assert_eq!(related, ["model/http.yaml"]);
+    }
+
+    #[test]
+    fn test_diagnostic_message_owners() {
+        let errors = ["http", "db"].map(|message| TestError {
+            message: message.to_owned(),
+        });
+        let mut diagnostic_messages = DiagnosticMessages::from_errors(errors.to_vec());
+        diagnostic_messages.assign_owners(|error| match error["message"].as_str() {
+            Some("http") => vec!["@org/http-team".to_owned()],
+            _ => vec![],
+        });
+        assert_eq!(diagnostic_messages.0[0].owners(), ["@org/http-team"]);
+        assert!(diagnostic_messages.0[1].owners().is_empty());
+        let json = serde_json::to_value(&diagnostic_messages).expect("Failed to serialize");
+        assert_eq!(json[0]["owners"][0], "@org/http-team");
+        assert!(json[1].get("owners").is_none());
+
+        diagnostic_messages.retain(|msg| !msg.owners().is_empty());
+        assert_eq!(diagnostic_messages.len(), 1);
+        assert_eq!(diagnostic_messages.0[0].error()["message"], "http");
+    }
 }
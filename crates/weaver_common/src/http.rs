@@ -0,0 +1,167 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! HTTP agents honoring the proxy and certificate settings of the environment.
+//!
+//! The agents use the proxy defined by the `HTTPS_PROXY`, `HTTP_PROXY` or
+//! `ALL_PROXY` environment variables (or their lowercase variants), except for
+//! the hosts listed in `NO_PROXY`. When `SSL_CERT_FILE` or `SSL_CERT_DIR` is
+//! set, the certificates it contains (e.g. the CA of a corporate network) are
+//! trusted in addition to the built-in root certificates.
+
+use std::sync::{Arc, OnceLock};
+
+/// The environment variable containing a bundle of CA certificates (PEM).
+pub const CA_FILE_ENV: &str = "SSL_CERT_FILE";
+
+/// The environment variable containing a directory of CA certificates.
+pub const CA_DIR_ENV: &str = "SSL_CERT_DIR";
+
+/// Returns an agent builder configured for the given URL.
+///
+/// Returns an error if the proxy or the CA certificates of the environment
+/// are invalid.
+pub fn agent_builder(url: &str) -> Result<ureq::AgentBuilder, String> {
+    let mut builder = ureq::AgentBuilder::new();
+    if let Some(proxy) = proxy_for(url, &|name| std::env::var(name).ok()) {
+        let proxy =
+            ureq::Proxy::new(&proxy).map_err(|e| format!("Invalid proxy `{proxy}`: {e}"))?;
+        builder = builder.proxy(proxy);
+    }
+    if let Some(tls_config) = tls_config()? {
+        builder = builder.tls_config(tls_config);
+    }
+    Ok(builder)
+}
+
+/// Returns an agent configured for the given URL.
+pub fn agent(url: &str) -> Result<ureq::Agent, String> {
+    agent_builder(url).map(ureq::AgentBuilder::build)
+}
+
+/// Returns the proxy to use for the given URL, if any.
+///
+/// # Arguments
+///
+/// * `url` - The URL to fetch.
+/// * `env` - The function reading the environment variables.
+fn proxy_for(url: &str, env: &dyn Fn(&str) -> Option<String>) -> Option<String> {
+    let var = |name: &str| {
+        env(name)
+            .or_else(|| env(&name.to_lowercase()))
+            .filter(|value| !value.trim().is_empty())
+    };
+    let (scheme, rest) = url.split_once("://")?;
+    let authority = rest.split(['/', '?', '#']).next().unwrap_or_default();
+    let host_port = authority.rsplit('@').next().unwrap_or(authority);
+    let host = if let Some(ipv6) = host_port.strip_prefix('[') {
+        ipv6.split(']').next().unwrap_or_default()
+    } else {
+        host_port.split(':').next().unwrap_or_default()
+    };
+    if var("NO_PROXY").is_some_and(|no_proxy| no_proxy_matches(host, &no_proxy)) {
+        return None;
+    }
+    match scheme {
+        "https" => var("HTTPS_PROXY").or_else(|| var("ALL_PROXY")),
+        "http" => var("HTTP_PROXY").or_else(|| var("ALL_PROXY")),
+        _ => var("ALL_PROXY"),
+    }
+}
+
+/// Returns true if the host is excluded from proxying by the `NO_PROXY`
+/// list, i.e. the list contains `*`, the host itself, or one of its parent
+/// domains (with or without a leading `.`).
+fn no_proxy_matches(host: &str, no_proxy: &str) -> bool {
+    let host = host.to_ascii_lowercase();
+    no_proxy
+        .split(',')
+        .map(|entry| entry.trim().to_ascii_lowercase())
+        .filter(|entry| !entry.is_empty())
+        .any(|entry| {
+            let domain = entry.trim_start_matches("*.").trim_start_matches('.');
+            entry == "*"
+                || host == domain
+                || host
+                    .strip_suffix(domain)
+                    .is_some_and(|prefix| prefix.ends_with('.'))
+        })
+}
+
+/// Returns the TLS configuration trusting the built-in root certificates and
+/// the certificates of `SSL_CERT_FILE` or `SSL_CERT_DIR`, if one of them is
+/// set. The configuration is loaded once.
+fn tls_config() -> Result<Option<Arc<rustls::ClientConfig>>, String> {
+    static TLS_CONFIG: OnceLock<Result<Option<Arc<rustls::ClientConfig>>, String>> =
+        OnceLock::new();
+    TLS_CONFIG
+        .get_or_init(|| {
+            if std::env::var_os(CA_FILE_ENV).is_none() && std::env::var_os(CA_DIR_ENV).is_none() {
+                return Ok(None);
+            }
+            let mut roots = rustls::RootCertStore {
+                roots: webpki_roots::TLS_SERVER_ROOTS.to_vec(),
+            };
+            let certificates = rustls_native_certs::load_native_certs().map_err(|e| {
+                format!("Failed to load the certificates of `{CA_FILE_ENV}`/`{CA_DIR_ENV}`: {e}")
+            })?;
+            let (valid, _) = roots.add_parsable_certificates(certificates);
+            if valid == 0 {
+                return Err(format!(
+                    "No valid certificate found in `{CA_FILE_ENV}`/`{CA_DIR_ENV}`"
+                ));
+            }
+            Ok(Some(Arc::new(
+                rustls::ClientConfig::builder()
+                    .with_root_certificates(roots)
+                    .with_no_client_auth(),
+            )))
+        })
+        .clone()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+
+    #[test]
+    fn test_proxy_for() {
+        let vars = HashMap::from([
+            ("HTTPS_PROXY", "http://proxy.acme.com:3128"),
+            ("http_proxy", "http://proxy.acme.com:8080"),
+            ("NO_PROXY", "localhost, .internal.acme.com,10.0.0.1"),
+        ]);
+        let env = |name: &str| vars.get(name).map(|value| (*value).to_owned());
+
+        assert_eq!(
+            proxy_for("https://github.com/acme/semconv.git", &env).as_deref(),
+            Some("http://proxy.acme.com:3128")
+        );
+        assert_eq!(
+            proxy_for("http://example.com/registry.yaml", &env).as_deref(),
+            Some("http://proxy.acme.com:8080")
+        );
+        assert_eq!(proxy_for("http://localhost:5000/v2/", &env), None);
+        assert_eq!(
+            proxy_for("https://user@git.internal.acme.com/semconv", &env),
+            None
+        );
+        assert_eq!(proxy_for("https://internal.acme.com/semconv", &env), None);
+        assert_eq!(proxy_for("http://10.0.0.1:8080/", &env), None);
+        assert_eq!(
+            proxy_for("https://notinternal.acme.com/semconv", &env).as_deref(),
+            Some("http://proxy.acme.com:3128")
+        );
+        assert_eq!(proxy_for("https://github.com", &|_| None), None);
+    }
+
+    #[test]
+    fn test_no_proxy_matches() {
+        assert!(no_proxy_matches("anything.com", "*"));
+        assert!(no_proxy_matches("ghcr.io", "example.com,GHCR.io"));
+        assert!(no_proxy_matches("a.b.example.com", "*.example.com"));
+        assert!(!no_proxy_matches("badexample.com", "example.com"));
+        assert!(!no_proxy_matches("ghcr.io", ""));
+    }
+}
@@ -5,7 +5,7 @@
 use similar::TextDiff;
 use std::collections::HashSet;
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use walkdir::WalkDir;
 
 const GREEN: &str = "\x1b[32m";
@@ -51,7 +51,7 @@ pub fn diff_dir<P: AsRef<Path>>(expected_dir: P, observed_dir: P) -> std::io::Re
         if path.is_file() {
             let relative_path = path
                 .strip_prefix(&expected_dir)
-                .map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e))?;
+                .map_err(std::io::Error::other)?;
             _ = expected_files.insert(relative_path.to_path_buf());
         }
     }
@@ -65,7 +65,7 @@ pub fn diff_dir<P: AsRef<Path>>(expected_dir: P, observed_dir: P) -> std::io::Re
         if path.is_file() {
             let relative_path = path
                 .strip_prefix(&observed_dir)
-                .map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e))?;
+                .map_err(std::io::Error::other)?;
             _ = observed_files.insert(relative_path.to_path_buf());
         }
     }
@@ -117,6 +117,71 @@ pub fn diff_dir<P: AsRef<Path>>(expected_dir: P, observed_dir: P) -> std::io::Re
     Ok(are_identical)
 }
 
+/// Constructs a unified diff of the original vs. updated content, using the given
+/// file names in the header of the diff. Returns an empty string if the contents
+/// are identical.
+#[must_use]
+pub fn unified_diff(
+    original: &str,
+    updated: &str,
+    original_name: &str,
+    updated_name: &str,
+) -> String {
+    if original == updated {
+        return String::new();
+    }
+    TextDiff::from_lines(original, updated)
+        .unified_diff()
+        .header(original_name, updated_name)
+        .to_string()
+}
+
+/// Returns the unified diffs between the files of `updated_dir` and the files with
+/// the same relative path in `original_dir`, sorted by relative path. A file missing
+/// from `original_dir` is considered empty, and files only present in `original_dir`
+/// are ignored. Identical files are not reported.
+pub fn unified_diff_dir<P: AsRef<Path>>(
+    original_dir: P,
+    updated_dir: P,
+) -> std::io::Result<Vec<(PathBuf, String)>> {
+    let mut diffs = Vec::new();
+
+    for entry in WalkDir::new(&updated_dir)
+        .sort_by_file_name()
+        .into_iter()
+        .filter_map(|e| e.ok())
+    {
+        let path = entry.path();
+        if !path.is_file() {
+            continue;
+        }
+        let relative_path = path
+            .strip_prefix(&updated_dir)
+            .map_err(std::io::Error::other)?;
+        let original_path = original_dir.as_ref().join(relative_path);
+        let (original, original_name) = if original_path.is_file() {
+            (
+                fs::read_to_string(&original_path)?.replace("\r\n", "\n"),
+                original_path.display().to_string(),
+            )
+        } else {
+            (String::new(), "/dev/null".to_owned())
+        };
+        let updated = fs::read_to_string(path)?.replace("\r\n", "\n");
+        let diff = unified_diff(
+            &original,
+            &updated,
+            &original_name,
+            &original_path.display().to_string(),
+        );
+        if !diff.is_empty() {
+            diffs.push((relative_path.to_path_buf(), diff));
+        }
+    }
+
+    Ok(diffs)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -148,4 +213,25 @@ mod tests {
             diff_dir(&expected_dir, &observed_dir).expect("Failed to diff directories");
         assert!(!are_identical);
     }
+
+    #[test]
+    fn test_unified_diff() {
+        assert_eq!(unified_diff("a\n", "a\n", "a.txt", "a.txt"), "");
+        assert_eq!(
+            unified_diff("a\nb\n", "a\nc\n", "old/a.txt", "new/a.txt"),
+            "--- old/a.txt\n+++ new/a.txt\n@@ -1,2 +1,2 @@\n a\n-b\n+c\n"
+        );
+    }
+
+    #[test]
+    fn test_unified_diff_dir() {
+        let diffs = unified_diff_dir("./src", "./src").expect("Failed to diff directories");
+        assert!(diffs.is_empty());
+
+        let diffs =
+            unified_diff_dir("./does-not-exist", "./src").expect("Failed to diff directories");
+        assert_eq!(diffs.len(), 1);
+        assert_eq!(diffs[0].0, Path::new("lib.rs"));
+        assert!(diffs[0].1.starts_with("--- /dev/null\n"));
+    }
 }
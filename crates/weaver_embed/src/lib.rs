@@ -0,0 +1,629 @@
+// SPDX-License-Identifier: Apache-2.0
+
+#![doc = include_str!("../README.md")]
+
+use std::collections::BTreeMap;
+
+use miette::Diagnostic;
+use serde::Serialize;
+
+use weaver_cache::Cache;
+use weaver_common::cancellation::CancellationToken;
+use weaver_forge::filter::Filter;
+use weaver_resolver::file_filter::RegistryFileFilter;
+use weaver_resolver::{ResolverOptions, SchemaResolver};
+use weaver_semconv::path::RegistryPath;
+use weaver_semconv::registry::SemConvRegistry;
+
+pub use weaver_forge::registry::{ResolvedGroup, ResolvedRegistry};
+pub use weaver_resolved_schema::attribute::Attribute;
+pub use weaver_semconv::group::GroupType;
+
+/// Id of the registry merging all the loaded registries.
+const REGISTRY_ID: &str = "default";
+
+/// An error that can occur while loading, resolving, querying, or serializing
+/// a registry.
+#[derive(thiserror::Error, Debug, Clone, PartialEq, Serialize, Diagnostic)]
+#[non_exhaustive]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Error {
+    /// No registry was specified.
+    #[error("No registry to load, specify a local path or a Git URL.")]
+    NoRegistry,
+
+    /// An include or exclude pattern is invalid.
+    #[error("Invalid file pattern, error: {error}")]
+    InvalidFilePattern {
+        /// The error that occurred.
+        error: String,
+    },
+
+    /// The cache of the Git registries could not be created.
+    #[error("The registry cache is unavailable, error: {error}")]
+    CacheUnavailable {
+        /// The error that occurred.
+        error: String,
+    },
+
+    /// The files of a registry could not be loaded.
+    #[error("Failed to load the registry `{registry}`:\n{}", .errors.join("\n"))]
+    LoadFailed {
+        /// The local path or the Git URL of the registry.
+        registry: String,
+        /// The errors that occurred.
+        errors: Vec<String>,
+    },
+
+    /// The registry could not be resolved.
+    #[error("Failed to resolve the registry:\n{}", .errors.join("\n"))]
+    ResolutionFailed {
+        /// The errors that occurred.
+        errors: Vec<String>,
+    },
+
+    /// A jq query is invalid or failed.
+    #[error("The query `{query}` failed, error: {error}")]
+    QueryFailed {
+        /// The jq query.
+        query: String,
+        /// The error that occurred.
+        error: String,
+    },
+
+    /// The resolved registry could not be serialized.
+    #[error("Failed to serialize the resolved registry, error: {error}")]
+    SerializationFailed {
+        /// The error that occurred.
+        error: String,
+    },
+}
+
+/// Loads and resolves one or several semantic convention registries.
+///
+/// The registries are merged into a single resolved registry.
+#[derive(Debug, Clone, Default)]
+pub struct RegistryLoader {
+    registries: Vec<RegistryPath>,
+    include: Vec<String>,
+    exclude: Vec<String>,
+    options: ResolverOptions,
+}
+
+/// A resolved semantic convention registry.
+#[derive(Debug, Clone)]
+pub struct Registry {
+    registry: ResolvedRegistry,
+}
+
+/// The differences between a baseline version of a registry and the current
+/// version (see [`Registry::diff`]).
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct RegistryDiff {
+    /// The changes of the attributes, by attribute name.
+    pub attributes: Changes,
+    /// The changes of the groups, by group id. The attributes of a group are
+    /// compared by name, their definitions are compared in `attributes`.
+    pub groups: Changes,
+}
+
+/// The added, removed, and changed attributes or groups of a registry.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct Changes {
+    /// The items absent from the baseline, sorted.
+    pub added: Vec<String>,
+    /// The items absent from the current registry, sorted.
+    pub removed: Vec<String>,
+    /// The fields changed since the baseline, by item and by field name.
+    pub changed: BTreeMap<String, BTreeMap<String, FieldChange>>,
+}
+
+/// The value of a field in the baseline and in the current registry, in the
+/// JSON representation of the resolved registry. An absent field is `null`.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct FieldChange {
+    /// The value in the baseline.
+    pub baseline: serde_json::Value,
+    /// The value in the current registry.
+    pub current: serde_json::Value,
+}
+
+impl RegistryDiff {
+    /// Returns true if the registries have the same attributes and groups.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.attributes.is_empty() && self.groups.is_empty()
+    }
+}
+
+impl Changes {
+    /// Returns true if nothing was added, removed, or changed.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+
+    /// Compares the JSON objects of the items of two registries, by key.
+    fn between(
+        baseline: &BTreeMap<String, serde_json::Value>,
+        current: &BTreeMap<String, serde_json::Value>,
+    ) -> Self {
+        let mut changes = Self::default();
+        for (key, current_item) in current {
+            let Some(baseline_item) = baseline.get(key) else {
+                changes.added.push(key.clone());
+                continue;
+            };
+            let field =
+                |item: &serde_json::Value, name: &str| item.get(name).cloned().unwrap_or_default();
+            let mut names: Vec<&String> = baseline_item
+                .as_object()
+                .into_iter()
+                .chain(current_item.as_object())
+                .flat_map(|object| object.keys())
+                .collect();
+            names.sort_unstable();
+            names.dedup();
+            let fields: BTreeMap<_, _> = names
+                .into_iter()
+                .filter_map(|name| {
+                    let baseline = field(baseline_item, name);
+                    let current = field(current_item, name);
+                    (baseline != current).then(|| (name.clone(), FieldChange { baseline, current }))
+                })
+                .collect();
+            if !fields.is_empty() {
+                _ = changes.changed.insert(key.clone(), fields);
+            }
+        }
+        changes.removed = baseline
+            .keys()
+            .filter(|key| !current.contains_key(*key))
+            .cloned()
+            .collect();
+        changes
+    }
+}
+
+impl RegistryLoader {
+    /// Creates a loader without any registry.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a local registry (a directory containing semantic convention
+    /// files).
+    #[must_use]
+    pub fn local(mut self, path: impl Into<String>) -> Self {
+        self.registries.push(RegistryPath::Local {
+            path_pattern: path.into(),
+        });
+        self
+    }
+
+    /// Adds a registry hosted in a Git repository, with the optional path of
+    /// the registry in the repository (e.g. `model`).
+    #[must_use]
+    pub fn git(mut self, url: impl Into<String>, sub_dir: Option<&str>) -> Self {
+        self.registries.push(RegistryPath::GitUrl {
+            git_url: url.into(),
+            path: sub_dir.map(ToOwned::to_owned),
+            git_ref: None,
+        });
+        self
+    }
+
+    /// Only loads the files of the local registries matching this glob
+    /// pattern, relative to the registry directory (e.g. `**/*.yaml`). Can be
+    /// called several times.
+    #[must_use]
+    pub fn include(mut self, pattern: impl Into<String>) -> Self {
+        self.include.push(pattern.into());
+        self
+    }
+
+    /// Skips the files or directories of the local registries matching this
+    /// glob pattern, relative to the registry directory (e.g. `deprecated/**`).
+    /// Can be called several times.
+    #[must_use]
+    pub fn exclude(mut self, pattern: impl Into<String>) -> Self {
+        self.exclude.push(pattern.into());
+        self
+    }
+
+    /// Limits the number of `extends` clauses followed from a group (e.g. 2
+    /// for `a` extending `b` extending `c`). Unlimited by default.
+    #[must_use]
+    pub fn max_extends_depth(mut self, max_extends_depth: usize) -> Self {
+        self.options = self.options.max_extends_depth(max_extends_depth);
+        self
+    }
+
+    /// Limits the number of iterations of each iterative resolution step
+    /// (`extends` clauses, attribute references, and `include` constraints).
+    /// Unlimited by default.
+    #[must_use]
+    pub fn max_iterations(mut self, max_iterations: usize) -> Self {
+        self.options = self.options.max_iterations(max_iterations);
+        self
+    }
+
+    /// Loads the files of the registries and resolves them into a single
+    /// registry.
+    pub fn load(&self) -> Result<Registry, Error> {
+        if self.registries.is_empty() {
+            return Err(Error::NoRegistry);
+        }
+        let file_filter =
+            RegistryFileFilter::try_new(&self.include, &self.exclude).map_err(|e| {
+                Error::InvalidFilePattern {
+                    error: e.to_string(),
+                }
+            })?;
+        let cache = Cache::try_new().map_err(|e| Error::CacheUnavailable {
+            error: e.to_string(),
+        })?;
+
+        let mut semconv_specs = vec![];
+        for registry in &self.registries {
+            let specs =
+                SchemaResolver::load_semconv_specs_with_filter(registry, &cache, &file_filter)
+                    .map_err(|e| Error::LoadFailed {
+                        registry: match registry {
+                            RegistryPath::Local { path_pattern } => path_pattern.clone(),
+                            RegistryPath::GitUrl { git_url, .. } => git_url.clone(),
+                            RegistryPath::Oci { oci_reference } => oci_reference.clone(),
+                            RegistryPath::Archive { archive_path } => archive_path.clone(),
+                        },
+                        errors: error_messages(e),
+                    })?;
+            semconv_specs.extend(specs);
+        }
+
+        let mut registry = SemConvRegistry::from_semconv_specs(REGISTRY_ID, semconv_specs);
+        let (schema, _) = SchemaResolver::resolve_semantic_convention_registry_with_options(
+            &mut registry,
+            &self.options,
+            &CancellationToken::default(),
+        )
+        .map_err(|e| Error::ResolutionFailed {
+            errors: error_messages(e),
+        })?;
+        let registry = schema
+            .registry(REGISTRY_ID)
+            .ok_or_else(|| Error::ResolutionFailed {
+                errors: vec![format!("The registry `{REGISTRY_ID}` was not resolved.")],
+            })?;
+        let registry = ResolvedRegistry::try_from_resolved_registry(registry, schema.catalog())
+            .map_err(|e| Error::ResolutionFailed {
+                errors: vec![e.to_string()],
+            })?;
+        Ok(Registry { registry })
+    }
+}
+
+impl Registry {
+    /// Returns the resolved registry.
+    #[must_use]
+    pub fn resolved(&self) -> &ResolvedRegistry {
+        &self.registry
+    }
+
+    /// Consumes the registry and returns the resolved registry.
+    #[must_use]
+    pub fn into_resolved(self) -> ResolvedRegistry {
+        self.registry
+    }
+
+    /// Returns the groups of the registry.
+    #[must_use]
+    pub fn groups(&self) -> &[ResolvedGroup] {
+        &self.registry.groups
+    }
+
+    /// Returns the group with the given id.
+    #[must_use]
+    pub fn group(&self, id: &str) -> Option<&ResolvedGroup> {
+        self.registry.groups.iter().find(|group| group.id == id)
+    }
+
+    /// Returns the metric group with the given metric name (e.g.
+    /// `http.server.request.duration`).
+    #[must_use]
+    pub fn metric(&self, metric_name: &str) -> Option<&ResolvedGroup> {
+        self.registry.groups.iter().find(|group| {
+            group.r#type == GroupType::Metric && group.metric_name.as_deref() == Some(metric_name)
+        })
+    }
+
+    /// Returns the attribute with the given name. The definition of an
+    /// attribute group is preferred to the (possibly refined) attributes of
+    /// the signal groups.
+    #[must_use]
+    pub fn attribute(&self, name: &str) -> Option<&Attribute> {
+        let find = |attribute_groups: bool| {
+            self.registry
+                .groups
+                .iter()
+                .filter(|group| (group.r#type == GroupType::AttributeGroup) == attribute_groups)
+                .flat_map(|group| &group.attributes)
+                .find(|attribute| attribute.name == name)
+        };
+        find(true).or_else(|| find(false))
+    }
+
+    /// Evaluates a jq expression against the JSON representation of the
+    /// resolved registry (as produced by `weaver registry resolve --format
+    /// json`) and returns the results.
+    pub fn query(&self, query: &str) -> Result<Vec<serde_json::Value>, Error> {
+        let query_failed = |error: String| Error::QueryFailed {
+            query: query.to_owned(),
+            error,
+        };
+        let filter = Filter::try_new(query, Vec::new()).map_err(|e| query_failed(e.to_string()))?;
+        let registry =
+            serde_json::to_value(&self.registry).map_err(|e| query_failed(e.to_string()))?;
+        filter
+            .apply_all(registry, Vec::new())
+            .map_err(|e| query_failed(e.to_string()))
+    }
+
+    /// Returns the attributes of the registry (one definition per attribute
+    /// name, see [`Registry::attribute`]), sorted by name.
+    #[must_use]
+    pub fn attributes(&self) -> Vec<&Attribute> {
+        let mut names: Vec<&str> = self
+            .registry
+            .groups
+            .iter()
+            .flat_map(|group| group.attributes.iter().map(|attr| attr.name.as_str()))
+            .collect();
+        names.sort_unstable();
+        names.dedup();
+        names
+            .into_iter()
+            .filter_map(|name| self.attribute(name))
+            .collect()
+    }
+
+    /// Computes the attributes and the groups added, removed, and changed
+    /// since the given baseline version of the registry. The lineage of the
+    /// groups (i.e. the provenance of their definitions) is not compared.
+    pub fn diff(&self, baseline: &Registry) -> Result<RegistryDiff, Error> {
+        let attributes = |registry: &Registry| {
+            registry
+                .attributes()
+                .into_iter()
+                .map(|attr| Ok((attr.name.to_string(), to_json_value(attr)?)))
+                .collect::<Result<BTreeMap<_, _>, Error>>()
+        };
+        let groups = |registry: &Registry| {
+            registry
+                .groups()
+                .iter()
+                .map(|group| {
+                    let mut value = to_json_value(group)?;
+                    if let Some(object) = value.as_object_mut() {
+                        _ = object.remove("lineage");
+                        let mut names: Vec<&str> = group
+                            .attributes
+                            .iter()
+                            .map(|attr| attr.name.as_str())
+                            .collect();
+                        names.sort_unstable();
+                        _ = object.insert("attributes".to_owned(), names.into());
+                    }
+                    Ok((group.id.clone(), value))
+                })
+                .collect::<Result<BTreeMap<_, _>, Error>>()
+        };
+        Ok(RegistryDiff {
+            attributes: Changes::between(&attributes(baseline)?, &attributes(self)?),
+            groups: Changes::between(&groups(baseline)?, &groups(self)?),
+        })
+    }
+
+    /// Serializes the resolved registry in JSON.
+    pub fn to_json(&self) -> Result<String, Error> {
+        serde_json::to_string_pretty(&self.registry).map_err(|e| Error::SerializationFailed {
+            error: e.to_string(),
+        })
+    }
+
+    /// Serializes the resolved registry in YAML.
+    pub fn to_yaml(&self) -> Result<String, Error> {
+        serde_yaml::to_string(&self.registry).map_err(|e| Error::SerializationFailed {
+            error: e.to_string(),
+        })
+    }
+}
+
+/// Converts a part of the resolved registry into its JSON representation.
+fn to_json_value(value: &impl Serialize) -> Result<serde_json::Value, Error> {
+    serde_json::to_value(value).map_err(|e| Error::SerializationFailed {
+        error: e.to_string(),
+    })
+}
+
+/// Returns the messages of a resolver error, one per error of a compound
+/// error.
+fn error_messages(error: weaver_resolver::Error) -> Vec<String> {
+    match error {
+        weaver_resolver::Error::CompoundError(errors) => {
+            errors.into_iter().flat_map(error_messages).collect()
+        }
+        error => vec![error.to_string()],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const REGISTRY: &str = "../weaver_codegen_test/semconv_registry";
+
+    #[test]
+    fn test_load_and_query() {
+        let registry = RegistryLoader::new()
+            .local(REGISTRY)
+            .load()
+            .expect("Failed to load the registry");
+
+        let metric = registry
+            .metric("http.server.request.duration")
+            .expect("Metric not found");
+        assert_eq!(metric.id, "metric.http.server.request.duration");
+        assert_eq!(
+            registry.group("metric.http.server.request.duration"),
+            Some(metric)
+        );
+        assert!(registry.metric("does.not.exist").is_none());
+
+        let attribute = registry
+            .attribute("http.request.method")
+            .expect("Attribute not found");
+        assert_eq!(attribute.name, "http.request.method");
+
+        let metric_names = registry
+            .query(r#"[.groups[] | select(.type == "metric") | .metric_name] | sort"#)
+            .expect("Query failed");
+        assert_eq!(metric_names.len(), 1);
+        assert!(metric_names[0]
+            .as_array()
+            .expect("Expected an array")
+            .contains(&"system.cpu.time".into()));
+
+        let json = registry
+            .to_json()
+            .expect("Failed to serialize the registry");
+        let json: serde_json::Value = serde_json::from_str(&json).expect("Invalid JSON");
+        assert_eq!(
+            json["groups"].as_array().map(Vec::len),
+            Some(registry.groups().len())
+        );
+        assert!(registry
+            .to_yaml()
+            .expect("Failed to serialize the registry")
+            .contains("groups:"));
+    }
+
+    #[test]
+    fn test_diff() {
+        let dir = std::env::temp_dir().join(format!("weaver-embed-diff-{}", std::process::id()));
+        let load = |version: &str, content: &str| {
+            let registry_dir = dir.join(version);
+            std::fs::create_dir_all(&registry_dir).expect("Failed to create the registry");
+            std::fs::write(registry_dir.join("registry.yaml"), content)
+                .expect("Failed to write the registry");
+            RegistryLoader::new()
+                .local(registry_dir.to_string_lossy())
+                .load()
+                .expect("Failed to load the registry")
+        };
+        let baseline = load(
+            "v1",
+            r#"
+groups:
+  - id: registry.db
+    type: attribute_group
+    brief: Database attributes.
+    attributes:
+      - id: db.name
+        type: string
+        brief: The name of the database.
+        stability: experimental
+        examples: ["customers"]
+      - id: db.user
+        type: string
+        brief: The database user.
+        stability: experimental
+        examples: ["admin"]
+"#,
+        );
+        let current = load(
+            "v2",
+            r#"
+groups:
+  - id: registry.db
+    type: attribute_group
+    brief: Database attributes.
+    attributes:
+      - id: db.name
+        type: string
+        brief: The name of the database.
+        stability: stable
+        examples: ["customers"]
+      - id: db.system
+        type: string
+        brief: The database management system.
+        stability: experimental
+        examples: ["postgresql"]
+  - id: span.db.client
+    type: span
+    span_kind: client
+    brief: Database client spans.
+    attributes:
+      - ref: db.name
+"#,
+        );
+        std::fs::remove_dir_all(&dir).expect("Failed to remove the registries");
+
+        assert!(current.diff(&current).expect("Diff failed").is_empty());
+        let diff = current.diff(&baseline).expect("Diff failed");
+        assert_eq!(diff.attributes.added, vec!["db.system".to_owned()]);
+        assert_eq!(diff.attributes.removed, vec!["db.user".to_owned()]);
+        assert_eq!(
+            diff.attributes.changed["db.name"]["stability"],
+            FieldChange {
+                baseline: "experimental".into(),
+                current: "stable".into(),
+            }
+        );
+        assert_eq!(diff.attributes.changed.len(), 1);
+        assert_eq!(diff.groups.added, vec!["span.db.client".to_owned()]);
+        assert!(diff.groups.removed.is_empty());
+        assert_eq!(
+            diff.groups.changed["registry.db"]["attributes"],
+            FieldChange {
+                baseline: serde_json::json!(["db.name", "db.user"]),
+                current: serde_json::json!(["db.name", "db.system"]),
+            }
+        );
+    }
+
+    #[test]
+    fn test_errors() {
+        assert_eq!(RegistryLoader::new().load().err(), Some(Error::NoRegistry));
+        assert!(matches!(
+            RegistryLoader::new().local(REGISTRY).include("[").load(),
+            Err(Error::InvalidFilePattern { .. })
+        ));
+
+        let registry = RegistryLoader::new()
+            .local(REGISTRY)
+            .exclude("metrics/**")
+            .load()
+            .expect("Failed to load the registry");
+        assert!(registry.metric("http.server.request.duration").is_none());
+        assert!(matches!(
+            registry.query(".groups[] |"),
+            Err(Error::QueryFailed { .. })
+        ));
+
+        // The registry has a chain of three `extends` clauses.
+        assert!(matches!(
+            RegistryLoader::new()
+                .local(REGISTRY)
+                .max_extends_depth(2)
+                .load(),
+            Err(Error::ResolutionFailed { .. })
+        ));
+        assert!(RegistryLoader::new()
+            .local(REGISTRY)
+            .max_extends_depth(3)
+            .max_iterations(100)
+            .load()
+            .is_ok());
+    }
+}
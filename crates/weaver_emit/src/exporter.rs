@@ -0,0 +1,107 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Export of the synthesized telemetry to an OTLP/gRPC endpoint.
+
+use std::fmt::Display;
+
+use opentelemetry_proto::tonic::collector::logs::v1::logs_service_client::LogsServiceClient;
+use opentelemetry_proto::tonic::collector::metrics::v1::metrics_service_client::MetricsServiceClient;
+use opentelemetry_proto::tonic::collector::trace::v1::trace_service_client::TraceServiceClient;
+use tonic::transport::Channel;
+
+use crate::telemetry::Telemetry;
+use crate::Error;
+
+/// Exports the telemetry to the given OTLP/gRPC endpoint (e.g.
+/// `http://localhost:4317`). Signals without any telemetry are not exported.
+pub fn export(telemetry: &Telemetry, endpoint: &str) -> Result<(), Error> {
+    let export_error = |error: &dyn Display| Error::ExportFailed {
+        endpoint: endpoint.to_owned(),
+        error: error.to_string(),
+    };
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .map_err(|e| export_error(&e))?;
+    runtime.block_on(async {
+        let channel = Channel::from_shared(endpoint.to_owned())
+            .map_err(|e| export_error(&e))?
+            .connect()
+            .await
+            .map_err(|e| export_error(&e))?;
+        if !telemetry.traces.resource_spans.is_empty() {
+            _ = TraceServiceClient::new(channel.clone())
+                .export(telemetry.traces.clone())
+                .await
+                .map_err(|e| export_error(&e))?;
+        }
+        if !telemetry.metrics.resource_metrics.is_empty() {
+            _ = MetricsServiceClient::new(channel.clone())
+                .export(telemetry.metrics.clone())
+                .await
+                .map_err(|e| export_error(&e))?;
+        }
+        if !telemetry.logs.resource_logs.is_empty() {
+            _ = LogsServiceClient::new(channel)
+                .export(telemetry.logs.clone())
+                .await
+                .map_err(|e| export_error(&e))?;
+        }
+        Ok(())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::SocketAddr;
+    use std::time::Duration;
+
+    use weaver_forge::registry::ResolvedRegistry;
+    use weaver_live_check::otlp::OtlpListener;
+    use weaver_resolver::SchemaResolver;
+    use weaver_semconv::registry::SemConvRegistry;
+
+    use super::*;
+
+    #[test]
+    fn test_export() {
+        let registry_id = "test";
+        let mut registry = SemConvRegistry::try_from_path_pattern(registry_id, "data/*.yaml")
+            .expect("Failed to load the registry");
+        let schema = SchemaResolver::resolve_semantic_convention_registry(&mut registry)
+            .expect("Failed to resolve the registry");
+        let registry = ResolvedRegistry::try_from_resolved_registry(
+            schema
+                .registry(registry_id)
+                .expect("Failed to get the registry from the resolved schema"),
+            schema.catalog(),
+        )
+        .expect("Failed to create the resolved registry");
+        let telemetry = Telemetry::from_registry(&registry);
+
+        let localhost: SocketAddr = "127.0.0.1:0".parse().expect("Invalid address");
+        let listener = OtlpListener::bind(localhost, localhost).expect("Failed to bind");
+        let endpoint = format!(
+            "http://{}",
+            listener.grpc_addr().expect("Failed to get the address")
+        );
+        let exporter = std::thread::spawn(move || export(&telemetry, &endpoint));
+        let mut sample_count = 0;
+        listener
+            .run(Duration::from_secs(2), |_| {
+                sample_count += 1;
+                Ok(())
+            })
+            .expect("Failed to run the listener");
+        exporter
+            .join()
+            .expect("The exporter panicked")
+            .expect("Failed to export the telemetry");
+        assert_eq!(sample_count, 7);
+
+        assert!(matches!(
+            export(&Telemetry::default(), "not a url"),
+            Err(Error::ExportFailed { .. })
+        ));
+    }
+}
@@ -0,0 +1,36 @@
+// SPDX-License-Identifier: Apache-2.0
+
+#![doc = include_str!("../README.md")]
+
+use miette::Diagnostic;
+use serde::Serialize;
+
+use weaver_common::diagnostic::{DiagnosticMessage, DiagnosticMessages};
+
+pub mod exporter;
+pub mod otlp_json;
+pub mod telemetry;
+
+/// An error that can occur while emitting telemetry.
+#[derive(thiserror::Error, Debug, Clone, PartialEq, Serialize, Diagnostic)]
+#[non_exhaustive]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Error {
+    /// The telemetry could not be exported to the OTLP endpoint.
+    #[error("Failed to export the telemetry to '{endpoint}', error: {error}")]
+    #[diagnostic(help(
+        "The endpoint must be the URL of an OTLP/gRPC receiver (e.g. `http://localhost:4317`)."
+    ))]
+    ExportFailed {
+        /// The OTLP endpoint.
+        endpoint: String,
+        /// The error that occurred.
+        error: String,
+    },
+}
+
+impl From<Error> for DiagnosticMessages {
+    fn from(error: Error) -> Self {
+        DiagnosticMessages::new(vec![DiagnosticMessage::new(error)])
+    }
+}
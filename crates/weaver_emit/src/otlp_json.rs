@@ -0,0 +1,336 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Encoding of the synthesized telemetry in
+//! [OTLP/JSON](https://opentelemetry.io/docs/specs/otlp/#json-protobuf-encoding)
+//! (lowerCamelCase field names, 64-bit integers encoded as strings, trace and
+//! span ids encoded in hexadecimal).
+
+use serde_json::{json, Map, Value};
+
+use opentelemetry_proto::tonic::collector::logs::v1::ExportLogsServiceRequest;
+use opentelemetry_proto::tonic::collector::metrics::v1::ExportMetricsServiceRequest;
+use opentelemetry_proto::tonic::collector::trace::v1::ExportTraceServiceRequest;
+use opentelemetry_proto::tonic::common::v1::{any_value, AnyValue, InstrumentationScope, KeyValue};
+use opentelemetry_proto::tonic::metrics::v1::{metric, number_data_point, Metric, NumberDataPoint};
+use opentelemetry_proto::tonic::resource::v1::Resource;
+
+use crate::telemetry::Telemetry;
+
+/// Encodes the telemetry in OTLP/JSON, one export request per signal (signals
+/// without any telemetry are skipped).
+#[must_use]
+pub fn telemetry_json(telemetry: &Telemetry) -> Vec<Value> {
+    let mut requests = vec![];
+    if !telemetry.traces.resource_spans.is_empty() {
+        requests.push(traces_json(&telemetry.traces));
+    }
+    if !telemetry.metrics.resource_metrics.is_empty() {
+        requests.push(metrics_json(&telemetry.metrics));
+    }
+    if !telemetry.logs.resource_logs.is_empty() {
+        requests.push(logs_json(&telemetry.logs));
+    }
+    requests
+}
+
+/// Encodes a trace export request in OTLP/JSON.
+#[must_use]
+pub fn traces_json(request: &ExportTraceServiceRequest) -> Value {
+    let resource_spans: Vec<Value> = request
+        .resource_spans
+        .iter()
+        .map(|resource_spans| {
+            let scope_spans: Vec<Value> = resource_spans
+                .scope_spans
+                .iter()
+                .map(|scope_spans| {
+                    let spans: Vec<Value> = scope_spans
+                        .spans
+                        .iter()
+                        .map(|span| {
+                            let events: Vec<Value> = span
+                                .events
+                                .iter()
+                                .map(|event| {
+                                    json!({
+                                        "timeUnixNano": event.time_unix_nano.to_string(),
+                                        "name": event.name,
+                                        "attributes": attributes_json(&event.attributes),
+                                    })
+                                })
+                                .collect();
+                            json!({
+                                "traceId": hex(&span.trace_id),
+                                "spanId": hex(&span.span_id),
+                                "name": span.name,
+                                "kind": span.kind,
+                                "startTimeUnixNano": span.start_time_unix_nano.to_string(),
+                                "endTimeUnixNano": span.end_time_unix_nano.to_string(),
+                                "attributes": attributes_json(&span.attributes),
+                                "events": events,
+                            })
+                        })
+                        .collect();
+                    json!({
+                        "scope": scope_json(scope_spans.scope.as_ref()),
+                        "spans": spans,
+                    })
+                })
+                .collect();
+            json!({
+                "resource": resource_json(resource_spans.resource.as_ref()),
+                "scopeSpans": scope_spans,
+            })
+        })
+        .collect();
+    json!({ "resourceSpans": resource_spans })
+}
+
+/// Encodes a metric export request in OTLP/JSON.
+#[must_use]
+pub fn metrics_json(request: &ExportMetricsServiceRequest) -> Value {
+    let resource_metrics: Vec<Value> = request
+        .resource_metrics
+        .iter()
+        .map(|resource_metrics| {
+            let scope_metrics: Vec<Value> = resource_metrics
+                .scope_metrics
+                .iter()
+                .map(|scope_metrics| {
+                    let metrics: Vec<Value> =
+                        scope_metrics.metrics.iter().map(metric_json).collect();
+                    json!({
+                        "scope": scope_json(scope_metrics.scope.as_ref()),
+                        "metrics": metrics,
+                    })
+                })
+                .collect();
+            json!({
+                "resource": resource_json(resource_metrics.resource.as_ref()),
+                "scopeMetrics": scope_metrics,
+            })
+        })
+        .collect();
+    json!({ "resourceMetrics": resource_metrics })
+}
+
+/// Encodes a log export request in OTLP/JSON.
+#[must_use]
+pub fn logs_json(request: &ExportLogsServiceRequest) -> Value {
+    let resource_logs: Vec<Value> = request
+        .resource_logs
+        .iter()
+        .map(|resource_logs| {
+            let scope_logs: Vec<Value> = resource_logs
+                .scope_logs
+                .iter()
+                .map(|scope_logs| {
+                    let log_records: Vec<Value> = scope_logs
+                        .log_records
+                        .iter()
+                        .map(|log_record| {
+                            json!({
+                                "timeUnixNano": log_record.time_unix_nano.to_string(),
+                                "observedTimeUnixNano": log_record.observed_time_unix_nano.to_string(),
+                                "body": log_record.body.as_ref().map_or(Value::Null, any_value_json),
+                                "attributes": attributes_json(&log_record.attributes),
+                            })
+                        })
+                        .collect();
+                    json!({
+                        "scope": scope_json(scope_logs.scope.as_ref()),
+                        "logRecords": log_records,
+                    })
+                })
+                .collect();
+            json!({
+                "resource": resource_json(resource_logs.resource.as_ref()),
+                "scopeLogs": scope_logs,
+            })
+        })
+        .collect();
+    json!({ "resourceLogs": resource_logs })
+}
+
+/// Encodes a metric in OTLP/JSON.
+fn metric_json(metric: &Metric) -> Value {
+    let mut value = json!({
+        "name": metric.name,
+        "description": metric.description,
+        "unit": metric.unit,
+    });
+    let (field, data) = match &metric.data {
+        Some(metric::Data::Gauge(gauge)) => (
+            "gauge",
+            json!({ "dataPoints": number_data_points_json(&gauge.data_points) }),
+        ),
+        Some(metric::Data::Sum(sum)) => (
+            "sum",
+            json!({
+                "dataPoints": number_data_points_json(&sum.data_points),
+                "aggregationTemporality": sum.aggregation_temporality,
+                "isMonotonic": sum.is_monotonic,
+            }),
+        ),
+        Some(metric::Data::Histogram(histogram)) => {
+            let data_points: Vec<Value> = histogram
+                .data_points
+                .iter()
+                .map(|data_point| {
+                    json!({
+                        "attributes": attributes_json(&data_point.attributes),
+                        "startTimeUnixNano": data_point.start_time_unix_nano.to_string(),
+                        "timeUnixNano": data_point.time_unix_nano.to_string(),
+                        "count": data_point.count.to_string(),
+                        "sum": data_point.sum,
+                        "bucketCounts": data_point.bucket_counts.iter().map(u64::to_string).collect::<Vec<_>>(),
+                        "explicitBounds": data_point.explicit_bounds,
+                    })
+                })
+                .collect();
+            (
+                "histogram",
+                json!({
+                    "dataPoints": data_points,
+                    "aggregationTemporality": histogram.aggregation_temporality,
+                }),
+            )
+        }
+        // The other kinds of metrics are never synthesized.
+        _ => return value,
+    };
+    if let Value::Object(map) = &mut value {
+        _ = map.insert(field.to_owned(), data);
+    }
+    value
+}
+
+/// Encodes number data points in OTLP/JSON.
+fn number_data_points_json(data_points: &[NumberDataPoint]) -> Vec<Value> {
+    data_points
+        .iter()
+        .map(|data_point| {
+            let mut value = json!({
+                "attributes": attributes_json(&data_point.attributes),
+                "startTimeUnixNano": data_point.start_time_unix_nano.to_string(),
+                "timeUnixNano": data_point.time_unix_nano.to_string(),
+            });
+            if let Value::Object(map) = &mut value {
+                match data_point.value {
+                    Some(number_data_point::Value::AsInt(v)) => {
+                        _ = map.insert("asInt".to_owned(), Value::String(v.to_string()));
+                    }
+                    Some(number_data_point::Value::AsDouble(v)) => {
+                        _ = map.insert("asDouble".to_owned(), json!(v));
+                    }
+                    None => {}
+                }
+            }
+            value
+        })
+        .collect()
+}
+
+/// Encodes a resource in OTLP/JSON.
+fn resource_json(resource: Option<&Resource>) -> Value {
+    json!({
+        "attributes": resource.map_or(vec![], |resource| attributes_json(&resource.attributes)),
+    })
+}
+
+/// Encodes an instrumentation scope in OTLP/JSON.
+fn scope_json(scope: Option<&InstrumentationScope>) -> Value {
+    scope.map_or(Value::Null, |scope| {
+        json!({
+            "name": scope.name,
+            "version": scope.version,
+        })
+    })
+}
+
+/// Encodes attributes in OTLP/JSON.
+fn attributes_json(attributes: &[KeyValue]) -> Vec<Value> {
+    attributes
+        .iter()
+        .map(|kv| {
+            json!({
+                "key": kv.key,
+                "value": kv.value.as_ref().map_or(Value::Null, any_value_json),
+            })
+        })
+        .collect()
+}
+
+/// Encodes an `AnyValue` in OTLP/JSON.
+fn any_value_json(value: &AnyValue) -> Value {
+    let (field, v) = match &value.value {
+        Some(any_value::Value::StringValue(v)) => ("stringValue", json!(v)),
+        Some(any_value::Value::BoolValue(v)) => ("boolValue", json!(v)),
+        Some(any_value::Value::IntValue(v)) => ("intValue", json!(v.to_string())),
+        Some(any_value::Value::DoubleValue(v)) => ("doubleValue", json!(v)),
+        Some(any_value::Value::ArrayValue(array)) => (
+            "arrayValue",
+            json!({ "values": array.values.iter().map(any_value_json).collect::<Vec<_>>() }),
+        ),
+        Some(any_value::Value::KvlistValue(kvlist)) => (
+            "kvlistValue",
+            json!({ "values": attributes_json(&kvlist.values) }),
+        ),
+        Some(any_value::Value::BytesValue(_)) | None => return json!({}),
+    };
+    let mut map = Map::new();
+    _ = map.insert(field.to_owned(), v);
+    Value::Object(map)
+}
+
+/// Encodes bytes in lowercase hexadecimal.
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use weaver_forge::registry::ResolvedRegistry;
+    use weaver_live_check::otlp::{log_samples, metric_samples, trace_samples};
+    use weaver_live_check::otlp_json::json_samples;
+    use weaver_resolver::SchemaResolver;
+    use weaver_semconv::registry::SemConvRegistry;
+
+    use super::*;
+
+    #[test]
+    fn test_telemetry_json() {
+        let registry_id = "test";
+        let mut registry = SemConvRegistry::try_from_path_pattern(registry_id, "data/*.yaml")
+            .expect("Failed to load the registry");
+        let schema = SchemaResolver::resolve_semantic_convention_registry(&mut registry)
+            .expect("Failed to resolve the registry");
+        let registry = ResolvedRegistry::try_from_resolved_registry(
+            schema
+                .registry(registry_id)
+                .expect("Failed to get the registry from the resolved schema"),
+            schema.catalog(),
+        )
+        .expect("Failed to create the resolved registry");
+        let telemetry = Telemetry::from_registry(&registry);
+
+        // The JSON encoding describes the same telemetry as the protobuf one.
+        let requests = telemetry_json(&telemetry);
+        assert_eq!(requests.len(), 3);
+        let json_samples: Vec<_> = requests
+            .iter()
+            .flat_map(|request| json_samples(request).expect("Invalid OTLP/JSON request"))
+            .collect();
+        let proto_samples: Vec<_> = trace_samples(telemetry.traces.clone())
+            .into_iter()
+            .chain(metric_samples(telemetry.metrics.clone()))
+            .chain(log_samples(telemetry.logs.clone()))
+            .collect();
+        assert_eq!(json_samples, proto_samples);
+
+        assert_eq!(
+            requests[0]["resourceSpans"][0]["scopeSpans"][0]["spans"][0]["traceId"],
+            "00000000000000000000000000000001"
+        );
+    }
+}
@@ -0,0 +1,448 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Synthesis of telemetry conforming to a resolved registry.
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use opentelemetry_proto::tonic::collector::logs::v1::ExportLogsServiceRequest;
+use opentelemetry_proto::tonic::collector::metrics::v1::ExportMetricsServiceRequest;
+use opentelemetry_proto::tonic::collector::trace::v1::ExportTraceServiceRequest;
+use opentelemetry_proto::tonic::common::v1::any_value::Value;
+use opentelemetry_proto::tonic::common::v1::{
+    AnyValue, ArrayValue, InstrumentationScope, KeyValue,
+};
+use opentelemetry_proto::tonic::logs::v1::{LogRecord, ResourceLogs, ScopeLogs};
+use opentelemetry_proto::tonic::metrics::v1::{
+    metric, number_data_point, AggregationTemporality, Gauge, Histogram, HistogramDataPoint,
+    Metric, NumberDataPoint, ResourceMetrics, ScopeMetrics, Sum,
+};
+use opentelemetry_proto::tonic::resource::v1::Resource;
+use opentelemetry_proto::tonic::trace::v1::{span, ResourceSpans, ScopeSpans, Span};
+
+use weaver_forge::registry::{ResolvedGroup, ResolvedRegistry};
+use weaver_resolved_schema::attribute::Attribute;
+use weaver_semconv::attribute::{
+    AttributeType, Examples, PrimitiveOrArrayTypeSpec, TemplateTypeSpec, ValueSpec,
+};
+use weaver_semconv::group::{GroupType, InstrumentSpec, SpanKindSpec};
+
+/// Telemetry synthesized from a resolved registry, one OTLP export request per
+/// signal.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Telemetry {
+    /// A span per span group.
+    pub traces: ExportTraceServiceRequest,
+    /// A metric per metric group.
+    pub metrics: ExportMetricsServiceRequest,
+    /// A log record per event group.
+    pub logs: ExportLogsServiceRequest,
+}
+
+impl Telemetry {
+    /// Synthesizes a span per span group (with the events it references), a
+    /// metric per metric group, and a log record per event group. The
+    /// attributes of the resource groups are used as resource attributes.
+    #[must_use]
+    pub fn from_registry(registry: &ResolvedRegistry) -> Self {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_nanos() as u64);
+        let groups = |r#type: GroupType| {
+            registry
+                .groups
+                .iter()
+                .filter(move |group| group.r#type == r#type)
+        };
+        let resource = Resource {
+            attributes: groups(GroupType::Resource)
+                .flat_map(|group| key_values(&group.attributes))
+                .collect(),
+            dropped_attributes_count: 0,
+        };
+        let scope = InstrumentationScope {
+            name: "weaver".to_owned(),
+            version: env!("CARGO_PKG_VERSION").to_owned(),
+            ..Default::default()
+        };
+
+        let spans: Vec<Span> = groups(GroupType::Span)
+            .enumerate()
+            .map(|(index, group)| {
+                let events = group
+                    .events
+                    .iter()
+                    .map(|name| span::Event {
+                        time_unix_nano: now,
+                        name: name.clone(),
+                        attributes: groups(GroupType::Event)
+                            .find(|event| event.name.as_ref() == Some(name))
+                            .map(|event| key_values(&event.attributes))
+                            .unwrap_or_default(),
+                        dropped_attributes_count: 0,
+                    })
+                    .collect();
+                Span {
+                    trace_id: id_bytes(index as u64 + 1, 16),
+                    span_id: id_bytes(index as u64 + 1, 8),
+                    name: group.id.clone(),
+                    kind: span_kind(group.span_kind.as_ref()) as i32,
+                    start_time_unix_nano: now,
+                    end_time_unix_nano: now,
+                    attributes: key_values(&group.attributes),
+                    events,
+                    ..Default::default()
+                }
+            })
+            .collect();
+        let metrics: Vec<Metric> = groups(GroupType::Metric)
+            .filter_map(|group| metric(group, now))
+            .collect();
+        let log_records: Vec<LogRecord> = groups(GroupType::Event)
+            .map(|group| LogRecord {
+                time_unix_nano: now,
+                observed_time_unix_nano: now,
+                body: Some(AnyValue {
+                    value: Some(Value::StringValue(
+                        group.name.clone().unwrap_or_else(|| group.id.clone()),
+                    )),
+                }),
+                attributes: key_values(&group.attributes),
+                ..Default::default()
+            })
+            .collect();
+
+        let mut telemetry = Telemetry::default();
+        if !spans.is_empty() {
+            telemetry.traces.resource_spans.push(ResourceSpans {
+                resource: Some(resource.clone()),
+                scope_spans: vec![ScopeSpans {
+                    scope: Some(scope.clone()),
+                    spans,
+                    ..Default::default()
+                }],
+                ..Default::default()
+            });
+        }
+        if !metrics.is_empty() {
+            telemetry.metrics.resource_metrics.push(ResourceMetrics {
+                resource: Some(resource.clone()),
+                scope_metrics: vec![ScopeMetrics {
+                    scope: Some(scope.clone()),
+                    metrics,
+                    ..Default::default()
+                }],
+                ..Default::default()
+            });
+        }
+        if !log_records.is_empty() {
+            telemetry.logs.resource_logs.push(ResourceLogs {
+                resource: Some(resource),
+                scope_logs: vec![ScopeLogs {
+                    scope: Some(scope),
+                    log_records,
+                    ..Default::default()
+                }],
+                ..Default::default()
+            });
+        }
+        telemetry
+    }
+
+    /// Returns the number of spans synthesized.
+    #[must_use]
+    pub fn span_count(&self) -> usize {
+        self.traces
+            .resource_spans
+            .iter()
+            .flat_map(|rs| &rs.scope_spans)
+            .map(|ss| ss.spans.len())
+            .sum()
+    }
+
+    /// Returns the number of metrics synthesized.
+    #[must_use]
+    pub fn metric_count(&self) -> usize {
+        self.metrics
+            .resource_metrics
+            .iter()
+            .flat_map(|rm| &rm.scope_metrics)
+            .map(|sm| sm.metrics.len())
+            .sum()
+    }
+
+    /// Returns the number of log records synthesized.
+    #[must_use]
+    pub fn log_count(&self) -> usize {
+        self.logs
+            .resource_logs
+            .iter()
+            .flat_map(|rl| &rl.scope_logs)
+            .map(|sl| sl.log_records.len())
+            .sum()
+    }
+}
+
+/// Synthesizes a metric recorded with the instrument of a metric group, or
+/// `None` if the group doesn't define a metric name or an instrument.
+fn metric(group: &ResolvedGroup, now: u64) -> Option<Metric> {
+    let name = group.metric_name.clone()?;
+    let number_data_point = || NumberDataPoint {
+        attributes: key_values(&group.attributes),
+        start_time_unix_nano: now,
+        time_unix_nano: now,
+        value: Some(number_data_point::Value::AsInt(1)),
+        ..Default::default()
+    };
+    let sum = |is_monotonic: bool| {
+        metric::Data::Sum(Sum {
+            data_points: vec![number_data_point()],
+            aggregation_temporality: AggregationTemporality::Cumulative as i32,
+            is_monotonic,
+        })
+    };
+    let data = match group.instrument.as_ref()? {
+        InstrumentSpec::Counter => sum(true),
+        InstrumentSpec::UpDownCounter => sum(false),
+        InstrumentSpec::Gauge => metric::Data::Gauge(Gauge {
+            data_points: vec![number_data_point()],
+        }),
+        InstrumentSpec::Histogram => metric::Data::Histogram(Histogram {
+            data_points: vec![HistogramDataPoint {
+                attributes: key_values(&group.attributes),
+                start_time_unix_nano: now,
+                time_unix_nano: now,
+                count: 1,
+                sum: Some(1.0),
+                bucket_counts: vec![1],
+                ..Default::default()
+            }],
+            aggregation_temporality: AggregationTemporality::Cumulative as i32,
+        }),
+    };
+    Some(Metric {
+        name,
+        description: group.brief.trim().to_owned(),
+        unit: group.unit.clone().unwrap_or_default(),
+        data: Some(data),
+        ..Default::default()
+    })
+}
+
+/// Synthesizes a value for each attribute. Template attributes are recorded
+/// with an `example` key (e.g. `http.request.header.example`).
+fn key_values(attributes: &[Attribute]) -> Vec<KeyValue> {
+    attributes
+        .iter()
+        .map(|attr| {
+            let key = if matches!(attr.r#type, AttributeType::Template(_)) {
+                format!("{}.example", attr.name)
+            } else {
+                attr.name.to_string()
+            };
+            KeyValue {
+                key,
+                value: Some(AnyValue {
+                    value: Some(attribute_value(attr)),
+                }),
+            }
+        })
+        .collect()
+}
+
+/// Returns a value conforming to the type of an attribute: the first member of
+/// an enum, the first example of the attribute, or a default value of the
+/// attribute type.
+fn attribute_value(attr: &Attribute) -> Value {
+    match &attr.r#type {
+        AttributeType::PrimitiveOrArray(r#type) => example_value(r#type, attr.examples.as_ref()),
+        AttributeType::Template(r#type) => {
+            let r#type = match r#type {
+                TemplateTypeSpec::Boolean => PrimitiveOrArrayTypeSpec::Boolean,
+                TemplateTypeSpec::Int => PrimitiveOrArrayTypeSpec::Int,
+                TemplateTypeSpec::Double => PrimitiveOrArrayTypeSpec::Double,
+                TemplateTypeSpec::String => PrimitiveOrArrayTypeSpec::String,
+                TemplateTypeSpec::Strings => PrimitiveOrArrayTypeSpec::Strings,
+                TemplateTypeSpec::Ints => PrimitiveOrArrayTypeSpec::Ints,
+                TemplateTypeSpec::Doubles => PrimitiveOrArrayTypeSpec::Doubles,
+                TemplateTypeSpec::Booleans => PrimitiveOrArrayTypeSpec::Booleans,
+            };
+            example_value(&r#type, attr.examples.as_ref())
+        }
+        AttributeType::Enum { members, .. } => match members.first().map(|m| &m.value) {
+            Some(ValueSpec::Int(v)) => Value::IntValue(*v),
+            Some(ValueSpec::Double(v)) => Value::DoubleValue(v.into_inner()),
+            Some(ValueSpec::String(v)) => Value::StringValue(v.clone()),
+            None => Value::StringValue("value".to_owned()),
+        },
+    }
+}
+
+/// Returns the first example matching the given type, or a default value of
+/// the type.
+fn example_value(r#type: &PrimitiveOrArrayTypeSpec, examples: Option<&Examples>) -> Value {
+    match r#type {
+        PrimitiveOrArrayTypeSpec::Boolean => Value::BoolValue(match examples {
+            Some(Examples::Bool(v)) => *v,
+            Some(Examples::Bools(v)) => v.first().copied().unwrap_or(true),
+            _ => true,
+        }),
+        PrimitiveOrArrayTypeSpec::Int => Value::IntValue(match examples {
+            Some(Examples::Int(v)) => *v,
+            Some(Examples::Ints(v)) => v.first().copied().unwrap_or(42),
+            _ => 42,
+        }),
+        PrimitiveOrArrayTypeSpec::Double => Value::DoubleValue(match examples {
+            Some(Examples::Double(v)) => v.into_inner(),
+            Some(Examples::Doubles(v)) => v.first().map_or(4.2, |v| v.into_inner()),
+            _ => 4.2,
+        }),
+        PrimitiveOrArrayTypeSpec::String => Value::StringValue(match examples {
+            Some(Examples::String(v)) => v.clone(),
+            Some(Examples::Strings(v)) => v.first().cloned().unwrap_or_else(|| "value".to_owned()),
+            _ => "value".to_owned(),
+        }),
+        PrimitiveOrArrayTypeSpec::Booleans => array(
+            match examples {
+                Some(Examples::Bools(v)) => v.clone(),
+                Some(Examples::ListOfBools(v)) => v.first().cloned().unwrap_or_default(),
+                Some(Examples::Bool(v)) => vec![*v],
+                _ => vec![],
+            },
+            true,
+            Value::BoolValue,
+        ),
+        PrimitiveOrArrayTypeSpec::Ints => array(
+            match examples {
+                Some(Examples::Ints(v)) => v.clone(),
+                Some(Examples::ListOfInts(v)) => v.first().cloned().unwrap_or_default(),
+                Some(Examples::Int(v)) => vec![*v],
+                _ => vec![],
+            },
+            42,
+            Value::IntValue,
+        ),
+        PrimitiveOrArrayTypeSpec::Doubles => array(
+            match examples {
+                Some(Examples::Doubles(v)) => v.iter().map(|v| v.into_inner()).collect(),
+                Some(Examples::ListOfDoubles(v)) => v
+                    .first()
+                    .map(|v| v.iter().map(|v| v.into_inner()).collect())
+                    .unwrap_or_default(),
+                Some(Examples::Double(v)) => vec![v.into_inner()],
+                _ => vec![],
+            },
+            4.2,
+            Value::DoubleValue,
+        ),
+        PrimitiveOrArrayTypeSpec::Strings => array(
+            match examples {
+                Some(Examples::Strings(v)) => v.clone(),
+                Some(Examples::ListOfStrings(v)) => v.first().cloned().unwrap_or_default(),
+                Some(Examples::String(v)) => vec![v.clone()],
+                _ => vec![],
+            },
+            "value".to_owned(),
+            Value::StringValue,
+        ),
+    }
+}
+
+/// Builds an array value, using a single default item if no item is provided
+/// (the type of an empty array can't be inferred).
+fn array<T>(mut items: Vec<T>, default: T, value: fn(T) -> Value) -> Value {
+    if items.is_empty() {
+        items.push(default);
+    }
+    Value::ArrayValue(ArrayValue {
+        values: items
+            .into_iter()
+            .map(|item| AnyValue {
+                value: Some(value(item)),
+            })
+            .collect(),
+    })
+}
+
+/// Returns the OTLP span kind corresponding to a span kind specification.
+fn span_kind(kind: Option<&SpanKindSpec>) -> span::SpanKind {
+    match kind {
+        Some(SpanKindSpec::Internal) => span::SpanKind::Internal,
+        Some(SpanKindSpec::Client) => span::SpanKind::Client,
+        Some(SpanKindSpec::Server) => span::SpanKind::Server,
+        Some(SpanKindSpec::Producer) => span::SpanKind::Producer,
+        Some(SpanKindSpec::Consumer) => span::SpanKind::Consumer,
+        None => span::SpanKind::Unspecified,
+    }
+}
+
+/// Returns a trace or span id of the given length (in bytes) ending with the
+/// big-endian representation of `n`.
+fn id_bytes(n: u64, len: usize) -> Vec<u8> {
+    let mut id = vec![0; len - 8];
+    id.extend(n.to_be_bytes());
+    id
+}
+
+#[cfg(test)]
+mod tests {
+    use weaver_live_check::live_checker::LiveChecker;
+    use weaver_live_check::otlp::{log_samples, metric_samples, trace_samples};
+    use weaver_resolver::SchemaResolver;
+    use weaver_semconv::registry::SemConvRegistry;
+
+    use super::*;
+
+    fn registry() -> ResolvedRegistry {
+        let registry_id = "test";
+        let mut registry = SemConvRegistry::try_from_path_pattern(registry_id, "data/*.yaml")
+            .expect("Failed to load the registry");
+        let schema = SchemaResolver::resolve_semantic_convention_registry(&mut registry)
+            .expect("Failed to resolve the registry");
+        ResolvedRegistry::try_from_resolved_registry(
+            schema
+                .registry(registry_id)
+                .expect("Failed to get the registry from the resolved schema"),
+            schema.catalog(),
+        )
+        .expect("Failed to create the resolved registry")
+    }
+
+    #[test]
+    fn test_from_registry() {
+        let registry = registry();
+        let telemetry = Telemetry::from_registry(&registry);
+        assert_eq!(telemetry.span_count(), 1);
+        assert_eq!(telemetry.metric_count(), 2);
+        assert_eq!(telemetry.log_count(), 1);
+
+        let span = &telemetry.traces.resource_spans[0].scope_spans[0].spans[0];
+        assert_eq!(span.kind, span::SpanKind::Server as i32);
+        assert_eq!(span.events.len(), 1);
+        let mut keys: Vec<&str> = span.attributes.iter().map(|kv| kv.key.as_str()).collect();
+        keys.sort_unstable();
+        assert_eq!(
+            keys,
+            vec![
+                "http.request.header.example",
+                "http.request.method",
+                "http.response.status_code",
+                "network.transport"
+            ]
+        );
+
+        // The synthesized telemetry complies with the registry.
+        let mut live_checker = LiveChecker::new(&registry);
+        let samples = trace_samples(telemetry.traces)
+            .into_iter()
+            .chain(metric_samples(telemetry.metrics))
+            .chain(log_samples(telemetry.logs));
+        for sample in samples {
+            live_checker
+                .check(&sample)
+                .expect("Failed to check the sample");
+        }
+        let report = live_checker.into_report();
+        assert!(report.is_empty(), "{:?}", report.findings);
+        assert_eq!(report.sample_count, 7);
+    }
+}
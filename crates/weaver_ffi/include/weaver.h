@@ -0,0 +1,87 @@
+/* SPDX-License-Identifier: Apache-2.0 */
+
+/*
+ * C API of the weaver semantic convention registry resolver.
+ *
+ * The strings and registry handles returned by the library are owned by the
+ * caller and must be freed with `weaver_string_free` and
+ * `weaver_registry_free`. All strings are UTF-8 and NUL-terminated.
+ */
+
+#ifndef WEAVER_H
+#define WEAVER_H
+
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/* Status codes returned by the functions of the library. */
+#define WEAVER_OK 0
+/* An argument is null, not UTF-8, or invalid. */
+#define WEAVER_ERROR_INVALID_ARGUMENT 1
+/* The files of the registry could not be loaded. */
+#define WEAVER_ERROR_LOAD_FAILED 2
+/* The registry could not be resolved. */
+#define WEAVER_ERROR_RESOLUTION_FAILED 3
+/* The resolved registry could not be serialized. */
+#define WEAVER_ERROR_SERIALIZATION_FAILED 4
+/* The attribute is not defined in the registry. */
+#define WEAVER_ERROR_UNKNOWN_ATTRIBUTE 5
+/* The type of the attribute values doesn't match its definition. */
+#define WEAVER_ERROR_TYPE_MISMATCH 6
+/* The attribute is deprecated. */
+#define WEAVER_ERROR_DEPRECATED_ATTRIBUTE 7
+/* An unexpected internal error occurred. */
+#define WEAVER_ERROR_INTERNAL 8
+
+/* An opaque handle on a resolved registry. */
+typedef struct WeaverRegistry WeaverRegistry;
+
+/*
+ * Loads and resolves the registry at the given local path or Git URL, and
+ * writes the resolved registry in JSON into `json`. On failure, `json`
+ * receives the error message instead.
+ */
+int32_t weaver_resolve_registry(const char *path, char **json);
+
+/*
+ * Loads and resolves the registry at the given local path or Git URL, and
+ * writes a handle on the resolved registry into `registry`. On failure,
+ * `error` (if not null) receives the error message.
+ */
+int32_t weaver_registry_load(const char *path, WeaverRegistry **registry, char **error);
+
+/* Writes the resolved registry in JSON into `json`. */
+int32_t weaver_registry_to_json(const WeaverRegistry *registry, char **json);
+
+/*
+ * Writes the definition of an attribute in JSON into `json`. The attributes
+ * of a template (e.g. `http.request.header.<key>`) match the template
+ * definition.
+ */
+int32_t weaver_registry_attribute(const WeaverRegistry *registry, const char *name, char **json);
+
+/*
+ * Checks an attribute observed at runtime against the registry. `value_type`
+ * is the type of the observed value (`string`, `int`, `double`, `boolean`,
+ * `string[]`, `int[]`, `double[]`, `boolean[]`), or null to skip the type
+ * check. Returns WEAVER_ERROR_UNKNOWN_ATTRIBUTE, WEAVER_ERROR_TYPE_MISMATCH,
+ * WEAVER_ERROR_DEPRECATED_ATTRIBUTE, or WEAVER_OK.
+ */
+int32_t weaver_registry_check_attribute(const WeaverRegistry *registry,
+                                        const char *name,
+                                        const char *value_type);
+
+/* Frees a registry handle. Does nothing if `registry` is null. */
+void weaver_registry_free(WeaverRegistry *registry);
+
+/* Frees a string returned by the library. Does nothing if `string` is null. */
+void weaver_string_free(char *string);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* WEAVER_H */
@@ -0,0 +1,429 @@
+// SPDX-License-Identifier: Apache-2.0
+
+#![doc = include_str!("../README.md")]
+
+use std::ffi::{c_char, CStr, CString};
+use std::panic::{catch_unwind, UnwindSafe};
+use std::ptr;
+
+use weaver_embed::{Attribute, Error, Registry, RegistryLoader};
+use weaver_semconv::attribute::{AttributeType, PrimitiveOrArrayTypeSpec};
+
+/// The operation succeeded.
+pub const WEAVER_OK: i32 = 0;
+/// An argument is null, not UTF-8, or invalid.
+pub const WEAVER_ERROR_INVALID_ARGUMENT: i32 = 1;
+/// The files of the registry could not be loaded.
+pub const WEAVER_ERROR_LOAD_FAILED: i32 = 2;
+/// The registry could not be resolved.
+pub const WEAVER_ERROR_RESOLUTION_FAILED: i32 = 3;
+/// The resolved registry could not be serialized.
+pub const WEAVER_ERROR_SERIALIZATION_FAILED: i32 = 4;
+/// The attribute is not defined in the registry.
+pub const WEAVER_ERROR_UNKNOWN_ATTRIBUTE: i32 = 5;
+/// The type of the attribute values doesn't match its definition.
+pub const WEAVER_ERROR_TYPE_MISMATCH: i32 = 6;
+/// The attribute is deprecated.
+pub const WEAVER_ERROR_DEPRECATED_ATTRIBUTE: i32 = 7;
+/// An unexpected internal error occurred.
+pub const WEAVER_ERROR_INTERNAL: i32 = 8;
+
+/// An opaque handle on a resolved registry.
+pub struct WeaverRegistry {
+    registry: Registry,
+}
+
+/// Loads and resolves the registry at the given local path or Git URL
+/// (`http(s)://...`), and writes the resolved registry in JSON into `json`.
+/// On failure, `json` receives the error message instead. In both cases, the
+/// string must be freed with [`weaver_string_free`].
+///
+/// # Safety
+///
+/// `path` must be a valid NUL-terminated string and `json` a valid pointer.
+#[no_mangle]
+pub unsafe extern "C" fn weaver_resolve_registry(
+    path: *const c_char,
+    json: *mut *mut c_char,
+) -> i32 {
+    guard(|| {
+        if json.is_null() {
+            return WEAVER_ERROR_INVALID_ARGUMENT;
+        }
+        // SAFETY: the caller guarantees that `path` is a NUL-terminated string.
+        let result = unsafe { load(path) }
+            .and_then(|registry| registry.to_json().map_err(|e| error_status(&e)));
+        let (status, output) = match result {
+            Ok(output) => (WEAVER_OK, output),
+            Err(error) => error,
+        };
+        // SAFETY: `json` is not null and the caller guarantees it is valid.
+        unsafe { *json = into_c_string(output) };
+        status
+    })
+}
+
+/// Loads and resolves the registry at the given local path or Git URL, and
+/// writes a handle on the resolved registry into `registry`. The handle must be
+/// freed with [`weaver_registry_free`]. On failure, `error` (if not null)
+/// receives the error message, to free with [`weaver_string_free`].
+///
+/// # Safety
+///
+/// `path` must be a valid NUL-terminated string, `registry` a valid pointer,
+/// and `error` a valid pointer or null.
+#[no_mangle]
+pub unsafe extern "C" fn weaver_registry_load(
+    path: *const c_char,
+    registry: *mut *mut WeaverRegistry,
+    error: *mut *mut c_char,
+) -> i32 {
+    guard(|| {
+        if registry.is_null() {
+            return WEAVER_ERROR_INVALID_ARGUMENT;
+        }
+        // SAFETY: the caller guarantees that `path` is a NUL-terminated string.
+        match unsafe { load(path) } {
+            Ok(loaded) => {
+                let handle = Box::into_raw(Box::new(WeaverRegistry { registry: loaded }));
+                // SAFETY: `registry` is not null and the caller guarantees it is valid.
+                unsafe { *registry = handle };
+                WEAVER_OK
+            }
+            Err((status, message)) => {
+                if !error.is_null() {
+                    // SAFETY: `error` is not null and the caller guarantees it is valid.
+                    unsafe { *error = into_c_string(message) };
+                }
+                status
+            }
+        }
+    })
+}
+
+/// Writes the resolved registry in JSON into `json`, to free with
+/// [`weaver_string_free`].
+///
+/// # Safety
+///
+/// `registry` must be a handle returned by [`weaver_registry_load`] and
+/// `json` a valid pointer.
+#[no_mangle]
+pub unsafe extern "C" fn weaver_registry_to_json(
+    registry: *const WeaverRegistry,
+    json: *mut *mut c_char,
+) -> i32 {
+    guard(|| {
+        // SAFETY: the caller guarantees that `registry` is a valid handle.
+        let Some(registry) = (unsafe { registry.as_ref() }) else {
+            return WEAVER_ERROR_INVALID_ARGUMENT;
+        };
+        if json.is_null() {
+            return WEAVER_ERROR_INVALID_ARGUMENT;
+        }
+        match registry.registry.to_json() {
+            Ok(output) => {
+                // SAFETY: `json` is not null and the caller guarantees it is valid.
+                unsafe { *json = into_c_string(output) };
+                WEAVER_OK
+            }
+            Err(e) => error_status(&e).0,
+        }
+    })
+}
+
+/// Writes the definition of an attribute in JSON into `json`, to free with
+/// [`weaver_string_free`]. The attributes of a template (e.g.
+/// `http.request.header.<key>`) match the template definition.
+///
+/// # Safety
+///
+/// `registry` must be a handle returned by [`weaver_registry_load`], `name` a
+/// valid NUL-terminated string, and `json` a valid pointer.
+#[no_mangle]
+pub unsafe extern "C" fn weaver_registry_attribute(
+    registry: *const WeaverRegistry,
+    name: *const c_char,
+    json: *mut *mut c_char,
+) -> i32 {
+    guard(|| {
+        // SAFETY: the caller guarantees the validity of the handle and the name.
+        let (Some(registry), Some(name)) = (unsafe { registry.as_ref() }, unsafe { to_str(name) })
+        else {
+            return WEAVER_ERROR_INVALID_ARGUMENT;
+        };
+        if json.is_null() {
+            return WEAVER_ERROR_INVALID_ARGUMENT;
+        }
+        let Some(attribute) = find_attribute(&registry.registry, name) else {
+            return WEAVER_ERROR_UNKNOWN_ATTRIBUTE;
+        };
+        match serde_json::to_string(attribute) {
+            Ok(output) => {
+                // SAFETY: `json` is not null and the caller guarantees it is valid.
+                unsafe { *json = into_c_string(output) };
+                WEAVER_OK
+            }
+            Err(_) => WEAVER_ERROR_SERIALIZATION_FAILED,
+        }
+    })
+}
+
+/// Checks an attribute observed at runtime against the registry: returns
+/// [`WEAVER_ERROR_UNKNOWN_ATTRIBUTE`] if it is not defined,
+/// [`WEAVER_ERROR_TYPE_MISMATCH`] if `value_type` (e.g. `string`, `int`,
+/// `double`, `boolean`, `string[]`, or null to skip the type check) doesn't
+/// match its definition, [`WEAVER_ERROR_DEPRECATED_ATTRIBUTE`] if it is
+/// deprecated, and [`WEAVER_OK`] otherwise.
+///
+/// # Safety
+///
+/// `registry` must be a handle returned by [`weaver_registry_load`], `name` a
+/// valid NUL-terminated string, and `value_type` a valid NUL-terminated string
+/// or null.
+#[no_mangle]
+pub unsafe extern "C" fn weaver_registry_check_attribute(
+    registry: *const WeaverRegistry,
+    name: *const c_char,
+    value_type: *const c_char,
+) -> i32 {
+    guard(|| {
+        // SAFETY: the caller guarantees the validity of the handle and the name.
+        let (Some(registry), Some(name)) = (unsafe { registry.as_ref() }, unsafe { to_str(name) })
+        else {
+            return WEAVER_ERROR_INVALID_ARGUMENT;
+        };
+        let value_type = if value_type.is_null() {
+            None
+        } else {
+            // SAFETY: the caller guarantees that `value_type` is a NUL-terminated string.
+            let parsed = unsafe { to_str(value_type) }.and_then(|value_type| {
+                serde_json::from_value::<PrimitiveOrArrayTypeSpec>(value_type.into()).ok()
+            });
+            let Some(parsed) = parsed else {
+                return WEAVER_ERROR_INVALID_ARGUMENT;
+            };
+            Some(parsed)
+        };
+
+        let Some(attribute) = find_attribute(&registry.registry, name) else {
+            return WEAVER_ERROR_UNKNOWN_ATTRIBUTE;
+        };
+        if value_type.is_some_and(|value_type| value_type != attribute.r#type.value_type()) {
+            WEAVER_ERROR_TYPE_MISMATCH
+        } else if attribute.deprecated.is_some() {
+            WEAVER_ERROR_DEPRECATED_ATTRIBUTE
+        } else {
+            WEAVER_OK
+        }
+    })
+}
+
+/// Frees a registry handle returned by [`weaver_registry_load`]. Does nothing
+/// if `registry` is null.
+///
+/// # Safety
+///
+/// `registry` must be a handle returned by [`weaver_registry_load`] or null,
+/// and must not be used after this call.
+#[no_mangle]
+pub unsafe extern "C" fn weaver_registry_free(registry: *mut WeaverRegistry) {
+    if !registry.is_null() {
+        // SAFETY: the handle was allocated by `weaver_registry_load`.
+        drop(unsafe { Box::from_raw(registry) });
+    }
+}
+
+/// Frees a string returned by this library. Does nothing if `string` is null.
+///
+/// # Safety
+///
+/// `string` must be a string returned by this library or null, and must not be
+/// used after this call.
+#[no_mangle]
+pub unsafe extern "C" fn weaver_string_free(string: *mut c_char) {
+    if !string.is_null() {
+        // SAFETY: the string was allocated by `into_c_string`.
+        drop(unsafe { CString::from_raw(string) });
+    }
+}
+
+/// Runs the body of an exported function, converting a panic into
+/// [`WEAVER_ERROR_INTERNAL`] as unwinding across the C ABI is undefined.
+///
+/// Panics are only caught if the library is built with `panic = "unwind"`
+/// (e.g. the `release-lib` profile), the `release` profile aborts on panic.
+fn guard(body: impl FnOnce() -> i32 + UnwindSafe) -> i32 {
+    catch_unwind(body).unwrap_or(WEAVER_ERROR_INTERNAL)
+}
+
+/// Converts a NUL-terminated string into a `&str`, or `None` if it is null or
+/// not UTF-8.
+///
+/// # Safety
+///
+/// `string` must be a valid NUL-terminated string or null.
+unsafe fn to_str<'a>(string: *const c_char) -> Option<&'a str> {
+    if string.is_null() {
+        return None;
+    }
+    // SAFETY: the caller guarantees that `string` is a NUL-terminated string.
+    unsafe { CStr::from_ptr(string) }.to_str().ok()
+}
+
+/// Converts a string into a C string allocated by this library. The interior
+/// NUL bytes (not expected in JSON or error messages) are removed.
+fn into_c_string(string: String) -> *mut c_char {
+    CString::new(string.replace('\0', ""))
+        .map(CString::into_raw)
+        .unwrap_or(ptr::null_mut())
+}
+
+/// Loads the registry at the given path or Git URL.
+///
+/// # Safety
+///
+/// `path` must be a valid NUL-terminated string or null.
+unsafe fn load(path: *const c_char) -> Result<Registry, (i32, String)> {
+    // SAFETY: the caller guarantees that `path` is a NUL-terminated string.
+    let Some(path) = (unsafe { to_str(path) }) else {
+        return Err((
+            WEAVER_ERROR_INVALID_ARGUMENT,
+            "The registry path is null or not UTF-8.".to_owned(),
+        ));
+    };
+    let loader = if path.starts_with("http://") || path.starts_with("https://") {
+        RegistryLoader::new().git(path, None)
+    } else {
+        RegistryLoader::new().local(path)
+    };
+    loader.load().map_err(|e| error_status(&e))
+}
+
+/// Returns the status code and the message of an error.
+fn error_status(error: &Error) -> (i32, String) {
+    let status = match error {
+        Error::NoRegistry | Error::InvalidFilePattern { .. } => WEAVER_ERROR_INVALID_ARGUMENT,
+        Error::CacheUnavailable { .. } | Error::LoadFailed { .. } => WEAVER_ERROR_LOAD_FAILED,
+        Error::ResolutionFailed { .. } => WEAVER_ERROR_RESOLUTION_FAILED,
+        Error::SerializationFailed { .. } => WEAVER_ERROR_SERIALIZATION_FAILED,
+        _ => WEAVER_ERROR_INTERNAL,
+    };
+    (status, error.to_string())
+}
+
+/// Returns the definition of an attribute, or of the template attribute whose
+/// name prefixes the given attribute name.
+fn find_attribute<'a>(registry: &'a Registry, name: &str) -> Option<&'a Attribute> {
+    registry.attribute(name).or_else(|| {
+        registry
+            .groups()
+            .iter()
+            .flat_map(|group| &group.attributes)
+            .filter(|attribute| matches!(attribute.r#type, AttributeType::Template(_)))
+            .find(|attribute| {
+                name.strip_prefix(attribute.name.as_str())
+                    .is_some_and(|key| key.len() > 1 && key.starts_with('.'))
+            })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const REGISTRY: &str = "../weaver_codegen_test/semconv_registry";
+
+    fn c(string: &str) -> CString {
+        CString::new(string).expect("Invalid C string")
+    }
+
+    fn take_string(string: *mut c_char) -> String {
+        assert!(!string.is_null());
+        // SAFETY: the string was returned by the library and is freed once.
+        unsafe {
+            let owned = CStr::from_ptr(string)
+                .to_str()
+                .expect("Invalid UTF-8")
+                .to_owned();
+            weaver_string_free(string);
+            owned
+        }
+    }
+
+    #[test]
+    fn test_resolve_registry() {
+        let mut json = ptr::null_mut();
+        // SAFETY: valid arguments.
+        let status = unsafe { weaver_resolve_registry(c(REGISTRY).as_ptr(), &mut json) };
+        assert_eq!(status, WEAVER_OK);
+        let registry: serde_json::Value =
+            serde_json::from_str(&take_string(json)).expect("Invalid JSON");
+        assert!(registry["groups"].as_array().is_some_and(|g| !g.is_empty()));
+
+        // SAFETY: valid arguments.
+        let status = unsafe { weaver_resolve_registry(c("does/not/exist").as_ptr(), &mut json) };
+        assert_eq!(status, WEAVER_ERROR_LOAD_FAILED);
+        assert!(take_string(json).contains("does/not/exist"));
+
+        // SAFETY: null arguments are rejected.
+        let status = unsafe { weaver_resolve_registry(ptr::null(), &mut json) };
+        assert_eq!(status, WEAVER_ERROR_INVALID_ARGUMENT);
+        _ = take_string(json);
+        // SAFETY: null arguments are rejected.
+        let status = unsafe { weaver_resolve_registry(c(REGISTRY).as_ptr(), ptr::null_mut()) };
+        assert_eq!(status, WEAVER_ERROR_INVALID_ARGUMENT);
+    }
+
+    #[test]
+    fn test_check_attributes() {
+        let mut registry = ptr::null_mut();
+        // SAFETY: valid arguments, the handle is freed once.
+        unsafe {
+            assert_eq!(
+                weaver_registry_load(c(REGISTRY).as_ptr(), &mut registry, ptr::null_mut()),
+                WEAVER_OK
+            );
+            let check = |name: &str, value_type: Option<&str>| {
+                let value_type = value_type.map(c);
+                weaver_registry_check_attribute(
+                    registry,
+                    c(name).as_ptr(),
+                    value_type.as_deref().map_or(ptr::null(), CStr::as_ptr),
+                )
+            };
+            assert_eq!(check("http.request.method", None), WEAVER_OK);
+            assert_eq!(check("http.request.method", Some("string")), WEAVER_OK);
+            assert_eq!(
+                check("http.request.method", Some("int")),
+                WEAVER_ERROR_TYPE_MISMATCH
+            );
+            assert_eq!(
+                check("http.request.method", Some("unknown")),
+                WEAVER_ERROR_INVALID_ARGUMENT
+            );
+            assert_eq!(
+                check("unknown.attribute", None),
+                WEAVER_ERROR_UNKNOWN_ATTRIBUTE
+            );
+
+            let mut json = ptr::null_mut();
+            assert_eq!(
+                weaver_registry_attribute(registry, c("http.request.method").as_ptr(), &mut json),
+                WEAVER_OK
+            );
+            assert!(take_string(json).contains("\"http.request.method\""));
+            assert_eq!(weaver_registry_to_json(registry, &mut json), WEAVER_OK);
+            assert!(take_string(json).contains("\"groups\""));
+            weaver_registry_free(registry);
+        }
+
+        let mut error = ptr::null_mut();
+        // SAFETY: valid arguments.
+        let status = unsafe {
+            weaver_registry_load(c("does/not/exist").as_ptr(), &mut registry, &mut error)
+        };
+        assert_eq!(status, WEAVER_ERROR_LOAD_FAILED);
+        assert!(!take_string(error).is_empty());
+    }
+}
@@ -81,6 +81,12 @@ pub(crate) struct TargetConfig {
     #[serde(default)]
     pub(crate) whitespace_control: WhitespaceControl,
 
+    /// Sort the groups of the template context by id, their attributes by name,
+    /// and the members of enum attributes by id, instead of keeping the order
+    /// of the registry files.
+    #[serde(default)]
+    pub(crate) sort_context: bool,
+
     /// Parameters for the templates.
     /// These parameters can be overridden by parameters passed to the CLI.
     #[serde(default)]
@@ -102,76 +108,91 @@ fn default_templates() -> Vec<TemplateConfig> {
             pattern: Glob::new("**/registry.md").expect("Invalid pattern"),
             filter: ".".to_owned(),
             application_mode: ApplicationMode::Single,
+            whitespace_control: None,
         },
         TemplateConfig {
             pattern: Glob::new("**/attribute_group.md").expect("Invalid pattern"),
             filter: ".groups[] | select(.type == \"attribute_group\")".to_owned(),
             application_mode: ApplicationMode::Each,
+            whitespace_control: None,
         },
         TemplateConfig {
             pattern: Glob::new("**/attribute_groups.md").expect("Invalid pattern"),
             filter: ".groups[] | select(.type == \"attribute_group\")".to_owned(),
             application_mode: ApplicationMode::Single,
+            whitespace_control: None,
         },
         TemplateConfig {
             pattern: Glob::new("**/event.md").expect("Invalid pattern"),
             filter: ".groups[] | select(.type == \"event\")".to_owned(),
             application_mode: ApplicationMode::Each,
+            whitespace_control: None,
         },
         TemplateConfig {
             pattern: Glob::new("**/events.md").expect("Invalid pattern"),
             filter: ".groups[] | select(.type == \"event\")".to_owned(),
             application_mode: ApplicationMode::Single,
+            whitespace_control: None,
         },
         TemplateConfig {
             pattern: Glob::new("**/group.md").expect("Invalid pattern"),
             filter: ".groups".to_owned(),
             application_mode: ApplicationMode::Each,
+            whitespace_control: None,
         },
         TemplateConfig {
             pattern: Glob::new("**/groups.md").expect("Invalid pattern"),
             filter: ".groups".to_owned(),
             application_mode: ApplicationMode::Single,
+            whitespace_control: None,
         },
         TemplateConfig {
             pattern: Glob::new("**/metric.md").expect("Invalid pattern"),
             filter: ".groups[] | select(.type == \"metric\")".to_owned(),
             application_mode: ApplicationMode::Each,
+            whitespace_control: None,
         },
         TemplateConfig {
             pattern: Glob::new("**/metrics.md").expect("Invalid pattern"),
             filter: ".groups[] | select(.type == \"metric\")".to_owned(),
             application_mode: ApplicationMode::Single,
+            whitespace_control: None,
         },
         TemplateConfig {
             pattern: Glob::new("**/resource.md").expect("Invalid pattern"),
             filter: ".groups[] | select(.type == \"resource\")".to_owned(),
             application_mode: ApplicationMode::Each,
+            whitespace_control: None,
         },
         TemplateConfig {
             pattern: Glob::new("**/resources.md").expect("Invalid pattern"),
             filter: ".groups[] | select(.type == \"resource\")".to_owned(),
             application_mode: ApplicationMode::Single,
+            whitespace_control: None,
         },
         TemplateConfig {
             pattern: Glob::new("**/scope.md").expect("Invalid pattern"),
             filter: ".groups[] | select(.type == \"scope\")".to_owned(),
             application_mode: ApplicationMode::Each,
+            whitespace_control: None,
         },
         TemplateConfig {
             pattern: Glob::new("**/scopes.md").expect("Invalid pattern"),
             filter: ".groups[] | select(.type == \"scope\")".to_owned(),
             application_mode: ApplicationMode::Single,
+            whitespace_control: None,
         },
         TemplateConfig {
             pattern: Glob::new("**/span.md").expect("Invalid pattern"),
             filter: ".groups[] | select(.type == \"span\")".to_owned(),
             application_mode: ApplicationMode::Each,
+            whitespace_control: None,
         },
         TemplateConfig {
             pattern: Glob::new("**/spans.md").expect("Invalid pattern"),
             filter: ".groups[] | select(.type == \"span\")".to_owned(),
             application_mode: ApplicationMode::Single,
+            whitespace_control: None,
         },
     ]
 }
@@ -213,6 +234,10 @@ pub(crate) struct TemplateConfig {
     /// `single`: Apply the template to the output of the filter as a whole.
     /// `each`: Apply the template to each item of the list returned by the filter.
     pub(crate) application_mode: ApplicationMode,
+    /// Whitespace behavior of the template engine for this template. Overrides
+    /// the `whitespace_control` section of the target configuration.
+    #[serde(default)]
+    pub(crate) whitespace_control: Option<WhitespaceControl>,
 }
 
 fn default_filter() -> String {
@@ -302,16 +327,20 @@ impl Default for TemplateSyntax {
 }
 
 /// Whitespace control configuration for the template engine.
+/// Options not specified in the configuration are disabled.
 #[derive(Deserialize, Debug, Clone, Default)]
 pub struct WhitespaceControl {
     /// Configures the behavior of the first newline after a block.
     /// See <https://docs.rs/minijinja/latest/minijinja/struct.Environment.html#method.set_trim_blocks>
+    #[serde(default)]
     pub trim_blocks: bool,
     /// Configures the behavior of leading spaces and tabs from the start of a line to a block.
     /// See <https://docs.rs/minijinja/latest/minijinja/struct.Environment.html#method.set_lstrip_blocks>
+    #[serde(default)]
     pub lstrip_blocks: bool,
     /// Configures whether trailing newline are preserved when rendering templates.
     /// See <https://docs.rs/minijinja/latest/minijinja/struct.Environment.html#method.set_keep_trailing_newline>
+    #[serde(default)]
     pub keep_trailing_newline: bool,
 }
 
@@ -2,36 +2,227 @@
 
 //! Utility functions to help with debugging.
 
-use crate::error::Error::{CompoundError, TemplateEvaluationFailed};
-use indexmap::IndexMap;
 use std::error::Error;
+use std::path::Path;
+use std::sync::OnceLock;
+
+use indexmap::IndexMap;
+use regex::Regex;
+
 use weaver_common::Logger;
 
-/// Return a nice summary of the error including the chain of causes.
-/// Only the last error in the chain is displayed with a full stack trace.
-pub(crate) fn error_summary(error: minijinja::Error) -> String {
-    let mut errors = Vec::new();
-    let mut curr_error: &dyn Error = &error;
+use crate::error::Error::{CompoundError, TemplateEvaluationFailed};
+use crate::error::TemplateErrorLocation;
+
+/// Number of template lines displayed before and after the line of an error.
+const SNIPPET_CONTEXT_LINES: usize = 2;
+
+/// Maximum number of `for` and `set` statements followed to compute the JSON
+/// path of a context value.
+const MAX_JSON_PATH_DEPTH: usize = 8;
+
+/// Build a `TemplateEvaluationFailed` error from a minijinja error.
+///
+/// The error message reports the chain of causes, the location of the error in
+/// the template (name, line, and column), a snippet of the surrounding template,
+/// the offending expression, and the JSON path of the context value involved in
+/// the error when it can be inferred from the template.
+pub(crate) fn template_evaluation_error(
+    template: &Path,
+    error: &minijinja::Error,
+) -> crate::error::Error {
+    let mut message = error_summary(error);
+    let mut location = None;
+
+    // The innermost error with a location is the most precise one (e.g. an
+    // error in a macro or an included template).
+    if let Some(located) = error_chain(error)
+        .into_iter()
+        .rev()
+        .find(|e| e.line().is_some())
+    {
+        let mut error_location = TemplateErrorLocation {
+            name: located
+                .name()
+                .map(str::to_owned)
+                .unwrap_or_else(|| template.display().to_string()),
+            line: located.line().unwrap_or_default(),
+            column: None,
+            expression: None,
+            json_path: None,
+        };
+
+        let span = located.template_source().and_then(|source| {
+            let range = located.range()?;
+            Some((source, range.start, source.get(range)?))
+        });
+        if let Some((source, offset, expr)) = span {
+            let (line, column) = line_column(source, offset);
+            let json_path = context_json_path(source, offset, expr, MAX_JSON_PATH_DEPTH);
+
+            message.push_str(&format!(
+                "  --> {}:{}:{}\n",
+                error_location.name, line, column
+            ));
+            message.push_str(&snippet(source, line, column, expr));
+            message.push_str(&format!("  = expression: `{}`\n", expr));
+            if let Some(json_path) = &json_path {
+                message.push_str(&format!("  = context path: `{}`\n", json_path));
+            }
+
+            error_location.line = line;
+            error_location.column = Some(column);
+            error_location.expression = Some(expr.to_owned());
+            error_location.json_path = json_path;
+        } else {
+            message.push_str(&format!(
+                "  --> {}:{}\n",
+                error_location.name, error_location.line
+            ));
+        }
+        location = Some(Box::new(error_location));
+    }
+
+    TemplateEvaluationFailed {
+        template: template.to_path_buf(),
+        location,
+        error_id: error.to_string(),
+        error: message,
+    }
+}
 
-    errors.push(curr_error);
+/// Return the minijinja errors of the chain of causes, starting with the given
+/// error.
+fn error_chain(error: &minijinja::Error) -> Vec<&minijinja::Error> {
+    let mut errors = vec![error];
+    let mut curr_error: &dyn Error = error;
 
     while let Some(e) = curr_error.source() {
-        errors.push(e);
+        if let Some(e) = e.downcast_ref::<minijinja::Error>() {
+            errors.push(e);
+        }
         curr_error = e;
     }
+    errors
+}
 
-    let mut error_msg = String::new();
-    for (i, e) in errors.iter().enumerate() {
-        if i == errors.len() - 1 {
-            // Display the last error with all the referenced variables
-            error_msg.push_str(&format!("{:#}\n", e));
-        } else {
-            error_msg.push_str(&format!("{}\nCaused by:\n", e));
-        }
+/// Return a summary of the error including the chain of causes.
+fn error_summary(error: &minijinja::Error) -> String {
+    let mut error_msg = format!("{}\n", error);
+    let mut curr_error: &dyn Error = error;
+
+    while let Some(e) = curr_error.source() {
+        error_msg.push_str(&format!("Caused by:\n{}\n", e));
+        curr_error = e;
     }
     error_msg
 }
 
+/// Return the line and column numbers (1-based) of a byte offset in a source.
+fn line_column(source: &str, offset: usize) -> (usize, usize) {
+    let before = &source[..offset];
+    let line = before.matches('\n').count() + 1;
+    let line_start = before.rfind('\n').map(|i| i + 1).unwrap_or(0);
+    let column = before[line_start..].chars().count() + 1;
+    (line, column)
+}
+
+/// Return the lines surrounding the line of an error, with the offending
+/// expression underlined.
+fn snippet(source: &str, line: usize, column: usize, expression: &str) -> String {
+    let lines: Vec<&str> = source.lines().collect();
+    let first = line.saturating_sub(SNIPPET_CONTEXT_LINES).max(1);
+    let last = (line + SNIPPET_CONTEXT_LINES).min(lines.len());
+    let width = last.to_string().len();
+
+    let mut snippet = format!("{:width$} |\n", "");
+    for (number, content) in lines
+        .iter()
+        .enumerate()
+        .map(|(i, content)| (i + 1, content))
+        .filter(|(number, _)| (first..=last).contains(number))
+    {
+        snippet.push_str(&format!("{:>width$} | {}\n", number, content));
+        if number == line {
+            // Underline the expression up to the end of the line.
+            let line_length = content.chars().count();
+            let length = expression
+                .lines()
+                .next()
+                .map(|l| l.chars().count())
+                .unwrap_or(0)
+                .min(line_length.saturating_sub(column - 1))
+                .max(1);
+            snippet.push_str(&format!(
+                "{:width$} | {}{}\n",
+                "",
+                " ".repeat(column - 1),
+                "^".repeat(length)
+            ));
+        }
+    }
+    snippet
+}
+
+/// Infer the JSON path of the context value referenced by a template expression.
+///
+/// Expressions starting with `ctx` are directly mapped to a JSON path (`$` being
+/// the context). Expressions starting with a variable defined by a `for` or `set`
+/// statement preceding the error are resolved through the expression assigned to
+/// this variable, e.g. `attr.name` in `{% for attr in ctx.attributes %}` is mapped
+/// to `$.attributes[*].name`.
+fn context_json_path(
+    source: &str,
+    offset: usize,
+    expression: &str,
+    depth: usize,
+) -> Option<String> {
+    static PATH: OnceLock<Regex> = OnceLock::new();
+    static DEFINITION: OnceLock<Regex> = OnceLock::new();
+    let path = PATH.get_or_init(|| {
+        Regex::new(r#"^([A-Za-z_]\w*)((?:\.[A-Za-z_]\w*|\[\d+\]|\["[^"]*"\]|\['[^']*'\])*)"#)
+            .expect("Invalid regex")
+    });
+    let definition = DEFINITION.get_or_init(|| {
+        Regex::new(r"\b(?:for\s+(\w+)\s+in|set\s+(\w+)\s*=)\s*").expect("Invalid regex")
+    });
+
+    let captures = path.captures(expression.trim())?;
+    let root = captures.get(1)?.as_str();
+    let accessors = captures.get(2).map(|m| m.as_str()).unwrap_or_default();
+
+    if root == "ctx" {
+        return Some(format!("${}", accessors));
+    }
+    if depth == 0 {
+        return None;
+    }
+
+    // The closest definition of the root variable preceding the error.
+    let before = &source[..offset];
+    let (definition, is_loop) = definition
+        .captures_iter(before)
+        .filter_map(|c| match (c.get(1), c.get(2)) {
+            (Some(var), _) if var.as_str() == root => Some((c.get(0)?, true)),
+            (_, Some(var)) if var.as_str() == root => Some((c.get(0)?, false)),
+            _ => None,
+        })
+        .last()?;
+    let base = context_json_path(
+        source,
+        definition.start(),
+        &source[definition.end()..],
+        depth - 1,
+    )?;
+
+    Some(format!(
+        "{}{}{}",
+        base,
+        if is_loop { "[*]" } else { "" },
+        accessors
+    ))
+}
+
 /// Print deduplicated errors.
 ///
 /// This function prints the error message and the number of occurrences of
@@ -109,6 +300,63 @@ mod tests {
     use super::*;
     use crate::error::Error::TargetNotSupported;
 
+    #[test]
+    fn test_template_evaluation_error() {
+        let mut env = minijinja::Environment::new();
+        env.set_debug(true);
+        env.add_template(
+            "test.j2",
+            "{% for group in ctx.groups %}\n{% for attr in group.attributes %}\n- {{ attr.name + 1 }}\n{% endfor %}\n{% endfor %}\n",
+        )
+        .expect("Failed to add template");
+        let ctx = serde_json::json!({"ctx": {"groups": [{"attributes": [{"name": "a"}]}]}});
+        let error = env
+            .get_template("test.j2")
+            .expect("Template not found")
+            .render(ctx)
+            .expect_err("The template should fail");
+
+        match template_evaluation_error(Path::new("test.j2"), &error) {
+            TemplateEvaluationFailed {
+                template,
+                location: Some(location),
+                error,
+                ..
+            } => {
+                assert_eq!(template, Path::new("test.j2"));
+                assert_eq!(location.name, "test.j2");
+                assert_eq!(location.line, 3);
+                assert_eq!(location.column, Some(6));
+                assert_eq!(location.expression.as_deref(), Some("attr.name + 1"));
+                assert_eq!(
+                    location.json_path.as_deref(),
+                    Some("$.groups[*].attributes[*].name")
+                );
+                assert!(error.contains("  --> test.j2:3:6\n"));
+                assert!(error.contains("3 | - {{ attr.name + 1 }}\n  |      ^^^^^^^^^^^^^\n"));
+            }
+            e => panic!("Unexpected error: {:?}", e),
+        }
+    }
+
+    #[test]
+    fn test_context_json_path() {
+        let source = "{% set groups = ctx.groups %}{% for g in groups %}{{ g.id }}";
+        let offset = source.len() - "g.id }}".len();
+        assert_eq!(
+            context_json_path(source, offset, "g.id", MAX_JSON_PATH_DEPTH).as_deref(),
+            Some("$.groups[*].id")
+        );
+        assert_eq!(
+            context_json_path(source, offset, "ctx[\"groups\"][0]", MAX_JSON_PATH_DEPTH).as_deref(),
+            Some("$[\"groups\"][0]")
+        );
+        assert_eq!(
+            context_json_path(source, offset, "loop.index", MAX_JSON_PATH_DEPTH),
+            None
+        );
+    }
+
     #[test]
     fn test_print_dedup_errors() {
         let logger = weaver_common::TestLogger::new();
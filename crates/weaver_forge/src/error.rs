@@ -86,9 +86,11 @@ pub enum Error {
     TemplateEvaluationFailed {
         /// Template path.
         template: PathBuf,
+        /// Location of the error in the template, if known.
+        location: Option<Box<TemplateErrorLocation>>,
         /// Error id used to deduplicate the error.
         error_id: String,
-        /// Error message.
+        /// Error message, including a snippet of the template around the error.
         error: String,
     },
 
@@ -152,11 +154,35 @@ pub enum Error {
         case: String,
     },
 
+    /// The comparison of the generated artifacts with the expected files failed.
+    #[error("Failed to compare the generated artifacts with the expected files of `{expected_dir}`: {error}")]
+    ExpectedOutputFailed {
+        /// Directory of the expected files.
+        expected_dir: PathBuf,
+        /// Error message.
+        error: String,
+    },
+
     /// A generic container for multiple errors.
     #[error("Errors:\n{0:#?}")]
     CompoundError(Vec<Error>),
 }
 
+/// Location of a template evaluation error.
+#[derive(Debug, Clone, Serialize)]
+pub struct TemplateErrorLocation {
+    /// Name of the template where the error occurred (e.g. an included template).
+    pub name: String,
+    /// Line number (1-based) of the error.
+    pub line: usize,
+    /// Column number (1-based) of the error, if known.
+    pub column: Option<usize>,
+    /// Template expression that caused the error, if known.
+    pub expression: Option<String>,
+    /// JSON path of the context value involved in the error, if known.
+    pub json_path: Option<String>,
+}
+
 impl WeaverError<Error> for Error {
     fn compound(errors: Vec<Error>) -> Error {
         Self::compound_error(errors)
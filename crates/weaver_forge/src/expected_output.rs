@@ -0,0 +1,208 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Test the templates of a target by comparing the artifacts generated from a
+//! (usually small) fixture registry with checked-in expected files.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+use walkdir::WalkDir;
+
+use weaver_common::Logger;
+use weaver_diff::{unified_diff, unified_diff_dir};
+
+use crate::error::Error;
+use crate::error::Error::ExpectedOutputFailed;
+use crate::{OutputDirective, TemplateEngine};
+
+/// A generated file that differs from its expected content.
+#[derive(Debug, Clone)]
+pub struct Mismatch {
+    /// Path of the file, relative to the expected output directory.
+    pub path: PathBuf,
+    /// Unified diff between the expected content and the generated content.
+    pub diff: String,
+}
+
+/// Result of the comparison of the generated artifacts with the expected files.
+#[derive(Debug, Default)]
+pub struct ExpectedOutputReport {
+    /// Generated files that differ from the expected files, including the files
+    /// missing from one of the two directories.
+    pub mismatches: Vec<Mismatch>,
+    /// Whether the expected files have been replaced with the generated files.
+    pub updated: bool,
+}
+
+impl ExpectedOutputReport {
+    /// Returns true if the generated artifacts match the expected files (or if the
+    /// expected files have been updated).
+    #[must_use]
+    pub fn passed(&self) -> bool {
+        self.updated || self.mismatches.is_empty()
+    }
+}
+
+impl TemplateEngine {
+    /// Generate the artifacts from a serializable context into a temporary directory
+    /// and compare them with the files of `expected_dir`.
+    ///
+    /// When `update` is true, the content of `expected_dir` is replaced with the
+    /// generated artifacts.
+    pub fn check_expected_output<T: Serialize>(
+        &self,
+        log: impl Logger + Clone + Sync,
+        context: &T,
+        expected_dir: &Path,
+        update: bool,
+    ) -> Result<ExpectedOutputReport, Error> {
+        let io_error = |e: std::io::Error| ExpectedOutputFailed {
+            expected_dir: expected_dir.to_path_buf(),
+            error: e.to_string(),
+        };
+
+        let observed_dir = tempfile::Builder::new()
+            .prefix("weaver-expected-output")
+            .tempdir()
+            .map_err(io_error)?;
+        self.generate(log, context, observed_dir.path(), &OutputDirective::File)?;
+
+        let mut mismatches: Vec<Mismatch> = unified_diff_dir(expected_dir, observed_dir.path())
+            .map_err(io_error)?
+            .into_iter()
+            .map(|(path, diff)| Mismatch { path, diff })
+            .collect();
+
+        // Expected files that have not been generated.
+        for entry in WalkDir::new(expected_dir)
+            .sort_by_file_name()
+            .into_iter()
+            .filter_map(|e| e.ok())
+            .filter(|e| e.path().is_file())
+        {
+            let path = entry
+                .path()
+                .strip_prefix(expected_dir)
+                .map_err(|e| io_error(std::io::Error::other(e)))?;
+            if !observed_dir.path().join(path).exists() {
+                let expected = fs::read_to_string(entry.path()).map_err(io_error)?;
+                mismatches.push(Mismatch {
+                    path: path.to_path_buf(),
+                    diff: unified_diff(
+                        &expected,
+                        "",
+                        &entry.path().display().to_string(),
+                        "/dev/null",
+                    ),
+                });
+            }
+        }
+        mismatches.sort_by(|a, b| a.path.cmp(&b.path));
+
+        let updated = update && !mismatches.is_empty();
+        if updated {
+            if expected_dir.exists() {
+                fs::remove_dir_all(expected_dir).map_err(io_error)?;
+            }
+            copy_dir(observed_dir.path(), expected_dir).map_err(io_error)?;
+        }
+
+        Ok(ExpectedOutputReport {
+            mismatches,
+            updated,
+        })
+    }
+}
+
+/// Recursively copy the files of a directory into another directory.
+fn copy_dir(from: &Path, to: &Path) -> std::io::Result<()> {
+    for entry in WalkDir::new(from).into_iter().filter_map(|e| e.ok()) {
+        let relative_path = entry
+            .path()
+            .strip_prefix(from)
+            .map_err(std::io::Error::other)?;
+        let target = to.join(relative_path);
+        if entry.path().is_dir() {
+            fs::create_dir_all(&target)?;
+        } else {
+            _ = fs::copy(entry.path(), &target)?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::path::PathBuf;
+
+    use tempdir::TempDir;
+
+    use weaver_common::TestLogger;
+    use weaver_resolver::SchemaResolver;
+    use weaver_semconv::registry::SemConvRegistry;
+
+    use crate::config::Params;
+    use crate::file_loader::FileSystemFileLoader;
+    use crate::registry::ResolvedRegistry;
+    use crate::TemplateEngine;
+
+    #[test]
+    fn test_check_expected_output() {
+        let logger = TestLogger::default();
+        let loader = FileSystemFileLoader::try_new("whitespace_control_templates".into(), "test")
+            .expect("Failed to create file system loader");
+        let engine = TemplateEngine::try_new(loader, Params::default())
+            .expect("Failed to create template engine");
+
+        let registry_id = "default";
+        let mut registry = SemConvRegistry::try_from_path_pattern(registry_id, "data/*.yaml")
+            .expect("Failed to load registry");
+        let schema = SchemaResolver::resolve_semantic_convention_registry(&mut registry)
+            .expect("Failed to resolve registry");
+        let template_registry = ResolvedRegistry::try_from_resolved_registry(
+            schema.registry(registry_id).expect("registry not found"),
+            schema.catalog(),
+        )
+        .expect("Failed to create the context for the template evaluation");
+
+        // The checked-in expected files match.
+        let report = engine
+            .check_expected_output(
+                logger.clone(),
+                &template_registry,
+                "whitespace_control_templates/test/expected_output".as_ref(),
+                false,
+            )
+            .expect("Failed to check the expected output");
+        assert!(report.passed());
+
+        // Missing and stale expected files are reported, then updated.
+        let temp_dir = TempDir::new("expected").expect("Failed to create temp dir");
+        let expected_dir = temp_dir.path().join("expected");
+        fs::create_dir_all(&expected_dir).expect("Failed to create expected dir");
+        fs::write(expected_dir.join("stale.md"), "stale").expect("Failed to write file");
+
+        let report = engine
+            .check_expected_output(logger.clone(), &template_registry, &expected_dir, false)
+            .expect("Failed to check the expected output");
+        assert!(!report.passed());
+        let paths: Vec<PathBuf> = report.mismatches.iter().map(|m| m.path.clone()).collect();
+        assert_eq!(
+            paths,
+            vec![PathBuf::from("registry.md"), PathBuf::from("stale.md")]
+        );
+
+        let report = engine
+            .check_expected_output(logger.clone(), &template_registry, &expected_dir, true)
+            .expect("Failed to update the expected output");
+        assert!(report.passed() && report.updated);
+        assert!(!expected_dir.join("stale.md").exists());
+
+        let report = engine
+            .check_expected_output(logger, &template_registry, &expected_dir, false)
+            .expect("Failed to check the expected output");
+        assert!(report.passed() && !report.updated);
+    }
+}
@@ -655,6 +655,7 @@ mod tests {
         let attributes: Vec<Attribute> = vec![
             Attribute {
                 name: "rec.a".into(),
+                short_name: Default::default(),
                 r#type: AttributeType::PrimitiveOrArray(PrimitiveOrArrayTypeSpec::String),
                 brief: "".into(),
                 examples: None,
@@ -664,12 +665,16 @@ mod tests {
                 note: "".into(),
                 stability: None,
                 deprecated: None,
+                renamed_from: vec![],
+                sensitivity: None,
+                annotations: Default::default(),
                 tags: None,
                 value: None,
                 prefix: false,
             },
             Attribute {
                 name: "rec.b".into(),
+                short_name: Default::default(),
                 r#type: AttributeType::PrimitiveOrArray(PrimitiveOrArrayTypeSpec::String),
                 brief: "".into(),
                 examples: None,
@@ -679,12 +684,16 @@ mod tests {
                 note: "".into(),
                 stability: None,
                 deprecated: None,
+                renamed_from: vec![],
+                sensitivity: None,
+                annotations: Default::default(),
                 tags: None,
                 value: None,
                 prefix: false,
             },
             Attribute {
                 name: "crec.a".into(),
+                short_name: Default::default(),
                 r#type: AttributeType::PrimitiveOrArray(PrimitiveOrArrayTypeSpec::String),
                 brief: "".into(),
                 examples: None,
@@ -694,12 +703,16 @@ mod tests {
                 note: "".into(),
                 stability: None,
                 deprecated: None,
+                renamed_from: vec![],
+                sensitivity: None,
+                annotations: Default::default(),
                 tags: None,
                 value: None,
                 prefix: false,
             },
             Attribute {
                 name: "crec.b".into(),
+                short_name: Default::default(),
                 r#type: AttributeType::PrimitiveOrArray(PrimitiveOrArrayTypeSpec::String),
                 brief: "".into(),
                 examples: None,
@@ -709,12 +722,16 @@ mod tests {
                 note: "".into(),
                 stability: None,
                 deprecated: None,
+                renamed_from: vec![],
+                sensitivity: None,
+                annotations: Default::default(),
                 tags: None,
                 value: None,
                 prefix: false,
             },
             Attribute {
                 name: "rec.c".into(),
+                short_name: Default::default(),
                 r#type: AttributeType::PrimitiveOrArray(PrimitiveOrArrayTypeSpec::String),
                 brief: "".into(),
                 examples: None,
@@ -724,12 +741,16 @@ mod tests {
                 note: "".into(),
                 stability: None,
                 deprecated: None,
+                renamed_from: vec![],
+                sensitivity: None,
+                annotations: Default::default(),
                 tags: None,
                 value: None,
                 prefix: false,
             },
             Attribute {
                 name: "rec.d".into(),
+                short_name: Default::default(),
                 r#type: AttributeType::PrimitiveOrArray(PrimitiveOrArrayTypeSpec::String),
                 brief: "".into(),
                 examples: None,
@@ -739,12 +760,16 @@ mod tests {
                 note: "".into(),
                 stability: None,
                 deprecated: None,
+                renamed_from: vec![],
+                sensitivity: None,
+                annotations: Default::default(),
                 tags: None,
                 value: None,
                 prefix: false,
             },
             Attribute {
                 name: "opt.a".into(),
+                short_name: Default::default(),
                 r#type: AttributeType::PrimitiveOrArray(PrimitiveOrArrayTypeSpec::String),
                 brief: "".into(),
                 examples: None,
@@ -754,12 +779,16 @@ mod tests {
                 note: "".into(),
                 stability: None,
                 deprecated: None,
+                renamed_from: vec![],
+                sensitivity: None,
+                annotations: Default::default(),
                 tags: None,
                 value: None,
                 prefix: false,
             },
             Attribute {
                 name: "opt.b".into(),
+                short_name: Default::default(),
                 r#type: AttributeType::PrimitiveOrArray(PrimitiveOrArrayTypeSpec::String),
                 brief: "".into(),
                 examples: None,
@@ -769,12 +798,16 @@ mod tests {
                 note: "".into(),
                 stability: None,
                 deprecated: None,
+                renamed_from: vec![],
+                sensitivity: None,
+                annotations: Default::default(),
                 tags: None,
                 value: None,
                 prefix: false,
             },
             Attribute {
                 name: "req.a".into(),
+                short_name: Default::default(),
                 r#type: AttributeType::PrimitiveOrArray(PrimitiveOrArrayTypeSpec::String),
                 brief: "".into(),
                 examples: None,
@@ -784,12 +817,16 @@ mod tests {
                 note: "".into(),
                 stability: None,
                 deprecated: None,
+                renamed_from: vec![],
+                sensitivity: None,
+                annotations: Default::default(),
                 tags: None,
                 value: None,
                 prefix: false,
             },
             Attribute {
                 name: "req.b".into(),
+                short_name: Default::default(),
                 r#type: AttributeType::PrimitiveOrArray(PrimitiveOrArrayTypeSpec::String),
                 brief: "".into(),
                 examples: None,
@@ -799,6 +836,9 @@ mod tests {
                 note: "".into(),
                 stability: None,
                 deprecated: None,
+                renamed_from: vec![],
+                sensitivity: None,
+                annotations: Default::default(),
                 tags: None,
                 value: None,
                 prefix: false,
@@ -847,46 +887,58 @@ mod tests {
     fn test_required_and_not_required_filters() {
         let attrs = vec![
             Attribute {
-                name: "attr1".to_owned(),
+                name: "attr1".into(),
+                short_name: Default::default(),
                 r#type: AttributeType::PrimitiveOrArray(PrimitiveOrArrayTypeSpec::String),
-                brief: "".to_owned(),
+                brief: "".into(),
                 examples: None,
                 tag: None,
                 requirement_level: RequirementLevel::Basic(BasicRequirementLevelSpec::Required),
                 sampling_relevant: None,
-                note: "".to_owned(),
+                note: "".into(),
                 stability: None,
                 deprecated: None,
+                renamed_from: vec![],
+                sensitivity: None,
+                annotations: Default::default(),
                 tags: None,
                 value: None,
                 prefix: false,
             },
             Attribute {
-                name: "attr2".to_owned(),
+                name: "attr2".into(),
+                short_name: Default::default(),
                 r#type: AttributeType::PrimitiveOrArray(PrimitiveOrArrayTypeSpec::Int),
-                brief: "".to_owned(),
+                brief: "".into(),
                 examples: None,
                 tag: None,
                 requirement_level: RequirementLevel::Basic(BasicRequirementLevelSpec::Recommended),
                 sampling_relevant: None,
-                note: "".to_owned(),
+                note: "".into(),
                 stability: None,
                 deprecated: None,
+                renamed_from: vec![],
+                sensitivity: None,
+                annotations: Default::default(),
                 tags: None,
                 value: None,
                 prefix: false,
             },
             Attribute {
-                name: "attr3".to_owned(),
+                name: "attr3".into(),
+                short_name: Default::default(),
                 r#type: AttributeType::PrimitiveOrArray(PrimitiveOrArrayTypeSpec::String),
-                brief: "".to_owned(),
+                brief: "".into(),
                 examples: None,
                 tag: None,
                 requirement_level: RequirementLevel::Basic(BasicRequirementLevelSpec::Required),
                 sampling_relevant: None,
-                note: "".to_owned(),
+                note: "".into(),
                 stability: None,
                 deprecated: None,
+                renamed_from: vec![],
+                sensitivity: None,
+                annotations: Default::default(),
                 tags: None,
                 value: None,
                 prefix: false,
@@ -1164,16 +1216,20 @@ mod tests {
 
         let mut env = Environment::new();
         let attr = Attribute {
-            name: "attr1".to_owned(),
+            name: "attr1".into(),
+            short_name: Default::default(),
             r#type: enum_type(vec!["value1".into(), "value2".into()]),
-            brief: "A brief description".to_owned(),
+            brief: "A brief description".into(),
             examples: None,
             tag: None,
             requirement_level: RequirementLevel::Basic(BasicRequirementLevelSpec::Required),
             sampling_relevant: None,
-            note: "A note".to_owned(),
+            note: "A note".into(),
             stability: None,
             deprecated: None,
+            renamed_from: vec![],
+            sensitivity: None,
+            annotations: Default::default(),
             tags: None,
             value: None,
             prefix: false,
@@ -1191,16 +1247,20 @@ mod tests {
         );
 
         let attr = Attribute {
-            name: "attr1".to_owned(),
+            name: "attr1".into(),
+            short_name: Default::default(),
             r#type: AttributeType::PrimitiveOrArray(PrimitiveOrArrayTypeSpec::String),
-            brief: "A brief description".to_owned(),
+            brief: "A brief description".into(),
             examples: None,
             tag: None,
             requirement_level: RequirementLevel::Basic(BasicRequirementLevelSpec::Required),
             sampling_relevant: None,
-            note: "A note".to_owned(),
+            note: "A note".into(),
             stability: None,
             deprecated: None,
+            renamed_from: vec![],
+            sensitivity: None,
+            annotations: Default::default(),
             tags: None,
             value: None,
             prefix: false,
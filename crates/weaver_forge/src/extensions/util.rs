@@ -13,6 +13,7 @@ pub(crate) fn add_filters(env: &mut Environment<'_>, target_config: &TargetConfi
     env.add_filter("acronym", acronym(target_config.acronyms.clone()));
     env.add_filter("flatten", flatten);
     env.add_filter("split_id", split_id);
+    env.add_filter("workflow_command_escape", workflow_command_escape);
 }
 
 // Helper filter to work around lack of `list.append()` support in minijinja.
@@ -44,6 +45,22 @@ fn split_id(value: Value) -> Result<Vec<Value>, minijinja::Error> {
     }
 }
 
+/// Escapes a string for a GitHub Actions workflow command (e.g.
+/// `::error file={file}::{message}`). The message of a command escapes `%`,
+/// `\r`, and `\n`, and the values of its properties (`property` set to true)
+/// also escape `:` and `,`.
+fn workflow_command_escape(input: &str, property: Option<bool>) -> String {
+    let escaped = input
+        .replace('%', "%25")
+        .replace('\r', "%0D")
+        .replace('\n', "%0A");
+    if property.unwrap_or(false) {
+        escaped.replace(':', "%3A").replace(',', "%2C")
+    } else {
+        escaped
+    }
+}
+
 /// Create a filter that replaces acronyms in the input string with the full
 /// name defined in the `acronyms` list.
 ///
@@ -6,6 +6,7 @@ use crate::error::Error;
 use core::fmt;
 use jaq_interpret::{Ctx, FilterT, RcIter, Val};
 use std::fmt::Debug;
+use weaver_common::error::handle_errors;
 
 /// A filter that can be applied to a JSON value.
 pub struct Filter {
@@ -74,6 +75,35 @@ impl Filter {
     }
 }
 
+impl Filter {
+    /// Apply the filter to a JSON value and return all the values produced by
+    /// the filter, in order. The errors raised while evaluating the filter are
+    /// reported.
+    pub fn apply_all(
+        &self,
+        ctx: serde_json::Value,
+        jq_ctx: Vec<Val>,
+    ) -> Result<Vec<serde_json::Value>, Error> {
+        let inputs = RcIter::new(core::iter::empty());
+        let filter_result = self.filter.run((Ctx::new(jq_ctx, &inputs), Val::from(ctx)));
+        let mut errs = Vec::new();
+        let mut values = Vec::new();
+
+        for r in filter_result {
+            match r {
+                Ok(v) => values.push(serde_json::Value::from(v)),
+                Err(e) => errs.push(Error::FilterError {
+                    filter: self.filter_expr.clone(),
+                    error: e.to_string(),
+                }),
+            }
+        }
+
+        handle_errors(errs)?;
+        Ok(values)
+    }
+}
+
 impl Debug for Filter {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "Filter({})", self.filter_expr)
@@ -173,4 +203,34 @@ end"#;
         let result = filter.apply(input.clone(), ctx).unwrap();
         assert_eq!(result, serde_json::Value::Null);
     }
+
+    #[test]
+    fn test_filter_apply_all() {
+        let input = serde_json::json!({
+            "groups": [
+                {"type": "metric", "metric_name": "m1"},
+                {"type": "span"},
+                {"type": "metric", "metric_name": "m2"},
+            ]
+        });
+        let filter = super::Filter::try_new(
+            r#".groups[] | select(.type == "metric") | .metric_name"#,
+            Vec::new(),
+        )
+        .unwrap();
+        let result = filter.apply_all(input.clone(), Vec::new()).unwrap();
+        assert_eq!(
+            result,
+            vec![serde_json::json!("m1"), serde_json::json!("m2")]
+        );
+
+        // A single value is not wrapped in an array.
+        let filter = super::Filter::try_new(".groups | length", Vec::new()).unwrap();
+        let result = filter.apply_all(input.clone(), Vec::new()).unwrap();
+        assert_eq!(result, vec![serde_json::json!(3)]);
+
+        // Runtime errors are reported.
+        let filter = super::Filter::try_new(".groups[] | .type + 1", Vec::new()).unwrap();
+        assert!(filter.apply_all(input, Vec::new()).is_err());
+    }
 }
@@ -0,0 +1,227 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Cache of the hashes of the generated artifacts.
+//!
+//! For each generated file, the cache records a hash of the inputs used to render
+//! it (template files, configuration, parameters, and filtered context) and a hash
+//! of the generated content. A file whose inputs and content have not changed since
+//! the previous generation is neither rendered nor rewritten, so build systems don't
+//! see spurious modification time changes.
+
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+/// Hashes of the inputs and of the content of a generated file.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+struct CacheEntry {
+    /// Hash of the inputs used to render the file.
+    inputs: String,
+    /// Hash of the generated content.
+    content: String,
+}
+
+/// Content of the cache file.
+#[derive(Serialize, Deserialize, Debug, Default)]
+struct CacheFile {
+    /// Generated files (relative to the output directory) and their hashes.
+    files: BTreeMap<PathBuf, CacheEntry>,
+}
+
+/// Outcome of the generation of a single file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum FileStatus {
+    /// The file has been (re)written.
+    Regenerated,
+    /// The file was already up to date and has not been rewritten.
+    Skipped,
+}
+
+/// Hashes of the generated files for a single generation run.
+///
+/// The cache is loaded from the cache file (if any) at the beginning of the
+/// generation, updated while the files are generated (possibly in parallel),
+/// and saved back at the end of the generation.
+#[derive(Debug, Default)]
+pub(crate) struct GenerationCache {
+    /// Path of the cache file, `None` if the hashes are not persisted.
+    path: Option<PathBuf>,
+    /// Hash of the inputs shared by all the renderings (templates, configuration,
+    /// and parameters).
+    templates_hash: String,
+    /// Files generated by the previous run indexed by the hash of their inputs.
+    previous: HashMap<String, (PathBuf, CacheEntry)>,
+    /// Files generated by the current run.
+    current: Mutex<BTreeMap<PathBuf, CacheEntry>>,
+    /// Number of files (re)written by the current run.
+    regenerated: AtomicUsize,
+    /// Number of files left untouched by the current run.
+    skipped: AtomicUsize,
+}
+
+impl GenerationCache {
+    /// Loads the cache from the given file. A missing or unreadable cache file
+    /// results in an empty cache, i.e. all the files are rendered.
+    pub(crate) fn load(path: Option<&Path>, templates_hash: String) -> Self {
+        let previous = path
+            .and_then(|path| fs::read_to_string(path).ok())
+            .and_then(|content| serde_json::from_str::<CacheFile>(&content).ok())
+            .unwrap_or_default()
+            .files
+            .into_iter()
+            .map(|(file, entry)| (entry.inputs.clone(), (file, entry)))
+            .collect();
+
+        Self {
+            path: path.map(Path::to_path_buf),
+            templates_hash,
+            previous,
+            ..Default::default()
+        }
+    }
+
+    /// Returns the hash of all the inputs of the rendering of a template with
+    /// the given context.
+    pub(crate) fn inputs_hash(&self, template_path: &Path, ctx: &serde_json::Value) -> String {
+        hash(&[
+            self.templates_hash.as_bytes(),
+            template_path.to_string_lossy().as_bytes(),
+            ctx.to_string().as_bytes(),
+        ])
+    }
+
+    /// Returns the relative path of a file generated by the previous run from the
+    /// same inputs, if this file still exists in the output directory with the
+    /// content produced by the previous run. The file is then recorded as skipped.
+    pub(crate) fn skip_unchanged(&self, output_dir: &Path, inputs: &str) -> Option<PathBuf> {
+        let (file, entry) = self.previous.get(inputs)?;
+        let content = fs::read(output_dir.join(file)).ok()?;
+        if hash(&[&content]) != entry.content {
+            return None;
+        }
+        self.record(file.clone(), entry.clone(), FileStatus::Skipped);
+        Some(file.clone())
+    }
+
+    /// Records the generation of a file.
+    pub(crate) fn record_file(
+        &self,
+        file: PathBuf,
+        inputs: String,
+        content: &str,
+        status: FileStatus,
+    ) {
+        let entry = CacheEntry {
+            inputs,
+            content: hash(&[content.as_bytes()]),
+        };
+        self.record(file, entry, status);
+    }
+
+    fn record(&self, file: PathBuf, entry: CacheEntry, status: FileStatus) {
+        _ = self
+            .current
+            .lock()
+            .expect("Lock poisoned")
+            .insert(file, entry);
+        let counter = match status {
+            FileStatus::Regenerated => &self.regenerated,
+            FileStatus::Skipped => &self.skipped,
+        };
+        _ = counter.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Returns the number of files (re)written and the number of files left
+    /// untouched by the current run.
+    pub(crate) fn stats(&self) -> (usize, usize) {
+        (
+            self.regenerated.load(Ordering::Relaxed),
+            self.skipped.load(Ordering::Relaxed),
+        )
+    }
+
+    /// Saves the hashes of the files generated by the current run into the cache
+    /// file (if any).
+    pub(crate) fn save(&self) -> std::io::Result<()> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        let cache_file = CacheFile {
+            files: self.current.lock().expect("Lock poisoned").clone(),
+        };
+        if let Some(parent_dir) = path.parent() {
+            fs::create_dir_all(parent_dir)?;
+        }
+        let content = serde_json::to_string_pretty(&cache_file).map_err(std::io::Error::other)?;
+        fs::write(path, content)
+    }
+}
+
+/// Returns the hex-encoded SHA-256 hash of the concatenation of the given parts.
+/// Each part is prefixed with its length so that the boundaries between parts
+/// are part of the hash.
+pub(crate) fn hash(parts: &[&[u8]]) -> String {
+    let mut hasher = Sha256::new();
+    for part in parts {
+        hasher.update((part.len() as u64).to_le_bytes());
+        hasher.update(part);
+    }
+    hasher
+        .finalize()
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use tempdir::TempDir;
+
+    use super::*;
+
+    #[test]
+    fn test_skip_unchanged() {
+        let dir = TempDir::new("generation-cache").expect("Failed to create temp dir");
+        let output_dir = dir.path().join("output");
+        let cache_path = dir.path().join("cache.json");
+        fs::create_dir_all(&output_dir).expect("Failed to create output dir");
+        fs::write(output_dir.join("a.txt"), "a").expect("Failed to write file");
+
+        // First run: nothing to skip.
+        let cache = GenerationCache::load(Some(&cache_path), "templates".to_owned());
+        assert_eq!(cache.skip_unchanged(&output_dir, "inputs-a"), None);
+        cache.record_file(
+            PathBuf::from("a.txt"),
+            "inputs-a".to_owned(),
+            "a",
+            FileStatus::Regenerated,
+        );
+        assert_eq!(cache.stats(), (1, 0));
+        cache.save().expect("Failed to save the cache");
+
+        // Second run: same inputs and untouched file.
+        let cache = GenerationCache::load(Some(&cache_path), "templates".to_owned());
+        assert_eq!(
+            cache.skip_unchanged(&output_dir, "inputs-a"),
+            Some(PathBuf::from("a.txt"))
+        );
+        assert_eq!(cache.skip_unchanged(&output_dir, "inputs-b"), None);
+        assert_eq!(cache.stats(), (0, 1));
+
+        // Third run: the file has been modified outside of Weaver.
+        fs::write(output_dir.join("a.txt"), "modified").expect("Failed to write file");
+        let cache = GenerationCache::load(Some(&cache_path), "templates".to_owned());
+        assert_eq!(cache.skip_unchanged(&output_dir, "inputs-a"), None);
+    }
+
+    #[test]
+    fn test_hash() {
+        assert_eq!(hash(&[b"ab", b"c"]), hash(&[b"ab", b"c"]));
+        assert_ne!(hash(&[b"ab", b"c"]), hash(&[b"a", b"bc"]));
+    }
+}
@@ -3,7 +3,7 @@
 #![doc = include_str!("../README.md")]
 
 use std::borrow::Cow;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::ffi::OsString;
 use std::fmt::{Debug, Display, Formatter};
 use std::fs;
@@ -19,27 +19,27 @@ use rayon::iter::ParallelIterator;
 use serde::Serialize;
 
 use error::Error;
-use error::Error::{
-    ContextSerializationFailed, InvalidTemplateFile, TemplateEvaluationFailed,
-    WriteGeneratedCodeFailed,
-};
+use error::Error::{ContextSerializationFailed, InvalidTemplateFile, WriteGeneratedCodeFailed};
 use weaver_common::error::handle_errors;
 use weaver_common::Logger;
 
-use crate::config::{ApplicationMode, Params, TargetConfig};
-use crate::debug::error_summary;
+use crate::config::{ApplicationMode, Params, TargetConfig, TemplateConfig, WhitespaceControl};
+use crate::debug::template_evaluation_error;
 use crate::error::Error::InvalidConfigFile;
 use crate::extensions::{ansi, case, code, otel, util};
 use crate::file_loader::FileLoader;
 use crate::filter::Filter;
+use crate::generation_cache::{FileStatus, GenerationCache};
 use crate::registry::{ResolvedGroup, ResolvedRegistry};
 
 pub mod config;
 pub mod debug;
 pub mod error;
+pub mod expected_output;
 pub mod extensions;
 pub mod file_loader;
-mod filter;
+pub mod filter;
+mod generation_cache;
 pub mod registry;
 
 /// Name of the Weaver configuration file.
@@ -128,6 +128,12 @@ pub struct TemplateEngine {
 
     /// Target configuration
     target_config: TargetConfig,
+
+    /// Optional file used to cache the hashes of the generated files.
+    generation_cache: Option<PathBuf>,
+
+    /// Enable the debug mode of the template environment.
+    debug: bool,
 }
 
 /// Global context for the template engine.
@@ -177,9 +183,30 @@ impl TemplateEngine {
         Ok(Self {
             file_loader: Arc::new(loader),
             target_config,
+            generation_cache: None,
+            debug: false,
         })
     }
 
+    /// Set the file used to cache the hashes of the inputs and of the content of
+    /// the generated files. When set, the files whose inputs have not changed since
+    /// the previous generation are not rendered again.
+    ///
+    /// Independently of this cache, a generated file is never rewritten when its
+    /// content is unchanged.
+    pub fn set_generation_cache(&mut self, cache_file: PathBuf) {
+        self.generation_cache = Some(cache_file);
+    }
+
+    /// Enable or disable the debug mode of the template environment.
+    ///
+    /// In debug mode, the template sources and spans are kept to report the
+    /// snippet, the expression, and the context path of evaluation errors.
+    /// Otherwise, only the template name and line are reported.
+    pub fn set_debug(&mut self, debug: bool) {
+        self.debug = debug;
+    }
+
     /// Generate a template snippet from serializable context and a snippet identifier.
     ///
     /// # Arguments
@@ -199,7 +226,7 @@ impl TemplateEngine {
             error: e.to_string(),
         })?;
 
-        let engine = self.template_engine()?;
+        let engine = self.template_engine(&self.target_config.whitespace_control)?;
         let template = engine
             .get_template(&snippet_id)
             .map_err(error::jinja_err_convert)?;
@@ -231,12 +258,23 @@ impl TemplateEngine {
         let tmpl_matcher = self.target_config.template_matcher()?;
 
         // Create a read-only context for the filter evaluations
-        let context = serde_json::to_value(context).map_err(|e| ContextSerializationFailed {
-            error: e.to_string(),
-        })?;
+        let mut context =
+            serde_json::to_value(context).map_err(|e| ContextSerializationFailed {
+                error: e.to_string(),
+            })?;
+        if self.target_config.sort_context {
+            registry::sort_context(&mut context);
+        }
 
         let mut errors = Vec::new();
 
+        let cache = match output_directive {
+            OutputDirective::File => {
+                GenerationCache::load(self.generation_cache.as_deref(), self.templates_hash()?)
+            }
+            _ => GenerationCache::default(),
+        };
+
         // Build JQ context from the params.
         let (jq_vars, jq_ctx): (Vec<String>, Vec<serde_json::Value>) = self
             .target_config
@@ -305,6 +343,8 @@ impl TemplateEngine {
                                 relative_path.as_path(),
                                 output_directive,
                                 output_dir,
+                                &cache,
+                                template,
                             ) {
                                 return Some(e);
                             }
@@ -323,6 +363,8 @@ impl TemplateEngine {
                                             relative_path.as_path(),
                                             output_directive,
                                             output_dir,
+                                            &cache,
+                                            template,
                                         ) {
                                             return Some(e);
                                         }
@@ -342,6 +384,8 @@ impl TemplateEngine {
                                 relative_path.as_path(),
                                 output_directive,
                                 output_dir,
+                                &cache,
+                                template,
                             ) {
                                 return Some(e);
                             }
@@ -353,9 +397,49 @@ impl TemplateEngine {
             .collect::<Vec<Error>>();
 
         errs.extend(errors);
+
+        if let OutputDirective::File = output_directive {
+            if let Err(e) = cache.save() {
+                log.warn(&format!("Failed to save the generation cache: {}", e));
+            }
+            let (regenerated, skipped) = cache.stats();
+            log.success(&format!(
+                "{} file(s) regenerated, {} file(s) unchanged",
+                regenerated, skipped
+            ));
+        }
+
         handle_errors(errs)
     }
 
+    /// Returns a hash of everything that influences the rendering of the templates
+    /// besides the context: the version of this crate, the template files
+    /// (including the configuration file), and the parameters.
+    fn templates_hash(&self) -> Result<String, Error> {
+        let mut parts = vec![env!("CARGO_PKG_VERSION").as_bytes().to_vec()];
+
+        let mut files = self.file_loader.all_files();
+        files.sort();
+        for file in files {
+            let content = self
+                .file_loader
+                .load_file(&file.to_string_lossy())?
+                .unwrap_or_default();
+            parts.push(file.to_string_lossy().as_bytes().to_vec());
+            parts.push(content.into_bytes());
+        }
+
+        let params: BTreeMap<_, _> = self.target_config.params.iter().collect();
+        parts.push(
+            serde_json::to_vec(&params).map_err(|e| ContextSerializationFailed {
+                error: e.to_string(),
+            })?,
+        );
+
+        let parts: Vec<&[u8]> = parts.iter().map(Vec::as_slice).collect();
+        Ok(generation_cache::hash(&parts))
+    }
+
     #[allow(clippy::print_stdout)] // This is used for the OutputDirective::Stdout variant
     #[allow(clippy::print_stderr)] // This is used for the OutputDirective::Stderr variant
     fn evaluate_template(
@@ -365,6 +449,8 @@ impl TemplateEngine {
         template_path: &Path,
         output_directive: &OutputDirective,
         output_dir: &Path,
+        cache: &GenerationCache,
+        template_config: &TemplateConfig,
     ) -> Result<(), Error> {
         // By default, the file name is the template file name without the extension ".j2"
         let file_name = template_path
@@ -373,10 +459,22 @@ impl TemplateEngine {
             .to_string_lossy()
             .trim_end_matches(".j2")
             .to_owned();
+        let inputs = cache.inputs_hash(template_path, &ctx);
+        if let OutputDirective::File = output_directive {
+            if let Some(file) = cache.skip_unchanged(output_dir, &inputs) {
+                log.log(&format!("Unchanged file {:?}", output_dir.join(file)));
+                return Ok(());
+            }
+        }
+
         let template_object = TemplateObject {
             file_name: Arc::new(Mutex::new(file_name)),
         };
-        let mut engine = self.template_engine()?;
+        let whitespace_control = template_config
+            .whitespace_control
+            .as_ref()
+            .unwrap_or(&self.target_config.whitespace_control);
+        let mut engine = self.template_engine(whitespace_control)?;
         let template_file = template_path.to_str().ok_or(InvalidTemplateFile {
             template: template_path.to_path_buf(),
             error: "".to_owned(),
@@ -402,11 +500,7 @@ impl TemplateEngine {
 
         let output = template
             .render(ctx.clone())
-            .map_err(|e| TemplateEvaluationFailed {
-                template: template_path.to_path_buf(),
-                error_id: e.to_string(),
-                error: error_summary(e),
-            })?;
+            .map_err(|e| template_evaluation_error(template_path, &e))?;
         match output_directive {
             OutputDirective::Stdout => {
                 println!("{}", output);
@@ -415,16 +509,29 @@ impl TemplateEngine {
                 eprintln!("{}", output);
             }
             OutputDirective::File => {
-                let generated_file =
-                    Self::save_generated_code(output_dir, template_object.file_name(), output)?;
-                log.success(&format!("Generated file {:?}", generated_file));
+                let relative_path = template_object.file_name();
+                let generated_file = output_dir.join(&relative_path);
+                let status = Self::save_generated_code(&generated_file, &output)?;
+                match status {
+                    FileStatus::Regenerated => {
+                        log.success(&format!("Generated file {:?}", generated_file));
+                    }
+                    FileStatus::Skipped => {
+                        log.log(&format!("Unchanged file {:?}", generated_file));
+                    }
+                }
+                cache.record_file(relative_path, inputs, &output, status);
             }
         }
         Ok(())
     }
 
-    /// Create a new template engine based on the target configuration.
-    fn template_engine(&self) -> Result<Environment<'_>, Error> {
+    /// Create a new template engine based on the target configuration and the
+    /// given whitespace behavior.
+    fn template_engine(
+        &self,
+        whitespace_control: &WhitespaceControl,
+    ) -> Result<Environment<'_>, Error> {
         let mut env = Environment::new();
         let template_syntax = self.target_config.template_syntax.clone();
 
@@ -455,10 +562,10 @@ impl TemplateEngine {
                 .map_err(|e| minijinja::Error::new(ErrorKind::InvalidOperation, e.to_string()))
         });
         env.set_syntax(syntax);
+        env.set_debug(self.debug);
 
         // Jinja whitespace control
         // https://docs.rs/minijinja/latest/minijinja/syntax/index.html#whitespace-control
-        let whitespace_control = self.target_config.whitespace_control.clone();
         env.set_trim_blocks(whitespace_control.trim_blocks);
         env.set_lstrip_blocks(whitespace_control.lstrip_blocks);
         env.set_keep_trailing_newline(whitespace_control.keep_trailing_newline);
@@ -472,40 +579,43 @@ impl TemplateEngine {
         Ok(env)
     }
 
-    /// Save the generated code to the output directory.
+    /// Save the generated code into the given file, unless the file already
+    /// exists with the same content.
     fn save_generated_code(
-        output_dir: &Path,
-        relative_path: PathBuf,
-        generated_code: String,
-    ) -> Result<PathBuf, Error> {
+        output_file_path: &Path,
+        generated_code: &str,
+    ) -> Result<FileStatus, Error> {
+        if fs::read(output_file_path).is_ok_and(|content| content == generated_code.as_bytes()) {
+            return Ok(FileStatus::Skipped);
+        }
+
         // Create all intermediary directories if they don't exist
-        let output_file_path = output_dir.join(relative_path);
         if let Some(parent_dir) = output_file_path.parent() {
             if let Err(e) = fs::create_dir_all(parent_dir) {
                 return Err(WriteGeneratedCodeFailed {
-                    template: output_file_path.clone(),
+                    template: output_file_path.to_path_buf(),
                     error: format!("{}", e),
                 });
             }
         }
 
         // Write the generated code to the output directory
-        fs::write(output_file_path.clone(), generated_code).map_err(|e| {
-            WriteGeneratedCodeFailed {
-                template: output_file_path.clone(),
-                error: format!("{}", e),
-            }
+        fs::write(output_file_path, generated_code).map_err(|e| WriteGeneratedCodeFailed {
+            template: output_file_path.to_path_buf(),
+            error: format!("{}", e),
         })?;
 
-        Ok(output_file_path)
+        Ok(FileStatus::Regenerated)
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use std::fs;
     use std::path::Path;
 
     use globset::Glob;
+    use tempdir::TempDir;
 
     use weaver_common::TestLogger;
     use weaver_diff::diff_dir;
@@ -656,6 +766,7 @@ mod tests {
             pattern: Glob::new("converter.md").unwrap(),
             filter: ".".to_owned(),
             application_mode: ApplicationMode::Single,
+            whitespace_control: None,
         });
 
         let registry_id = "default";
@@ -733,4 +844,112 @@ mod tests {
         )
         .unwrap());
     }
+
+    #[test]
+    fn test_template_whitespace_control() {
+        let logger = TestLogger::default();
+        let loader = FileSystemFileLoader::try_new("whitespace_control_templates".into(), "test")
+            .expect("Failed to create file system loader");
+        let mut engine = super::TemplateEngine::try_new(loader, Params::default())
+            .expect("Failed to create template engine");
+
+        // The whitespace behavior defined for the template overrides the one
+        // defined for the target.
+        engine.target_config.whitespace_control = Default::default();
+        engine.target_config.templates = vec![serde_yaml::from_str(
+            r#"
+pattern: registry.md
+application_mode: single
+whitespace_control:
+  trim_blocks: true
+  lstrip_blocks: true
+  keep_trailing_newline: true
+"#,
+        )
+        .expect("Failed to parse the template configuration")];
+
+        let registry_id = "default";
+        let mut registry = SemConvRegistry::try_from_path_pattern(registry_id, "data/*.yaml")
+            .expect("Failed to load registry");
+        let schema = SchemaResolver::resolve_semantic_convention_registry(&mut registry)
+            .expect("Failed to resolve registry");
+        let template_registry = ResolvedRegistry::try_from_resolved_registry(
+            schema.registry(registry_id).expect("registry not found"),
+            schema.catalog(),
+        )
+        .expect("Failed to create the context for the template evaluation");
+
+        let temp_dir = TempDir::new("whitespace").expect("Failed to create temp dir");
+        engine
+            .generate(
+                logger.clone(),
+                &template_registry,
+                temp_dir.path(),
+                &OutputDirective::File,
+            )
+            .expect("Failed to generate registry assets");
+
+        assert!(diff_dir(
+            Path::new("whitespace_control_templates/test/expected_output"),
+            temp_dir.path()
+        )
+        .unwrap());
+    }
+
+    #[test]
+    fn test_incremental_generation() {
+        let logger = TestLogger::default();
+        let loader = FileSystemFileLoader::try_new("whitespace_control_templates".into(), "test")
+            .expect("Failed to create file system loader");
+        let mut engine = super::TemplateEngine::try_new(loader, Params::default())
+            .expect("Failed to create template engine");
+        let temp_dir = TempDir::new("incremental").expect("Failed to create temp dir");
+        let output_dir = temp_dir.path().join("output");
+        engine.set_generation_cache(temp_dir.path().join("cache.json"));
+
+        let registry_id = "default";
+        let mut registry = SemConvRegistry::try_from_path_pattern(registry_id, "data/*.yaml")
+            .expect("Failed to load registry");
+        let schema = SchemaResolver::resolve_semantic_convention_registry(&mut registry)
+            .expect("Failed to resolve registry");
+        let template_registry = ResolvedRegistry::try_from_resolved_registry(
+            schema.registry(registry_id).expect("registry not found"),
+            schema.catalog(),
+        )
+        .expect("Failed to create the context for the template evaluation");
+
+        let generate = || {
+            engine
+                .generate(
+                    logger.clone(),
+                    &template_registry,
+                    &output_dir,
+                    &OutputDirective::File,
+                )
+                .expect("Failed to generate registry assets");
+        };
+        let generated_file = output_dir.join("registry.md");
+        let modified = || {
+            fs::metadata(&generated_file)
+                .and_then(|metadata| metadata.modified())
+                .expect("Failed to get the modification time")
+        };
+
+        generate();
+        assert!(temp_dir.path().join("cache.json").exists());
+        let first_generation = modified();
+
+        // Unchanged inputs: the file is not rewritten.
+        generate();
+        assert_eq!(modified(), first_generation);
+
+        // A file modified outside of Weaver is regenerated.
+        fs::write(&generated_file, "modified").expect("Failed to write file");
+        generate();
+        assert!(diff_dir(
+            Path::new("whitespace_control_templates/test/expected_output"),
+            output_dir.as_path()
+        )
+        .unwrap());
+    }
 }
@@ -7,10 +7,15 @@
 use crate::error::Error;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use weaver_resolved_schema::attribute::Attribute;
 use weaver_resolved_schema::catalog::Catalog;
+use weaver_resolved_schema::interned::InternedStr;
 use weaver_resolved_schema::lineage::GroupLineage;
 use weaver_resolved_schema::registry::{Constraint, Group, Registry};
+use weaver_resolved_schema::AttributeUsage;
+use weaver_semconv::annotation::Annotations;
+use weaver_semconv::attribute::RequirementLevel;
 use weaver_semconv::group::{GroupType, InstrumentSpec, SpanKindSpec};
 use weaver_semconv::stability::Stability;
 
@@ -20,10 +25,23 @@ use weaver_semconv::stability::Stability;
 #[serde(deny_unknown_fields)]
 pub struct ResolvedRegistry {
     /// The semantic convention registry url.
+    #[serde(default)]
     #[serde(skip_serializing_if = "String::is_empty")]
     pub registry_url: String,
     /// A list of semantic convention groups.
     pub groups: Vec<ResolvedGroup>,
+    /// Index of the groups using each attribute, by attribute name (see
+    /// [`weaver_resolved_schema::ResolvedTelemetrySchema::attribute_index`]).
+    /// The usages of each attribute are sorted by group id.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub attribute_index: BTreeMap<String, Vec<AttributeUsage>>,
+    /// The resource groups of the registry, listed separately for the
+    /// templates generating resource detectors (see
+    /// [`ResolvedRegistry::expose_resources`]).
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub resources: Vec<ResolvedGroup>,
 }
 
 /// Resolved group specification used in the context of the template engine.
@@ -34,6 +52,7 @@ pub struct ResolvedGroup {
     /// The type of the group including the specific fields for each type.
     pub r#type: GroupType,
     /// A brief description of the semantic convention.
+    #[serde(default)]
     #[serde(skip_serializing_if = "String::is_empty")]
     pub brief: String,
     /// A more elaborate description of the semantic convention.
@@ -44,8 +63,8 @@ pub struct ResolvedGroup {
     /// Prefix for the attributes for this semantic convention.
     /// It defaults to an empty string.
     #[serde(default)]
-    #[serde(skip_serializing_if = "String::is_empty")]
-    pub prefix: String,
+    #[serde(skip_serializing_if = "InternedStr::is_empty")]
+    pub prefix: InternedStr,
     /// Reference another semantic convention id. It inherits the prefix,
     /// constraints, and all attributes defined in the specified semantic
     /// convention.
@@ -97,13 +116,29 @@ pub struct ResolvedGroup {
     pub unit: Option<String>,
     /// The name of the event. If not specified, the prefix is used.
     /// If prefix is empty (or unspecified), name is required.
+    /// For a scope, the name of the instrumentation scope.
     pub name: Option<String>,
+    /// The requirement level of the version of the instrumentation scope.
+    /// Note: only valid if type is scope.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub scope_version: Option<RequirementLevel>,
     /// The lineage of the group.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub lineage: Option<GroupLineage>,
     /// The readable name for attribute groups used when generating registry tables.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub display_name: Option<String>,
+    /// The URL of the documentation of the group.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub doc_url: Option<String>,
+    /// The version of the registry in which the group was introduced.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub since_version: Option<String>,
+    /// The annotations of the group.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Annotations::is_empty")]
+    #[schemars(with = "BTreeMap<String, serde_json::Value>")]
+    pub annotations: Annotations,
 }
 
 impl ResolvedGroup {
@@ -154,8 +189,12 @@ impl ResolvedGroup {
             instrument: group.instrument.clone(),
             unit: group.unit.clone(),
             name: group.name.clone(),
+            scope_version: group.scope_version.clone(),
             lineage,
             display_name: group.display_name.clone(),
+            doc_url: group.doc_url.clone(),
+            since_version: group.since_version.clone(),
+            annotations: group.annotations.clone(),
         })
     }
 }
@@ -213,8 +252,12 @@ impl ResolvedRegistry {
                     instrument: group.instrument.clone(),
                     unit: group.unit.clone(),
                     name: group.name.clone(),
+                    scope_version: group.scope_version.clone(),
                     lineage,
                     display_name: group.display_name.clone(),
+                    doc_url: group.doc_url.clone(),
+                    since_version: group.since_version.clone(),
+                    annotations: group.annotations.clone(),
                 }
             })
             .collect();
@@ -223,18 +266,90 @@ impl ResolvedRegistry {
             return Err(Error::CompoundError(errors));
         }
 
-        Ok(Self {
+        let mut registry = Self {
             registry_url: registry.registry_url.clone(),
             groups,
-        })
+            attribute_index: BTreeMap::new(),
+            resources: Vec::new(),
+        };
+        registry.build_attribute_index();
+        registry.expose_resources();
+        Ok(registry)
+    }
+
+    /// Builds the index of the groups using each attribute (see
+    /// [`ResolvedRegistry::attribute_index`]).
+    pub fn build_attribute_index(&mut self) {
+        let mut attribute_index: BTreeMap<String, Vec<AttributeUsage>> = BTreeMap::new();
+        for group in &self.groups {
+            for attr in &group.attributes {
+                attribute_index
+                    .entry(attr.name.as_str().to_owned())
+                    .or_default()
+                    .push(AttributeUsage {
+                        group_id: group.id.clone(),
+                        group_type: group.r#type.clone(),
+                        requirement_level: attr.requirement_level.clone(),
+                    });
+            }
+        }
+        for usages in attribute_index.values_mut() {
+            usages.sort_by(|a, b| a.group_id.cmp(&b.group_id));
+        }
+        self.attribute_index = attribute_index;
+    }
+
+    /// Lists the resource groups of the registry in `resources`.
+    pub fn expose_resources(&mut self) {
+        self.resources = self
+            .groups
+            .iter()
+            .filter(|group| group.r#type == GroupType::Resource)
+            .cloned()
+            .collect();
+    }
+}
+
+/// Sorts the groups of a serialized registry by id, their attributes by name,
+/// and the members of enum attributes by id, so the template context doesn't
+/// depend on the order of the registry files (see the `sort_context` option of
+/// the `weaver.yaml` configuration file).
+pub(crate) fn sort_context(context: &mut serde_json::Value) {
+    fn sort_by_key<'a>(
+        values: Option<&'a mut serde_json::Value>,
+        key: &str,
+    ) -> &'a mut [serde_json::Value] {
+        match values.and_then(serde_json::Value::as_array_mut) {
+            Some(values) => {
+                values.sort_by(|a, b| {
+                    a.get(key)
+                        .and_then(|v| v.as_str())
+                        .cmp(&b.get(key).and_then(|v| v.as_str()))
+                });
+                values
+            }
+            None => &mut [],
+        }
+    }
+
+    for group_list in ["groups", "resources"] {
+        for group in sort_by_key(context.get_mut(group_list), "id") {
+            for attribute in sort_by_key(group.get_mut("attributes"), "name") {
+                let members = attribute
+                    .get_mut("type")
+                    .and_then(|r#type| r#type.get_mut("members"));
+                _ = sort_by_key(members, "id");
+            }
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use crate::registry::sort_context;
     use crate::ResolvedRegistry;
     use schemars::schema_for;
-    use serde_json::to_string_pretty;
+    use serde_json::{json, to_string_pretty};
 
     #[test]
     fn test_json_schema_gen() {
@@ -244,4 +359,37 @@ mod tests {
         // Ensure the schema can be serialized to a string
         assert!(to_string_pretty(&schema).is_ok());
     }
+
+    #[test]
+    fn test_sort_context() {
+        let mut context = json!({
+            "registry_url": "",
+            "groups": [
+                {"id": "b", "attributes": [
+                    {"name": "b.y", "type": "string"},
+                    {"name": "b.x", "type": {"members": [{"id": "z"}, {"id": "a"}]}}
+                ]},
+                {"id": "a", "attributes": []}
+            ]
+        });
+        sort_context(&mut context);
+        assert_eq!(
+            context,
+            json!({
+                "registry_url": "",
+                "groups": [
+                    {"id": "a", "attributes": []},
+                    {"id": "b", "attributes": [
+                        {"name": "b.x", "type": {"members": [{"id": "a"}, {"id": "z"}]}},
+                        {"name": "b.y", "type": "string"}
+                    ]}
+                ]
+            })
+        );
+
+        // Contexts without groups are left untouched.
+        let mut context = json!([{"id": "b"}, {"id": "a"}]);
+        sort_context(&mut context);
+        assert_eq!(context, json!([{"id": "b"}, {"id": "a"}]));
+    }
 }
@@ -0,0 +1,17 @@
+package live_check_advice
+
+# Attributes that are not defined in the registry must be prefixed by the
+# namespace of the company.
+deny[advice("missing_namespace_prefix", "improvement", "Define the attribute in the registry or use an `acme.` prefix")] {
+    input.definition == null
+    not startswith(input.attribute.name, "acme.")
+}
+
+advice(id, level, message) = violation {
+    violation := {
+        "id": id,
+        "type": "advice",
+        "level": level,
+        "message": message,
+    }
+}
@@ -0,0 +1,518 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Advice on the observed attributes, given by pluggable advisors.
+//!
+//! Each attribute observed by the live check is submitted, with its definition
+//! in the registry, to a list of advisors. Built-in advisors check the type,
+//! the enum value, and the stability of the attribute. User-defined advisors are
+//! Rego policies declaring the `live_check_advice` package, or WASM modules,
+//! both producing advice in the format of [`Violation::Advice`].
+
+use std::fmt::{Display, Formatter};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+use weaver_checker::violation::Violation;
+use weaver_checker::wasm::WasmPolicy;
+use weaver_checker::{Engine, PolicyStage};
+use weaver_resolved_schema::attribute::Attribute;
+use weaver_semconv::attribute::AttributeType;
+use weaver_semconv::stability::Stability;
+
+use crate::live_checker::is_enum_member;
+use crate::sample::SampleAttribute;
+use crate::Error;
+
+/// The level of an advice, from the least to the most severe.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AdviceLevel {
+    /// Useful information, nothing needs to be changed.
+    Information,
+    /// A change that would improve the telemetry.
+    Improvement,
+    /// A violation of the semantic conventions.
+    Violation,
+}
+
+impl Display for AdviceLevel {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AdviceLevel::Information => write!(f, "information"),
+            AdviceLevel::Improvement => write!(f, "improvement"),
+            AdviceLevel::Violation => write!(f, "violation"),
+        }
+    }
+}
+
+impl FromStr for AdviceLevel {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "information" => Ok(AdviceLevel::Information),
+            "improvement" => Ok(AdviceLevel::Improvement),
+            "violation" => Ok(AdviceLevel::Violation),
+            _ => Err(format!(
+                "unknown advice level `{}` (expected `information`, `improvement`, or `violation`)",
+                s
+            )),
+        }
+    }
+}
+
+/// An advice on an observed attribute.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize)]
+pub struct Advice {
+    /// The id of the advice (e.g. `type_mismatch`).
+    pub id: String,
+    /// The level of the advice.
+    pub level: AdviceLevel,
+    /// The name of the observed attribute.
+    pub attribute: String,
+    /// The remediation advice.
+    pub message: String,
+}
+
+impl Display for Advice {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "[{}] {}: {}", self.level, self.attribute, self.message)
+    }
+}
+
+/// An advice and the number of times it has been given.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct AdviceCount {
+    /// The advice.
+    #[serde(flatten)]
+    pub advice: Advice,
+    /// The number of times the advice has been given.
+    pub count: usize,
+}
+
+/// Gives advice on the observed attributes.
+pub trait Advisor {
+    /// Returns the id of the advisor.
+    fn id(&self) -> &str;
+
+    /// Returns the advice on an observed attribute, given its definition in the
+    /// registry (`None` if the attribute is not defined).
+    fn advise(
+        &mut self,
+        attribute: &SampleAttribute,
+        definition: Option<&Attribute>,
+    ) -> Result<Vec<Advice>, Error>;
+}
+
+/// The ids of the built-in advisors.
+pub const BUILTIN_ADVISORS: [&str; 3] = ["type", "enum", "stability"];
+
+/// Returns the built-in advisors, except the ones whose id is listed in
+/// `disabled`.
+pub fn builtin_advisors(disabled: &[String]) -> Result<Vec<Box<dyn Advisor>>, Error> {
+    if let Some(id) = disabled
+        .iter()
+        .find(|id| !BUILTIN_ADVISORS.contains(&id.as_str()))
+    {
+        return Err(Error::UnknownAdvisor {
+            id: id.clone(),
+            known: BUILTIN_ADVISORS.join(", "),
+        });
+    }
+    let advisors: Vec<Box<dyn Advisor>> = vec![
+        Box::new(TypeAdvisor),
+        Box::new(EnumAdvisor),
+        Box::new(StabilityAdvisor),
+    ];
+    Ok(advisors
+        .into_iter()
+        .filter(|advisor| !disabled.iter().any(|id| id == advisor.id()))
+        .collect())
+}
+
+/// Advises on the attributes whose value doesn't match the type defined in the
+/// registry.
+pub struct TypeAdvisor;
+
+impl Advisor for TypeAdvisor {
+    fn id(&self) -> &str {
+        "type"
+    }
+
+    fn advise(
+        &mut self,
+        attribute: &SampleAttribute,
+        definition: Option<&Attribute>,
+    ) -> Result<Vec<Advice>, Error> {
+        let Some(definition) = definition else {
+            return Ok(vec![]);
+        };
+        let expected = definition.r#type.value_type();
+        let message = match attribute.r#type() {
+            Some(observed) if observed == expected => return Ok(vec![]),
+            Some(observed) => format!(
+                "Record the value as `{}` instead of `{}`",
+                expected, observed
+            ),
+            None => format!("Record the value as `{}`", expected),
+        };
+        Ok(vec![Advice {
+            id: "type_mismatch".to_owned(),
+            level: AdviceLevel::Violation,
+            attribute: attribute.name.clone(),
+            message,
+        }])
+    }
+}
+
+/// Advises on the enum attributes whose value is not a member of the enum.
+/// Values outside of a closed enum are violations, values outside of an open
+/// enum are only reported for information.
+pub struct EnumAdvisor;
+
+impl Advisor for EnumAdvisor {
+    fn id(&self) -> &str {
+        "enum"
+    }
+
+    fn advise(
+        &mut self,
+        attribute: &SampleAttribute,
+        definition: Option<&Attribute>,
+    ) -> Result<Vec<Advice>, Error> {
+        let Some(definition) = definition else {
+            return Ok(vec![]);
+        };
+        let AttributeType::Enum {
+            allow_custom_values,
+            members,
+        } = &definition.r#type
+        else {
+            return Ok(vec![]);
+        };
+        // Values of the wrong type are reported by the type advisor.
+        if attribute.r#type() != Some(definition.r#type.value_type())
+            || is_enum_member(members, &attribute.value)
+        {
+            return Ok(vec![]);
+        }
+        let values = members
+            .iter()
+            .map(|m| format!("`{}`", m.value))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let advice = if *allow_custom_values {
+            Advice {
+                id: "custom_enum_value".to_owned(),
+                level: AdviceLevel::Information,
+                attribute: attribute.name.clone(),
+                message: format!(
+                    "The value {} is not a well-known value, use one of {} when applicable",
+                    attribute.value, values
+                ),
+            }
+        } else {
+            Advice {
+                id: "undefined_enum_value".to_owned(),
+                level: AdviceLevel::Violation,
+                attribute: attribute.name.clone(),
+                message: format!(
+                    "The value {} is not allowed, use one of {}",
+                    attribute.value, values
+                ),
+            }
+        };
+        Ok(vec![advice])
+    }
+}
+
+/// Advises on the deprecated attributes (violation) and on the attributes that
+/// are not stable yet (information).
+pub struct StabilityAdvisor;
+
+impl Advisor for StabilityAdvisor {
+    fn id(&self) -> &str {
+        "stability"
+    }
+
+    fn advise(
+        &mut self,
+        attribute: &SampleAttribute,
+        definition: Option<&Attribute>,
+    ) -> Result<Vec<Advice>, Error> {
+        let Some(definition) = definition else {
+            return Ok(vec![]);
+        };
+        let advice = if let Some(note) = &definition.deprecated {
+            Advice {
+                id: "deprecated".to_owned(),
+                level: AdviceLevel::Violation,
+                attribute: attribute.name.clone(),
+                message: format!("The attribute is deprecated: {}", note.trim()),
+            }
+        } else if definition.stability != Some(Stability::Stable) {
+            Advice {
+                id: "not_stable".to_owned(),
+                level: AdviceLevel::Information,
+                attribute: attribute.name.clone(),
+                message: "The attribute is not stable yet, its definition may change".to_owned(),
+            }
+        } else {
+            return Ok(vec![]);
+        };
+        Ok(vec![advice])
+    }
+}
+
+/// The input of the advice policies.
+#[derive(Serialize)]
+struct AdviceInput<'a> {
+    /// The observed attribute.
+    attribute: &'a SampleAttribute,
+    /// The definition of the attribute in the registry, if any.
+    definition: Option<&'a Attribute>,
+}
+
+/// Advises with Rego policies declaring the `live_check_advice` package.
+pub struct RegoAdvisor {
+    /// The policy engine.
+    engine: Engine,
+}
+
+impl RegoAdvisor {
+    /// Creates an advisor evaluating the `live_check_advice` policies of the
+    /// given Rego files.
+    pub fn new(policies: &[PathBuf]) -> Result<Self, Error> {
+        let mut engine = Engine::new();
+        for policy in policies {
+            _ = engine
+                .add_policy(policy)
+                .map_err(|e| advisor_error("rego", &e))?;
+        }
+        if !engine.has_policies(PolicyStage::LiveCheckAdvice) {
+            return Err(advisor_error(
+                "rego",
+                &"no policy declares the `live_check_advice` package",
+            ));
+        }
+        Ok(Self { engine })
+    }
+}
+
+impl Advisor for RegoAdvisor {
+    fn id(&self) -> &str {
+        "rego"
+    }
+
+    fn advise(
+        &mut self,
+        attribute: &SampleAttribute,
+        definition: Option<&Attribute>,
+    ) -> Result<Vec<Advice>, Error> {
+        self.engine
+            .set_input(&AdviceInput {
+                attribute,
+                definition,
+            })
+            .map_err(|e| advisor_error("rego", &e))?;
+        let violations = self
+            .engine
+            .check(PolicyStage::LiveCheckAdvice)
+            .map_err(|e| advisor_error("rego", &e))?;
+        violations_to_advice("rego", attribute, violations)
+    }
+}
+
+/// Advises with a policy compiled to a WASM module. The module receives the
+/// same input as the Rego advice policies.
+pub struct WasmAdvisor {
+    /// The WASM policy.
+    policy: WasmPolicy,
+}
+
+impl WasmAdvisor {
+    /// Loads an advisor from a WASM module.
+    pub fn load(path: &Path) -> Result<Self, Error> {
+        let policy = WasmPolicy::load(path).map_err(|e| advisor_error("wasm", &e))?;
+        Ok(Self { policy })
+    }
+}
+
+impl Advisor for WasmAdvisor {
+    fn id(&self) -> &str {
+        self.policy.file()
+    }
+
+    fn advise(
+        &mut self,
+        attribute: &SampleAttribute,
+        definition: Option<&Attribute>,
+    ) -> Result<Vec<Advice>, Error> {
+        let input = serde_json::to_string(&AdviceInput {
+            attribute,
+            definition,
+        })
+        .map_err(|e| advisor_error(self.id(), &e))?;
+        let violations = self
+            .policy
+            .check(&input)
+            .map_err(|e| advisor_error(self.id(), &e))?;
+        violations_to_advice(self.id(), attribute, violations)
+    }
+}
+
+/// Converts the violations produced by an advice policy into advice. Violations
+/// that are not advice are considered as violation-level advice.
+fn violations_to_advice(
+    advisor: &str,
+    attribute: &SampleAttribute,
+    violations: Vec<Violation>,
+) -> Result<Vec<Advice>, Error> {
+    violations
+        .into_iter()
+        .map(|violation| match violation {
+            Violation::Advice { id, level, message } => Ok(Advice {
+                id,
+                level: level.parse().map_err(|e| advisor_error(advisor, &e))?,
+                attribute: attribute.name.clone(),
+                message,
+            }),
+            violation @ Violation::SemconvAttribute { .. } => Ok(Advice {
+                id: violation.id().to_owned(),
+                level: AdviceLevel::Violation,
+                attribute: attribute.name.clone(),
+                message: violation.to_string(),
+            }),
+        })
+        .collect()
+}
+
+/// Builds an advisor error.
+fn advisor_error<E: Display + ?Sized>(advisor: &str, error: &E) -> Error {
+    Error::AdvisorError {
+        advisor: advisor.to_owned(),
+        error: error.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use weaver_forge::registry::ResolvedRegistry;
+    use weaver_resolver::SchemaResolver;
+    use weaver_semconv::registry::SemConvRegistry;
+
+    use super::*;
+
+    fn attr(name: &str, value: serde_json::Value) -> SampleAttribute {
+        SampleAttribute {
+            name: name.to_owned(),
+            value,
+        }
+    }
+
+    fn definition(name: &str) -> Attribute {
+        let registry_id = "test";
+        let mut registry = SemConvRegistry::try_from_path_pattern(registry_id, "data/*.yaml")
+            .expect("Failed to load the registry");
+        let schema = SchemaResolver::resolve_semantic_convention_registry(&mut registry)
+            .expect("Failed to resolve the registry");
+        let registry = ResolvedRegistry::try_from_resolved_registry(
+            schema
+                .registry(registry_id)
+                .expect("Failed to get the registry from the resolved schema"),
+            schema.catalog(),
+        )
+        .expect("Failed to create the resolved registry");
+        registry
+            .groups
+            .into_iter()
+            .flat_map(|group| group.attributes)
+            .find(|attr| attr.name == name)
+            .expect("Attribute not found")
+    }
+
+    #[test]
+    fn test_builtin_advisors() {
+        let mut advisors = builtin_advisors(&[]).expect("Failed to create the advisors");
+        let mut advise = |attribute: SampleAttribute, definition: Option<&Attribute>| {
+            advisors
+                .iter_mut()
+                .flat_map(|advisor| {
+                    advisor
+                        .advise(&attribute, definition)
+                        .expect("Failed to advise")
+                })
+                .map(|advice| (advice.id, advice.level))
+                .collect::<Vec<_>>()
+        };
+
+        let status_code = definition("http.response.status_code");
+        assert!(advise(
+            attr("http.response.status_code", json!(200)),
+            Some(&status_code)
+        )
+        .is_empty());
+        assert_eq!(
+            advise(
+                attr("http.response.status_code", json!("200")),
+                Some(&status_code)
+            ),
+            vec![("type_mismatch".to_owned(), AdviceLevel::Violation)]
+        );
+        let transport = definition("network.transport");
+        assert_eq!(
+            advise(attr("network.transport", json!("quic")), Some(&transport)),
+            vec![("undefined_enum_value".to_owned(), AdviceLevel::Violation)]
+        );
+        let flavor = definition("http.flavor");
+        assert_eq!(
+            advise(attr("http.flavor", json!("3.0")), Some(&flavor)),
+            vec![
+                ("custom_enum_value".to_owned(), AdviceLevel::Information),
+                ("deprecated".to_owned(), AdviceLevel::Violation)
+            ]
+        );
+        assert!(advise(attr("custom.attr", json!(1)), None).is_empty());
+
+        assert_eq!(
+            builtin_advisors(&["stability".to_owned()])
+                .expect("Failed to create the advisors")
+                .len(),
+            2
+        );
+        assert!(matches!(
+            builtin_advisors(&["unknown".to_owned()]),
+            Err(Error::UnknownAdvisor { .. })
+        ));
+    }
+
+    #[test]
+    fn test_rego_advisor() {
+        let mut advisor = RegoAdvisor::new(&[PathBuf::from("data/policies/advice.rego")])
+            .expect("Failed to create the advisor");
+        let advice = advisor
+            .advise(&attr("custom.attr", json!(1)), None)
+            .expect("Failed to advise");
+        assert_eq!(
+            advice,
+            vec![Advice {
+                id: "missing_namespace_prefix".to_owned(),
+                level: AdviceLevel::Improvement,
+                attribute: "custom.attr".to_owned(),
+                message: "Define the attribute in the registry or use an `acme.` prefix".to_owned(),
+            }]
+        );
+        let definition = definition("http.request.method");
+        assert!(advisor
+            .advise(
+                &attr("http.request.method", json!("GET")),
+                Some(&definition)
+            )
+            .expect("Failed to advise")
+            .is_empty());
+    }
+}
@@ -0,0 +1,346 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Coverage of a resolved registry by the observed telemetry.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::{Display, Formatter};
+
+use serde::Serialize;
+
+use weaver_forge::registry::ResolvedRegistry;
+use weaver_semconv::attribute::{BasicRequirementLevelSpec, RequirementLevel};
+use weaver_semconv::stability::Stability;
+
+use crate::live_checker::LiveCheckReport;
+
+/// Number of conventions defined in the registry and observed in the telemetry.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CoverageCount {
+    /// The number of conventions defined in the registry.
+    pub defined: usize,
+    /// The number of conventions observed in the telemetry.
+    pub observed: usize,
+    /// The percentage of conventions observed (100 if nothing is defined).
+    pub percentage: f64,
+}
+
+impl CoverageCount {
+    /// Creates a new coverage count.
+    #[must_use]
+    pub fn new(defined: usize, observed: usize) -> Self {
+        let percentage = if defined == 0 {
+            100.0
+        } else {
+            observed as f64 * 100.0 / defined as f64
+        };
+        Self {
+            defined,
+            observed,
+            percentage,
+        }
+    }
+}
+
+/// Coverage of the required attributes of a group.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct GroupCoverage {
+    /// The id of the group.
+    pub id: String,
+    /// The coverage of the required attributes of the group.
+    pub required_attributes: CoverageCount,
+    /// The required attributes never observed.
+    pub missing_required_attributes: Vec<String>,
+}
+
+/// Coverage of a resolved registry by the observed telemetry.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CoverageReport {
+    /// The coverage of the attributes.
+    pub attributes: CoverageCount,
+    /// The coverage of the metrics.
+    pub metrics: CoverageCount,
+    /// The coverage of the required attributes, for each group defining
+    /// required attributes.
+    pub groups: Vec<GroupCoverage>,
+    /// The stable attributes never observed.
+    pub unobserved_stable_attributes: Vec<String>,
+    /// The stable metrics never observed.
+    pub unobserved_stable_metrics: Vec<String>,
+}
+
+impl CoverageReport {
+    /// Cross-references the conventions observed during a live check with the
+    /// conventions defined in the registry.
+    #[must_use]
+    pub fn new(registry: &ResolvedRegistry, report: &LiveCheckReport) -> Self {
+        // Attributes and metrics are identified by name, the first definition wins.
+        let mut attributes = BTreeMap::new();
+        let mut metrics = BTreeMap::new();
+        let mut groups = vec![];
+        for group in &registry.groups {
+            for attr in &group.attributes {
+                _ = attributes
+                    .entry(attr.name.as_str())
+                    .or_insert(attr.stability.as_ref());
+            }
+            if let Some(metric_name) = &group.metric_name {
+                _ = metrics
+                    .entry(metric_name.as_str())
+                    .or_insert(group.stability.as_ref());
+            }
+
+            let required: Vec<&str> = group
+                .attributes
+                .iter()
+                .filter(|attr| {
+                    attr.requirement_level
+                        == RequirementLevel::Basic(BasicRequirementLevelSpec::Required)
+                })
+                .map(|attr| attr.name.as_str())
+                .collect();
+            if !required.is_empty() {
+                let missing: Vec<String> = required
+                    .iter()
+                    .filter(|name| !report.observed_attributes.contains(**name))
+                    .map(|name| (*name).to_owned())
+                    .collect();
+                groups.push(GroupCoverage {
+                    id: group.id.clone(),
+                    required_attributes: CoverageCount::new(
+                        required.len(),
+                        required.len() - missing.len(),
+                    ),
+                    missing_required_attributes: missing,
+                });
+            }
+        }
+
+        Self {
+            attributes: CoverageCount::new(
+                attributes.len(),
+                attributes
+                    .keys()
+                    .filter(|name| report.observed_attributes.contains(**name))
+                    .count(),
+            ),
+            metrics: CoverageCount::new(
+                metrics.len(),
+                metrics
+                    .keys()
+                    .filter(|name| report.observed_metrics.contains(**name))
+                    .count(),
+            ),
+            groups,
+            unobserved_stable_attributes: unobserved_stable(
+                &attributes,
+                &report.observed_attributes,
+            ),
+            unobserved_stable_metrics: unobserved_stable(&metrics, &report.observed_metrics),
+        }
+    }
+
+    /// Renders the coverage report as a self-contained HTML page.
+    #[must_use]
+    pub fn to_html(&self) -> String {
+        let count_row = |label: &str, count: &CoverageCount| {
+            format!(
+                "<tr><td>{}</td><td>{}</td><td>{}</td><td>{:.1}%</td></tr>\n",
+                label, count.observed, count.defined, count.percentage
+            )
+        };
+        let list = |names: &[String]| {
+            if names.is_empty() {
+                "<p>None</p>\n".to_owned()
+            } else {
+                let items: String = names
+                    .iter()
+                    .map(|name| format!("<li><code>{}</code></li>\n", escape_html(name)))
+                    .collect();
+                format!("<ul>\n{}</ul>\n", items)
+            }
+        };
+
+        let mut html = String::from(
+            "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n\
+             <title>Live check coverage</title>\n<style>\n\
+             body { font-family: sans-serif; margin: 2em; }\n\
+             table { border-collapse: collapse; }\n\
+             th, td { border: 1px solid #ccc; padding: 4px 8px; text-align: left; }\n\
+             .missing { color: #b00; }\n\
+             </style>\n</head>\n<body>\n<h1>Live check coverage</h1>\n",
+        );
+        html.push_str("<h2>Summary</h2>\n<table>\n");
+        html.push_str("<tr><th></th><th>Observed</th><th>Defined</th><th>Coverage</th></tr>\n");
+        html.push_str(&count_row("Attributes", &self.attributes));
+        html.push_str(&count_row("Metrics", &self.metrics));
+        html.push_str("</table>\n");
+
+        html.push_str("<h2>Required attributes per group</h2>\n<table>\n");
+        html.push_str(
+            "<tr><th>Group</th><th>Observed</th><th>Required</th><th>Coverage</th><th>Missing</th></tr>\n",
+        );
+        for group in &self.groups {
+            html.push_str(&format!(
+                "<tr><td><code>{}</code></td><td>{}</td><td>{}</td><td>{:.1}%</td><td class=\"missing\">{}</td></tr>\n",
+                escape_html(&group.id),
+                group.required_attributes.observed,
+                group.required_attributes.defined,
+                group.required_attributes.percentage,
+                group
+                    .missing_required_attributes
+                    .iter()
+                    .map(|name| escape_html(name))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ));
+        }
+        html.push_str("</table>\n");
+
+        html.push_str("<h2>Stable attributes never observed</h2>\n");
+        html.push_str(&list(&self.unobserved_stable_attributes));
+        html.push_str("<h2>Stable metrics never observed</h2>\n");
+        html.push_str(&list(&self.unobserved_stable_metrics));
+        html.push_str("</body>\n</html>\n");
+        html
+    }
+}
+
+/// Renders the coverage report as text.
+impl Display for CoverageReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "Attributes: {}/{} observed ({:.1}%)",
+            self.attributes.observed, self.attributes.defined, self.attributes.percentage
+        )?;
+        writeln!(
+            f,
+            "Metrics: {}/{} observed ({:.1}%)",
+            self.metrics.observed, self.metrics.defined, self.metrics.percentage
+        )?;
+        if !self.groups.is_empty() {
+            writeln!(f, "Required attributes per group:")?;
+            for group in &self.groups {
+                write!(
+                    f,
+                    "  {}: {}/{} ({:.1}%)",
+                    group.id,
+                    group.required_attributes.observed,
+                    group.required_attributes.defined,
+                    group.required_attributes.percentage
+                )?;
+                if !group.missing_required_attributes.is_empty() {
+                    write!(
+                        f,
+                        ", missing: {}",
+                        group.missing_required_attributes.join(", ")
+                    )?;
+                }
+                writeln!(f)?;
+            }
+        }
+        if !self.unobserved_stable_attributes.is_empty() {
+            writeln!(
+                f,
+                "Stable attributes never observed: {}",
+                self.unobserved_stable_attributes.join(", ")
+            )?;
+        }
+        if !self.unobserved_stable_metrics.is_empty() {
+            writeln!(
+                f,
+                "Stable metrics never observed: {}",
+                self.unobserved_stable_metrics.join(", ")
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Returns the stable conventions never observed.
+fn unobserved_stable(
+    conventions: &BTreeMap<&str, Option<&Stability>>,
+    observed: &BTreeSet<String>,
+) -> Vec<String> {
+    conventions
+        .iter()
+        .filter(|(name, stability)| {
+            **stability == Some(&Stability::Stable) && !observed.contains(**name)
+        })
+        .map(|(name, _)| (*name).to_owned())
+        .collect()
+}
+
+/// Escapes the HTML special characters of a string.
+fn escape_html(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use weaver_resolver::SchemaResolver;
+    use weaver_semconv::registry::SemConvRegistry;
+
+    use super::*;
+
+    #[test]
+    fn test_coverage_report() {
+        let registry_id = "test";
+        let mut registry = SemConvRegistry::try_from_path_pattern(registry_id, "data/*.yaml")
+            .expect("Failed to load the registry");
+        let schema = SchemaResolver::resolve_semantic_convention_registry(&mut registry)
+            .expect("Failed to resolve the registry");
+        let registry = ResolvedRegistry::try_from_resolved_registry(
+            schema
+                .registry(registry_id)
+                .expect("Failed to get the registry from the resolved schema"),
+            schema.catalog(),
+        )
+        .expect("Failed to create the resolved registry");
+        let report = LiveCheckReport {
+            observed_attributes: BTreeSet::from(["http.response.status_code".to_owned()]),
+            observed_metrics: BTreeSet::from(["http.server.request.duration".to_owned()]),
+            ..Default::default()
+        };
+
+        let coverage = CoverageReport::new(&registry, &report);
+        assert_eq!(coverage.attributes, CoverageCount::new(5, 1));
+        assert_eq!(coverage.metrics, CoverageCount::new(1, 1));
+        assert_eq!(
+            coverage.groups,
+            [
+                "metric.http.server.request.duration",
+                "span.http.server",
+                "span.http.client"
+            ]
+            .into_iter()
+            .map(|id| GroupCoverage {
+                id: id.to_owned(),
+                required_attributes: CoverageCount::new(1, 0),
+                missing_required_attributes: vec!["http.request.method".to_owned()],
+            })
+            .collect::<Vec<_>>()
+        );
+        assert_eq!(
+            coverage.unobserved_stable_attributes,
+            vec![
+                "http.request.header",
+                "http.request.method",
+                "network.transport"
+            ]
+        );
+        assert!(coverage.unobserved_stable_metrics.is_empty());
+
+        let text = coverage.to_string();
+        assert!(text.contains("Attributes: 1/5 observed (20.0%)"));
+        assert!(text.contains(
+            "metric.http.server.request.duration: 0/1 (0.0%), missing: http.request.method"
+        ));
+        let html = coverage.to_html();
+        assert!(html.contains("<tr><td>Attributes</td><td>1</td><td>5</td><td>20.0%</td></tr>"));
+        assert!(html.contains("<li><code>network.transport</code></li>"));
+    }
+}
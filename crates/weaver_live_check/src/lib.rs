@@ -0,0 +1,108 @@
+// SPDX-License-Identifier: Apache-2.0
+
+#![doc = include_str!("../README.md")]
+
+use std::path::PathBuf;
+
+use miette::Diagnostic;
+use serde::Serialize;
+
+use weaver_common::cancellation::Cancelled;
+use weaver_common::diagnostic::{DiagnosticMessage, DiagnosticMessages};
+use weaver_common::error::{format_errors, WeaverError};
+
+use crate::Error::CompoundError;
+
+pub mod advice;
+pub mod coverage;
+pub mod live_checker;
+pub mod otlp;
+pub mod otlp_file;
+pub mod otlp_json;
+pub mod sample;
+
+/// An error that can occur while checking the live telemetry.
+#[derive(thiserror::Error, Debug, Clone, PartialEq, Serialize, Diagnostic)]
+#[non_exhaustive]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Error {
+    /// The OTLP listener failed.
+    #[error("OTLP listener error: {error}")]
+    OtlpListenerError {
+        /// The error that occurred.
+        error: String,
+    },
+
+    /// An OTLP file that can't be read.
+    #[error("Invalid OTLP file '{file}', error: {error}")]
+    #[diagnostic(help(
+        "OTLP files must contain OTLP export requests encoded in JSON (`.json` or `.jsonl`) or protobuf."
+    ))]
+    InvalidOtlpFile {
+        /// The file that caused the error.
+        file: PathBuf,
+        /// The error that occurred.
+        error: String,
+    },
+
+    /// An advisor that can't be loaded or that failed to give advice.
+    #[error("Advisor '{advisor}' failed: {error}")]
+    AdvisorError {
+        /// The id of the advisor.
+        advisor: String,
+        /// The error that occurred.
+        error: String,
+    },
+
+    /// A built-in advisor that doesn't exist.
+    #[error("Unknown built-in advisor '{id}' (known advisors: {known})")]
+    UnknownAdvisor {
+        /// The id of the advisor.
+        id: String,
+        /// The ids of the built-in advisors.
+        known: String,
+    },
+
+    /// The live check was cancelled or timed out.
+    #[error("The live check was cancelled or timed out")]
+    Cancelled,
+
+    /// A container for multiple errors.
+    #[error("{:?}", format_errors(.0))]
+    CompoundError(Vec<Error>),
+}
+
+impl WeaverError<Error> for Error {
+    fn compound(errors: Vec<Error>) -> Error {
+        Self::CompoundError(
+            errors
+                .into_iter()
+                .flat_map(|e| match e {
+                    Self::CompoundError(errors) => errors,
+                    e => vec![e],
+                })
+                .collect(),
+        )
+    }
+}
+
+impl From<Cancelled> for Error {
+    fn from(_: Cancelled) -> Self {
+        Error::Cancelled
+    }
+}
+
+impl From<Error> for DiagnosticMessages {
+    fn from(error: Error) -> Self {
+        DiagnosticMessages::new(match error {
+            CompoundError(errors) => errors
+                .into_iter()
+                .flat_map(|e| {
+                    let diag_msgs: DiagnosticMessages = e.into();
+                    diag_msgs.into_inner()
+                })
+                .collect(),
+            _ => vec![DiagnosticMessage::new(error)],
+        })
+    }
+}
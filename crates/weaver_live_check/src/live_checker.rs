@@ -0,0 +1,775 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Check the telemetry samples against a resolved registry.
+
+use std::collections::{BTreeSet, HashMap};
+use std::fmt::{Display, Formatter};
+
+use serde::Serialize;
+use serde_json::Value;
+
+use weaver_common::cancellation::CancellationToken;
+use weaver_forge::registry::{ResolvedGroup, ResolvedRegistry};
+use weaver_resolved_schema::attribute::Attribute;
+use weaver_semconv::attribute::{
+    AttributeType, BasicRequirementLevelSpec, EnumEntriesSpec, RequirementLevel, ValueSpec,
+};
+use weaver_semconv::group::GroupType;
+
+use crate::advice::{Advice, AdviceCount, AdviceLevel, Advisor};
+use crate::sample::{Sample, SampleAttribute, SampleSpan};
+use crate::Error;
+
+/// A discrepancy between the observed telemetry and the registry.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Finding {
+    /// An attribute that is not defined in the registry.
+    UnknownAttribute {
+        /// The name of the attribute.
+        name: String,
+    },
+    /// An attribute that is not defined in the registry, but that a registry
+    /// attribute declares as one of its previous names.
+    RenamedAttribute {
+        /// The name of the attribute.
+        name: String,
+        /// The name of the registry attribute replacing it.
+        renamed_to: String,
+    },
+    /// An attribute whose value doesn't match the type defined in the registry.
+    AttributeTypeMismatch {
+        /// The name of the attribute.
+        name: String,
+        /// The type defined in the registry.
+        expected: String,
+        /// The type of the observed value.
+        observed: String,
+    },
+    /// An enum attribute whose value is not a member of a closed enum.
+    UndefinedEnumValue {
+        /// The name of the attribute.
+        name: String,
+        /// The observed value.
+        value: String,
+    },
+    /// A metric that is not defined in the registry.
+    UnknownMetric {
+        /// The name of the metric.
+        name: String,
+    },
+    /// A metric recorded with an instrument different from the registry.
+    MetricInstrumentMismatch {
+        /// The name of the metric.
+        name: String,
+        /// The instrument defined in the registry.
+        expected: String,
+        /// The observed instrument.
+        observed: String,
+    },
+    /// A metric recorded with a unit different from the registry.
+    MetricUnitMismatch {
+        /// The name of the metric.
+        name: String,
+        /// The unit defined in the registry.
+        expected: String,
+        /// The observed unit.
+        observed: String,
+    },
+    /// A required attribute missing from a span or from a data point of a
+    /// metric.
+    MissingRequiredAttribute {
+        /// The name of the attribute.
+        name: String,
+        /// The id of the span or metric group requiring the attribute.
+        group: String,
+    },
+}
+
+impl Display for Finding {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Finding::UnknownAttribute { name } => {
+                write!(f, "Attribute '{}' is not defined in the registry", name)
+            }
+            Finding::RenamedAttribute { name, renamed_to } => write!(
+                f,
+                "Attribute '{}' has been renamed to '{}' in the registry",
+                name, renamed_to
+            ),
+            Finding::AttributeTypeMismatch {
+                name,
+                expected,
+                observed,
+            } => write!(
+                f,
+                "Attribute '{}' has type '{}', expected '{}'",
+                name, observed, expected
+            ),
+            Finding::UndefinedEnumValue { name, value } => write!(
+                f,
+                "Attribute '{}' has value '{}' which is not a member of the enum",
+                name, value
+            ),
+            Finding::UnknownMetric { name } => {
+                write!(f, "Metric '{}' is not defined in the registry", name)
+            }
+            Finding::MetricInstrumentMismatch {
+                name,
+                expected,
+                observed,
+            } => write!(
+                f,
+                "Metric '{}' is recorded as a '{}', expected '{}'",
+                name, observed, expected
+            ),
+            Finding::MetricUnitMismatch {
+                name,
+                expected,
+                observed,
+            } => write!(
+                f,
+                "Metric '{}' has unit '{}', expected '{}'",
+                name, observed, expected
+            ),
+            Finding::MissingRequiredAttribute { name, group } => write!(
+                f,
+                "Required attribute '{}' of group '{}' is missing",
+                name, group
+            ),
+        }
+    }
+}
+
+/// A finding and the number of times it has been observed.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct FindingCount {
+    /// The finding.
+    #[serde(flatten)]
+    pub finding: Finding,
+    /// The number of occurrences of the finding.
+    pub count: usize,
+}
+
+/// The outcome of a live check.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct LiveCheckReport {
+    /// The number of samples checked.
+    pub sample_count: usize,
+    /// The distinct findings, in order of first occurrence.
+    pub findings: Vec<FindingCount>,
+    /// The names of the registry attributes observed (the name of the template
+    /// for template attributes).
+    pub observed_attributes: BTreeSet<String>,
+    /// The names of the registry metrics observed.
+    pub observed_metrics: BTreeSet<String>,
+    /// The distinct advice given by the advisors, in order of first occurrence.
+    pub advice: Vec<AdviceCount>,
+}
+
+impl LiveCheckReport {
+    /// Returns true if no finding has been reported.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.findings.is_empty()
+    }
+
+    /// Returns the number of distinct advice at or above the given level.
+    #[must_use]
+    pub fn advice_count(&self, level: AdviceLevel) -> usize {
+        self.advice
+            .iter()
+            .filter(|advice| advice.advice.level >= level)
+            .count()
+    }
+}
+
+/// Checks telemetry samples against the attributes and metrics defined in a
+/// resolved registry.
+pub struct LiveChecker {
+    /// Attributes indexed by name.
+    attributes: HashMap<String, Attribute>,
+    /// Template attributes (name used as a prefix).
+    templates: Vec<Attribute>,
+    /// Names of the attributes replacing a previous name, indexed by the
+    /// previous name.
+    renames: HashMap<String, String>,
+    /// Metric groups indexed by metric name.
+    metrics: HashMap<String, ResolvedGroup>,
+    /// Span groups.
+    spans: Vec<ResolvedGroup>,
+    /// The report being built.
+    report: LiveCheckReport,
+    /// Index of the findings in the report.
+    finding_index: HashMap<Finding, usize>,
+    /// The advisors enriching the observed attributes with advice.
+    advisors: Vec<Box<dyn Advisor>>,
+    /// Index of the advice in the report.
+    advice_index: HashMap<Advice, usize>,
+}
+
+impl LiveChecker {
+    /// Creates a new live checker for the given registry.
+    #[must_use]
+    pub fn new(registry: &ResolvedRegistry) -> Self {
+        let mut attributes = HashMap::new();
+        let mut templates = vec![];
+        let mut renames = HashMap::new();
+        let mut metrics = HashMap::new();
+        let mut spans = vec![];
+        for group in &registry.groups {
+            for attr in &group.attributes {
+                for previous_name in &attr.renamed_from {
+                    _ = renames
+                        .entry(previous_name.clone())
+                        .or_insert_with(|| attr.name.to_string());
+                }
+                if matches!(attr.r#type, AttributeType::Template(_)) {
+                    if !templates.iter().any(|t: &Attribute| t.name == attr.name) {
+                        templates.push(attr.clone());
+                    }
+                } else {
+                    _ = attributes
+                        .entry(attr.name.to_string())
+                        .or_insert_with(|| attr.clone());
+                }
+            }
+            if let Some(metric_name) = &group.metric_name {
+                _ = metrics
+                    .entry(metric_name.clone())
+                    .or_insert_with(|| group.clone());
+            }
+            if group.r#type == GroupType::Span {
+                spans.push(group.clone());
+            }
+        }
+        // The longest template prefix wins.
+        templates.sort_by_key(|t| std::cmp::Reverse(t.name.len()));
+        Self {
+            attributes,
+            templates,
+            renames,
+            metrics,
+            spans,
+            report: LiveCheckReport::default(),
+            finding_index: HashMap::new(),
+            advisors: vec![],
+            advice_index: HashMap::new(),
+        }
+    }
+
+    /// Adds an advisor consulted for each observed attribute.
+    pub fn add_advisor(&mut self, advisor: Box<dyn Advisor>) {
+        self.advisors.push(advisor);
+    }
+
+    /// Checks a telemetry sample and records the findings and the advice.
+    pub fn check(&mut self, sample: &Sample) -> Result<(), Error> {
+        self.report.sample_count += 1;
+        match sample {
+            Sample::Resource(resource) => self.check_attributes(&resource.attributes)?,
+            Sample::Span(span) => {
+                self.check_attributes(&span.attributes)?;
+                for event in &span.events {
+                    self.check_attributes(&event.attributes)?;
+                }
+                let findings: Vec<_> = self
+                    .span_group(span)
+                    .into_iter()
+                    .flat_map(|group| {
+                        required_attributes(group)
+                            .filter(|attr| span.attributes.iter().all(|a| a.name != attr.name))
+                            .map(|attr| Finding::MissingRequiredAttribute {
+                                name: attr.name.to_string(),
+                                group: group.id.clone(),
+                            })
+                    })
+                    .collect();
+                for finding in findings {
+                    self.add_finding(finding);
+                }
+            }
+            Sample::Log(log) => self.check_attributes(&log.attributes)?,
+            Sample::Metric(metric) => {
+                for data_point in &metric.data_points {
+                    self.check_attributes(data_point)?;
+                }
+                let Some(group) = self.metrics.get(&metric.name) else {
+                    self.add_finding(Finding::UnknownMetric {
+                        name: metric.name.clone(),
+                    });
+                    return Ok(());
+                };
+                _ = self.report.observed_metrics.insert(metric.name.clone());
+                let mut findings = vec![];
+                if let (Some(expected), Some(observed)) = (&group.instrument, &metric.instrument) {
+                    if expected != observed {
+                        findings.push(Finding::MetricInstrumentMismatch {
+                            name: metric.name.clone(),
+                            expected: expected.to_string(),
+                            observed: observed.to_string(),
+                        });
+                    }
+                }
+                if let Some(expected) = &group.unit {
+                    if *expected != metric.unit {
+                        findings.push(Finding::MetricUnitMismatch {
+                            name: metric.name.clone(),
+                            expected: expected.clone(),
+                            observed: metric.unit.clone(),
+                        });
+                    }
+                }
+                for attr in required_attributes(group) {
+                    if metric
+                        .data_points
+                        .iter()
+                        .any(|data_point| data_point.iter().all(|a| a.name != attr.name))
+                    {
+                        findings.push(Finding::MissingRequiredAttribute {
+                            name: attr.name.to_string(),
+                            group: group.id.clone(),
+                        });
+                    }
+                }
+                for finding in findings {
+                    self.add_finding(finding);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Checks the given samples, stopping with [`Error::Cancelled`] once the
+    /// given token is cancelled or timed out. The samples checked before the
+    /// cancellation are kept in the report.
+    pub fn check_samples<'a>(
+        &mut self,
+        samples: impl IntoIterator<Item = &'a Sample>,
+        cancellation: &CancellationToken,
+    ) -> Result<(), Error> {
+        for sample in samples {
+            cancellation.check()?;
+            self.check(sample)?;
+        }
+        Ok(())
+    }
+
+    /// Returns the report of the samples checked so far.
+    #[must_use]
+    pub fn report(&self) -> &LiveCheckReport {
+        &self.report
+    }
+
+    /// Consumes the checker and returns the report.
+    #[must_use]
+    pub fn into_report(self) -> LiveCheckReport {
+        self.report
+    }
+
+    /// Checks a list of observed attributes and asks the advisors for advice.
+    fn check_attributes(&mut self, attributes: &[SampleAttribute]) -> Result<(), Error> {
+        for attribute in attributes {
+            let definition = self
+                .attributes
+                .get(&attribute.name)
+                .or_else(|| find_template(&self.templates, &attribute.name));
+            let mut advice = vec![];
+            for advisor in &mut self.advisors {
+                advice.extend(advisor.advise(attribute, definition)?);
+            }
+            if let Some(definition) = definition {
+                _ = self
+                    .report
+                    .observed_attributes
+                    .insert(definition.name.to_string());
+            }
+            if let Some(finding) = self.check_attribute(attribute) {
+                self.add_finding(finding);
+            }
+            for advice in advice {
+                self.add_advice(advice);
+            }
+        }
+        Ok(())
+    }
+
+    /// Checks an observed attribute against its definition in the registry.
+    fn check_attribute(&self, attribute: &SampleAttribute) -> Option<Finding> {
+        let observed = attribute.r#type();
+        let type_mismatch = |expected: &dyn Display| Finding::AttributeTypeMismatch {
+            name: attribute.name.clone(),
+            expected: expected.to_string(),
+            observed: observed
+                .as_ref()
+                .map_or_else(|| "unknown".to_owned(), ToString::to_string),
+        };
+
+        if let Some(attr) = self.attributes.get(&attribute.name) {
+            return match &attr.r#type {
+                AttributeType::PrimitiveOrArray(expected) => {
+                    (observed.as_ref() != Some(expected)).then(|| type_mismatch(expected))
+                }
+                AttributeType::Enum {
+                    allow_custom_values,
+                    members,
+                } => {
+                    if observed != Some(attr.r#type.value_type()) {
+                        return Some(type_mismatch(&attr.r#type));
+                    }
+                    (!allow_custom_values && !is_enum_member(members, &attribute.value)).then(
+                        || Finding::UndefinedEnumValue {
+                            name: attribute.name.clone(),
+                            value: attribute.value.to_string(),
+                        },
+                    )
+                }
+                AttributeType::Template(_) => None,
+            };
+        }
+
+        match find_template(&self.templates, &attribute.name) {
+            Some(template) => {
+                let AttributeType::Template(expected) = &template.r#type else {
+                    return None;
+                };
+                (observed != Some(template.r#type.value_type())).then(|| type_mismatch(expected))
+            }
+            None => Some(match self.renames.get(&attribute.name) {
+                Some(renamed_to) => Finding::RenamedAttribute {
+                    name: attribute.name.clone(),
+                    renamed_to: renamed_to.clone(),
+                },
+                None => Finding::UnknownAttribute {
+                    name: attribute.name.clone(),
+                },
+            }),
+        }
+    }
+
+    /// Returns the span group matching an observed span: the group named after
+    /// the span, or else the only span group with the kind of the span.
+    fn span_group(&self, span: &SampleSpan) -> Option<&ResolvedGroup> {
+        if let Some(group) = self
+            .spans
+            .iter()
+            .find(|group| group.name.as_ref() == Some(&span.name) || group.id == span.name)
+        {
+            return Some(group);
+        }
+        let kind = span.kind.as_ref()?;
+        let mut groups = self
+            .spans
+            .iter()
+            .filter(|group| group.span_kind.as_ref() == Some(kind));
+        match (groups.next(), groups.next()) {
+            (Some(group), None) => Some(group),
+            _ => None,
+        }
+    }
+
+    /// Records a finding, counting the duplicates.
+    fn add_finding(&mut self, finding: Finding) {
+        if let Some(index) = self.finding_index.get(&finding) {
+            self.report.findings[*index].count += 1;
+        } else {
+            _ = self
+                .finding_index
+                .insert(finding.clone(), self.report.findings.len());
+            self.report
+                .findings
+                .push(FindingCount { finding, count: 1 });
+        }
+    }
+
+    /// Records an advice, counting the duplicates.
+    fn add_advice(&mut self, advice: Advice) {
+        if let Some(index) = self.advice_index.get(&advice) {
+            self.report.advice[*index].count += 1;
+        } else {
+            _ = self
+                .advice_index
+                .insert(advice.clone(), self.report.advice.len());
+            self.report.advice.push(AdviceCount { advice, count: 1 });
+        }
+    }
+}
+
+/// Returns the required attributes of a group.
+fn required_attributes(group: &ResolvedGroup) -> impl Iterator<Item = &Attribute> {
+    group.attributes.iter().filter(|attr| {
+        attr.requirement_level == RequirementLevel::Basic(BasicRequirementLevelSpec::Required)
+    })
+}
+
+/// Returns the template attribute matching the given attribute name, if any.
+fn find_template<'a>(templates: &'a [Attribute], name: &str) -> Option<&'a Attribute> {
+    templates.iter().find(|t| {
+        name.strip_prefix(t.name.as_str())
+            .is_some_and(|key| key.starts_with('.'))
+    })
+}
+
+/// Returns true if the value is one of the members of an enum.
+pub(crate) fn is_enum_member(members: &[EnumEntriesSpec], value: &Value) -> bool {
+    members.iter().any(|m| match (&m.value, value) {
+        (ValueSpec::Int(v), Value::Number(n)) => n.as_i64() == Some(*v),
+        (ValueSpec::String(v), Value::String(s)) => v == s,
+        _ => false,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use weaver_forge::registry::ResolvedRegistry;
+    use weaver_resolver::SchemaResolver;
+    use weaver_semconv::registry::SemConvRegistry;
+
+    use weaver_semconv::group::{InstrumentSpec, SpanKindSpec};
+
+    use crate::advice::builtin_advisors;
+    use crate::sample::SampleMetric;
+
+    use super::*;
+
+    fn attr(name: &str, value: Value) -> SampleAttribute {
+        SampleAttribute {
+            name: name.to_owned(),
+            value,
+        }
+    }
+
+    fn live_checker() -> LiveChecker {
+        let registry_id = "test";
+        let mut registry = SemConvRegistry::try_from_path_pattern(registry_id, "data/*.yaml")
+            .expect("Failed to load the registry");
+        let schema = SchemaResolver::resolve_semantic_convention_registry(&mut registry)
+            .expect("Failed to resolve the registry");
+        let registry = ResolvedRegistry::try_from_resolved_registry(
+            schema
+                .registry(registry_id)
+                .expect("Failed to get the registry from the resolved schema"),
+            schema.catalog(),
+        )
+        .expect("Failed to create the resolved registry");
+        LiveChecker::new(&registry)
+    }
+
+    #[test]
+    fn test_check_span() {
+        let mut checker = live_checker();
+        let span = Sample::Span(SampleSpan {
+            name: "GET /".to_owned(),
+            kind: Some(SpanKindSpec::Server),
+            attributes: vec![
+                attr("http.request.method", json!("GET")),
+                attr("http.response.status_code", json!("200")),
+                attr("http.request.header.content-type", json!(["text/html"])),
+                attr("http.request.header.accept", json!("text/html")),
+                attr("custom.attr", json!(1)),
+                attr("network.transport", json!("carrier_pigeon")),
+                attr("http.status_code", json!(200)),
+            ],
+            events: vec![],
+        });
+        checker.check(&span).expect("Failed to check the sample");
+        checker.check(&span).expect("Failed to check the sample");
+        let report = checker.into_report();
+        assert_eq!(report.sample_count, 2);
+        assert_eq!(
+            report.findings,
+            vec![
+                FindingCount {
+                    finding: Finding::AttributeTypeMismatch {
+                        name: "http.response.status_code".to_owned(),
+                        expected: "int".to_owned(),
+                        observed: "string".to_owned(),
+                    },
+                    count: 2,
+                },
+                FindingCount {
+                    finding: Finding::AttributeTypeMismatch {
+                        name: "http.request.header.accept".to_owned(),
+                        expected: "template[string[]]".to_owned(),
+                        observed: "string".to_owned(),
+                    },
+                    count: 2,
+                },
+                FindingCount {
+                    finding: Finding::UnknownAttribute {
+                        name: "custom.attr".to_owned(),
+                    },
+                    count: 2,
+                },
+                FindingCount {
+                    finding: Finding::UndefinedEnumValue {
+                        name: "network.transport".to_owned(),
+                        value: "\"carrier_pigeon\"".to_owned(),
+                    },
+                    count: 2,
+                },
+                FindingCount {
+                    finding: Finding::RenamedAttribute {
+                        name: "http.status_code".to_owned(),
+                        renamed_to: "http.response.status_code".to_owned(),
+                    },
+                    count: 2,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_check_samples_cancelled() {
+        let mut checker = live_checker();
+        let samples = vec![
+            Sample::Span(SampleSpan {
+                name: "GET /".to_owned(),
+                kind: None,
+                attributes: vec![attr("http.request.method", json!("GET"))],
+                events: vec![],
+            });
+            3
+        ];
+        checker
+            .check_samples(&samples, &CancellationToken::new())
+            .expect("Failed to check the samples");
+        assert_eq!(checker.report().sample_count, 3);
+
+        let cancellation = CancellationToken::new();
+        cancellation.cancel();
+        assert_eq!(
+            checker.check_samples(&samples, &cancellation),
+            Err(Error::Cancelled)
+        );
+        assert_eq!(checker.report().sample_count, 3);
+    }
+
+    #[test]
+    fn test_check_metric() {
+        let mut checker = live_checker();
+        checker
+            .check(&Sample::Metric(SampleMetric {
+                name: "http.server.request.duration".to_owned(),
+                instrument: Some(InstrumentSpec::Counter),
+                unit: "ms".to_owned(),
+                data_points: vec![vec![attr("http.request.method", json!("GET"))], vec![]],
+            }))
+            .expect("Failed to check the sample");
+        checker
+            .check(&Sample::Metric(SampleMetric {
+                name: "unknown.metric".to_owned(),
+                instrument: Some(InstrumentSpec::Gauge),
+                unit: "1".to_owned(),
+                data_points: vec![],
+            }))
+            .expect("Failed to check the sample");
+        let findings: Vec<Finding> = checker
+            .into_report()
+            .findings
+            .into_iter()
+            .map(|f| f.finding)
+            .collect();
+        assert_eq!(
+            findings,
+            vec![
+                Finding::MetricInstrumentMismatch {
+                    name: "http.server.request.duration".to_owned(),
+                    expected: "histogram".to_owned(),
+                    observed: "counter".to_owned(),
+                },
+                Finding::MetricUnitMismatch {
+                    name: "http.server.request.duration".to_owned(),
+                    expected: "s".to_owned(),
+                    observed: "ms".to_owned(),
+                },
+                Finding::MissingRequiredAttribute {
+                    name: "http.request.method".to_owned(),
+                    group: "metric.http.server.request.duration".to_owned(),
+                },
+                Finding::UnknownMetric {
+                    name: "unknown.metric".to_owned(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_check_span_required_attributes() {
+        let mut checker = live_checker();
+        let span = |name: &str, kind: Option<SpanKindSpec>| {
+            Sample::Span(SampleSpan {
+                name: name.to_owned(),
+                kind,
+                attributes: vec![],
+                events: vec![],
+            })
+        };
+        // Matched by span kind, by group name, and not matched.
+        checker
+            .check(&span("GET /", Some(SpanKindSpec::Server)))
+            .expect("Failed to check the sample");
+        checker
+            .check(&span("http.client.request", None))
+            .expect("Failed to check the sample");
+        checker
+            .check(&span("GET /", Some(SpanKindSpec::Consumer)))
+            .expect("Failed to check the sample");
+        let findings: Vec<Finding> = checker
+            .into_report()
+            .findings
+            .into_iter()
+            .map(|f| f.finding)
+            .collect();
+        assert_eq!(
+            findings,
+            vec![
+                Finding::MissingRequiredAttribute {
+                    name: "http.request.method".to_owned(),
+                    group: "span.http.server".to_owned(),
+                },
+                Finding::MissingRequiredAttribute {
+                    name: "http.request.method".to_owned(),
+                    group: "span.http.client".to_owned(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_advice() {
+        let mut checker = live_checker();
+        for advisor in builtin_advisors(&[]).expect("Failed to create the advisors") {
+            checker.add_advisor(advisor);
+        }
+        let span = Sample::Span(SampleSpan {
+            name: "GET /".to_owned(),
+            kind: None,
+            attributes: vec![
+                attr("http.flavor", json!("1.1")),
+                attr("network.transport", json!("tcp")),
+            ],
+            events: vec![],
+        });
+        checker.check(&span).expect("Failed to check the sample");
+        checker.check(&span).expect("Failed to check the sample");
+        let report = checker.into_report();
+        assert!(report.is_empty());
+        assert_eq!(
+            report.advice,
+            vec![AdviceCount {
+                advice: Advice {
+                    id: "deprecated".to_owned(),
+                    level: AdviceLevel::Violation,
+                    attribute: "http.flavor".to_owned(),
+                    message: "The attribute is deprecated: Replaced by `network.protocol.version`."
+                        .to_owned(),
+                },
+                count: 2,
+            }]
+        );
+        assert_eq!(report.advice_count(AdviceLevel::Information), 1);
+    }
+}
@@ -0,0 +1,792 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! OTLP receiver converting the spans, metrics, and logs sent by an application
+//! under test into samples.
+//!
+//! Both the OTLP/gRPC and OTLP/HTTP (protobuf or JSON encoded) protocols are
+//! supported, OTLP/HTTP requests being optionally gzip-compressed. See the
+//! [`crate::otlp_json`] module for the JSON encoding.
+
+use std::borrow::Cow;
+use std::fmt::Display;
+use std::future::IntoFuture;
+use std::io::Read;
+use std::net::SocketAddr;
+use std::time::Duration;
+
+use axum::body::Bytes;
+use axum::extract::State;
+use axum::http::{header, HeaderMap, StatusCode};
+use axum::response::{IntoResponse, Response};
+use axum::routing::post;
+use axum::Router;
+use flate2::read::GzDecoder;
+use opentelemetry_proto::tonic::collector::logs::v1::logs_service_server::{
+    LogsService, LogsServiceServer,
+};
+use opentelemetry_proto::tonic::collector::logs::v1::{
+    ExportLogsServiceRequest, ExportLogsServiceResponse,
+};
+use opentelemetry_proto::tonic::collector::metrics::v1::metrics_service_server::{
+    MetricsService, MetricsServiceServer,
+};
+use opentelemetry_proto::tonic::collector::metrics::v1::{
+    ExportMetricsServiceRequest, ExportMetricsServiceResponse,
+};
+use opentelemetry_proto::tonic::collector::trace::v1::trace_service_server::{
+    TraceService, TraceServiceServer,
+};
+use opentelemetry_proto::tonic::collector::trace::v1::{
+    ExportTraceServiceRequest, ExportTraceServiceResponse,
+};
+use opentelemetry_proto::tonic::common::v1::any_value::Value as OtlpValue;
+use opentelemetry_proto::tonic::common::v1::{AnyValue, KeyValue};
+use opentelemetry_proto::tonic::metrics::v1::metric::Data;
+use opentelemetry_proto::tonic::resource::v1::Resource;
+use opentelemetry_proto::tonic::trace::v1::span::SpanKind;
+use serde_json::Value;
+use tokio::net::TcpListener;
+use tokio::runtime::Runtime;
+use tokio::sync::mpsc::{unbounded_channel, UnboundedSender};
+use tokio::task::JoinError;
+use tonic::transport::server::TcpIncoming;
+
+use weaver_common::cancellation::CancellationToken;
+use weaver_semconv::group::{InstrumentSpec, SpanKindSpec};
+
+use crate::otlp_json::{json_log_samples, json_metric_samples, json_trace_samples};
+use crate::sample::{
+    Sample, SampleAttribute, SampleLog, SampleMetric, SampleResource, SampleSpan, SampleSpanEvent,
+};
+use crate::Error;
+
+/// Converts an OTLP trace export request into samples.
+#[must_use]
+pub fn trace_samples(request: ExportTraceServiceRequest) -> Vec<Sample> {
+    let mut samples = vec![];
+    for resource_spans in request.resource_spans {
+        samples.extend(resource_sample(resource_spans.resource));
+        for span in resource_spans
+            .scope_spans
+            .into_iter()
+            .flat_map(|scope_spans| scope_spans.spans)
+        {
+            samples.push(Sample::Span(SampleSpan {
+                name: span.name,
+                kind: span_kind(span.kind),
+                attributes: sample_attributes(span.attributes),
+                events: span
+                    .events
+                    .into_iter()
+                    .map(|event| SampleSpanEvent {
+                        name: event.name,
+                        attributes: sample_attributes(event.attributes),
+                    })
+                    .collect(),
+            }));
+        }
+    }
+    samples
+}
+
+/// Converts an OTLP span kind into a span kind. The unspecified span kind is
+/// converted to `None`.
+fn span_kind(kind: i32) -> Option<SpanKindSpec> {
+    match SpanKind::try_from(kind).ok()? {
+        SpanKind::Internal => Some(SpanKindSpec::Internal),
+        SpanKind::Server => Some(SpanKindSpec::Server),
+        SpanKind::Client => Some(SpanKindSpec::Client),
+        SpanKind::Producer => Some(SpanKindSpec::Producer),
+        SpanKind::Consumer => Some(SpanKindSpec::Consumer),
+        SpanKind::Unspecified => None,
+    }
+}
+
+/// Converts an OTLP metrics export request into samples.
+#[must_use]
+pub fn metric_samples(request: ExportMetricsServiceRequest) -> Vec<Sample> {
+    let mut samples = vec![];
+    for resource_metrics in request.resource_metrics {
+        samples.extend(resource_sample(resource_metrics.resource));
+        for metric in resource_metrics
+            .scope_metrics
+            .into_iter()
+            .flat_map(|scope_metrics| scope_metrics.metrics)
+        {
+            let (instrument, data_points) = match metric.data {
+                Some(Data::Gauge(gauge)) => (
+                    Some(InstrumentSpec::Gauge),
+                    gauge
+                        .data_points
+                        .into_iter()
+                        .map(|dp| sample_attributes(dp.attributes))
+                        .collect(),
+                ),
+                Some(Data::Sum(sum)) => (
+                    Some(if sum.is_monotonic {
+                        InstrumentSpec::Counter
+                    } else {
+                        InstrumentSpec::UpDownCounter
+                    }),
+                    sum.data_points
+                        .into_iter()
+                        .map(|dp| sample_attributes(dp.attributes))
+                        .collect(),
+                ),
+                Some(Data::Histogram(histogram)) => (
+                    Some(InstrumentSpec::Histogram),
+                    histogram
+                        .data_points
+                        .into_iter()
+                        .map(|dp| sample_attributes(dp.attributes))
+                        .collect(),
+                ),
+                Some(Data::ExponentialHistogram(histogram)) => (
+                    Some(InstrumentSpec::Histogram),
+                    histogram
+                        .data_points
+                        .into_iter()
+                        .map(|dp| sample_attributes(dp.attributes))
+                        .collect(),
+                ),
+                Some(Data::Summary(summary)) => (
+                    None,
+                    summary
+                        .data_points
+                        .into_iter()
+                        .map(|dp| sample_attributes(dp.attributes))
+                        .collect(),
+                ),
+                None => (None, vec![]),
+            };
+            samples.push(Sample::Metric(SampleMetric {
+                name: metric.name,
+                instrument,
+                unit: metric.unit,
+                data_points,
+            }));
+        }
+    }
+    samples
+}
+
+/// Converts an OTLP logs export request into samples.
+#[must_use]
+pub fn log_samples(request: ExportLogsServiceRequest) -> Vec<Sample> {
+    let mut samples = vec![];
+    for resource_logs in request.resource_logs {
+        samples.extend(resource_sample(resource_logs.resource));
+        for log_record in resource_logs
+            .scope_logs
+            .into_iter()
+            .flat_map(|scope_logs| scope_logs.log_records)
+        {
+            samples.push(Sample::Log(SampleLog {
+                attributes: sample_attributes(log_record.attributes),
+            }));
+        }
+    }
+    samples
+}
+
+/// Converts an OTLP resource into a sample.
+fn resource_sample(resource: Option<Resource>) -> Option<Sample> {
+    resource.map(|resource| {
+        Sample::Resource(SampleResource {
+            attributes: sample_attributes(resource.attributes),
+        })
+    })
+}
+
+/// Converts OTLP attributes into sample attributes.
+fn sample_attributes(attributes: Vec<KeyValue>) -> Vec<SampleAttribute> {
+    attributes
+        .into_iter()
+        .map(|kv| SampleAttribute {
+            name: kv.key,
+            value: kv.value.map_or(Value::Null, json_value),
+        })
+        .collect()
+}
+
+/// Converts an OTLP value into a JSON value. Byte arrays don't have any
+/// corresponding semantic convention type and are converted to null.
+fn json_value(value: AnyValue) -> Value {
+    match value.value {
+        Some(OtlpValue::StringValue(v)) => Value::String(v),
+        Some(OtlpValue::BoolValue(v)) => Value::Bool(v),
+        Some(OtlpValue::IntValue(v)) => Value::from(v),
+        Some(OtlpValue::DoubleValue(v)) => serde_json::Number::from_f64(v)
+            .map(Value::Number)
+            .unwrap_or(Value::Null),
+        Some(OtlpValue::ArrayValue(array)) => {
+            Value::Array(array.values.into_iter().map(json_value).collect())
+        }
+        Some(OtlpValue::KvlistValue(kvlist)) => Value::Object(
+            kvlist
+                .values
+                .into_iter()
+                .map(|kv| (kv.key, kv.value.map_or(Value::Null, json_value)))
+                .collect(),
+        ),
+        Some(OtlpValue::BytesValue(_)) | None => Value::Null,
+    }
+}
+
+/// Interval at which a running listener checks its cancellation token.
+const CANCELLATION_CHECK_INTERVAL: Duration = Duration::from_millis(100);
+
+/// OTLP receiver listening for OTLP/gRPC and OTLP/HTTP requests.
+pub struct OtlpListener {
+    /// The runtime driving the servers.
+    runtime: Runtime,
+    /// The listener accepting the OTLP/gRPC connections.
+    grpc_listener: TcpListener,
+    /// The listener accepting the OTLP/HTTP connections.
+    http_listener: TcpListener,
+}
+
+impl OtlpListener {
+    /// Binds the OTLP/gRPC and OTLP/HTTP listeners to the given addresses.
+    pub fn bind(grpc_addr: SocketAddr, http_addr: SocketAddr) -> Result<Self, Error> {
+        let runtime = tokio::runtime::Builder::new_multi_thread()
+            .enable_all()
+            .build()
+            .map_err(|e| listener_error(&e))?;
+        let (grpc_listener, http_listener) = runtime.block_on(async {
+            let grpc_listener = TcpListener::bind(grpc_addr)
+                .await
+                .map_err(|e| listener_error(&format!("{}: {}", grpc_addr, e)))?;
+            let http_listener = TcpListener::bind(http_addr)
+                .await
+                .map_err(|e| listener_error(&format!("{}: {}", http_addr, e)))?;
+            Ok::<_, Error>((grpc_listener, http_listener))
+        })?;
+        Ok(Self {
+            runtime,
+            grpc_listener,
+            http_listener,
+        })
+    }
+
+    /// Returns the address of the OTLP/gRPC listener.
+    pub fn grpc_addr(&self) -> Result<SocketAddr, Error> {
+        self.grpc_listener
+            .local_addr()
+            .map_err(|e| listener_error(&e))
+    }
+
+    /// Returns the address of the OTLP/HTTP listener.
+    pub fn http_addr(&self) -> Result<SocketAddr, Error> {
+        self.http_listener
+            .local_addr()
+            .map_err(|e| listener_error(&e))
+    }
+
+    /// Receives the telemetry and calls `on_sample` for each sample received.
+    ///
+    /// The listener stops when no telemetry has been received for the given
+    /// inactivity timeout, when the process receives a Ctrl-C signal, or when
+    /// `on_sample` returns an error.
+    pub fn run<F>(self, inactivity_timeout: Duration, on_sample: F) -> Result<(), Error>
+    where
+        F: FnMut(Sample) -> Result<(), Error>,
+    {
+        self.run_with_cancellation(inactivity_timeout, &CancellationToken::default(), on_sample)
+    }
+
+    /// Same as [`OtlpListener::run`], but the listener also stops with
+    /// [`Error::Cancelled`] once the given token is cancelled or timed out.
+    pub fn run_with_cancellation<F>(
+        self,
+        inactivity_timeout: Duration,
+        cancellation: &CancellationToken,
+        mut on_sample: F,
+    ) -> Result<(), Error>
+    where
+        F: FnMut(Sample) -> Result<(), Error>,
+    {
+        let (sender, mut receiver) = unbounded_channel::<Sample>();
+        let receiver_service = Receiver { sender };
+
+        let grpc_incoming = TcpIncoming::from_listener(self.grpc_listener, true, None)
+            .map_err(|e| listener_error(&e))?;
+        let grpc_server = tonic::transport::Server::builder()
+            .add_service(TraceServiceServer::new(receiver_service.clone()))
+            .add_service(MetricsServiceServer::new(receiver_service.clone()))
+            .add_service(LogsServiceServer::new(receiver_service.clone()))
+            .serve_with_incoming(grpc_incoming);
+        let http_router = Router::new()
+            .route("/v1/traces", post(http_traces))
+            .route("/v1/metrics", post(http_metrics))
+            .route("/v1/logs", post(http_logs))
+            .with_state(receiver_service);
+        let http_server = axum::serve(self.http_listener, http_router);
+
+        let result = self.runtime.block_on(async {
+            let mut grpc_server = tokio::spawn(grpc_server);
+            let mut http_server = tokio::spawn(http_server.into_future());
+            let ctrl_c = tokio::signal::ctrl_c();
+            tokio::pin!(ctrl_c);
+            // The inactivity deadline is only pushed back when a sample is
+            // received, not when the cancellation token is polled.
+            let inactivity = tokio::time::sleep(inactivity_timeout);
+            tokio::pin!(inactivity);
+            // The token is polled as it can be cancelled from any thread.
+            let mut cancellation_check = tokio::time::interval(CANCELLATION_CHECK_INTERVAL);
+            let result = loop {
+                tokio::select! {
+                    sample = receiver.recv() => {
+                        match sample {
+                            Some(sample) => {
+                                inactivity
+                                    .as_mut()
+                                    .reset(tokio::time::Instant::now() + inactivity_timeout);
+                                if let Err(e) = on_sample(sample) {
+                                    break Err(e);
+                                }
+                            }
+                            None => break Ok(()),
+                        }
+                    }
+                    _ = &mut inactivity => break Ok(()),
+                    _ = &mut ctrl_c => break Ok(()),
+                    _ = cancellation_check.tick() => {
+                        if cancellation.is_cancelled() {
+                            break Err(Error::Cancelled);
+                        }
+                    }
+                    result = &mut grpc_server => break Err(server_stopped(result)),
+                    result = &mut http_server => break Err(server_stopped(result)),
+                }
+            };
+            grpc_server.abort();
+            http_server.abort();
+            result
+        });
+        self.runtime.shutdown_timeout(Duration::from_secs(1));
+        result
+    }
+}
+
+/// Forwards the samples received by the OTLP services to the listener.
+#[derive(Clone)]
+struct Receiver {
+    /// The channel used to forward the samples.
+    sender: UnboundedSender<Sample>,
+}
+
+impl Receiver {
+    /// Forwards the given samples. Samples received after the listener stopped
+    /// are ignored.
+    fn forward(&self, samples: Vec<Sample>) {
+        for sample in samples {
+            _ = self.sender.send(sample);
+        }
+    }
+}
+
+#[tonic::async_trait]
+impl TraceService for Receiver {
+    async fn export(
+        &self,
+        request: tonic::Request<ExportTraceServiceRequest>,
+    ) -> Result<tonic::Response<ExportTraceServiceResponse>, tonic::Status> {
+        self.forward(trace_samples(request.into_inner()));
+        Ok(tonic::Response::new(ExportTraceServiceResponse::default()))
+    }
+}
+
+#[tonic::async_trait]
+impl MetricsService for Receiver {
+    async fn export(
+        &self,
+        request: tonic::Request<ExportMetricsServiceRequest>,
+    ) -> Result<tonic::Response<ExportMetricsServiceResponse>, tonic::Status> {
+        self.forward(metric_samples(request.into_inner()));
+        Ok(tonic::Response::new(ExportMetricsServiceResponse::default()))
+    }
+}
+
+#[tonic::async_trait]
+impl LogsService for Receiver {
+    async fn export(
+        &self,
+        request: tonic::Request<ExportLogsServiceRequest>,
+    ) -> Result<tonic::Response<ExportLogsServiceResponse>, tonic::Status> {
+        self.forward(log_samples(request.into_inner()));
+        Ok(tonic::Response::new(ExportLogsServiceResponse::default()))
+    }
+}
+
+/// Handles an OTLP/HTTP trace export request.
+async fn http_traces(
+    State(receiver): State<Receiver>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Response {
+    http_export(
+        &receiver,
+        &headers,
+        &body,
+        trace_samples,
+        json_trace_samples,
+        ExportTraceServiceResponse::default(),
+    )
+}
+
+/// Handles an OTLP/HTTP metrics export request.
+async fn http_metrics(
+    State(receiver): State<Receiver>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Response {
+    http_export(
+        &receiver,
+        &headers,
+        &body,
+        metric_samples,
+        json_metric_samples,
+        ExportMetricsServiceResponse::default(),
+    )
+}
+
+/// Handles an OTLP/HTTP logs export request.
+async fn http_logs(State(receiver): State<Receiver>, headers: HeaderMap, body: Bytes) -> Response {
+    http_export(
+        &receiver,
+        &headers,
+        &body,
+        log_samples,
+        json_log_samples,
+        ExportLogsServiceResponse::default(),
+    )
+}
+
+/// Decodes an OTLP/HTTP export request, encoded in JSON or protobuf depending
+/// on its content type and optionally gzip-compressed, forwards its samples,
+/// and builds the response using the same encoding.
+fn http_export<Req, Resp>(
+    receiver: &Receiver,
+    headers: &HeaderMap,
+    body: &[u8],
+    to_samples: fn(Req) -> Vec<Sample>,
+    json_to_samples: fn(&Value) -> Result<Vec<Sample>, String>,
+    response: Resp,
+) -> Response
+where
+    Req: prost::Message + Default,
+    Resp: prost::Message,
+{
+    let body = match decompress(headers, body) {
+        Ok(body) => body,
+        Err(error) => return error.into_response(),
+    };
+    let is_json = headers
+        .get(header::CONTENT_TYPE)
+        .and_then(|content_type| content_type.to_str().ok())
+        .is_some_and(|content_type| content_type.starts_with("application/json"));
+    if is_json {
+        let samples = serde_json::from_slice::<Value>(&body)
+            .map_err(|e| e.to_string())
+            .and_then(|request| json_to_samples(&request));
+        match samples {
+            Ok(samples) => receiver.forward(samples),
+            Err(e) => return (StatusCode::BAD_REQUEST, e).into_response(),
+        }
+        ([(header::CONTENT_TYPE, "application/json")], "{}").into_response()
+    } else {
+        match Req::decode(body.as_ref()) {
+            Ok(request) => receiver.forward(to_samples(request)),
+            Err(e) => return (StatusCode::BAD_REQUEST, e.to_string()).into_response(),
+        }
+        (
+            [(header::CONTENT_TYPE, "application/x-protobuf")],
+            response.encode_to_vec(),
+        )
+            .into_response()
+    }
+}
+
+/// Decompresses the body of an OTLP/HTTP request according to its content
+/// encoding (`gzip` or none), or returns the status and the message of the
+/// error response to send.
+fn decompress<'a>(
+    headers: &HeaderMap,
+    body: &'a [u8],
+) -> Result<Cow<'a, [u8]>, (StatusCode, String)> {
+    let encoding = headers
+        .get(header::CONTENT_ENCODING)
+        .map(|encoding| encoding.to_str().unwrap_or_default().trim());
+    match encoding {
+        None | Some("identity") => Ok(Cow::Borrowed(body)),
+        Some("gzip") => {
+            let mut decompressed = vec![];
+            match GzDecoder::new(body).read_to_end(&mut decompressed) {
+                Ok(_) => Ok(Cow::Owned(decompressed)),
+                Err(e) => Err((StatusCode::BAD_REQUEST, e.to_string())),
+            }
+        }
+        Some(encoding) => Err((
+            StatusCode::UNSUPPORTED_MEDIA_TYPE,
+            format!("unsupported content encoding `{}`", encoding),
+        )),
+    }
+}
+
+/// Builds a listener error.
+fn listener_error(error: &dyn Display) -> Error {
+    Error::OtlpListenerError {
+        error: error.to_string(),
+    }
+}
+
+/// Builds the error reported when a server stopped unexpectedly.
+fn server_stopped<E: Display>(result: Result<Result<(), E>, JoinError>) -> Error {
+    match result {
+        Ok(Ok(())) => listener_error(&"server stopped unexpectedly"),
+        Ok(Err(e)) => listener_error(&e),
+        Err(e) => listener_error(&e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+    use std::net::TcpStream;
+
+    use flate2::write::GzEncoder;
+    use flate2::Compression;
+
+    use opentelemetry_proto::tonic::collector::trace::v1::trace_service_client::TraceServiceClient;
+    use opentelemetry_proto::tonic::common::v1::ArrayValue;
+    use opentelemetry_proto::tonic::metrics::v1::{
+        Metric, NumberDataPoint, ResourceMetrics, ScopeMetrics, Sum,
+    };
+    use opentelemetry_proto::tonic::trace::v1::{ResourceSpans, ScopeSpans, Span};
+    use prost::Message;
+    use serde_json::json;
+
+    use super::*;
+
+    fn key_value(key: &str, value: OtlpValue) -> KeyValue {
+        KeyValue {
+            key: key.to_owned(),
+            value: Some(AnyValue { value: Some(value) }),
+        }
+    }
+
+    fn trace_request() -> ExportTraceServiceRequest {
+        ExportTraceServiceRequest {
+            resource_spans: vec![ResourceSpans {
+                resource: Some(Resource {
+                    attributes: vec![key_value(
+                        "service.name",
+                        OtlpValue::StringValue("test".to_owned()),
+                    )],
+                    dropped_attributes_count: 0,
+                }),
+                scope_spans: vec![ScopeSpans {
+                    spans: vec![Span {
+                        name: "GET /".to_owned(),
+                        kind: SpanKind::Server as i32,
+                        attributes: vec![
+                            key_value("http.response.status_code", OtlpValue::IntValue(200)),
+                            key_value(
+                                "http.request.header.accept",
+                                OtlpValue::ArrayValue(ArrayValue {
+                                    values: vec![AnyValue {
+                                        value: Some(OtlpValue::StringValue("*/*".to_owned())),
+                                    }],
+                                }),
+                            ),
+                        ],
+                        ..Default::default()
+                    }],
+                    ..Default::default()
+                }],
+                ..Default::default()
+            }],
+        }
+    }
+
+    #[test]
+    fn test_trace_samples() {
+        let samples = trace_samples(trace_request());
+        assert_eq!(
+            samples,
+            vec![
+                Sample::Resource(SampleResource {
+                    attributes: vec![SampleAttribute {
+                        name: "service.name".to_owned(),
+                        value: json!("test"),
+                    }],
+                }),
+                Sample::Span(SampleSpan {
+                    name: "GET /".to_owned(),
+                    kind: Some(SpanKindSpec::Server),
+                    attributes: vec![
+                        SampleAttribute {
+                            name: "http.response.status_code".to_owned(),
+                            value: json!(200),
+                        },
+                        SampleAttribute {
+                            name: "http.request.header.accept".to_owned(),
+                            value: json!(["*/*"]),
+                        },
+                    ],
+                    events: vec![],
+                }),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_otlp_listener() {
+        let listener = OtlpListener::bind(
+            "127.0.0.1:0".parse().expect("Invalid address"),
+            "127.0.0.1:0".parse().expect("Invalid address"),
+        )
+        .expect("Failed to bind the listener");
+        let grpc_addr = listener
+            .grpc_addr()
+            .expect("Failed to get the gRPC address");
+        let http_addr = listener
+            .http_addr()
+            .expect("Failed to get the HTTP address");
+
+        let client = std::thread::spawn(move || {
+            // Traces sent over OTLP/gRPC.
+            let runtime = Runtime::new().expect("Failed to create the runtime");
+            runtime.block_on(async {
+                let mut client = TraceServiceClient::connect(format!("http://{}", grpc_addr))
+                    .await
+                    .expect("Failed to connect to the gRPC server");
+                _ = client
+                    .export(trace_request())
+                    .await
+                    .expect("Failed to export the traces");
+            });
+
+            // Metrics sent over OTLP/HTTP (protobuf) and logs over OTLP/HTTP
+            // (gzip-compressed JSON).
+            let metrics = ExportMetricsServiceRequest {
+                resource_metrics: vec![ResourceMetrics {
+                    scope_metrics: vec![ScopeMetrics {
+                        metrics: vec![Metric {
+                            name: "requests".to_owned(),
+                            unit: "1".to_owned(),
+                            data: Some(Data::Sum(Sum {
+                                data_points: vec![NumberDataPoint::default()],
+                                is_monotonic: true,
+                                ..Default::default()
+                            })),
+                            ..Default::default()
+                        }],
+                        ..Default::default()
+                    }],
+                    ..Default::default()
+                }],
+            };
+            let logs = r#"{"resourceLogs": [{"scopeLogs": [{"logRecords": [
+                {"attributes": [{"key": "log.attr", "value": {"boolValue": true}}]}
+            ]}]}]}"#;
+            let mut gzip_logs = GzEncoder::new(vec![], Compression::default());
+            gzip_logs
+                .write_all(logs.as_bytes())
+                .expect("Failed to compress the logs");
+            let gzip_logs = gzip_logs.finish().expect("Failed to compress the logs");
+            let traces = r#"{"resourceSpans": []}"#;
+            for (path, content_type, content_encoding, body, status) in [
+                (
+                    "/v1/metrics",
+                    "application/x-protobuf",
+                    "identity",
+                    metrics.encode_to_vec(),
+                    200,
+                ),
+                ("/v1/logs", "application/json", "gzip", gzip_logs, 200),
+                // A trace export request sent to the metrics endpoint.
+                (
+                    "/v1/metrics",
+                    "application/json",
+                    "identity",
+                    traces.as_bytes().to_vec(),
+                    400,
+                ),
+                (
+                    "/v1/logs",
+                    "application/json",
+                    "br",
+                    logs.as_bytes().to_vec(),
+                    415,
+                ),
+            ] {
+                let mut stream = TcpStream::connect(http_addr).expect("Failed to connect");
+                write!(
+                    stream,
+                    "POST {} HTTP/1.1\r\nHost: {}\r\nContent-Type: {}\r\nContent-Encoding: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+                    path,
+                    http_addr,
+                    content_type,
+                    content_encoding,
+                    body.len()
+                )
+                .expect("Failed to write the request");
+                stream.write_all(&body).expect("Failed to write the body");
+                let mut response = String::new();
+                _ = stream
+                    .read_to_string(&mut response)
+                    .expect("Failed to read the response");
+                assert!(
+                    response.starts_with(&format!("HTTP/1.1 {}", status)),
+                    "{}",
+                    response
+                );
+            }
+        });
+
+        let mut samples = vec![];
+        listener
+            .run(Duration::from_secs(2), |sample| {
+                samples.push(sample);
+                Ok(())
+            })
+            .expect("Failed to run the listener");
+        client.join().expect("The client failed");
+
+        assert_eq!(samples.len(), 4);
+        assert!(matches!(&samples[1], Sample::Span(span) if span.name == "GET /"));
+        assert_eq!(
+            samples[2],
+            Sample::Metric(SampleMetric {
+                name: "requests".to_owned(),
+                instrument: Some(InstrumentSpec::Counter),
+                unit: "1".to_owned(),
+                data_points: vec![vec![]],
+            })
+        );
+        assert_eq!(
+            samples[3],
+            Sample::Log(SampleLog {
+                attributes: vec![SampleAttribute {
+                    name: "log.attr".to_owned(),
+                    value: json!(true),
+                }],
+            })
+        );
+    }
+
+    #[test]
+    fn test_otlp_listener_timeout() {
+        let listener = OtlpListener::bind(
+            "127.0.0.1:0".parse().expect("Invalid address"),
+            "127.0.0.1:0".parse().expect("Invalid address"),
+        )
+        .expect("Failed to bind the listener");
+        let cancellation = CancellationToken::new().with_timeout(Duration::from_millis(200));
+        let result =
+            listener.run_with_cancellation(Duration::from_secs(60), &cancellation, |_| Ok(()));
+        assert_eq!(result, Err(Error::Cancelled));
+    }
+}
@@ -0,0 +1,162 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Read the telemetry recorded in OTLP files (e.g. produced by the file exporter
+//! of the OpenTelemetry Collector) and convert it into samples.
+//!
+//! JSON files (`.json` or `.jsonl`) contain one or several OTLP/JSON export
+//! requests (one per line for the JSON lines format), the signal of each request
+//! being detected from its top-level field (`resourceSpans`, `resourceMetrics`,
+//! or `resourceLogs`). Any other file is decoded as a protobuf export request whose
+//! signal is deduced from the file name, which must contain `trace`, `metric`,
+//! or `log`.
+
+use std::fmt::Display;
+use std::path::{Path, PathBuf};
+
+use opentelemetry_proto::tonic::collector::logs::v1::ExportLogsServiceRequest;
+use opentelemetry_proto::tonic::collector::metrics::v1::ExportMetricsServiceRequest;
+use opentelemetry_proto::tonic::collector::trace::v1::ExportTraceServiceRequest;
+use prost::Message;
+use serde_json::Value;
+use walkdir::WalkDir;
+
+use weaver_common::error::handle_errors;
+
+use crate::otlp::{log_samples, metric_samples, trace_samples};
+use crate::otlp_json::json_samples;
+use crate::sample::Sample;
+use crate::Error;
+
+/// Reads the samples recorded in the given OTLP file or in the OTLP files of
+/// the given directory (visited recursively, hidden files are ignored).
+pub fn read_samples(path: &Path) -> Result<Vec<Sample>, Error> {
+    let mut files: Vec<PathBuf> = WalkDir::new(path)
+        .into_iter()
+        .filter_entry(|entry| {
+            entry.depth() == 0 || !entry.file_name().to_string_lossy().starts_with('.')
+        })
+        .flatten()
+        .filter(|entry| entry.file_type().is_file())
+        .map(|entry| entry.into_path())
+        .collect();
+    if files.is_empty() {
+        return Err(invalid_file(path, "no OTLP file found"));
+    }
+    files.sort();
+
+    let mut samples = vec![];
+    let mut errors = vec![];
+    for file in files {
+        match read_file_samples(&file) {
+            Ok(file_samples) => samples.extend(file_samples),
+            Err(e) => errors.push(e),
+        }
+    }
+    handle_errors(errors)?;
+    Ok(samples)
+}
+
+/// Reads the samples recorded in an OTLP file.
+fn read_file_samples(file: &Path) -> Result<Vec<Sample>, Error> {
+    let content = std::fs::read(file).map_err(|e| invalid_file(file, &e))?;
+    let is_json = file
+        .extension()
+        .is_some_and(|ext| ext == "json" || ext == "jsonl");
+    if is_json {
+        json_file_samples(file, &content)
+    } else {
+        protobuf_samples(file, &content)
+    }
+}
+
+/// Converts the OTLP export requests of a JSON file into samples.
+fn json_file_samples(file: &Path, content: &[u8]) -> Result<Vec<Sample>, Error> {
+    let mut samples = vec![];
+    for request in serde_json::Deserializer::from_slice(content).into_iter::<Value>() {
+        let request = request.map_err(|e| invalid_file(file, &e))?;
+        samples.extend(json_samples(&request).map_err(|e| invalid_file(file, &e))?);
+    }
+    Ok(samples)
+}
+
+/// Converts the OTLP export request of a protobuf file into samples.
+fn protobuf_samples(file: &Path, content: &[u8]) -> Result<Vec<Sample>, Error> {
+    let file_name = file
+        .file_name()
+        .map(|name| name.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+    let samples = if file_name.contains("trace") {
+        ExportTraceServiceRequest::decode(content).map(trace_samples)
+    } else if file_name.contains("metric") {
+        ExportMetricsServiceRequest::decode(content).map(metric_samples)
+    } else if file_name.contains("log") {
+        ExportLogsServiceRequest::decode(content).map(log_samples)
+    } else {
+        return Err(invalid_file(
+            file,
+            "the name of a protobuf file must contain `trace`, `metric`, or `log`",
+        ));
+    };
+    samples.map_err(|e| invalid_file(file, &e))
+}
+
+/// Builds an invalid OTLP file error.
+fn invalid_file<E: Display + ?Sized>(file: &Path, error: &E) -> Error {
+    Error::InvalidOtlpFile {
+        file: file.to_path_buf(),
+        error: error.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use opentelemetry_proto::tonic::logs::v1::{LogRecord, ResourceLogs, ScopeLogs};
+    use tempdir::TempDir;
+
+    use super::*;
+
+    #[test]
+    fn test_read_samples() {
+        let samples = read_samples(Path::new("data/otlp")).expect("Failed to read the samples");
+        let kinds: Vec<&str> = samples
+            .iter()
+            .map(|sample| match sample {
+                Sample::Resource(_) => "resource",
+                Sample::Span(_) => "span",
+                Sample::Metric(_) => "metric",
+                Sample::Log(_) => "log",
+            })
+            .collect();
+        // `metrics.json` is read before `traces.jsonl`.
+        assert_eq!(
+            kinds,
+            vec!["resource", "metric", "resource", "span", "resource", "span"]
+        );
+    }
+
+    #[test]
+    fn test_read_protobuf_samples() {
+        let dir = TempDir::new("weaver-live-check").expect("Failed to create a temp dir");
+        let logs = ExportLogsServiceRequest {
+            resource_logs: vec![ResourceLogs {
+                scope_logs: vec![ScopeLogs {
+                    log_records: vec![LogRecord::default()],
+                    ..Default::default()
+                }],
+                ..Default::default()
+            }],
+        };
+        std::fs::write(dir.path().join("logs.pb"), logs.encode_to_vec())
+            .expect("Failed to write the file");
+        let samples = read_samples(dir.path()).expect("Failed to read the samples");
+        assert_eq!(samples.len(), 1);
+        assert!(matches!(samples[0], Sample::Log(_)));
+
+        std::fs::write(dir.path().join("data.bin"), logs.encode_to_vec())
+            .expect("Failed to write the file");
+        assert!(matches!(
+            read_samples(dir.path()),
+            Err(Error::InvalidOtlpFile { .. })
+        ));
+    }
+}
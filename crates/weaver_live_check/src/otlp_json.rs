@@ -0,0 +1,333 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Conversion of OTLP export requests encoded in JSON into samples.
+//!
+//! The requests follow the [OTLP/JSON](https://opentelemetry.io/docs/specs/otlp/#json-protobuf-encoding)
+//! encoding (lowerCamelCase field names, 64-bit integers encoded as strings).
+//! Only the fields used by the live check are read, missing fields are treated
+//! as empty.
+
+use serde_json::{Map, Value};
+
+use weaver_semconv::group::{InstrumentSpec, SpanKindSpec};
+
+use crate::sample::{
+    Sample, SampleAttribute, SampleLog, SampleMetric, SampleResource, SampleSpan, SampleSpanEvent,
+};
+
+/// The top-level fields of the trace, metrics, and logs export requests.
+const SIGNAL_FIELDS: [&str; 3] = ["resourceSpans", "resourceMetrics", "resourceLogs"];
+
+/// Converts an OTLP export request (traces, metrics, or logs) encoded in JSON
+/// into samples. The signal is detected from the top-level field of the
+/// request (`resourceSpans`, `resourceMetrics`, or `resourceLogs`).
+pub fn json_samples(request: &Value) -> Result<Vec<Sample>, String> {
+    if request.get("resourceSpans").is_some() {
+        json_trace_samples(request)
+    } else if request.get("resourceMetrics").is_some() {
+        json_metric_samples(request)
+    } else if request.get("resourceLogs").is_some() {
+        json_log_samples(request)
+    } else {
+        Err("expected a `resourceSpans`, `resourceMetrics`, or `resourceLogs` field".to_owned())
+    }
+}
+
+/// Converts an OTLP trace export request encoded in JSON into samples. The
+/// export requests of the other signals are rejected.
+pub fn json_trace_samples(request: &Value) -> Result<Vec<Sample>, String> {
+    let mut samples = vec![];
+    for resource_spans in items(signal_field(request, "resourceSpans")?) {
+        samples.extend(resource_sample(resource_spans));
+        for span in items(resource_spans.get("scopeSpans"))
+            .flat_map(|scope_spans| items(scope_spans.get("spans")))
+        {
+            samples.push(Sample::Span(SampleSpan {
+                name: string(span.get("name")),
+                kind: span.get("kind").and_then(span_kind),
+                attributes: attributes(span),
+                events: items(span.get("events"))
+                    .map(|event| SampleSpanEvent {
+                        name: string(event.get("name")),
+                        attributes: attributes(event),
+                    })
+                    .collect(),
+            }));
+        }
+    }
+    Ok(samples)
+}
+
+/// Converts an OTLP metrics export request encoded in JSON into samples. The
+/// export requests of the other signals are rejected.
+pub fn json_metric_samples(request: &Value) -> Result<Vec<Sample>, String> {
+    let mut samples = vec![];
+    for resource_metrics in items(signal_field(request, "resourceMetrics")?) {
+        samples.extend(resource_sample(resource_metrics));
+        for metric in items(resource_metrics.get("scopeMetrics"))
+            .flat_map(|scope_metrics| items(scope_metrics.get("metrics")))
+        {
+            samples.push(Sample::Metric(metric_sample(metric)));
+        }
+    }
+    Ok(samples)
+}
+
+/// Converts an OTLP logs export request encoded in JSON into samples. The
+/// export requests of the other signals are rejected.
+pub fn json_log_samples(request: &Value) -> Result<Vec<Sample>, String> {
+    let mut samples = vec![];
+    for resource_logs in items(signal_field(request, "resourceLogs")?) {
+        samples.extend(resource_sample(resource_logs));
+        for log_record in items(resource_logs.get("scopeLogs"))
+            .flat_map(|scope_logs| items(scope_logs.get("logRecords")))
+        {
+            samples.push(Sample::Log(SampleLog {
+                attributes: attributes(log_record),
+            }));
+        }
+    }
+    Ok(samples)
+}
+
+/// Returns the top-level field of an export request of the given signal, or
+/// an error if the request is not a JSON object or holds the top-level field
+/// of another signal. A missing field is an empty request.
+fn signal_field<'a>(request: &'a Value, field: &str) -> Result<Option<&'a Value>, String> {
+    let request = request
+        .as_object()
+        .ok_or_else(|| "expected a JSON object".to_owned())?;
+    if let Some(other) = SIGNAL_FIELDS
+        .iter()
+        .find(|other| **other != field && request.contains_key(**other))
+    {
+        return Err(format!("expected a `{}` field, found `{}`", field, other));
+    }
+    Ok(request.get(field))
+}
+
+/// Converts an OTLP span kind, encoded as an integer or as the name of the
+/// enum value (e.g. `SPAN_KIND_SERVER`), into a span kind. The unspecified
+/// span kind is converted to `None`.
+fn span_kind(kind: &Value) -> Option<SpanKindSpec> {
+    let kind = match kind {
+        Value::Number(n) => n.as_i64()?,
+        Value::String(s) => match s.as_str() {
+            "SPAN_KIND_INTERNAL" => 1,
+            "SPAN_KIND_SERVER" => 2,
+            "SPAN_KIND_CLIENT" => 3,
+            "SPAN_KIND_PRODUCER" => 4,
+            "SPAN_KIND_CONSUMER" => 5,
+            _ => return None,
+        },
+        _ => return None,
+    };
+    match kind {
+        1 => Some(SpanKindSpec::Internal),
+        2 => Some(SpanKindSpec::Server),
+        3 => Some(SpanKindSpec::Client),
+        4 => Some(SpanKindSpec::Producer),
+        5 => Some(SpanKindSpec::Consumer),
+        _ => None,
+    }
+}
+
+/// Converts an OTLP metric into a sample.
+fn metric_sample(metric: &Value) -> SampleMetric {
+    let (instrument, data) = if let Some(gauge) = metric.get("gauge") {
+        (Some(InstrumentSpec::Gauge), Some(gauge))
+    } else if let Some(sum) = metric.get("sum") {
+        let is_monotonic = sum
+            .get("isMonotonic")
+            .and_then(Value::as_bool)
+            .unwrap_or(false);
+        let instrument = if is_monotonic {
+            InstrumentSpec::Counter
+        } else {
+            InstrumentSpec::UpDownCounter
+        };
+        (Some(instrument), Some(sum))
+    } else if let Some(histogram) = metric
+        .get("histogram")
+        .or_else(|| metric.get("exponentialHistogram"))
+    {
+        (Some(InstrumentSpec::Histogram), Some(histogram))
+    } else {
+        (None, metric.get("summary"))
+    };
+    SampleMetric {
+        name: string(metric.get("name")),
+        instrument,
+        unit: string(metric.get("unit")),
+        data_points: items(data.and_then(|data| data.get("dataPoints")))
+            .map(attributes)
+            .collect(),
+    }
+}
+
+/// Converts the resource of a `resourceSpans`, `resourceMetrics`, or
+/// `resourceLogs` entry into a sample.
+fn resource_sample(resource_entry: &Value) -> Option<Sample> {
+    resource_entry.get("resource").map(|resource| {
+        Sample::Resource(SampleResource {
+            attributes: attributes(resource),
+        })
+    })
+}
+
+/// Returns the attributes of an OTLP element.
+fn attributes(element: &Value) -> Vec<SampleAttribute> {
+    items(element.get("attributes"))
+        .map(|kv| SampleAttribute {
+            name: string(kv.get("key")),
+            value: kv.get("value").map_or(Value::Null, any_value),
+        })
+        .collect()
+}
+
+/// Converts an OTLP `AnyValue` into a JSON value. Byte arrays don't have any
+/// corresponding semantic convention type and are converted to null.
+fn any_value(value: &Value) -> Value {
+    if let Some(v) = value.get("stringValue") {
+        v.clone()
+    } else if let Some(v) = value.get("boolValue") {
+        v.clone()
+    } else if let Some(v) = value.get("intValue") {
+        // 64-bit integers are encoded as strings.
+        match v {
+            Value::String(s) => s.parse::<i64>().map_or(Value::Null, Value::from),
+            v => v.clone(),
+        }
+    } else if let Some(v) = value.get("doubleValue") {
+        // Doubles are encoded as numbers, or as strings for NaN and infinity.
+        match v {
+            Value::Number(n) => n.as_f64().map_or(Value::Null, Value::from),
+            _ => Value::Null,
+        }
+    } else if let Some(array) = value.get("arrayValue") {
+        Value::Array(items(array.get("values")).map(any_value).collect())
+    } else if let Some(kvlist) = value.get("kvlistValue") {
+        Value::Object(
+            items(kvlist.get("values"))
+                .map(|kv| {
+                    (
+                        string(kv.get("key")),
+                        kv.get("value").map_or(Value::Null, any_value),
+                    )
+                })
+                .collect::<Map<String, Value>>(),
+        )
+    } else {
+        Value::Null
+    }
+}
+
+/// Returns the items of an optional JSON array.
+fn items(value: Option<&Value>) -> impl Iterator<Item = &Value> {
+    value
+        .and_then(Value::as_array)
+        .into_iter()
+        .flat_map(|items| items.iter())
+}
+
+/// Returns the content of an optional JSON string.
+fn string(value: Option<&Value>) -> String {
+    value.and_then(Value::as_str).unwrap_or_default().to_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_json_samples() {
+        let request = json!({
+            "resourceMetrics": [{
+                "scopeMetrics": [{
+                    "metrics": [{
+                        "name": "requests",
+                        "unit": "1",
+                        "sum": {
+                            "isMonotonic": true,
+                            "dataPoints": [{
+                                "asInt": "1",
+                                "attributes": [
+                                    {"key": "int", "value": {"intValue": "42"}},
+                                    {"key": "double", "value": {"doubleValue": 1.5}},
+                                    {"key": "array", "value": {"arrayValue": {"values": [{"boolValue": true}]}}},
+                                    {"key": "bytes", "value": {"bytesValue": "AQI="}}
+                                ]
+                            }]
+                        }
+                    }]
+                }]
+            }]
+        });
+        assert_eq!(
+            json_samples(&request).expect("Failed to convert the request"),
+            vec![Sample::Metric(SampleMetric {
+                name: "requests".to_owned(),
+                instrument: Some(InstrumentSpec::Counter),
+                unit: "1".to_owned(),
+                data_points: vec![vec![
+                    SampleAttribute {
+                        name: "int".to_owned(),
+                        value: json!(42),
+                    },
+                    SampleAttribute {
+                        name: "double".to_owned(),
+                        value: json!(1.5),
+                    },
+                    SampleAttribute {
+                        name: "array".to_owned(),
+                        value: json!([true]),
+                    },
+                    SampleAttribute {
+                        name: "bytes".to_owned(),
+                        value: Value::Null,
+                    },
+                ]],
+            })]
+        );
+
+        assert!(json_samples(&json!({"spans": []})).is_err());
+    }
+
+    #[test]
+    fn test_json_signal_samples() {
+        let request = json!({
+            "resourceSpans": [{
+                "scopeSpans": [{
+                    "spans": [
+                        {"name": "GET /", "kind": 2},
+                        {"name": "publish", "kind": "SPAN_KIND_PRODUCER"},
+                        {"name": "unspecified", "kind": 0}
+                    ]
+                }]
+            }]
+        });
+        let kinds: Vec<_> = json_trace_samples(&request)
+            .expect("Failed to convert the request")
+            .into_iter()
+            .map(|sample| match sample {
+                Sample::Span(span) => span.kind,
+                _ => panic!("Expected a span"),
+            })
+            .collect();
+        assert_eq!(
+            kinds,
+            vec![
+                Some(SpanKindSpec::Server),
+                Some(SpanKindSpec::Producer),
+                None
+            ]
+        );
+
+        // The export requests of the other signals are rejected.
+        assert!(json_metric_samples(&request).is_err());
+        assert!(json_log_samples(&request).is_err());
+        assert_eq!(json_log_samples(&json!({})), Ok(vec![]));
+    }
+}
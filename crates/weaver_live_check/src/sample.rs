@@ -0,0 +1,162 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Telemetry samples observed from an application under test.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use weaver_semconv::attribute::PrimitiveOrArrayTypeSpec;
+use weaver_semconv::group::{InstrumentSpec, SpanKindSpec};
+
+/// An attribute observed in the telemetry.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SampleAttribute {
+    /// The name of the attribute.
+    pub name: String,
+    /// The value of the attribute.
+    pub value: Value,
+}
+
+impl SampleAttribute {
+    /// Returns the semantic convention type of the observed value, or `None`
+    /// if the value doesn't have any corresponding semantic convention type
+    /// (e.g. null, empty or heterogeneous array, or map).
+    #[must_use]
+    pub fn r#type(&self) -> Option<PrimitiveOrArrayTypeSpec> {
+        match &self.value {
+            Value::Bool(_) => Some(PrimitiveOrArrayTypeSpec::Boolean),
+            Value::Number(n) if n.is_f64() => Some(PrimitiveOrArrayTypeSpec::Double),
+            Value::Number(_) => Some(PrimitiveOrArrayTypeSpec::Int),
+            Value::String(_) => Some(PrimitiveOrArrayTypeSpec::String),
+            Value::Array(values) => {
+                let item_types: Vec<_> = values
+                    .iter()
+                    .map(|value| {
+                        SampleAttribute {
+                            name: String::new(),
+                            value: value.clone(),
+                        }
+                        .r#type()
+                    })
+                    .collect();
+                let first = item_types.first().cloned().flatten()?;
+                if item_types.iter().any(|t| t.as_ref() != Some(&first)) {
+                    return None;
+                }
+                match first {
+                    PrimitiveOrArrayTypeSpec::Boolean => Some(PrimitiveOrArrayTypeSpec::Booleans),
+                    PrimitiveOrArrayTypeSpec::Int => Some(PrimitiveOrArrayTypeSpec::Ints),
+                    PrimitiveOrArrayTypeSpec::Double => Some(PrimitiveOrArrayTypeSpec::Doubles),
+                    PrimitiveOrArrayTypeSpec::String => Some(PrimitiveOrArrayTypeSpec::Strings),
+                    _ => None,
+                }
+            }
+            Value::Null | Value::Object(_) => None,
+        }
+    }
+}
+
+/// An event recorded on a span.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SampleSpanEvent {
+    /// The name of the event.
+    pub name: String,
+    /// The attributes of the event.
+    pub attributes: Vec<SampleAttribute>,
+}
+
+/// A span observed in the telemetry.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SampleSpan {
+    /// The name of the span.
+    pub name: String,
+    /// The kind of the span, `None` if unspecified.
+    pub kind: Option<SpanKindSpec>,
+    /// The attributes of the span.
+    pub attributes: Vec<SampleAttribute>,
+    /// The events recorded on the span.
+    pub events: Vec<SampleSpanEvent>,
+}
+
+/// A metric observed in the telemetry.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SampleMetric {
+    /// The name of the metric.
+    pub name: String,
+    /// The instrument inferred from the metric data type, if any.
+    pub instrument: Option<InstrumentSpec>,
+    /// The unit of the metric.
+    pub unit: String,
+    /// The attributes of each data point of the metric.
+    pub data_points: Vec<Vec<SampleAttribute>>,
+}
+
+/// A log record observed in the telemetry.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SampleLog {
+    /// The attributes of the log record.
+    pub attributes: Vec<SampleAttribute>,
+}
+
+/// A resource observed in the telemetry.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SampleResource {
+    /// The attributes of the resource.
+    pub attributes: Vec<SampleAttribute>,
+}
+
+/// A telemetry sample received from the application under test.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Sample {
+    /// A resource.
+    Resource(SampleResource),
+    /// A span.
+    Span(SampleSpan),
+    /// A metric.
+    Metric(SampleMetric),
+    /// A log record.
+    Log(SampleLog),
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_attribute_type() {
+        let attr_type = |value: Value| {
+            SampleAttribute {
+                name: "attr".to_owned(),
+                value,
+            }
+            .r#type()
+        };
+        assert_eq!(
+            attr_type(json!(true)),
+            Some(PrimitiveOrArrayTypeSpec::Boolean)
+        );
+        assert_eq!(attr_type(json!(1)), Some(PrimitiveOrArrayTypeSpec::Int));
+        assert_eq!(
+            attr_type(json!(1.5)),
+            Some(PrimitiveOrArrayTypeSpec::Double)
+        );
+        assert_eq!(
+            attr_type(json!("a")),
+            Some(PrimitiveOrArrayTypeSpec::String)
+        );
+        assert_eq!(
+            attr_type(json!(["a", "b"])),
+            Some(PrimitiveOrArrayTypeSpec::Strings)
+        );
+        assert_eq!(
+            attr_type(json!([1, 2])),
+            Some(PrimitiveOrArrayTypeSpec::Ints)
+        );
+        assert_eq!(attr_type(json!([1, "a"])), None);
+        assert_eq!(attr_type(json!([])), None);
+        assert_eq!(attr_type(json!({"a": 1})), None);
+    }
+}
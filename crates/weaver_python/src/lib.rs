@@ -0,0 +1,197 @@
+// SPDX-License-Identifier: Apache-2.0
+
+#![doc = include_str!("../README.md")]
+
+use pyo3::create_exception;
+use pyo3::exceptions::PyException;
+use pyo3::prelude::{
+    pyclass, pyfunction, pymethods, pymodule, wrap_pyfunction, Bound, FromPyObject, PyAny,
+    PyAnyMethods, PyErr, PyModule, PyModuleMethods, PyObject, PyResult, Python,
+};
+use serde::Serialize;
+
+use weaver_embed::{GroupType, RegistryDiff, RegistryLoader, ResolvedGroup};
+use weaver_schema::diff::diff_registries as diff_resolved_registries;
+use weaver_version::VersionSpec;
+
+create_exception!(
+    weaver,
+    WeaverError,
+    PyException,
+    "Error raised when a registry can't be loaded, resolved, or queried."
+);
+
+/// Converts a weaver error into a Python exception.
+fn weaver_error(error: impl ToString) -> PyErr {
+    WeaverError::new_err(error.to_string())
+}
+
+/// Converts a serializable value into the corresponding Python object (dict,
+/// list, str, ...), through its JSON representation.
+fn to_python(py: Python<'_>, value: &impl Serialize) -> PyResult<PyObject> {
+    let json = serde_json::to_string(value).map_err(weaver_error)?;
+    Ok(py.import("json")?.call_method1("loads", (json,))?.unbind())
+}
+
+/// A resolved semantic convention registry.
+///
+/// The groups and attributes are returned as dictionaries, in the format of
+/// `weaver registry resolve --format json`.
+#[pyclass(module = "weaver", frozen)]
+struct Registry {
+    registry: weaver_embed::Registry,
+}
+
+#[pymethods]
+impl Registry {
+    /// Returns the groups of the registry, optionally only the groups of the
+    /// given type (e.g. `metric`).
+    #[pyo3(signature = (r#type = None))]
+    fn groups(&self, py: Python<'_>, r#type: Option<&str>) -> PyResult<PyObject> {
+        let groups: Vec<&ResolvedGroup> = self
+            .registry
+            .groups()
+            .iter()
+            .filter(|group| r#type.map_or(true, |t| group_type_name(&group.r#type) == t))
+            .collect();
+        to_python(py, &groups)
+    }
+
+    /// Returns the group with the given id, or `None`.
+    fn group(&self, py: Python<'_>, id: &str) -> PyResult<PyObject> {
+        to_python(py, &self.registry.group(id))
+    }
+
+    /// Returns the metric group with the given metric name, or `None`.
+    fn metric(&self, py: Python<'_>, name: &str) -> PyResult<PyObject> {
+        to_python(py, &self.registry.metric(name))
+    }
+
+    /// Returns the attribute with the given name, or `None`.
+    fn attribute(&self, py: Python<'_>, name: &str) -> PyResult<PyObject> {
+        to_python(py, &self.registry.attribute(name))
+    }
+
+    /// Returns the catalog of the attributes of the registry (one definition
+    /// per attribute name, sorted by name).
+    fn attributes(&self, py: Python<'_>) -> PyResult<PyObject> {
+        to_python(py, &self.registry.attributes())
+    }
+
+    /// Evaluates a jq expression against the resolved registry and returns
+    /// the list of results.
+    fn query(&self, py: Python<'_>, expression: &str) -> PyResult<PyObject> {
+        let results = py
+            .allow_threads(|| self.registry.query(expression))
+            .map_err(weaver_error)?;
+        to_python(py, &results)
+    }
+
+    /// Returns the resolved registry as a dictionary.
+    fn to_dict(&self, py: Python<'_>) -> PyResult<PyObject> {
+        to_python(py, self.registry.resolved())
+    }
+
+    /// Returns the resolved registry in JSON.
+    fn to_json(&self) -> PyResult<String> {
+        self.registry.to_json().map_err(weaver_error)
+    }
+
+    /// Returns the resolved registry in YAML.
+    fn to_yaml(&self) -> PyResult<String> {
+        self.registry.to_yaml().map_err(weaver_error)
+    }
+
+    fn __len__(&self) -> usize {
+        self.registry.groups().len()
+    }
+
+    fn __repr__(&self) -> String {
+        format!("<Registry groups={}>", self.registry.groups().len())
+    }
+}
+
+/// Returns the name of a group type, as serialized in the resolved registry.
+fn group_type_name(group_type: &GroupType) -> String {
+    serde_json::to_value(group_type)
+        .ok()
+        .and_then(|value| value.as_str().map(ToOwned::to_owned))
+        .unwrap_or_default()
+}
+
+/// Loads and resolves a semantic convention registry from a local path or a
+/// Git URL (`http(s)://...`). Several registries can be merged by passing a
+/// list. The files of the local registries can be selected with glob patterns.
+#[pyfunction]
+#[pyo3(signature = (registry, *, sub_dir = None, include = Vec::new(), exclude = Vec::new()))]
+fn resolve_registry(
+    py: Python<'_>,
+    registry: RegistryPaths,
+    sub_dir: Option<&str>,
+    include: Vec<String>,
+    exclude: Vec<String>,
+) -> PyResult<Registry> {
+    let mut loader = RegistryLoader::new();
+    for path in registry.0 {
+        loader = if path.starts_with("http://") || path.starts_with("https://") {
+            loader.git(path, sub_dir)
+        } else {
+            loader.local(path)
+        };
+    }
+    for pattern in include {
+        loader = loader.include(pattern);
+    }
+    for pattern in exclude {
+        loader = loader.exclude(pattern);
+    }
+    let registry = py.allow_threads(|| loader.load()).map_err(weaver_error)?;
+    Ok(Registry { registry })
+}
+
+/// The differences between two versions of a registry, as returned to Python.
+#[derive(Serialize)]
+struct Diff {
+    #[serde(flatten)]
+    diff: RegistryDiff,
+    /// The renamed attributes and metrics.
+    renames: VersionSpec,
+}
+
+/// Computes the differences between the baseline registry and the current
+/// registry: the attributes and the groups added, removed, and changed (with
+/// the baseline and current values of each changed field), and the renamed
+/// attributes and metrics, in the format of the `versions` section of a
+/// telemetry schema file.
+#[pyfunction]
+fn diff_registries(py: Python<'_>, baseline: &Registry, current: &Registry) -> PyResult<PyObject> {
+    let diff = py
+        .allow_threads(|| current.registry.diff(&baseline.registry))
+        .map_err(weaver_error)?;
+    let renames =
+        diff_resolved_registries(baseline.registry.resolved(), current.registry.resolved());
+    to_python(py, &Diff { diff, renames })
+}
+
+/// One or several registry paths.
+struct RegistryPaths(Vec<String>);
+
+impl<'py> FromPyObject<'py> for RegistryPaths {
+    fn extract_bound(ob: &Bound<'py, PyAny>) -> PyResult<Self> {
+        match ob.extract::<String>() {
+            Ok(path) => Ok(Self(vec![path])),
+            Err(_) => Ok(Self(ob.extract::<Vec<String>>()?)),
+        }
+    }
+}
+
+/// Python bindings of weaver.
+#[pymodule]
+#[pyo3(name = "weaver")]
+fn weaver_python(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<Registry>()?;
+    m.add_function(wrap_pyfunction!(resolve_registry, m)?)?;
+    m.add_function(wrap_pyfunction!(diff_registries, m)?)?;
+    m.add("WeaverError", m.py().get_type::<WeaverError>())?;
+    Ok(())
+}
@@ -0,0 +1,72 @@
+# SPDX-License-Identifier: Apache-2.0
+
+"""Tests of the Python bindings of weaver (run `maturin develop` first)."""
+
+from pathlib import Path
+
+import pytest
+
+import weaver
+
+CRATES = Path(__file__).resolve().parents[2]
+REGISTRY = str(CRATES / "weaver_codegen_test" / "semconv_registry")
+
+
+@pytest.fixture(scope="module")
+def registry():
+    return weaver.resolve_registry(REGISTRY)
+
+
+def test_resolve_registry(registry):
+    assert len(registry) == len(registry.groups())
+    assert repr(registry).startswith("<Registry groups=")
+
+    metric = registry.metric("http.server.request.duration")
+    assert metric["id"] == "metric.http.server.request.duration"
+    assert registry.group(metric["id"]) == metric
+    assert registry.metric("does.not.exist") is None
+
+    assert all(group["type"] == "metric" for group in registry.groups(type="metric"))
+    assert registry.attribute("http.request.method")["type"]
+
+    names = [attr["name"] for attr in registry.attributes()]
+    assert names == sorted(set(names))
+    assert "http.request.method" in names
+
+
+def test_query_and_serialization(registry):
+    metric_names = registry.query('[.groups[] | select(.type == "metric") | .metric_name] | sort')
+    assert "http.server.request.duration" in metric_names[0]
+    assert registry.to_dict()["groups"] == registry.groups()
+    assert '"groups"' in registry.to_json()
+    assert "groups:" in registry.to_yaml()
+
+    with pytest.raises(weaver.WeaverError):
+        registry.query(".groups[] |")
+
+
+def test_file_filter():
+    registry = weaver.resolve_registry([REGISTRY], exclude=["metrics/**"])
+    assert registry.metric("http.server.request.duration") is None
+
+
+def test_errors():
+    with pytest.raises(weaver.WeaverError):
+        weaver.resolve_registry([])
+    with pytest.raises(weaver.WeaverError):
+        weaver.resolve_registry(REGISTRY, include=["["])
+
+
+def test_diff_registries(registry):
+    no_changes = {"added": [], "removed": [], "changed": {}}
+    assert weaver.diff_registries(registry, registry) == {
+        "attributes": no_changes,
+        "groups": no_changes,
+        "renames": {},
+    }
+
+    baseline = weaver.resolve_registry([REGISTRY], exclude=["metrics/**"])
+    diff = weaver.diff_registries(baseline, registry)
+    assert "metric.http.server.request.duration" in diff["groups"]["added"]
+    assert diff["groups"]["removed"] == []
+    assert diff["attributes"]["removed"] == []
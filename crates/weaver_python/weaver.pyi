@@ -0,0 +1,29 @@
+# SPDX-License-Identifier: Apache-2.0
+
+from typing import Any, Dict, List, Optional, Sequence, Union
+
+class WeaverError(Exception):
+    """Error raised when a registry can't be loaded, resolved, or queried."""
+
+class Registry:
+    """A resolved semantic convention registry."""
+
+    def groups(self, type: Optional[str] = None) -> List[Dict[str, Any]]: ...
+    def group(self, id: str) -> Optional[Dict[str, Any]]: ...
+    def metric(self, name: str) -> Optional[Dict[str, Any]]: ...
+    def attribute(self, name: str) -> Optional[Dict[str, Any]]: ...
+    def attributes(self) -> List[Dict[str, Any]]: ...
+    def query(self, expression: str) -> List[Any]: ...
+    def to_dict(self) -> Dict[str, Any]: ...
+    def to_json(self) -> str: ...
+    def to_yaml(self) -> str: ...
+    def __len__(self) -> int: ...
+
+def resolve_registry(
+    registry: Union[str, Sequence[str]],
+    *,
+    sub_dir: Optional[str] = None,
+    include: Sequence[str] = (),
+    exclude: Sequence[str] = (),
+) -> Registry: ...
+def diff_registries(baseline: Registry, current: Registry) -> Dict[str, Any]: ...
@@ -4,27 +4,38 @@
 
 //! Specification of a resolved attribute.
 
+use crate::interned::InternedStr;
 use crate::tags::Tags;
 use crate::value::Value;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::fmt::Display;
 use std::ops::Not;
+use weaver_semconv::annotation::Annotations;
 use weaver_semconv::attribute::{AttributeSpec, AttributeType, Examples, RequirementLevel};
+use weaver_semconv::sensitivity::Sensitivity;
 use weaver_semconv::stability::Stability;
 
 /// An attribute definition.
 #[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq, Hash, JsonSchema)]
 #[serde(deny_unknown_fields)]
 pub struct Attribute {
-    /// Attribute name.
-    pub name: String,
+    /// Attribute name, fully qualified with the group prefix if any.
+    pub name: InternedStr,
+    /// Attribute name as declared, before the group prefix is applied. Only
+    /// set when it differs from the fully qualified name (e.g. `method` for
+    /// the attribute `http.method` declared in a group with the `http`
+    /// prefix).
+    #[serde(default)]
+    #[serde(skip_serializing_if = "InternedStr::is_empty")]
+    pub short_name: InternedStr,
     /// Either a string literal denoting the type as a primitive or an
     /// array type, a template type or an enum definition.
     pub r#type: AttributeType,
     /// A brief description of the attribute.
-    #[serde(skip_serializing_if = "String::is_empty")]
-    pub brief: String,
+    #[serde(skip_serializing_if = "InternedStr::is_empty")]
+    pub brief: InternedStr,
     /// Sequence of example values for the attribute or single example
     /// value. They are required only for string and string array
     /// attributes. Example values must be of the same type of the
@@ -50,9 +61,9 @@ pub struct Attribute {
     pub sampling_relevant: Option<bool>,
     /// A more elaborate description of the attribute.
     /// It defaults to an empty string.
-    #[serde(skip_serializing_if = "String::is_empty")]
+    #[serde(skip_serializing_if = "InternedStr::is_empty")]
     #[serde(default)]
-    pub note: String,
+    pub note: InternedStr,
     /// Specifies the stability of the attribute.
     /// Note that, if stability is missing but deprecated is present, it will
     /// automatically set the stability to deprecated. If deprecated is
@@ -65,6 +76,19 @@ pub struct Attribute {
     /// to use instead. See also stability.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub deprecated: Option<String>,
+    /// The previous names of the attribute, i.e. the names of the attributes
+    /// it replaces.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub renamed_from: Vec<String>,
+    /// The sensitivity classification of the values of the attribute.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sensitivity: Option<Sensitivity>,
+    /// The annotations of the attribute.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Annotations::is_empty")]
+    #[schemars(with = "BTreeMap<String, serde_json::Value>")]
+    pub annotations: Annotations,
     /// Specifies the prefix of the attribute.
     /// If this parameter is set, the resolved id of the referenced attribute will
     /// have group prefix added to it.
@@ -4,8 +4,9 @@
 //! that are shared across multiple signals in the Resolved Telemetry Schema.
 
 use crate::attribute::{Attribute, AttributeRef};
+use crate::interned::StringInterner;
 use schemars::JsonSchema;
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize};
 use std::collections::{BTreeMap, HashMap};
 use std::fmt::Debug;
 use weaver_semconv::attribute::{AttributeType, BasicRequirementLevelSpec, RequirementLevel};
@@ -21,9 +22,27 @@ use weaver_semconv::stability::Stability;
 pub struct Catalog {
     /// Catalog of attributes used in the schema.
     #[serde(skip_serializing_if = "Vec::is_empty")]
+    #[serde(deserialize_with = "deserialize_attributes")]
     pub attributes: Vec<Attribute>,
 }
 
+/// Deserializes the attributes of a catalog, interning their strings (names,
+/// briefs, notes) so that identical strings share the same allocation as they
+/// do after the resolution.
+fn deserialize_attributes<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Vec<Attribute>, D::Error> {
+    let mut attributes = Vec::<Attribute>::deserialize(deserializer)?;
+    let mut strings = StringInterner::new();
+    for attr in &mut attributes {
+        strings.intern_in_place(&mut attr.name);
+        strings.intern_in_place(&mut attr.short_name);
+        strings.intern_in_place(&mut attr.brief);
+        strings.intern_in_place(&mut attr.note);
+    }
+    Ok(attributes)
+}
+
 /// Statistics on a catalog.
 #[derive(Debug, Serialize)]
 #[must_use]
@@ -38,6 +57,17 @@ pub struct Stats {
     pub stability_breakdown: HashMap<Stability, usize>,
     /// Number of deprecated attributes.
     pub deprecated_count: usize,
+    /// Statistics on the enum attributes, by attribute name.
+    pub enum_stats: BTreeMap<String, EnumStats>,
+}
+
+/// Statistics on an enum attribute.
+#[derive(Debug, Serialize, PartialEq)]
+pub struct EnumStats {
+    /// Number of members of the enum.
+    pub member_count: usize,
+    /// True if values other than the members are accepted (open-ended enum).
+    pub allow_custom_values: bool,
 }
 
 impl Catalog {
@@ -64,8 +94,8 @@ impl Catalog {
                 .attributes
                 .iter()
                 .map(|attr| {
-                    if let AttributeType::Enum { members, .. } = &attr.r#type {
-                        (format!("enum(card:{:03})", members.len()), 1)
+                    if let AttributeType::Enum { .. } = &attr.r#type {
+                        ("enum".to_owned(), 1)
                     } else {
                         (format!("{:#}", attr.r#type), 1)
                     }
@@ -108,6 +138,50 @@ impl Catalog {
                 .iter()
                 .filter(|attr| attr.deprecated.is_some())
                 .count(),
+            // The catalog may contain several variants of the same attribute.
+            enum_stats: self
+                .attributes
+                .iter()
+                .filter_map(|attr| match &attr.r#type {
+                    AttributeType::Enum {
+                        allow_custom_values,
+                        members,
+                    } => Some((
+                        attr.name.as_str().to_owned(),
+                        EnumStats {
+                            member_count: members.len(),
+                            allow_custom_values: *allow_custom_values,
+                        },
+                    )),
+                    _ => None,
+                })
+                .collect(),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deserialized_strings_are_interned() {
+        let catalog: Catalog = serde_json::from_str(
+            r#"{"attributes": [
+                {"name": "http.request.method", "type": "string", "brief": "HTTP request method.", "note": "See RFC9110.", "requirement_level": "recommended"},
+                {"name": "http.request.method_original", "type": "string", "brief": "HTTP request method.", "note": "See RFC9110.", "requirement_level": "recommended"}
+            ]}"#,
+        )
+        .expect("Failed to deserialize the catalog");
+        let json = serde_json::to_string(&catalog).expect("Failed to serialize the catalog");
+        let catalog: Catalog =
+            serde_json::from_str(&json).expect("Failed to deserialize the catalog");
+
+        let [first, second] = catalog.attributes.as_slice() else {
+            panic!("Expected two attributes");
+        };
+        assert!(first.brief.ptr_eq(&second.brief));
+        assert!(first.note.ptr_eq(&second.note));
+        assert!(!first.name.ptr_eq(&second.name));
+    }
+}
@@ -0,0 +1,252 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Interned strings shared by the items of a resolved schema.
+//!
+//! Large registries repeat the same names, namespace prefixes, and briefs
+//! thousands of times (e.g. an attribute referenced by many groups is copied
+//! in each group of the template registry). An [`InternedStr`] is an immutable
+//! reference-counted string, so cloning it doesn't copy the string, and a
+//! [`StringInterner`] returns the same allocation for identical strings.
+//! Interned strings are serialized as plain strings, and interned again when a
+//! catalog is deserialized.
+
+use std::borrow::Borrow;
+use std::collections::HashSet;
+use std::fmt::{Debug, Display, Formatter};
+use std::ops::Deref;
+use std::sync::Arc;
+
+use schemars::gen::SchemaGenerator;
+use schemars::schema::Schema;
+use schemars::JsonSchema;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// An immutable, cheaply cloneable string.
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct InternedStr(Arc<str>);
+
+impl InternedStr {
+    /// Returns the string slice.
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Returns true if the string is empty.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns true if both interned strings share the same allocation.
+    #[must_use]
+    pub fn ptr_eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl Deref for InternedStr {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl AsRef<str> for InternedStr {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Borrow<str> for InternedStr {
+    fn borrow(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Display for InternedStr {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        Display::fmt(&*self.0, f)
+    }
+}
+
+impl Debug for InternedStr {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        Debug::fmt(&*self.0, f)
+    }
+}
+
+impl From<&str> for InternedStr {
+    fn from(value: &str) -> Self {
+        Self(Arc::from(value))
+    }
+}
+
+impl From<String> for InternedStr {
+    fn from(value: String) -> Self {
+        Self(Arc::from(value))
+    }
+}
+
+impl From<&String> for InternedStr {
+    fn from(value: &String) -> Self {
+        Self(Arc::from(value.as_str()))
+    }
+}
+
+impl From<InternedStr> for String {
+    fn from(value: InternedStr) -> Self {
+        value.0.to_string()
+    }
+}
+
+impl PartialEq<str> for InternedStr {
+    fn eq(&self, other: &str) -> bool {
+        &*self.0 == other
+    }
+}
+
+impl PartialEq<&str> for InternedStr {
+    fn eq(&self, other: &&str) -> bool {
+        &*self.0 == *other
+    }
+}
+
+impl PartialEq<String> for InternedStr {
+    fn eq(&self, other: &String) -> bool {
+        *self.0 == **other
+    }
+}
+
+impl PartialEq<InternedStr> for str {
+    fn eq(&self, other: &InternedStr) -> bool {
+        self == &*other.0
+    }
+}
+
+impl PartialEq<InternedStr> for &str {
+    fn eq(&self, other: &InternedStr) -> bool {
+        *self == &*other.0
+    }
+}
+
+impl PartialEq<InternedStr> for String {
+    fn eq(&self, other: &InternedStr) -> bool {
+        **self == *other.0
+    }
+}
+
+impl Serialize for InternedStr {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.0)
+    }
+}
+
+impl<'de> Deserialize<'de> for InternedStr {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer).map(Self::from)
+    }
+}
+
+impl JsonSchema for InternedStr {
+    fn schema_name() -> String {
+        String::schema_name()
+    }
+
+    fn json_schema(gen: &mut SchemaGenerator) -> Schema {
+        String::json_schema(gen)
+    }
+
+    fn is_referenceable() -> bool {
+        false
+    }
+}
+
+/// A table of interned strings. Interning a string already present in the
+/// table returns a clone of the existing [`InternedStr`].
+#[derive(Debug, Default, PartialEq)]
+pub struct StringInterner {
+    strings: HashSet<InternedStr>,
+}
+
+impl StringInterner {
+    /// Creates an empty table.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the interned string equal to the given string, adding it to
+    /// the table if needed.
+    pub fn intern(&mut self, value: &str) -> InternedStr {
+        if let Some(interned) = self.strings.get(value) {
+            return interned.clone();
+        }
+        let interned = InternedStr::from(value);
+        _ = self.strings.insert(interned.clone());
+        interned
+    }
+
+    /// Returns the interned string equal to the given string, reusing the
+    /// allocation of the string if it is not in the table yet.
+    pub fn intern_string(&mut self, value: String) -> InternedStr {
+        if let Some(interned) = self.strings.get(value.as_str()) {
+            return interned.clone();
+        }
+        let interned = InternedStr::from(value);
+        _ = self.strings.insert(interned.clone());
+        interned
+    }
+
+    /// Replaces the given interned string with the equal string of the table,
+    /// adding it to the table if needed, so that identical strings loaded
+    /// separately (e.g. deserialized) share the same allocation.
+    pub fn intern_in_place(&mut self, value: &mut InternedStr) {
+        if let Some(interned) = self.strings.get(value.as_str()) {
+            *value = interned.clone();
+        } else {
+            _ = self.strings.insert(value.clone());
+        }
+    }
+
+    /// Returns the number of distinct strings in the table.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.strings.len()
+    }
+
+    /// Returns true if the table is empty.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.strings.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_interner() {
+        let mut interner = StringInterner::new();
+        let first = interner.intern("http.request.method");
+        let second = interner.intern_string("http.request.method".to_owned());
+        let other = interner.intern("http.response.status_code");
+
+        assert!(first.ptr_eq(&second));
+        assert!(!first.ptr_eq(&other));
+        assert_eq!(interner.len(), 2);
+        assert_eq!(first, "http.request.method");
+        assert_eq!(first.as_str(), "http.request.method");
+    }
+
+    #[test]
+    fn test_serialization() {
+        let value = InternedStr::from("http.request.method");
+        let json = serde_json::to_string(&value).expect("Failed to serialize");
+        assert_eq!(json, "\"http.request.method\"");
+        let deserialized: InternedStr = serde_json::from_str(&json).expect("Failed to deserialize");
+        assert_eq!(deserialized, value);
+    }
+}
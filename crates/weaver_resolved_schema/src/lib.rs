@@ -4,19 +4,24 @@
 //! A Resolved Telemetry Schema is self-contained and doesn't contain any
 //! external references to other schemas or semantic conventions.
 
+use crate::attribute::Attribute;
 use crate::catalog::Catalog;
 use crate::instrumentation_library::InstrumentationLibrary;
-use crate::registry::Registry;
+use crate::registry::{Group, Registry};
 use crate::resource::Resource;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use weaver_semconv::attribute::RequirementLevel;
+use weaver_semconv::group::GroupType;
+use weaver_semconv::stability::Stability;
 use weaver_version::Versions;
 
 pub mod attribute;
 pub mod catalog;
 mod error;
 pub mod instrumentation_library;
+pub mod interned;
 pub mod lineage;
 pub mod metric;
 pub mod registry;
@@ -64,6 +69,24 @@ pub struct ResolvedTelemetrySchema {
     /// <https://github.com/open-telemetry/oteps/blob/main/text/0152-telemetry-schemas.md>
     #[serde(skip_serializing_if = "Option::is_none")]
     pub versions: Option<Versions>,
+    /// Index of the groups using each attribute of the catalog, by attribute
+    /// name. Built during the resolution (see
+    /// [`ResolvedTelemetrySchema::build_attribute_index`]), it can be cleared
+    /// to serialize the schema without it.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub attribute_index: BTreeMap<String, Vec<AttributeUsage>>,
+}
+
+/// A usage of an attribute by a group.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema)]
+pub struct AttributeUsage {
+    /// The id of the group using the attribute.
+    pub group_id: String,
+    /// The type of the group using the attribute.
+    pub group_type: GroupType,
+    /// The requirement level of the attribute in the group.
+    pub requirement_level: RequirementLevel,
 }
 
 /// Statistics on a resolved telemetry schema.
@@ -76,6 +99,43 @@ pub struct Stats {
     pub registry_stats: Vec<registry::Stats>,
     /// Statistics on the catalog.
     pub catalog_stats: catalog::Stats,
+    /// Number of groups referencing each attribute of the catalog (i.e. the
+    /// groups containing the attribute, except the group defining it), by
+    /// attribute name.
+    pub attribute_reuse: BTreeMap<String, usize>,
+    /// Statistics on each root namespace (e.g. `http`).
+    pub namespace_stats: BTreeMap<String, NamespaceStats>,
+}
+
+/// Statistics on the groups and attributes of a root namespace.
+///
+/// The namespace of an attribute is the first component of its name (e.g.
+/// `http` for `http.request.method`). The namespace of a group is the second
+/// component of its id, the first one being its kind (e.g. `http` for
+/// `registry.http` or `span.http.client`).
+#[derive(Debug, Serialize, Default)]
+#[must_use]
+pub struct NamespaceStats {
+    /// Number of groups by type.
+    pub group_breakdown: HashMap<GroupType, usize>,
+    /// Number of attributes.
+    pub attribute_count: usize,
+    /// Number of stable attributes.
+    pub stable_attribute_count: usize,
+    /// Number of deprecated attributes.
+    pub deprecated_attribute_count: usize,
+    /// Total length (in characters) of the briefs of the attributes.
+    pub total_brief_length: usize,
+}
+
+impl NamespaceStats {
+    /// Average length (in characters) of the briefs of the attributes.
+    #[must_use]
+    pub fn average_brief_length(&self) -> usize {
+        self.total_brief_length
+            .checked_div(self.attribute_count)
+            .unwrap_or_default()
+    }
 }
 
 impl ResolvedTelemetrySchema {
@@ -90,6 +150,58 @@ impl ResolvedTelemetrySchema {
         &self.catalog
     }
 
+    /// Returns true if both schemas contain the same registries, ignoring the
+    /// ordering and the lineage (see [`Registry::semantic_eq`]).
+    ///
+    /// Unlike [`Registry::semantic_eq`], the attribute references of the
+    /// groups are compared through the catalog of each schema, i.e. by the
+    /// attributes they refer to, so the schemas may have been resolved with
+    /// catalogs in a different order. Catalog entries that are not referenced
+    /// by any group are ignored.
+    #[must_use]
+    pub fn semantic_eq(&self, other: &ResolvedTelemetrySchema) -> bool {
+        self.registries.len() == other.registries.len()
+            && self.registries.iter().all(|(registry_id, registry)| {
+                other
+                    .registries
+                    .get(registry_id)
+                    .is_some_and(|other_registry| {
+                        registry.registry_url == other_registry.registry_url
+                            && semantic_groups(registry, &self.catalog)
+                                == semantic_groups(other_registry, &other.catalog)
+                    })
+            })
+    }
+
+    /// Builds the index of the groups using each attribute of the catalog (see
+    /// [`ResolvedTelemetrySchema::attribute_index`]). The usages of each
+    /// attribute are sorted by group id.
+    pub fn build_attribute_index(&mut self) {
+        let mut attribute_index: BTreeMap<String, Vec<AttributeUsage>> = BTreeMap::new();
+        for group in self
+            .registries
+            .values()
+            .flat_map(|registry| &registry.groups)
+        {
+            for attr_ref in &group.attributes {
+                if let Some(attr) = self.catalog.attribute(attr_ref) {
+                    attribute_index
+                        .entry(attr.name.as_str().to_owned())
+                        .or_default()
+                        .push(AttributeUsage {
+                            group_id: group.id.clone(),
+                            group_type: group.r#type.clone(),
+                            requirement_level: attr.requirement_level.clone(),
+                        });
+                }
+            }
+        }
+        for usages in attribute_index.values_mut() {
+            usages.sort_by(|a, b| a.group_id.cmp(&b.group_id));
+        }
+        self.attribute_index = attribute_index;
+    }
+
     /// Compute statistics on the resolved telemetry schema.
     pub fn stats(&self) -> Stats {
         let mut registry_stats = Vec::new();
@@ -100,10 +212,92 @@ impl ResolvedTelemetrySchema {
             registry_count: self.registries.len(),
             registry_stats,
             catalog_stats: self.catalog.stats(),
+            attribute_reuse: self.attribute_reuse(),
+            namespace_stats: self.namespace_stats(),
+        }
+    }
+
+    /// Computes the statistics on each root namespace.
+    fn namespace_stats(&self) -> BTreeMap<String, NamespaceStats> {
+        let mut namespace_stats: BTreeMap<String, NamespaceStats> = BTreeMap::new();
+        for group in self
+            .registries
+            .values()
+            .flat_map(|registry| &registry.groups)
+        {
+            let mut components = group.id.split('.');
+            let kind = components.next().unwrap_or_default();
+            let namespace = components.next().unwrap_or(kind);
+            *namespace_stats
+                .entry(namespace.to_owned())
+                .or_default()
+                .group_breakdown
+                .entry(group.r#type.clone())
+                .or_insert(0) += 1;
         }
+
+        // Several catalog entries share the same name when a reference
+        // overrides some fields of the attribute, only the first one is counted.
+        let mut names = HashSet::new();
+        for attr in &self.catalog.attributes {
+            if !names.insert(attr.name.as_str()) {
+                continue;
+            }
+            let namespace = attr.name.split('.').next().unwrap_or_default();
+            let stats = namespace_stats.entry(namespace.to_owned()).or_default();
+            stats.attribute_count += 1;
+            stats.stable_attribute_count += (attr.stability == Some(Stability::Stable)) as usize;
+            stats.deprecated_attribute_count += attr.deprecated.is_some() as usize;
+            stats.total_brief_length += attr.brief.trim().chars().count();
+        }
+        namespace_stats
+    }
+
+    /// Computes the number of groups referencing each attribute of the catalog.
+    /// Each attribute is defined by exactly one group, so every group containing
+    /// the attribute except one references it.
+    fn attribute_reuse(&self) -> BTreeMap<String, usize> {
+        self.attribute_index
+            .iter()
+            .map(|(name, usages)| {
+                let group_count = usages
+                    .iter()
+                    .map(|usage| usage.group_id.as_str())
+                    .collect::<HashSet<_>>()
+                    .len();
+                (name.clone(), group_count.saturating_sub(1))
+            })
+            .collect()
     }
 }
 
+/// Returns the normalized groups of a registry (see [`Group::normalize`]), each
+/// one with the attributes it refers to sorted by name instead of its attribute
+/// references (`None` for a reference missing from the catalog).
+fn semantic_groups<'a>(
+    registry: &Registry,
+    catalog: &'a Catalog,
+) -> Vec<(Group, Vec<Option<&'a Attribute>>)> {
+    let mut groups: Vec<_> = registry
+        .groups
+        .iter()
+        .map(|group| {
+            let mut attributes: Vec<_> = group
+                .attributes
+                .iter()
+                .map(|attr_ref| catalog.attribute(attr_ref))
+                .collect();
+            attributes.sort_by_key(|attr| attr.map(|attr| attr.name.clone()));
+            let mut group = group.clone();
+            group.attributes.clear();
+            group.normalize();
+            (group, attributes)
+        })
+        .collect();
+    groups.sort_by(|(a, _), (b, _)| a.id.cmp(&b.id));
+    groups
+}
+
 #[cfg(test)]
 mod tests {
     use crate::ResolvedTelemetrySchema;
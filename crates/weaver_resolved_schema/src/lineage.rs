@@ -10,6 +10,8 @@ use serde::{Deserialize, Serialize};
 use weaver_semconv::attribute::{AttributeSpec, Examples, RequirementLevel};
 use weaver_semconv::stability::Stability;
 
+use crate::registry::Constraint;
+
 /// Attribute lineage (at the field level).
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
@@ -26,6 +28,31 @@ pub struct AttributeLineage {
     #[serde(skip_serializing_if = "BTreeSet::is_empty")]
     #[serde(default)]
     pub locally_overridden_fields: BTreeSet<String>,
+
+    /// The group overriding each overridden field (local or inherited), by
+    /// field name. The overriding group is the group whose reference sets the
+    /// value of the field, it's either the local group or one of the extended
+    /// groups.
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    #[serde(default)]
+    pub overridden_by: BTreeMap<String, String>,
+}
+
+/// Lineage of a constraint inherited from an extended group.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema)]
+pub struct ConstraintLineage {
+    /// The group id where the constraint is coming from.
+    pub source_group: String,
+
+    /// The attribute ids of the inherited `any_of` constraint, if any.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    #[serde(default)]
+    pub any_of: Vec<String>,
+
+    /// The group id of the inherited `include` constraint, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub include: Option<String>,
 }
 
 /// Group lineage.
@@ -42,6 +69,11 @@ pub struct GroupLineage {
     #[serde(skip_serializing_if = "BTreeMap::is_empty")]
     #[serde(default)]
     attributes: BTreeMap<String, AttributeLineage>,
+
+    /// The constraints inherited from the extended groups.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    #[serde(default)]
+    constraints: Vec<ConstraintLineage>,
 }
 
 impl AttributeLineage {
@@ -52,6 +84,7 @@ impl AttributeLineage {
             source_group: source_group.to_owned(),
             inherited_fields: Default::default(),
             locally_overridden_fields: Default::default(),
+            overridden_by: Default::default(),
         }
     }
 
@@ -63,6 +96,7 @@ impl AttributeLineage {
             source_group: source_group.to_owned(),
             inherited_fields: Default::default(),
             locally_overridden_fields: Default::default(),
+            overridden_by: Default::default(),
         };
         match attr_spec {
             AttributeSpec::Ref {
@@ -110,6 +144,13 @@ impl AttributeLineage {
                 if *prefix {
                     _ = attr_lineage.inherited_fields.insert("prefix".to_owned());
                 }
+                // The fields set by the reference of the source group override
+                // the definition of the attribute.
+                attr_lineage.overridden_by = attr_lineage
+                    .inherited_fields
+                    .iter()
+                    .map(|field| (field.clone(), source_group.to_owned()))
+                    .collect();
             }
             AttributeSpec::Id {
                 brief,
@@ -151,6 +192,39 @@ impl AttributeLineage {
         attr_lineage
     }
 
+    /// Records the overriding groups of the inherited fields that are
+    /// recorded in the lineage of the attribute in the source group, if any.
+    /// This is used to trace an override back through a chain of `extends`.
+    pub fn inherit_overrides(&mut self, source_lineage: &AttributeLineage) {
+        for (field, group_id) in &source_lineage.overridden_by {
+            if self.inherited_fields.contains(field) {
+                _ = self.overridden_by.insert(field.clone(), group_id.clone());
+            }
+        }
+    }
+
+    /// Records the overriding group of each locally overridden field: the
+    /// overriding group recorded in `previous` for this field if any (i.e. the
+    /// lineage built while resolving the `extends` clause), the given group
+    /// otherwise.
+    pub fn record_overriding_group(&mut self, group_id: &str, previous: Option<&AttributeLineage>) {
+        for field in &self.locally_overridden_fields {
+            let overriding_group = previous
+                .and_then(|previous| previous.overriding_group(field))
+                .unwrap_or(group_id);
+            _ = self
+                .overridden_by
+                .insert(field.clone(), overriding_group.to_owned());
+        }
+    }
+
+    /// Returns the group overriding the given field, if the field is
+    /// overridden.
+    #[must_use]
+    pub fn overriding_group(&self, field: &str) -> Option<&str> {
+        self.overridden_by.get(field).map(String::as_str)
+    }
+
     /// Determines if the attribute lineage is empty.
     #[must_use]
     pub fn is_empty(&self) -> bool {
@@ -421,6 +495,7 @@ impl GroupLineage {
         Self {
             source_file: provenance.replace('\\', "/"),
             attributes: Default::default(),
+            constraints: Default::default(),
         }
     }
 
@@ -434,4 +509,39 @@ impl GroupLineage {
     pub fn source_file(&self) -> &str {
         &self.source_file
     }
+
+    /// Returns the lineage of an attribute, if the attribute is a reference to
+    /// an attribute defined in another group.
+    #[must_use]
+    pub fn attribute(&self, attr_id: &str) -> Option<&AttributeLineage> {
+        self.attributes.get(attr_id)
+    }
+
+    /// Adds the lineage of a constraint inherited from the given group.
+    pub fn add_inherited_constraint(&mut self, source_group: &str, constraint: &Constraint) {
+        self.constraints.push(ConstraintLineage {
+            source_group: source_group.to_owned(),
+            any_of: constraint.any_of.clone(),
+            include: constraint.include.clone(),
+        });
+    }
+
+    /// Returns the lineage of the constraints inherited from the extended
+    /// groups.
+    #[must_use]
+    pub fn inherited_constraints(&self) -> &[ConstraintLineage] {
+        &self.constraints
+    }
+
+    /// Returns the group the given constraint is inherited from, if the
+    /// constraint is inherited.
+    #[must_use]
+    pub fn constraint_source(&self, constraint: &Constraint) -> Option<&str> {
+        self.constraints
+            .iter()
+            .find(|lineage| {
+                lineage.any_of == constraint.any_of && lineage.include == constraint.include
+            })
+            .map(|lineage| lineage.source_group.as_str())
+    }
 }
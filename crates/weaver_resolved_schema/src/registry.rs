@@ -9,12 +9,15 @@ use std::collections::{BTreeMap, HashMap, HashSet};
 
 use serde::{Deserialize, Serialize};
 
+use weaver_semconv::annotation::Annotations;
+use weaver_semconv::attribute::RequirementLevel;
 use weaver_semconv::group::{GroupType, InstrumentSpec, SpanKindSpec};
 use weaver_semconv::stability::Stability;
 
 use crate::attribute::{Attribute, AttributeRef};
 use crate::catalog::Catalog;
 use crate::error::{handle_errors, Error};
+use crate::interned::InternedStr;
 use crate::lineage::GroupLineage;
 use crate::registry::GroupStats::{
     AttributeGroup, Event, Metric, MetricGroup, Resource, Scope, Span,
@@ -61,8 +64,8 @@ pub struct Group {
     /// Prefix for the attributes for this semantic convention.
     /// It defaults to an empty string.
     #[serde(default)]
-    #[serde(skip_serializing_if = "String::is_empty")]
-    pub prefix: String,
+    #[serde(skip_serializing_if = "InternedStr::is_empty")]
+    pub prefix: InternedStr,
     /// Reference another semantic convention id. It inherits the prefix,
     /// constraints, and all attributes defined in the specified semantic
     /// convention.
@@ -118,14 +121,30 @@ pub struct Group {
     pub unit: Option<String>,
     /// The name of the event. If not specified, the prefix is used.
     /// If prefix is empty (or unspecified), name is required.
+    /// For a scope, the name of the instrumentation scope.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub name: Option<String>,
+    /// The requirement level of the version of the instrumentation scope.
+    /// Note: only valid if type is scope.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub scope_version: Option<RequirementLevel>,
     /// The lineage of the group.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub lineage: Option<GroupLineage>,
     /// The readable name for attribute groups used when generating registry tables.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub display_name: Option<String>,
+    /// The URL of the documentation of the group.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub doc_url: Option<String>,
+    /// The version of the registry in which the group was introduced.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub since_version: Option<String>,
+    /// The annotations of the group.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Annotations::is_empty")]
+    #[schemars(with = "BTreeMap<String, serde_json::Value>")]
+    pub annotations: Annotations,
 }
 
 /// Common statistics for a group.
@@ -243,6 +262,36 @@ impl Registry {
             .filter(move |group| group_type == group.r#type)
     }
 
+    /// Normalizes the registry, so that registries defining the same groups
+    /// compare equal regardless of the order in which the groups were defined
+    /// and resolved. The groups are sorted by id, each group is normalized
+    /// (see [`Group::normalize`]).
+    pub fn normalize(&mut self) {
+        self.groups.sort_by(|a, b| a.id.cmp(&b.id));
+        for group in self.groups.iter_mut() {
+            group.normalize();
+        }
+    }
+
+    /// Returns true if both registries define the same groups, ignoring the
+    /// order of the groups, of their attributes, constraints, and events, and
+    /// the lineage of the groups.
+    ///
+    /// The attribute references are compared as is, so both registries must
+    /// refer to the same catalog. Use [`crate::ResolvedTelemetrySchema::semantic_eq`]
+    /// to compare registries resolved with different catalogs.
+    #[must_use]
+    pub fn semantic_eq(&self, other: &Registry) -> bool {
+        if self.registry_url != other.registry_url || self.groups.len() != other.groups.len() {
+            return false;
+        }
+        let mut registry = self.clone();
+        let mut other = other.clone();
+        registry.normalize();
+        other.normalize();
+        registry == other
+    }
+
     /// Statistics on a registry.
     pub fn stats(&self) -> Stats {
         Stats {
@@ -372,6 +421,16 @@ impl Group {
         Ok(attributes)
     }
 
+    /// Normalizes the group: the attribute references, the constraints, and
+    /// the events are sorted, and the lineage is removed.
+    pub fn normalize(&mut self) {
+        self.attributes.sort();
+        self.constraints
+            .sort_by(|a, b| (&a.include, &a.any_of).cmp(&(&b.include, &b.any_of)));
+        self.events.sort();
+        self.lineage = None;
+    }
+
     /// Returns true if the group contains at least one `include` constraint.
     #[must_use]
     pub fn has_include(&self) -> bool {
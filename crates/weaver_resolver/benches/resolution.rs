@@ -0,0 +1,211 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Benchmarks of the loading, the resolution, and the constraint checking of a
+//! semantic convention registry.
+//!
+//! The benchmarks run against a synthetic registry with the shape and the size
+//! of the OpenTelemetry registry (namespaces of attributes referenced, extended,
+//! and overridden by spans, events, and metrics). Set `WEAVER_BENCH_REGISTRY`
+//! to the `model` directory of a checkout of the OpenTelemetry semantic
+//! conventions to also benchmark the real registry.
+//!
+//! Run them with `cargo xtask bench`, which compares the results with the
+//! committed baselines (see `benches/baselines.json`).
+
+#![allow(missing_docs)]
+#![allow(clippy::print_stderr)]
+
+use std::fmt::Write;
+use std::path::Path;
+use std::time::Duration;
+
+use criterion::{criterion_group, criterion_main, BatchSize, Criterion};
+use tempdir::TempDir;
+
+use weaver_cache::Cache;
+use weaver_resolved_schema::registry::{Constraint, Registry};
+use weaver_resolved_schema::ResolvedTelemetrySchema;
+use weaver_resolver::registry::check_any_of_constraints;
+use weaver_resolver::SchemaResolver;
+use weaver_semconv::path::RegistryPath;
+use weaver_semconv::registry::SemConvRegistry;
+use weaver_semconv::semconv::SemConvSpec;
+
+/// Number of namespaces of the synthetic registry.
+const NAMESPACES: usize = 60;
+/// Number of attributes per namespace of the synthetic registry.
+const ATTRIBUTES_PER_NAMESPACE: usize = 20;
+
+/// Generates the semantic convention file of a namespace of the synthetic
+/// registry: an attribute registry, a common attribute group extended by two
+/// spans and three metrics, and an event.
+fn synthetic_namespace(ns: usize) -> String {
+    let mut yaml = String::from("groups:\n");
+    _ = write!(
+        yaml,
+        "  - id: registry.ns{ns}\n    type: attribute_group\n    prefix: ns{ns}\n    brief: Attributes of the namespace {ns}.\n    attributes:\n"
+    );
+    for attr in 0..ATTRIBUTES_PER_NAMESPACE {
+        let r#type = match attr % 4 {
+            0 => "string",
+            1 => "int",
+            2 => "boolean",
+            _ => "string[]",
+        };
+        _ = write!(
+            yaml,
+            "      - id: attr{attr}\n        type: {type}\n        stability: stable\n        brief: Attribute {attr} of the namespace {ns}.\n        note: A longer description of the attribute {attr}.\n        examples: {examples}\n",
+            examples = match r#type {
+                "string" => "['value']",
+                "int" => "[42]",
+                "boolean" => "[true]",
+                _ => "[['a', 'b']]",
+            }
+        );
+    }
+    _ = write!(
+        yaml,
+        "      - id: kind\n        type:\n          members:\n            - id: first\n              value: first\n              brief: The first kind.\n            - id: second\n              value: second\n              brief: The second kind.\n        stability: stable\n        brief: Kind of the operation.\n"
+    );
+
+    _ = write!(
+        yaml,
+        "  - id: attributes.ns{ns}.common\n    type: attribute_group\n    brief: Common attributes of the namespace {ns}.\n    attributes:\n      - ref: ns{ns}.kind\n        requirement_level: required\n"
+    );
+    for attr in 0..5 {
+        _ = writeln!(yaml, "      - ref: ns{ns}.attr{attr}");
+    }
+    // Attributes of the previous namespace are shared across namespaces.
+    let other = (ns + NAMESPACES - 1) % NAMESPACES;
+    _ = write!(yaml, "      - ref: ns{other}.attr0\n        brief: Attribute shared with the namespace {other}.\n");
+
+    for (span, kind) in ["client", "server"].iter().enumerate() {
+        _ = write!(
+            yaml,
+            "  - id: span.ns{ns}.{kind}\n    type: span\n    span_kind: {kind}\n    brief: The {kind} span of the namespace {ns}.\n    extends: attributes.ns{ns}.common\n    attributes:\n"
+        );
+        for attr in (5 + span * 5)..(10 + span * 5) {
+            _ = write!(
+                yaml,
+                "      - ref: ns{ns}.attr{attr}\n        requirement_level: recommended\n"
+            );
+        }
+        _ = write!(
+            yaml,
+            "    constraints:\n      - any_of:\n          - ns{ns}.kind\n"
+        );
+    }
+    for metric in 0..3 {
+        _ = write!(
+            yaml,
+            "  - id: metric.ns{ns}.metric{metric}\n    type: metric\n    metric_name: ns{ns}.metric{metric}\n    brief: Metric {metric} of the namespace {ns}.\n    instrument: histogram\n    unit: s\n    stability: stable\n    extends: attributes.ns{ns}.common\n    attributes:\n      - ref: ns{ns}.attr{attr}\n        requirement_level: opt_in\n",
+            attr = 15 + metric
+        );
+    }
+    _ = write!(
+        yaml,
+        "  - id: event.ns{ns}\n    type: event\n    name: ns{ns}.event\n    brief: Event of the namespace {ns}.\n    attributes:\n      - ref: ns{ns}.attr18\n      - ref: ns{ns}.attr19\n"
+    );
+    yaml
+}
+
+/// Writes the files of the synthetic registry into a temporary directory.
+fn write_synthetic_registry() -> TempDir {
+    let dir = TempDir::new("weaver-bench").expect("Failed to create the registry directory");
+    for ns in 0..NAMESPACES {
+        std::fs::write(
+            dir.path().join(format!("ns{ns}.yaml")),
+            synthetic_namespace(ns),
+        )
+        .expect("Failed to write the registry");
+    }
+    dir
+}
+
+/// Loads (reads and parses) the semantic convention files of a local registry.
+fn load(path: &Path, cache: &Cache) -> Vec<(String, SemConvSpec)> {
+    let registry_path = RegistryPath::Local {
+        path_pattern: path.to_string_lossy().into_owned(),
+    };
+    SchemaResolver::load_semconv_specs(&registry_path, cache).expect("Failed to load the registry")
+}
+
+/// Resolves the given semantic convention files.
+fn resolve(specs: Vec<(String, SemConvSpec)>) -> ResolvedTelemetrySchema {
+    let mut registry = SemConvRegistry::from_semconv_specs("default", specs);
+    SchemaResolver::resolve_semantic_convention_registry(&mut registry)
+        .expect("Failed to resolve the registry")
+}
+
+/// Returns the resolved registry with an `any_of` constraint (satisfied by the
+/// first attribute of the group) on each group, and the index of the attribute
+/// names of the catalog.
+fn constrained_registry(schema: &ResolvedTelemetrySchema) -> Registry {
+    let mut registry = schema
+        .registry("default")
+        .expect("Failed to get the registry")
+        .clone();
+    let attr_names: Vec<String> = schema
+        .catalog()
+        .attributes
+        .iter()
+        .map(|attr| attr.name.to_string())
+        .collect();
+    for group in registry.groups.iter_mut() {
+        let any_of = group
+            .attributes
+            .iter()
+            .take(1)
+            .map(|attr_ref| attr_names[attr_ref.0 as usize].clone())
+            .collect();
+        group.constraints.push(Constraint {
+            any_of,
+            include: None,
+        });
+    }
+    registry
+}
+
+/// Benchmarks the loading, the resolution, and the constraint checking of the
+/// registry stored in the given directory.
+fn bench_registry(c: &mut Criterion, name: &str, path: &Path) {
+    let mut group = c.benchmark_group(name);
+    _ = group
+        .sample_size(20)
+        .measurement_time(Duration::from_secs(10));
+
+    let cache = Cache::try_new().expect("Failed to create the cache");
+    _ = group.bench_function("load", |b| b.iter(|| load(path, &cache)));
+
+    let specs = load(path, &cache);
+    _ = group.bench_function("resolve", |b| {
+        b.iter_batched(|| specs.clone(), resolve, BatchSize::LargeInput);
+    });
+
+    let schema = resolve(specs);
+    let registry = constrained_registry(&schema);
+    let attrs: Vec<_> = schema.catalog().attributes.iter().collect();
+    _ = group.bench_function("check_constraints", |b| {
+        b.iter(|| check_any_of_constraints(&registry, &attrs));
+    });
+
+    group.finish();
+}
+
+fn synthetic_registry(c: &mut Criterion) {
+    let dir = write_synthetic_registry();
+    bench_registry(c, "synthetic", dir.path());
+}
+
+fn otel_registry(c: &mut Criterion) {
+    let Some(path) = std::env::var_os("WEAVER_BENCH_REGISTRY") else {
+        eprintln!(
+            "WEAVER_BENCH_REGISTRY is not set, skipping the OpenTelemetry registry benchmarks"
+        );
+        return;
+    };
+    bench_registry(c, "otel", Path::new(&path));
+}
+
+criterion_group!(benches, synthetic_registry, otel_registry);
+criterion_main!(benches);
@@ -2,14 +2,22 @@
 
 //! Attribute resolution.
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 use serde::Deserialize;
 
 use weaver_resolved_schema::attribute;
 use weaver_resolved_schema::attribute::AttributeRef;
+use weaver_resolved_schema::interned::{InternedStr, StringInterner};
 use weaver_resolved_schema::lineage::{AttributeLineage, GroupLineage};
-use weaver_semconv::attribute::AttributeSpec;
+use weaver_resolved_schema::tags::Tags;
+use weaver_semconv::annotation::Annotations;
+use weaver_semconv::attribute::{AttributeSpec, AttributeType, PrimitiveOrArrayTypeSpec};
+
+/// The tag set on the placeholder attributes created by the lenient resolution
+/// for the attribute references that can't be resolved. The value of the tag
+/// is the id of the group containing the unresolved reference.
+pub const UNRESOLVED_REF_TAG: &str = "weaver.unresolved_ref";
 
 /// A catalog of deduplicated resolved attributes with their corresponding reference.
 #[derive(Deserialize, Debug, Default, PartialEq)]
@@ -20,6 +28,13 @@ pub struct AttributeCatalog {
     /// A map of root attributes indexed by their name.
     /// Root attributes are attributes that doesn't inherit from another attribute.
     root_attributes: HashMap<String, AttributeWithGroupId>,
+    #[serde(skip)]
+    /// The table of the strings shared by the resolved attributes (names, briefs, notes).
+    strings: StringInterner,
+    #[serde(skip)]
+    /// A map of the names of the attributes declared in a group with a prefix
+    /// to their names before the prefix is applied.
+    short_names: HashMap<String, InternedStr>,
 }
 
 #[derive(Debug, PartialEq)]
@@ -39,6 +54,58 @@ impl AttributeCatalog {
             .or_insert_with(|| AttributeRef(next_id))
     }
 
+    /// Records the name of an attribute declared in a group with a prefix
+    /// before the prefix is applied.
+    pub fn add_short_name(&mut self, name: &str, short_name: &str) {
+        let short_name = self.strings.intern(short_name);
+        _ = self.short_names.insert(name.to_owned(), short_name);
+    }
+
+    /// Creates a placeholder attribute for an attribute reference that can't be
+    /// resolved and returns its reference. The placeholder is a string
+    /// attribute keeping the fields set by the reference, and tagged with
+    /// [`UNRESOLVED_REF_TAG`]. Returns `None` if the attribute spec is not a
+    /// reference.
+    pub fn placeholder(&mut self, group_id: &str, attr: &AttributeSpec) -> Option<AttributeRef> {
+        let AttributeSpec::Ref {
+            r#ref,
+            brief,
+            examples,
+            tag,
+            requirement_level,
+            sampling_relevant,
+            note,
+            stability,
+            deprecated,
+            ..
+        } = attr
+        else {
+            return None;
+        };
+        let placeholder = attribute::Attribute {
+            name: self.strings.intern(r#ref),
+            short_name: InternedStr::default(),
+            r#type: AttributeType::PrimitiveOrArray(PrimitiveOrArrayTypeSpec::String),
+            brief: self.strings.intern(brief.as_deref().unwrap_or_default()),
+            examples: examples.clone(),
+            tag: tag.clone(),
+            requirement_level: requirement_level.clone().unwrap_or_default(),
+            sampling_relevant: *sampling_relevant,
+            note: self.strings.intern(note.as_deref().unwrap_or_default()),
+            stability: stability.clone(),
+            deprecated: deprecated.clone(),
+            renamed_from: vec![],
+            sensitivity: None,
+            annotations: Annotations::new(),
+            tags: Some(Tags {
+                tags: BTreeMap::from([(UNRESOLVED_REF_TAG.to_owned(), group_id.to_owned())]),
+            }),
+            value: None,
+            prefix: false,
+        };
+        Some(self.attribute_ref(placeholder))
+    }
+
     /// Returns a list of deduplicated attributes ordered by their references.
     #[must_use]
     pub fn drain_attributes(self) -> Vec<attribute::Attribute> {
@@ -48,6 +115,15 @@ impl AttributeCatalog {
         attributes.into_iter().map(|(attr, _)| attr).collect()
     }
 
+    /// Returns a list of indexed attributes ordered by their references.
+    #[must_use]
+    pub fn attribute_index(&self) -> Vec<&attribute::Attribute> {
+        let mut attributes: Vec<(&attribute::Attribute, &AttributeRef)> =
+            self.attribute_refs.iter().collect();
+        attributes.sort_by_key(|(_, attr_ref)| attr_ref.0);
+        attributes.into_iter().map(|(attr, _)| attr).collect()
+    }
+
     /// Returns a list of indexed attribute names ordered by their references.
     #[must_use]
     pub fn attribute_name_index(&self) -> Vec<String> {
@@ -56,7 +132,7 @@ impl AttributeCatalog {
         attributes.sort_by_key(|(_, attr_ref)| attr_ref.0);
         attributes
             .iter()
-            .map(|(attr, _)| attr.name.clone())
+            .map(|(attr, _)| attr.name.to_string())
             .collect()
     }
 
@@ -84,6 +160,7 @@ impl AttributeCatalog {
                 prefix,
             } => {
                 let name;
+                let short_name;
                 let root_attr = self.root_attributes.get(r#ref);
                 if let Some(root_attr) = root_attr {
                     let mut attr_lineage = AttributeLineage::new(&root_attr.group_id);
@@ -91,17 +168,22 @@ impl AttributeCatalog {
                     if *prefix {
                         // depending on the prefix we either create embedded attribute or normal reference
                         name = format!("{}.{}", group_prefix, r#ref);
+                        short_name = self.strings.intern(r#ref);
                     } else {
                         name = r#ref.clone();
+                        short_name = root_attr.attribute.short_name.clone();
                     }
 
                     // Create a fully resolved attribute from an attribute spec
                     // (ref) and override the root attribute with the new
                     // values if they are present.
                     let resolved_attr = attribute::Attribute {
-                        name: name.clone(),
+                        name: self.strings.intern(&name),
+                        short_name,
                         r#type: root_attr.attribute.r#type.clone(),
-                        brief: attr_lineage.brief(brief, &root_attr.attribute.brief),
+                        brief: self
+                            .strings
+                            .intern_string(attr_lineage.brief(brief, &root_attr.attribute.brief)),
                         examples: attr_lineage.examples(examples, &root_attr.attribute.examples),
                         tag: attr_lineage.tag(tag, &root_attr.attribute.tag),
                         requirement_level: attr_lineage.requirement_level(
@@ -112,11 +194,21 @@ impl AttributeCatalog {
                             sampling_relevant,
                             &root_attr.attribute.sampling_relevant,
                         ),
-                        note: attr_lineage.note(note, &root_attr.attribute.note),
+                        note: self
+                            .strings
+                            .intern_string(attr_lineage.note(note, &root_attr.attribute.note)),
                         stability: attr_lineage
                             .stability(stability, &root_attr.attribute.stability),
                         deprecated: attr_lineage
                             .deprecated(deprecated, &root_attr.attribute.deprecated),
+                        // An attribute embedded with a prefix is a new attribute.
+                        renamed_from: if *prefix {
+                            vec![]
+                        } else {
+                            root_attr.attribute.renamed_from.clone()
+                        },
+                        sensitivity: root_attr.attribute.sensitivity,
+                        annotations: root_attr.attribute.annotations.clone(),
                         tags: root_attr.attribute.tags.clone(),
                         value: root_attr.attribute.value.clone(),
                         prefix: *prefix,
@@ -127,6 +219,10 @@ impl AttributeCatalog {
                     // Update the lineage based on the inherited fields.
                     // Note: the lineage is only updated if a group lineage is provided.
                     if let Some(lineage) = lineage {
+                        // The fields inherited from an extended group are
+                        // overridden by the group recorded while resolving the
+                        // `extends` clause.
+                        attr_lineage.record_overriding_group(group_id, lineage.attribute(r#ref));
                         lineage.add_attribute_lineage(name.clone(), attr_lineage);
                     }
 
@@ -158,22 +254,29 @@ impl AttributeCatalog {
                 note,
                 stability,
                 deprecated,
+                renamed_from,
+                sensitivity,
+                annotations,
             } => {
                 // Create a fully resolved attribute from an attribute spec (id),
                 // and check if it already exists in the catalog.
                 // If it does, return the reference to the existing attribute.
                 // If it does not, add it to the catalog and return a new reference.
                 let attr = attribute::Attribute {
-                    name: id.clone(),
+                    name: self.strings.intern(id),
+                    short_name: self.short_names.get(id).cloned().unwrap_or_default(),
                     r#type: r#type.clone(),
-                    brief: brief.clone().unwrap_or_default(),
+                    brief: self.strings.intern(brief.as_deref().unwrap_or_default()),
                     examples: examples.clone(),
                     tag: tag.clone(),
                     requirement_level: requirement_level.clone(),
                     sampling_relevant: *sampling_relevant,
-                    note: note.clone(),
+                    note: self.strings.intern(note),
                     stability: stability.clone(),
                     deprecated: deprecated.clone(),
+                    renamed_from: renamed_from.clone(),
+                    sensitivity: *sensitivity,
+                    annotations: annotations.clone(),
                     tags: None,
                     value: None,
                     prefix: false,
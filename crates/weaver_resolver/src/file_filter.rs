@@ -0,0 +1,168 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Selection of the semantic convention files loaded from a local registry.
+
+use std::path::Path;
+
+use globset::{Glob, GlobSet, GlobSetBuilder};
+
+use crate::Error;
+
+/// Name of the file, at the root of a local registry, listing the glob patterns
+/// of the files and directories to skip (one pattern per line, `#` for comments).
+pub const IGNORE_FILE_NAME: &str = ".weaverignore";
+
+/// Include and exclude glob patterns selecting the files of a local registry.
+/// The patterns are matched against the paths relative to the registry
+/// directory.
+#[derive(Debug, Clone, Default)]
+pub struct RegistryFileFilter {
+    /// Only the files matching these patterns are loaded (all the files if
+    /// `None`).
+    include: Option<GlobSet>,
+    /// The files matching these patterns, or located in a directory matching
+    /// these patterns, are skipped.
+    exclude: Vec<GlobSet>,
+}
+
+impl RegistryFileFilter {
+    /// Creates a filter from include and exclude glob patterns.
+    pub fn try_new(include: &[String], exclude: &[String]) -> Result<Self, Error> {
+        Ok(Self {
+            include: if include.is_empty() {
+                None
+            } else {
+                Some(glob_set(include)?)
+            },
+            exclude: vec![glob_set(exclude)?],
+        })
+    }
+
+    /// Returns a filter also excluding the patterns of the ignore file located
+    /// in the given registry directory, if any.
+    pub fn with_ignore_file(&self, registry_dir: &Path) -> Result<Self, Error> {
+        let ignore_file = registry_dir.join(IGNORE_FILE_NAME);
+        if !ignore_file.is_file() {
+            return Ok(self.clone());
+        }
+        let content = std::fs::read_to_string(&ignore_file).map_err(|e| Error::SemConvError {
+            message: format!("Failed to read `{}`: {}", ignore_file.display(), e),
+        })?;
+        let patterns: Vec<_> = content
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .map(|line| line.trim_end_matches('/').to_owned())
+            .collect();
+        let mut filter = self.clone();
+        filter.exclude.push(glob_set(&patterns)?);
+        Ok(filter)
+    }
+
+    /// Returns true if the file, given by its path relative to the registry
+    /// directory, must be loaded.
+    #[must_use]
+    pub fn is_included(&self, relative_path: &Path) -> bool {
+        let excluded = relative_path
+            .ancestors()
+            .filter(|path| !path.as_os_str().is_empty())
+            .any(|path| self.exclude.iter().any(|exclude| exclude.is_match(path)));
+        !excluded
+            && self
+                .include
+                .as_ref()
+                .map_or(true, |include| include.is_match(relative_path))
+    }
+}
+
+/// Builds a glob set from a list of patterns.
+fn glob_set(patterns: &[String]) -> Result<GlobSet, Error> {
+    let mut builder = GlobSetBuilder::new();
+    for pattern in patterns {
+        let glob = Glob::new(pattern).map_err(|e| Error::InvalidFilePattern {
+            pattern: pattern.clone(),
+            error: e.to_string(),
+        })?;
+        _ = builder.add(glob);
+    }
+    builder.build().map_err(|e| Error::InvalidFilePattern {
+        pattern: patterns.join(", "),
+        error: e.to_string(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    #[cfg(feature = "git")]
+    use weaver_cache::Cache;
+    #[cfg(feature = "git")]
+    use weaver_semconv::path::RegistryPath;
+
+    use crate::file_filter::{RegistryFileFilter, IGNORE_FILE_NAME};
+    #[cfg(feature = "git")]
+    use crate::SchemaResolver;
+
+    #[test]
+    fn test_file_filter() {
+        let filter = RegistryFileFilter::default();
+        assert!(filter.is_included(Path::new("http/http.yaml")));
+
+        let filter = RegistryFileFilter::try_new(
+            &["**/*.yaml".to_owned()],
+            &["deprecated/**".to_owned(), "*.draft.yaml".to_owned()],
+        )
+        .expect("Invalid patterns");
+        assert!(filter.is_included(Path::new("http.yaml")));
+        assert!(filter.is_included(Path::new("http/http.yaml")));
+        assert!(!filter.is_included(Path::new("http/http.yml")));
+        assert!(!filter.is_included(Path::new("deprecated/http.yaml")));
+        assert!(!filter.is_included(Path::new("http/metrics.draft.yaml")));
+
+        assert!(RegistryFileFilter::try_new(&[], &["a/**{".to_owned()]).is_err());
+    }
+
+    #[test]
+    fn test_ignore_file() {
+        let dir = tempdir::TempDir::new("registry").expect("Failed to create the temp dir");
+        let filter = RegistryFileFilter::default();
+        assert!(filter
+            .with_ignore_file(dir.path())
+            .expect("No ignore file")
+            .is_included(Path::new("vendor/http.yaml")));
+
+        std::fs::write(
+            dir.path().join(IGNORE_FILE_NAME),
+            "# Vendored files\nvendor/\n\nexperimental/*.yaml\n",
+        )
+        .expect("Failed to write the ignore file");
+        let filter = filter
+            .with_ignore_file(dir.path())
+            .expect("Invalid ignore file");
+        assert!(!filter.is_included(Path::new("vendor/http.yaml")));
+        assert!(!filter.is_included(Path::new("vendor/nested/http.yaml")));
+        assert!(!filter.is_included(Path::new("experimental/http.yaml")));
+        assert!(filter.is_included(Path::new("http/http.yaml")));
+    }
+
+    #[test]
+    #[cfg(feature = "git")]
+    fn test_load_with_filter() {
+        let cache = Cache::try_new().expect("Failed to create the cache");
+        let registry_path = RegistryPath::Local {
+            path_pattern: "../../crates/weaver_codegen_test/semconv_registry".to_owned(),
+        };
+        let all = SchemaResolver::load_semconv_specs(&registry_path, &cache)
+            .expect("Failed to load the registry");
+        let filter =
+            RegistryFileFilter::try_new(&[], &["metrics/**".to_owned()]).expect("Invalid patterns");
+        let filtered =
+            SchemaResolver::load_semconv_specs_with_filter(&registry_path, &cache, &filter)
+                .expect("Failed to load the registry");
+        assert_eq!(filtered.len(), all.len() - 2);
+        assert!(filtered
+            .iter()
+            .all(|(provenance, _)| !provenance.contains("/metrics/")));
+    }
+}
@@ -3,30 +3,45 @@
 #![doc = include_str!("../README.md")]
 
 use miette::Diagnostic;
-use std::collections::HashMap;
-use std::path::{PathBuf, MAIN_SEPARATOR};
-
-use rayon::iter::ParallelBridge;
-use rayon::iter::ParallelIterator;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::{Display, Formatter};
+use std::path::PathBuf;
+#[cfg(feature = "git")]
+use std::path::MAIN_SEPARATOR;
+
+#[cfg(feature = "git")]
+use rayon::iter::{ParallelBridge, ParallelIterator};
 use serde::Serialize;
+#[cfg(feature = "git")]
 use walkdir::DirEntry;
 
+#[cfg(feature = "git")]
 use weaver_cache::Cache;
+use weaver_common::cancellation::{CancellationToken, Cancelled};
 use weaver_common::diagnostic::{DiagnosticMessage, DiagnosticMessages};
-use weaver_common::error::{format_errors, handle_errors, WeaverError};
+#[cfg(feature = "git")]
+use weaver_common::error::handle_errors;
+use weaver_common::error::{format_errors, WeaverError};
 use weaver_common::Logger;
 use weaver_resolved_schema::catalog::Catalog;
-use weaver_resolved_schema::registry::Constraint;
+use weaver_resolved_schema::registry::{Constraint, Registry};
 use weaver_resolved_schema::ResolvedTelemetrySchema;
+#[cfg(feature = "git")]
+use weaver_semconv::manifest::REGISTRY_MANIFEST;
 use weaver_semconv::path::RegistryPath;
 use weaver_semconv::registry::SemConvRegistry;
+#[cfg(feature = "git")]
 use weaver_semconv::semconv::SemConvSpec;
 
 use crate::attribute::AttributeCatalog;
-use crate::registry::resolve_semconv_registry;
+#[cfg(feature = "git")]
+use crate::file_filter::RegistryFileFilter;
+use crate::registry::{resolve_group, resolve_semconv_registry_with_options};
 
 pub mod attribute;
 mod constraint;
+pub mod file_filter;
+pub mod owners;
 pub mod registry;
 
 /// A resolver that can be used to resolve telemetry schemas.
@@ -55,6 +70,14 @@ pub enum Error {
         message: String,
     },
 
+    /// A semantic convention file that could not be loaded, with the location
+    /// of the error in the file if known.
+    #[error("{error}")]
+    InvalidSemConvFile {
+        /// The error that occurred.
+        error: Box<weaver_semconv::Error>,
+    },
+
     /// Failed to resolve a set of attributes.
     #[error("Failed to resolve a set of attributes {ids:?}: {error}")]
     FailToResolveAttributes {
@@ -133,6 +156,42 @@ pub enum Error {
         missing_attributes: Vec<String>,
     },
 
+    /// An entry of an `any_of` constraint using a legacy name of an attribute
+    /// of the group (a name it was renamed from, or a deprecated attribute it
+    /// replaces), reported as a warning.
+    #[error("The `any_of` constraint of the group '{group_id}' uses the legacy name '{alias}' of the attribute '{attribute}'.\nProvenance: {provenance}")]
+    #[diagnostic(severity(Warning))]
+    AnyOfConstraintAlias {
+        /// The id of the group containing the `any_of` constraint.
+        group_id: String,
+        /// The legacy name used by the constraint.
+        alias: String,
+        /// The name of the attribute of the group.
+        attribute: String,
+        /// The provenance of the group (URL or path).
+        provenance: String,
+    },
+
+    /// An invalid glob pattern selecting the files of a local registry.
+    #[error("Invalid file pattern '{pattern}', error: {error}")]
+    #[diagnostic(
+        url("https://docs.rs/globset/latest/globset/"),
+        help("Check the glob pattern for syntax errors.")
+    )]
+    InvalidFilePattern {
+        /// The glob pattern that caused the error.
+        pattern: String,
+        /// The error that occurred.
+        error: String,
+    },
+
+    /// A group that doesn't exist in the registry.
+    #[error("The group '{group_id}' doesn't exist in the registry")]
+    GroupNotFound {
+        /// The id of the group.
+        group_id: String,
+    },
+
     /// An invalid Schema path.
     #[error("Invalid Schema path: {path}")]
     InvalidSchemaPath {
@@ -140,11 +199,224 @@ pub enum Error {
         path: PathBuf,
     },
 
+    /// The resolution was cancelled or timed out.
+    #[error("The resolution was cancelled or timed out")]
+    Cancelled,
+
+    /// An `extends` chain deeper than the maximum depth of the resolver options.
+    #[error("The `extends` chain `{}` is deeper than the maximum depth ({max_depth})", chain.join(" -> "))]
+    #[diagnostic(help(
+        "Reduce the depth of the chain (e.g. by defining the attributes in a common group) or raise the maximum `extends` depth."
+    ))]
+    MaxExtendsDepthExceeded {
+        /// The maximum depth.
+        max_depth: usize,
+        /// The deepest chain of groups found (each group extends the next one).
+        chain: Vec<String>,
+    },
+
+    /// A resolution step exceeding the maximum number of iterations of the
+    /// resolver options.
+    #[error("The resolution of the {step} exceeded the maximum number of iterations ({max_iterations}), the deepest chain found is `{}`", chain.join(" -> "))]
+    #[diagnostic(help(
+        "Reduce the depth of the chain or raise the maximum number of iterations."
+    ))]
+    MaxIterationsExceeded {
+        /// The resolution step (e.g. `extends` clauses).
+        step: String,
+        /// The maximum number of iterations.
+        max_iterations: usize,
+        /// The deepest chain of unresolved references found.
+        chain: Vec<String>,
+    },
+
+    /// An error ignored by the lenient resolution mode, reported as a warning.
+    #[error("{error}\nThis error is ignored by the lenient resolution mode.")]
+    #[diagnostic(severity(Warning))]
+    IgnoredByLenientMode {
+        /// The ignored error.
+        error: Box<Error>,
+    },
+
+    /// A reference to a deprecated attribute or group (attribute reference,
+    /// `extends` clause or `include` constraint), reported as a warning.
+    #[error("The group '{group_id}' references the {item}\nProvenance: {provenance}")]
+    #[diagnostic(severity(Warning))]
+    DeprecatedRef {
+        /// The id of the group containing the reference.
+        group_id: String,
+        /// The deprecated attribute or group.
+        item: Box<DeprecatedItem>,
+        /// The provenance of the reference (URL or path).
+        provenance: String,
+    },
+
+    /// A reference to a deprecated item rejected by the resolution options
+    /// (see [`ResolverOptions::fail_on_deprecated_ref`]).
+    #[error("{error}\nReferences to deprecated items are not allowed.")]
+    DeprecatedRefNotAllowed {
+        /// The reference to the deprecated item.
+        error: Box<Error>,
+    },
+
+    /// A required attribute of a telemetry group is classified as secret.
+    #[error("The attribute '{attribute}' is classified as secret and can't be required by the group '{group_id}'.\nProvenance: {provenance}")]
+    #[diagnostic(help(
+        "Secret values must not be recorded, lower the requirement level of the attribute or its sensitivity."
+    ))]
+    RequiredSecretAttribute {
+        /// The id of the group requiring the attribute.
+        group_id: String,
+        /// The name of the attribute.
+        attribute: String,
+        /// The provenance of the group (URL or path).
+        provenance: String,
+    },
+
+    /// A resource group breaking a convention specific to the resources,
+    /// reported as a warning.
+    #[error("The resource group '{group_id}' {error}.\nProvenance: {provenance}")]
+    #[diagnostic(
+        severity(Warning),
+        help("The attributes of a resource are known when the resource is detected (they can't be `conditionally_required`), are as stable as the resource, and `service.name` is required to identify the service.")
+    )]
+    InvalidResourceGroup {
+        /// The id of the resource group.
+        group_id: String,
+        /// The convention broken by the group.
+        error: String,
+        /// The provenance of the group (URL or path).
+        provenance: String,
+    },
+
+    /// An id of a group or an attribute containing a non-ASCII or an
+    /// invisible character.
+    #[error("The {kind} id '{id}' contains the non-ASCII or invisible character {character}.\nProvenance: {provenance}")]
+    #[diagnostic(help("Ids must only contain visible ASCII characters."))]
+    InvalidIdCharacter {
+        /// The kind of the id (`group` or `attribute`).
+        kind: &'static str,
+        /// The id.
+        id: String,
+        /// The code point of the invalid character (e.g. `U+200B`).
+        character: String,
+        /// The provenance of the id (URL or path).
+        provenance: String,
+    },
+
+    /// Two distinct ids of groups or attributes that are equal once
+    /// case-folded and Unicode-normalized.
+    #[error("The {kind} id '{id}' collides with the {kind} id '{other_id}', {}.\nProvenance: {provenance}\nOther provenance: {other_provenance}", id_collision_reason(.id, .other_id))]
+    IdCollision {
+        /// The kind of the ids (`group` or `attribute`).
+        kind: &'static str,
+        /// The id.
+        id: String,
+        /// The id it collides with, defined first.
+        other_id: String,
+        /// The provenance of the id (URL or path).
+        provenance: String,
+        /// The provenance of the id it collides with (URL or path).
+        other_provenance: String,
+    },
+
+    /// Two metric groups defining the same metric name.
+    #[error("The group '{group_id}' defines the metric name '{name}' already defined by the group '{other_group_id}'.\nProvenance: {provenance}\nOther provenance: {other_provenance}")]
+    #[diagnostic(help(
+        "Each metric name must be defined by a single group, rename one of the metrics."
+    ))]
+    MetricNameCollision {
+        /// The metric name.
+        name: String,
+        /// The id of the group defining the name last.
+        group_id: String,
+        /// The id of the group defining the name first.
+        other_group_id: String,
+        /// The provenance of the group (URL or path), boxed to keep the
+        /// error small.
+        provenance: Box<str>,
+        /// The provenance of the other group (URL or path).
+        other_provenance: Box<str>,
+    },
+
+    /// Two event groups defining the same event name. The registry doesn't
+    /// record the scope the events are emitted in, so this is only a warning.
+    #[error("The group '{group_id}' defines the event name '{name}' already defined by the group '{other_group_id}'.\nProvenance: {provenance}\nOther provenance: {other_provenance}")]
+    #[diagnostic(
+        severity(Warning),
+        help("Events sharing a name must be emitted in distinct scopes (e.g. on distinct platforms), otherwise rename one of the events.")
+    )]
+    EventNameCollision {
+        /// The event name.
+        name: String,
+        /// The id of the group defining the name last.
+        group_id: String,
+        /// The id of the group defining the name first.
+        other_group_id: String,
+        /// The provenance of the group (URL or path), boxed to keep the
+        /// error small.
+        provenance: Box<str>,
+        /// The provenance of the other group (URL or path).
+        other_provenance: Box<str>,
+    },
+
     /// A container for multiple errors.
     #[error("{:?}", format_errors(.0))]
     CompoundError(Vec<Error>),
 }
 
+/// The kind of a deprecated item referenced by a group.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DeprecatedItemKind {
+    /// A deprecated attribute.
+    Attribute,
+    /// A deprecated group.
+    Group,
+}
+
+/// A deprecated attribute or group referenced by a group.
+#[derive(Debug, Clone, Serialize)]
+pub struct DeprecatedItem {
+    /// The kind of the deprecated item.
+    pub kind: DeprecatedItemKind,
+    /// The id of the deprecated attribute or group.
+    pub id: String,
+    /// The deprecation note of the deprecated item.
+    pub deprecated: String,
+    /// The replacement declared by the deprecation note, if any.
+    pub replacement: Option<String>,
+}
+
+impl Display for DeprecatedItem {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let kind = match self.kind {
+            DeprecatedItemKind::Attribute => "attribute",
+            DeprecatedItemKind::Group => "group",
+        };
+        write!(f, "deprecated {} '{}'", kind, self.id)?;
+        if let Some(replacement) = &self.replacement {
+            write!(f, " (replaced by '{}')", replacement)?;
+        }
+        write!(f, ".\nDeprecation note: {}", self.deprecated)
+    }
+}
+
+/// Returns the reason why two distinct ids collide.
+fn id_collision_reason(id: &str, other_id: &str) -> &'static str {
+    if id.to_lowercase() == other_id.to_lowercase() {
+        "they differ only by case"
+    } else {
+        "they have the same NFC/NFKC normalization"
+    }
+}
+
+impl From<Cancelled> for Error {
+    fn from(_: Cancelled) -> Self {
+        Error::Cancelled
+    }
+}
+
 impl WeaverError<Error> for Error {
     fn compound(errors: Vec<Error>) -> Error {
         Self::CompoundError(
@@ -174,6 +446,54 @@ impl From<Error> for DiagnosticMessages {
     }
 }
 
+/// The resolution mode of a semantic convention registry.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ResolutionMode {
+    /// Any unresolved reference or unsatisfied constraint fails the resolution.
+    #[default]
+    Strict,
+    /// The unresolved attribute references are replaced with placeholder
+    /// attributes (see [`attribute::UNRESOLVED_REF_TAG`]), the unresolved
+    /// `extends` and `include` references are ignored, and the unsatisfied
+    /// `any_of` constraints are kept. The corresponding errors are reported as
+    /// warnings. This is intended to explore partially broken registries.
+    Lenient,
+}
+
+/// The options of the resolution process.
+#[derive(Debug, Clone, Default)]
+pub struct ResolverOptions {
+    /// The resolution mode.
+    pub mode: ResolutionMode,
+    /// If true, the references to deprecated attributes or groups fail the
+    /// resolution instead of being reported as warnings.
+    pub fail_on_deprecated_ref: bool,
+    /// The maximum number of `extends` clauses followed from a group (e.g. 2
+    /// for `a` extending `b` extending `c`). Unlimited if `None`.
+    pub max_extends_depth: Option<usize>,
+    /// The maximum number of iterations of each iterative resolution step
+    /// (`extends` clauses, attribute references, and `include` constraints).
+    /// Unlimited if `None`.
+    pub max_iterations: Option<usize>,
+}
+
+impl ResolverOptions {
+    /// Sets the maximum number of `extends` clauses followed from a group.
+    #[must_use]
+    pub fn max_extends_depth(mut self, max_extends_depth: usize) -> Self {
+        self.max_extends_depth = Some(max_extends_depth);
+        self
+    }
+
+    /// Sets the maximum number of iterations of each iterative resolution
+    /// step.
+    #[must_use]
+    pub fn max_iterations(mut self, max_iterations: usize) -> Self {
+        self.max_iterations = Some(max_iterations);
+        self
+    }
+}
+
 /// A constraint that is not satisfied and its missing attributes.
 #[derive(Debug)]
 pub struct UnsatisfiedAnyOfConstraint {
@@ -204,8 +524,44 @@ impl SchemaResolver {
     pub fn resolve_semantic_convention_registry(
         registry: &mut SemConvRegistry,
     ) -> Result<ResolvedTelemetrySchema, Error> {
+        Self::resolve_semantic_convention_registry_with_cancellation(
+            registry,
+            &CancellationToken::default(),
+        )
+    }
+
+    /// Same as [`SchemaResolver::resolve_semantic_convention_registry`], but
+    /// the resolution stops with [`Error::Cancelled`] once the given token is
+    /// cancelled or timed out.
+    pub fn resolve_semantic_convention_registry_with_cancellation(
+        registry: &mut SemConvRegistry,
+        cancellation: &CancellationToken,
+    ) -> Result<ResolvedTelemetrySchema, Error> {
+        let (resolved_schema, _) = Self::resolve_semantic_convention_registry_with_options(
+            registry,
+            &ResolverOptions::default(),
+            cancellation,
+        )?;
+        Ok(resolved_schema)
+    }
+
+    /// Same as [`SchemaResolver::resolve_semantic_convention_registry_with_cancellation`],
+    /// with the given resolution options. Returns the resolved telemetry schema
+    /// and the warnings of the resolution (i.e. the references to deprecated
+    /// items, and the errors ignored by the lenient resolution mode).
+    pub fn resolve_semantic_convention_registry_with_options(
+        registry: &mut SemConvRegistry,
+        options: &ResolverOptions,
+        cancellation: &CancellationToken,
+    ) -> Result<(ResolvedTelemetrySchema, Vec<Error>), Error> {
         let mut attr_catalog = AttributeCatalog::default();
-        let resolved_registry = resolve_semconv_registry(&mut attr_catalog, "", registry)?;
+        let (resolved_registry, warnings) = resolve_semconv_registry_with_options(
+            &mut attr_catalog,
+            "",
+            registry,
+            options,
+            cancellation,
+        )?;
 
         let catalog = Catalog {
             attributes: attr_catalog.drain_attributes(),
@@ -214,7 +570,7 @@ impl SchemaResolver {
         let mut registries = HashMap::new();
         _ = registries.insert(registry.id().into(), resolved_registry);
 
-        let resolved_schema = ResolvedTelemetrySchema {
+        let mut resolved_schema = ResolvedTelemetrySchema {
             file_format: "1.0.0".to_owned(),
             schema_url: "".to_owned(),
             registries,
@@ -223,8 +579,48 @@ impl SchemaResolver {
             instrumentation_library: None,
             dependencies: vec![],
             versions: None, // ToDo LQ: Implement this!
+            attribute_index: BTreeMap::new(),
         };
+        resolved_schema.build_attribute_index();
 
+        Ok((resolved_schema, warnings))
+    }
+
+    /// Resolves a single group of the given semantic convention registry and
+    /// returns a resolved telemetry schema whose registry only contains this
+    /// group. Only the group and the groups it transitively depends on are
+    /// resolved (see [`registry::resolve_group`]), which is much faster than
+    /// resolving the whole registry for on-demand lookups.
+    pub fn resolve_group(
+        registry: &SemConvRegistry,
+        group_id: &str,
+    ) -> Result<ResolvedTelemetrySchema, Error> {
+        let mut attr_catalog = AttributeCatalog::default();
+        let group = resolve_group(&mut attr_catalog, registry, group_id)?;
+
+        let mut registries = HashMap::new();
+        _ = registries.insert(
+            registry.id().into(),
+            Registry {
+                registry_url: String::new(),
+                groups: vec![group],
+            },
+        );
+
+        let mut resolved_schema = ResolvedTelemetrySchema {
+            file_format: "1.0.0".to_owned(),
+            schema_url: "".to_owned(),
+            registries,
+            catalog: Catalog {
+                attributes: attr_catalog.drain_attributes(),
+            },
+            resource: None,
+            instrumentation_library: None,
+            dependencies: vec![],
+            versions: None,
+            attribute_index: BTreeMap::new(),
+        };
+        resolved_schema.build_attribute_index();
         Ok(resolved_schema)
     }
 
@@ -235,30 +631,153 @@ impl SchemaResolver {
     /// # Arguments
     /// * `registry_path` - The registry path containing the semantic convention files.
     /// * `cache` - The cache to store the semantic convention files.
+    #[cfg(feature = "git")]
     pub fn load_semconv_specs(
         registry_path: &RegistryPath,
         cache: &Cache,
     ) -> Result<Vec<(String, SemConvSpec)>, Error> {
-        let (local_path, registry_path_repr) = Self::path_to_registry(registry_path, cache)?;
-        Self::load_semconv_from_local_path(local_path, &registry_path_repr)
+        Self::load_semconv_specs_with_filter(registry_path, cache, &RegistryFileFilter::default())
+    }
+
+    /// Loads the semantic convention specifications from the given registry path,
+    /// skipping the files rejected by the file filter. The filter, completed by the
+    /// `.weaverignore` file of the registry, only applies to local registries.
+    ///
+    /// # Arguments
+    /// * `registry_path` - The registry path containing the semantic convention files.
+    /// * `cache` - The cache to store the semantic convention files.
+    /// * `file_filter` - The include and exclude patterns of the files to load.
+    #[cfg(feature = "git")]
+    pub fn load_semconv_specs_with_filter(
+        registry_path: &RegistryPath,
+        cache: &Cache,
+        file_filter: &RegistryFileFilter,
+    ) -> Result<Vec<(String, SemConvSpec)>, Error> {
+        Self::load_semconv_specs_with_cancellation(
+            registry_path,
+            cache,
+            file_filter,
+            &CancellationToken::default(),
+        )
+    }
+
+    /// Same as [`SchemaResolver::load_semconv_specs_with_filter`], but the
+    /// clone of a git registry and the loading of the files stop with
+    /// [`Error::Cancelled`] once the given token is cancelled or timed out.
+    #[cfg(feature = "git")]
+    pub fn load_semconv_specs_with_cancellation(
+        registry_path: &RegistryPath,
+        cache: &Cache,
+        file_filter: &RegistryFileFilter,
+        cancellation: &CancellationToken,
+    ) -> Result<Vec<(String, SemConvSpec)>, Error> {
+        let (semconv_specs, errors) =
+            Self::load_semconv_specs_and_errors(registry_path, cache, file_filter, cancellation)?;
+        handle_errors(errors)?;
+        Ok(semconv_specs)
+    }
+
+    /// Same as [`SchemaResolver::load_semconv_specs_with_filter`], but a file
+    /// failing to load (e.g. an invalid YAML file) doesn't abort the loading
+    /// of the registry. The specifications of the valid files are returned
+    /// along with the errors of the invalid files.
+    #[cfg(feature = "git")]
+    pub fn load_valid_semconv_specs(
+        registry_path: &RegistryPath,
+        cache: &Cache,
+        file_filter: &RegistryFileFilter,
+    ) -> Result<(Vec<(String, SemConvSpec)>, Vec<Error>), Error> {
+        Self::load_semconv_specs_and_errors(
+            registry_path,
+            cache,
+            file_filter,
+            &CancellationToken::default(),
+        )
+    }
+
+    /// Loads the specifications of the valid files of a registry, and returns
+    /// them along with the errors of the invalid files.
+    #[cfg(feature = "git")]
+    fn load_semconv_specs_and_errors(
+        registry_path: &RegistryPath,
+        cache: &Cache,
+        file_filter: &RegistryFileFilter,
+        cancellation: &CancellationToken,
+    ) -> Result<(Vec<(String, SemConvSpec)>, Vec<Error>), Error> {
+        let (local_path, registry_path_repr) =
+            Self::path_to_registry_with_cancellation(registry_path, cache, cancellation)?;
+        let file_filter = match registry_path {
+            RegistryPath::Local { .. } => file_filter.with_ignore_file(&local_path)?,
+            RegistryPath::GitUrl { .. }
+            | RegistryPath::Oci { .. }
+            | RegistryPath::Archive { .. } => RegistryFileFilter::default(),
+        };
+        Self::load_semconv_from_local_path(
+            local_path,
+            &registry_path_repr,
+            &file_filter,
+            cancellation,
+        )
     }
 
     /// Returns a tuple absolute ['PathBuf'], logical registry path to the registry based on the
     /// given ['RegistryPath'] and the cache.
+    #[cfg(feature = "git")]
     pub fn path_to_registry(
         registry_path: &RegistryPath,
         cache: &Cache,
+    ) -> Result<(PathBuf, String), Error> {
+        Self::path_to_registry_with_cancellation(
+            registry_path,
+            cache,
+            &CancellationToken::default(),
+        )
+    }
+
+    /// Same as [`SchemaResolver::path_to_registry`], but the clone of a git
+    /// registry stops with [`Error::Cancelled`] once the given token is
+    /// cancelled or timed out.
+    #[cfg(feature = "git")]
+    pub fn path_to_registry_with_cancellation(
+        registry_path: &RegistryPath,
+        cache: &Cache,
+        cancellation: &CancellationToken,
     ) -> Result<(PathBuf, String), Error> {
         match registry_path {
             RegistryPath::Local { path_pattern: path } => Ok((path.into(), path.clone())),
-            RegistryPath::GitUrl { git_url, path } => {
-                match cache.git_repo(git_url.clone(), path.clone()) {
+            RegistryPath::GitUrl {
+                git_url,
+                path,
+                git_ref,
+            } => {
+                match cache.git_repo_at_ref_with_cancellation(
+                    git_url.clone(),
+                    git_ref.clone(),
+                    path.clone(),
+                    cancellation,
+                ) {
                     Ok(local_git_repo) => Ok((local_git_repo, git_url.clone())),
+                    Err(weaver_cache::Error::Cancelled { .. }) => Err(Error::Cancelled),
                     Err(e) => Err(Error::SemConvError {
                         message: e.to_string(),
                     }),
                 }
             }
+            RegistryPath::Oci { oci_reference } => {
+                match cache.oci_registry_with_cancellation(oci_reference, cancellation) {
+                    Ok(local_registry) => Ok((local_registry, oci_reference.clone())),
+                    Err(weaver_cache::Error::Cancelled { .. }) => Err(Error::Cancelled),
+                    Err(e) => Err(Error::SemConvError {
+                        message: e.to_string(),
+                    }),
+                }
+            }
+            RegistryPath::Archive { archive_path } => match cache.archive_registry(archive_path) {
+                Ok(local_registry) => Ok((local_registry, archive_path.clone())),
+                Err(e) => Err(Error::SemConvError {
+                    message: e.to_string(),
+                }),
+            },
         }
     }
 
@@ -269,10 +788,15 @@ impl SchemaResolver {
     /// # Arguments
     /// * `local_path` - The local path containing the semantic convention files.
     /// * `registry_path_repr` - The representation of the registry path (URL or path).
+    /// * `file_filter` - The include and exclude patterns of the files to load.
+    /// * `cancellation` - The token used to stop the loading of the files.
+    #[cfg(feature = "git")]
     fn load_semconv_from_local_path(
         local_path: PathBuf,
         registry_path_repr: &str,
-    ) -> Result<Vec<(String, SemConvSpec)>, Error> {
+        file_filter: &RegistryFileFilter,
+        cancellation: &CancellationToken,
+    ) -> Result<(Vec<(String, SemConvSpec)>, Vec<Error>), Error> {
         fn is_hidden(entry: &DirEntry) -> bool {
             entry
                 .file_name()
@@ -287,53 +811,63 @@ impl SchemaResolver {
             path.is_file()
                 && (extension == "yaml" || extension == "yml")
                 && file_name != "schema-next.yaml"
+                && file_name != REGISTRY_MANIFEST
         }
 
         // Loads the semantic convention specifications from the git repo.
         // All yaml files are recursively loaded and parsed in parallel from
         // the given path.
-        let result =
-            walkdir::WalkDir::new(local_path.clone())
-                .into_iter()
-                .filter_entry(|e| !is_hidden(e))
-                .par_bridge()
-                .filter_map(|entry| {
-                    match entry {
-                        Ok(entry) => {
-                            if !is_semantic_convention_file(&entry) {
-                                return None;
-                            }
+        let result = walkdir::WalkDir::new(local_path.clone())
+            .into_iter()
+            .filter_entry(|e| !is_hidden(e))
+            .par_bridge()
+            .filter_map(|entry| {
+                // The remaining files are skipped once cancelled.
+                if cancellation.is_cancelled() {
+                    return None;
+                }
+                match entry {
+                    Ok(entry) => {
+                        if !is_semantic_convention_file(&entry) {
+                            return None;
+                        }
+                        let relative_path = entry
+                            .path()
+                            .strip_prefix(&local_path)
+                            .unwrap_or(entry.path());
+                        if !file_filter.is_included(relative_path) {
+                            return None;
+                        }
 
-                            let spec = SemConvRegistry::semconv_spec_from_file(entry.path())
-                                .map_err(|e| Error::SemConvError {
-                                    message: e.to_string(),
-                                });
-                            match spec {
-                                Ok((path, spec)) => {
-                                    // Replace the local path with the git URL combined with the relative path
-                                    // of the semantic convention file.
-                                    let prefix = local_path
-                                        .to_str()
-                                        .map(|s| s.to_owned())
-                                        .unwrap_or_default();
-                                    let path = if registry_path_repr.ends_with(MAIN_SEPARATOR) {
-                                        let relative_path = &path[prefix.len()..];
-                                        format!("{}{}", registry_path_repr, relative_path)
-                                    } else {
-                                        let relative_path = &path[prefix.len() + 1..];
-                                        format!("{}/{}", registry_path_repr, relative_path)
-                                    };
-                                    Some(Ok((path, spec)))
-                                }
-                                Err(e) => Some(Err(e)),
+                        let spec = SemConvRegistry::semconv_spec_from_file(entry.path())
+                            .map_err(|e| Error::InvalidSemConvFile { error: Box::new(e) });
+                        match spec {
+                            Ok((path, spec)) => {
+                                // Replace the local path with the git URL combined with the relative path
+                                // of the semantic convention file.
+                                let prefix = local_path
+                                    .to_str()
+                                    .map(|s| s.to_owned())
+                                    .unwrap_or_default();
+                                let path = if registry_path_repr.ends_with(MAIN_SEPARATOR) {
+                                    let relative_path = &path[prefix.len()..];
+                                    format!("{}{}", registry_path_repr, relative_path)
+                                } else {
+                                    let relative_path = &path[prefix.len() + 1..];
+                                    format!("{}/{}", registry_path_repr, relative_path)
+                                };
+                                Some(Ok((path, spec)))
                             }
+                            Err(e) => Some(Err(e)),
                         }
-                        Err(e) => Some(Err(Error::SemConvError {
-                            message: e.to_string(),
-                        })),
                     }
-                })
-                .collect::<Vec<_>>();
+                    Err(e) => Some(Err(Error::SemConvError {
+                        message: e.to_string(),
+                    })),
+                }
+            })
+            .collect::<Vec<_>>();
+        cancellation.check()?;
 
         let mut error = vec![];
         let result = result
@@ -347,8 +881,6 @@ impl SchemaResolver {
             })
             .collect::<Vec<_>>();
 
-        handle_errors(error)?;
-
-        Ok(result)
+        Ok((result, error))
     }
 }
@@ -0,0 +1,152 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Ownership of the namespaces of a registry, declared in a CODEOWNERS-style
+//! `OWNERS` file located at the root of a local registry.
+
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use crate::Error;
+
+/// Name of the file declaring the owners of the namespaces of a registry.
+pub const OWNERS_FILE_NAME: &str = "OWNERS";
+
+/// The namespace giving the default owners.
+const DEFAULT_NAMESPACE: &str = "*";
+
+/// The owners of the namespaces of a registry.
+///
+/// Each line of an `OWNERS` file maps a namespace to its owners, separated by
+/// whitespace (e.g. `http.server @org/http-team jane@example.com`). The `*`
+/// namespace gives the default owners. Empty lines and lines starting with `#`
+/// are ignored.
+///
+/// An attribute belongs to the longest namespace its name starts with, e.g.
+/// `http.server.request.duration` belongs to `http.server` rather than `http`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Owners {
+    /// The owners of each namespace.
+    owners: BTreeMap<String, Vec<String>>,
+}
+
+impl Owners {
+    /// Parses the content of an `OWNERS` file. A namespace declared several
+    /// times keeps its last owners.
+    #[must_use]
+    pub fn parse(content: &str) -> Self {
+        let owners = content
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .filter_map(|line| {
+                let mut fields = line.split_whitespace();
+                let namespace = fields.next()?.to_owned();
+                Some((namespace, fields.map(str::to_owned).collect()))
+            })
+            .collect();
+        Self { owners }
+    }
+
+    /// Loads the `OWNERS` file located in the given registry directory. The
+    /// ownership is empty if the registry has no `OWNERS` file.
+    pub fn from_registry_dir(registry_dir: &Path) -> Result<Self, Error> {
+        let owners_file = registry_dir.join(OWNERS_FILE_NAME);
+        if !owners_file.is_file() {
+            return Ok(Self::default());
+        }
+        let content = std::fs::read_to_string(&owners_file).map_err(|e| Error::SemConvError {
+            message: format!("Failed to read `{}`: {}", owners_file.display(), e),
+        })?;
+        Ok(Self::parse(&content))
+    }
+
+    /// Adds the owners of another registry. The namespaces declared by both
+    /// keep the owners of the other registry.
+    pub fn merge(&mut self, other: Owners) {
+        self.owners.extend(other.owners);
+    }
+
+    /// Returns true if no namespace has owners.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.owners.is_empty()
+    }
+
+    /// Returns the owners of an attribute (or of any dotted name), i.e. the
+    /// owners of the longest namespace it starts with, or the default owners.
+    #[must_use]
+    pub fn owners_of(&self, name: &str) -> &[String] {
+        self.namespace_owners(name)
+            .or_else(|| self.default_owners())
+            .unwrap_or_default()
+    }
+
+    /// Returns the owners of a group. The group ids usually start with the
+    /// type of the group (e.g. `registry.http` or `span.http.client`), so the
+    /// id is matched with and without its first segment.
+    #[must_use]
+    pub fn owners_of_group(&self, group_id: &str) -> &[String] {
+        self.namespace_owners(group_id)
+            .or_else(|| {
+                group_id
+                    .split_once('.')
+                    .and_then(|(_, name)| self.namespace_owners(name))
+            })
+            .or_else(|| self.default_owners())
+            .unwrap_or_default()
+    }
+
+    /// Returns the owners of the longest namespace the name starts with.
+    fn namespace_owners(&self, name: &str) -> Option<&[String]> {
+        let mut namespace = name;
+        loop {
+            if let Some(owners) = self.owners.get(namespace) {
+                return Some(owners);
+            }
+            namespace = &namespace[..namespace.rfind('.')?];
+        }
+    }
+
+    /// Returns the default owners, if any.
+    fn default_owners(&self) -> Option<&[String]> {
+        self.owners.get(DEFAULT_NAMESPACE).map(Vec::as_slice)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_owners() {
+        let owners = Owners::parse(
+            "# Owners of the namespaces
+* @org/approvers
+
+http @org/http-team
+http.server @org/http-server-team jane@example.com
+db @org/db-team
+",
+        );
+        assert_eq!(owners.owners_of("http.request.method"), ["@org/http-team"]);
+        assert_eq!(
+            owners.owners_of("http.server.request.duration"),
+            ["@org/http-server-team", "jane@example.com"]
+        );
+        assert_eq!(owners.owners_of("http"), ["@org/http-team"]);
+        assert_eq!(owners.owners_of("https.enabled"), ["@org/approvers"]);
+        assert_eq!(owners.owners_of_group("registry.db"), ["@org/db-team"]);
+        assert_eq!(
+            owners.owners_of_group("span.http.client"),
+            ["@org/http-team"]
+        );
+        assert_eq!(owners.owners_of_group("metric.rpc"), ["@org/approvers"]);
+
+        let mut merged = Owners::parse("db @org/db-team\nrpc @org/rpc-team");
+        merged.merge(Owners::parse("db @vendor/db-team"));
+        assert_eq!(merged.owners_of("db.system"), ["@vendor/db-team"]);
+        assert_eq!(merged.owners_of("rpc.method"), ["@org/rpc-team"]);
+        assert!(merged.owners_of("http.route").is_empty());
+        assert!(Owners::default().is_empty());
+    }
+}
@@ -2,21 +2,30 @@
 
 //! Functions to resolve a semantic convention registry.
 
+use std::collections::hash_map::Entry;
 use std::collections::{BTreeMap, HashMap, HashSet};
 
 use serde::Deserialize;
+use unicode_normalization::UnicodeNormalization;
 
+use weaver_common::cancellation::CancellationToken;
 use weaver_common::error::handle_errors;
-use weaver_resolved_schema::attribute::UnresolvedAttribute;
+use weaver_resolved_schema::attribute::{Attribute, UnresolvedAttribute};
+use weaver_resolved_schema::interned::StringInterner;
 use weaver_resolved_schema::lineage::{AttributeLineage, GroupLineage};
 use weaver_resolved_schema::registry::{Constraint, Group, Registry};
-use weaver_semconv::attribute::AttributeSpec;
-use weaver_semconv::group::GroupSpecWithProvenance;
+use weaver_semconv::attribute::{AttributeSpec, BasicRequirementLevelSpec, RequirementLevel};
+use weaver_semconv::group::{GroupSpecWithProvenance, GroupType};
 use weaver_semconv::registry::SemConvRegistry;
+use weaver_semconv::sensitivity::Sensitivity;
+use weaver_semconv::stability::Stability;
 
 use crate::attribute::AttributeCatalog;
 use crate::constraint::resolve_constraints;
-use crate::{Error, UnsatisfiedAnyOfConstraint};
+use crate::{
+    DeprecatedItem, DeprecatedItemKind, Error, ResolutionMode, ResolverOptions,
+    UnsatisfiedAnyOfConstraint,
+};
 
 /// A registry containing unresolved groups.
 #[derive(Debug, Deserialize)]
@@ -72,15 +81,224 @@ pub fn resolve_semconv_registry(
     registry_url: &str,
     registry: &SemConvRegistry,
 ) -> Result<Registry, Error> {
-    let mut ureg = unresolved_registry_from_specs(registry_url, registry);
+    resolve_semconv_registry_with_cancellation(
+        attr_catalog,
+        registry_url,
+        registry,
+        &CancellationToken::default(),
+    )
+}
+
+/// Same as [`resolve_semconv_registry`], but the resolution process stops with
+/// `Error::Cancelled` once the given token is cancelled or timed out. The
+/// token is checked between the steps and at each iteration of the steps.
+pub fn resolve_semconv_registry_with_cancellation(
+    attr_catalog: &mut AttributeCatalog,
+    registry_url: &str,
+    registry: &SemConvRegistry,
+    cancellation: &CancellationToken,
+) -> Result<Registry, Error> {
+    let (registry, _) = resolve_semconv_registry_with_options(
+        attr_catalog,
+        registry_url,
+        registry,
+        &ResolverOptions::default(),
+        cancellation,
+    )?;
+    Ok(registry)
+}
+
+/// Same as [`resolve_semconv_registry_with_cancellation`], with the given
+/// resolution options. Returns the resolved registry and the warnings of the
+/// resolution, i.e. the references to deprecated items and the errors ignored
+/// by the lenient resolution mode.
+pub fn resolve_semconv_registry_with_options(
+    attr_catalog: &mut AttributeCatalog,
+    registry_url: &str,
+    registry: &SemConvRegistry,
+    options: &ResolverOptions,
+    cancellation: &CancellationToken,
+) -> Result<(Registry, Vec<Error>), Error> {
+    cancellation.check()?;
+    let ureg = unresolved_registry_from_specs(registry_url, registry);
+    resolve_unresolved_registry(ureg, attr_catalog, options, cancellation)
+}
+
+/// Resolves a single group of the semantic convention registry passed as
+/// argument, without resolving the whole registry. Only the group and the
+/// transitive closure of the groups it depends on (the extended groups, the
+/// included groups, and the groups defining the referenced attributes) are
+/// resolved, which makes this function suitable for interactive use cases
+/// (e.g. the hover and completion requests of a language server).
+///
+/// The resolved attributes of the group are added to the attribute catalog.
+/// The resolution process is the same as [`resolve_semconv_registry`] (in
+/// strict mode), the errors of the groups of the closure are reported.
+///
+/// # Returns
+///
+/// This function returns the resolved group or an error if the group doesn't
+/// exist or if the resolution process failed.
+pub fn resolve_group(
+    attr_catalog: &mut AttributeCatalog,
+    registry: &SemConvRegistry,
+    group_id: &str,
+) -> Result<Group, Error> {
+    // Index the groups by id and the groups defining each attribute.
+    let mut groups = HashMap::new();
+    let mut attr_groups = HashMap::new();
+    for (group, provenance) in registry.group_with_provenance_iter() {
+        for attr in &group.attributes {
+            if let AttributeSpec::Id { id, .. } = attr {
+                let attr_id = if group.prefix.is_empty() {
+                    id.clone()
+                } else {
+                    format!("{}.{}", group.prefix, id)
+                };
+                _ = attr_groups.insert(attr_id, group.id.as_str());
+            }
+        }
+        _ = groups.insert(group.id.as_str(), (group, provenance));
+    }
+    if !groups.contains_key(group_id) {
+        return Err(Error::GroupNotFound {
+            group_id: group_id.to_owned(),
+        });
+    }
+
+    // Collect the transitive closure of the dependencies of the group. The
+    // unknown dependencies are kept to be reported by the resolution process.
+    let mut closure = HashSet::from([group_id]);
+    let mut to_visit = vec![group_id];
+    while let Some(id) = to_visit.pop() {
+        let Some((group, _)) = groups.get(id) else {
+            continue;
+        };
+        let dependencies = group
+            .extends
+            .iter()
+            .map(String::as_str)
+            .chain(
+                group
+                    .constraints
+                    .iter()
+                    .filter_map(|constraint| constraint.include.as_deref()),
+            )
+            .chain(group.attributes.iter().filter_map(|attr| match attr {
+                AttributeSpec::Ref { r#ref, .. } => attr_groups.get(r#ref.as_str()).copied(),
+                AttributeSpec::Id { .. } => None,
+            }));
+        for dependency in dependencies {
+            if closure.insert(dependency) {
+                to_visit.push(dependency);
+            }
+        }
+    }
 
-    resolve_prefix_on_attributes(&mut ureg)?;
+    // Resolve the groups of the closure, in the order of the registry.
+    let mut prefixes = StringInterner::new();
+    let ureg = UnresolvedRegistry {
+        registry: Registry {
+            registry_url: String::new(),
+            groups: vec![],
+        },
+        groups: registry
+            .group_with_provenance_iter()
+            .filter(|(group, _)| closure.contains(group.id.as_str()))
+            .map(|(group, provenance)| {
+                let group = GroupSpecWithProvenance {
+                    spec: group.clone(),
+                    provenance: provenance.to_owned(),
+                };
+                group_from_spec(group, &mut prefixes)
+            })
+            .collect(),
+    };
+    let (registry, _) = resolve_unresolved_registry(
+        ureg,
+        attr_catalog,
+        &ResolverOptions::default(),
+        &CancellationToken::default(),
+    )?;
+    Ok(registry
+        .groups
+        .into_iter()
+        .find(|group| group.id == group_id)
+        .expect("The group is part of its closure"))
+}
+
+/// Resolves the groups of the given unresolved registry. Returns the resolved
+/// registry and the warnings of the resolution (references to deprecated items
+/// and errors ignored by the lenient resolution mode).
+fn resolve_unresolved_registry(
+    mut ureg: UnresolvedRegistry,
+    attr_catalog: &mut AttributeCatalog,
+    options: &ResolverOptions,
+    cancellation: &CancellationToken,
+) -> Result<(Registry, Vec<Error>), Error> {
+    let lenient = options.mode == ResolutionMode::Lenient;
+    let mut warnings = vec![];
+
+    resolve_prefix_on_attributes(&mut ureg, attr_catalog)?;
+
+    // Check the ids of the groups and the attributes.
+    match check_ids(&ureg) {
+        Err(error) if lenient => warnings.extend(ignored_by_lenient_mode(vec![error])),
+        result => result?,
+    }
+
+    // Check the names of the metrics and the events.
+    let (metric_name_errors, event_name_warnings) = signal_name_collisions(&ureg);
+    match handle_errors(metric_name_errors) {
+        Err(error) if lenient => warnings.extend(ignored_by_lenient_mode(vec![error])),
+        result => result?,
+    }
+    warnings.extend(event_name_warnings);
 
-    resolve_extends_references(&mut ureg)?;
+    let deprecated_refs = check_deprecated_refs(&ureg);
+    if options.fail_on_deprecated_ref && !deprecated_refs.is_empty() {
+        return Err(Error::CompoundError(
+            deprecated_refs
+                .into_iter()
+                .map(|error| Error::DeprecatedRefNotAllowed {
+                    error: Box::new(error),
+                })
+                .collect(),
+        ));
+    }
+    warnings.extend(deprecated_refs);
 
-    resolve_attribute_references(&mut ureg, attr_catalog)?;
+    if let Some(max_depth) = options.max_extends_depth {
+        let chain = deepest_chain(&extends_references(&ureg));
+        if chain.len() > max_depth + 1 {
+            return Err(Error::MaxExtendsDepthExceeded { max_depth, chain });
+        }
+    }
 
-    resolve_include_constraints(&mut ureg)?;
+    resolve_extends_references(
+        &mut ureg,
+        lenient,
+        options.max_iterations,
+        &mut warnings,
+        cancellation,
+    )?;
+
+    resolve_attribute_references(
+        &mut ureg,
+        attr_catalog,
+        lenient,
+        options.max_iterations,
+        &mut warnings,
+        cancellation,
+    )?;
+
+    resolve_include_constraints(
+        &mut ureg,
+        lenient,
+        options.max_iterations,
+        &mut warnings,
+        cancellation,
+    )?;
 
     // Sort the attribute internal references in each group.
     // This is needed to ensure that the resolved registry is easy to compare
@@ -95,8 +313,25 @@ pub fn resolve_semconv_registry(
         .collect();
 
     // Check the `any_of` constraints.
-    let attr_name_index = attr_catalog.attribute_name_index();
-    check_any_of_constraints(&ureg.registry, &attr_name_index)?;
+    cancellation.check()?;
+    match check_any_of_constraints(&ureg.registry, &attr_catalog.attribute_index()) {
+        Ok(alias_warnings) => warnings.extend(alias_warnings),
+        Err(error) if lenient => warnings.extend(ignored_by_lenient_mode(vec![error])),
+        Err(error) => return Err(error),
+    }
+
+    // Check the sensitivity of the required attributes.
+    match check_required_secret_attributes(&ureg.registry, &attr_catalog.attribute_index()) {
+        Err(error) if lenient => warnings.extend(ignored_by_lenient_mode(vec![error])),
+        result => result?,
+    }
+
+    // Check the conventions specific to the resources, the groups breaking
+    // them are reported as warnings.
+    warnings.extend(check_resource_groups(
+        &ureg.registry,
+        &attr_catalog.attribute_index(),
+    ));
 
     // All constraints are satisfied.
     // Remove the constraints from the resolved registry.
@@ -104,63 +339,425 @@ pub fn resolve_semconv_registry(
         group.constraints.clear();
     }
 
-    Ok(ureg.registry)
+    Ok((ureg.registry, warnings))
+}
+
+/// Returns the errors ignored by the lenient resolution mode when a resolution
+/// step is stuck: the errors of the references to unknown groups if any, or all
+/// the errors otherwise (i.e. the remaining references form a cycle).
+fn errors_ignored_by_lenient_mode(
+    ureg: &UnresolvedRegistry,
+    errors: Vec<Error>,
+    referenced_group: impl Fn(&Error) -> Option<&String>,
+) -> Vec<Error> {
+    let group_ids: HashSet<_> = ureg.groups.iter().map(|g| &g.group.id).collect();
+    let (unknown, remaining): (Vec<_>, Vec<_>) = errors.into_iter().partition(|error| {
+        referenced_group(error).is_some_and(|group_id| !group_ids.contains(group_id))
+    });
+    if unknown.is_empty() {
+        remaining
+    } else {
+        unknown
+    }
+}
+
+/// Converts the given errors into warnings reported by the lenient resolution
+/// mode.
+fn ignored_by_lenient_mode(errors: Vec<Error>) -> impl Iterator<Item = Error> {
+    errors
+        .into_iter()
+        .flat_map(|error| match error {
+            Error::CompoundError(errors) => errors,
+            error => vec![error],
+        })
+        .map(|error| Error::IgnoredByLenientMode {
+            error: Box::new(error),
+        })
+}
+
+/// Checks the ids of the groups and the attributes defined by the given
+/// registry. An id must only contain visible ASCII characters, and must not
+/// collide with another id once both are case-folded and NFKC-normalized (e.g.
+/// `http.Method` and `http.method`, or `ﬁle.name`, with the `ﬁ` ligature, and `file.name`).
+///
+/// Note: the prefixes must be applied to the attribute ids beforehand.
+fn check_ids(ureg: &UnresolvedRegistry) -> Result<(), Error> {
+    let group_ids = ureg
+        .groups
+        .iter()
+        .map(|g| (g.group.id.as_str(), g.provenance.as_str()));
+    let attribute_ids = ureg.groups.iter().flat_map(|g| {
+        g.attributes.iter().filter_map(|attr| match &attr.spec {
+            AttributeSpec::Id { id, .. } => Some((id.as_str(), g.provenance.as_str())),
+            AttributeSpec::Ref { .. } => None,
+        })
+    });
+    let mut errors = id_errors("group", group_ids);
+    errors.extend(id_errors("attribute", attribute_ids));
+    handle_errors(errors)
+}
+
+/// Returns the errors of the given ids (with their provenance) of a kind of
+/// item. A collision is reported on the id defined last.
+fn id_errors<'a>(kind: &'static str, ids: impl Iterator<Item = (&'a str, &'a str)>) -> Vec<Error> {
+    let mut errors = vec![];
+    let mut normalized_ids: HashMap<String, (&str, &str)> = HashMap::new();
+    for (id, provenance) in ids {
+        if let Some(c) = id.chars().find(|c| !c.is_ascii_graphic()) {
+            errors.push(Error::InvalidIdCharacter {
+                kind,
+                id: id.to_owned(),
+                character: format!("U+{:04X}", u32::from(c)),
+                provenance: provenance.to_owned(),
+            });
+        }
+        match normalized_ids.entry(id.nfkc().collect::<String>().to_lowercase()) {
+            Entry::Occupied(entry) => {
+                let (other_id, other_provenance) = *entry.get();
+                if other_id != id {
+                    errors.push(Error::IdCollision {
+                        kind,
+                        id: id.to_owned(),
+                        other_id: other_id.to_owned(),
+                        provenance: provenance.to_owned(),
+                        other_provenance: other_provenance.to_owned(),
+                    });
+                }
+            }
+            Entry::Vacant(entry) => _ = entry.insert((id, provenance)),
+        }
+    }
+    errors
+}
+
+/// Returns the collisions of the metric names (errors) and of the event names
+/// (warnings) of the given registry, i.e. the names defined by several groups.
+/// The name of an event is its `name` field, or its prefix if not specified.
+/// A collision is reported on the group defined last.
+///
+/// Unlike the metric names, the event names are only unique per scope (e.g.
+/// the same event can be described for several platforms), and the scope of
+/// the events is not part of the registry.
+fn signal_name_collisions(ureg: &UnresolvedRegistry) -> (Vec<Error>, Vec<Error>) {
+    let mut metric_errors = vec![];
+    let mut event_warnings = vec![];
+    let mut names: HashMap<(GroupType, &str), (&str, &str)> = HashMap::new();
+    for g in &ureg.groups {
+        let name = match g.group.r#type {
+            GroupType::Metric => g.group.metric_name.as_deref(),
+            GroupType::Event => g
+                .group
+                .name
+                .as_deref()
+                .or_else(|| Some(g.group.prefix.as_str()).filter(|prefix| !prefix.is_empty())),
+            _ => None,
+        };
+        let Some(name) = name else {
+            continue;
+        };
+        match names.entry((g.group.r#type.clone(), name)) {
+            Entry::Occupied(entry) => {
+                let (other_group_id, other_provenance) = *entry.get();
+                let (group_id, provenance) = (g.group.id.clone(), g.provenance.as_str().into());
+                let (other_group_id, other_provenance) =
+                    (other_group_id.to_owned(), other_provenance.into());
+                if g.group.r#type == GroupType::Metric {
+                    metric_errors.push(Error::MetricNameCollision {
+                        name: name.to_owned(),
+                        group_id,
+                        other_group_id,
+                        provenance,
+                        other_provenance,
+                    });
+                } else {
+                    event_warnings.push(Error::EventNameCollision {
+                        name: name.to_owned(),
+                        group_id,
+                        other_group_id,
+                        provenance,
+                        other_provenance,
+                    });
+                }
+            }
+            Entry::Vacant(entry) => _ = entry.insert((g.group.id.as_str(), g.provenance.as_str())),
+        }
+    }
+    (metric_errors, event_warnings)
+}
+
+/// Returns the references to deprecated attributes and groups made by the
+/// groups of the given registry, i.e. the attribute references, `extends`
+/// clauses and `include` constraints. The references made by deprecated groups
+/// and the attribute references deprecating the referenced attribute
+/// themselves are not reported.
+///
+/// Note: the prefixes must be applied to the attribute ids beforehand.
+fn check_deprecated_refs(ureg: &UnresolvedRegistry) -> Vec<Error> {
+    let deprecated_groups: HashMap<&str, &str> = ureg
+        .groups
+        .iter()
+        .filter_map(|g| Some((g.group.id.as_str(), g.group.deprecated.as_deref()?)))
+        .collect();
+    let deprecated_attrs: HashMap<&str, &str> = ureg
+        .groups
+        .iter()
+        .flat_map(|g| g.attributes.iter())
+        .filter_map(|attr| match &attr.spec {
+            AttributeSpec::Id {
+                id,
+                deprecated: Some(deprecated),
+                ..
+            } => Some((id.as_str(), deprecated.as_str())),
+            _ => None,
+        })
+        .collect();
+
+    let mut warnings = vec![];
+    for unresolved_group in ureg.groups.iter() {
+        let group = &unresolved_group.group;
+        if group.deprecated.is_some() {
+            continue;
+        }
+        let deprecated_ref = |kind, id: &str, deprecated: &str| Error::DeprecatedRef {
+            group_id: group.id.clone(),
+            item: Box::new(DeprecatedItem {
+                kind,
+                id: id.to_owned(),
+                deprecated: deprecated.to_owned(),
+                replacement: deprecation_replacement(deprecated),
+            }),
+            provenance: unresolved_group.provenance.clone(),
+        };
+        for attr in unresolved_group.attributes.iter() {
+            if let AttributeSpec::Ref {
+                r#ref,
+                deprecated: None,
+                ..
+            } = &attr.spec
+            {
+                if let Some(deprecated) = deprecated_attrs.get(r#ref.as_str()) {
+                    warnings.push(deprecated_ref(
+                        DeprecatedItemKind::Attribute,
+                        r#ref,
+                        deprecated,
+                    ));
+                }
+            }
+        }
+        let referenced_groups = group.extends.iter().chain(
+            group
+                .constraints
+                .iter()
+                .filter_map(|constraint| constraint.include.as_ref()),
+        );
+        for group_id in referenced_groups {
+            if let Some(deprecated) = deprecated_groups.get(group_id.as_str()) {
+                warnings.push(deprecated_ref(
+                    DeprecatedItemKind::Group,
+                    group_id,
+                    deprecated,
+                ));
+            }
+        }
+    }
+    warnings
+}
+
+/// Returns the replacement declared by a deprecation note, i.e. the first
+/// backquoted id following "replaced by" (case-insensitive), if any.
+#[must_use]
+pub fn deprecation_replacement(deprecated: &str) -> Option<String> {
+    const REPLACED_BY: &str = "replaced by";
+    let start = deprecated.to_ascii_lowercase().find(REPLACED_BY)? + REPLACED_BY.len();
+    let rest = deprecated.get(start..)?.trim_start().strip_prefix('`')?;
+    let end = rest.find('`')?;
+    Some(rest[..end].to_owned())
 }
 
 /// Checks the `any_of` constraints in the given registry.
 ///
+/// The entries of the constraints are resolved through the prefix of their
+/// group (e.g. `method` in a group with the `http` prefix) and through the
+/// legacy names of the attributes, i.e. the names they were renamed from and
+/// the deprecated attributes they replace.
+///
 /// # Arguments
 ///
 /// * `registry` - The registry to check.
-/// * `attr_name_index` - The index of attribute names (catalog).
+/// * `attr_index` - The index of the attributes (catalog).
 ///
 /// # Returns
 ///
-/// This function returns `Ok(())` if all the `any_of` constraints are satisfied.
-/// Otherwise, it returns the error `Error::UnsatisfiedAnyOfConstraint`.
+/// This function returns the warnings of the entries using a legacy name if all
+/// the `any_of` constraints are satisfied. Otherwise, it returns the error
+/// `Error::UnsatisfiedAnyOfConstraint`.
 pub fn check_any_of_constraints(
     registry: &Registry,
-    attr_name_index: &[String],
-) -> Result<(), Error> {
+    attr_index: &[&Attribute],
+) -> Result<Vec<Error>, Error> {
     let mut errors = vec![];
+    let mut warnings = vec![];
+
+    // Map the legacy names of the attributes to their current names.
+    let mut aliases = HashMap::new();
+    for attr in attr_index {
+        for renamed_from in &attr.renamed_from {
+            _ = aliases.insert(renamed_from.clone(), attr.name.to_string());
+        }
+        if let Some(replacement) = attr.deprecated.as_deref().and_then(deprecation_replacement) {
+            _ = aliases.insert(attr.name.to_string(), replacement);
+        }
+    }
 
     for group in registry.groups.iter() {
         // Build a list of attribute names for the group.
         let mut group_attr_names = HashSet::new();
         for attr_ref in group.attributes.iter() {
-            match attr_name_index.get(attr_ref.0 as usize) {
+            match attr_index.get(attr_ref.0 as usize) {
                 None => errors.push(Error::UnresolvedAttributeRef {
                     group_id: group.id.clone(),
                     attribute_ref: attr_ref.0.to_string(),
                     provenance: group.provenance().to_owned(),
                 }),
-                Some(attr_name) => {
-                    _ = group_attr_names.insert(attr_name.clone());
+                Some(attr) => {
+                    _ = group_attr_names.insert(attr.name.to_string());
                 }
             }
         }
 
-        if let Err(e) = check_group_any_of_constraints(
+        match check_group_any_of_constraints(
             group.id.as_ref(),
+            &group.prefix,
             group_attr_names,
             group.constraints.as_ref(),
+            &aliases,
         ) {
-            errors.push(e);
+            Ok(aliases_used) => {
+                warnings.extend(aliases_used.into_iter().map(|(alias, attribute)| {
+                    Error::AnyOfConstraintAlias {
+                        group_id: group.id.clone(),
+                        alias,
+                        attribute,
+                        provenance: group.provenance().to_owned(),
+                    }
+                }));
+            }
+            Err(e) => errors.push(e),
         }
     }
 
+    handle_errors(errors)?;
+    Ok(warnings)
+}
+
+/// Checks that the attributes required by the telemetry groups (i.e. all the
+/// groups but the attribute groups) are not classified as secret, as secret
+/// values must not be recorded.
+///
+/// # Arguments
+///
+/// * `registry` - The registry to check.
+/// * `attr_index` - The index of the attributes (catalog).
+///
+/// # Returns
+///
+/// This function returns `Ok(())` if no required attribute is secret.
+/// Otherwise, it returns the error `Error::RequiredSecretAttribute`.
+fn check_required_secret_attributes(
+    registry: &Registry,
+    attr_index: &[&Attribute],
+) -> Result<(), Error> {
+    let errors = registry
+        .groups
+        .iter()
+        .filter(|group| group.r#type != GroupType::AttributeGroup)
+        .flat_map(|group| {
+            group
+                .attributes
+                .iter()
+                .filter_map(|attr_ref| attr_index.get(attr_ref.0 as usize))
+                .filter(|attr| {
+                    attr.sensitivity == Some(Sensitivity::Secret)
+                        && attr.requirement_level
+                            == RequirementLevel::Basic(BasicRequirementLevelSpec::Required)
+                })
+                .map(|attr| Error::RequiredSecretAttribute {
+                    group_id: group.id.clone(),
+                    attribute: attr.name.to_string(),
+                    provenance: group.provenance().to_owned(),
+                })
+        })
+        .collect();
     handle_errors(errors)?;
     Ok(())
 }
 
-/// Checks the `any_of` constraints for the given group.
+/// Checks the conventions specific to the resource groups:
+/// - the attributes of a resource are known when the resource is detected, so
+///   they can't be `conditionally_required`,
+/// - the attributes of a stable resource are stable,
+/// - `service.name`, which identifies the service the telemetry relates to, is
+///   required by the resources using it.
+///
+/// # Arguments
+///
+/// * `registry` - The registry to check.
+/// * `attr_index` - The index of the attributes (catalog).
+///
+/// # Returns
+///
+/// This function returns the warning `Error::InvalidResourceGroup` for each
+/// broken convention.
+fn check_resource_groups(registry: &Registry, attr_index: &[&Attribute]) -> Vec<Error> {
+    let mut errors = vec![];
+    for group in registry.groups(GroupType::Resource) {
+        let attributes = group
+            .attributes
+            .iter()
+            .filter_map(|attr_ref| attr_index.get(attr_ref.0 as usize));
+        for attr in attributes {
+            let required = attr.requirement_level
+                == RequirementLevel::Basic(BasicRequirementLevelSpec::Required);
+            let error = if matches!(
+                attr.requirement_level,
+                RequirementLevel::ConditionallyRequired { .. }
+            ) {
+                format!(
+                    "declares the attribute '{}' as `conditionally_required`",
+                    attr.name
+                )
+            } else if group.stability == Some(Stability::Stable)
+                && attr.stability != Some(Stability::Stable)
+            {
+                format!("is stable but its attribute '{}' is not", attr.name)
+            } else if attr.name == "service.name" && !required {
+                "doesn't require the attribute 'service.name'".to_owned()
+            } else {
+                continue;
+            };
+            errors.push(Error::InvalidResourceGroup {
+                group_id: group.id.clone(),
+                error,
+                provenance: group.provenance().to_owned(),
+            });
+        }
+    }
+    errors
+}
+
+/// Checks the `any_of` constraints for the given group. Returns the entries of
+/// the constraints using a legacy name, with the name of their attribute, if
+/// all the constraints are satisfied.
 fn check_group_any_of_constraints(
     group_id: &str,
+    group_prefix: &str,
     group_attr_names: HashSet<String>,
     constraints: &[Constraint],
-) -> Result<(), Error> {
+    aliases: &HashMap<String, String>,
+) -> Result<Vec<(String, String)>, Error> {
     let mut unsatisfied_any_of_constraints: HashMap<&Constraint, UnsatisfiedAnyOfConstraint> =
         HashMap::new();
+    let mut aliases_used = vec![];
 
     for constraint in constraints.iter() {
         if constraint.any_of.is_empty() {
@@ -168,11 +765,18 @@ fn check_group_any_of_constraints(
         }
 
         // Check if the group satisfies the `any_of` constraint.
-        if let Some(attr) = constraint
-            .any_of
-            .iter()
-            .find(|name| !group_attr_names.contains(*name))
-        {
+        let mut missing_attribute = None;
+        for name in constraint.any_of.iter() {
+            match resolve_any_of_entry(name, group_prefix, &group_attr_names, aliases) {
+                None => {
+                    missing_attribute = Some(name);
+                    break;
+                }
+                Some((attribute, true)) => aliases_used.push((name.clone(), attribute)),
+                Some((_, false)) => {}
+            }
+        }
+        if let Some(attr) = missing_attribute {
             // The any_of constraint is not satisfied.
             // Insert the attribute into the list of missing attributes for the
             // constraint.
@@ -197,7 +801,41 @@ fn check_group_any_of_constraints(
             .collect();
         return Err(Error::CompoundError(errors));
     }
-    Ok(())
+    Ok(aliases_used)
+}
+
+/// Resolves an entry of an `any_of` constraint to the name of an attribute of
+/// the group: the entry itself, the entry qualified with the group prefix, or
+/// the current name of a legacy name (following successive renamings).
+/// Returns `None` if the entry doesn't resolve to an attribute of the group,
+/// and whether a legacy name was used otherwise.
+fn resolve_any_of_entry(
+    name: &str,
+    group_prefix: &str,
+    group_attr_names: &HashSet<String>,
+    aliases: &HashMap<String, String>,
+) -> Option<(String, bool)> {
+    if group_attr_names.contains(name) {
+        return Some((name.to_owned(), false));
+    }
+    if !group_prefix.is_empty() {
+        let qualified_name = format!("{group_prefix}.{name}");
+        if group_attr_names.contains(&qualified_name) {
+            return Some((qualified_name, false));
+        }
+    }
+    let mut visited = HashSet::from([name]);
+    let mut current = name;
+    while let Some(next) = aliases.get(current) {
+        if group_attr_names.contains(next) {
+            return Some((next.clone(), true));
+        }
+        if !visited.insert(next.as_str()) {
+            break;
+        }
+        current = next;
+    }
+    None
 }
 
 /// Creates a semantic convention registry from a set of semantic convention
@@ -221,9 +859,10 @@ fn unresolved_registry_from_specs(
     registry_url: &str,
     registry: &SemConvRegistry,
 ) -> UnresolvedRegistry {
+    let mut prefixes = StringInterner::new();
     let groups = registry
         .unresolved_group_with_provenance_iter()
-        .map(group_from_spec)
+        .map(|group| group_from_spec(group, &mut prefixes))
         .collect();
 
     UnresolvedRegistry {
@@ -237,7 +876,10 @@ fn unresolved_registry_from_specs(
 
 /// Creates a group from a semantic convention group specification.
 /// Note: this function does not resolve references.
-fn group_from_spec(group: GroupSpecWithProvenance) -> UnresolvedGroup {
+fn group_from_spec(
+    group: GroupSpecWithProvenance,
+    prefixes: &mut StringInterner,
+) -> UnresolvedGroup {
     let attrs = group
         .spec
         .attributes
@@ -251,7 +893,7 @@ fn group_from_spec(group: GroupSpecWithProvenance) -> UnresolvedGroup {
             r#type: group.spec.r#type,
             brief: group.spec.brief,
             note: group.spec.note,
-            prefix: group.spec.prefix,
+            prefix: prefixes.intern_string(group.spec.prefix),
             extends: group.spec.extends,
             stability: group.spec.stability,
             deprecated: group.spec.deprecated,
@@ -263,8 +905,12 @@ fn group_from_spec(group: GroupSpecWithProvenance) -> UnresolvedGroup {
             instrument: group.spec.instrument,
             unit: group.spec.unit,
             name: group.spec.name,
+            scope_version: group.spec.scope_version,
             lineage: Some(GroupLineage::new(&group.provenance)),
             display_name: group.spec.display_name,
+            doc_url: group.spec.doc_url,
+            since_version: group.spec.since_version,
+            annotations: group.spec.annotations,
         },
         attributes: attrs,
         provenance: group.provenance,
@@ -275,12 +921,18 @@ fn group_from_spec(group: GroupSpecWithProvenance) -> UnresolvedGroup {
 /// the group prefix before continuing resolution.
 ///
 /// This should be the *only* method that updates attribute ids.
-fn resolve_prefix_on_attributes(ureg: &mut UnresolvedRegistry) -> Result<(), Error> {
+/// The names before the prefix is applied are recorded in the attribute catalog.
+fn resolve_prefix_on_attributes(
+    ureg: &mut UnresolvedRegistry,
+    attr_catalog: &mut AttributeCatalog,
+) -> Result<(), Error> {
     for unresolved_group in ureg.groups.iter_mut() {
         if !unresolved_group.group.prefix.is_empty() {
             for attribute in unresolved_group.attributes.iter_mut() {
                 if let AttributeSpec::Id { id, .. } = &mut attribute.spec {
-                    *id = format!("{}.{}", unresolved_group.group.prefix, id);
+                    let name = format!("{}.{}", unresolved_group.group.prefix, id);
+                    attr_catalog.add_short_name(&name, id);
+                    *id = name;
                 }
             }
         }
@@ -288,6 +940,105 @@ fn resolve_prefix_on_attributes(ureg: &mut UnresolvedRegistry) -> Result<(), Err
     Ok(())
 }
 
+/// Fails with [`Error::MaxIterationsExceeded`] if the given iteration of a
+/// resolution step exceeds the maximum number of iterations. The chain
+/// reported in the error is only computed in this case.
+fn check_iterations(
+    step: &str,
+    iteration: usize,
+    max_iterations: Option<usize>,
+    chain: impl FnOnce() -> Vec<String>,
+) -> Result<(), Error> {
+    match max_iterations {
+        Some(max_iterations) if iteration > max_iterations => Err(Error::MaxIterationsExceeded {
+            step: step.to_owned(),
+            max_iterations,
+            chain: chain(),
+        }),
+        _ => Ok(()),
+    }
+}
+
+/// Returns the group referenced by the `extends` clause of each group.
+fn extends_references(ureg: &UnresolvedRegistry) -> HashMap<&str, Vec<&str>> {
+    ureg.groups
+        .iter()
+        .filter_map(|group| {
+            group
+                .group
+                .extends
+                .as_deref()
+                .map(|extends| (group.group.id.as_str(), vec![extends]))
+        })
+        .collect()
+}
+
+/// Returns the groups referenced by the `include` constraints of each group.
+fn include_references(ureg: &UnresolvedRegistry) -> HashMap<&str, Vec<&str>> {
+    ureg.groups
+        .iter()
+        .filter_map(|group| {
+            let includes: Vec<_> = group
+                .group
+                .constraints
+                .iter()
+                .filter_map(|constraint| constraint.include.as_deref())
+                .collect();
+            (!includes.is_empty()).then_some((group.group.id.as_str(), includes))
+        })
+        .collect()
+}
+
+/// Returns the longest chain of groups following the given references (e.g.
+/// `a -> b -> c` for `a` extending `b` extending `c`). The cycles are cut.
+///
+/// The chains are explored without recursion, so pathological registries can't
+/// overflow the stack.
+fn deepest_chain(references: &HashMap<&str, Vec<&str>>) -> Vec<String> {
+    // The depth of the deepest chain starting from each group, and the next
+    // group of this chain.
+    let mut depths: HashMap<&str, (usize, Option<&str>)> = HashMap::new();
+    let mut ids: Vec<_> = references.keys().copied().collect();
+    ids.sort_unstable();
+
+    for &id in &ids {
+        let mut in_progress = HashSet::new();
+        let mut stack = vec![(id, false)];
+        while let Some((id, visited)) = stack.pop() {
+            if visited {
+                _ = in_progress.remove(id);
+                let deepest = references
+                    .get(id)
+                    .into_iter()
+                    .flatten()
+                    .filter_map(|next| depths.get(next).map(|(depth, _)| (depth + 1, Some(*next))))
+                    .max_by_key(|(depth, _)| *depth)
+                    .unwrap_or((0, None));
+                _ = depths.insert(id, deepest);
+            } else if !depths.contains_key(id) && in_progress.insert(id) {
+                stack.push((id, true));
+                for next in references.get(id).into_iter().flatten() {
+                    if !depths.contains_key(next) && !in_progress.contains(next) {
+                        stack.push((next, false));
+                    }
+                }
+            }
+        }
+    }
+
+    let mut chain = vec![];
+    let mut next = ids
+        .iter()
+        .copied()
+        .rev()
+        .max_by_key(|id| depths.get(id).map(|(depth, _)| *depth));
+    while let Some(id) = next {
+        chain.push(id.to_owned());
+        next = depths.get(id).and_then(|(_, next)| *next);
+    }
+    chain
+}
+
 /// Resolves attribute references in the given registry.
 /// The resolution process is iterative. The process stops when all the
 /// attribute references are resolved or when no attribute reference could
@@ -300,8 +1051,26 @@ fn resolve_prefix_on_attributes(ureg: &mut UnresolvedRegistry) -> Result<(), Err
 fn resolve_attribute_references(
     ureg: &mut UnresolvedRegistry,
     attr_catalog: &mut AttributeCatalog,
+    lenient: bool,
+    max_iterations: Option<usize>,
+    warnings: &mut Vec<Error>,
+    cancellation: &CancellationToken,
 ) -> Result<(), Error> {
-    loop {
+    for iteration in 1.. {
+        cancellation.check()?;
+        check_iterations("attribute references", iteration, max_iterations, || {
+            ureg.groups
+                .iter()
+                .find_map(|group| {
+                    group.attributes.iter().find_map(|attr| match &attr.spec {
+                        AttributeSpec::Ref { r#ref, .. } => {
+                            Some(vec![group.group.id.clone(), r#ref.clone()])
+                        }
+                        AttributeSpec::Id { .. } => None,
+                    })
+                })
+                .unwrap_or_default()
+        })?;
         let mut errors = vec![];
         let mut resolved_attr_count = 0;
 
@@ -351,7 +1120,21 @@ fn resolve_attribute_references(
         // It means that we have an issue with the semantic convention
         // specifications.
         if resolved_attr_count == 0 {
-            return Err(Error::CompoundError(errors));
+            if !lenient {
+                return Err(Error::CompoundError(errors));
+            }
+            // Replace the unresolved references with placeholder attributes.
+            for unresolved_group in ureg.groups.iter_mut() {
+                for attr in unresolved_group.attributes.drain(..) {
+                    if let Some(attr_ref) =
+                        attr_catalog.placeholder(&unresolved_group.group.id, &attr.spec)
+                    {
+                        unresolved_group.group.attributes.push(attr_ref);
+                    }
+                }
+            }
+            warnings.extend(ignored_by_lenient_mode(errors));
+            break;
         }
     }
 
@@ -364,35 +1147,64 @@ fn resolve_attribute_references(
 /// be resolved in an iteration.
 ///
 /// Returns true if all the `extends` references have been resolved.
-fn resolve_extends_references(ureg: &mut UnresolvedRegistry) -> Result<(), Error> {
-    loop {
+fn resolve_extends_references(
+    ureg: &mut UnresolvedRegistry,
+    lenient: bool,
+    max_iterations: Option<usize>,
+    warnings: &mut Vec<Error>,
+    cancellation: &CancellationToken,
+) -> Result<(), Error> {
+    for iteration in 1.. {
+        cancellation.check()?;
+        check_iterations("`extends` clauses", iteration, max_iterations, || {
+            deepest_chain(&extends_references(ureg))
+        })?;
         let mut errors = vec![];
         let mut resolved_extends_count = 0;
 
-        // Create a map group_id -> attributes for groups
-        // that don't have an `extends` clause.
+        // Create a map group_id -> (attributes, lineage, constraints) for
+        // groups that don't have an `extends` clause.
         let mut group_index = HashMap::new();
         for group in ureg.groups.iter() {
             if group.group.extends.is_none() {
-                _ = group_index.insert(group.group.id.clone(), group.attributes.clone());
+                _ = group_index.insert(
+                    group.group.id.clone(),
+                    (
+                        group.attributes.clone(),
+                        group.group.lineage.clone(),
+                        group.group.constraints.clone(),
+                    ),
+                );
             }
         }
 
         // Iterate over all groups and resolve the `extends` clauses.
         for unresolved_group in ureg.groups.iter_mut() {
-            if let Some(extends) = unresolved_group.group.extends.as_ref() {
-                if let Some(attrs) = group_index.get(extends) {
+            if let Some(extends) = unresolved_group.group.extends.clone() {
+                if let Some((attrs, parent_lineage, parent_constraints)) = group_index.get(&extends)
+                {
                     unresolved_group.attributes = resolve_inheritance_attrs(
                         &unresolved_group.group.id,
                         &unresolved_group.attributes,
-                        extends,
+                        &extends,
                         attrs,
+                        parent_lineage.as_ref(),
                         unresolved_group.group.lineage.as_mut(),
                     );
+                    resolve_inheritance_constraints(
+                        &mut unresolved_group.group,
+                        &extends,
+                        parent_constraints,
+                        parent_lineage.as_ref(),
+                    );
                     _ = unresolved_group.group.extends.take();
                     _ = group_index.insert(
                         unresolved_group.group.id.clone(),
-                        unresolved_group.attributes.clone(),
+                        (
+                            unresolved_group.attributes.clone(),
+                            unresolved_group.group.lineage.clone(),
+                            unresolved_group.group.constraints.clone(),
+                        ),
                     );
                     resolved_extends_count += 1;
                 } else {
@@ -417,7 +1229,23 @@ fn resolve_extends_references(ureg: &mut UnresolvedRegistry) -> Result<(), Error
         // It means that we have an issue with the semantic convention
         // specifications.
         if resolved_extends_count == 0 {
-            return Err(Error::CompoundError(errors));
+            if !lenient {
+                return Err(Error::CompoundError(errors));
+            }
+            // Ignore the `extends` clauses referencing unknown groups, or all
+            // the remaining ones if they form a cycle.
+            let ignored = errors_ignored_by_lenient_mode(ureg, errors, |error| match error {
+                Error::UnresolvedExtendsRef { extends_ref, .. } => Some(extends_ref),
+                _ => None,
+            });
+            for error in &ignored {
+                if let Error::UnresolvedExtendsRef { group_id, .. } = error {
+                    if let Some(group) = ureg.groups.iter_mut().find(|g| &g.group.id == group_id) {
+                        _ = group.group.extends.take();
+                    }
+                }
+            }
+            warnings.extend(ignored_by_lenient_mode(ignored));
         }
     }
     Ok(())
@@ -429,8 +1257,18 @@ fn resolve_extends_references(ureg: &mut UnresolvedRegistry) -> Result<(), Error
 /// and iterative algorithm that is most likely good enough for now. If the
 /// semconv registry becomes too large, we may need to revisit the resolution
 /// process to make it more efficient by using a topological sort algorithm.
-fn resolve_include_constraints(ureg: &mut UnresolvedRegistry) -> Result<(), Error> {
-    loop {
+fn resolve_include_constraints(
+    ureg: &mut UnresolvedRegistry,
+    lenient: bool,
+    max_iterations: Option<usize>,
+    warnings: &mut Vec<Error>,
+    cancellation: &CancellationToken,
+) -> Result<(), Error> {
+    for iteration in 1.. {
+        cancellation.check()?;
+        check_iterations("`include` constraints", iteration, max_iterations, || {
+            deepest_chain(&include_references(ureg))
+        })?;
         let mut errors = vec![];
         let mut resolved_include_count = 0;
 
@@ -508,17 +1346,70 @@ fn resolve_include_constraints(ureg: &mut UnresolvedRegistry) -> Result<(), Erro
         // It means that we have an issue with the semantic convention
         // specifications.
         if resolved_include_count == 0 {
-            return Err(Error::CompoundError(errors));
+            if !lenient {
+                return Err(Error::CompoundError(errors));
+            }
+            // Ignore the `include` constraints referencing unknown groups, or
+            // all the remaining ones if they form a cycle.
+            let ignored = errors_ignored_by_lenient_mode(ureg, errors, |error| match error {
+                Error::UnresolvedIncludeRef { include_ref, .. } => Some(include_ref),
+                _ => None,
+            });
+            for error in &ignored {
+                if let Error::UnresolvedIncludeRef {
+                    group_id,
+                    include_ref,
+                    ..
+                } = error
+                {
+                    if let Some(group) = ureg.groups.iter_mut().find(|g| &g.group.id == group_id) {
+                        group
+                            .group
+                            .update_constraints(vec![], HashSet::from([include_ref.clone()]));
+                    }
+                }
+            }
+            warnings.extend(ignored_by_lenient_mode(ignored));
         }
     }
     Ok(())
 }
 
+/// Inherits the constraints of the extended group. The `include` constraints
+/// of the extended group are always inherited, its `any_of` constraints are
+/// inherited unless the group declares its own `any_of` constraints (which
+/// override them). The inherited constraints are recorded in the lineage of
+/// the group along with the group declaring them.
+fn resolve_inheritance_constraints(
+    group: &mut Group,
+    parent_group_id: &str,
+    parent_constraints: &[Constraint],
+    parent_group_lineage: Option<&GroupLineage>,
+) {
+    let overrides_any_of = group.constraints.iter().any(|c| !c.any_of.is_empty());
+    for constraint in parent_constraints {
+        if group.constraints.contains(constraint)
+            || (overrides_any_of && !constraint.any_of.is_empty())
+        {
+            continue;
+        }
+        group.constraints.push(constraint.clone());
+        if let Some(lineage) = group.lineage.as_mut() {
+            // The constraint may have been inherited by the extended group.
+            let source_group = parent_group_lineage
+                .and_then(|lineage| lineage.constraint_source(constraint))
+                .unwrap_or(parent_group_id);
+            lineage.add_inherited_constraint(source_group, constraint);
+        }
+    }
+}
+
 fn resolve_inheritance_attrs(
     group_id: &str,
     attrs_group: &[UnresolvedAttribute],
     parent_group_id: &str,
     attrs_parent_group: &[UnresolvedAttribute],
+    parent_group_lineage: Option<&GroupLineage>,
     group_lineage: Option<&mut GroupLineage>,
 ) -> Vec<UnresolvedAttribute> {
     struct AttrWithLineage {
@@ -536,11 +1427,19 @@ fn resolve_inheritance_attrs(
     // Inherit the attributes from the parent group.
     for parent_attr in attrs_parent_group.iter() {
         let attr_id = parent_attr.spec.id();
+        let mut lineage = AttributeLineage::inherit_from(parent_group_id, &parent_attr.spec);
+        // The fields overridden in the parent group may have been inherited
+        // from a group further up the `extends` chain.
+        if let Some(parent_attr_lineage) =
+            parent_group_lineage.and_then(|lineage| lineage.attribute(&attr_id))
+        {
+            lineage.inherit_overrides(parent_attr_lineage);
+        }
         _ = inherited_attrs.insert(
             attr_id.clone(),
             AttrWithLineage {
                 spec: parent_attr.spec.clone(),
-                lineage: AttributeLineage::inherit_from(parent_group_id, &parent_attr.spec),
+                lineage,
             },
         );
     }
@@ -555,6 +1454,7 @@ fn resolve_inheritance_attrs(
                 }) = inherited_attrs.get_mut(r#ref)
                 {
                     *parent_attr = resolve_inheritance_attr(&attr.spec, parent_attr, lineage);
+                    lineage.record_overriding_group(group_id, None);
                 } else {
                     _ = inherited_attrs.insert(
                         r#ref.clone(),
@@ -660,6 +1560,9 @@ fn resolve_inheritance_attr(
                     note: parent_note,
                     stability: parent_stability,
                     deprecated: parent_deprecated,
+                    renamed_from: parent_renamed_from,
+                    sensitivity: parent_sensitivity,
+                    annotations: parent_annotations,
                     ..
                 } => {
                     // attr is a reference and attr_parent is an id.
@@ -677,6 +1580,9 @@ fn resolve_inheritance_attr(
                         note: lineage.note(note, parent_note),
                         stability: lineage.stability(stability, parent_stability),
                         deprecated: lineage.deprecated(deprecated, parent_deprecated),
+                        renamed_from: parent_renamed_from.clone(),
+                        sensitivity: *parent_sensitivity,
+                        annotations: parent_annotations.clone(),
                     }
                 }
             }
@@ -687,20 +1593,27 @@ fn resolve_inheritance_attr(
 
 #[cfg(test)]
 mod tests {
-    use std::collections::HashSet;
+    use std::collections::{HashMap, HashSet};
     use std::error::Error;
+    use std::time::Duration;
 
     use glob::glob;
+    use miette::Diagnostic;
     use serde::Serialize;
 
+    use weaver_common::cancellation::CancellationToken;
     use weaver_resolved_schema::attribute;
     use weaver_resolved_schema::registry::{Constraint, Registry};
+    use weaver_semconv::attribute::{BasicRequirementLevelSpec, RequirementLevel};
     use weaver_semconv::group::GroupType;
     use weaver_semconv::registry::SemConvRegistry;
 
-    use crate::attribute::AttributeCatalog;
-    use crate::registry::{check_group_any_of_constraints, resolve_semconv_registry};
-    use crate::SchemaResolver;
+    use crate::attribute::{AttributeCatalog, UNRESOLVED_REF_TAG};
+    use crate::registry::{
+        check_group_any_of_constraints, deepest_chain, resolve_semconv_registry,
+        resolve_semconv_registry_with_options,
+    };
+    use crate::{DeprecatedItemKind, ResolutionMode, ResolverOptions, SchemaResolver};
 
     /// Test the resolution of semantic convention registries stored in the
     /// data directory. The provided test cases cover the following resolution
@@ -846,42 +1759,992 @@ groups:
     }
 
     #[test]
-    fn test_registry_error_unresolved_includes() {
-        let result = create_registry_from_string(
-            "
+    fn test_lenient_resolution() {
+        let mut sc_specs = SemConvRegistry::new("default");
+        sc_specs
+            .add_semconv_spec_from_string(
+                "<str>",
+                "
 groups:
+    - id: registry.one
+      type: attribute_group
+      brief: 'Registry one'
+      attributes:
+        - id: attr.one
+          type: string
+          brief: 'Attribute one'
+          examples: ['one']
     - id: span.one
       type: span
       brief: 'Span one'
+      extends: non.existent.group
+      attributes:
+        - ref: attr.one
+        - ref: non.existent.attr
+          requirement_level: opt_in
       constraints:
-        - include: 'non.existent.one'
-        - include: 'non.existent.two'
-        - include: 'non.existent.three'",
-        );
+        - include: non.existent.include",
+            )
+            .expect("Failed to load semconv spec");
 
-        assert!(result.is_err());
+        // The strict mode reports the unresolved references as errors.
+        let mut attr_catalog = AttributeCatalog::default();
+        assert!(
+            resolve_semconv_registry(&mut attr_catalog, "https://127.0.0.1", &sc_specs).is_err()
+        );
 
-        if let crate::Error::CompoundError(errors) = result.unwrap_err() {
-            assert!(errors.len() == 3);
-        } else {
-            panic!("Expected a CompoundError");
+        let options = ResolverOptions {
+            mode: ResolutionMode::Lenient,
+            ..ResolverOptions::default()
+        };
+        let mut attr_catalog = AttributeCatalog::default();
+        let (registry, warnings) = resolve_semconv_registry_with_options(
+            &mut attr_catalog,
+            "https://127.0.0.1",
+            &sc_specs,
+            &options,
+            &CancellationToken::default(),
+        )
+        .expect("The lenient resolution should succeed");
+
+        assert_eq!(warnings.len(), 3);
+        for warning in &warnings {
+            assert!(matches!(warning, crate::Error::IgnoredByLenientMode { .. }));
+            assert_eq!(warning.severity(), Some(miette::Severity::Warning));
         }
+        assert!(warnings.iter().any(|w| matches!(w, crate::Error::IgnoredByLenientMode { error } if matches!(**error, crate::Error::UnresolvedAttributeRef { .. }))));
+        assert!(warnings.iter().any(|w| matches!(w, crate::Error::IgnoredByLenientMode { error } if matches!(**error, crate::Error::UnresolvedExtendsRef { .. }))));
+        assert!(warnings.iter().any(|w| matches!(w, crate::Error::IgnoredByLenientMode { error } if matches!(**error, crate::Error::UnresolvedIncludeRef { .. }))));
+
+        let span = registry
+            .groups
+            .iter()
+            .find(|g| g.id == "span.one")
+            .expect("span.one not found");
+        assert_eq!(span.attributes.len(), 2);
+        let attributes = attr_catalog.drain_attributes();
+        let placeholder = attributes
+            .iter()
+            .find(|attr| attr.name.as_str() == "non.existent.attr")
+            .expect("Placeholder attribute not found");
+        let tags = placeholder
+            .tags
+            .as_ref()
+            .expect("Placeholder should be tagged");
+        assert_eq!(tags.tags[UNRESOLVED_REF_TAG], "span.one");
+        assert_eq!(
+            placeholder.requirement_level,
+            RequirementLevel::Basic(BasicRequirementLevelSpec::OptIn)
+        );
     }
 
-    /// Test the validation of the `any_of` constraints in a group.
     #[test]
-    fn test_check_group_any_of_constraints() -> Result<(), crate::Error> {
-        // No attribute and no constraint.
+    fn test_constraint_inheritance() {
+        let specs = |child_constraints: &str| {
+            let mut sc_specs = SemConvRegistry::new("default");
+            sc_specs
+                .add_semconv_spec_from_string(
+                    "<str>",
+                    &format!(
+                        "
+groups:
+    - id: registry.common
+      type: attribute_group
+      brief: 'Common attributes'
+      attributes:
+        - id: attr.one
+          type: string
+          brief: 'Attribute one'
+          examples: ['one']
+        - id: attr.two
+          type: string
+          brief: 'Attribute two'
+          examples: ['two']
+    - id: base
+      type: attribute_group
+      brief: 'Base group'
+      constraints:
+        - any_of:
+            - attr.one
+        - include: registry.common
+    - id: intermediate
+      type: span
+      brief: 'Intermediate group'
+      extends: base
+    - id: child
+      type: span
+      brief: 'Child group'
+      extends: intermediate
+      {child_constraints}"
+                    ),
+                )
+                .expect("Failed to load semconv spec");
+            sc_specs
+        };
+
+        // The constraints of `base` are inherited through `intermediate`.
+        let mut attr_catalog = AttributeCatalog::default();
+        let registry = resolve_semconv_registry(&mut attr_catalog, "https://127.0.0.1", &specs(""))
+            .expect("Failed to resolve registry");
+        let child = registry
+            .groups
+            .iter()
+            .find(|g| g.id == "child")
+            .expect("child not found");
+        assert_eq!(child.attributes.len(), 2);
+        let lineage = child.lineage.as_ref().expect("lineage not found");
+        let inherited = lineage.inherited_constraints();
+        assert_eq!(inherited.len(), 2);
+        assert!(inherited.iter().all(|c| c.source_group == "base"));
+
+        // A group overriding the `any_of` constraints of its parent must
+        // satisfy its own constraints only.
+        let mut attr_catalog = AttributeCatalog::default();
+        let registry = resolve_semconv_registry(
+            &mut attr_catalog,
+            "https://127.0.0.1",
+            &specs("constraints:\n        - any_of:\n            - attr.two"),
+        )
+        .expect("Failed to resolve registry");
+        let child = registry
+            .groups
+            .iter()
+            .find(|g| g.id == "child")
+            .expect("child not found");
+        let inherited = child
+            .lineage
+            .as_ref()
+            .expect("lineage not found")
+            .inherited_constraints();
+        assert_eq!(inherited.len(), 1);
+        assert_eq!(inherited[0].include.as_deref(), Some("registry.common"));
+    }
+
+    #[test]
+    fn test_required_secret_attributes() {
+        let mut sc_specs = SemConvRegistry::new("default");
+        sc_specs
+            .add_semconv_spec_from_string(
+                "<str>",
+                "
+groups:
+    - id: registry.user
+      type: attribute_group
+      brief: 'User attributes'
+      attributes:
+        - id: user.password
+          type: string
+          brief: 'User password'
+          examples: ['secret']
+          requirement_level: required
+          sensitivity: secret
+    - id: span.login
+      type: span
+      brief: 'Login span'
+      attributes:
+        - ref: user.password
+    - id: span.logout
+      type: span
+      brief: 'Logout span'
+      attributes:
+        - ref: user.password
+          requirement_level: opt_in",
+            )
+            .expect("Failed to load semconv spec");
+
+        let mut attr_catalog = AttributeCatalog::default();
+        let result = resolve_semconv_registry(&mut attr_catalog, "https://127.0.0.1", &sc_specs);
+        // The attribute group and the span not requiring the attribute are
+        // not reported.
+        match result {
+            Err(crate::Error::RequiredSecretAttribute {
+                group_id,
+                attribute,
+                ..
+            }) => {
+                assert_eq!(group_id, "span.login");
+                assert_eq!(attribute, "user.password");
+            }
+            _ => panic!("Expected a RequiredSecretAttribute error"),
+        }
+    }
+
+    #[test]
+    fn test_check_ids() {
+        let mut sc_specs = SemConvRegistry::new("default");
+        sc_specs
+            .add_semconv_spec_from_string(
+                "http.yaml",
+                "
+groups:
+    - id: registry.http
+      type: attribute_group
+      brief: 'HTTP attributes'
+      attributes:
+        - id: http.method
+          type: string
+          brief: 'HTTP method'
+          examples: ['GET']
+        - id: http.route\u{200b}
+          type: string
+          brief: 'HTTP route'
+          examples: ['/users']
+    - id: registry.\u{fb01}le
+      type: attribute_group
+      brief: 'File attributes'
+      attributes: []",
+            )
+            .expect("Failed to load semconv spec");
+        sc_specs
+            .add_semconv_spec_from_string(
+                "legacy.yaml",
+                "
+groups:
+    - id: registry.legacy
+      type: attribute_group
+      brief: 'Legacy attributes'
+      attributes:
+        - id: http.Method
+          type: string
+          brief: 'HTTP method'
+          examples: ['GET']
+    - id: registry.file
+      type: attribute_group
+      brief: 'File attributes'
+      attributes: []",
+            )
+            .expect("Failed to load semconv spec");
+
+        let mut attr_catalog = AttributeCatalog::default();
+        let result = resolve_semconv_registry(&mut attr_catalog, "https://127.0.0.1", &sc_specs);
+        let Err(crate::Error::CompoundError(errors)) = result else {
+            panic!("Expected a compound error");
+        };
+        let messages: Vec<String> = errors.iter().map(ToString::to_string).collect();
+        assert_eq!(
+            messages,
+            vec![
+                "The group id 'registry.\u{fb01}le' contains the non-ASCII or invisible character U+FB01.\nProvenance: http.yaml",
+                "The group id 'registry.file' collides with the group id 'registry.\u{fb01}le', they have the same NFC/NFKC normalization.\nProvenance: legacy.yaml\nOther provenance: http.yaml",
+                "The attribute id 'http.route\u{200b}' contains the non-ASCII or invisible character U+200B.\nProvenance: http.yaml",
+                "The attribute id 'http.Method' collides with the attribute id 'http.method', they differ only by case.\nProvenance: legacy.yaml\nOther provenance: http.yaml",
+            ]
+        );
+
+        // The lenient mode reports the errors as warnings.
+        let mut attr_catalog = AttributeCatalog::default();
+        let (_, warnings) = resolve_semconv_registry_with_options(
+            &mut attr_catalog,
+            "https://127.0.0.1",
+            &sc_specs,
+            &ResolverOptions {
+                mode: ResolutionMode::Lenient,
+                ..ResolverOptions::default()
+            },
+            &CancellationToken::default(),
+        )
+        .expect("The lenient mode should not fail");
+        assert_eq!(warnings.len(), 4);
+    }
+
+    #[test]
+    fn test_check_signal_names() {
+        let mut sc_specs = SemConvRegistry::new("default");
+        sc_specs
+            .add_semconv_spec_from_string(
+                "http.yaml",
+                "
+groups:
+    - id: metric.http.server.duration
+      type: metric
+      metric_name: http.server.duration
+      instrument: histogram
+      unit: s
+      brief: 'HTTP server duration'
+    - id: metric.http.client.duration
+      type: metric
+      metric_name: http.client.duration
+      instrument: histogram
+      unit: s
+      brief: 'HTTP client duration'
+    - id: event.http.retry
+      type: event
+      name: http.retry
+      brief: 'HTTP retry'
+    - id: event.http.redirect
+      type: event
+      prefix: http.redirect
+      brief: 'HTTP redirect'",
+            )
+            .expect("Failed to load semconv spec");
+        sc_specs
+            .add_semconv_spec_from_string(
+                "legacy.yaml",
+                "
+groups:
+    - id: metric.legacy.http.server.duration
+      type: metric
+      metric_name: http.server.duration
+      instrument: histogram
+      unit: ms
+      brief: 'Legacy HTTP server duration'
+    - id: event.legacy.http.redirect
+      type: event
+      name: http.redirect
+      brief: 'Legacy HTTP redirect'
+    - id: event.legacy.http.client.duration
+      type: event
+      name: http.client.duration
+      brief: 'An event named like a metric'",
+            )
+            .expect("Failed to load semconv spec");
+
+        // The metric name collisions are errors.
+        let mut attr_catalog = AttributeCatalog::default();
+        let result = resolve_semconv_registry(&mut attr_catalog, "https://127.0.0.1", &sc_specs);
+        let Err(error) = result else {
+            panic!("Expected an error");
+        };
+        assert_eq!(
+            error.to_string(),
+            "The group 'metric.legacy.http.server.duration' defines the metric name 'http.server.duration' already defined by the group 'metric.http.server.duration'.\nProvenance: legacy.yaml\nOther provenance: http.yaml"
+        );
+
+        // The lenient mode reports the errors as warnings, the event name
+        // collisions are always warnings.
+        let mut attr_catalog = AttributeCatalog::default();
+        let (_, warnings) = resolve_semconv_registry_with_options(
+            &mut attr_catalog,
+            "https://127.0.0.1",
+            &sc_specs,
+            &ResolverOptions {
+                mode: ResolutionMode::Lenient,
+                ..ResolverOptions::default()
+            },
+            &CancellationToken::default(),
+        )
+        .expect("The lenient mode should not fail");
+        assert_eq!(warnings.len(), 2);
+        assert!(matches!(
+            warnings[0],
+            crate::Error::IgnoredByLenientMode { .. }
+        ));
+        assert_eq!(
+            warnings[1].to_string(),
+            "The group 'event.legacy.http.redirect' defines the event name 'http.redirect' already defined by the group 'event.http.redirect'.\nProvenance: legacy.yaml\nOther provenance: http.yaml"
+        );
+        assert_eq!(warnings[1].severity(), Some(miette::Severity::Warning));
+    }
+
+    #[test]
+    fn test_check_resource_groups() {
+        let mut sc_specs = SemConvRegistry::new("default");
+        sc_specs
+            .add_semconv_spec_from_string(
+                "resource.yaml",
+                "
+groups:
+    - id: registry.service
+      type: attribute_group
+      brief: 'Service attributes'
+      attributes:
+        - id: service.name
+          type: string
+          stability: stable
+          brief: 'Service name'
+          examples: ['shop']
+        - id: service.version
+          type: string
+          stability: experimental
+          brief: 'Service version'
+          examples: ['1.0.0']
+    - id: resource.service
+      type: resource
+      stability: stable
+      brief: 'Service resource'
+      attributes:
+        - ref: service.name
+          requirement_level: required
+        - ref: service.version
+    - id: resource.service.experimental
+      type: resource
+      stability: experimental
+      brief: 'Experimental service resource'
+      attributes:
+        - ref: service.name
+        - ref: service.version
+          requirement_level:
+            conditionally_required: 'If known'",
+            )
+            .expect("Failed to load semconv spec");
+
+        let mut attr_catalog = AttributeCatalog::default();
+        let (_, warnings) = resolve_semconv_registry_with_options(
+            &mut attr_catalog,
+            "https://127.0.0.1",
+            &sc_specs,
+            &ResolverOptions::default(),
+            &CancellationToken::default(),
+        )
+        .expect("The resource conventions are only checked as warnings");
+        assert!(warnings
+            .iter()
+            .all(|warning| warning.severity() == Some(miette::Severity::Warning)));
+        let warnings: Vec<_> = warnings.iter().map(ToString::to_string).collect();
+        assert_eq!(
+            warnings,
+            vec![
+                "The resource group 'resource.service' is stable but its attribute 'service.version' is not.\nProvenance: resource.yaml",
+                "The resource group 'resource.service.experimental' doesn't require the attribute 'service.name'.\nProvenance: resource.yaml",
+                "The resource group 'resource.service.experimental' declares the attribute 'service.version' as `conditionally_required`.\nProvenance: resource.yaml",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_annotations() {
+        let mut sc_specs = SemConvRegistry::new("default");
+        sc_specs
+            .add_semconv_spec_from_string(
+                "<str>",
+                "
+groups:
+    - id: registry.user
+      type: attribute_group
+      brief: 'User attributes'
+      annotations:
+        owner: identity-team
+      attributes:
+        - id: user.email
+          type: string
+          brief: 'User email'
+          examples: ['a@b.c']
+          annotations:
+            pii: true
+            links: ['https://example.com/PII-42']
+    - id: span.login
+      type: span
+      brief: 'Login span'
+      attributes:
+        - ref: user.email",
+            )
+            .expect("Failed to load semconv spec");
+
+        let mut attr_catalog = AttributeCatalog::default();
+        let registry = resolve_semconv_registry(&mut attr_catalog, "https://127.0.0.1", &sc_specs)
+            .expect("Failed to resolve registry");
+        let group = |id: &str| {
+            registry
+                .groups
+                .iter()
+                .find(|g| g.id == id)
+                .expect("group not found")
+        };
+        assert_eq!(
+            group("registry.user").annotations["owner"],
+            serde_yaml::Value::from("identity-team")
+        );
+        assert!(group("span.login").annotations.is_empty());
+
+        // The annotations of the attribute are kept by the references.
+        let attributes = attr_catalog.drain_attributes();
+        assert_eq!(attributes.len(), 1);
+        let annotations = &attributes[0].annotations;
+        assert_eq!(annotations["pii"], serde_yaml::Value::Bool(true));
+        assert_eq!(
+            annotations["links"],
+            serde_yaml::Value::Sequence(vec!["https://example.com/PII-42".into()])
+        );
+    }
+
+    #[test]
+    fn test_sampling_relevant_propagation() {
+        let mut sc_specs = SemConvRegistry::new("default");
+        sc_specs
+            .add_semconv_spec_from_string(
+                "<str>",
+                "
+groups:
+    - id: registry.http
+      type: attribute_group
+      brief: 'HTTP attributes'
+      attributes:
+        - id: http.request.method
+          type: string
+          brief: 'HTTP request method'
+          examples: ['GET']
+          requirement_level: required
+          sampling_relevant: true
+    - id: span.http.client
+      type: span
+      brief: 'HTTP client span'
+      attributes:
+        - ref: http.request.method
+    - id: span.http.client.retry
+      type: span
+      brief: 'HTTP client retry span'
+      extends: span.http.client
+    - id: span.http.server
+      type: span
+      brief: 'HTTP server span'
+      attributes:
+        - ref: http.request.method
+          sampling_relevant: false",
+            )
+            .expect("Failed to load semconv spec");
+
+        let mut attr_catalog = AttributeCatalog::default();
+        let registry = resolve_semconv_registry(&mut attr_catalog, "https://127.0.0.1", &sc_specs)
+            .expect("Failed to resolve registry");
+        let attributes = attr_catalog.drain_attributes();
+        let sampling_relevant = |id: &str| {
+            let group = registry
+                .groups
+                .iter()
+                .find(|g| g.id == id)
+                .expect("group not found");
+            assert_eq!(group.attributes.len(), 1);
+            attributes[group.attributes[0].0 as usize].sampling_relevant
+        };
+
+        // The flag is kept by the references and inherited through `extends`,
+        // unless a reference overrides it.
+        assert_eq!(sampling_relevant("registry.http"), Some(true));
+        assert_eq!(sampling_relevant("span.http.client"), Some(true));
+        assert_eq!(sampling_relevant("span.http.client.retry"), Some(true));
+        assert_eq!(sampling_relevant("span.http.server"), Some(false));
+    }
+
+    #[test]
+    fn test_attribute_index() {
+        let mut registry = SemConvRegistry::new("default");
+        registry
+            .add_semconv_spec_from_string(
+                "<str>",
+                "
+groups:
+    - id: registry.http
+      type: attribute_group
+      brief: 'HTTP attributes'
+      attributes:
+        - id: http.request.method
+          type: string
+          brief: 'HTTP request method'
+          examples: ['GET']
+        - id: http.route
+          type: string
+          brief: 'HTTP route'
+          examples: ['/users/:id']
+    - id: span.http.server
+      type: span
+      brief: 'HTTP server span'
+      attributes:
+        - ref: http.request.method
+          requirement_level: required
+    - id: span.http.server.retry
+      type: span
+      brief: 'HTTP server retry span'
+      extends: span.http.server
+    - id: metric.http.server.duration
+      type: metric
+      metric_name: http.server.duration
+      instrument: histogram
+      unit: s
+      brief: 'HTTP server duration'
+      attributes:
+        - ref: http.request.method
+          requirement_level: opt_in",
+            )
+            .expect("Failed to load semconv spec");
+        let schema = SchemaResolver::resolve_semantic_convention_registry(&mut registry)
+            .expect("Failed to resolve registry");
+
+        let usages: Vec<_> = schema.attribute_index["http.request.method"]
+            .iter()
+            .map(|usage| {
+                (
+                    usage.group_id.as_str(),
+                    usage.group_type.clone(),
+                    usage.requirement_level.clone(),
+                )
+            })
+            .collect();
+        let level = |level| RequirementLevel::Basic(level);
+        assert_eq!(
+            usages,
+            vec![
+                (
+                    "metric.http.server.duration",
+                    GroupType::Metric,
+                    level(BasicRequirementLevelSpec::OptIn)
+                ),
+                (
+                    "registry.http",
+                    GroupType::AttributeGroup,
+                    level(BasicRequirementLevelSpec::Recommended)
+                ),
+                (
+                    "span.http.server",
+                    GroupType::Span,
+                    level(BasicRequirementLevelSpec::Required)
+                ),
+                (
+                    "span.http.server.retry",
+                    GroupType::Span,
+                    level(BasicRequirementLevelSpec::Required)
+                ),
+            ]
+        );
+        assert_eq!(schema.attribute_index["http.route"].len(), 1);
+        assert_eq!(schema.attribute_index.len(), 2);
+    }
+
+    #[test]
+    fn test_resolution_limits() {
+        // The groups are declared in reverse order so that each iteration of
+        // the resolution of the `extends` clauses resolves one level.
+        let mut sc_specs = SemConvRegistry::new("default");
+        sc_specs
+            .add_semconv_spec_from_string(
+                "<str>",
+                "
+groups:
+    - id: span.d
+      type: span
+      brief: 'Span D'
+      extends: span.c
+    - id: span.c
+      type: span
+      brief: 'Span C'
+      extends: span.b
+    - id: span.b
+      type: span
+      brief: 'Span B'
+      extends: registry.a
+    - id: registry.a
+      type: attribute_group
+      brief: 'Attributes A'
+      attributes:
+        - id: a.name
+          type: string
+          brief: 'A name'
+          examples: ['a']",
+            )
+            .expect("Failed to load semconv spec");
+        let resolve = |options: ResolverOptions| {
+            resolve_semconv_registry_with_options(
+                &mut AttributeCatalog::default(),
+                "https://127.0.0.1",
+                &sc_specs,
+                &options,
+                &CancellationToken::default(),
+            )
+        };
+
+        match resolve(ResolverOptions {
+            max_extends_depth: Some(2),
+            ..ResolverOptions::default()
+        }) {
+            Err(crate::Error::MaxExtendsDepthExceeded { max_depth, chain }) => {
+                assert_eq!(max_depth, 2);
+                assert_eq!(chain, vec!["span.d", "span.c", "span.b", "registry.a"]);
+            }
+            _ => panic!("Expected a MaxExtendsDepthExceeded error"),
+        }
+
+        match resolve(ResolverOptions {
+            max_iterations: Some(2),
+            ..ResolverOptions::default()
+        }) {
+            Err(crate::Error::MaxIterationsExceeded {
+                max_iterations,
+                chain,
+                ..
+            }) => {
+                assert_eq!(max_iterations, 2);
+                // The chain still unresolved after 2 iterations.
+                assert_eq!(chain, vec!["span.d", "span.c"]);
+            }
+            _ => panic!("Expected a MaxIterationsExceeded error"),
+        }
+
+        assert!(resolve(ResolverOptions {
+            max_extends_depth: Some(3),
+            max_iterations: Some(3),
+            ..ResolverOptions::default()
+        })
+        .is_ok());
+    }
+
+    #[test]
+    fn test_deepest_chain() {
+        let references = HashMap::from([
+            ("a", vec!["b", "x"]),
+            ("b", vec!["c"]),
+            ("c", vec!["a", "d"]),
+            ("x", vec!["y"]),
+        ]);
+        // The cycle `a -> b -> c -> a` is cut.
+        assert_eq!(deepest_chain(&references), vec!["a", "b", "c", "d"]);
+        assert!(deepest_chain(&HashMap::new()).is_empty());
+    }
+
+    #[test]
+    fn test_deprecated_refs() {
+        let mut sc_specs = SemConvRegistry::new("default");
+        sc_specs
+            .add_semconv_spec_from_string(
+                "<str>",
+                "
+groups:
+    - id: registry.http
+      type: attribute_group
+      brief: 'HTTP attributes'
+      prefix: http
+      attributes:
+        - id: method
+          type: string
+          brief: 'HTTP method'
+          examples: ['GET']
+          deprecated: 'Replaced by `http.request.method`.'
+        - id: request.method
+          type: string
+          brief: 'HTTP request method'
+          examples: ['GET']
+    - id: http.common
+      type: attribute_group
+      brief: 'Common HTTP attributes'
+      deprecated: 'No longer used.'
+      attributes:
+        - ref: http.request.method
+    - id: http.client
+      type: span
+      brief: 'HTTP client span'
+      extends: http.common
+      attributes:
+        - ref: http.method
+    - id: http.server
+      type: span
+      brief: 'HTTP server span'
+      attributes:
+        - ref: http.method
+          deprecated: 'Use `http.request.method`.'
+      constraints:
+        - include: http.common
+    - id: http.legacy
+      type: span
+      brief: 'Legacy HTTP span'
+      deprecated: 'Removed.'
+      attributes:
+        - ref: http.method",
+            )
+            .expect("Failed to load semconv spec");
+
+        let mut attr_catalog = AttributeCatalog::default();
+        let (_, warnings) = resolve_semconv_registry_with_options(
+            &mut attr_catalog,
+            "https://127.0.0.1",
+            &sc_specs,
+            &ResolverOptions::default(),
+            &CancellationToken::default(),
+        )
+        .expect("Failed to resolve registry");
+
+        // The deprecated group and the references deprecating the attribute
+        // themselves are not reported.
+        let mut refs: Vec<_> = warnings
+            .iter()
+            .map(|warning| match warning {
+                crate::Error::DeprecatedRef {
+                    group_id,
+                    item,
+                    provenance,
+                } => {
+                    assert_eq!(provenance, "<str>");
+                    assert_eq!(warning.severity(), Some(miette::Severity::Warning));
+                    (
+                        group_id.as_str(),
+                        item.kind,
+                        item.id.as_str(),
+                        item.replacement.as_deref(),
+                    )
+                }
+                _ => panic!("Unexpected warning: {:?}", warning),
+            })
+            .collect();
+        refs.sort();
+        assert_eq!(
+            refs,
+            vec![
+                (
+                    "http.client",
+                    DeprecatedItemKind::Attribute,
+                    "http.method",
+                    Some("http.request.method")
+                ),
+                (
+                    "http.client",
+                    DeprecatedItemKind::Group,
+                    "http.common",
+                    None
+                ),
+                (
+                    "http.server",
+                    DeprecatedItemKind::Group,
+                    "http.common",
+                    None
+                ),
+            ]
+        );
+
+        // The references to deprecated items fail the resolution if requested.
+        let options = ResolverOptions {
+            fail_on_deprecated_ref: true,
+            ..ResolverOptions::default()
+        };
+        let mut attr_catalog = AttributeCatalog::default();
+        let result = resolve_semconv_registry_with_options(
+            &mut attr_catalog,
+            "https://127.0.0.1",
+            &sc_specs,
+            &options,
+            &CancellationToken::default(),
+        );
+        match result {
+            Err(crate::Error::CompoundError(errors)) => {
+                assert_eq!(errors.len(), 3);
+                assert!(errors
+                    .iter()
+                    .all(|e| matches!(e, crate::Error::DeprecatedRefNotAllowed { .. })));
+            }
+            _ => panic!("The resolution should fail"),
+        }
+    }
+
+    #[test]
+    fn test_registry_error_unresolved_includes() {
+        let result = create_registry_from_string(
+            "
+groups:
+    - id: span.one
+      type: span
+      brief: 'Span one'
+      constraints:
+        - include: 'non.existent.one'
+        - include: 'non.existent.two'
+        - include: 'non.existent.three'",
+        );
+
+        assert!(result.is_err());
+
+        if let crate::Error::CompoundError(errors) = result.unwrap_err() {
+            assert!(errors.len() == 3);
+        } else {
+            panic!("Expected a CompoundError");
+        }
+    }
+
+    #[test]
+    fn test_any_of_constraints_with_prefix_and_aliases() {
+        let mut sc_specs = SemConvRegistry::new("default");
+        sc_specs
+            .add_semconv_spec_from_string(
+                "<str>",
+                "
+groups:
+    - id: registry.http
+      type: attribute_group
+      brief: 'HTTP attributes'
+      prefix: http
+      attributes:
+        - id: request.method
+          type: string
+          brief: 'HTTP request method'
+          examples: ['GET']
+          renamed_from: [http.method]
+        - id: status_code
+          type: int
+          brief: 'HTTP status code'
+          examples: [200]
+          deprecated: 'Replaced by `http.response.status_code`.'
+        - id: response.status_code
+          type: int
+          brief: 'HTTP response status code'
+          examples: [200]
+    - id: http.server
+      type: span
+      brief: 'HTTP server span'
+      prefix: http
+      attributes:
+        - id: route
+          type: string
+          brief: 'The matched route'
+          examples: ['/users/:id']
+        - ref: http.request.method
+        - ref: http.response.status_code
+      constraints:
+        - any_of:
+            - route
+            - http.method
+            - http.status_code",
+            )
+            .expect("Failed to load semconv spec");
+
+        let mut attr_catalog = AttributeCatalog::default();
+        let (_, warnings) = resolve_semconv_registry_with_options(
+            &mut attr_catalog,
+            "https://127.0.0.1",
+            &sc_specs,
+            &ResolverOptions::default(),
+            &CancellationToken::default(),
+        )
+        .expect("Failed to resolve registry");
+
+        // The short name is qualified with the group prefix, and the legacy
+        // names are reported.
+        let mut aliases: Vec<_> = warnings
+            .iter()
+            .map(|warning| match warning {
+                crate::Error::AnyOfConstraintAlias {
+                    group_id,
+                    alias,
+                    attribute,
+                    provenance,
+                } => {
+                    assert_eq!(group_id, "http.server");
+                    assert_eq!(provenance, "<str>");
+                    assert_eq!(warning.severity(), Some(miette::Severity::Warning));
+                    (alias.as_str(), attribute.as_str())
+                }
+                _ => panic!("Unexpected warning: {:?}", warning),
+            })
+            .collect();
+        aliases.sort();
+        assert_eq!(
+            aliases,
+            vec![
+                ("http.method", "http.request.method"),
+                ("http.status_code", "http.response.status_code"),
+            ]
+        );
+    }
+
+    /// Test the validation of the `any_of` constraints in a group.
+    #[test]
+    fn test_check_group_any_of_constraints() -> Result<(), crate::Error> {
+        let aliases = HashMap::new();
+
+        // No attribute and no constraint.
         let group_attr_names = HashSet::new();
         let constraints = vec![];
-        check_group_any_of_constraints("group", group_attr_names, &constraints)?;
+        _ = check_group_any_of_constraints("group", "", group_attr_names, &constraints, &aliases)?;
 
         // Attributes and no constraint.
         let group_attr_names = vec!["attr1".to_owned(), "attr2".to_owned()]
             .into_iter()
             .collect();
         let constraints = vec![];
-        check_group_any_of_constraints("group", group_attr_names, &constraints)?;
+        _ = check_group_any_of_constraints("group", "", group_attr_names, &constraints, &aliases)?;
 
         // Attributes and multiple constraints (all satisfiable).
         let group_attr_names = vec!["attr1".to_owned(), "attr2".to_owned(), "attr3".to_owned()]
@@ -901,7 +2764,7 @@ groups:
                 include: None,
             },
         ];
-        check_group_any_of_constraints("group", group_attr_names, &constraints)?;
+        _ = check_group_any_of_constraints("group", "", group_attr_names, &constraints, &aliases)?;
 
         // Attributes and multiple constraints (one unsatisfiable).
         let group_attr_names = vec!["attr1".to_owned(), "attr2".to_owned(), "attr3".to_owned()]
@@ -917,12 +2780,78 @@ groups:
                 include: None,
             },
         ];
-        let result = check_group_any_of_constraints("group", group_attr_names, &constraints);
+        let result =
+            check_group_any_of_constraints("group", "", group_attr_names, &constraints, &aliases);
         assert!(result.is_err());
 
         Ok(())
     }
 
+    /// Test the resolution of the entries of the `any_of` constraints through
+    /// the group prefix and the legacy names of the attributes.
+    #[test]
+    fn test_check_group_any_of_constraints_with_prefix_and_aliases() -> Result<(), crate::Error> {
+        let group_attr_names: HashSet<String> =
+            vec!["http.request.method".to_owned(), "http.route".to_owned()]
+                .into_iter()
+                .collect();
+        let aliases: HashMap<String, String> = [
+            ("http.method", "http.request.method"),
+            ("http.verb", "http.method"),
+            ("loop.a", "loop.b"),
+            ("loop.b", "loop.a"),
+        ]
+        .into_iter()
+        .map(|(alias, name)| (alias.to_owned(), name.to_owned()))
+        .collect();
+        let constraint = |any_of: &[&str]| {
+            vec![Constraint {
+                any_of: any_of.iter().map(|name| (*name).to_owned()).collect(),
+                include: None,
+            }]
+        };
+
+        // Short names are qualified with the group prefix, without warning.
+        let aliases_used = check_group_any_of_constraints(
+            "group",
+            "http",
+            group_attr_names.clone(),
+            &constraint(&["route", "http.request.method"]),
+            &aliases,
+        )?;
+        assert!(aliases_used.is_empty());
+
+        // Legacy names (and successive renamings) are reported.
+        let aliases_used = check_group_any_of_constraints(
+            "group",
+            "",
+            group_attr_names.clone(),
+            &constraint(&["http.method", "http.verb"]),
+            &aliases,
+        )?;
+        assert_eq!(
+            aliases_used,
+            vec![
+                ("http.method".to_owned(), "http.request.method".to_owned()),
+                ("http.verb".to_owned(), "http.request.method".to_owned()),
+            ]
+        );
+
+        // Short names without group prefix and cycles of aliases are missing.
+        for any_of in [&["route"], &["loop.a"]] {
+            let result = check_group_any_of_constraints(
+                "group",
+                "",
+                group_attr_names.clone(),
+                &constraint(any_of),
+                &aliases,
+            );
+            assert!(result.is_err());
+        }
+
+        Ok(())
+    }
+
     #[test]
     fn test_api_usage() -> Result<(), Box<dyn Error>> {
         let registry_id = "local";
@@ -969,6 +2898,298 @@ groups:
         Ok(())
     }
 
+    #[test]
+    fn test_attribute_reuse_stats() -> Result<(), Box<dyn Error>> {
+        let mut semconv_registry = SemConvRegistry::new("local");
+        semconv_registry.add_semconv_spec_from_string(
+            "<str>",
+            r#"groups:
+  - id: registry.http
+    type: attribute_group
+    brief: HTTP attributes.
+    attributes:
+      - id: http.request.method
+        type: string
+        brief: HTTP request method.
+        examples: [GET]
+      - id: http.route
+        type: string
+        brief: The matched route.
+        examples: ['/users/:id']
+  - id: span.http.client
+    type: span
+    span_kind: client
+    brief: HTTP client span.
+    attributes:
+      - ref: http.request.method
+  - id: span.http.server
+    type: span
+    span_kind: server
+    brief: HTTP server span.
+    attributes:
+      - ref: http.request.method
+        brief: The method of the received request.
+"#,
+        )?;
+        let resolved_schema =
+            SchemaResolver::resolve_semantic_convention_registry(&mut semconv_registry)?;
+        let stats = resolved_schema.stats();
+        assert_eq!(
+            stats.attribute_reuse.into_iter().collect::<Vec<_>>(),
+            vec![
+                ("http.request.method".to_owned(), 2),
+                ("http.route".to_owned(), 0)
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_string_interning() -> Result<(), Box<dyn Error>> {
+        let mut semconv_registry = SemConvRegistry::new("local");
+        semconv_registry.add_semconv_spec_from_string(
+            "<str>",
+            r#"groups:
+  - id: registry.http
+    type: attribute_group
+    prefix: http
+    brief: HTTP attributes.
+    attributes:
+      - id: request.method
+        type: string
+        brief: HTTP request method.
+        examples: [GET]
+  - id: span.http.server
+    type: span
+    prefix: http
+    span_kind: server
+    brief: HTTP server span.
+    attributes:
+      - ref: http.request.method
+        note: The method of the received request.
+"#,
+        )?;
+        let resolved_schema =
+            SchemaResolver::resolve_semantic_convention_registry(&mut semconv_registry)?;
+
+        // The attribute definition and its override share their name and brief.
+        let attributes = &resolved_schema.catalog().attributes;
+        assert_eq!(attributes.len(), 2);
+        assert!(attributes[0].name.ptr_eq(&attributes[1].name));
+        assert!(attributes[0].brief.ptr_eq(&attributes[1].brief));
+
+        // The groups share their prefix.
+        let groups = &resolved_schema.registry("local").unwrap().groups;
+        assert!(groups[0].prefix.ptr_eq(&groups[1].prefix));
+
+        // Interned strings are serialized as plain strings.
+        let json = serde_json::to_value(&attributes[1])?;
+        assert_eq!(json["name"], "http.request.method");
+        assert_eq!(json["note"], "The method of the received request.");
+        Ok(())
+    }
+
+    #[test]
+    fn test_semantic_eq() -> Result<(), Box<dyn Error>> {
+        let mut specs = glob("data/registry-test-8-http/registry/*.yaml")?
+            .map(|path| Ok(SemConvRegistry::semconv_spec_from_file(path?)?))
+            .collect::<Result<Vec<_>, Box<dyn Error>>>()?;
+        let schema = SchemaResolver::resolve_semantic_convention_registry(
+            &mut SemConvRegistry::from_semconv_specs("default", specs.clone()),
+        )?;
+
+        // The files loaded in the reverse order are resolved into a catalog and
+        // groups in a different order.
+        specs.reverse();
+        let reversed_schema = SchemaResolver::resolve_semantic_convention_registry(
+            &mut SemConvRegistry::from_semconv_specs("default", specs.clone()),
+        )?;
+        let registry = schema.registry("default").expect("Registry not found");
+        let reversed_registry = reversed_schema
+            .registry("default")
+            .expect("Registry not found");
+        assert_ne!(registry, reversed_registry);
+        assert!(schema.semantic_eq(&reversed_schema));
+
+        // With the same catalog, the order of the groups and of their
+        // attributes, and the lineage are ignored.
+        let mut shuffled = registry.clone();
+        shuffled.groups.reverse();
+        for group in shuffled.groups.iter_mut() {
+            group.attributes.reverse();
+            group.lineage = None;
+        }
+        assert_ne!(registry, &shuffled);
+        assert!(registry.semantic_eq(&shuffled));
+        shuffled.normalize();
+        let mut normalized = registry.clone();
+        normalized.normalize();
+        assert_eq!(normalized, shuffled);
+
+        // A changed attribute is detected.
+        let mut changed_schema = SchemaResolver::resolve_semantic_convention_registry(
+            &mut SemConvRegistry::from_semconv_specs("default", specs),
+        )?;
+        let attr = changed_schema
+            .catalog
+            .attributes
+            .iter_mut()
+            .find(|attr| attr.name == "network.protocol.name")
+            .expect("Attribute not found");
+        attr.brief = "Changed brief.".into();
+        assert!(!schema.semantic_eq(&changed_schema));
+        Ok(())
+    }
+
+    #[test]
+    fn test_cancelled_resolution() -> Result<(), Box<dyn Error>> {
+        let mut semconv_registry = SemConvRegistry::try_from_path_pattern(
+            "local",
+            "data/registry-test-7-spans/registry/*.yaml",
+        )?;
+
+        // A cancelled token stops the resolution.
+        let cancellation = CancellationToken::new();
+        cancellation.cancel();
+        let result = SchemaResolver::resolve_semantic_convention_registry_with_cancellation(
+            &mut semconv_registry,
+            &cancellation,
+        );
+        assert!(matches!(result, Err(crate::Error::Cancelled)));
+
+        // So does an expired timeout.
+        let cancellation = CancellationToken::new().with_timeout(Duration::ZERO);
+        let result = SchemaResolver::resolve_semantic_convention_registry_with_cancellation(
+            &mut semconv_registry,
+            &cancellation,
+        );
+        assert!(matches!(result, Err(crate::Error::Cancelled)));
+
+        // The resolution completes before a distant deadline.
+        let cancellation = CancellationToken::new().with_timeout(Duration::from_secs(600));
+        _ = SchemaResolver::resolve_semantic_convention_registry_with_cancellation(
+            &mut semconv_registry,
+            &cancellation,
+        )?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_resolve_group() -> Result<(), Box<dyn Error>> {
+        let mut semconv_registry = SemConvRegistry::try_from_path_pattern(
+            "local",
+            "data/registry-test-8-http/registry/*.yaml",
+        )?;
+        let group_ids: Vec<_> = semconv_registry
+            .group_with_provenance_iter()
+            .map(|(group, _)| group.id.clone())
+            .collect();
+        let full_schema =
+            SchemaResolver::resolve_semantic_convention_registry(&mut semconv_registry)?;
+        let full_registry = full_schema.registry("local").expect("registry not found");
+
+        // Each group resolved on demand is identical to the group resolved
+        // with the whole registry.
+        for group_id in &group_ids {
+            let schema = SchemaResolver::resolve_group(&semconv_registry, group_id)?;
+            let registry = schema.registry("local").expect("registry not found");
+            assert_eq!(registry.groups.len(), 1);
+            let group = &registry.groups[0];
+            let expected = full_registry
+                .groups
+                .iter()
+                .find(|g| &g.id == group_id)
+                .expect("group not found");
+            assert_eq!(group.lineage, expected.lineage);
+            let attributes =
+                |schema: &weaver_resolved_schema::ResolvedTelemetrySchema,
+                 group: &weaver_resolved_schema::registry::Group| {
+                    let mut attributes: Vec<_> = group
+                        .attributes
+                        .iter()
+                        .map(|attr_ref| schema.catalog().attribute(attr_ref).cloned())
+                        .collect();
+                    attributes.sort_by_key(|attr| attr.as_ref().map(|attr| attr.name.clone()));
+                    attributes
+                };
+            assert_eq!(
+                attributes(&schema, group),
+                attributes(&full_schema, expected),
+                "Group `{}` differs",
+                group_id
+            );
+        }
+
+        // Only the closure of the group is resolved.
+        let schema = SchemaResolver::resolve_group(&semconv_registry, "server")?;
+        assert!(schema.catalog().attributes.len() < full_schema.catalog().attributes.len());
+
+        let result = SchemaResolver::resolve_group(&semconv_registry, "unknown.group");
+        assert!(matches!(result, Err(crate::Error::GroupNotFound { .. })));
+        Ok(())
+    }
+
+    #[test]
+    fn test_namespace_stats() -> Result<(), Box<dyn Error>> {
+        let mut semconv_registry = SemConvRegistry::new("local");
+        semconv_registry.add_semconv_spec_from_string(
+            "<str>",
+            r#"groups:
+  - id: registry.http
+    type: attribute_group
+    brief: HTTP attributes.
+    attributes:
+      - id: http.request.method
+        type: string
+        stability: stable
+        brief: HTTP request method.
+        examples: [GET]
+      - id: http.method
+        type: string
+        brief: Deprecated.
+        examples: [GET]
+        deprecated: Replaced by `http.request.method`.
+  - id: registry.url
+    type: attribute_group
+    brief: URL attributes.
+    attributes:
+      - id: url.full
+        type: string
+        brief: Full URL.
+        examples: ['https://example.com']
+  - id: span.http.client
+    type: span
+    span_kind: client
+    brief: HTTP client span.
+    attributes:
+      - ref: http.request.method
+        brief: The method of the sent request.
+      - ref: url.full
+"#,
+        )?;
+        let resolved_schema =
+            SchemaResolver::resolve_semantic_convention_registry(&mut semconv_registry)?;
+        let stats = resolved_schema.stats();
+        assert_eq!(
+            stats.namespace_stats.keys().collect::<Vec<_>>(),
+            vec!["http", "url"]
+        );
+
+        let http = &stats.namespace_stats["http"];
+        assert_eq!(http.group_breakdown[&GroupType::AttributeGroup], 1);
+        assert_eq!(http.group_breakdown[&GroupType::Span], 1);
+        assert_eq!(http.attribute_count, 2);
+        assert_eq!(http.stable_attribute_count, 1);
+        assert_eq!(http.deprecated_attribute_count, 1);
+        assert_eq!(http.average_brief_length(), (20 + 11) / 2);
+
+        let url = &stats.namespace_stats["url"];
+        assert_eq!(url.group_breakdown.len(), 1);
+        assert_eq!(url.attribute_count, 1);
+        assert_eq!(url.average_brief_length(), 9);
+        Ok(())
+    }
+
     fn to_json<T: Serialize + ?Sized>(value: &T) -> String {
         serde_json::to_string_pretty(value).unwrap()
     }
@@ -0,0 +1,275 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Application telemetry schema, i.e. the declaration of the resources,
+//! instrumentation scopes, spans, metrics, and events emitted by an
+//! application, expressed as references to the groups of a semantic
+//! convention registry.
+
+use std::fs::File;
+use std::io::BufReader;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use weaver_common::error::handle_errors;
+use weaver_forge::registry::{ResolvedGroup, ResolvedRegistry};
+use weaver_resolved_schema::attribute::Attribute;
+use weaver_semconv::group::GroupType;
+
+use crate::Error;
+
+/// An application telemetry schema referencing the groups of a registry.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct AppSchema {
+    /// The schema URL of the telemetry emitted by the application.
+    pub schema_url: String,
+    /// The resource of the application.
+    #[serde(default)]
+    pub resource: AppResource,
+    /// The instrumentation scopes of the application.
+    #[serde(default)]
+    pub instrumentation_scopes: Vec<AppInstrumentationScope>,
+}
+
+/// The resource of an application.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct AppResource {
+    /// The ids of the resource groups describing the resource.
+    #[serde(default)]
+    pub groups: Vec<String>,
+}
+
+/// An instrumentation scope of an application.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct AppInstrumentationScope {
+    /// The name of the instrumentation scope.
+    pub name: String,
+    /// The version of the instrumentation scope.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    /// The ids of the span groups of the spans emitted by the scope.
+    #[serde(default)]
+    pub spans: Vec<String>,
+    /// The ids of the metric groups of the metrics emitted by the scope.
+    #[serde(default)]
+    pub metrics: Vec<String>,
+    /// The ids of the event groups of the events emitted by the scope.
+    #[serde(default)]
+    pub events: Vec<String>,
+}
+
+/// A self-contained application telemetry schema in which the references to
+/// the registry groups have been replaced by the resolved groups.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct ResolvedAppSchema {
+    /// The schema URL of the telemetry emitted by the application.
+    pub schema_url: String,
+    /// The URL of the registry the schema has been resolved against.
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub registry_url: String,
+    /// The resource of the application.
+    pub resource: ResolvedAppResource,
+    /// The instrumentation scopes of the application.
+    pub instrumentation_scopes: Vec<ResolvedAppInstrumentationScope>,
+}
+
+/// The resolved resource of an application.
+#[derive(Serialize, Debug, Clone, Default, PartialEq)]
+pub struct ResolvedAppResource {
+    /// The attributes of the resource groups (without duplicates).
+    pub attributes: Vec<Attribute>,
+}
+
+/// A resolved instrumentation scope of an application.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct ResolvedAppInstrumentationScope {
+    /// The name of the instrumentation scope.
+    pub name: String,
+    /// The version of the instrumentation scope.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    /// The span groups of the spans emitted by the scope.
+    pub spans: Vec<ResolvedGroup>,
+    /// The metric groups of the metrics emitted by the scope.
+    pub metrics: Vec<ResolvedGroup>,
+    /// The event groups of the events emitted by the scope.
+    pub events: Vec<ResolvedGroup>,
+}
+
+impl AppSchema {
+    /// Loads an application schema from a YAML file.
+    pub fn load_from_file<P: AsRef<Path>>(path: P) -> Result<AppSchema, Error> {
+        let path = path.as_ref();
+        let file = File::open(path).map_err(|e| Error::AppSchemaNotFound {
+            path: path.to_path_buf(),
+            error: e.to_string(),
+        })?;
+        serde_yaml::from_reader(BufReader::new(file)).map_err(|e| Error::InvalidAppSchema {
+            path: path.to_path_buf(),
+            error: e.to_string(),
+        })
+    }
+
+    /// Resolves the application schema against a registry. All the references
+    /// to groups that are not defined in the registry, or that are not of the
+    /// expected type, are reported.
+    pub fn resolve(&self, registry: &ResolvedRegistry) -> Result<ResolvedAppSchema, Error> {
+        let mut errors = vec![];
+        let mut resolve_groups = |group_ids: &[String], group_type: GroupType| {
+            group_ids
+                .iter()
+                .filter_map(|group_id| {
+                    let Some(group) = registry.groups.iter().find(|group| &group.id == group_id)
+                    else {
+                        errors.push(Error::UnknownGroup {
+                            signal: group_type_name(&group_type).to_owned(),
+                            group_id: group_id.clone(),
+                        });
+                        return None;
+                    };
+                    if group.r#type != group_type {
+                        errors.push(Error::InvalidGroupType {
+                            signal: group_type_name(&group_type).to_owned(),
+                            group_id: group_id.clone(),
+                            group_type: group_type_name(&group.r#type).to_owned(),
+                        });
+                        return None;
+                    }
+                    Some(group.clone())
+                })
+                .collect::<Vec<_>>()
+        };
+
+        let mut resource = ResolvedAppResource::default();
+        for group in resolve_groups(&self.resource.groups, GroupType::Resource) {
+            for attr in group.attributes {
+                if !resource.attributes.iter().any(|a| a.name == attr.name) {
+                    resource.attributes.push(attr);
+                }
+            }
+        }
+
+        let instrumentation_scopes = self
+            .instrumentation_scopes
+            .iter()
+            .map(|scope| ResolvedAppInstrumentationScope {
+                name: scope.name.clone(),
+                version: scope.version.clone(),
+                spans: resolve_groups(&scope.spans, GroupType::Span),
+                metrics: resolve_groups(&scope.metrics, GroupType::Metric),
+                events: resolve_groups(&scope.events, GroupType::Event),
+            })
+            .collect();
+
+        handle_errors(errors)?;
+        Ok(ResolvedAppSchema {
+            schema_url: self.schema_url.clone(),
+            registry_url: registry.registry_url.clone(),
+            resource,
+            instrumentation_scopes,
+        })
+    }
+}
+
+/// Returns the name of a group type as used in the semantic convention files.
+fn group_type_name(group_type: &GroupType) -> &'static str {
+    match group_type {
+        GroupType::AttributeGroup => "attribute_group",
+        GroupType::Span => "span",
+        GroupType::Event => "event",
+        GroupType::Metric => "metric",
+        GroupType::MetricGroup => "metric_group",
+        GroupType::Resource => "resource",
+        GroupType::Scope => "scope",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use weaver_resolver::SchemaResolver;
+    use weaver_semconv::registry::SemConvRegistry;
+
+    use super::*;
+
+    #[test]
+    fn test_resolve_app_schema() {
+        let registry_id = "test";
+        let mut registry =
+            SemConvRegistry::try_from_path_pattern(registry_id, "data/app/registry/*.yaml")
+                .expect("Failed to load the registry");
+        let schema = SchemaResolver::resolve_semantic_convention_registry(&mut registry)
+            .expect("Failed to resolve the registry");
+        let registry = ResolvedRegistry::try_from_resolved_registry(
+            schema
+                .registry(registry_id)
+                .expect("Failed to get the registry from the resolved schema"),
+            schema.catalog(),
+        )
+        .expect("Failed to create the resolved registry");
+
+        let app_schema =
+            AppSchema::load_from_file("data/app/app.yaml").expect("Failed to load the app schema");
+        let resolved = app_schema
+            .resolve(&registry)
+            .expect("Failed to resolve the app schema");
+        assert_eq!(resolved.schema_url, "https://example.com/schemas/1.0.0");
+        let resource_attributes: Vec<_> = resolved
+            .resource
+            .attributes
+            .iter()
+            .map(|attr| attr.name.as_str())
+            .collect();
+        assert_eq!(
+            resource_attributes,
+            vec!["service.name", "service.version", "host.name"]
+        );
+        assert_eq!(resolved.instrumentation_scopes.len(), 1);
+        let scope = &resolved.instrumentation_scopes[0];
+        assert_eq!(scope.name, "acme.http");
+        assert_eq!(scope.version.as_deref(), Some("1.2.0"));
+        assert_eq!(scope.spans[0].id, "span.http.server");
+        assert_eq!(scope.spans[0].attributes.len(), 2);
+        assert_eq!(scope.metrics[0].id, "metric.http.server.request.duration");
+        assert_eq!(scope.events[0].id, "event.http.request.received");
+
+        // All the invalid references are reported.
+        let invalid_app_schema = AppSchema {
+            schema_url: "https://example.com/schemas/1.0.0".to_owned(),
+            resource: AppResource {
+                groups: vec!["resource.unknown".to_owned()],
+            },
+            instrumentation_scopes: vec![AppInstrumentationScope {
+                name: "acme.http".to_owned(),
+                version: None,
+                spans: vec!["metric.http.server.request.duration".to_owned()],
+                metrics: vec![],
+                events: vec![],
+            }],
+        };
+        let Err(Error::CompoundError(errors)) = invalid_app_schema.resolve(&registry) else {
+            panic!("Expected a compound error");
+        };
+        assert_eq!(
+            errors,
+            vec![
+                Error::UnknownGroup {
+                    signal: "resource".to_owned(),
+                    group_id: "resource.unknown".to_owned(),
+                },
+                Error::InvalidGroupType {
+                    signal: "span".to_owned(),
+                    group_id: "metric.http.server.request.duration".to_owned(),
+                    group_type: "metric".to_owned(),
+                },
+            ]
+        );
+
+        assert!(matches!(
+            AppSchema::load_from_file("data/app/unknown.yaml"),
+            Err(Error::AppSchemaNotFound { .. })
+        ));
+    }
+}
@@ -0,0 +1,149 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Computation of the transformations between two versions of a registry.
+
+use std::collections::BTreeMap;
+
+use weaver_forge::registry::ResolvedRegistry;
+use weaver_semconv::group::GroupType;
+use weaver_version::all_change::{AllChange, RenameAttributes};
+use weaver_version::all_version::AllVersion;
+use weaver_version::metrics_change::MetricsChange;
+use weaver_version::metrics_version::MetricsVersion;
+use weaver_version::VersionSpec;
+
+/// Computes the transformations converting telemetry conforming to the
+/// previous version of a registry into telemetry conforming to the current
+/// version.
+///
+/// An attribute (or a metric) is renamed when it is deprecated in the current
+/// version, was not deprecated in the previous version, and its deprecation
+/// note references a non-deprecated attribute (or metric) of the current
+/// version.
+///
+/// An attribute is also renamed when a non-deprecated attribute of the current
+/// version declares it in its `renamed_from` field, and it was not deprecated
+/// in the previous version but is removed or deprecated in the current
+/// version. These declared renames take precedence over the deprecation notes.
+#[must_use]
+pub fn diff_registries(previous: &ResolvedRegistry, current: &ResolvedRegistry) -> VersionSpec {
+    let mut spec = VersionSpec::default();
+
+    let previous_attributes = attributes(previous);
+    let current_attributes = attributes(current);
+    let mut attribute_map = renames(&previous_attributes, &current_attributes);
+    attribute_map.extend(declared_renames(
+        current,
+        &previous_attributes,
+        &current_attributes,
+    ));
+    if !attribute_map.is_empty() {
+        spec.all = Some(AllVersion {
+            changes: vec![AllChange {
+                rename_attributes: RenameAttributes { attribute_map },
+            }],
+        });
+    }
+
+    let rename_metrics = renames(&metrics(previous), &metrics(current));
+    if !rename_metrics.is_empty() {
+        spec.metrics = Some(MetricsVersion {
+            changes: vec![MetricsChange {
+                rename_metrics,
+                ..Default::default()
+            }],
+        });
+    }
+
+    spec
+}
+
+/// Returns the deprecation note (if any) of each attribute of the registry.
+fn attributes(registry: &ResolvedRegistry) -> BTreeMap<&str, Option<&str>> {
+    let mut attributes = BTreeMap::new();
+    for attr in registry
+        .groups
+        .iter()
+        .flat_map(|group| group.attributes.iter())
+    {
+        _ = attributes
+            .entry(attr.name.as_str())
+            .or_insert(attr.deprecated.as_deref());
+    }
+    attributes
+}
+
+/// Returns the old to new names of the attributes declared as renamed (see the
+/// `renamed_from` field) by the non-deprecated attributes of the current
+/// version.
+fn declared_renames(
+    current: &ResolvedRegistry,
+    previous_attributes: &BTreeMap<&str, Option<&str>>,
+    current_attributes: &BTreeMap<&str, Option<&str>>,
+) -> BTreeMap<String, String> {
+    let is_active =
+        |items: &BTreeMap<&str, Option<&str>>, name: &str| matches!(items.get(name), Some(None));
+    let mut renames = BTreeMap::new();
+    for attr in current
+        .groups
+        .iter()
+        .flat_map(|group| group.attributes.iter())
+        .filter(|attr| attr.deprecated.is_none())
+    {
+        for old_name in &attr.renamed_from {
+            if is_active(previous_attributes, old_name) && !is_active(current_attributes, old_name)
+            {
+                _ = renames.insert(old_name.clone(), attr.name.to_string());
+            }
+        }
+    }
+    renames
+}
+
+/// Returns the deprecation note (if any) of each metric of the registry.
+fn metrics(registry: &ResolvedRegistry) -> BTreeMap<&str, Option<&str>> {
+    let mut metrics = BTreeMap::new();
+    for group in registry
+        .groups
+        .iter()
+        .filter(|group| group.r#type == GroupType::Metric)
+    {
+        if let Some(metric_name) = group.metric_name.as_deref() {
+            _ = metrics
+                .entry(metric_name)
+                .or_insert(group.deprecated.as_deref());
+        }
+    }
+    metrics
+}
+
+/// Returns the old to new names of the items deprecated in the current version
+/// in favor of another item.
+fn renames(
+    previous: &BTreeMap<&str, Option<&str>>,
+    current: &BTreeMap<&str, Option<&str>>,
+) -> BTreeMap<String, String> {
+    let is_active =
+        |items: &BTreeMap<&str, Option<&str>>, name: &str| matches!(items.get(name), Some(None));
+    let mut renames = BTreeMap::new();
+    for (name, note) in current {
+        let Some(note) = note else {
+            continue;
+        };
+        if !is_active(previous, name) {
+            // Unknown or already deprecated in the previous version.
+            continue;
+        }
+        // The replacement is the first item of the current version quoted in
+        // the deprecation note, e.g. "Replaced by `network.protocol.version`."
+        let replacement = note
+            .split('`')
+            .skip(1)
+            .step_by(2)
+            .find(|quoted| quoted != name && is_active(current, quoted));
+        if let Some(replacement) = replacement {
+            _ = renames.insert((*name).to_owned(), replacement.to_owned());
+        }
+    }
+    renames
+}
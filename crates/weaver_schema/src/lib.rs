@@ -0,0 +1,349 @@
+// SPDX-License-Identifier: Apache-2.0
+
+#![doc = include_str!("../README.md")]
+
+use std::path::PathBuf;
+
+use miette::Diagnostic;
+use serde::Serialize;
+
+use weaver_common::diagnostic::{DiagnosticMessage, DiagnosticMessages};
+use weaver_common::error::{format_errors, WeaverError};
+use weaver_forge::registry::ResolvedRegistry;
+use weaver_version::{VersionSpec, Versions};
+
+use crate::diff::diff_registries;
+
+pub mod app;
+pub mod diff;
+pub mod migration;
+pub mod ottl;
+
+/// The version of the telemetry schema file format.
+pub const FILE_FORMAT: &str = "1.1.0";
+
+/// An error that can occur while computing a telemetry schema file.
+#[derive(thiserror::Error, Debug, Clone, PartialEq, Serialize, Diagnostic)]
+#[non_exhaustive]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Error {
+    /// The same version has been specified for several registries.
+    #[error("The version '{version}' is specified for several registries")]
+    DuplicateVersion {
+        /// The duplicated version.
+        version: String,
+    },
+
+    /// The telemetry schema file could not be serialized.
+    #[error("Failed to serialize the telemetry schema file, error: {error}")]
+    SerializationFailed {
+        /// The error that occurred.
+        error: String,
+    },
+
+    /// The application schema was not found.
+    #[error("Application schema `{path}` not found, error: {error}")]
+    AppSchemaNotFound {
+        /// The path of the application schema.
+        path: PathBuf,
+        /// The error that occurred.
+        error: String,
+    },
+
+    /// The application schema is invalid.
+    #[error("Invalid application schema `{path}`, error: {error}")]
+    InvalidAppSchema {
+        /// The path of the application schema.
+        path: PathBuf,
+        /// The error that occurred.
+        error: String,
+    },
+
+    /// The application schema references a group not defined in the registry.
+    #[error("The {signal} group '{group_id}' is not defined in the registry")]
+    #[diagnostic(help("The application schema must reference the id of a registry group."))]
+    UnknownGroup {
+        /// The kind of signal the group is referenced for.
+        signal: String,
+        /// The id of the group.
+        group_id: String,
+    },
+
+    /// The application schema references a group of the wrong type.
+    #[error(
+        "The group '{group_id}' is referenced as a {signal} group but is of type '{group_type}'"
+    )]
+    InvalidGroupType {
+        /// The kind of signal the group is referenced for.
+        signal: String,
+        /// The id of the group.
+        group_id: String,
+        /// The actual type of the group.
+        group_type: String,
+    },
+
+    /// The version is not defined in the telemetry schema.
+    #[error("The version '{version}' is not defined in the telemetry schema")]
+    UnknownVersion {
+        /// The unknown version.
+        version: String,
+    },
+
+    /// The OTLP file is invalid.
+    #[error("Invalid OTLP file `{file}`, error: {error}")]
+    InvalidOtlpFile {
+        /// The path of the OTLP file.
+        file: PathBuf,
+        /// The error that occurred.
+        error: String,
+    },
+
+    /// The migrated OTLP file could not be written.
+    #[error("Failed to write the OTLP file `{file}`, error: {error}")]
+    OtlpFileWriteFailed {
+        /// The path of the OTLP file.
+        file: PathBuf,
+        /// The error that occurred.
+        error: String,
+    },
+
+    /// A container for multiple errors.
+    #[error("{:?}", format_errors(.0))]
+    CompoundError(Vec<Error>),
+}
+
+impl WeaverError<Error> for Error {
+    fn compound(errors: Vec<Error>) -> Error {
+        Error::CompoundError(
+            errors
+                .into_iter()
+                .flat_map(|e| match e {
+                    Error::CompoundError(errors) => errors,
+                    e => vec![e],
+                })
+                .collect(),
+        )
+    }
+}
+
+impl From<Error> for DiagnosticMessages {
+    fn from(error: Error) -> Self {
+        DiagnosticMessages::new(match error {
+            Error::CompoundError(errors) => errors
+                .into_iter()
+                .flat_map(|e| {
+                    let diag_msgs: DiagnosticMessages = e.into();
+                    diag_msgs.into_inner()
+                })
+                .collect(),
+            _ => vec![DiagnosticMessage::new(error)],
+        })
+    }
+}
+
+/// A telemetry schema file, i.e. the document published at the schema URL of
+/// a registry.
+#[derive(Serialize, Debug, Clone)]
+pub struct SchemaFile {
+    /// The version of the schema file format.
+    pub file_format: String,
+    /// The schema URL, the last segment of which is the latest version.
+    pub schema_url: String,
+    /// The transformations of each version.
+    pub versions: Versions,
+}
+
+impl SchemaFile {
+    /// Computes the schema file of the given versions of a registry. The
+    /// transformations of each version are computed by diffing its registry
+    /// with the registry of the previous version (the first version has no
+    /// transformations).
+    pub fn from_registries(
+        schema_url: &str,
+        mut registries: Vec<(semver::Version, ResolvedRegistry)>,
+    ) -> Result<SchemaFile, Error> {
+        registries.sort_by(|(v1, _), (v2, _)| v1.cmp(v2));
+
+        let mut versions = Versions::default();
+        for (i, (version, registry)) in registries.iter().enumerate() {
+            let spec = match i.checked_sub(1).map(|prev| &registries[prev]) {
+                Some((previous_version, _)) if previous_version == version => {
+                    return Err(Error::DuplicateVersion {
+                        version: version.to_string(),
+                    });
+                }
+                Some((_, previous)) => diff_registries(previous, registry),
+                None => VersionSpec::default(),
+            };
+            versions.insert(version.clone(), spec);
+        }
+
+        Ok(SchemaFile {
+            file_format: FILE_FORMAT.to_owned(),
+            schema_url: schema_url.to_owned(),
+            versions,
+        })
+    }
+
+    /// Serializes the schema file in YAML.
+    pub fn to_yaml(&self) -> Result<String, Error> {
+        serde_yaml::to_string(self).map_err(|e| Error::SerializationFailed {
+            error: e.to_string(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use weaver_resolver::SchemaResolver;
+    use weaver_semconv::registry::SemConvRegistry;
+
+    use super::*;
+
+    fn resolved_registry(path_pattern: &str) -> ResolvedRegistry {
+        let registry_id = "test";
+        let mut registry = SemConvRegistry::try_from_path_pattern(registry_id, path_pattern)
+            .expect("Failed to load the registry");
+        let schema = SchemaResolver::resolve_semantic_convention_registry(&mut registry)
+            .expect("Failed to resolve the registry");
+        ResolvedRegistry::try_from_resolved_registry(
+            schema
+                .registry(registry_id)
+                .expect("Failed to get the registry from the resolved schema"),
+            schema.catalog(),
+        )
+        .expect("Failed to create the resolved registry")
+    }
+
+    fn resolved_registry_from_string(spec: &str) -> ResolvedRegistry {
+        let registry_id = "test";
+        let mut registry = SemConvRegistry::new(registry_id);
+        registry
+            .add_semconv_spec_from_string("<str>", spec)
+            .expect("Failed to load the registry");
+        let schema = SchemaResolver::resolve_semantic_convention_registry(&mut registry)
+            .expect("Failed to resolve the registry");
+        ResolvedRegistry::try_from_resolved_registry(
+            schema
+                .registry(registry_id)
+                .expect("Failed to get the registry from the resolved schema"),
+            schema.catalog(),
+        )
+        .expect("Failed to create the resolved registry")
+    }
+
+    #[test]
+    fn test_declared_renames() {
+        let previous = resolved_registry_from_string(
+            r#"groups:
+  - id: registry.http
+    type: attribute_group
+    brief: "HTTP attributes."
+    attributes:
+      - id: http.status_code
+        type: int
+        brief: HTTP response status code.
+      - id: http.target
+        type: int
+        brief: HTTP request target.
+"#,
+        );
+        let current = resolved_registry_from_string(
+            r#"groups:
+  - id: registry.http
+    type: attribute_group
+    brief: "HTTP attributes."
+    attributes:
+      - id: http.response.status_code
+        type: int
+        brief: HTTP response status code.
+        renamed_from: [http.status_code]
+      - id: http.target
+        type: int
+        brief: HTTP request target.
+      - id: url.path
+        type: int
+        brief: URL path.
+        renamed_from: [http.target]
+"#,
+        );
+        let spec = diff_registries(&previous, &current);
+        let all = spec.all.expect("No renamed attributes");
+        assert_eq!(
+            all.changes[0].rename_attributes.attribute_map,
+            [(
+                "http.status_code".to_owned(),
+                "http.response.status_code".to_owned()
+            )]
+            .into_iter()
+            .collect()
+        );
+    }
+
+    #[test]
+    fn test_schema_file() {
+        let v1_0 = semver::Version::new(1, 0, 0);
+        let v1_1 = semver::Version::new(1, 1, 0);
+        let schema_file = SchemaFile::from_registries(
+            "https://example.com/schemas/1.1.0",
+            vec![
+                (v1_1.clone(), resolved_registry("data/v1.1.0/*.yaml")),
+                (v1_0.clone(), resolved_registry("data/v1.0.0/*.yaml")),
+            ],
+        )
+        .expect("Failed to compute the schema file");
+
+        let versions = schema_file.versions.versions_asc();
+        assert_eq!(versions.len(), 2);
+        let (_, first) = versions[0];
+        assert!(first.all.is_none() && first.metrics.is_none());
+
+        let changes = schema_file.versions.version_changes_for(
+            schema_file
+                .versions
+                .latest_version()
+                .as_ref()
+                .expect("No versions"),
+        );
+        // Renamed attribute.
+        assert_eq!(
+            changes.get_span_attribute_name("http.method"),
+            "http.request.method"
+        );
+        assert_eq!(
+            changes.get_metric_attribute_name("http.method"),
+            "http.request.method"
+        );
+        // Already deprecated in the previous version.
+        assert_eq!(changes.get_span_attribute_name("http.url"), "http.url");
+        // Deprecated without replacement.
+        assert_eq!(
+            changes.get_span_attribute_name("http.flavor"),
+            "http.flavor"
+        );
+        // Renamed metric.
+        assert_eq!(
+            changes.get_metric_name("http.server.duration"),
+            "http.server.request.duration"
+        );
+
+        let yaml = schema_file.to_yaml().expect("Failed to serialize");
+        assert!(
+            yaml.starts_with("file_format: 1.1.0\nschema_url: https://example.com/schemas/1.1.0\n")
+        );
+
+        assert_eq!(
+            SchemaFile::from_registries(
+                "https://example.com/schemas/1.0.0",
+                vec![
+                    (v1_0.clone(), resolved_registry("data/v1.0.0/*.yaml")),
+                    (v1_0, resolved_registry("data/v1.0.0/*.yaml")),
+                ],
+            )
+            .err(),
+            Some(Error::DuplicateVersion {
+                version: "1.0.0".to_owned()
+            })
+        );
+    }
+}
@@ -0,0 +1,691 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Migration of telemetry from one version of a telemetry schema to another.
+//!
+//! The migration applies the attribute and metric renames of every version
+//! between the two versions, one version at a time, so renames chained across
+//! versions are followed. Migrating to an older version applies the inverse
+//! renames in reverse order.
+//!
+//! OTLP/JSON files (`.json` or `.jsonl`) and OTLP protobuf files (the name of
+//! which must contain `trace`, `metric`, or `log`) are supported.
+
+use std::collections::BTreeMap;
+use std::fmt::Display;
+use std::io::Write;
+use std::path::Path;
+
+use opentelemetry_proto::tonic::collector::logs::v1::ExportLogsServiceRequest;
+use opentelemetry_proto::tonic::collector::metrics::v1::ExportMetricsServiceRequest;
+use opentelemetry_proto::tonic::collector::trace::v1::ExportTraceServiceRequest;
+use opentelemetry_proto::tonic::common::v1::{InstrumentationScope, KeyValue};
+use opentelemetry_proto::tonic::metrics::v1::metric::Data;
+use opentelemetry_proto::tonic::metrics::v1::Metric;
+use opentelemetry_proto::tonic::resource::v1::Resource;
+use prost::Message;
+use serde::Serialize;
+use serde_json::Value;
+
+use weaver_version::{VersionSpec, Versions};
+
+use crate::Error;
+
+/// The renames of a single version of the schema.
+#[derive(Serialize, Debug, Clone, Default, PartialEq)]
+pub struct MigrationStep {
+    /// The version introducing the renames (or whose renames are reverted when
+    /// migrating to an older version).
+    pub version: String,
+    /// Renames of the attributes of all the signals.
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub attributes: BTreeMap<String, String>,
+    /// Renames of the resource attributes.
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub resource_attributes: BTreeMap<String, String>,
+    /// Renames of the span attributes.
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub span_attributes: BTreeMap<String, String>,
+    /// Renames of the log attributes.
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub log_attributes: BTreeMap<String, String>,
+    /// Renames of the metric attributes.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub metric_attributes: Vec<MetricAttributeRenames>,
+    /// Renames of the metrics.
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub metrics: BTreeMap<String, String>,
+}
+
+/// Renames of metric attributes, optionally restricted to some metrics.
+#[derive(Serialize, Debug, Clone, Default, PartialEq)]
+pub struct MetricAttributeRenames {
+    /// The renames of the attributes.
+    pub attribute_map: BTreeMap<String, String>,
+    /// The metrics the renames apply to (all the metrics if empty).
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub apply_to_metrics: Vec<String>,
+}
+
+/// The migration of telemetry between two versions of a telemetry schema.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct Migration {
+    /// The version of the telemetry to migrate.
+    pub from: String,
+    /// The version of the migrated telemetry.
+    pub to: String,
+    /// The renames of each version, in the order in which they are applied.
+    pub steps: Vec<MigrationStep>,
+}
+
+impl Migration {
+    /// Computes the migration of telemetry from the version `from` to the
+    /// version `to` of the given schema versions.
+    pub fn new(
+        versions: &Versions,
+        from: &semver::Version,
+        to: &semver::Version,
+    ) -> Result<Migration, Error> {
+        for version in [from, to] {
+            if !versions.contains(version) {
+                return Err(Error::UnknownVersion {
+                    version: version.to_string(),
+                });
+            }
+        }
+
+        let steps = if from <= to {
+            versions
+                .versions_between(from, to)
+                .into_iter()
+                .map(|(version, spec)| MigrationStep::from_spec(version.to_string(), spec))
+                .collect()
+        } else {
+            versions
+                .versions_between(to, from)
+                .into_iter()
+                .rev()
+                .map(|(version, spec)| {
+                    MigrationStep::from_spec(version.to_string(), spec).inverse()
+                })
+                .collect()
+        };
+
+        Ok(Migration {
+            from: from.to_string(),
+            to: to.to_string(),
+            steps,
+        })
+    }
+
+    /// Returns true if the migration doesn't rename anything.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.steps.iter().all(MigrationStep::is_empty)
+    }
+
+    /// Migrates a trace export request.
+    pub fn migrate_traces(&self, request: &mut ExportTraceServiceRequest) {
+        for step in &self.steps {
+            for resource_spans in &mut request.resource_spans {
+                step.migrate_resource(resource_spans.resource.as_mut());
+                for scope_spans in &mut resource_spans.scope_spans {
+                    step.migrate_scope(scope_spans.scope.as_mut());
+                    for span in &mut scope_spans.spans {
+                        rename_keys(&mut span.attributes, |key| {
+                            step.rename(&step.span_attributes, key)
+                        });
+                        for event in &mut span.events {
+                            rename_keys(&mut event.attributes, |key| step.attributes.get(key));
+                        }
+                        for link in &mut span.links {
+                            rename_keys(&mut link.attributes, |key| step.attributes.get(key));
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /// Migrates a metric export request.
+    pub fn migrate_metrics(&self, request: &mut ExportMetricsServiceRequest) {
+        for step in &self.steps {
+            for resource_metrics in &mut request.resource_metrics {
+                step.migrate_resource(resource_metrics.resource.as_mut());
+                for scope_metrics in &mut resource_metrics.scope_metrics {
+                    step.migrate_scope(scope_metrics.scope.as_mut());
+                    for metric in &mut scope_metrics.metrics {
+                        step.migrate_metric(metric);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Migrates a log export request.
+    pub fn migrate_logs(&self, request: &mut ExportLogsServiceRequest) {
+        for step in &self.steps {
+            for resource_logs in &mut request.resource_logs {
+                step.migrate_resource(resource_logs.resource.as_mut());
+                for scope_logs in &mut resource_logs.scope_logs {
+                    step.migrate_scope(scope_logs.scope.as_mut());
+                    for log_record in &mut scope_logs.log_records {
+                        rename_keys(&mut log_record.attributes, |key| {
+                            step.rename(&step.log_attributes, key)
+                        });
+                    }
+                }
+            }
+        }
+    }
+
+    /// Migrates an export request (of any signal) encoded in OTLP/JSON.
+    pub fn migrate_json(&self, request: &mut Value) {
+        for step in &self.steps {
+            step.migrate_json(request);
+        }
+    }
+
+    /// Migrates the telemetry of an OTLP file and writes the migrated telemetry
+    /// to the output file, in the same encoding. The export requests of
+    /// OTLP/JSON files are written one per line.
+    pub fn migrate_file(&self, input: &Path, output: &Path) -> Result<(), Error> {
+        let content = std::fs::read(input).map_err(|e| invalid_file(input, &e))?;
+        let is_json = input
+            .extension()
+            .is_some_and(|ext| ext == "json" || ext == "jsonl");
+        let migrated = if is_json {
+            let mut migrated = vec![];
+            for request in serde_json::Deserializer::from_slice(&content).into_iter::<Value>() {
+                let mut request = request.map_err(|e| invalid_file(input, &e))?;
+                self.migrate_json(&mut request);
+                serde_json::to_writer(&mut migrated, &request)
+                    .map_err(|e| invalid_file(input, &e))?;
+                migrated
+                    .write_all(b"\n")
+                    .map_err(|e| invalid_file(input, &e))?;
+            }
+            migrated
+        } else {
+            self.migrate_protobuf(input, &content)?
+        };
+        std::fs::write(output, migrated).map_err(|e| Error::OtlpFileWriteFailed {
+            file: output.to_path_buf(),
+            error: e.to_string(),
+        })
+    }
+
+    /// Migrates the export request of a protobuf file, the signal of which is
+    /// deduced from the file name.
+    fn migrate_protobuf(&self, file: &Path, content: &[u8]) -> Result<Vec<u8>, Error> {
+        let file_name = file
+            .file_name()
+            .map(|name| name.to_string_lossy().to_lowercase())
+            .unwrap_or_default();
+        if file_name.contains("trace") {
+            let mut request =
+                ExportTraceServiceRequest::decode(content).map_err(|e| invalid_file(file, &e))?;
+            self.migrate_traces(&mut request);
+            Ok(request.encode_to_vec())
+        } else if file_name.contains("metric") {
+            let mut request =
+                ExportMetricsServiceRequest::decode(content).map_err(|e| invalid_file(file, &e))?;
+            self.migrate_metrics(&mut request);
+            Ok(request.encode_to_vec())
+        } else if file_name.contains("log") {
+            let mut request =
+                ExportLogsServiceRequest::decode(content).map_err(|e| invalid_file(file, &e))?;
+            self.migrate_logs(&mut request);
+            Ok(request.encode_to_vec())
+        } else {
+            Err(invalid_file(
+                file,
+                "the name of a protobuf file must contain `trace`, `metric`, or `log`",
+            ))
+        }
+    }
+}
+
+impl MigrationStep {
+    /// Builds the step applying the renames of a version.
+    pub(crate) fn from_spec(version: String, spec: &VersionSpec) -> Self {
+        let mut step = MigrationStep {
+            version,
+            ..Default::default()
+        };
+        for change in spec.all.iter().flat_map(|all| all.changes.iter()) {
+            step.attributes
+                .extend(change.rename_attributes.attribute_map.clone());
+        }
+        for change in spec.resources.iter().flat_map(|r| r.changes.iter()) {
+            step.resource_attributes
+                .extend(change.rename_attributes.attribute_map.clone());
+        }
+        for change in spec.spans.iter().flat_map(|s| s.changes.iter()) {
+            step.span_attributes
+                .extend(change.rename_attributes.attribute_map.clone());
+        }
+        for change in spec.logs.iter().flat_map(|l| l.changes.iter()) {
+            step.log_attributes
+                .extend(change.rename_attributes.attribute_map.clone());
+        }
+        for change in spec.metrics.iter().flat_map(|m| m.changes.iter()) {
+            if !change.rename_attributes.is_empty() {
+                step.metric_attributes.push(MetricAttributeRenames {
+                    attribute_map: change.rename_attributes.attribute_map.clone(),
+                    apply_to_metrics: change.rename_attributes.apply_to_metrics.clone(),
+                });
+            }
+            step.metrics.extend(change.rename_metrics.clone());
+        }
+        step
+    }
+
+    /// Returns the step reverting the renames of this step.
+    fn inverse(self) -> Self {
+        let invert = |map: BTreeMap<String, String>| -> BTreeMap<String, String> {
+            map.into_iter().map(|(old, new)| (new, old)).collect()
+        };
+        MigrationStep {
+            version: self.version,
+            attributes: invert(self.attributes),
+            resource_attributes: invert(self.resource_attributes),
+            span_attributes: invert(self.span_attributes),
+            log_attributes: invert(self.log_attributes),
+            metric_attributes: self
+                .metric_attributes
+                .into_iter()
+                .map(|renames| MetricAttributeRenames {
+                    attribute_map: invert(renames.attribute_map),
+                    apply_to_metrics: renames.apply_to_metrics,
+                })
+                .collect(),
+            metrics: invert(self.metrics),
+        }
+    }
+
+    /// Returns true if the step doesn't rename anything.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.attributes.is_empty()
+            && self.resource_attributes.is_empty()
+            && self.span_attributes.is_empty()
+            && self.log_attributes.is_empty()
+            && self.metric_attributes.is_empty()
+            && self.metrics.is_empty()
+    }
+
+    /// Returns the new name of an attribute, the renames specific to a signal
+    /// taking precedence over the renames of all the signals.
+    fn rename<'a>(
+        &'a self,
+        signal_renames: &'a BTreeMap<String, String>,
+        key: &str,
+    ) -> Option<&'a String> {
+        signal_renames.get(key).or_else(|| self.attributes.get(key))
+    }
+
+    /// Returns the new name of an attribute of the given metric (named before
+    /// or after the renaming of the metrics of this step).
+    fn rename_metric_attribute<'a>(
+        &'a self,
+        metric_names: &[&str],
+        key: &str,
+    ) -> Option<&'a String> {
+        self.metric_attributes
+            .iter()
+            .filter(|renames| {
+                renames.apply_to_metrics.is_empty()
+                    || renames
+                        .apply_to_metrics
+                        .iter()
+                        .any(|metric| metric_names.contains(&metric.as_str()))
+            })
+            .find_map(|renames| renames.attribute_map.get(key))
+            .or_else(|| self.attributes.get(key))
+    }
+
+    /// Migrates the attributes of a resource.
+    fn migrate_resource(&self, resource: Option<&mut Resource>) {
+        if let Some(resource) = resource {
+            rename_keys(&mut resource.attributes, |key| {
+                self.rename(&self.resource_attributes, key)
+            });
+        }
+    }
+
+    /// Migrates the attributes of an instrumentation scope.
+    fn migrate_scope(&self, scope: Option<&mut InstrumentationScope>) {
+        if let Some(scope) = scope {
+            rename_keys(&mut scope.attributes, |key| self.attributes.get(key));
+        }
+    }
+
+    /// Migrates the name and the data point attributes of a metric.
+    fn migrate_metric(&self, metric: &mut Metric) {
+        let old_name = metric.name.clone();
+        if let Some(new_name) = self.metrics.get(&metric.name) {
+            metric.name.clone_from(new_name);
+        }
+        let metric_names = [old_name.as_str(), metric.name.as_str()];
+        let rename = |key: &str| self.rename_metric_attribute(&metric_names, key);
+        match &mut metric.data {
+            Some(Data::Gauge(gauge)) => gauge
+                .data_points
+                .iter_mut()
+                .for_each(|dp| rename_keys(&mut dp.attributes, rename)),
+            Some(Data::Sum(sum)) => sum
+                .data_points
+                .iter_mut()
+                .for_each(|dp| rename_keys(&mut dp.attributes, rename)),
+            Some(Data::Histogram(histogram)) => histogram
+                .data_points
+                .iter_mut()
+                .for_each(|dp| rename_keys(&mut dp.attributes, rename)),
+            Some(Data::ExponentialHistogram(histogram)) => histogram
+                .data_points
+                .iter_mut()
+                .for_each(|dp| rename_keys(&mut dp.attributes, rename)),
+            Some(Data::Summary(summary)) => summary
+                .data_points
+                .iter_mut()
+                .for_each(|dp| rename_keys(&mut dp.attributes, rename)),
+            None => {}
+        }
+    }
+
+    /// Migrates an export request encoded in OTLP/JSON.
+    fn migrate_json(&self, request: &mut Value) {
+        let resource_attributes = |key: &str| self.rename(&self.resource_attributes, key);
+        let scope_attributes = |key: &str| self.attributes.get(key);
+
+        for resource_spans in json_array(request, "resourceSpans") {
+            rename_json_keys(resource_spans.get_mut("resource"), resource_attributes);
+            for scope_spans in json_array(resource_spans, "scopeSpans") {
+                rename_json_keys(scope_spans.get_mut("scope"), scope_attributes);
+                for span in json_array(scope_spans, "spans") {
+                    rename_json_keys(Some(span), |key| self.rename(&self.span_attributes, key));
+                    for event in json_array(span, "events") {
+                        rename_json_keys(Some(event), scope_attributes);
+                    }
+                    for link in json_array(span, "links") {
+                        rename_json_keys(Some(link), scope_attributes);
+                    }
+                }
+            }
+        }
+
+        for resource_metrics in json_array(request, "resourceMetrics") {
+            rename_json_keys(resource_metrics.get_mut("resource"), resource_attributes);
+            for scope_metrics in json_array(resource_metrics, "scopeMetrics") {
+                rename_json_keys(scope_metrics.get_mut("scope"), scope_attributes);
+                for metric in json_array(scope_metrics, "metrics") {
+                    let old_name = metric["name"].as_str().unwrap_or_default().to_owned();
+                    let new_name = self.metrics.get(&old_name).unwrap_or(&old_name).clone();
+                    if let Some(name) = metric.get_mut("name") {
+                        *name = Value::String(new_name.clone());
+                    }
+                    let metric_names = [old_name.as_str(), new_name.as_str()];
+                    for data in [
+                        "gauge",
+                        "sum",
+                        "histogram",
+                        "exponentialHistogram",
+                        "summary",
+                    ] {
+                        let Some(data) = metric.get_mut(data) else {
+                            continue;
+                        };
+                        for data_point in json_array(data, "dataPoints") {
+                            rename_json_keys(Some(data_point), |key| {
+                                self.rename_metric_attribute(&metric_names, key)
+                            });
+                        }
+                    }
+                }
+            }
+        }
+
+        for resource_logs in json_array(request, "resourceLogs") {
+            rename_json_keys(resource_logs.get_mut("resource"), resource_attributes);
+            for scope_logs in json_array(resource_logs, "scopeLogs") {
+                rename_json_keys(scope_logs.get_mut("scope"), scope_attributes);
+                for log_record in json_array(scope_logs, "logRecords") {
+                    rename_json_keys(Some(log_record), |key| {
+                        self.rename(&self.log_attributes, key)
+                    });
+                }
+            }
+        }
+    }
+}
+
+/// Renames the keys of the given attributes.
+fn rename_keys<'a>(attributes: &mut [KeyValue], rename: impl Fn(&str) -> Option<&'a String>) {
+    for attribute in attributes {
+        if let Some(new_key) = rename(&attribute.key) {
+            attribute.key.clone_from(new_key);
+        }
+    }
+}
+
+/// Renames the keys of the `attributes` of an OTLP/JSON object (if any).
+fn rename_json_keys<'a>(object: Option<&mut Value>, rename: impl Fn(&str) -> Option<&'a String>) {
+    let Some(object) = object else {
+        return;
+    };
+    for attribute in json_array(object, "attributes") {
+        let new_key = attribute["key"].as_str().and_then(&rename);
+        if let Some(new_key) = new_key {
+            attribute["key"] = Value::String(new_key.clone());
+        }
+    }
+}
+
+/// Returns the elements of the array field of an OTLP/JSON object (none if the
+/// field is missing or is not an array).
+fn json_array<'a>(object: &'a mut Value, field: &str) -> impl Iterator<Item = &'a mut Value> {
+    object
+        .get_mut(field)
+        .and_then(Value::as_array_mut)
+        .into_iter()
+        .flatten()
+}
+
+/// Builds an invalid OTLP file error.
+fn invalid_file<E: Display + ?Sized>(file: &Path, error: &E) -> Error {
+    Error::InvalidOtlpFile {
+        file: file.to_path_buf(),
+        error: error.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use opentelemetry_proto::tonic::common::v1::AnyValue;
+    use opentelemetry_proto::tonic::trace::v1::{span::Event, ResourceSpans, ScopeSpans, Span};
+    use serde_json::json;
+    use tempdir::TempDir;
+
+    use super::*;
+
+    fn migration(from: &str, to: &str) -> Migration {
+        let versions = Versions::load_from_file("data/migration/schema.yaml")
+            .expect("Failed to load the schema file");
+        Migration::new(
+            &versions,
+            &semver::Version::parse(from).expect("Invalid version"),
+            &semver::Version::parse(to).expect("Invalid version"),
+        )
+        .expect("Failed to compute the migration")
+    }
+
+    fn attribute(key: &str) -> Value {
+        json!({ "key": key, "value": { "stringValue": "value" } })
+    }
+
+    /// Returns trace and metric requests conforming to the given version.
+    fn requests(version: &str) -> (Value, Value) {
+        // (span attribute, attribute, address attribute, histogram address attribute, histogram name)
+        let (span_method, method, address, histogram_address, metric) = match version {
+            "1.0.0" => (
+                "http.method",
+                "http.method",
+                "net.peer.name",
+                "net.peer.name",
+                "http.server.duration",
+            ),
+            _ => (
+                "http.request.verb",
+                "http.request.method",
+                "server.address",
+                "server.host",
+                "http.server.request.duration",
+            ),
+        };
+        let traces = json!({
+            "resourceSpans": [{
+                "resource": { "attributes": [attribute(address)] },
+                "scopeSpans": [{
+                    "scope": { "name": "acme" },
+                    "spans": [{
+                        "name": "GET",
+                        "attributes": [attribute(span_method), attribute("url.full")],
+                        "events": [{ "name": "received", "attributes": [attribute(method)] }],
+                    }],
+                }],
+            }],
+        });
+        let metrics = json!({
+            "resourceMetrics": [{
+                "scopeMetrics": [{
+                    "metrics": [{
+                        "name": metric,
+                        "histogram": {
+                            "dataPoints": [{ "attributes": [attribute(method), attribute(histogram_address)] }],
+                        },
+                    }, {
+                        "name": "http.client.duration",
+                        "sum": {
+                            "dataPoints": [{ "attributes": [attribute(address)] }],
+                        },
+                    }],
+                }],
+            }],
+        });
+        (traces, metrics)
+    }
+
+    #[test]
+    fn test_migration() {
+        let upgrade = migration("1.0.0", "1.2.0");
+        assert_eq!(
+            upgrade
+                .steps
+                .iter()
+                .map(|step| step.version.as_str())
+                .collect::<Vec<_>>(),
+            vec!["1.1.0", "1.2.0"]
+        );
+
+        // Renames are chained across versions and restricted to the signals
+        // (and metrics) they apply to.
+        let (mut traces, mut metrics) = requests("1.0.0");
+        upgrade.migrate_json(&mut traces);
+        upgrade.migrate_json(&mut metrics);
+        let (expected_traces, expected_metrics) = requests("1.2.0");
+        assert_eq!(traces, expected_traces);
+        assert_eq!(metrics, expected_metrics);
+
+        // Migrating to an older version reverts the renames.
+        let downgrade = migration("1.2.0", "1.0.0");
+        downgrade.migrate_json(&mut traces);
+        downgrade.migrate_json(&mut metrics);
+        let (expected_traces, expected_metrics) = requests("1.0.0");
+        assert_eq!(traces, expected_traces);
+        assert_eq!(metrics, expected_metrics);
+
+        assert!(migration("1.1.0", "1.1.0").is_empty());
+
+        let versions = Versions::load_from_file("data/migration/schema.yaml")
+            .expect("Failed to load the schema file");
+        assert_eq!(
+            Migration::new(
+                &versions,
+                &semver::Version::new(1, 0, 0),
+                &semver::Version::new(2, 0, 0)
+            ),
+            Err(Error::UnknownVersion {
+                version: "2.0.0".to_owned()
+            })
+        );
+    }
+
+    #[test]
+    fn test_migrate_file() {
+        let upgrade = migration("1.0.0", "1.2.0");
+        let dir = TempDir::new("weaver-migration").expect("Failed to create a temp dir");
+
+        // OTLP/JSON file.
+        let (traces, metrics) = requests("1.0.0");
+        let input = dir.path().join("telemetry.jsonl");
+        std::fs::write(&input, format!("{}\n{}\n", traces, metrics))
+            .expect("Failed to write the input file");
+        let output = dir.path().join("migrated.jsonl");
+        upgrade
+            .migrate_file(&input, &output)
+            .expect("Failed to migrate the file");
+        let migrated: Vec<Value> = std::fs::read_to_string(&output)
+            .expect("Failed to read the output file")
+            .lines()
+            .map(|line| serde_json::from_str(line).expect("Invalid JSON"))
+            .collect();
+        let (expected_traces, expected_metrics) = requests("1.2.0");
+        assert_eq!(migrated, vec![expected_traces, expected_metrics]);
+
+        // OTLP protobuf file.
+        let key_value = |key: &str| KeyValue {
+            key: key.to_owned(),
+            value: Some(AnyValue::default()),
+        };
+        let request = ExportTraceServiceRequest {
+            resource_spans: vec![ResourceSpans {
+                scope_spans: vec![ScopeSpans {
+                    spans: vec![Span {
+                        attributes: vec![key_value("http.method")],
+                        events: vec![Event {
+                            attributes: vec![key_value("http.method")],
+                            ..Default::default()
+                        }],
+                        ..Default::default()
+                    }],
+                    ..Default::default()
+                }],
+                ..Default::default()
+            }],
+        };
+        let input = dir.path().join("traces.binpb");
+        std::fs::write(&input, request.encode_to_vec()).expect("Failed to write the input file");
+        let output = dir.path().join("migrated_traces.binpb");
+        upgrade
+            .migrate_file(&input, &output)
+            .expect("Failed to migrate the file");
+        let migrated = ExportTraceServiceRequest::decode(
+            std::fs::read(&output)
+                .expect("Failed to read the output file")
+                .as_slice(),
+        )
+        .expect("Invalid protobuf file");
+        let span = &migrated.resource_spans[0].scope_spans[0].spans[0];
+        assert_eq!(span.attributes[0].key, "http.request.verb");
+        assert_eq!(span.events[0].attributes[0].key, "http.request.method");
+
+        assert!(matches!(
+            upgrade.migrate_file(&input, &dir.path().join("unknown").join("file")),
+            Err(Error::OtlpFileWriteFailed { .. })
+        ));
+        assert!(matches!(
+            upgrade.migrate_file(&dir.path().join("unknown.binpb"), &output),
+            Err(Error::InvalidOtlpFile { .. })
+        ));
+    }
+}
@@ -0,0 +1,230 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Generation of an OpenTelemetry Collector
+//! [`transform` processor](https://github.com/open-telemetry/opentelemetry-collector-contrib/tree/main/processor/transformprocessor)
+//! configuration renaming the attributes and metrics of a version of a
+//! telemetry schema with [OTTL](https://github.com/open-telemetry/opentelemetry-collector-contrib/tree/main/pkg/ottl)
+//! statements, so telemetry emitted with the previous version can be bridged to
+//! the new one at the collector.
+
+use std::collections::BTreeMap;
+
+use serde::Serialize;
+
+use weaver_version::VersionSpec;
+
+use crate::migration::MigrationStep;
+use crate::Error;
+
+/// The configuration of a `transform` processor.
+#[derive(Serialize, Debug, Clone, Default, PartialEq)]
+pub struct TransformProcessor {
+    /// The statements applied to the traces.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub trace_statements: Vec<ContextStatements>,
+    /// The statements applied to the metrics.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub metric_statements: Vec<ContextStatements>,
+    /// The statements applied to the logs.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub log_statements: Vec<ContextStatements>,
+}
+
+/// OTTL statements executed in a given context.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct ContextStatements {
+    /// The OTTL context (e.g. `resource`, `span`, or `datapoint`).
+    pub context: String,
+    /// The OTTL statements.
+    pub statements: Vec<String>,
+}
+
+impl TransformProcessor {
+    /// Builds the `transform` processor configuration applying the renames of
+    /// a version of a telemetry schema.
+    #[must_use]
+    pub fn from_spec(spec: &VersionSpec) -> Self {
+        let step = MigrationStep::from_spec(String::new(), spec);
+        let merged = |signal_renames: &BTreeMap<String, String>| {
+            let mut renames = step.attributes.clone();
+            renames.extend(signal_renames.clone());
+            renames
+        };
+        let resource = rename_attributes(&merged(&step.resource_attributes), None);
+        let scope = rename_attributes(&step.attributes, None);
+
+        let mut datapoint = vec![];
+        for renames in &step.metric_attributes {
+            let condition = (!renames.apply_to_metrics.is_empty()).then(|| {
+                let names: Vec<String> = renames
+                    .apply_to_metrics
+                    .iter()
+                    .map(|metric| format!("metric.name == {}", quote(metric)))
+                    .collect();
+                format!("({})", names.join(" or "))
+            });
+            datapoint.extend(rename_attributes(
+                &renames.attribute_map,
+                condition.as_deref(),
+            ));
+        }
+        datapoint.extend(rename_attributes(&step.attributes, None));
+        let metric = step
+            .metrics
+            .iter()
+            .map(|(old, new)| format!("set(name, {}) where name == {}", quote(new), quote(old)))
+            .collect();
+
+        TransformProcessor {
+            trace_statements: contexts(vec![
+                ("resource", resource.clone()),
+                ("scope", scope.clone()),
+                (
+                    "span",
+                    rename_attributes(&merged(&step.span_attributes), None),
+                ),
+                ("spanevent", rename_attributes(&step.attributes, None)),
+            ]),
+            // The data points are processed before the metrics so the metric
+            // names of the conditions are the names before the renaming.
+            metric_statements: contexts(vec![
+                ("resource", resource.clone()),
+                ("scope", scope.clone()),
+                ("datapoint", datapoint),
+                ("metric", metric),
+            ]),
+            log_statements: contexts(vec![
+                ("resource", resource),
+                ("scope", scope),
+                (
+                    "log",
+                    rename_attributes(&merged(&step.log_attributes), None),
+                ),
+            ]),
+        }
+    }
+
+    /// Returns true if the processor doesn't rename anything.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.trace_statements.is_empty()
+            && self.metric_statements.is_empty()
+            && self.log_statements.is_empty()
+    }
+
+    /// Serializes the processor in YAML, as the `processors` section of a
+    /// collector configuration.
+    pub fn to_yaml(&self) -> Result<String, Error> {
+        #[derive(Serialize)]
+        struct Processors<'a> {
+            transform: &'a TransformProcessor,
+        }
+        #[derive(Serialize)]
+        struct Config<'a> {
+            processors: Processors<'a>,
+        }
+
+        serde_yaml::to_string(&Config {
+            processors: Processors { transform: self },
+        })
+        .map_err(|e| Error::SerializationFailed {
+            error: e.to_string(),
+        })
+    }
+}
+
+/// Returns the statements renaming the given attributes, optionally restricted
+/// by a condition.
+fn rename_attributes(renames: &BTreeMap<String, String>, condition: Option<&str>) -> Vec<String> {
+    let and_condition = condition.map_or(String::new(), |c| format!(" and {}", c));
+    let where_condition = condition.map_or(String::new(), |c| format!(" where {}", c));
+    renames
+        .iter()
+        .flat_map(|(old, new)| {
+            let (old, new) = (quote(old), quote(new));
+            [
+                format!(
+                    "set(attributes[{new}], attributes[{old}]) where attributes[{old}] != nil{and_condition}"
+                ),
+                format!("delete_key(attributes, {old}){where_condition}"),
+            ]
+        })
+        .collect()
+}
+
+/// Returns the contexts having at least one statement.
+fn contexts(statements: Vec<(&str, Vec<String>)>) -> Vec<ContextStatements> {
+    statements
+        .into_iter()
+        .filter(|(_, statements)| !statements.is_empty())
+        .map(|(context, statements)| ContextStatements {
+            context: context.to_owned(),
+            statements,
+        })
+        .collect()
+}
+
+/// Quotes a string literal.
+fn quote(value: &str) -> String {
+    format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+#[cfg(test)]
+mod tests {
+    use weaver_version::Versions;
+
+    use super::*;
+
+    #[test]
+    fn test_transform_processor() {
+        let versions = Versions::load_from_file("data/migration/schema.yaml")
+            .expect("Failed to load the schema file");
+        let specs = versions.versions_between(
+            &semver::Version::new(1, 0, 0),
+            &semver::Version::new(1, 2, 0),
+        );
+
+        let v1_1 = TransformProcessor::from_spec(specs[0].1);
+        let rename_method = [
+            "set(attributes[\"http.request.method\"], attributes[\"http.method\"]) where attributes[\"http.method\"] != nil".to_owned(),
+            "delete_key(attributes, \"http.method\")".to_owned(),
+        ];
+        let span = v1_1
+            .trace_statements
+            .iter()
+            .find(|statements| statements.context == "span")
+            .expect("No span statements");
+        assert_eq!(span.statements[..2], rename_method[..]);
+        let metric = v1_1.metric_statements.last().expect("No metric statements");
+        assert_eq!(metric.context, "metric");
+        assert_eq!(
+            metric.statements,
+            vec!["set(name, \"http.server.request.duration\") where name == \"http.server.duration\""]
+        );
+
+        // Renames restricted to a signal or to some metrics.
+        let v1_2 = TransformProcessor::from_spec(specs[1].1);
+        assert_eq!(
+            v1_2.trace_statements
+                .iter()
+                .map(|statements| statements.context.as_str())
+                .collect::<Vec<_>>(),
+            vec!["span"]
+        );
+        assert!(v1_2.log_statements.is_empty());
+        assert_eq!(
+            v1_2.metric_statements,
+            vec![ContextStatements {
+                context: "datapoint".to_owned(),
+                statements: vec![
+                    "set(attributes[\"server.host\"], attributes[\"server.address\"]) where attributes[\"server.address\"] != nil and (metric.name == \"http.server.request.duration\")".to_owned(),
+                    "delete_key(attributes, \"server.address\") where (metric.name == \"http.server.request.duration\")".to_owned(),
+                ],
+            }]
+        );
+
+        let yaml = v1_2.to_yaml().expect("Failed to serialize");
+        assert!(yaml.starts_with("processors:\n  transform:\n    trace_statements:\n"));
+        assert!(TransformProcessor::from_spec(&VersionSpec::default()).is_empty());
+    }
+}
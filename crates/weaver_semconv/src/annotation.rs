@@ -0,0 +1,11 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Annotations of groups and attributes.
+
+use std::collections::BTreeMap;
+
+/// An open-ended map of annotations (e.g. owners, issue links, PII flags)
+/// attached to a group or an attribute. The annotations carry no semantic
+/// meaning for Weaver, they are kept as is through the resolution and exposed
+/// to the templates and the policies.
+pub type Annotations = BTreeMap<String, serde_yaml::Value>;
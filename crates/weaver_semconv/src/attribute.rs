@@ -10,6 +10,8 @@ use serde::{Deserialize, Serialize};
 use std::fmt::{Display, Formatter};
 use std::ops::Not;
 
+use crate::annotation::Annotations;
+use crate::sensitivity::Sensitivity;
 use crate::stability::Stability;
 
 /// An attribute specification.
@@ -125,6 +127,20 @@ pub enum AttributeSpec {
         /// to use instead. See also stability.
         #[serde(skip_serializing_if = "Option::is_none")]
         deprecated: Option<String>,
+        /// The previous names of the attribute, i.e. the names of the attributes
+        /// it replaces. The names are fully qualified (the group prefix is not
+        /// applied). It defaults to an empty list.
+        #[serde(default)]
+        #[serde(skip_serializing_if = "Vec::is_empty")]
+        renamed_from: Vec<String>,
+        /// The sensitivity classification of the values of the attribute.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        sensitivity: Option<Sensitivity>,
+        /// The annotations of the attribute (see [`Annotations`]).
+        /// It defaults to an empty map.
+        #[serde(default)]
+        #[serde(skip_serializing_if = "Annotations::is_empty")]
+        annotations: Annotations,
     },
 }
 
@@ -221,6 +237,35 @@ impl Display for AttributeType {
     }
 }
 
+impl AttributeType {
+    /// Returns the type of the values of the attribute: the type of the members
+    /// for an enum (int if all the members are ints, string otherwise) and the
+    /// type of the values for a template.
+    #[must_use]
+    pub fn value_type(&self) -> PrimitiveOrArrayTypeSpec {
+        match self {
+            AttributeType::PrimitiveOrArray(value_type) => value_type.clone(),
+            AttributeType::Enum { members, .. } => {
+                if members.iter().all(|m| matches!(m.value, ValueSpec::Int(_))) {
+                    PrimitiveOrArrayTypeSpec::Int
+                } else {
+                    PrimitiveOrArrayTypeSpec::String
+                }
+            }
+            AttributeType::Template(value_type) => match value_type {
+                TemplateTypeSpec::Boolean => PrimitiveOrArrayTypeSpec::Boolean,
+                TemplateTypeSpec::Int => PrimitiveOrArrayTypeSpec::Int,
+                TemplateTypeSpec::Double => PrimitiveOrArrayTypeSpec::Double,
+                TemplateTypeSpec::String => PrimitiveOrArrayTypeSpec::String,
+                TemplateTypeSpec::Strings => PrimitiveOrArrayTypeSpec::Strings,
+                TemplateTypeSpec::Ints => PrimitiveOrArrayTypeSpec::Ints,
+                TemplateTypeSpec::Doubles => PrimitiveOrArrayTypeSpec::Doubles,
+                TemplateTypeSpec::Booleans => PrimitiveOrArrayTypeSpec::Booleans,
+            },
+        }
+    }
+}
+
 /// Specifies the default value for allow_custom_values.
 fn default_as_true() -> bool {
     true
@@ -768,6 +813,9 @@ mod tests {
             note: "note".to_owned(),
             stability: Some(Stability::Stable),
             deprecated: Some("deprecated".to_owned()),
+            renamed_from: vec![],
+            sensitivity: None,
+            annotations: Default::default(),
         };
         assert_eq!(attr.id(), "id");
         assert_eq!(attr.brief(), "brief");
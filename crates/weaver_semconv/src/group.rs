@@ -9,7 +9,8 @@ use std::fmt::{Display, Formatter};
 
 use serde::{Deserialize, Serialize};
 
-use crate::attribute::{AttributeSpec, AttributeType, PrimitiveOrArrayTypeSpec};
+use crate::annotation::Annotations;
+use crate::attribute::{AttributeSpec, AttributeType, PrimitiveOrArrayTypeSpec, RequirementLevel};
 use crate::group::InstrumentSpec::{Counter, Gauge, Histogram, UpDownCounter};
 use crate::stability::Stability;
 use crate::Error;
@@ -83,9 +84,29 @@ pub struct GroupSpec {
     pub unit: Option<String>,
     /// The name of the event. If not specified, the prefix is used.
     /// If prefix is empty (or unspecified), name is required.
+    /// For a scope, the name of the instrumentation scope (e.g.
+    /// `io.opentelemetry.jdbc`), which is required.
     pub name: Option<String>,
+    /// The requirement level of the version of the instrumentation scope,
+    /// i.e. whether the instrumentations must report their version along
+    /// with the name of the scope.
+    /// Note: only valid if type is scope.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub scope_version: Option<RequirementLevel>,
     /// The readable name for attribute groups used when generating registry tables.
     pub display_name: Option<String>,
+    /// The URL of the documentation of the semantic convention.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub doc_url: Option<String>,
+    /// The version of the registry in which the semantic convention was
+    /// introduced (e.g. `1.26.0`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub since_version: Option<String>,
+    /// The annotations of the group (see [`Annotations`]).
+    /// It defaults to an empty map.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Annotations::is_empty")]
+    pub annotations: Annotations,
 }
 
 impl GroupSpec {
@@ -122,6 +143,26 @@ impl GroupSpec {
             });
         }
 
+        // Field name is required if type is scope, field scope_version is only
+        // valid if type is scope.
+        if self.r#type == GroupType::Scope {
+            if self.name.is_none() {
+                errors.push(Error::InvalidGroup {
+                    path_or_url: path_or_url.to_owned(),
+                    group_id: self.id.clone(),
+                    error: "This group contains a scope type but the name is not set.".to_owned(),
+                });
+            }
+        } else if self.scope_version.is_some() {
+            errors.push(Error::InvalidGroup {
+                path_or_url: path_or_url.to_owned(),
+                group_id: self.id.clone(),
+                error:
+                    "This group contains a scope_version field but the type is not set to scope."
+                        .to_owned(),
+            });
+        }
+
         // Fields metric_name, instrument and unit are required if type is metric.
         if self.r#type == GroupType::Metric {
             if self.metric_name.is_none() {
@@ -149,8 +190,40 @@ impl GroupSpec {
             }
         }
 
+        // The prefix, if any, must be a well-formed namespace.
+        if !self.prefix.is_empty() && !is_valid_namespace(&self.prefix) {
+            errors.push(Error::InvalidGroup {
+                path_or_url: path_or_url.to_owned(),
+                group_id: self.id.clone(),
+                error: format!(
+                    "The prefix `{}` is not a valid namespace (dot-separated lowercase segments).",
+                    self.prefix
+                ),
+            });
+        }
+
         // Validates the attributes.
         for attribute in &self.attributes {
+            // Attribute ids are prefixed with the group prefix, an id already
+            // including the prefix would be prefixed twice.
+            if let AttributeSpec::Id { id, .. } = attribute {
+                if !self.prefix.is_empty()
+                    && id
+                        .strip_prefix(self.prefix.as_str())
+                        .is_some_and(|rest| rest.starts_with('.'))
+                {
+                    errors.push(Error::InvalidAttribute {
+                        path_or_url: path_or_url.to_owned(),
+                        group_id: self.id.clone(),
+                        attribute_id: attribute.id(),
+                        error: format!(
+                            "This attribute id already includes the group prefix `{}`, the resolved name would be `{}.{}`.",
+                            self.prefix, self.prefix, id
+                        ),
+                    });
+                }
+            }
+
             // If deprecated is present and stability differs from deprecated, this
             // will result in an error.
             match attribute {
@@ -206,6 +279,17 @@ impl GroupSpec {
     }
 }
 
+/// Returns true if the given string is a well-formed namespace, i.e. a
+/// sequence of dot-separated segments made of lowercase letters, digits and
+/// underscores, each segment starting with a letter.
+fn is_valid_namespace(namespace: &str) -> bool {
+    namespace.split('.').all(|segment| {
+        let mut chars = segment.chars();
+        chars.next().is_some_and(|c| c.is_ascii_lowercase())
+            && chars.all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+    })
+}
+
 /// The different types of groups (specification).
 #[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Hash, Clone, JsonSchema)]
 #[serde(rename_all = "snake_case")]
@@ -325,6 +409,9 @@ mod tests {
                 requirement_level: Default::default(),
                 sampling_relevant: None,
                 note: "".to_owned(),
+                renamed_from: vec![],
+                sensitivity: None,
+                annotations: Default::default(),
             }],
             constraints: vec![],
             span_kind: Some(SpanKindSpec::Client),
@@ -333,7 +420,11 @@ mod tests {
             instrument: None,
             unit: None,
             name: None,
+            scope_version: None,
             display_name: None,
+            doc_url: None,
+            since_version: None,
+            annotations: Default::default(),
         };
         assert!(group.validate("<test>").is_ok());
 
@@ -403,6 +494,148 @@ mod tests {
         ), result);
     }
 
+    #[test]
+    fn test_validate_prefix() {
+        let mut group = GroupSpec {
+            id: "test".to_owned(),
+            r#type: GroupType::AttributeGroup,
+            brief: "test".to_owned(),
+            note: "".to_owned(),
+            prefix: "http.client".to_owned(),
+            extends: None,
+            stability: None,
+            deprecated: None,
+            attributes: vec![AttributeSpec::Id {
+                id: "method".to_owned(),
+                r#type: AttributeType::PrimitiveOrArray(PrimitiveOrArrayTypeSpec::Int),
+                brief: Some("test".to_owned()),
+                stability: None,
+                deprecated: None,
+                examples: None,
+                tag: None,
+                requirement_level: Default::default(),
+                sampling_relevant: None,
+                note: "".to_owned(),
+                renamed_from: vec![],
+                sensitivity: None,
+                annotations: Default::default(),
+            }],
+            constraints: vec![],
+            span_kind: None,
+            events: vec![],
+            metric_name: None,
+            instrument: None,
+            unit: None,
+            name: None,
+            scope_version: None,
+            display_name: None,
+            doc_url: None,
+            since_version: None,
+            annotations: Default::default(),
+        };
+        assert!(group.validate("<test>").is_ok());
+
+        // Malformed prefixes.
+        for prefix in [
+            "http.",
+            ".http",
+            "Http",
+            "http-client",
+            "http..client",
+            "1http",
+        ] {
+            prefix.clone_into(&mut group.prefix);
+            assert_eq!(
+                Err(InvalidGroup {
+                    path_or_url: "<test>".to_owned(),
+                    group_id: "test".to_owned(),
+                    error: format!(
+                        "The prefix `{}` is not a valid namespace (dot-separated lowercase segments).",
+                        prefix
+                    ),
+                }),
+                group.validate("<test>"),
+                "prefix `{}` should be invalid",
+                prefix
+            );
+        }
+
+        // Attribute id already including the prefix.
+        "http".clone_into(&mut group.prefix);
+        if let AttributeSpec::Id { id, .. } = &mut group.attributes[0] {
+            "http.method".clone_into(id);
+        }
+        assert_eq!(
+            Err(InvalidAttribute {
+                path_or_url: "<test>".to_owned(),
+                group_id: "test".to_owned(),
+                attribute_id: "http.method".to_owned(),
+                error: "This attribute id already includes the group prefix `http`, the resolved name would be `http.http.method`.".to_owned(),
+            }),
+            group.validate("<test>")
+        );
+
+        // An id starting with the prefix but in another namespace is valid.
+        if let AttributeSpec::Id { id, .. } = &mut group.attributes[0] {
+            "https_method".clone_into(id);
+        }
+        assert!(group.validate("<test>").is_ok());
+    }
+
+    #[test]
+    fn test_validate_scope() {
+        let mut group = GroupSpec {
+            id: "scope.jdbc".to_owned(),
+            r#type: GroupType::Scope,
+            brief: "test".to_owned(),
+            note: "".to_owned(),
+            prefix: "".to_owned(),
+            extends: None,
+            stability: None,
+            deprecated: None,
+            attributes: vec![],
+            constraints: vec![],
+            span_kind: None,
+            events: vec![],
+            metric_name: None,
+            instrument: None,
+            unit: None,
+            name: Some("io.opentelemetry.jdbc".to_owned()),
+            scope_version: Some(RequirementLevel::Basic(
+                crate::attribute::BasicRequirementLevelSpec::Required,
+            )),
+            display_name: None,
+            doc_url: None,
+            since_version: None,
+            annotations: Default::default(),
+        };
+        assert!(group.validate("<test>").is_ok());
+
+        // The name of the scope is required.
+        group.name = None;
+        assert_eq!(
+            Err(InvalidGroup {
+                path_or_url: "<test>".to_owned(),
+                group_id: "scope.jdbc".to_owned(),
+                error: "This group contains a scope type but the name is not set.".to_owned(),
+            }),
+            group.validate("<test>")
+        );
+
+        // The version of the scope is only valid for scopes.
+        group.r#type = GroupType::AttributeGroup;
+        assert_eq!(
+            Err(InvalidGroup {
+                path_or_url: "<test>".to_owned(),
+                group_id: "scope.jdbc".to_owned(),
+                error:
+                    "This group contains a scope_version field but the type is not set to scope."
+                        .to_owned(),
+            }),
+            group.validate("<test>")
+        );
+    }
+
     #[test]
     fn test_validate_attribute() {
         let mut group = GroupSpec {
@@ -425,6 +658,9 @@ mod tests {
                 requirement_level: Default::default(),
                 sampling_relevant: None,
                 note: "".to_owned(),
+                renamed_from: vec![],
+                sensitivity: None,
+                annotations: Default::default(),
             }],
             constraints: vec![],
             span_kind: Some(SpanKindSpec::Client),
@@ -433,7 +669,11 @@ mod tests {
             instrument: None,
             unit: None,
             name: None,
+            scope_version: None,
             display_name: None,
+            doc_url: None,
+            since_version: None,
+            annotations: Default::default(),
         };
         assert!(group.validate("<test>").is_ok());
 
@@ -449,6 +689,9 @@ mod tests {
             requirement_level: Default::default(),
             sampling_relevant: None,
             note: "".to_owned(),
+            renamed_from: vec![],
+            sensitivity: None,
+            annotations: Default::default(),
         }];
         let result = group.validate("<test>");
         assert_eq!(
@@ -474,6 +717,9 @@ mod tests {
             requirement_level: Default::default(),
             sampling_relevant: None,
             note: "".to_owned(),
+            renamed_from: vec![],
+            sensitivity: None,
+            annotations: Default::default(),
         }];
         let result = group.validate("<test>");
         assert_eq!(
@@ -8,12 +8,15 @@ use serde::Serialize;
 use weaver_common::diagnostic::{DiagnosticMessage, DiagnosticMessages};
 use weaver_common::error::{format_errors, WeaverError};
 
+pub mod annotation;
 pub mod attribute;
 pub mod group;
+pub mod manifest;
 pub mod metric;
 pub mod path;
 pub mod registry;
 pub mod semconv;
+pub mod sensitivity;
 pub mod stability;
 pub mod stats;
 
@@ -89,6 +92,19 @@ pub enum Error {
         error: String,
     },
 
+    /// The manifest of the semantic convention registry is invalid.
+    #[error("The registry manifest is invalid (path: {path:?}). {error}")]
+    InvalidRegistryManifest {
+        /// The path of the manifest.
+        path: String,
+        /// The line where the error occurred.
+        line: Option<usize>,
+        /// The column where the error occurred.
+        column: Option<usize>,
+        /// The error that occurred.
+        error: String,
+    },
+
     /// A container for multiple errors.
     #[error("{:?}", format_errors(.0))]
     CompoundError(Vec<Error>),
@@ -152,6 +168,7 @@ mod tests {
             "data/network.yaml",
             "data/rpc.yaml",
             "data/rpc-metrics.yaml",
+            "data/scope.yaml",
             "data/server.yaml",
             "data/source.yaml",
             "data/trace-exception.yaml",
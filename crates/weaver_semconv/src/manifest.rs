@@ -0,0 +1,103 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Manifest of a semantic convention registry.
+
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::Error;
+
+/// The name of the manifest file located at the root of a registry.
+pub const REGISTRY_MANIFEST: &str = "registry_manifest.yaml";
+
+/// The manifest of a semantic convention registry, declaring the namespaces
+/// reserved by the registry.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct RegistryManifest {
+    /// The name of the registry.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// The version of the registry.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    /// The namespaces of the attributes defined by the registry (e.g. `acme`
+    /// or `acme.http`). When declared, the registry can only define
+    /// attributes under these namespaces.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub namespaces: Vec<String>,
+}
+
+impl RegistryManifest {
+    /// Loads the manifest located at the root of a registry, if any.
+    ///
+    /// # Arguments
+    ///
+    /// * `registry_root` - The root directory of the registry.
+    pub fn try_from_registry_root<P: AsRef<Path>>(
+        registry_root: P,
+    ) -> Result<Option<RegistryManifest>, Error> {
+        let path = registry_root.as_ref().join(REGISTRY_MANIFEST);
+        if !path.is_file() {
+            return Ok(None);
+        }
+        let manifest_error = |line, column, error| Error::InvalidRegistryManifest {
+            path: path.display().to_string(),
+            line,
+            column,
+            error,
+        };
+        let content = std::fs::read_to_string(&path)
+            .map_err(|e| manifest_error(None, None, e.to_string()))?;
+        serde_yaml::from_str(&content).map(Some).map_err(|e| {
+            manifest_error(
+                e.location().map(|loc| loc.line()),
+                e.location().map(|loc| loc.column()),
+                e.to_string(),
+            )
+        })
+    }
+
+    /// Returns the declared namespace containing the given attribute id, if
+    /// any.
+    #[must_use]
+    pub fn namespace_of(&self, attribute_id: &str) -> Option<&str> {
+        self.namespaces
+            .iter()
+            .map(String::as_str)
+            .find(|namespace| in_namespace(attribute_id, namespace))
+    }
+}
+
+/// Returns true if the attribute id belongs to the namespace, i.e. the id is
+/// the namespace itself or starts with the namespace followed by a `.`.
+#[must_use]
+pub fn in_namespace(attribute_id: &str, namespace: &str) -> bool {
+    attribute_id
+        .strip_prefix(namespace)
+        .is_some_and(|rest| rest.is_empty() || rest.starts_with('.'))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_registry_manifest() {
+        let manifest: RegistryManifest =
+            serde_yaml::from_str("name: acme\nnamespaces: [acme, vendor.http]\n")
+                .expect("Invalid manifest");
+        assert_eq!(manifest.namespace_of("acme.tenant"), Some("acme"));
+        assert_eq!(
+            manifest.namespace_of("vendor.http.route"),
+            Some("vendor.http")
+        );
+        assert_eq!(manifest.namespace_of("acmecorp.tenant"), None);
+        assert_eq!(manifest.namespace_of("vendor.db.name"), None);
+
+        // Registries without manifest.
+        assert_eq!(RegistryManifest::try_from_registry_root("data"), Ok(None));
+        assert!(serde_yaml::from_str::<RegistryManifest>("namespace: [acme]\n").is_err());
+    }
+}
@@ -23,6 +23,23 @@ pub enum RegistryPath {
         /// An optional path to the semantic convention directory containing
         /// the semantic convention files.
         path: Option<String>,
+        /// An optional ref (tag, branch, or full ref name) to check out
+        /// instead of the default branch of the repo.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        git_ref: Option<String>,
+    },
+    /// An OCI artifact containing a packaged semantic convention registry.
+    Oci {
+        /// The OCI reference of the artifact (e.g.
+        /// `oci://ghcr.io/acme/semconv:1.2.0`), optionally pinned by digest.
+        oci_reference: String,
+    },
+    /// A local registry archive built by `weaver registry package`.
+    Archive {
+        /// The path of the `.tar.gz` archive, optionally followed by a
+        /// fragment declaring its checksum or its signing key (e.g.
+        /// `semconv-1.2.0.tar.gz#sha256=<hex>`).
+        archive_path: String,
     },
 }
 
@@ -30,10 +47,22 @@ impl Display for RegistryPath {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         let path = match self {
             RegistryPath::Local { path_pattern } => format!("LocalRegistry:{}", path_pattern),
-            RegistryPath::GitUrl { git_url, path } => match path {
-                Some(path) => format!("GitRegistry:{}/{:?}", git_url, path),
-                None => format!("GitRegistry:{}", git_url),
-            },
+            RegistryPath::GitUrl {
+                git_url,
+                path,
+                git_ref,
+            } => {
+                let git_url = match git_ref {
+                    Some(git_ref) => format!("{}@{}", git_url, git_ref),
+                    None => git_url.clone(),
+                };
+                match path {
+                    Some(path) => format!("GitRegistry:{}/{:?}", git_url, path),
+                    None => format!("GitRegistry:{}", git_url),
+                }
+            }
+            RegistryPath::Oci { oci_reference } => format!("OciRegistry:{}", oci_reference),
+            RegistryPath::Archive { archive_path } => format!("ArchiveRegistry:{}", archive_path),
         };
         f.write_str(&path)
     }
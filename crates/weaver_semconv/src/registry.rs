@@ -3,7 +3,7 @@
 //! Semantic Convention Registry.
 
 use crate::attribute::AttributeSpecWithProvenance;
-use crate::group::GroupSpecWithProvenance;
+use crate::group::{GroupSpec, GroupSpecWithProvenance};
 use crate::metric::MetricSpecWithProvenance;
 use crate::semconv::{SemConvSpec, SemConvSpecWithProvenance};
 use crate::stats::Stats;
@@ -146,6 +146,7 @@ impl SemConvRegistry {
     }
 
     /// Downloads and returns the semantic convention spec from an URL.
+    #[cfg(feature = "http")]
     pub fn semconv_spec_from_url(sem_conv_url: &str) -> Result<(String, SemConvSpec), Error> {
         let spec = SemConvSpec::from_url(sem_conv_url)?;
         Ok((sem_conv_url.to_owned(), spec))
@@ -175,6 +176,19 @@ impl SemConvRegistry {
             })
     }
 
+    /// Returns an iterator over all the groups defined in the semantic
+    /// convention registry, without cloning them. Each group is associated with
+    /// its provenance (path or URL).
+    pub fn group_with_provenance_iter(&self) -> impl Iterator<Item = (&GroupSpec, &str)> + '_ {
+        self.specs
+            .iter()
+            .flat_map(|SemConvSpecWithProvenance { spec, provenance }| {
+                spec.groups
+                    .iter()
+                    .map(move |group| (group, provenance.as_str()))
+            })
+    }
+
     /// Returns a set of stats about the semantic convention registry.
     pub fn stats(&self) -> Stats {
         Stats {
@@ -218,6 +232,7 @@ mod tests {
     }
 
     #[test]
+    #[cfg(feature = "http")]
     fn test_semconv_spec_from_url() {
         let semconv_url = "https://raw.githubusercontent.com/open-telemetry/semantic-conventions/main/model/url.yaml";
         let result = SemConvRegistry::semconv_spec_from_url(semconv_url);
@@ -246,6 +261,9 @@ mod tests {
                             note: "note".to_owned(),
                             stability: None,
                             deprecated: None,
+                            renamed_from: vec![],
+                            sensitivity: None,
+                            annotations: Default::default(),
                         }],
                         constraints: vec![],
                         span_kind: None,
@@ -260,7 +278,11 @@ mod tests {
                         deprecated: None,
                         events: vec![],
                         name: None,
+                        scope_version: None,
                         display_name: Some("Group 1".to_owned()),
+                        doc_url: None,
+                        since_version: None,
+                        annotations: Default::default(),
                     }],
                 },
             ),
@@ -284,7 +306,11 @@ mod tests {
                         deprecated: None,
                         events: vec![],
                         name: None,
+                        scope_version: None,
                         display_name: Some("Group 2".to_owned()),
+                        doc_url: None,
+                        since_version: None,
+                        annotations: Default::default(),
                     }],
                 },
             ),
@@ -5,8 +5,8 @@
 use crate::group::GroupSpec;
 use crate::Error;
 use serde::{Deserialize, Serialize};
-use std::fs::File;
-use std::io::BufReader;
+#[cfg(feature = "http")]
+use std::io::Read;
 use std::path::Path;
 use weaver_common::error::handle_errors;
 
@@ -42,17 +42,16 @@ impl SemConvSpec {
         let provenance = path.as_ref().display().to_string();
 
         // Load and deserialize the semantic convention registry
-        let semconv_file = File::open(path).map_err(|e| Error::RegistryNotFound {
+        let content = std::fs::read_to_string(path).map_err(|e| Error::RegistryNotFound {
             path_or_url: provenance.clone(),
             error: e.to_string(),
         })?;
-        let semconv_spec: SemConvSpec = serde_yaml::from_reader(BufReader::new(semconv_file))
-            .map_err(|e| Error::InvalidSemConvSpec {
-                path_or_url: provenance.clone(),
-                line: e.location().map(|loc| loc.line()),
-                column: e.location().map(|loc| loc.column()),
-                error: e.to_string(),
-            })?;
+        let semconv_spec = parse_semconv_spec(&content).map_err(|e| Error::InvalidSemConvSpec {
+            path_or_url: provenance.clone(),
+            line: e.location().map(|loc| loc.line()),
+            column: e.location().map(|loc| loc.column()),
+            error: e.to_string(),
+        })?;
 
         // Important note: the resolution process expects this step of validation to be done for
         // each semantic convention spec.
@@ -70,13 +69,12 @@ impl SemConvSpec {
     ///
     /// The [`SemConvSpec`] or an [`Error`] if the semantic convention spec is invalid.
     pub fn from_string(spec: &str) -> Result<SemConvSpec, Error> {
-        let semconv_spec: SemConvSpec =
-            serde_yaml::from_str(spec).map_err(|e| Error::InvalidSemConvSpec {
-                path_or_url: "<str>".to_owned(),
-                line: None,
-                column: None,
-                error: e.to_string(),
-            })?;
+        let semconv_spec = parse_semconv_spec(spec).map_err(|e| Error::InvalidSemConvSpec {
+            path_or_url: "<str>".to_owned(),
+            line: e.location().map(|loc| loc.line()),
+            column: e.location().map(|loc| loc.column()),
+            error: e.to_string(),
+        })?;
 
         // Important note: the resolution process expects this step of validation to be done for
         // each semantic convention spec.
@@ -93,24 +91,30 @@ impl SemConvSpec {
     /// # Returns
     ///
     /// The [`SemConvSpec`] or an [`Error`] if the semantic convention spec is invalid.
+    #[cfg(feature = "http")]
     pub fn from_url(semconv_url: &str) -> Result<SemConvSpec, Error> {
         // Create a content reader from the semantic convention URL
-        let reader = ureq::get(semconv_url)
+        let not_found = |error: String| Error::RegistryNotFound {
+            path_or_url: semconv_url.to_owned(),
+            error,
+        };
+        let mut content = String::new();
+        _ = weaver_common::http::agent(semconv_url)
+            .map_err(not_found)?
+            .get(semconv_url)
             .call()
-            .map_err(|e| Error::RegistryNotFound {
-                path_or_url: semconv_url.to_owned(),
-                error: e.to_string(),
-            })?
-            .into_reader();
-
-        // Deserialize the telemetry schema from the content reader
-        let semconv_spec: SemConvSpec =
-            serde_yaml::from_reader(reader).map_err(|e| Error::InvalidSemConvSpec {
-                path_or_url: semconv_url.to_owned(),
-                line: e.location().map(|loc| loc.line()),
-                column: e.location().map(|loc| loc.column()),
-                error: e.to_string(),
-            })?;
+            .map_err(|e| not_found(e.to_string()))?
+            .into_reader()
+            .read_to_string(&mut content)
+            .map_err(|e| not_found(e.to_string()))?;
+
+        // Deserialize the telemetry schema from the content
+        let semconv_spec = parse_semconv_spec(&content).map_err(|e| Error::InvalidSemConvSpec {
+            path_or_url: semconv_url.to_owned(),
+            line: e.location().map(|loc| loc.line()),
+            column: e.location().map(|loc| loc.column()),
+            error: e.to_string(),
+        })?;
 
         // Important note: the resolution process expects this step of validation to be done for
         // each semantic convention spec.
@@ -118,6 +122,18 @@ impl SemConvSpec {
         Ok(semconv_spec)
     }
 
+    /// Returns the groups defined in the semantic convention spec.
+    #[must_use]
+    pub fn groups(&self) -> &[GroupSpec] {
+        &self.groups
+    }
+
+    /// Returns a mutable reference to the groups defined in the semantic
+    /// convention spec.
+    pub fn groups_mut(&mut self) -> &mut Vec<GroupSpec> {
+        &mut self.groups
+    }
+
     fn validate(&self, provenance: &str) -> Result<(), Error> {
         let errors: Vec<Error> = self
             .groups
@@ -130,6 +146,22 @@ impl SemConvSpec {
     }
 }
 
+/// Deserializes a semantic convention spec from its YAML content. The anchors
+/// and aliases are resolved by the YAML parser, and the merge keys (`<<`) are
+/// applied before the deserialization (and therefore before the validation)
+/// of the spec.
+///
+/// The location of the deserialization errors is only known for the files
+/// without merge keys.
+fn parse_semconv_spec(content: &str) -> Result<SemConvSpec, serde_yaml::Error> {
+    if !content.contains("<<") {
+        return serde_yaml::from_str(content);
+    }
+    let mut value: serde_yaml::Value = serde_yaml::from_str(content)?;
+    value.apply_merge()?;
+    serde_yaml::from_value(value)
+}
+
 impl SemConvSpecWithProvenance {
     /// Creates a semantic convention spec with provenance from a file.
     ///
@@ -196,6 +228,82 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn test_semconv_spec_with_anchors_and_merge_keys() {
+        let spec = r#"
+        groups:
+          - id: "group1"
+            brief: &brief "Shared description"
+            attributes:
+              - &base_attr
+                id: "attr1"
+                brief: *brief
+                type: "string"
+                examples: ["example1"]
+                stability: experimental
+              - <<: *base_attr
+                id: "attr2"
+                type: "int"
+                examples: [42]
+          - id: "group2"
+            brief: *brief
+            attributes:
+              - <<: [*base_attr, {note: "A note."}]
+                id: "attr3"
+        "#;
+        let semconv_spec = SemConvSpec::from_string(spec).unwrap();
+        let attributes: Vec<_> = semconv_spec
+            .groups
+            .iter()
+            .flat_map(|group| group.attributes.iter())
+            .map(|attr| serde_yaml::to_value(attr).unwrap())
+            .collect();
+        assert_eq!(semconv_spec.groups[1].brief, "Shared description");
+        assert_eq!(attributes.len(), 3);
+        // The aliased node is copied, and the explicit keys override the merged ones.
+        assert_eq!(attributes[0]["brief"], "Shared description");
+        assert_eq!(attributes[1]["id"], "attr2");
+        assert_eq!(attributes[1]["type"], "int");
+        assert_eq!(attributes[1]["brief"], "Shared description");
+        assert_eq!(attributes[1]["stability"], "experimental");
+        assert_eq!(attributes[2]["id"], "attr3");
+        assert_eq!(attributes[2]["type"], "string");
+        assert_eq!(attributes[2]["note"], "A note.");
+
+        // The merged attributes are validated.
+        let spec = r#"
+        groups:
+          - id: "group1"
+            brief: "description1"
+            attributes:
+              - &base_attr
+                id: "attr1"
+                brief: "description1"
+                type: "int"
+              - <<: *base_attr
+                id: "attr2"
+                type: "string"
+        "#;
+        assert!(matches!(
+            SemConvSpec::from_string(spec).unwrap_err(),
+            InvalidAttribute { attribute_id, .. } if attribute_id == "attr2"
+        ));
+
+        // A merge key must reference a mapping or a sequence of mappings.
+        let spec = r#"
+        groups:
+          - id: "group1"
+            brief: "description1"
+            attributes:
+              - <<: "not a mapping"
+                id: "attr1"
+        "#;
+        assert!(matches!(
+            SemConvSpec::from_string(spec).unwrap_err(),
+            InvalidSemConvSpec { .. }
+        ));
+    }
+
     #[test]
     fn test_semconv_spec_from_string() {
         // Valid spec
@@ -228,7 +336,11 @@ mod tests {
         assert!(semconv_spec.is_err());
         assert!(matches!(
             semconv_spec.unwrap_err(),
-            InvalidSemConvSpec { .. }
+            InvalidSemConvSpec {
+                line: Some(_),
+                column: Some(_),
+                ..
+            }
         ));
 
         // Invalid spec
@@ -282,6 +394,7 @@ mod tests {
     }
 
     #[test]
+    #[cfg(feature = "http")]
     fn test_semconv_spec_from_url() {
         // Existing URL. The URL is a raw file from the semantic conventions repository.
         // This file is expected to be available.
@@ -0,0 +1,51 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Sensitivity classification specification.
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter};
+
+/// The sensitivity of the values of an attribute, used to drive privacy
+/// reviews. The levels are ordered from the least to the most sensitive.
+#[derive(
+    Serialize, Deserialize, Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Hash, JsonSchema,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum Sensitivity {
+    /// The values are not sensitive.
+    None,
+    /// The values are mildly sensitive (e.g. internal host names).
+    Low,
+    /// The values may contain personally identifiable information.
+    Pii,
+    /// The values are secrets (e.g. credentials, tokens) and must not be
+    /// recorded.
+    Secret,
+}
+
+/// Implements a human readable display for the sensitivity.
+impl Display for Sensitivity {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Sensitivity::None => write!(f, "none"),
+            Sensitivity::Low => write!(f, "low"),
+            Sensitivity::Pii => write!(f, "pii"),
+            Sensitivity::Secret => write!(f, "secret"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        assert_eq!(Sensitivity::None.to_string(), "none");
+        assert_eq!(Sensitivity::Low.to_string(), "low");
+        assert_eq!(Sensitivity::Pii.to_string(), "pii");
+        assert_eq!(Sensitivity::Secret.to_string(), "secret");
+        assert!(Sensitivity::Pii < Sensitivity::Secret);
+    }
+}
@@ -302,7 +302,7 @@ impl<'a> AttributeView<'a> {
                 out,
                 "{} {}",
                 self.attribute.brief.trim(),
-                ctx.add_note(self.attribute.note.clone())
+                ctx.add_note(self.attribute.note.to_string())
             )?;
             Ok(())
         }
@@ -21,6 +21,7 @@ use weaver_resolved_schema::ResolvedTelemetrySchema;
 use weaver_resolver::SchemaResolver;
 use weaver_semconv::path::RegistryPath;
 use weaver_semconv::registry::SemConvRegistry;
+use weaver_semconv::semconv::SemConvSpec;
 
 use crate::gen::{AttributeTableView, GenerateMarkdownContext, MetricView};
 
@@ -360,6 +361,19 @@ impl SnippetGenerator {
         })
     }
 
+    /// Resolve already loaded semconv specifications (e.g. merged from several
+    /// registries), and make them available for rendering.
+    pub fn try_from_semconv_specs(
+        semconv_specs: Vec<(String, SemConvSpec)>,
+        template_engine: Option<TemplateEngine>,
+    ) -> Result<SnippetGenerator, Error> {
+        let registry = ResolvedSemconvRegistry::try_from_semconv_specs(semconv_specs)?;
+        Ok(SnippetGenerator {
+            lookup: registry,
+            template_engine,
+        })
+    }
+
     // Used in tests
     #[allow(dead_code)]
     fn try_from_path(
@@ -389,8 +403,15 @@ impl ResolvedSemconvRegistry {
         registry_path: RegistryPath,
         cache: &Cache,
     ) -> Result<ResolvedSemconvRegistry, Error> {
-        let registry_id = "semantic_conventions";
         let semconv_specs = SchemaResolver::load_semconv_specs(&registry_path, cache)?;
+        Self::try_from_semconv_specs(semconv_specs)
+    }
+
+    /// Resolve semconv specifications, and make them available for rendering.
+    fn try_from_semconv_specs(
+        semconv_specs: Vec<(String, SemConvSpec)>,
+    ) -> Result<ResolvedSemconvRegistry, Error> {
+        let registry_id = "semantic_conventions";
         let mut registry = SemConvRegistry::from_semconv_specs(registry_id, semconv_specs);
         let schema = SchemaResolver::resolve_semantic_convention_registry(&mut registry)?;
         let lookup = ResolvedSemconvRegistry {
@@ -10,6 +10,8 @@ use std::path::Path;
 
 use serde::{Deserialize, Serialize};
 
+use crate::all_change::AllChange;
+use crate::all_version::AllVersion;
 use crate::logs_change::LogsChange;
 use crate::logs_version::LogsVersion;
 use crate::metrics_change::MetricsChange;
@@ -19,6 +21,8 @@ use crate::resource_version::ResourceVersion;
 use crate::spans_change::SpansChange;
 use crate::spans_version::SpansVersion;
 
+pub mod all_change;
+pub mod all_version;
 pub mod logs_change;
 pub mod logs_version;
 pub mod metrics_change;
@@ -67,16 +71,23 @@ pub struct Versions {
 }
 
 /// An history of changes to apply to the schema for different versions.
-#[derive(Serialize, Deserialize, Debug, Clone, JsonSchema)]
+#[derive(Serialize, Deserialize, Debug, Default, Clone, JsonSchema)]
 #[serde(deny_unknown_fields)]
 pub struct VersionSpec {
+    /// The changes to apply to all the signals for a specific version.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub all: Option<AllVersion>,
     /// The changes to apply to the metrics specification for a specific version.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub metrics: Option<MetricsVersion>,
     /// The changes to apply to the logs specification for a specific version.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub logs: Option<LogsVersion>,
     /// The changes to apply to the spans specification for a specific version.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub spans: Option<SpansVersion>,
     /// The changes to apply to the resource specification for a specific version.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub resources: Option<ResourceVersion>,
 }
 
@@ -176,10 +187,31 @@ impl Versions {
             .collect()
     }
 
+    /// Returns a vector of tuples containing the versions and their corresponding changes
+    /// in ascending order, for the versions greater than `from` and lower than or equal
+    /// to `to`.
+    #[must_use]
+    pub fn versions_between(
+        &self,
+        from: &semver::Version,
+        to: &semver::Version,
+    ) -> Vec<(&semver::Version, &VersionSpec)> {
+        self.versions
+            .iter()
+            .filter(|(v, _)| *v > from && *v <= to)
+            .collect()
+    }
+
+    /// Returns true if the given version is defined.
+    #[must_use]
+    pub fn contains(&self, version: &semver::Version) -> bool {
+        self.versions.contains_key(version)
+    }
+
     /// Returns the changes to apply for the given version including the changes
     /// of the previous versions.
     /// The current supported changes are:
-    /// - Renaming of attributes (for resources, logs and spans)
+    /// - Renaming of attributes (for all the signals, resources, metrics, logs and spans)
     /// - Renaming of metrics
     #[must_use]
     pub fn version_changes_for(&self, version: &Version) -> VersionChanges {
@@ -262,6 +294,26 @@ impl Versions {
                         }
                     });
             }
+            // Attributes renamed for all the signals apply to the resources, metrics,
+            // logs and spans unless a signal-specific renaming has been defined.
+            if let Some(all) = spec.all.as_ref() {
+                all.changes
+                    .iter()
+                    .flat_map(|change| change.rename_attributes.attribute_map.iter())
+                    .for_each(|(old_name, new_name)| {
+                        for old_to_new_attributes in [
+                            &mut resource_old_to_new_attributes,
+                            &mut metric_old_to_new_attributes,
+                            &mut log_old_to_new_attributes,
+                            &mut span_old_to_new_attributes,
+                        ] {
+                            if !old_to_new_attributes.contains_key(old_name) {
+                                _ = old_to_new_attributes
+                                    .insert(old_name.clone(), new_name.clone());
+                            }
+                        }
+                    });
+            }
         }
 
         VersionChanges {
@@ -288,6 +340,12 @@ impl Versions {
         }
     }
 
+    /// Adds the changes of a version, replacing the changes previously defined
+    /// for this version (if any).
+    pub fn insert(&mut self, version: semver::Version, spec: VersionSpec) {
+        _ = self.versions.insert(version, spec);
+    }
+
     /// Returns true if the `Versions` is empty.
     #[must_use]
     pub fn is_empty(&self) -> bool {
@@ -305,6 +363,50 @@ impl VersionSpec {
     /// Update the current `VersionSpec` to include the transformations of the parent `VersionSpec`.
     /// Transformations of the current `VersionSpec` take precedence over the parent `VersionSpec`.
     pub fn extend(&mut self, parent_spec: VersionSpec) {
+        // Process all the signals
+        if let Some(all) = parent_spec.all {
+            let mut all_change = AllChange::default();
+            for change in all.changes {
+                'next_parent_renaming: for (old, new) in change.rename_attributes.attribute_map {
+                    for local_change in self
+                        .all
+                        .get_or_insert_with(AllVersion::default)
+                        .changes
+                        .iter()
+                    {
+                        if local_change
+                            .rename_attributes
+                            .attribute_map
+                            .contains_key(&old)
+                        {
+                            // renaming already present in local changes, skip it
+                            continue 'next_parent_renaming;
+                        }
+                    }
+                    // renaming not found in local changes, add it
+                    _ = all_change.rename_attributes.attribute_map.insert(old, new);
+                }
+            }
+            if !all_change.rename_attributes.attribute_map.is_empty() {
+                if self
+                    .all
+                    .get_or_insert_with(AllVersion::default)
+                    .changes
+                    .is_empty()
+                {
+                    self.all
+                        .get_or_insert_with(AllVersion::default)
+                        .changes
+                        .push(all_change);
+                } else {
+                    self.all.get_or_insert_with(AllVersion::default).changes[0]
+                        .rename_attributes
+                        .attribute_map
+                        .extend(all_change.rename_attributes.attribute_map);
+                }
+            }
+        }
+
         // Process resources
         if let Some(resources) = parent_spec.resources {
             let mut resource_change = ResourceChange::default();
@@ -639,8 +741,8 @@ mod tests {
         let mut version = None;
 
         for (v, _) in versions.versions_asc() {
-            if version.is_some() {
-                assert!(v > version.unwrap());
+            if let Some(version) = version {
+                assert!(v > version);
             }
             version = Some(v);
         }
@@ -648,8 +750,8 @@ mod tests {
         let mut version = None;
 
         for (v, _) in versions.versions_desc() {
-            if version.is_some() {
-                assert!(v < version.unwrap());
+            if let Some(version) = version {
+                assert!(v < version);
             }
             version = Some(v);
         }
@@ -700,6 +802,24 @@ mod tests {
         );
         assert_eq!("metric_1", changes.get_metric_name("m1"));
         assert_eq!("metric_2", changes.get_metric_name("m2"));
+
+        // Test renaming of attributes for all the signals
+        assert_eq!(
+            "container.label",
+            changes.get_resource_attribute_name("container.labels")
+        );
+        assert_eq!(
+            "container.label",
+            changes.get_metric_attribute_name("container.labels")
+        );
+        assert_eq!(
+            "container.label",
+            changes.get_log_attribute_name("container.labels")
+        );
+        assert_eq!(
+            "container.label",
+            changes.get_span_attribute_name("container.labels")
+        );
     }
 
     #[test]
@@ -0,0 +1,44 @@
+// SPDX-License-Identifier: Apache-2.0
+
+/** Semantic convention files: path of each file to its YAML content. */
+export type SemConvFiles = Record<string, string> | Map<string, string>;
+
+/** A diagnostic message, as reported by `--diagnostic-format json`. */
+export interface DiagnosticMessage {
+  /** The serialized error. */
+  error: unknown;
+  /** The rendered diagnostic. */
+  diagnostic: {
+    message: string;
+    ansi_message: string;
+    code?: string;
+    severity?: "Advice" | "Warning" | "Error";
+    help?: string;
+    url?: string;
+  };
+}
+
+/** Error thrown when a registry cannot be resolved. */
+export class WeaverError extends Error {
+  readonly diagnostics: DiagnosticMessage[];
+}
+
+/** Sources from which the WebAssembly module can be loaded. */
+export type WeaverSource =
+  | Response
+  | Promise<Response>
+  | WebAssembly.Module
+  | BufferSource;
+
+/** An instance of the weaver WebAssembly module. */
+export class Weaver {
+  static load(source: WeaverSource): Promise<Weaver>;
+  /** Returns the resolved registry in JSON (`weaver registry resolve --format json`). */
+  resolveRegistry(files: SemConvFiles): string;
+}
+
+/** Loads the module used by `resolveRegistry`. */
+export function init(source?: WeaverSource): Promise<Weaver>;
+
+/** Resolves a registry with the module loaded by `init`. */
+export function resolveRegistry(files: SemConvFiles): string;
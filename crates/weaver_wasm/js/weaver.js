@@ -0,0 +1,109 @@
+// SPDX-License-Identifier: Apache-2.0
+
+// JavaScript bindings of the weaver semantic convention resolver compiled to
+// WebAssembly (see the README of the `weaver_wasm` crate for the interface of
+// the module).
+
+const STATUS_RESOLVED = 0;
+const HEADER_LENGTH = 5;
+
+const encoder = new TextEncoder();
+const decoder = new TextDecoder();
+
+/**
+ * Error thrown when a registry cannot be resolved. The diagnostic messages
+ * are the ones reported by `weaver registry resolve --diagnostic-format json`.
+ */
+export class WeaverError extends Error {
+  constructor(diagnostics) {
+    super(diagnostics.map((d) => d.diagnostic.message).join("\n"));
+    this.name = "WeaverError";
+    this.diagnostics = diagnostics;
+  }
+}
+
+/**
+ * An instance of the weaver WebAssembly module.
+ */
+export class Weaver {
+  #exports;
+
+  constructor(instance) {
+    this.#exports = instance.exports;
+  }
+
+  /**
+   * Instantiates the module from a `Response` (or a promise of one), a
+   * `WebAssembly.Module`, or the bytes of the module.
+   */
+  static async load(source) {
+    const resolved = await source;
+    if (resolved instanceof WebAssembly.Module) {
+      return new Weaver(await WebAssembly.instantiate(resolved, {}));
+    }
+    const bytes =
+      typeof Response !== "undefined" && resolved instanceof Response
+        ? await resolved.arrayBuffer()
+        : resolved;
+    const { instance } = await WebAssembly.instantiate(bytes, {});
+    return new Weaver(instance);
+  }
+
+  /**
+   * Resolves a registry from the content of its semantic convention files
+   * (an object or a `Map` from file paths to YAML contents) and returns the
+   * resolved registry in JSON. Throws a `WeaverError` if the files are
+   * invalid or cannot be resolved.
+   */
+  resolveRegistry(files) {
+    const exports = this.#exports;
+    const input = encoder.encode(
+      JSON.stringify(files instanceof Map ? Object.fromEntries(files) : files),
+    );
+
+    const inputPtr = exports.weaver_alloc(input.length) >>> 0;
+    new Uint8Array(exports.memory.buffer, inputPtr, input.length).set(input);
+    const outputPtr = exports.weaver_resolve_registry(inputPtr, input.length) >>> 0;
+    exports.weaver_dealloc(inputPtr, input.length);
+
+    // The memory may have grown during the resolution, the views are created
+    // after the call.
+    const view = new DataView(exports.memory.buffer);
+    const length = view.getUint32(outputPtr, true);
+    const status = view.getUint8(outputPtr + 4);
+    const payload = decoder.decode(
+      new Uint8Array(exports.memory.buffer, outputPtr + HEADER_LENGTH, length),
+    );
+    exports.weaver_dealloc(outputPtr, length + HEADER_LENGTH);
+
+    if (status !== STATUS_RESOLVED) {
+      throw new WeaverError(JSON.parse(payload));
+    }
+    return payload;
+  }
+}
+
+let defaultWeaver;
+
+/**
+ * Loads the module used by `resolveRegistry`. By default, the module is
+ * fetched next to this file (browsers); in Node.js, pass the bytes of
+ * `weaver_wasm.wasm`.
+ */
+export async function init(source) {
+  defaultWeaver = await Weaver.load(
+    source ?? fetch(new URL("./weaver_wasm.wasm", import.meta.url)),
+  );
+  return defaultWeaver;
+}
+
+/**
+ * Resolves a registry with the module loaded by `init`. See
+ * `Weaver.resolveRegistry`.
+ */
+export function resolveRegistry(files) {
+  if (defaultWeaver === undefined) {
+    throw new Error("The weaver module is not loaded, call `init` first.");
+  }
+  return defaultWeaver.resolveRegistry(files);
+}
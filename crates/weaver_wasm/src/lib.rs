@@ -0,0 +1,239 @@
+// SPDX-License-Identifier: Apache-2.0
+
+#![doc = include_str!("../README.md")]
+
+use std::collections::BTreeMap;
+
+use miette::Diagnostic;
+use serde::Serialize;
+
+use weaver_common::diagnostic::DiagnosticMessages;
+use weaver_forge::registry::ResolvedRegistry;
+use weaver_resolver::SchemaResolver;
+use weaver_semconv::registry::SemConvRegistry;
+
+/// Id of the resolved registry.
+const REGISTRY_ID: &str = "default";
+
+/// Status byte of an output buffer containing the resolved registry.
+const STATUS_RESOLVED: u8 = 0;
+
+/// Status byte of an output buffer containing diagnostic messages.
+const STATUS_DIAGNOSTICS: u8 = 1;
+
+/// An error that can occur while reading the input or writing the output of
+/// the WebAssembly interface.
+#[derive(thiserror::Error, Debug, Clone, Serialize, Diagnostic)]
+#[non_exhaustive]
+pub enum Error {
+    /// The input is not a JSON object mapping file paths to file contents.
+    #[error("Invalid input, expected a JSON object mapping the path of each semantic convention file to its content, error: {error}")]
+    InvalidInput {
+        /// The error that occurred.
+        error: String,
+    },
+
+    /// The registry is missing from the resolved schema.
+    #[error("The registry `{registry_id}` was not resolved.")]
+    RegistryNotResolved {
+        /// The id of the registry.
+        registry_id: String,
+    },
+
+    /// The resolved registry could not be serialized.
+    #[error("Failed to serialize the resolved registry, error: {error}")]
+    SerializationFailed {
+        /// The error that occurred.
+        error: String,
+    },
+}
+
+/// Parses and resolves a registry from the content of its semantic convention
+/// files, given as `(path, content)` pairs. The paths are only used as the
+/// provenance of the groups.
+///
+/// The errors of all the files are reported, as in `weaver registry resolve`.
+pub fn resolve_registry<'a>(
+    files: impl IntoIterator<Item = (&'a str, &'a str)>,
+) -> Result<ResolvedRegistry, DiagnosticMessages> {
+    let mut registry = SemConvRegistry::new(REGISTRY_ID);
+    let mut diag_msgs = DiagnosticMessages::empty();
+    for (path, content) in files {
+        if let Err(e) = registry.add_semconv_spec_from_string(path, content) {
+            diag_msgs.extend(e.into());
+        }
+    }
+    if !diag_msgs.is_empty() {
+        return Err(diag_msgs);
+    }
+
+    let schema = SchemaResolver::resolve_semantic_convention_registry(&mut registry)?;
+    let registry = schema.registry(REGISTRY_ID).ok_or_else(|| {
+        DiagnosticMessages::from_error(Error::RegistryNotResolved {
+            registry_id: REGISTRY_ID.to_owned(),
+        })
+    })?;
+    Ok(ResolvedRegistry::try_from_resolved_registry(
+        registry,
+        schema.catalog(),
+    )?)
+}
+
+/// Resolves a registry given as a JSON object mapping the path of each
+/// semantic convention file to its content, and returns the resolved registry
+/// in JSON.
+pub fn resolve_registry_json(files: &str) -> Result<String, DiagnosticMessages> {
+    let files: BTreeMap<String, String> = serde_json::from_str(files).map_err(|e| {
+        DiagnosticMessages::from_error(Error::InvalidInput {
+            error: e.to_string(),
+        })
+    })?;
+    let registry = resolve_registry(
+        files
+            .iter()
+            .map(|(path, content)| (path.as_str(), content.as_str())),
+    )?;
+    serde_json::to_string(&registry).map_err(|e| {
+        DiagnosticMessages::from_error(Error::SerializationFailed {
+            error: e.to_string(),
+        })
+    })
+}
+
+/// Allocates a buffer of `len` bytes in the memory of the module. The host
+/// writes the input of [`weaver_resolve_registry`] in this buffer and frees
+/// it with [`weaver_dealloc`].
+#[no_mangle]
+pub extern "C" fn weaver_alloc(len: usize) -> *mut u8 {
+    Box::into_raw(vec![0u8; len].into_boxed_slice()).cast::<u8>()
+}
+
+/// Frees a buffer allocated by [`weaver_alloc`] or returned by
+/// [`weaver_resolve_registry`].
+///
+/// # Safety
+///
+/// `ptr` and `len` must be the address and the length of a buffer allocated
+/// by [`weaver_alloc`] or returned by [`weaver_resolve_registry`] (payload
+/// length plus 5 bytes), and the buffer must not be used after this call.
+#[no_mangle]
+pub unsafe extern "C" fn weaver_dealloc(ptr: *mut u8, len: usize) {
+    // SAFETY: the buffer was allocated as a boxed slice of `len` bytes.
+    drop(unsafe { Box::from_raw(std::ptr::slice_from_raw_parts_mut(ptr, len)) });
+}
+
+/// Resolves the registry described by the input buffer (see
+/// [`resolve_registry_json`]) and returns a new buffer containing the length
+/// of the payload (little-endian `u32`), a status byte, and the payload: the
+/// resolved registry in JSON (status 0) or the diagnostic messages in JSON
+/// (status 1). The input buffer is not freed.
+///
+/// # Safety
+///
+/// `ptr` and `len` must be the address and the length of a buffer allocated
+/// by [`weaver_alloc`] and containing UTF-8 text.
+#[no_mangle]
+pub unsafe extern "C" fn weaver_resolve_registry(ptr: *const u8, len: usize) -> *mut u8 {
+    // SAFETY: the buffer was allocated by `weaver_alloc` with `len` bytes.
+    let input = String::from_utf8_lossy(unsafe { std::slice::from_raw_parts(ptr, len) });
+    let (status, payload) = match resolve_registry_json(&input) {
+        Ok(registry) => (STATUS_RESOLVED, registry),
+        Err(diag_msgs) => (
+            STATUS_DIAGNOSTICS,
+            serde_json::to_string(&diag_msgs).expect("Failed to serialize the diagnostics"),
+        ),
+    };
+
+    let mut output = Vec::with_capacity(payload.len() + 5);
+    output.extend_from_slice(&(payload.len() as u32).to_le_bytes());
+    output.push(status);
+    output.extend_from_slice(payload.as_bytes());
+    Box::into_raw(output.into_boxed_slice()).cast::<u8>()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HTTP: &str = r#"
+groups:
+  - id: registry.http
+    type: attribute_group
+    brief: HTTP attributes.
+    attributes:
+      - id: http.request.method
+        type: string
+        stability: stable
+        brief: HTTP request method.
+        examples: ["GET", "POST"]
+"#;
+
+    const SPAN: &str = r#"
+groups:
+  - id: span.http.client
+    type: span
+    span_kind: client
+    stability: stable
+    brief: HTTP client span.
+    attributes:
+      - ref: http.request.method
+        requirement_level: required
+"#;
+
+    #[test]
+    fn test_resolve_registry() {
+        let registry = resolve_registry([("http.yaml", HTTP), ("span.yaml", SPAN)])
+            .expect("Failed to resolve the registry");
+        let span = registry
+            .groups
+            .iter()
+            .find(|group| group.id == "span.http.client")
+            .expect("Span not found");
+        assert_eq!(span.attributes.len(), 1);
+        assert_eq!(span.attributes[0].name, "http.request.method");
+
+        let diag_msgs =
+            resolve_registry([("span.yaml", SPAN)]).expect_err("Unresolved reference expected");
+        assert!(!diag_msgs.is_empty());
+
+        let diag_msgs = resolve_registry([("invalid.yaml", "groups: 1"), ("span.yaml", "{")])
+            .expect_err("Invalid files expected");
+        assert_eq!(diag_msgs.len(), 2);
+    }
+
+    #[test]
+    fn test_wasm_interface() {
+        fn call(input: &str) -> (u8, serde_json::Value) {
+            let ptr = weaver_alloc(input.len());
+            // SAFETY: the buffers are allocated by the module and freed once.
+            unsafe {
+                std::slice::from_raw_parts_mut(ptr, input.len()).copy_from_slice(input.as_bytes());
+                let output = weaver_resolve_registry(ptr, input.len());
+                weaver_dealloc(ptr, input.len());
+
+                let mut len = [0u8; 4];
+                len.copy_from_slice(std::slice::from_raw_parts(output, 4));
+                let len = u32::from_le_bytes(len) as usize;
+                let output_buffer = std::slice::from_raw_parts(output, len + 5);
+                let result = (
+                    output_buffer[4],
+                    serde_json::from_slice(&output_buffer[5..]).expect("Invalid JSON payload"),
+                );
+                weaver_dealloc(output, len + 5);
+                result
+            }
+        }
+
+        let input = serde_json::json!({"http.yaml": HTTP, "span.yaml": SPAN}).to_string();
+        let (status, registry) = call(&input);
+        assert_eq!(status, STATUS_RESOLVED);
+        assert_eq!(registry["groups"].as_array().map(Vec::len), Some(2));
+
+        let (status, diagnostics) = call("[]");
+        assert_eq!(status, STATUS_DIAGNOSTICS);
+        assert!(diagnostics[0]["diagnostic"]["message"]
+            .as_str()
+            .expect("Message expected")
+            .starts_with("Invalid input"));
+    }
+}
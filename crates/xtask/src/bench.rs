@@ -0,0 +1,156 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Tasks related to the benchmarks.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use serde::{Deserialize, Serialize};
+
+/// Path of the committed baselines of the resolution benchmarks.
+const BASELINES_PATH: &str = "crates/weaver_resolver/benches/baselines.json";
+
+/// Committed baselines of the benchmarks.
+#[derive(Serialize, Deserialize)]
+struct Baselines {
+    /// Maximum accepted slowdown compared to the baselines (e.g. 0.25 for 25%).
+    tolerance: f64,
+    /// Time per iteration, in nanoseconds, of each benchmark.
+    benchmarks: BTreeMap<String, f64>,
+}
+
+/// Runs the resolution benchmarks and compares their time per iteration with the
+/// committed baselines. Fails if a benchmark exceeds its baseline by more than
+/// the tolerance (the performance budget).
+///
+/// With `--save-baseline`, the committed baselines are replaced by the results
+/// of the run instead.
+#[cfg(not(tarpaulin_include))]
+pub fn run(args: &[String]) -> anyhow::Result<()> {
+    let save_baseline = args.iter().any(|arg| arg == "--save-baseline");
+
+    let status = Command::new(std::env::var("CARGO").unwrap_or_else(|_| "cargo".to_owned()))
+        .args(["bench", "-p", "weaver_resolver", "--bench", "resolution"])
+        .status()?;
+    if !status.success() {
+        anyhow::bail!("The benchmarks failed ({})", status);
+    }
+
+    let results = read_results(&criterion_dir())?;
+    let mut baselines: Baselines = serde_json::from_str(&std::fs::read_to_string(BASELINES_PATH)?)?;
+
+    if save_baseline {
+        baselines.benchmarks = results;
+        let mut json = serde_json::to_string_pretty(&baselines)?;
+        json.push('\n');
+        std::fs::write(BASELINES_PATH, json)?;
+        println!("Baselines saved in {}", BASELINES_PATH);
+        return Ok(());
+    }
+
+    let mut regressions = 0;
+    println!();
+    println!(
+        "{:<40} {:>12} {:>12} {:>9}",
+        "benchmark", "baseline", "current", "change"
+    );
+    for (id, current) in &results {
+        let Some(baseline) = baselines.benchmarks.get(id) else {
+            println!(
+                "{:<40} {:>12} {:>12} {:>9}",
+                id,
+                "-",
+                format_ns(*current),
+                "-"
+            );
+            continue;
+        };
+        let change = current / baseline - 1.0;
+        let over_budget = change > baselines.tolerance;
+        if over_budget {
+            regressions += 1;
+        }
+        println!(
+            "{:<40} {:>12} {:>12} {:>+8.1}%{}",
+            id,
+            format_ns(*baseline),
+            format_ns(*current),
+            change * 100.0,
+            if over_budget { "  over budget" } else { "" }
+        );
+    }
+
+    if regressions > 0 {
+        anyhow::bail!(
+            "{} benchmark(s) are more than {:.0}% slower than their baseline",
+            regressions,
+            baselines.tolerance * 100.0
+        );
+    }
+    println!();
+    println!("All the benchmarks are within the performance budget.");
+    Ok(())
+}
+
+/// Returns the directory where Criterion stores its results.
+fn criterion_dir() -> PathBuf {
+    std::env::var_os("CARGO_TARGET_DIR")
+        .map_or_else(|| PathBuf::from("target"), PathBuf::from)
+        .join("criterion")
+}
+
+/// Reads the time per iteration of the last run of each benchmark stored in the
+/// Criterion directory, indexed by benchmark id (e.g. `synthetic/resolve`).
+fn read_results(dir: &Path) -> anyhow::Result<BTreeMap<String, f64>> {
+    #[derive(Deserialize)]
+    struct Benchmark {
+        full_id: String,
+    }
+    #[derive(Deserialize)]
+    struct Estimate {
+        point_estimate: f64,
+    }
+    #[derive(Deserialize)]
+    struct Estimates {
+        mean: Estimate,
+        slope: Option<Estimate>,
+    }
+
+    let mut results = BTreeMap::new();
+    let mut dirs = vec![dir.to_path_buf()];
+    while let Some(dir) = dirs.pop() {
+        for entry in std::fs::read_dir(&dir)? {
+            let path = entry?.path();
+            if !path.is_dir() {
+                continue;
+            }
+            if path.file_name().is_some_and(|name| name == "new") {
+                let benchmark: Benchmark =
+                    serde_json::from_str(&std::fs::read_to_string(path.join("benchmark.json"))?)?;
+                let estimates: Estimates =
+                    serde_json::from_str(&std::fs::read_to_string(path.join("estimates.json"))?)?;
+                // The slope is the estimate reported by Criterion for the
+                // benchmarks sampled linearly.
+                let estimate = estimates.slope.unwrap_or(estimates.mean);
+                _ = results.insert(benchmark.full_id, estimate.point_estimate.round());
+            } else {
+                dirs.push(path);
+            }
+        }
+    }
+    Ok(results)
+}
+
+/// Formats a duration in nanoseconds with a human readable unit.
+fn format_ns(ns: f64) -> String {
+    if ns >= 1e9 {
+        format!("{:.2} s", ns / 1e9)
+    } else if ns >= 1e6 {
+        format!("{:.2} ms", ns / 1e6)
+    } else if ns >= 1e3 {
+        format!("{:.2} µs", ns / 1e3)
+    } else {
+        format!("{:.0} ns", ns)
+    }
+}
@@ -10,16 +10,19 @@
 #![allow(clippy::print_stdout)]
 #![allow(clippy::print_stderr)]
 
+mod bench;
 mod validate;
 
 #[cfg(not(tarpaulin_include))]
 fn main() -> anyhow::Result<()> {
-    let task = std::env::args().nth(1);
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let task = args.first().cloned();
 
     match task {
         None => print_help(),
         Some(task) => match task.as_str() {
             "validate" => validate::run(),
+            "bench" => bench::run(&args[1..]),
             "help" => print_help(),
             _ => {
                 eprintln!("Unknown task: {}", task);
@@ -38,6 +41,8 @@ Usage: Execute the command using `cargo xtask <task>`, e.g., `cargo xtask valida
 
 Tasks:
   - validate: Validate the entire structure of the weaver project.
+  - bench: Run the resolution benchmarks and check them against the committed
+    baselines (`--save-baseline` to update the baselines).
 "
     );
     Ok(())
@@ -0,0 +1,31 @@
+// Client-side search over the index defined in `search-index.js`.
+(function () {
+  const input = document.getElementById("search");
+  const results = document.getElementById("search-results");
+  if (!input || !results || typeof WEAVER_SEARCH_INDEX === "undefined") {
+    return;
+  }
+  input.addEventListener("input", function () {
+    const query = input.value.trim().toLowerCase();
+    results.innerHTML = "";
+    if (query.length < 2) {
+      return;
+    }
+    const matches = WEAVER_SEARCH_INDEX.filter(function (entry) {
+      return entry.name.toLowerCase().includes(query) ||
+        (entry.brief || "").toLowerCase().includes(query);
+    }).slice(0, 50);
+    for (const entry of matches) {
+      const item = document.createElement("li");
+      const link = document.createElement("a");
+      link.href = entry.href;
+      link.textContent = entry.name;
+      const kind = document.createElement("span");
+      kind.className = "kind";
+      kind.textContent = entry.kind;
+      item.appendChild(link);
+      item.appendChild(kind);
+      results.appendChild(item);
+    }
+  });
+})();
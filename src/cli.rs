@@ -2,9 +2,19 @@
 
 //! Manage command line arguments
 
+use crate::completion::CompletionArgs;
 use crate::diagnostic::DiagnosticCommand;
+use crate::emit::EmitArgs;
+use crate::live_check::LiveCheckArgs;
+use crate::lsp::LspArgs;
+use crate::migrate::MigrateArgs;
+use crate::policy::PolicyCommand;
 use crate::registry::RegistryCommand;
-use clap::{Parser, Subcommand};
+use crate::serve::ServeArgs;
+use crate::template::TemplateCommand;
+use clap::{Args, Parser, Subcommand};
+use std::time::Duration;
+use weaver_cache::{CachePolicy, DEFAULT_CACHE_TTL_SECS};
 
 /// Command line arguments.
 #[derive(Parser)]
@@ -25,11 +35,54 @@ pub struct Cli {
     #[arg(long, global = true)]
     pub quiet: bool,
 
+    /// Freshness of the registries fetched into the cache.
+    #[command(flatten)]
+    pub cache: CacheArgs,
+
     /// List of supported commands
     #[command(subcommand)]
     pub command: Option<Commands>,
 }
 
+/// Parameters controlling the freshness of the cached registries.
+#[derive(Args, Debug, Clone)]
+pub struct CacheArgs {
+    /// Fetch the registries again (Git repositories, OCI artifacts), ignoring
+    /// the cached copies.
+    #[arg(long, global = true, help_heading = "Cache options")]
+    pub refresh: bool,
+
+    /// Number of seconds during which a registry fetched from a Git branch or
+    /// an OCI tag is used from the cache without being fetched again. The
+    /// registries pinned by digest never expire.
+    #[arg(
+        long,
+        global = true,
+        default_value_t = DEFAULT_CACHE_TTL_SECS,
+        help_heading = "Cache options"
+    )]
+    pub cache_ttl: u64,
+}
+
+impl Default for CacheArgs {
+    fn default() -> Self {
+        Self {
+            refresh: false,
+            cache_ttl: DEFAULT_CACHE_TTL_SECS,
+        }
+    }
+}
+
+impl CacheArgs {
+    /// Returns the freshness policy of the cache.
+    pub(crate) fn policy(&self) -> CachePolicy {
+        CachePolicy {
+            refresh: self.refresh,
+            ttl: Duration::from_secs(self.cache_ttl),
+        }
+    }
+}
+
 /// Supported commands.
 #[derive(Subcommand)]
 pub enum Commands {
@@ -37,4 +90,50 @@ pub enum Commands {
     Registry(RegistryCommand),
     /// Manage Diagnostic Messages
     Diagnostic(DiagnosticCommand),
+    /// Check the telemetry emitted by an application against a semantic convention registry.
+    ///
+    /// The command listens for OTLP/gRPC and OTLP/HTTP requests and reports the attributes and metrics that are not defined in the registry, have the wrong type, or violate the requirement levels.
+    ///
+    /// The process exits with a code of 0 if the received telemetry complies with the registry.
+    #[clap(verbatim_doc_comment)]
+    LiveCheck(LiveCheckArgs),
+    /// Emit synthetic telemetry conforming to a semantic convention registry.
+    ///
+    /// The command synthesizes a span per span group, a metric per metric group, and a log record per event group, using the examples of the attributes and the members of the enums as values. The telemetry is sent to an OTLP/gRPC endpoint or written to stdout in OTLP/JSON, so backends and dashboards can be tested before any instrumentation exists.
+    ///
+    /// The process exits with a code of 0 if the telemetry is emitted successfully.
+    #[clap(verbatim_doc_comment)]
+    Emit(EmitArgs),
+    /// Migrate telemetry from one version of a telemetry schema to another.
+    ///
+    /// The attribute and metric renames of every version between the two versions of the telemetry schema file are applied to an OTLP file (OTLP/JSON or protobuf), renames being reverted when migrating to an older version. Without an input file, the transformations are printed to stdout.
+    ///
+    /// The process exits with a code of 0 if the migration is successful.
+    #[clap(verbatim_doc_comment)]
+    Migrate(MigrateArgs),
+    /// Serve one or more resolved semantic convention registries over HTTP.
+    ///
+    /// The server exposes a read-only REST API (`/registries`, `/groups`, `/groups/{id}`, `/attributes/{name}`, `/search?q=`, and `/resolved`) described by an OpenAPI document at `/openapi.json`, so developer portals can query the conventions without embedding weaver. The server runs until the process receives a Ctrl-C signal.
+    ///
+    /// Note: The `-d` and `--registry-git-sub-dir` options are only used when the registries are Git URLs otherwise these options are ignored.
+    #[clap(verbatim_doc_comment)]
+    Serve(ServeArgs),
+    /// Run a language server for authoring semantic convention registries.
+    ///
+    /// The server communicates over stdin/stdout with the Language Server Protocol and provides diagnostics (parsing and resolution errors), go-to-definition for the `ref`, `extends`, and `include` entries, hover documentation of the resolved attributes, and completion of the attribute and group ids.
+    ///
+    /// The registry must be a local directory, the workspace root of the editor by default.
+    #[clap(verbatim_doc_comment)]
+    Lsp(LspArgs),
+    /// Manage the Rego policies
+    Policy(PolicyCommand),
+    /// Manage the templates of a target
+    Template(TemplateCommand),
+    /// Generate the completion script of a shell.
+    ///
+    /// The script completes the commands, options, and values of weaver, including the built-in targets of `registry generate` and `template test` in bash and zsh, and the Git and OCI registries of the cache for the registry options in bash, zsh, and fish. For example, to enable the completion in bash:
+    ///
+    ///   source <(weaver completion bash)
+    #[clap(verbatim_doc_comment)]
+    Completion(CompletionArgs),
 }
@@ -0,0 +1,278 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Generate shell completion scripts.
+
+use std::any::TypeId;
+use std::io::Write;
+
+use clap::builder::PossibleValuesParser;
+use clap::{Args, Command, CommandFactory, ValueHint};
+use clap_complete::{generate, Shell};
+
+use weaver_cache::{Cache, CachePolicy};
+use weaver_common::diagnostic::DiagnosticMessages;
+use weaver_common::Logger;
+
+use crate::cli::Cli;
+use crate::registry::generate::builtin_targets;
+use crate::registry::RegistryPath;
+use crate::serve::NamedRegistry;
+use crate::{CmdResult, ExitDirectives};
+
+/// Parameters for the `completion` command
+#[derive(Debug, Args)]
+pub struct CompletionArgs {
+    /// Shell to generate the completion script for.
+    #[arg(value_enum, required_unless_present = "cached_registries")]
+    pub shell: Option<Shell>,
+
+    /// Print the registries of the cache if the last of the arguments given
+    /// after `--` is an option taking a registry. Used by the completion
+    /// scripts to complete the registries dynamically.
+    #[arg(long, hide = true)]
+    pub cached_registries: bool,
+
+    /// The arguments of the command line being completed, without `weaver`.
+    #[arg(last = true, hide = true)]
+    pub words: Vec<String>,
+}
+
+/// Print the completion script and return the exit code.
+#[cfg(not(tarpaulin_include))]
+pub fn completion(
+    log: impl Logger + Sync + Clone,
+    args: &CompletionArgs,
+    cache_policy: &CachePolicy,
+) -> CmdResult {
+    // Stdout is reserved for the completion script.
+    log.mute();
+    if args.cached_registries {
+        // The completion never fails, at worst nothing is suggested.
+        if let Ok(cache) = Cache::try_new_with_policy(*cache_policy) {
+            _ = write_cached_registries(&args.words, &cache, &mut std::io::stdout());
+        }
+        return CmdResult::new(
+            Ok(ExitDirectives {
+                exit_code: 0,
+                quiet_mode: true,
+            }),
+            None,
+        );
+    }
+    CmdResult::new(command(args, &mut std::io::stdout()), None)
+}
+
+/// Write the completion script of the given shell.
+pub(crate) fn command(
+    args: &CompletionArgs,
+    output: &mut impl Write,
+) -> Result<ExitDirectives, DiagnosticMessages> {
+    let shell = args.shell.unwrap_or(Shell::Bash);
+    let mut cmd = completion_command(Cli::command());
+    let mut script = vec![];
+    generate(shell, &mut cmd, "weaver", &mut script);
+    let script = String::from_utf8_lossy(&script);
+    // As the generators of `clap_complete`, fails if the script can't be written.
+    output
+        .write_all(with_cached_registries(shell, &script).as_bytes())
+        .expect("failed to write the completion script");
+    Ok(ExitDirectives {
+        exit_code: 0,
+        quiet_mode: true,
+    })
+}
+
+/// Writes the registries of the cache, one per line, if the last of the
+/// given arguments is an option taking a registry.
+fn write_cached_registries(
+    words: &[String],
+    cache: &Cache,
+    output: &mut impl Write,
+) -> std::io::Result<()> {
+    if completes_registry(words) {
+        for registry in cache.cached_registries() {
+            writeln!(output, "{}", registry)?;
+        }
+    }
+    Ok(())
+}
+
+/// Returns true if the last of the given arguments is an option taking a
+/// registry that can be fetched into the cache (a Git URL or an OCI
+/// reference), e.g. `registry check -r`.
+fn completes_registry(words: &[String]) -> bool {
+    let Some((option, words)) = words.split_last() else {
+        return false;
+    };
+    let root = Cli::command();
+    let mut cmd = &root;
+    for word in words {
+        if let Some(subcommand) = cmd.find_subcommand(word) {
+            cmd = subcommand;
+        }
+    }
+    let completes_registry = cmd.get_arguments().any(|arg| {
+        let type_id = arg.get_value_parser().type_id();
+        (type_id == TypeId::of::<RegistryPath>() || type_id == TypeId::of::<NamedRegistry>())
+            && (option.strip_prefix("--") == arg.get_long()
+                || arg
+                    .get_short()
+                    .is_some_and(|short| *option == format!("-{}", short)))
+    });
+    completes_registry
+}
+
+/// Extends the generated completion script so the registries of the cache
+/// are suggested for the options taking a registry. The suggestions are
+/// computed at completion time by `weaver completion --cached-registries`.
+/// The other shells only complete the local paths.
+fn with_cached_registries(shell: Shell, script: &str) -> String {
+    match shell {
+        Shell::Bash => {
+            let script = script.replace("complete -F _weaver ", "complete -F _weaver_registries ");
+            format!(
+                r#"{script}
+_weaver_registries() {{
+    _weaver "$@"
+    local registries
+    registries="$(weaver completion --cached-registries -- "${{COMP_WORDS[@]:1:COMP_CWORD-1}}" 2>/dev/null)"
+    if [[ -n "${{registries}}" ]]; then
+        COMPREPLY+=($(compgen -W "${{registries}}" -- "${{COMP_WORDS[COMP_CWORD]}}"))
+    fi
+}}
+"#
+            )
+        }
+        Shell::Zsh => {
+            let script = script
+                .replace(
+                    "if [ \"$funcstack[1]\" = \"_weaver\" ]; then\n    _weaver \"$@\"",
+                    "if [ \"$funcstack[1]\" = \"_weaver\" ]; then\n    _weaver_registries \"$@\"",
+                )
+                .replace("compdef _weaver weaver", "compdef _weaver_registries weaver");
+            let function = r#"_weaver_registries() {
+    local -a registries
+    registries=(${(f)"$(weaver completion --cached-registries -- ${words[2,CURRENT-1]} 2>/dev/null)"})
+    _weaver "$@"
+    (( $#registries )) && compadd -a registries
+}
+
+"#;
+            match script.rfind("if [ \"$funcstack[1]\"") {
+                Some(pos) => format!("{}{}{}", &script[..pos], function, &script[pos..]),
+                None => script,
+            }
+        }
+        Shell::Fish => format!(
+            "{script}complete -c weaver -s r -l registry -l baseline -l baseline-registry -r -a \"(weaver completion --cached-registries -- (commandline -opc)[2..-1] 2>/dev/null)\"\n"
+        ),
+        _ => script.to_owned(),
+    }
+}
+
+/// Adds the completion hints that can't be expressed in the argument
+/// definitions: the built-in targets are suggested for the `target` argument
+/// of the commands rendering the registry templates (the templates directory
+/// can still define other targets), and local paths for the registries and
+/// the templates.
+fn completion_command(cmd: Command) -> Command {
+    let renders_templates = matches!(cmd.get_name(), "generate" | "test");
+    let mut cmd = cmd.mut_args(|arg| match arg.get_id().as_str() {
+        "target" if renders_templates => {
+            arg.value_parser(PossibleValuesParser::new(builtin_targets()))
+        }
+        "registry" | "templates" => arg.value_hint(ValueHint::AnyPath),
+        _ => arg,
+    });
+    let subcommands: Vec<_> = cmd
+        .get_subcommands()
+        .map(|subcommand| subcommand.get_name().to_owned())
+        .collect();
+    for subcommand in subcommands {
+        cmd = cmd.mut_subcommand(subcommand, completion_command);
+    }
+    cmd
+}
+
+#[cfg(test)]
+mod tests {
+    use clap_complete::Shell;
+
+    use crate::completion::{command, completes_registry, CompletionArgs};
+    use crate::registry::generate::DEFAULT_REGISTRY_TEMPLATES;
+
+    fn args(shell: Shell) -> CompletionArgs {
+        CompletionArgs {
+            shell: Some(shell),
+            cached_registries: false,
+            words: vec![],
+        }
+    }
+
+    #[test]
+    fn test_completion() {
+        for shell in [Shell::Bash, Shell::Zsh, Shell::Fish, Shell::PowerShell] {
+            let mut script = vec![];
+            let exit_directives = command(&args(shell), &mut script).expect("Generation failed");
+            assert!(exit_directives.quiet_mode);
+            let script = String::from_utf8(script).expect("Invalid script");
+            assert!(script.contains("registry"), "{shell}");
+            assert!(script.contains("live-check"), "{shell}");
+        }
+
+        // The built-in targets are suggested for `registry generate`.
+        let mut targets: Vec<_> = DEFAULT_REGISTRY_TEMPLATES
+            .dirs()
+            .filter_map(|dir| dir.path().file_name()?.to_str())
+            .collect();
+        targets.sort_unstable();
+        let mut script = vec![];
+        _ = command(&args(Shell::Zsh), &mut script).expect("Generation failed");
+        let script = String::from_utf8(script).expect("Invalid script");
+        assert!(script.lines().any(|line| line
+            .starts_with("':target -- Target to generate the artifacts for.")
+            && line.contains(&format!(":({})", targets.join(" ")))));
+
+        // The cached registries are completed in bash, zsh, and fish.
+        for shell in [Shell::Bash, Shell::Zsh, Shell::Fish] {
+            let mut script = vec![];
+            _ = command(&args(shell), &mut script).expect("Generation failed");
+            let script = String::from_utf8(script).expect("Invalid script");
+            assert!(
+                script.contains("weaver completion --cached-registries --"),
+                "{shell}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_completes_registry() {
+        let completes = |words: &[&str]| {
+            completes_registry(&words.iter().map(|w| (*w).to_owned()).collect::<Vec<_>>())
+        };
+        assert!(completes(&["registry", "check", "-r"]));
+        assert!(completes(&[
+            "--quiet",
+            "registry",
+            "generate",
+            "--registry"
+        ]));
+        assert!(completes(&[
+            "registry",
+            "stats",
+            "-r",
+            "model",
+            "--baseline"
+        ]));
+        assert!(completes(&["registry", "ottl", "--baseline-registry"]));
+        assert!(completes(&["serve", "-r"]));
+
+        // The local registries and the other options are not completed with
+        // the cached registries.
+        assert!(!completes(&["lsp", "-r"]));
+        assert!(!completes(&["registry", "fmt", "-r"]));
+        assert!(!completes(&["registry", "check", "--templates"]));
+        assert!(!completes(&["registry", "check", "-r", "model"]));
+        assert!(!completes(&[]));
+    }
+}
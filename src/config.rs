@@ -0,0 +1,331 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Project-level configuration file (`weaver.toml` or `.weaver.yaml`) defining
+//! the default values of the command line options.
+
+use std::path::{Path, PathBuf};
+
+use clap::{Arg, Command};
+use miette::Diagnostic;
+use serde::{Deserialize, Deserializer, Serialize};
+
+/// Names of the configuration files, by order of precedence in a directory.
+pub(crate) const CONFIG_FILE_NAMES: [&str; 3] = ["weaver.toml", ".weaver.yaml", ".weaver.yml"];
+
+/// Errors emitted while loading the configuration file.
+#[derive(thiserror::Error, Debug, Serialize, Diagnostic)]
+#[non_exhaustive]
+pub enum Error {
+    /// The configuration file could not be read or parsed.
+    #[error("Invalid configuration file `{path}`. {error}")]
+    InvalidConfigFile { path: PathBuf, error: String },
+}
+
+/// Default values of the command line options. The options specified on the
+/// command line override these values.
+#[derive(Debug, Default, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub(crate) struct Config {
+    /// Local paths or Git URLs of the semantic convention registries
+    /// (`--registry`), either a single registry or a list of registries.
+    #[serde(default, deserialize_with = "one_or_many")]
+    registry: Vec<String>,
+    /// Path in the Git repository where the registry is located
+    /// (`--registry-git-sub-dir`).
+    registry_git_sub_dir: Option<String>,
+    /// Policy files, or directories containing `*.rego` policies, checked
+    /// against the registry (`--policy`).
+    #[serde(default)]
+    policies: Vec<PathBuf>,
+    /// Directory where the templates are located (`--templates`).
+    templates: Option<PathBuf>,
+    /// Output format of the resolved registries (`--format`).
+    format: Option<String>,
+    /// Format of the diagnostic messages (`--diagnostic-format`).
+    diagnostic_format: Option<String>,
+    /// Settings of the registry checks.
+    #[serde(default)]
+    lint: LintConfig,
+    /// Number of seconds during which a registry fetched from a Git branch or
+    /// an OCI tag is used from the cache (`--cache-ttl`).
+    cache_ttl: Option<u64>,
+}
+
+/// Settings of the registry checks.
+#[derive(Debug, Default, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub(crate) struct LintConfig {
+    /// Built-in policies to disable (`--disable-builtin-policy`).
+    #[serde(default)]
+    disabled_builtin_policies: Vec<String>,
+}
+
+impl Config {
+    /// Finds the closest configuration file in the given directory or its
+    /// ancestors and loads it.
+    pub(crate) fn discover(dir: &Path) -> Result<Option<(PathBuf, Config)>, Error> {
+        for dir in dir.ancestors() {
+            for name in CONFIG_FILE_NAMES {
+                let path = dir.join(name);
+                if path.is_file() {
+                    let config = Self::load(&path)?;
+                    return Ok(Some((path, config)));
+                }
+            }
+        }
+        Ok(None)
+    }
+
+    /// Loads a configuration file, in TOML or YAML depending on its extension.
+    /// The relative paths of the file are resolved against its directory.
+    pub(crate) fn load(path: &Path) -> Result<Config, Error> {
+        let invalid_config_file = |error: String| Error::InvalidConfigFile {
+            path: path.to_path_buf(),
+            error,
+        };
+        let content =
+            std::fs::read_to_string(path).map_err(|e| invalid_config_file(e.to_string()))?;
+        let mut config: Config = if path.extension().is_some_and(|ext| ext == "toml") {
+            toml::from_str(&content).map_err(|e| invalid_config_file(e.to_string()))?
+        } else {
+            serde_yaml::from_str(&content).map_err(|e| invalid_config_file(e.to_string()))?
+        };
+
+        let base_dir = path.parent().unwrap_or_else(|| Path::new(""));
+        config.registry = config
+            .registry
+            .iter()
+            .map(|registry| {
+                if registry.starts_with("http://")
+                    || registry.starts_with("https://")
+                    || registry.starts_with("oci://")
+                {
+                    registry.clone()
+                } else {
+                    base_dir.join(registry).display().to_string()
+                }
+            })
+            .collect();
+        config.policies = config
+            .policies
+            .iter()
+            .map(|policy| base_dir.join(policy))
+            .collect();
+        config.templates = config.templates.map(|templates| base_dir.join(templates));
+        Ok(config)
+    }
+
+    /// Replaces the default values of the options of the command and its
+    /// sub-commands by the values of the configuration.
+    pub(crate) fn apply(&self, cmd: Command) -> Command {
+        let mut cmd = cmd.mut_args(|arg| self.apply_to_arg(arg));
+        let subcommands: Vec<_> = cmd
+            .get_subcommands()
+            .map(|subcommand| subcommand.get_name().to_owned())
+            .collect();
+        for subcommand in subcommands {
+            cmd = cmd.mut_subcommand(subcommand, |subcommand| self.apply(subcommand));
+        }
+        cmd
+    }
+
+    fn apply_to_arg(&self, arg: Arg) -> Arg {
+        // Only the options with a default value are overridden (e.g. the
+        // `lsp` registry, which is local only, has no default value), except
+        // the lists of policies.
+        let has_default = !arg.get_default_values().is_empty();
+        let value = match arg.get_id().as_str() {
+            "registry" if has_default && !self.registry.is_empty() => {
+                return arg.default_values(self.registry.clone());
+            }
+            "registry_git_sub_dir" if has_default => self.registry_git_sub_dir.clone(),
+            "templates" if has_default => self
+                .templates
+                .as_ref()
+                .map(|templates| templates.display().to_string()),
+            "diagnostic_format" => self.diagnostic_format.clone(),
+            "cache_ttl" => self.cache_ttl.map(|cache_ttl| cache_ttl.to_string()),
+            // The format is only applied to the commands supporting it.
+            "format" => self.format.clone().filter(|format| {
+                arg.get_possible_values()
+                    .iter()
+                    .any(|value| value.matches(format, false))
+            }),
+            "policies" if arg.get_long() == Some("policy") && !self.policies.is_empty() => {
+                return arg.default_values(
+                    self.policies
+                        .iter()
+                        .map(|policy| policy.display().to_string()),
+                );
+            }
+            "disabled_builtin_policies" if !self.lint.disabled_builtin_policies.is_empty() => {
+                return arg.default_values(self.lint.disabled_builtin_policies.clone());
+            }
+            _ => None,
+        };
+        match value {
+            Some(value) => arg.default_value(value),
+            None => arg,
+        }
+    }
+}
+
+/// Deserializes either a single value or a list of values.
+fn one_or_many<'de, D>(deserializer: D) -> Result<Vec<String>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum OneOrMany {
+        One(String),
+        Many(Vec<String>),
+    }
+
+    Ok(match OneOrMany::deserialize(deserializer)? {
+        OneOrMany::One(value) => vec![value],
+        OneOrMany::Many(values) => values,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::{Path, PathBuf};
+
+    use clap::{ArgMatches, CommandFactory, FromArgMatches};
+    use tempdir::TempDir;
+
+    use crate::cli::{Cli, Commands};
+    use crate::config::Config;
+    use crate::registry::RegistryPath;
+
+    /// Returns the matches of the `registry generate` sub-command.
+    fn generate_matches(config: &Config, args: &[&str]) -> ArgMatches {
+        let matches = config
+            .apply(Cli::command())
+            .try_get_matches_from(
+                ["weaver", "registry", "generate", "markdown"]
+                    .iter()
+                    .chain(args),
+            )
+            .expect("Invalid arguments");
+        matches
+            .subcommand_matches("registry")
+            .and_then(|matches| matches.subcommand_matches("generate"))
+            .expect("Expected a generate command")
+            .clone()
+    }
+
+    #[test]
+    fn test_config_discovery() {
+        let root = TempDir::new("config").expect("Failed to create the temp dir");
+        let nested = root.path().join("a/b");
+        std::fs::create_dir_all(&nested).expect("Failed to create the nested dir");
+        if let Some((path, _)) = Config::discover(&nested).expect("Discovery failed") {
+            assert!(!path.starts_with(root.path()));
+        }
+
+        std::fs::write(
+            root.path().join(".weaver.yaml"),
+            "registry: model\npolicies: [policies]\nlint:\n  disabled_builtin_policies: [attr_name_format]\n",
+        )
+        .expect("Failed to write the config");
+        let (path, config) = Config::discover(&nested)
+            .expect("Discovery failed")
+            .expect("Config not found");
+        assert_eq!(path, root.path().join(".weaver.yaml"));
+        assert_eq!(
+            config.registry,
+            vec![root.path().join("model").display().to_string()]
+        );
+        assert_eq!(config.policies, vec![root.path().join("policies")]);
+
+        // `weaver.toml` has precedence over `.weaver.yaml`.
+        std::fs::write(
+            root.path().join("weaver.toml"),
+            "registry = [\"https://example.com/semconv.git\", \"oci://ghcr.io/acme/semconv:1.0.0\", \"vendor\"]\nformat = \"json\"\ncache_ttl = 60\n",
+        )
+        .expect("Failed to write the config");
+        let (path, config) = Config::discover(&nested)
+            .expect("Discovery failed")
+            .expect("Config not found");
+        assert_eq!(path, root.path().join("weaver.toml"));
+        assert_eq!(
+            config.registry,
+            vec![
+                "https://example.com/semconv.git".to_owned(),
+                "oci://ghcr.io/acme/semconv:1.0.0".to_owned(),
+                root.path().join("vendor").display().to_string()
+            ]
+        );
+        let cli = Cli::from_arg_matches(
+            &config
+                .apply(Cli::command())
+                .try_get_matches_from(["weaver", "registry", "check"])
+                .expect("Invalid arguments"),
+        )
+        .expect("Invalid arguments");
+        assert_eq!(cli.cache.cache_ttl, 60);
+
+        std::fs::write(root.path().join("weaver.toml"), "unknown = 1\n")
+            .expect("Failed to write the config");
+        assert!(Config::discover(&nested).is_err());
+    }
+
+    #[test]
+    fn test_config_defaults() {
+        let config = Config {
+            registry: vec!["model".to_owned()],
+            policies: vec![PathBuf::from("policies")],
+            templates: Some(PathBuf::from("my_templates")),
+            format: Some("json".to_owned()),
+            diagnostic_format: Some("json".to_owned()),
+            ..Config::default()
+        };
+
+        let matches = generate_matches(&config, &[]);
+        let registry = matches.get_one::<RegistryPath>("registry");
+        assert_eq!(registry.map(ToString::to_string).as_deref(), Some("model"));
+        let policies: Vec<_> = matches
+            .get_many::<PathBuf>("policies")
+            .expect("No policies")
+            .collect();
+        assert_eq!(policies, vec![Path::new("policies")]);
+        assert_eq!(
+            matches
+                .get_one::<PathBuf>("templates")
+                .map(PathBuf::as_path),
+            Some(Path::new("my_templates"))
+        );
+        assert_eq!(
+            matches
+                .get_one::<String>("diagnostic_format")
+                .map(String::as_str),
+            Some("json")
+        );
+
+        // The command line has precedence over the configuration.
+        let matches = generate_matches(&config, &["-r", "other_model", "-p", "other.rego"]);
+        let registry = matches.get_one::<RegistryPath>("registry");
+        assert_eq!(
+            registry.map(ToString::to_string).as_deref(),
+            Some("other_model")
+        );
+        let policies: Vec<_> = matches
+            .get_many::<PathBuf>("policies")
+            .expect("No policies")
+            .collect();
+        assert_eq!(policies, vec![Path::new("other.rego")]);
+
+        // The `lsp` registry has no default and is not overridden.
+        let matches = config
+            .apply(Cli::command())
+            .try_get_matches_from(["weaver", "lsp"])
+            .expect("Invalid arguments");
+        let cli = Cli::from_arg_matches(&matches).expect("Invalid arguments");
+        let Some(Commands::Lsp(args)) = cli.command else {
+            panic!("Expected a lsp command");
+        };
+        assert!(args.registry.is_none());
+    }
+}
@@ -98,6 +98,7 @@ mod tests {
         let cli = Cli {
             debug: 0,
             quiet: false,
+            cache: Default::default(),
             command: Some(Commands::Diagnostic(DiagnosticCommand {
                 command: DiagnosticSubCommand::Init(DiagnosticInitArgs {
                     target: "".to_owned(),
@@ -111,9 +112,9 @@ mod tests {
         // The command should succeed.
         assert_eq!(exit_directive.exit_code, 0);
 
-        // Check the presence of 3 subdirectories in the temp_output directory
+        // Check the presence of 5 subdirectories in the temp_output directory
         let subdirs = fs::read_dir(&temp_output).unwrap().count();
-        assert_eq!(subdirs, 3);
+        assert_eq!(subdirs, 5);
 
         // Let's init for a specific target
         let temp_output = TempDir::new("output")
@@ -122,6 +123,7 @@ mod tests {
         let cli = Cli {
             debug: 0,
             quiet: false,
+            cache: Default::default(),
             command: Some(Commands::Diagnostic(DiagnosticCommand {
                 command: DiagnosticSubCommand::Init(DiagnosticInitArgs {
                     target: "json".to_owned(),
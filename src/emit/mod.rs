@@ -0,0 +1,151 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Emit synthetic telemetry conforming to a semantic convention registry.
+
+use clap::Args;
+
+use weaver_cache::{Cache, CachePolicy};
+use weaver_common::diagnostic::DiagnosticMessages;
+use weaver_common::Logger;
+use weaver_emit::exporter::export;
+use weaver_emit::otlp_json::telemetry_json;
+use weaver_emit::telemetry::Telemetry;
+use weaver_forge::registry::ResolvedRegistry;
+use weaver_semconv::registry::SemConvRegistry;
+
+use crate::registry::RegistryArgs;
+use crate::util::{load_semconv_specs, resolve_semconv_specs};
+use crate::{CmdResult, DiagnosticArgs, ExitDirectives};
+
+/// Parameters for the `emit` command
+#[derive(Debug, Args)]
+pub struct EmitArgs {
+    /// Parameters to specify the semantic convention registry
+    #[command(flatten)]
+    pub registry: RegistryArgs,
+
+    /// OTLP/gRPC endpoint to which the telemetry is sent.
+    #[arg(long, default_value = "http://localhost:4317")]
+    pub endpoint: String,
+
+    /// Write the telemetry to stdout in OTLP/JSON (one export request per
+    /// line) instead of sending it to the OTLP endpoint.
+    #[arg(long, default_value = "false")]
+    pub stdout: bool,
+
+    /// Parameters to specify the diagnostic format.
+    #[command(flatten)]
+    pub diagnostic: DiagnosticArgs,
+}
+
+/// Emit the synthetic telemetry and return the exit code.
+#[cfg(not(tarpaulin_include))]
+pub fn emit(
+    log: impl Logger + Sync + Clone,
+    args: &EmitArgs,
+    cache_policy: &CachePolicy,
+) -> CmdResult {
+    let cache = match Cache::try_new_with_policy(*cache_policy) {
+        Ok(cache) => cache,
+        Err(e) => return CmdResult::new(Err(e.into()), None),
+    };
+    CmdResult::new(command(log, &cache, args), Some(args.diagnostic.clone()))
+}
+
+/// Synthesize spans, metrics, and log records conforming to the semantic
+/// convention registry and send them to an OTLP endpoint (or write them to
+/// stdout in OTLP/JSON).
+#[cfg(not(tarpaulin_include))]
+pub(crate) fn command(
+    logger: impl Logger + Sync + Clone,
+    cache: &Cache,
+    args: &EmitArgs,
+) -> Result<ExitDirectives, DiagnosticMessages> {
+    if args.stdout {
+        logger.mute();
+    }
+    logger.loading(&format!(
+        "Emitting the telemetry of the registry `{}`",
+        args.registry.registries()
+    ));
+
+    let registry_id = "default";
+    let registry_paths = args.registry.registry_paths();
+
+    // Load and resolve the semantic convention registry.
+    let semconv_specs = load_semconv_specs(
+        &registry_paths,
+        &args.registry.file_filter()?,
+        cache,
+        logger.clone(),
+    )?;
+    let mut registry = SemConvRegistry::from_semconv_specs(registry_id, semconv_specs);
+    let schema = resolve_semconv_specs(&mut registry, logger.clone())?;
+    let resolved_registry = ResolvedRegistry::try_from_resolved_registry(
+        schema
+            .registry(registry_id)
+            .expect("Failed to get the registry from the resolved schema"),
+        schema.catalog(),
+    )?;
+
+    let telemetry = Telemetry::from_registry(&resolved_registry);
+    if args.stdout {
+        for request in telemetry_json(&telemetry) {
+            println!("{}", request);
+        }
+    } else {
+        export(&telemetry, &args.endpoint)?;
+    }
+    logger.success(&format!(
+        "{} span(s), {} metric(s), and {} log record(s) emitted",
+        telemetry.span_count(),
+        telemetry.metric_count(),
+        telemetry.log_count()
+    ));
+
+    Ok(ExitDirectives {
+        exit_code: 0,
+        quiet_mode: args.stdout,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use weaver_common::TestLogger;
+
+    use crate::cli::{Cli, Commands};
+    use crate::emit::EmitArgs;
+    use crate::registry::{RegistryArgs, RegistryPath};
+    use crate::run_command;
+
+    fn cli(endpoint: &str, stdout: bool) -> Cli {
+        Cli {
+            debug: 0,
+            quiet: false,
+            cache: Default::default(),
+            command: Some(Commands::Emit(EmitArgs {
+                registry: RegistryArgs {
+                    registry: vec![RegistryPath::Local("crates/weaver_emit/data/".to_owned())],
+                    registry_git_sub_dir: None,
+                    include: vec![],
+                    exclude: vec![],
+                },
+                endpoint: endpoint.to_owned(),
+                stdout,
+                diagnostic: Default::default(),
+            })),
+        }
+    }
+
+    #[test]
+    fn test_emit() {
+        let logger = TestLogger::new();
+        let exit_directive = run_command(&cli("http://localhost:4317", true), logger.clone());
+        assert_eq!(exit_directive.exit_code, 0);
+        assert_eq!(logger.error_count(), 0);
+
+        // Nothing listens on port 1.
+        let exit_directive = run_command(&cli("http://127.0.0.1:1", false), logger);
+        assert_eq!(exit_directive.exit_code, 1);
+    }
+}
@@ -12,6 +12,10 @@ pub(crate) enum Format {
     Yaml,
     /// JSON format
     Json,
+    /// Protobuf format (binary message and `.proto` schema)
+    Protobuf,
+    /// Binary format (versioned CBOR encoding, loadable with `--resolved-registry`)
+    Binary,
 }
 
 #[cfg(not(tarpaulin_include))]
@@ -21,5 +25,11 @@ pub(crate) fn apply_format<T: Serialize>(format: &Format, object: &T) -> Result<
             .map_err(|e| format!("Failed to serialize in Yaml the resolved registry: {:?}", e)),
         Format::Json => serde_json::to_string_pretty(object)
             .map_err(|e| format!("Failed to serialize in Json the resolved registry: {:?}", e)),
+        Format::Protobuf => {
+            Err("The protobuf format is only supported by `weaver registry resolve`".to_owned())
+        }
+        Format::Binary => {
+            Err("The binary format is only supported by `weaver registry resolve`".to_owned())
+        }
     }
 }
@@ -0,0 +1,409 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Check the telemetry emitted by an application against a semantic convention registry.
+
+use std::net::{IpAddr, SocketAddr};
+use std::path::PathBuf;
+use std::time::Duration;
+
+use clap::{Args, ValueEnum};
+use miette::Diagnostic;
+use serde::Serialize;
+
+use weaver_cache::{Cache, CachePolicy};
+use weaver_common::diagnostic::{DiagnosticMessage, DiagnosticMessages};
+use weaver_common::Logger;
+use weaver_forge::registry::ResolvedRegistry;
+use weaver_live_check::advice::{builtin_advisors, AdviceLevel, Advisor, RegoAdvisor, WasmAdvisor};
+use weaver_live_check::coverage::CoverageReport;
+use weaver_live_check::live_checker::LiveChecker;
+use weaver_live_check::otlp::OtlpListener;
+use weaver_live_check::otlp_file::read_samples;
+use weaver_semconv::registry::SemConvRegistry;
+
+use crate::registry::RegistryArgs;
+use crate::util::{load_semconv_specs, resolve_semconv_specs};
+use crate::{CmdResult, DiagnosticArgs, ExitDirectives};
+
+/// Errors emitted by the `live-check` command
+#[derive(thiserror::Error, Debug, Serialize, Diagnostic)]
+#[non_exhaustive]
+pub enum Error {
+    /// The observed telemetry doesn't comply with the registry.
+    #[error("{findings} discrepancy(ies) found between the {samples} telemetry sample(s) received and the registry.")]
+    LiveCheckFailed { findings: usize, samples: usize },
+
+    /// Advice at or above the fail level has been given.
+    #[error("{advice} advice at or above the `{level}` level given on the {samples} telemetry sample(s) received.")]
+    AdviceLevelReached {
+        advice: usize,
+        level: String,
+        samples: usize,
+    },
+
+    /// The coverage report could not be written.
+    #[error("Failed to write the coverage report to `{path}`. {error}")]
+    CoverageReportFailed { path: PathBuf, error: String },
+}
+
+impl From<Error> for DiagnosticMessages {
+    fn from(error: Error) -> Self {
+        DiagnosticMessages::new(vec![DiagnosticMessage::new(error)])
+    }
+}
+
+/// Supported formats for the coverage report
+#[derive(Debug, Clone, ValueEnum)]
+pub enum CoverageFormat {
+    /// Text format
+    Text,
+    /// JSON format
+    Json,
+    /// Self-contained HTML page
+    Html,
+}
+
+/// Advice levels making the live check fail
+#[derive(Debug, Clone, ValueEnum)]
+pub enum FailLevel {
+    /// Fail on any advice
+    Information,
+    /// Fail on improvements and violations
+    Improvement,
+    /// Fail on violations only
+    Violation,
+}
+
+impl From<&FailLevel> for AdviceLevel {
+    fn from(level: &FailLevel) -> Self {
+        match level {
+            FailLevel::Information => AdviceLevel::Information,
+            FailLevel::Improvement => AdviceLevel::Improvement,
+            FailLevel::Violation => AdviceLevel::Violation,
+        }
+    }
+}
+
+/// Parameters for the `live-check` command
+#[derive(Debug, Args)]
+pub struct LiveCheckArgs {
+    /// Parameters to specify the semantic convention registry
+    #[command(flatten)]
+    pub registry: RegistryArgs,
+
+    /// OTLP file, or directory of OTLP files, to check instead of listening for
+    /// telemetry. Files are encoded in OTLP/JSON (`.json`, `.jsonl`) or protobuf
+    /// (file name containing `trace`, `metric`, or `log`).
+    #[arg(short = 'i', long)]
+    pub input: Option<PathBuf>,
+
+    /// Address on which the OTLP listeners are bound.
+    #[arg(long, default_value = "127.0.0.1")]
+    pub address: IpAddr,
+
+    /// Port of the OTLP/gRPC listener.
+    #[arg(long, default_value = "4317")]
+    pub grpc_port: u16,
+
+    /// Port of the OTLP/HTTP listener.
+    #[arg(long, default_value = "4318")]
+    pub http_port: u16,
+
+    /// Number of seconds without receiving any telemetry after which the
+    /// listeners are stopped and the report is produced.
+    #[arg(long, default_value = "10")]
+    pub inactivity_timeout: u64,
+
+    /// Produce a coverage report, in the given format, cross-referencing the
+    /// observed telemetry with the registry.
+    #[arg(long)]
+    pub coverage: Option<CoverageFormat>,
+
+    /// Advice policies evaluated against each observed attribute: Rego files
+    /// declaring the `live_check_advice` package, or WASM modules.
+    #[arg(short = 'a', long = "advice-policy")]
+    pub advice_policies: Vec<PathBuf>,
+
+    /// Built-in advisors to disable (`type`, `enum`, or `stability`).
+    #[arg(long = "disable-advisor")]
+    pub disabled_advisors: Vec<String>,
+
+    /// Minimum level of advice making the live check fail.
+    #[arg(long, default_value = "violation")]
+    pub fail_level: FailLevel,
+
+    /// File where the coverage report is written. If not specified, the coverage
+    /// report is printed to stdout.
+    #[arg(long, requires = "coverage")]
+    pub coverage_output: Option<PathBuf>,
+
+    /// Parameters to specify the diagnostic format.
+    #[command(flatten)]
+    pub diagnostic: DiagnosticArgs,
+}
+
+/// Check the live telemetry and return the exit code.
+#[cfg(not(tarpaulin_include))]
+pub fn live_check(
+    log: impl Logger + Sync + Clone,
+    args: &LiveCheckArgs,
+    cache_policy: &CachePolicy,
+) -> CmdResult {
+    let cache = match Cache::try_new_with_policy(*cache_policy) {
+        Ok(cache) => cache,
+        Err(e) => return CmdResult::new(Err(e.into()), None),
+    };
+    CmdResult::new(command(log, &cache, args), Some(args.diagnostic.clone()))
+}
+
+/// Receive the telemetry of an application over OTLP (or read it from OTLP
+/// files) and report the discrepancies with the semantic convention registry.
+#[cfg(not(tarpaulin_include))]
+pub(crate) fn command(
+    logger: impl Logger + Sync + Clone,
+    cache: &Cache,
+    args: &LiveCheckArgs,
+) -> Result<ExitDirectives, DiagnosticMessages> {
+    logger.loading(&format!(
+        "Checking the live telemetry against the registry `{}`",
+        args.registry.registries()
+    ));
+
+    let registry_id = "default";
+    let registry_paths = args.registry.registry_paths();
+
+    // Load and resolve the semantic convention registry.
+    let semconv_specs = load_semconv_specs(
+        &registry_paths,
+        &args.registry.file_filter()?,
+        cache,
+        logger.clone(),
+    )?;
+    let mut registry = SemConvRegistry::from_semconv_specs(registry_id, semconv_specs);
+    let schema = resolve_semconv_specs(&mut registry, logger.clone())?;
+    let resolved_registry = ResolvedRegistry::try_from_resolved_registry(
+        schema
+            .registry(registry_id)
+            .expect("Failed to get the registry from the resolved schema"),
+        schema.catalog(),
+    )?;
+
+    let mut live_checker = LiveChecker::new(&resolved_registry);
+    for advisor in init_advisors(args)? {
+        live_checker.add_advisor(advisor);
+    }
+    if let Some(input) = &args.input {
+        for sample in read_samples(input)? {
+            live_checker.check(&sample)?;
+        }
+    } else {
+        let listener = OtlpListener::bind(
+            SocketAddr::new(args.address, args.grpc_port),
+            SocketAddr::new(args.address, args.http_port),
+        )?;
+        logger.log(&format!(
+            "OTLP/gRPC listening on {}, OTLP/HTTP listening on {} (stops after {}s of inactivity or on Ctrl-C)",
+            listener.grpc_addr()?,
+            listener.http_addr()?,
+            args.inactivity_timeout
+        ));
+        listener.run(Duration::from_secs(args.inactivity_timeout), |sample| {
+            live_checker.check(&sample)
+        })?;
+    }
+    let report = live_checker.into_report();
+
+    if let Some(format) = &args.coverage {
+        let coverage = CoverageReport::new(&resolved_registry, &report);
+        let rendered = match format {
+            CoverageFormat::Text => coverage.to_string(),
+            CoverageFormat::Json => serde_json::to_string_pretty(&coverage)
+                .expect("Failed to serialize the coverage report"),
+            CoverageFormat::Html => coverage.to_html(),
+        };
+        if let Some(path) = &args.coverage_output {
+            std::fs::write(path, rendered).map_err(|e| Error::CoverageReportFailed {
+                path: path.clone(),
+                error: e.to_string(),
+            })?;
+            logger.success(&format!("Coverage report written to `{}`", path.display()));
+        } else {
+            println!("{}", rendered);
+        }
+    }
+
+    let fail_level = AdviceLevel::from(&args.fail_level);
+    for finding in &report.findings {
+        logger.error(&format!("{} (x{})", finding.finding, finding.count));
+    }
+    for advice in &report.advice {
+        let message = format!("{} (x{})", advice.advice, advice.count);
+        if advice.advice.level >= fail_level {
+            logger.error(&message);
+        } else {
+            logger.warn(&message);
+        }
+    }
+    if !report.is_empty() {
+        return Err(Error::LiveCheckFailed {
+            findings: report.findings.len(),
+            samples: report.sample_count,
+        }
+        .into());
+    }
+    let advice_count = report.advice_count(fail_level);
+    if advice_count > 0 {
+        return Err(Error::AdviceLevelReached {
+            advice: advice_count,
+            level: fail_level.to_string(),
+            samples: report.sample_count,
+        }
+        .into());
+    }
+    logger.success(&format!(
+        "{} telemetry sample(s) received, no discrepancy found",
+        report.sample_count
+    ));
+
+    Ok(ExitDirectives {
+        exit_code: 0,
+        quiet_mode: false,
+    })
+}
+
+/// Returns the built-in advisors that are not disabled followed by the advisors
+/// defined by the advice policies (Rego files or WASM modules).
+fn init_advisors(args: &LiveCheckArgs) -> Result<Vec<Box<dyn Advisor>>, DiagnosticMessages> {
+    let mut advisors = builtin_advisors(&args.disabled_advisors)?;
+    let (wasm_policies, rego_policies): (Vec<PathBuf>, Vec<PathBuf>) = args
+        .advice_policies
+        .iter()
+        .cloned()
+        .partition(|policy| policy.extension().is_some_and(|ext| ext == "wasm"));
+    if !rego_policies.is_empty() {
+        advisors.push(Box::new(RegoAdvisor::new(&rego_policies)?));
+    }
+    for policy in &wasm_policies {
+        advisors.push(Box::new(WasmAdvisor::load(policy)?));
+    }
+    Ok(advisors)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use tempdir::TempDir;
+    use weaver_common::TestLogger;
+
+    use crate::cli::{Cli, Commands};
+    use crate::live_check::{CoverageFormat, FailLevel, LiveCheckArgs};
+    use crate::registry::{RegistryArgs, RegistryPath};
+    use crate::run_command;
+
+    fn cli(input: Option<&str>) -> Cli {
+        Cli {
+            debug: 0,
+            quiet: false,
+            cache: Default::default(),
+            command: Some(Commands::LiveCheck(LiveCheckArgs {
+                registry: RegistryArgs {
+                    registry: vec![RegistryPath::Local(
+                        "crates/weaver_live_check/data/".to_owned(),
+                    )],
+                    registry_git_sub_dir: None,
+                    include: vec![],
+                    exclude: vec![],
+                },
+                input: input.map(PathBuf::from),
+                address: "127.0.0.1".parse().expect("Invalid address"),
+                grpc_port: 0,
+                http_port: 0,
+                inactivity_timeout: 1,
+                advice_policies: vec![],
+                disabled_advisors: vec![],
+                fail_level: FailLevel::Violation,
+                coverage: None,
+                coverage_output: None,
+                diagnostic: Default::default(),
+            })),
+        }
+    }
+
+    #[test]
+    fn test_live_check_without_telemetry() {
+        let logger = TestLogger::new();
+        let exit_directive = run_command(&cli(None), logger.clone());
+        assert_eq!(exit_directive.exit_code, 0);
+        assert_eq!(logger.error_count(), 0);
+    }
+
+    #[test]
+    fn test_live_check_input() {
+        // The recorded telemetry contains a resource attribute not defined in the
+        // fixture registry, a value not defined by a closed enum (reported as a
+        // finding and as a violation by the enum advisor), and a server span
+        // missing a required attribute.
+        let logger = TestLogger::new();
+        let exit_directive = run_command(
+            &cli(Some("crates/weaver_live_check/data/otlp")),
+            logger.clone(),
+        );
+        assert_eq!(exit_directive.exit_code, 1);
+        assert_eq!(logger.error_count(), 4);
+
+        let exit_directive = run_command(&cli(Some("does-not-exist")), logger);
+        assert_eq!(exit_directive.exit_code, 1);
+    }
+
+    #[test]
+    fn test_live_check_fail_level() {
+        // A deprecated attribute (violation) recorded with a value that is not a
+        // member of its open enum (information).
+        let input = TempDir::new("weaver-live-check").expect("Failed to create a temp dir");
+        std::fs::write(
+            input.path().join("traces.json"),
+            r#"{"resourceSpans": [{"scopeSpans": [{"spans": [{"name": "GET", "attributes": [
+                {"key": "http.flavor", "value": {"stringValue": "3.0"}}
+            ]}]}]}]}"#,
+        )
+        .expect("Failed to write the input");
+        let run = |disabled_advisors: &[&str], fail_level: FailLevel| {
+            let mut cli = cli(input.path().to_str());
+            if let Some(Commands::LiveCheck(args)) = &mut cli.command {
+                args.disabled_advisors = disabled_advisors
+                    .iter()
+                    .map(|id| (*id).to_owned())
+                    .collect();
+                args.fail_level = fail_level;
+            }
+            run_command(&cli, TestLogger::new()).exit_code
+        };
+
+        assert_eq!(run(&[], FailLevel::Violation), 1);
+        assert_eq!(run(&["stability"], FailLevel::Violation), 0);
+        assert_eq!(run(&["stability"], FailLevel::Information), 1);
+        assert_eq!(run(&["unknown"], FailLevel::Violation), 1);
+    }
+
+    #[test]
+    fn test_live_check_coverage() {
+        let logger = TestLogger::new();
+        let output = TempDir::new("weaver-live-check").expect("Failed to create a temp dir");
+        let coverage_file = output.path().join("coverage.json");
+        let mut cli = cli(Some("crates/weaver_live_check/data/otlp"));
+        if let Some(Commands::LiveCheck(args)) = &mut cli.command {
+            args.coverage = Some(CoverageFormat::Json);
+            args.coverage_output = Some(coverage_file.clone());
+        }
+
+        let exit_directive = run_command(&cli, logger);
+        assert_eq!(exit_directive.exit_code, 1);
+        let coverage: serde_json::Value = serde_json::from_str(
+            &std::fs::read_to_string(coverage_file).expect("Failed to read the coverage report"),
+        )
+        .expect("Failed to parse the coverage report");
+        assert_eq!(coverage["metrics"]["observed"], 1);
+        assert_eq!(coverage["attributes"]["observed"], 3);
+    }
+}
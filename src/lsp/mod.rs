@@ -0,0 +1,76 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Language server for authoring semantic convention registries.
+
+use std::path::PathBuf;
+
+use clap::Args;
+use lsp_server::Connection;
+use miette::Diagnostic;
+use serde::Serialize;
+
+use weaver_common::diagnostic::{DiagnosticMessage, DiagnosticMessages};
+use weaver_common::Logger;
+
+use crate::lsp::server::Server;
+use crate::{CmdResult, DiagnosticArgs, ExitDirectives};
+
+mod server;
+mod workspace;
+
+/// Errors emitted by the `lsp` command
+#[derive(thiserror::Error, Debug, Serialize, Diagnostic)]
+#[non_exhaustive]
+pub enum Error {
+    /// The communication with the language client failed.
+    #[error("The language server failed. {error}")]
+    LspFailed { error: String },
+}
+
+impl From<Error> for DiagnosticMessages {
+    fn from(error: Error) -> Self {
+        DiagnosticMessages::new(vec![DiagnosticMessage::new(error)])
+    }
+}
+
+/// Parameters for the `lsp` command
+#[derive(Debug, Args)]
+pub struct LspArgs {
+    /// Local path of the semantic convention registry. If not specified, the
+    /// registry is the workspace root of the editor.
+    #[arg(short = 'r', long)]
+    pub registry: Option<PathBuf>,
+
+    /// Parameters to specify the diagnostic format.
+    #[command(flatten)]
+    pub diagnostic: DiagnosticArgs,
+}
+
+/// Run the language server and return the exit code.
+#[cfg(not(tarpaulin_include))]
+pub fn lsp(log: impl Logger + Sync + Clone, args: &LspArgs) -> CmdResult {
+    CmdResult::new(command(log, args), Some(args.diagnostic.clone()))
+}
+
+/// Run the language server on stdin/stdout until the client requests its
+/// shutdown.
+#[cfg(not(tarpaulin_include))]
+pub(crate) fn command(
+    logger: impl Logger + Sync + Clone,
+    args: &LspArgs,
+) -> Result<ExitDirectives, DiagnosticMessages> {
+    // Stdout is the channel of the protocol.
+    logger.mute();
+
+    let (connection, io_threads) = Connection::stdio();
+    Server::initialize(&connection, args.registry.clone())?.run()?;
+    drop(connection);
+    io_threads.join().map_err(|e| Error::LspFailed {
+        error: e.to_string(),
+    })?;
+
+    Ok(ExitDirectives {
+        exit_code: 0,
+        quiet_mode: true,
+    })
+}
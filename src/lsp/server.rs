@@ -0,0 +1,420 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Language server main loop, translating the LSP messages into calls to the
+//! workspace.
+
+use std::collections::BTreeSet;
+use std::path::PathBuf;
+
+use lsp_server::{Connection, ErrorCode, Message, Notification, Request, Response};
+use lsp_types::notification::{
+    DidChangeTextDocument, DidCloseTextDocument, DidOpenTextDocument, DidSaveTextDocument,
+    LogMessage, Notification as _, PublishDiagnostics,
+};
+use lsp_types::request::{Completion, GotoDefinition, HoverRequest, Request as _};
+use lsp_types::{
+    CompletionOptions, CompletionParams, CompletionResponse, DidChangeTextDocumentParams,
+    DidCloseTextDocumentParams, DidOpenTextDocumentParams, DidSaveTextDocumentParams,
+    GotoDefinitionParams, GotoDefinitionResponse, Hover, HoverContents, HoverParams,
+    HoverProviderCapability, InitializeParams, LogMessageParams, MarkupContent, MarkupKind,
+    MessageType, OneOf, PublishDiagnosticsParams, ServerCapabilities, TextDocumentSyncCapability,
+    TextDocumentSyncKind, Url,
+};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use crate::lsp::workspace::Workspace;
+use crate::lsp::Error;
+
+/// A language server for the semconv files of a registry.
+pub(crate) struct Server<'a> {
+    connection: &'a Connection,
+    workspace: Workspace,
+    /// Files with diagnostics published to the client.
+    published: BTreeSet<PathBuf>,
+}
+
+impl<'a> Server<'a> {
+    /// Performs the initialization handshake with the client and loads the
+    /// registry, located in the given directory or, if not specified, in the
+    /// workspace root of the client.
+    pub(crate) fn initialize(
+        connection: &'a Connection,
+        registry: Option<PathBuf>,
+    ) -> Result<Self, Error> {
+        let capabilities = ServerCapabilities {
+            text_document_sync: Some(TextDocumentSyncCapability::Kind(TextDocumentSyncKind::FULL)),
+            definition_provider: Some(OneOf::Left(true)),
+            hover_provider: Some(HoverProviderCapability::Simple(true)),
+            completion_provider: Some(CompletionOptions {
+                trigger_characters: Some(vec![" ".to_owned(), ".".to_owned()]),
+                ..CompletionOptions::default()
+            }),
+            ..ServerCapabilities::default()
+        };
+        let (id, params) = connection.initialize_start().map_err(protocol_error)?;
+        let params: InitializeParams = from_json(params)?;
+        connection
+            .initialize_finish(
+                id,
+                serde_json::json!({
+                    "capabilities": capabilities,
+                    "serverInfo": {
+                        "name": "weaver",
+                        "version": env!("CARGO_PKG_VERSION"),
+                    },
+                }),
+            )
+            .map_err(protocol_error)?;
+
+        #[allow(deprecated)]
+        let client_root = params
+            .workspace_folders
+            .and_then(|folders| folders.into_iter().next().map(|folder| folder.uri))
+            .or(params.root_uri)
+            .and_then(|uri| uri.to_file_path().ok());
+        let root = registry
+            .or(client_root)
+            .map_or_else(std::env::current_dir, Ok)
+            .map_err(|e| Error::LspFailed {
+                error: e.to_string(),
+            })?;
+
+        Ok(Self {
+            connection,
+            workspace: Workspace::load(&root),
+            published: BTreeSet::new(),
+        })
+    }
+
+    /// Handles the messages of the client until it requests the shutdown of
+    /// the server.
+    pub(crate) fn run(mut self) -> Result<(), Error> {
+        self.publish_diagnostics()?;
+        for message in &self.connection.receiver {
+            match message {
+                Message::Request(request) => {
+                    if self
+                        .connection
+                        .handle_shutdown(&request)
+                        .map_err(protocol_error)?
+                    {
+                        return Ok(());
+                    }
+                    self.handle_request(request)?;
+                }
+                Message::Notification(notification) => self.handle_notification(notification)?,
+                Message::Response(_) => {}
+            }
+        }
+        Ok(())
+    }
+
+    fn handle_request(&self, request: Request) -> Result<(), Error> {
+        let response = match request.method.as_str() {
+            GotoDefinition::METHOD => {
+                let params: GotoDefinitionParams = from_json(request.params)?;
+                let position = params.text_document_position_params;
+                let location = to_path(&position.text_document.uri).and_then(|path| {
+                    let location = self.workspace.definition(&path, position.position)?;
+                    Some(GotoDefinitionResponse::Scalar(lsp_types::Location::new(
+                        Url::from_file_path(&location.path).ok()?,
+                        location.range,
+                    )))
+                });
+                Response::new_ok(request.id, location)
+            }
+            HoverRequest::METHOD => {
+                let params: HoverParams = from_json(request.params)?;
+                let position = params.text_document_position_params;
+                let hover = to_path(&position.text_document.uri).and_then(|path| {
+                    let (markdown, range) = self.workspace.hover(&path, position.position)?;
+                    Some(Hover {
+                        contents: HoverContents::Markup(MarkupContent {
+                            kind: MarkupKind::Markdown,
+                            value: markdown,
+                        }),
+                        range: Some(range),
+                    })
+                });
+                Response::new_ok(request.id, hover)
+            }
+            Completion::METHOD => {
+                let params: CompletionParams = from_json(request.params)?;
+                let position = params.text_document_position;
+                let items = to_path(&position.text_document.uri)
+                    .map(|path| self.workspace.completion(&path, position.position))
+                    .unwrap_or_default();
+                Response::new_ok(request.id, CompletionResponse::Array(items))
+            }
+            method => Response::new_err(
+                request.id,
+                ErrorCode::MethodNotFound as i32,
+                format!("Unsupported method `{}`", method),
+            ),
+        };
+        self.send(Message::Response(response))
+    }
+
+    fn handle_notification(&mut self, notification: Notification) -> Result<(), Error> {
+        match notification.method.as_str() {
+            DidOpenTextDocument::METHOD => {
+                let params: DidOpenTextDocumentParams = from_json(notification.params)?;
+                if let Some(path) = to_path(&params.text_document.uri) {
+                    self.workspace.update(&path, params.text_document.text);
+                }
+            }
+            DidChangeTextDocument::METHOD => {
+                let params: DidChangeTextDocumentParams = from_json(notification.params)?;
+                // The documents are synchronized in full, the last change is
+                // the new content of the document.
+                let text = params.content_changes.into_iter().last();
+                if let (Some(path), Some(text)) = (to_path(&params.text_document.uri), text) {
+                    self.workspace.update(&path, text.text);
+                }
+            }
+            DidSaveTextDocument::METHOD => {
+                let params: DidSaveTextDocumentParams = from_json(notification.params)?;
+                if let Some(path) = to_path(&params.text_document.uri) {
+                    self.workspace.reload(&path);
+                }
+            }
+            DidCloseTextDocument::METHOD => {
+                let params: DidCloseTextDocumentParams = from_json(notification.params)?;
+                if let Some(path) = to_path(&params.text_document.uri) {
+                    self.workspace.reload(&path);
+                }
+            }
+            _ => return Ok(()),
+        }
+        self.publish_diagnostics()
+    }
+
+    /// Publishes the diagnostics of the files with errors, and clears the
+    /// diagnostics previously published for the files that are now valid.
+    fn publish_diagnostics(&mut self) -> Result<(), Error> {
+        let mut published = BTreeSet::new();
+        for (path, diagnostics) in self.workspace.diagnostics() {
+            if diagnostics.is_empty() && !self.published.contains(path) {
+                continue;
+            }
+            let Ok(uri) = Url::from_file_path(path) else {
+                continue;
+            };
+            if !diagnostics.is_empty() {
+                _ = published.insert(path.clone());
+            }
+            self.notify::<PublishDiagnostics>(PublishDiagnosticsParams::new(
+                uri,
+                diagnostics.clone(),
+                None,
+            ))?;
+        }
+        self.published = published;
+
+        for error in self.workspace.unlocated_errors() {
+            self.notify::<LogMessage>(LogMessageParams {
+                typ: MessageType::ERROR,
+                message: error.clone(),
+            })?;
+        }
+        Ok(())
+    }
+
+    fn notify<N: lsp_types::notification::Notification>(
+        &self,
+        params: N::Params,
+    ) -> Result<(), Error>
+    where
+        N::Params: Serialize,
+    {
+        self.send(Message::Notification(Notification::new(
+            N::METHOD.to_owned(),
+            params,
+        )))
+    }
+
+    fn send(&self, message: Message) -> Result<(), Error> {
+        self.connection
+            .sender
+            .send(message)
+            .map_err(|e| Error::LspFailed {
+                error: e.to_string(),
+            })
+    }
+}
+
+/// Returns the path of a `file` URI.
+fn to_path(uri: &Url) -> Option<PathBuf> {
+    uri.to_file_path().ok()
+}
+
+fn from_json<T: DeserializeOwned>(value: serde_json::Value) -> Result<T, Error> {
+    serde_json::from_value(value).map_err(|e| Error::LspFailed {
+        error: format!("Invalid message parameters. {}", e),
+    })
+}
+
+fn protocol_error(error: lsp_server::ProtocolError) -> Error {
+    Error::LspFailed {
+        error: error.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+    use std::time::Duration;
+
+    use lsp_server::{Connection, Message, Notification, Request, RequestId, Response};
+    use lsp_types::notification::{
+        DidChangeTextDocument, DidOpenTextDocument, Exit, Initialized, Notification as _,
+        PublishDiagnostics,
+    };
+    use lsp_types::request::{
+        Completion, GotoDefinition, HoverRequest, Initialize, Request as _, Shutdown,
+    };
+    use lsp_types::{
+        CompletionResponse, GotoDefinitionResponse, Hover, PublishDiagnosticsParams, Url,
+    };
+    use serde_json::json;
+
+    use crate::lsp::server::Server;
+
+    /// Receives the messages of the server until the response to the given request.
+    fn response(client: &Connection, id: i32) -> Response {
+        loop {
+            match client.receiver.recv_timeout(Duration::from_secs(30)) {
+                Ok(Message::Response(response)) if response.id == RequestId::from(id) => {
+                    return response
+                }
+                Ok(_) => {}
+                Err(e) => panic!("No response to the request {id}: {e}"),
+            }
+        }
+    }
+
+    /// Receives the messages of the server until the diagnostics of the given file.
+    fn diagnostics(client: &Connection, uri: &Url) -> PublishDiagnosticsParams {
+        loop {
+            match client.receiver.recv_timeout(Duration::from_secs(30)) {
+                Ok(Message::Notification(notification))
+                    if notification.method == PublishDiagnostics::METHOD =>
+                {
+                    let params: PublishDiagnosticsParams =
+                        serde_json::from_value(notification.params).expect("Invalid params");
+                    if &params.uri == uri {
+                        return params;
+                    }
+                }
+                Ok(_) => {}
+                Err(e) => panic!("No diagnostics for {uri}: {e}"),
+            }
+        }
+    }
+
+    fn request(client: &Connection, id: i32, method: &str, params: serde_json::Value) {
+        client
+            .sender
+            .send(Message::Request(Request::new(
+                RequestId::from(id),
+                method.to_owned(),
+                params,
+            )))
+            .expect("Failed to send the request");
+    }
+
+    fn notify(client: &Connection, method: &str, params: serde_json::Value) {
+        client
+            .sender
+            .send(Message::Notification(Notification::new(
+                method.to_owned(),
+                params,
+            )))
+            .expect("Failed to send the notification");
+    }
+
+    #[test]
+    fn test_language_server() {
+        let root = Path::new("crates/weaver_codegen_test/semconv_registry")
+            .canonicalize()
+            .expect("Registry not found");
+        let uri = Url::from_file_path(root.join("http-common.yaml")).expect("Invalid path");
+        let text = std::fs::read_to_string(root.join("http-common.yaml"))
+            .expect("Failed to read the file");
+        let (server, client) = Connection::memory();
+        let server_root = root.clone();
+        let server_thread = std::thread::spawn(move || {
+            Server::initialize(&server, Some(server_root))
+                .and_then(Server::run)
+                .expect("Language server failed");
+        });
+
+        request(
+            &client,
+            1,
+            Initialize::METHOD,
+            json!({ "capabilities": {}, "processId": null, "rootUri": null }),
+        );
+        let capabilities = response(&client, 1).result.expect("Initialization failed");
+        assert_eq!(capabilities["capabilities"]["definitionProvider"], true);
+        notify(&client, Initialized::METHOD, json!({}));
+
+        // Unresolved reference in an opened document.
+        notify(
+            &client,
+            DidOpenTextDocument::METHOD,
+            json!({ "textDocument": {
+                "uri": uri, "languageId": "yaml", "version": 1,
+                "text": text.replace("ref: http.request.method", "ref: http.request.verb"),
+            }}),
+        );
+        let params = diagnostics(&client, &uri);
+        assert_eq!(params.diagnostics.len(), 1);
+        assert_eq!(params.diagnostics[0].range.start.line, 5);
+
+        // Fixed reference.
+        notify(
+            &client,
+            DidChangeTextDocument::METHOD,
+            json!({
+                "textDocument": { "uri": uri, "version": 2 },
+                "contentChanges": [{ "text": text }],
+            }),
+        );
+        assert!(diagnostics(&client, &uri).diagnostics.is_empty());
+
+        let position =
+            json!({ "textDocument": { "uri": uri }, "position": { "line": 5, "character": 20 } });
+        request(&client, 2, GotoDefinition::METHOD, position.clone());
+        let definition: GotoDefinitionResponse =
+            serde_json::from_value(response(&client, 2).result.expect("No definition"))
+                .expect("Invalid definition");
+        let GotoDefinitionResponse::Scalar(location) = definition else {
+            panic!("Expected a single location");
+        };
+        assert_eq!(
+            location.uri,
+            Url::from_file_path(root.join("registry/http.yaml")).expect("Invalid path")
+        );
+
+        request(&client, 3, HoverRequest::METHOD, position.clone());
+        let hover: Hover = serde_json::from_value(response(&client, 3).result.expect("No hover"))
+            .expect("Invalid hover");
+        assert!(format!("{:?}", hover.contents).contains("http.request.method"));
+
+        request(&client, 4, Completion::METHOD, position);
+        let completion: CompletionResponse =
+            serde_json::from_value(response(&client, 4).result.expect("No completion"))
+                .expect("Invalid completion");
+        let CompletionResponse::Array(items) = completion else {
+            panic!("Expected a list of completion items");
+        };
+        assert!(items.iter().any(|item| item.label == "http.request.method"));
+
+        request(&client, 5, Shutdown::METHOD, json!(null));
+        _ = response(&client, 5);
+        notify(&client, Exit::METHOD, json!(null));
+        server_thread.join().expect("Language server panicked");
+    }
+}
@@ -0,0 +1,589 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! In-memory view of a semantic convention registry being edited, and the
+//! language features computed from it.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use lsp_types::{
+    CompletionItem, CompletionItemKind, CompletionTextEdit, Diagnostic, DiagnosticSeverity,
+    Position, Range, TextEdit,
+};
+use walkdir::DirEntry;
+
+use weaver_forge::registry::ResolvedRegistry;
+use weaver_resolver::SchemaResolver;
+use weaver_semconv::attribute::AttributeSpec;
+use weaver_semconv::registry::SemConvRegistry;
+use weaver_semconv::semconv::SemConvSpec;
+
+use crate::registry::search::{registry_items, RegistryItem, SearchKind};
+
+/// A position in a file of the workspace.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Location {
+    /// The path of the file.
+    pub path: PathBuf,
+    /// The range in the file.
+    pub range: Range,
+}
+
+/// The semconv files of a registry, loaded from the disk or provided by the
+/// editor for the opened documents.
+pub(crate) struct Workspace {
+    /// The root directory of the registry.
+    root: PathBuf,
+    /// The content of the semconv files.
+    documents: BTreeMap<PathBuf, String>,
+    /// Definition of the group ids.
+    groups: BTreeMap<String, Location>,
+    /// Definition of the attribute ids.
+    attributes: BTreeMap<String, Location>,
+    /// Diagnostics of each file (an empty list for the files without errors).
+    diagnostics: BTreeMap<PathBuf, Vec<Diagnostic>>,
+    /// Errors of the resolution that can't be attached to a file.
+    unlocated_errors: Vec<String>,
+    /// Attributes of the last successful resolution of the registry.
+    resolved_attributes: BTreeMap<String, RegistryItem>,
+}
+
+impl Workspace {
+    /// Loads the semconv files found in the given directory and analyzes them.
+    pub(crate) fn load(root: &Path) -> Self {
+        fn is_hidden(entry: &DirEntry) -> bool {
+            entry
+                .file_name()
+                .to_str()
+                .is_some_and(|name| name.starts_with('.'))
+        }
+
+        let mut workspace = Self {
+            root: root.to_path_buf(),
+            documents: BTreeMap::new(),
+            groups: BTreeMap::new(),
+            attributes: BTreeMap::new(),
+            diagnostics: BTreeMap::new(),
+            unlocated_errors: vec![],
+            resolved_attributes: BTreeMap::new(),
+        };
+        for entry in walkdir::WalkDir::new(root)
+            .into_iter()
+            .filter_entry(|entry| !is_hidden(entry))
+            .filter_map(Result::ok)
+        {
+            if workspace.contains(entry.path()) {
+                if let Ok(text) = std::fs::read_to_string(entry.path()) {
+                    _ = workspace.documents.insert(entry.path().to_path_buf(), text);
+                }
+            }
+        }
+        workspace.analyze();
+        workspace
+    }
+
+    /// Returns true if the given path is a semconv file of the workspace.
+    pub(crate) fn contains(&self, path: &Path) -> bool {
+        path.starts_with(&self.root)
+            && path
+                .extension()
+                .is_some_and(|ext| ext == "yaml" || ext == "yml")
+            && path
+                .file_name()
+                .is_some_and(|name| name != "schema-next.yaml")
+    }
+
+    /// Replaces the content of a semconv file with the content provided by the
+    /// editor and re-analyzes the workspace.
+    pub(crate) fn update(&mut self, path: &Path, text: String) {
+        if self.contains(path) {
+            _ = self.documents.insert(path.to_path_buf(), text);
+            self.analyze();
+        }
+    }
+
+    /// Reloads a semconv file from the disk (e.g. when the editor closes it
+    /// without saving) and re-analyzes the workspace.
+    pub(crate) fn reload(&mut self, path: &Path) {
+        if self.contains(path) {
+            match std::fs::read_to_string(path) {
+                Ok(text) => _ = self.documents.insert(path.to_path_buf(), text),
+                Err(_) => _ = self.documents.remove(path),
+            }
+            self.analyze();
+        }
+    }
+
+    /// Returns the diagnostics of each file of the workspace.
+    pub(crate) fn diagnostics(&self) -> &BTreeMap<PathBuf, Vec<Diagnostic>> {
+        &self.diagnostics
+    }
+
+    /// Returns the resolution errors that are not attached to a file.
+    pub(crate) fn unlocated_errors(&self) -> &[String] {
+        &self.unlocated_errors
+    }
+
+    /// Parses, indexes, and resolves the semconv files. The registry is only
+    /// resolved when all the files are valid, so invalid files don't cause
+    /// spurious unresolved references in the other files.
+    fn analyze(&mut self) {
+        self.groups.clear();
+        self.attributes.clear();
+        self.unlocated_errors.clear();
+        self.diagnostics = self
+            .documents
+            .keys()
+            .map(|path| (path.clone(), vec![]))
+            .collect();
+
+        let mut specs = vec![];
+        let mut valid = true;
+        for (path, text) in &self.documents {
+            let spec = match SemConvSpec::from_string(text) {
+                Ok(spec) => {
+                    specs.push((path.display().to_string(), spec.clone()));
+                    Some(spec)
+                }
+                Err(error) => {
+                    valid = false;
+                    let diagnostics = self.diagnostics.entry(path.clone()).or_default();
+                    semconv_diagnostics(text, error, diagnostics);
+                    // Invalid specs are still indexed to navigate in the file.
+                    serde_yaml::from_str::<SemConvSpec>(text).ok()
+                }
+            };
+            if let Some(spec) = spec {
+                index_spec(path, text, &spec, &mut self.groups, &mut self.attributes);
+            }
+        }
+
+        if !valid {
+            return;
+        }
+        let registry_id = "default";
+        let mut registry = SemConvRegistry::from_semconv_specs(registry_id, specs);
+        match SchemaResolver::resolve_semantic_convention_registry(&mut registry) {
+            Ok(schema) => {
+                let resolved_registry = schema.registry(registry_id).and_then(|registry| {
+                    ResolvedRegistry::try_from_resolved_registry(registry, schema.catalog()).ok()
+                });
+                if let Some(resolved_registry) = resolved_registry {
+                    self.resolved_attributes = registry_items(&resolved_registry)
+                        .into_iter()
+                        .filter(|item| item.kind == SearchKind::Attribute)
+                        .map(|item| (item.id.clone(), item))
+                        .collect();
+                }
+            }
+            Err(error) => self.resolver_diagnostics(error),
+        }
+    }
+
+    /// Attaches the resolution errors to the references that can't be resolved.
+    fn resolver_diagnostics(&mut self, error: weaver_resolver::Error) {
+        let (provenance, group_id, key, value) = match &error {
+            weaver_resolver::Error::CompoundError(errors) => {
+                for error in errors {
+                    self.resolver_diagnostics(error.clone());
+                }
+                return;
+            }
+            weaver_resolver::Error::UnresolvedAttributeRef {
+                group_id,
+                attribute_ref,
+                provenance,
+            } => (provenance, group_id, "ref", attribute_ref),
+            weaver_resolver::Error::UnresolvedExtendsRef {
+                group_id,
+                extends_ref,
+                provenance,
+            } => (provenance, group_id, "extends", extends_ref),
+            weaver_resolver::Error::UnresolvedIncludeRef {
+                group_id,
+                include_ref,
+                provenance,
+            } => (provenance, group_id, "include", include_ref),
+            weaver_resolver::Error::UnsatisfiedAnyOfConstraint { group_id, .. }
+            | weaver_resolver::Error::IncompatibleMetricAttributes {
+                metric_group_ref: group_id,
+                ..
+            } => {
+                match self.groups.get(group_id) {
+                    Some(location) => self
+                        .diagnostics
+                        .entry(location.path.clone())
+                        .or_default()
+                        .push(diagnostic(location.range, error.to_string())),
+                    None => self.unlocated_errors.push(error.to_string()),
+                }
+                return;
+            }
+            _ => {
+                self.unlocated_errors.push(error.to_string());
+                return;
+            }
+        };
+
+        let path = PathBuf::from(provenance);
+        let range = self.documents.get(&path).and_then(|text| {
+            let lines: Vec<_> = text.lines().collect();
+            let (group_line, _) = find_entry(&lines, 0, "id", group_id)?;
+            find_entry(&lines, group_line, key, value).map(|(_, range)| range)
+        });
+        match range {
+            Some(range) => self
+                .diagnostics
+                .entry(path)
+                .or_default()
+                .push(diagnostic(range, error.to_string())),
+            None => self.unlocated_errors.push(error.to_string()),
+        }
+    }
+
+    /// Returns the definition of the attribute referenced by a `ref` entry or
+    /// of the group referenced by an `extends` or `include` entry.
+    pub(crate) fn definition(&self, path: &Path, position: Position) -> Option<&Location> {
+        let entry = self.entry_at(path, position)?;
+        match entry.key {
+            "ref" => self.attributes.get(entry.value),
+            "extends" | "include" => self.groups.get(entry.value),
+            _ => None,
+        }
+    }
+
+    /// Returns the documentation, in Markdown, of the resolved attribute
+    /// referenced or defined at the given position.
+    pub(crate) fn hover(&self, path: &Path, position: Position) -> Option<(String, Range)> {
+        let entry = self.entry_at(path, position)?;
+        let attribute_id = match entry.key {
+            "ref" => entry.value.to_owned(),
+            "id" => self
+                .attributes
+                .iter()
+                .find(|(_, location)| {
+                    location.path == path && location.range.start.line == position.line
+                })
+                .map(|(id, _)| id.clone())?,
+            _ => return None,
+        };
+        let item = self.resolved_attributes.get(&attribute_id)?;
+
+        let mut markdown = format!("**`{}`** `{}`", item.id, item.r#type);
+        if let Some(stability) = &item.stability {
+            markdown.push_str(&format!(" ({})", stability));
+        }
+        if !item.brief.is_empty() {
+            markdown.push_str(&format!("\n\n{}", item.brief.trim()));
+        }
+        if !item.note.is_empty() {
+            markdown.push_str(&format!("\n\n{}", item.note.trim()));
+        }
+        markdown.push_str(&format!("\n\nDefined in group `{}`.", item.group_id));
+        Some((markdown, entry.range))
+    }
+
+    /// Returns the attribute ids that can complete a `ref` entry, or the group
+    /// ids that can complete an `extends` or `include` entry, at the given
+    /// position.
+    pub(crate) fn completion(&self, path: &Path, position: Position) -> Vec<CompletionItem> {
+        let Some(line) = self
+            .documents
+            .get(path)
+            .and_then(|text| text.lines().nth(position.line as usize))
+        else {
+            return vec![];
+        };
+        let prefix: String = line.chars().take(position.character as usize).collect();
+        let Some(entry) = parse_entry(&prefix, position.line) else {
+            return vec![];
+        };
+        let (ids, kind) = match entry.key {
+            "ref" => (&self.attributes, CompletionItemKind::FIELD),
+            "extends" | "include" => (&self.groups, CompletionItemKind::MODULE),
+            _ => return vec![],
+        };
+        // The edit replaces the whole value as the ids contain dots, which
+        // editors usually consider as word separators.
+        let range = Range::new(entry.range.start, position);
+        ids.keys()
+            .filter(|id| id.starts_with(entry.value))
+            .map(|id| CompletionItem {
+                label: id.clone(),
+                kind: Some(kind),
+                detail: self
+                    .resolved_attributes
+                    .get(id)
+                    .map(|item| item.brief.trim().to_owned()),
+                text_edit: Some(CompletionTextEdit::Edit(TextEdit::new(range, id.clone()))),
+                ..CompletionItem::default()
+            })
+            .collect()
+    }
+
+    /// Returns the entry of the line at the given position if the position is
+    /// in its value.
+    fn entry_at(&self, path: &Path, position: Position) -> Option<Entry<'_>> {
+        let line = self
+            .documents
+            .get(path)?
+            .lines()
+            .nth(position.line as usize)?;
+        parse_entry(line, position.line).filter(|entry| {
+            entry.range.start.character <= position.character
+                && position.character <= entry.range.end.character
+        })
+    }
+}
+
+/// A `key: value` entry of a line of a YAML document.
+#[derive(Debug, PartialEq)]
+struct Entry<'a> {
+    key: &'a str,
+    /// The value, without quotes.
+    value: &'a str,
+    /// The range of the value.
+    range: Range,
+}
+
+/// Parses a line of the form `[- ]key: value`.
+fn parse_entry(line: &str, line_number: u32) -> Option<Entry<'_>> {
+    let content = line.trim_start();
+    let content = content.strip_prefix('-').map_or(content, str::trim_start);
+    let (key, rest) = content.split_once(':')?;
+    if key.is_empty() || !key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+        return None;
+    }
+    let rest = rest.find(" #").map_or(rest, |comment| &rest[..comment]);
+    let mut value = rest.trim();
+    // `content` is a suffix of `line`.
+    let mut start = line.len() - content.len() + key.len() + 1;
+    start += rest.len() - rest.trim_start().len();
+    for quote in ['"', '\''] {
+        if value.len() >= 2 && value.starts_with(quote) && value.ends_with(quote) {
+            value = &value[1..value.len() - 1];
+            start += 1;
+        }
+    }
+    let column = |byte: usize| line[..byte].chars().count() as u32;
+    Some(Entry {
+        key,
+        value,
+        range: Range::new(
+            Position::new(line_number, column(start)),
+            Position::new(line_number, column(start + value.len())),
+        ),
+    })
+}
+
+/// Finds the first `key: value` entry from the given line and returns its line
+/// and the range of the value.
+fn find_entry(lines: &[&str], from: usize, key: &str, value: &str) -> Option<(usize, Range)> {
+    lines
+        .iter()
+        .enumerate()
+        .skip(from)
+        .find_map(|(line_number, line)| {
+            parse_entry(line, line_number as u32)
+                .filter(|entry| entry.key == key && entry.value == value)
+                .map(|entry| (line_number, entry.range))
+        })
+}
+
+/// Records the location of the groups and attributes defined in a spec.
+fn index_spec(
+    path: &Path,
+    text: &str,
+    spec: &SemConvSpec,
+    groups: &mut BTreeMap<String, Location>,
+    attributes: &mut BTreeMap<String, Location>,
+) {
+    let lines: Vec<_> = text.lines().collect();
+    let mut group_line = 0;
+    for group in spec.groups() {
+        let Some((line, range)) = find_entry(&lines, group_line, "id", &group.id) else {
+            continue;
+        };
+        group_line = line;
+        _ = groups.insert(
+            group.id.clone(),
+            Location {
+                path: path.to_path_buf(),
+                range,
+            },
+        );
+
+        let mut attribute_line = group_line + 1;
+        for attribute in &group.attributes {
+            let AttributeSpec::Id { id, .. } = attribute else {
+                continue;
+            };
+            let Some((line, range)) = find_entry(&lines, attribute_line, "id", id.as_str()) else {
+                continue;
+            };
+            attribute_line = line + 1;
+            let attribute_id = if group.prefix.is_empty() {
+                id.clone()
+            } else {
+                format!("{}.{}", group.prefix, id)
+            };
+            _ = attributes.insert(
+                attribute_id,
+                Location {
+                    path: path.to_path_buf(),
+                    range,
+                },
+            );
+        }
+    }
+}
+
+/// Converts the errors of a semconv file into diagnostics.
+fn semconv_diagnostics(
+    text: &str,
+    error: weaver_semconv::Error,
+    diagnostics: &mut Vec<Diagnostic>,
+) {
+    let lines: Vec<_> = text.lines().collect();
+    let group_range = |group_id: &str| find_entry(&lines, 0, "id", group_id);
+    let range = match &error {
+        weaver_semconv::Error::CompoundError(errors) => {
+            for error in errors {
+                semconv_diagnostics(text, error.clone(), diagnostics);
+            }
+            return;
+        }
+        weaver_semconv::Error::InvalidSemConvSpec { line, column, .. } => {
+            let line = line.unwrap_or(1).saturating_sub(1);
+            let column = column.unwrap_or(1).saturating_sub(1);
+            let end = lines.get(line).map_or(0, |line| line.chars().count());
+            Some(Range::new(
+                Position::new(line as u32, column as u32),
+                Position::new(line as u32, end.max(column) as u32),
+            ))
+        }
+        weaver_semconv::Error::InvalidGroup { group_id, .. }
+        | weaver_semconv::Error::InvalidMetric { group_id, .. } => {
+            group_range(group_id).map(|(_, range)| range)
+        }
+        weaver_semconv::Error::InvalidAttribute {
+            group_id,
+            attribute_id,
+            ..
+        } => group_range(group_id).map(|(group_line, group_range)| {
+            find_entry(&lines, group_line, "id", attribute_id)
+                .or_else(|| find_entry(&lines, group_line, "ref", attribute_id))
+                .map_or(group_range, |(_, range)| range)
+        }),
+        _ => None,
+    };
+    diagnostics.push(diagnostic(range.unwrap_or_default(), error.to_string()));
+}
+
+/// Creates an error diagnostic.
+fn diagnostic(range: Range, message: String) -> Diagnostic {
+    Diagnostic {
+        range,
+        severity: Some(DiagnosticSeverity::ERROR),
+        source: Some("weaver".to_owned()),
+        message,
+        ..Diagnostic::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use lsp_types::{Position, Range};
+
+    use super::{parse_entry, Workspace};
+
+    #[test]
+    fn test_parse_entry() {
+        let entry = parse_entry("      - ref: 'http.request.method'  # comment", 3)
+            .expect("Entry not found");
+        assert_eq!(entry.key, "ref");
+        assert_eq!(entry.value, "http.request.method");
+        assert_eq!(
+            entry.range,
+            Range::new(Position::new(3, 14), Position::new(3, 33))
+        );
+        assert!(parse_entry("    brief: >", 0).is_some());
+        assert!(parse_entry("      Some text, with: a colon", 0).is_none());
+    }
+
+    #[test]
+    fn test_workspace() {
+        let root = Path::new("crates/weaver_codegen_test/semconv_registry");
+        let workspace = Workspace::load(root);
+        assert!(workspace.diagnostics().values().all(Vec::is_empty));
+        assert!(workspace.unlocated_errors().is_empty());
+
+        // `ref: http.request.method` in `attributes.http.common`.
+        let http_common = root.join("http-common.yaml");
+        let position = Position::new(5, 20);
+        let definition = workspace
+            .definition(&http_common, position)
+            .expect("Definition not found");
+        assert_eq!(definition.path, root.join("registry/http.yaml"));
+        let (hover, _) = workspace
+            .hover(&http_common, position)
+            .expect("Hover not found");
+        assert!(hover.starts_with("**`http.request.method`**"));
+        assert!(hover.contains("Defined in group `registry.http`."));
+
+        // Hovering the definition of the attribute.
+        assert_eq!(
+            workspace.hover(&definition.path, definition.range.start),
+            Some((hover, definition.range))
+        );
+
+        // `extends: attributes.http.common` in `attributes.http.client`.
+        let definition = workspace
+            .definition(&http_common, Position::new(41, 15))
+            .expect("Definition not found");
+        assert_eq!(definition.path, http_common);
+        assert_eq!(definition.range.start.line, 1);
+
+        let items = workspace.completion(&http_common, Position::new(5, 22));
+        assert!(!items.is_empty());
+        assert!(items.iter().all(|item| item.label.starts_with("http.")));
+        assert!(items
+            .iter()
+            .any(|item| item.label == "http.request.method" && item.detail.is_some()));
+    }
+
+    #[test]
+    fn test_workspace_diagnostics() {
+        let root = Path::new("crates/weaver_codegen_test/semconv_registry");
+        let mut workspace = Workspace::load(root);
+        let http_common = root.join("http-common.yaml");
+        let text = std::fs::read_to_string(&http_common).expect("Failed to read the file");
+
+        // Unresolved reference.
+        workspace.update(
+            &http_common,
+            text.replace("ref: http.request.method", "ref: http.request.verb"),
+        );
+        let diagnostics = &workspace.diagnostics()[&http_common];
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(
+            diagnostics[0].range,
+            Range::new(Position::new(5, 13), Position::new(5, 30))
+        );
+
+        // Invalid YAML.
+        workspace.update(&http_common, text.replace("    brief:", "    brief"));
+        let diagnostics = &workspace.diagnostics()[&http_common];
+        assert_eq!(diagnostics.len(), 1);
+        assert_ne!(diagnostics[0].range, Range::default());
+
+        // Navigation still works in the invalid file.
+        assert!(workspace
+            .definition(&http_common, Position::new(5, 20))
+            .is_some());
+        workspace.reload(&http_common);
+        assert!(workspace.diagnostics().values().all(Vec::is_empty));
+    }
+}
@@ -4,7 +4,8 @@
 
 use std::path::PathBuf;
 
-use clap::{Args, Parser};
+use clap::error::ErrorKind;
+use clap::{Args, CommandFactory, FromArgMatches, Parser};
 
 use registry::semconv_registry;
 use weaver_common::diagnostic::DiagnosticMessages;
@@ -15,19 +16,29 @@ use weaver_forge::file_loader::EmbeddedFileLoader;
 use weaver_forge::{OutputDirective, TemplateEngine};
 
 use crate::cli::{Cli, Commands};
+use crate::config::Config;
 use crate::diagnostic::DEFAULT_DIAGNOSTIC_TEMPLATES;
 
 mod cli;
+mod completion;
+mod config;
 mod diagnostic;
+mod emit;
 mod format;
+mod live_check;
+mod lsp;
+mod migrate;
+mod policy;
 mod registry;
+mod serve;
+mod template;
 mod util;
 
 /// Set of parameters used to specify the diagnostic format.
 #[derive(Args, Debug, Clone)]
 pub(crate) struct DiagnosticArgs {
     /// Format used to render the diagnostic messages. Predefined formats are: ansi, json,
-    /// gh_workflow_command.
+    /// ndjson, gh_workflow_command, gh-workflow.
     #[arg(long, default_value = "ansi")]
     pub(crate) diagnostic_format: String,
 
@@ -76,7 +87,7 @@ impl CmdResult {
 
 #[cfg(not(tarpaulin_include))]
 fn main() {
-    let cli = Cli::parse();
+    let cli = parse_cli();
 
     let start = std::time::Instant::now();
     let exit_directives = if cli.quiet {
@@ -97,12 +108,47 @@ fn main() {
     std::process::exit(exit_directives.exit_code);
 }
 
+/// Parse the command line arguments. The closest configuration file (if any)
+/// defines the default values of the options.
+#[cfg(not(tarpaulin_include))]
+fn parse_cli() -> Cli {
+    let config =
+        std::env::current_dir().map_or(Ok(None), |current_dir| Config::discover(&current_dir));
+    match config {
+        Ok(Some((_, config))) => {
+            let matches = config.apply(Cli::command()).get_matches();
+            Cli::from_arg_matches(&matches).unwrap_or_else(|e| e.exit())
+        }
+        Ok(None) => Cli::parse(),
+        Err(e) => Cli::command()
+            .error(ErrorKind::InvalidValue, e.to_string())
+            .exit(),
+    }
+}
+
 /// Run the command specified by the CLI arguments and return the exit directives.
 #[cfg(not(tarpaulin_include))]
 fn run_command(cli: &Cli, log: impl Logger + Sync + Clone) -> ExitDirectives {
+    let cache_policy = cli.cache.policy();
     let cmd_result = match &cli.command {
-        Some(Commands::Registry(params)) => semconv_registry(log.clone(), params),
+        Some(Commands::Registry(params)) => {
+            semconv_registry(log.clone(), params, &cache_policy, cli.debug > 0)
+        }
         Some(Commands::Diagnostic(params)) => diagnostic::diagnostic(log.clone(), params),
+        Some(Commands::LiveCheck(params)) => {
+            live_check::live_check(log.clone(), params, &cache_policy)
+        }
+        Some(Commands::Emit(params)) => emit::emit(log.clone(), params, &cache_policy),
+        Some(Commands::Migrate(params)) => migrate::migrate(log.clone(), params),
+        Some(Commands::Serve(params)) => serve::serve(log.clone(), params, &cache_policy),
+        Some(Commands::Lsp(params)) => lsp::lsp(log.clone(), params),
+        Some(Commands::Policy(params)) => policy::policy(log.clone(), params),
+        Some(Commands::Template(params)) => {
+            template::template(log.clone(), params, &cache_policy, cli.debug > 0)
+        }
+        Some(Commands::Completion(params)) => {
+            completion::completion(log.clone(), params, &cache_policy)
+        }
         None => {
             return ExitDirectives {
                 exit_code: 0,
@@ -116,7 +162,7 @@ fn run_command(cli: &Cli, log: impl Logger + Sync + Clone) -> ExitDirectives {
 
 /// Render the diagnostic messages based on the diagnostic configuration and return the exit
 /// directives based on the diagnostic messages and the CmdResult quiet mode.
-fn process_diagnostics(
+pub(crate) fn process_diagnostics(
     cmd_result: CmdResult,
     logger: impl Logger + Sync + Clone,
 ) -> ExitDirectives {
@@ -0,0 +1,184 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Migrate telemetry from one version of a telemetry schema to another.
+
+use std::path::PathBuf;
+
+use clap::Args;
+use miette::Diagnostic;
+use serde::Serialize;
+
+use weaver_common::diagnostic::{DiagnosticMessage, DiagnosticMessages};
+use weaver_common::Logger;
+use weaver_schema::migration::Migration;
+use weaver_version::Versions;
+
+use crate::{CmdResult, DiagnosticArgs, ExitDirectives};
+
+/// Errors emitted by the `migrate` command
+#[derive(thiserror::Error, Debug, Serialize, Diagnostic)]
+#[non_exhaustive]
+pub enum Error {
+    /// The telemetry schema file could not be loaded.
+    #[error("Failed to load the telemetry schema file `{schema_file}`. {error}")]
+    InvalidSchemaFile { schema_file: PathBuf, error: String },
+
+    /// The transformations could not be serialized.
+    #[error("Failed to serialize the transformations. {error}")]
+    SerializationFailed { error: String },
+}
+
+impl From<Error> for DiagnosticMessages {
+    fn from(error: Error) -> Self {
+        DiagnosticMessages::new(vec![DiagnosticMessage::new(error)])
+    }
+}
+
+/// Parameters for the `migrate` command
+#[derive(Debug, Args)]
+pub struct MigrateArgs {
+    /// Telemetry schema file (or `versions` file) defining the transformations
+    /// of each version.
+    #[arg(short = 's', long)]
+    pub schema_file: PathBuf,
+
+    /// Version of the telemetry to migrate.
+    #[arg(long)]
+    pub from: semver::Version,
+
+    /// Version to which the telemetry is migrated.
+    #[arg(long)]
+    pub to: semver::Version,
+
+    /// OTLP file to migrate, encoded in OTLP/JSON (`.json`, `.jsonl`) or
+    /// protobuf (file name containing `trace`, `metric`, or `log`). If not
+    /// specified, the transformations are printed to stdout.
+    #[arg(short = 'i', long, requires = "output")]
+    pub input: Option<PathBuf>,
+
+    /// File where the migrated telemetry is written, in the encoding of the
+    /// input file.
+    #[arg(short = 'o', long, requires = "input")]
+    pub output: Option<PathBuf>,
+
+    /// Parameters to specify the diagnostic format.
+    #[command(flatten)]
+    pub diagnostic: DiagnosticArgs,
+}
+
+/// Migrate the telemetry and return the exit code.
+#[cfg(not(tarpaulin_include))]
+pub fn migrate(log: impl Logger + Sync + Clone, args: &MigrateArgs) -> CmdResult {
+    CmdResult::new(command(log, args), Some(args.diagnostic.clone()))
+}
+
+/// Compute the transformations between two versions of a telemetry schema and
+/// apply them to an OTLP file (or print them to stdout).
+#[cfg(not(tarpaulin_include))]
+pub(crate) fn command(
+    logger: impl Logger + Sync + Clone,
+    args: &MigrateArgs,
+) -> Result<ExitDirectives, DiagnosticMessages> {
+    let print_transformations = args.input.is_none();
+    if print_transformations {
+        logger.mute();
+    }
+
+    let versions =
+        Versions::load_from_file(&args.schema_file).map_err(|e| Error::InvalidSchemaFile {
+            schema_file: args.schema_file.clone(),
+            error: e.to_string(),
+        })?;
+    let migration = Migration::new(&versions, &args.from, &args.to)?;
+
+    if let (Some(input), Some(output)) = (&args.input, &args.output) {
+        logger.loading(&format!(
+            "Migrating `{}` from version {} to version {}",
+            input.display(),
+            args.from,
+            args.to
+        ));
+        migration.migrate_file(input, output)?;
+        logger.success(&format!(
+            "Migrated telemetry written to `{}`",
+            output.display()
+        ));
+    } else {
+        let yaml = serde_yaml::to_string(&migration).map_err(|e| Error::SerializationFailed {
+            error: e.to_string(),
+        })?;
+        print!("{}", yaml);
+    }
+
+    Ok(ExitDirectives {
+        exit_code: 0,
+        quiet_mode: print_transformations,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use serde_json::Value;
+    use tempdir::TempDir;
+    use weaver_common::TestLogger;
+
+    use crate::cli::{Cli, Commands};
+    use crate::migrate::MigrateArgs;
+    use crate::run_command;
+
+    fn cli(schema_file: &str, input: Option<PathBuf>, output: Option<PathBuf>) -> Cli {
+        Cli {
+            debug: 0,
+            quiet: false,
+            cache: Default::default(),
+            command: Some(Commands::Migrate(MigrateArgs {
+                schema_file: PathBuf::from(schema_file),
+                from: semver::Version::new(1, 0, 0),
+                to: semver::Version::new(1, 1, 0),
+                input,
+                output,
+                diagnostic: Default::default(),
+            })),
+        }
+    }
+
+    #[test]
+    fn test_migrate() {
+        let logger = TestLogger::new();
+        let schema_file = "crates/weaver_schema/data/migration/schema.yaml";
+
+        // Print the transformations.
+        let exit_directive = run_command(&cli(schema_file, None, None), logger.clone());
+        assert_eq!(exit_directive.exit_code, 0);
+
+        // Migrate an OTLP/JSON file.
+        let dir = TempDir::new("weaver-migrate").expect("Failed to create a temp dir");
+        let input = dir.path().join("logs.json");
+        std::fs::write(
+            &input,
+            r#"{"resourceLogs":[{"scopeLogs":[{"logRecords":[{"attributes":[{"key":"http.method","value":{"stringValue":"GET"}}]}]}]}]}"#,
+        )
+        .expect("Failed to write the input file");
+        let output = dir.path().join("migrated.json");
+        let exit_directive = run_command(
+            &cli(schema_file, Some(input), Some(output.clone())),
+            logger.clone(),
+        );
+        assert_eq!(exit_directive.exit_code, 0);
+        assert_eq!(logger.error_count(), 0);
+        let migrated: Value = serde_json::from_str(
+            &std::fs::read_to_string(&output).expect("Failed to read the output file"),
+        )
+        .expect("Invalid JSON");
+        assert_eq!(
+            migrated["resourceLogs"][0]["scopeLogs"][0]["logRecords"][0]["attributes"][0]["key"],
+            "http.request.method"
+        );
+
+        // The schema file doesn't exist.
+        let exit_directive = run_command(&cli("unknown.yaml", None, None), logger);
+        assert_eq!(exit_directive.exit_code, 1);
+    }
+}
@@ -0,0 +1,43 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Commands to manage the Rego policies.
+
+mod test;
+
+use clap::{Args, Subcommand};
+
+use weaver_common::Logger;
+
+use crate::policy::test::PolicyTestArgs;
+use crate::CmdResult;
+
+/// Parameters for the `policy` command
+#[derive(Debug, Args)]
+pub struct PolicyCommand {
+    /// Define the sub-commands for the `policy` command
+    #[clap(subcommand)]
+    pub command: PolicySubCommand,
+}
+
+/// Sub-commands to manage the Rego policies.
+#[derive(Debug, Subcommand)]
+#[clap(verbatim_doc_comment)]
+pub enum PolicySubCommand {
+    /// Runs the policy tests defined in the `*_test.rego` files.
+    ///
+    /// Every rule whose name starts with `test_` is a test case that passes when it evaluates to true. Test files are evaluated together with the policy files located in the same directory.
+    ///
+    /// The process exits with a code of 0 if all the policy tests pass.
+    #[clap(verbatim_doc_comment)]
+    Test(PolicyTestArgs),
+}
+
+/// Manage the Rego policies.
+#[cfg(not(tarpaulin_include))]
+pub fn policy(log: impl Logger + Sync + Clone, command: &PolicyCommand) -> CmdResult {
+    match &command.command {
+        PolicySubCommand::Test(args) => {
+            CmdResult::new(test::command(log, args), Some(args.diagnostic.clone()))
+        }
+    }
+}
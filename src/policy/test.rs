@@ -0,0 +1,112 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Run the tests defined in the `*_test.rego` files located next to the policies.
+
+use std::path::PathBuf;
+
+use clap::Args;
+
+use weaver_checker::policy_test::{run_policy_tests, PolicyTestOutcome};
+use weaver_common::diagnostic::DiagnosticMessages;
+use weaver_common::Logger;
+
+use crate::registry::Error;
+use crate::{DiagnosticArgs, ExitDirectives};
+
+/// Parameters for the `policy test` sub-command
+#[derive(Debug, Args)]
+pub struct PolicyTestArgs {
+    /// Policy test files or directories in which the `*_test.rego` files are
+    /// searched (recursively). Default is the current directory.
+    #[arg(default_value = ".")]
+    pub policies: Vec<PathBuf>,
+
+    /// Parameters to specify the diagnostic format.
+    #[command(flatten)]
+    pub diagnostic: DiagnosticArgs,
+}
+
+/// Run the policy tests and report the outcome of each test.
+#[cfg(not(tarpaulin_include))]
+pub(crate) fn command(
+    logger: impl Logger + Sync + Clone,
+    args: &PolicyTestArgs,
+) -> Result<ExitDirectives, DiagnosticMessages> {
+    logger.loading(&format!("Running the policy tests in {:?}", args.policies));
+
+    let results = run_policy_tests(&args.policies)?;
+
+    let mut failed = 0;
+    for result in &results {
+        let test = format!(
+            "{}: {}.{}",
+            result.file.display(),
+            result.package,
+            result.name
+        );
+        match &result.outcome {
+            PolicyTestOutcome::Passed => logger.success(&format!("PASS {}", test)),
+            PolicyTestOutcome::Failed => {
+                failed += 1;
+                logger.error(&format!("FAIL {}", test));
+            }
+            PolicyTestOutcome::Error(error) => {
+                failed += 1;
+                logger.error(&format!("ERROR {}\n{}", test, error));
+            }
+        }
+    }
+
+    if failed > 0 {
+        return Err(Error::PolicyTestsFailed {
+            failed,
+            total: results.len(),
+        }
+        .into());
+    }
+    logger.success(&format!("{} policy test(s) passed", results.len()));
+
+    Ok(ExitDirectives {
+        exit_code: 0,
+        quiet_mode: false,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use weaver_common::TestLogger;
+
+    use crate::cli::{Cli, Commands};
+    use crate::policy::test::PolicyTestArgs;
+    use crate::policy::{PolicyCommand, PolicySubCommand};
+    use crate::run_command;
+
+    #[test]
+    fn test_policy_test() {
+        let logger = TestLogger::new();
+        let cli = |policies: &str| Cli {
+            debug: 0,
+            quiet: false,
+            cache: Default::default(),
+            command: Some(Commands::Policy(PolicyCommand {
+                command: PolicySubCommand::Test(PolicyTestArgs {
+                    policies: vec![PathBuf::from(policies)],
+                    diagnostic: Default::default(),
+                }),
+            })),
+        };
+
+        // The fixture contains a failing test.
+        let exit_directive = run_command(
+            &cli("crates/weaver_checker/data/policy_tests/"),
+            logger.clone(),
+        );
+        assert_eq!(exit_directive.exit_code, 1);
+
+        // No test file in this directory.
+        let exit_directive = run_command(&cli("crates/weaver_checker/data/stages/"), logger);
+        assert_eq!(exit_directive.exit_code, 0);
+    }
+}
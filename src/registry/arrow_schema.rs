@@ -0,0 +1,367 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Generate Apache Arrow (and Parquet) schemas from the signal groups of a
+//! semantic convention registry.
+
+use std::collections::{BTreeMap, HashMap};
+use std::fs::File;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use arrow_ipc::writer::FileWriter;
+use arrow_schema::{DataType, Field, Fields, Schema};
+use clap::Args;
+use parquet::arrow::ArrowWriter;
+
+use weaver_cache::Cache;
+use weaver_common::diagnostic::DiagnosticMessages;
+use weaver_common::Logger;
+use weaver_resolved_schema::attribute::Attribute;
+use weaver_resolved_schema::ResolvedTelemetrySchema;
+use weaver_semconv::attribute::{
+    AttributeType, PrimitiveOrArrayTypeSpec, TemplateTypeSpec, ValueSpec,
+};
+use weaver_semconv::group::GroupType;
+use weaver_semconv::registry::SemConvRegistry;
+
+use crate::registry::{Error, RegistryArgs};
+use crate::util::{load_semconv_specs, resolve_semconv_specs};
+use crate::{DiagnosticArgs, ExitDirectives};
+
+/// Parameters for the `registry arrow-schema` sub-command
+#[derive(Debug, Args)]
+pub struct RegistryArrowSchemaArgs {
+    /// Parameters to specify the semantic convention registry
+    #[command(flatten)]
+    registry: RegistryArgs,
+
+    /// Path to the directory where the schemas will be saved.
+    #[arg(short, long, default_value = "output")]
+    output: PathBuf,
+
+    /// Also generate an empty Parquet file with the schema of each signal type.
+    #[arg(long)]
+    parquet: bool,
+
+    /// Parameters to specify the diagnostic format.
+    #[command(flatten)]
+    pub diagnostic: DiagnosticArgs,
+}
+
+/// Generate the Arrow schemas of the signal types of a semantic convention
+/// registry.
+#[cfg(not(tarpaulin_include))]
+pub(crate) fn command(
+    logger: impl Logger + Sync + Clone,
+    cache: &Cache,
+    args: &RegistryArrowSchemaArgs,
+) -> Result<ExitDirectives, DiagnosticMessages> {
+    logger.loading(&format!(
+        "Generating the Arrow schemas of the registry `{}`",
+        args.registry.registries()
+    ));
+
+    let registry_id = "default";
+    let semconv_specs = load_semconv_specs(
+        &args.registry.registry_paths(),
+        &args.registry.file_filter()?,
+        cache,
+        logger.clone(),
+    )?;
+    let mut registry = SemConvRegistry::from_semconv_specs(registry_id, semconv_specs);
+    let schema = resolve_semconv_specs(&mut registry, logger.clone())?;
+
+    std::fs::create_dir_all(&args.output).map_err(|e| Error::SchemaWriteFailed {
+        output: args.output.clone(),
+        error: e.to_string(),
+    })?;
+    for (signal, schema) in signal_schemas(&schema) {
+        let schema = Arc::new(schema);
+        let path = args.output.join(format!("{}.arrow", signal));
+        write_ipc(&path, &schema)?;
+        logger.success(&format!(
+            "Arrow schema of the {} signals ({} columns) written to `{}`",
+            signal,
+            schema.fields().len(),
+            path.display()
+        ));
+        if args.parquet {
+            let path = args.output.join(format!("{}.parquet", signal));
+            write_parquet(&path, &schema)?;
+            logger.success(&format!(
+                "Parquet schema of the {} signals written to `{}`",
+                signal,
+                path.display()
+            ));
+        }
+    }
+
+    Ok(ExitDirectives {
+        exit_code: 0,
+        quiet_mode: false,
+    })
+}
+
+/// Returns the name of the signal type of a group, or None if the group is not
+/// a signal group.
+fn signal_name(group_type: &GroupType) -> Option<&'static str> {
+    match group_type {
+        GroupType::Span => Some("span"),
+        GroupType::Event => Some("event"),
+        GroupType::Metric => Some("metric"),
+        GroupType::Resource => Some("resource"),
+        GroupType::Scope => Some("scope"),
+        GroupType::AttributeGroup | GroupType::MetricGroup => None,
+    }
+}
+
+/// Returns the Arrow schema of each signal type, by signal name.
+///
+/// The columns of a schema are the attributes used by the groups of the signal
+/// type, sorted by name. All the columns are nullable as a signal only records
+/// the attributes of its own group. The brief, the stability, and the
+/// deprecation note of the attributes are kept in the metadata of the fields.
+fn signal_schemas(schema: &ResolvedTelemetrySchema) -> BTreeMap<&'static str, Schema> {
+    let mut attributes: BTreeMap<&'static str, BTreeMap<&str, &Attribute>> = BTreeMap::new();
+    for group in schema
+        .registries
+        .values()
+        .flat_map(|registry| &registry.groups)
+    {
+        let Some(signal) = signal_name(&group.r#type) else {
+            continue;
+        };
+        let columns = attributes.entry(signal).or_default();
+        for attr in group
+            .attributes
+            .iter()
+            .filter_map(|attr_ref| schema.catalog().attribute(attr_ref))
+        {
+            _ = columns.entry(attr.name.as_str()).or_insert(attr);
+        }
+    }
+    attributes
+        .into_iter()
+        .map(|(signal, columns)| {
+            let fields: Vec<Field> = columns.into_values().map(field).collect();
+            let metadata = HashMap::from([("signal".to_owned(), signal.to_owned())]);
+            (signal, Schema::new_with_metadata(fields, metadata))
+        })
+        .collect()
+}
+
+/// Returns the Arrow field of an attribute.
+fn field(attr: &Attribute) -> Field {
+    let mut metadata = HashMap::from([("brief".to_owned(), attr.brief.trim().to_owned())]);
+    if let Some(stability) = &attr.stability {
+        _ = metadata.insert("stability".to_owned(), stability.to_string());
+    }
+    if let Some(deprecated) = &attr.deprecated {
+        _ = metadata.insert("deprecated".to_owned(), deprecated.clone());
+    }
+    Field::new(attr.name.as_str(), data_type(&attr.r#type), true).with_metadata(metadata)
+}
+
+/// Returns the Arrow data type of an attribute type.
+///
+/// Arrays are mapped to lists, template attributes to maps from the key
+/// suffix to the value, and string enums to dictionary encoded strings.
+fn data_type(attr_type: &AttributeType) -> DataType {
+    let list = |item: DataType| DataType::List(Arc::new(Field::new("item", item, true)));
+    match attr_type {
+        AttributeType::PrimitiveOrArray(primitive) => match primitive {
+            PrimitiveOrArrayTypeSpec::Boolean => DataType::Boolean,
+            PrimitiveOrArrayTypeSpec::Int => DataType::Int64,
+            PrimitiveOrArrayTypeSpec::Double => DataType::Float64,
+            PrimitiveOrArrayTypeSpec::String => DataType::Utf8,
+            PrimitiveOrArrayTypeSpec::Booleans => list(DataType::Boolean),
+            PrimitiveOrArrayTypeSpec::Ints => list(DataType::Int64),
+            PrimitiveOrArrayTypeSpec::Doubles => list(DataType::Float64),
+            PrimitiveOrArrayTypeSpec::Strings => list(DataType::Utf8),
+        },
+        AttributeType::Template(template) => {
+            let value = match template {
+                TemplateTypeSpec::Boolean => DataType::Boolean,
+                TemplateTypeSpec::Int => DataType::Int64,
+                TemplateTypeSpec::Double => DataType::Float64,
+                TemplateTypeSpec::String => DataType::Utf8,
+                TemplateTypeSpec::Booleans => list(DataType::Boolean),
+                TemplateTypeSpec::Ints => list(DataType::Int64),
+                TemplateTypeSpec::Doubles => list(DataType::Float64),
+                TemplateTypeSpec::Strings => list(DataType::Utf8),
+            };
+            let entries = Fields::from(vec![
+                Field::new("key", DataType::Utf8, false),
+                Field::new("value", value, true),
+            ]);
+            DataType::Map(
+                Arc::new(Field::new("entries", DataType::Struct(entries), false)),
+                false,
+            )
+        }
+        AttributeType::Enum { members, .. } => {
+            if members
+                .iter()
+                .all(|member| matches!(member.value, ValueSpec::Int(_)))
+            {
+                DataType::Int64
+            } else if members
+                .iter()
+                .all(|member| matches!(member.value, ValueSpec::Double(_)))
+            {
+                DataType::Float64
+            } else {
+                DataType::Dictionary(Box::new(DataType::Int32), Box::new(DataType::Utf8))
+            }
+        }
+    }
+}
+
+/// Writes the schema in an Arrow IPC file without any record batch.
+fn write_ipc(path: &Path, schema: &Schema) -> Result<(), Error> {
+    let write_failed = |e: &dyn ToString| Error::SchemaWriteFailed {
+        output: path.to_path_buf(),
+        error: e.to_string(),
+    };
+    let file = File::create(path).map_err(|e| write_failed(&e))?;
+    let mut writer = FileWriter::try_new(file, schema).map_err(|e| write_failed(&e))?;
+    writer.finish().map_err(|e| write_failed(&e))
+}
+
+/// Writes the schema in a Parquet file without any row.
+fn write_parquet(path: &Path, schema: &Arc<Schema>) -> Result<(), Error> {
+    let write_failed = |e: &dyn ToString| Error::SchemaWriteFailed {
+        output: path.to_path_buf(),
+        error: e.to_string(),
+    };
+    let file = File::create(path).map_err(|e| write_failed(&e))?;
+    let writer = ArrowWriter::try_new(file, schema.clone(), None).map_err(|e| write_failed(&e))?;
+    _ = writer.close().map_err(|e| write_failed(&e))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::File;
+
+    use arrow_ipc::reader::FileReader;
+    use arrow_schema::DataType;
+    use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+    use tempdir::TempDir;
+
+    use weaver_resolver::SchemaResolver;
+    use weaver_semconv::registry::SemConvRegistry;
+
+    use crate::registry::arrow_schema::{signal_schemas, write_ipc, write_parquet};
+
+    #[test]
+    fn test_signal_schemas() {
+        let mut registry = SemConvRegistry::new("default");
+        registry
+            .add_semconv_spec_from_string(
+                "registry.yaml",
+                r#"groups:
+  - id: registry.http
+    type: attribute_group
+    brief: HTTP attributes.
+    prefix: http
+    attributes:
+      - id: request.method
+        type:
+          members:
+            - id: get
+              value: GET
+            - id: post
+              value: POST
+        stability: stable
+        brief: HTTP request method.
+      - id: request.header
+        type: template[string[]]
+        brief: HTTP request headers.
+        examples: ['http.request.header.content-type=["application/json"]']
+      - id: response.status_code
+        type: int
+        brief: HTTP response status code.
+        examples: [200]
+  - id: span.http.client
+    type: span
+    span_kind: client
+    brief: HTTP client span.
+    attributes:
+      - ref: http.request.method
+      - ref: http.response.status_code
+      - ref: http.request.header
+  - id: metric.http.client.request.duration
+    type: metric
+    metric_name: http.client.request.duration
+    brief: Duration of HTTP client requests.
+    instrument: histogram
+    unit: s
+    attributes:
+      - ref: http.request.method
+"#,
+            )
+            .expect("Invalid registry");
+        let schema = SchemaResolver::resolve_semantic_convention_registry(&mut registry)
+            .expect("Failed to resolve the registry");
+        let schemas = signal_schemas(&schema);
+        assert_eq!(
+            schemas.keys().copied().collect::<Vec<_>>(),
+            vec!["metric", "span"]
+        );
+
+        let spans = &schemas["span"];
+        assert_eq!(spans.metadata()["signal"], "span");
+        let method = spans
+            .field_with_name("http.request.method")
+            .expect("Missing column");
+        assert!(method.is_nullable());
+        assert_eq!(
+            method.data_type(),
+            &DataType::Dictionary(Box::new(DataType::Int32), Box::new(DataType::Utf8))
+        );
+        assert_eq!(method.metadata()["stability"], "stable");
+        let header = spans
+            .field_with_name("http.request.header")
+            .expect("Missing column");
+        let DataType::Map(entries, false) = header.data_type() else {
+            panic!("Map expected");
+        };
+        let DataType::Struct(entries) = entries.data_type() else {
+            panic!("Struct expected");
+        };
+        assert_eq!(entries[0].data_type(), &DataType::Utf8);
+        assert!(
+            matches!(entries[1].data_type(), DataType::List(item) if item.data_type() == &DataType::Utf8)
+        );
+        assert_eq!(
+            spans
+                .fields()
+                .iter()
+                .map(|f| (f.name().as_str(), f.data_type()))
+                .skip(1)
+                .collect::<Vec<_>>(),
+            vec![
+                ("http.request.method", method.data_type()),
+                ("http.response.status_code", &DataType::Int64),
+            ]
+        );
+        assert_eq!(schemas["metric"].fields().len(), 1);
+
+        let dir = TempDir::new("weaver-arrow").expect("Failed to create a temp dir");
+        let ipc_path = dir.path().join("span.arrow");
+        write_ipc(&ipc_path, spans).expect("Failed to write the Arrow file");
+        let reader = FileReader::try_new(File::open(&ipc_path).expect("Missing file"), None)
+            .expect("Invalid Arrow file");
+        assert_eq!(reader.schema().as_ref(), spans);
+
+        let parquet_path = dir.path().join("span.parquet");
+        write_parquet(&parquet_path, &schemas["span"].clone().into())
+            .expect("Failed to write the Parquet file");
+        let builder = ParquetRecordBatchReaderBuilder::try_new(
+            File::open(&parquet_path).expect("Missing file"),
+        )
+        .expect("Invalid Parquet file");
+        assert_eq!(builder.schema().fields(), spans.fields());
+    }
+}
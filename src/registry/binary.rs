@@ -0,0 +1,160 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Binary format of the resolved registry.
+//!
+//! A binary file starts with a header (the `WEAVERRR` magic bytes, the version
+//! of the format as a little-endian `u16`, and the length-prefixed version of
+//! weaver that wrote the file) followed by the resolved registry encoded in
+//! CBOR. The file is loaded back with `--resolved-registry` to skip the
+//! parsing and the resolution of the registry. Files written by another
+//! version of weaver are rejected as the resolved registry model may differ.
+
+use std::io::Write;
+use std::path::Path;
+
+use weaver_forge::registry::ResolvedRegistry;
+
+use crate::registry::Error;
+
+/// Magic bytes identifying a resolved registry in the binary format.
+const MAGIC: &[u8; 8] = b"WEAVERRR";
+
+/// Version of the binary format.
+const FORMAT_VERSION: u16 = 1;
+
+/// Version of weaver writing and reading the binary files.
+const WEAVER_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// Encodes a resolved registry in the binary format.
+pub(crate) fn to_binary(registry: &ResolvedRegistry) -> Result<Vec<u8>, String> {
+    let mut bytes = Vec::with_capacity(1024 * 1024);
+    bytes.extend_from_slice(MAGIC);
+    bytes.extend_from_slice(&FORMAT_VERSION.to_le_bytes());
+    bytes.push(WEAVER_VERSION.len() as u8);
+    bytes.extend_from_slice(WEAVER_VERSION.as_bytes());
+    ciborium::into_writer(registry, &mut bytes)
+        .map_err(|e| format!("Failed to encode the resolved registry: {e}"))?;
+    Ok(bytes)
+}
+
+/// Decodes a resolved registry encoded in the binary format by the same
+/// version of weaver.
+pub(crate) fn from_binary(bytes: &[u8]) -> Result<ResolvedRegistry, String> {
+    let payload = bytes
+        .strip_prefix(MAGIC)
+        .ok_or("Not a resolved registry in the binary format")?;
+    if payload.len() < 2 {
+        return Err("Truncated header".to_owned());
+    }
+    let (format_version, payload) = payload.split_at(2);
+    let format_version = u16::from_le_bytes([format_version[0], format_version[1]]);
+    if format_version != FORMAT_VERSION {
+        return Err(format!(
+            "Unsupported binary format version {format_version} (expected {FORMAT_VERSION})"
+        ));
+    }
+    let (version_len, payload) = payload.split_first().ok_or("Truncated header")?;
+    if payload.len() < *version_len as usize {
+        return Err("Truncated header".to_owned());
+    }
+    let (version, payload) = payload.split_at(*version_len as usize);
+    if version != WEAVER_VERSION.as_bytes() {
+        return Err(format!(
+            "Written by weaver {}, this is weaver {WEAVER_VERSION}",
+            String::from_utf8_lossy(version)
+        ));
+    }
+    ciborium::from_reader(payload).map_err(|e| format!("Failed to decode the registry: {e}"))
+}
+
+/// Writes a resolved registry in the binary format into a file, or into the
+/// standard output.
+pub(crate) fn write_binary(
+    registry: &ResolvedRegistry,
+    output: Option<&Path>,
+) -> Result<(), String> {
+    let bytes = to_binary(registry)?;
+    let Some(path) = output else {
+        let mut stdout = std::io::stdout().lock();
+        return stdout
+            .write_all(&bytes)
+            .and_then(|_| stdout.flush())
+            .map_err(|e| format!("Failed to write the resolved registry to stdout: {e:?}"));
+    };
+    std::fs::write(path, bytes)
+        .map_err(|e| format!("Failed to write the resolved registry to file: {e:?}"))
+}
+
+/// Loads a resolved registry written in the binary format by
+/// `weaver registry resolve --format binary`.
+pub(crate) fn load_resolved_registry(path: &Path) -> Result<ResolvedRegistry, Error> {
+    let invalid = |error: String| Error::InvalidResolvedRegistry {
+        path: path.to_path_buf(),
+        error,
+    };
+    let bytes = std::fs::read(path).map_err(|e| invalid(e.to_string()))?;
+    let mut registry = from_binary(&bytes).map_err(invalid)?;
+    // The index and the resources are derived from the groups, they are not
+    // necessarily in the file (e.g. without `--attribute-index`).
+    registry.build_attribute_index();
+    registry.expose_resources();
+    Ok(registry)
+}
+
+#[cfg(test)]
+mod tests {
+    use tempdir::TempDir;
+    use weaver_forge::registry::ResolvedRegistry;
+    use weaver_resolver::SchemaResolver;
+    use weaver_semconv::registry::SemConvRegistry;
+
+    use super::*;
+
+    #[test]
+    fn test_binary_round_trip() {
+        let registry_path = "crates/weaver_codegen_test/semconv_registry";
+        let mut registry = SemConvRegistry::try_from_path_pattern(
+            "default",
+            &format!("{registry_path}/**/*.yaml"),
+        )
+        .expect("Failed to load the registry");
+        let schema = SchemaResolver::resolve_semantic_convention_registry(&mut registry)
+            .expect("Failed to resolve the registry");
+        let registry = ResolvedRegistry::try_from_resolved_registry(
+            schema.registry("default").expect("Registry not found"),
+            schema.catalog(),
+        )
+        .expect("Failed to create the registry");
+
+        let bytes = to_binary(&registry).expect("Failed to encode the registry");
+        assert_eq!(
+            from_binary(&bytes).expect("Failed to decode the registry"),
+            registry
+        );
+
+        let json = serde_json::to_vec(&registry).expect("Failed to serialize the registry");
+        assert!(bytes.len() < json.len());
+
+        assert!(from_binary(b"{}").is_err());
+        assert!(from_binary(&bytes[..MAGIC.len() + 1]).is_err());
+        let mut other_version = bytes.clone();
+        other_version[MAGIC.len()] = 2;
+        assert!(from_binary(&other_version)
+            .expect_err("Unsupported version expected")
+            .contains("format version 2"));
+
+        // The attribute index and the resources are rebuilt when loading a
+        // file written without them.
+        assert!(!registry.attribute_index.is_empty());
+        let mut stripped = registry.clone();
+        stripped.attribute_index.clear();
+        stripped.resources.clear();
+        let dir = TempDir::new("weaver-binary").expect("Failed to create a temp dir");
+        let path = dir.path().join("registry.bin");
+        write_binary(&stripped, Some(&path)).expect("Failed to write the registry");
+        assert_eq!(
+            load_resolved_registry(&path).expect("Failed to load the registry"),
+            registry
+        );
+    }
+}
@@ -2,22 +2,29 @@
 
 //! Check a semantic convention registry.
 
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
 
 use clap::Args;
 
 use weaver_cache::Cache;
-use weaver_checker::PolicyStage;
+use weaver_common::cancellation::CancellationToken;
 use weaver_common::diagnostic::{DiagnosticMessages, ResultExt};
-use weaver_common::error::handle_errors;
 use weaver_common::Logger;
 use weaver_forge::registry::ResolvedRegistry;
+use weaver_resolver::ResolverOptions;
 use weaver_semconv::registry::SemConvRegistry;
 
+use crate::registry::binary::load_resolved_registry;
+use crate::registry::dead_definitions::check_dead_definitions;
+use crate::registry::links::check_links;
+use crate::registry::owners::{load_owners, route_findings};
+use crate::registry::text_lint::{check_text, Dictionary};
+use crate::registry::watch::{watch, WatchedPaths};
 use crate::registry::RegistryArgs;
 use crate::util::{
-    check_policies, check_policy_stage, init_policy_engine, load_semconv_specs,
-    resolve_semconv_specs, semconv_registry_path_from,
+    check_policies, check_resolved_policies, init_policy_engine, load_semconv_specs,
+    resolve_semconv_specs_with_options,
 };
 use crate::{DiagnosticArgs, ExitDirectives};
 
@@ -37,39 +44,180 @@ pub struct RegistryCheckArgs {
     #[arg(long, default_value = "false")]
     pub skip_policies: bool,
 
+    /// Built-in policies to disable, identified by their id (e.g. `attr_name_format`).
+    /// The built-in policies implement the semantic convention authoring rules and
+    /// run by default.
+    #[arg(long = "disable-builtin-policy")]
+    pub disabled_builtin_policies: Vec<String>,
+
     /// Display the policy coverage report (useful for debugging).
     #[arg(long, default_value = "false")]
     pub display_policy_coverage: bool,
 
+    /// Report, as warnings, the attributes defined in attribute groups but never
+    /// used by a span, event, metric, resource, or scope group, and the groups
+    /// that are neither extended nor used by such a group.
+    #[arg(long, default_value = "false")]
+    pub dead_definitions: bool,
+
+    /// Report, as warnings, the misspelled words, the discouraged terms, and the
+    /// RFC 2119 keywords not capitalized (e.g. `should` instead of `SHOULD`)
+    /// found in the `brief` and `note` of the groups and attributes.
+    #[arg(long, default_value = "false")]
+    pub lint_text: bool,
+
+    /// Project dictionary of the text lint: one accepted word per line, or a
+    /// `word -> replacement` rule for a misspelling or a discouraged term.
+    #[arg(long, requires = "lint_text")]
+    pub dictionary: Option<PathBuf>,
+
+    /// Check that the HTTP(S) links found in the `brief` and `note` of the
+    /// groups and attributes resolve. The links found alive are cached for a
+    /// day.
+    #[arg(long, default_value = "false")]
+    pub check_links: bool,
+
+    /// Fail the check when a group references a deprecated attribute or group
+    /// (attribute reference, `extends` clause, or `include` constraint). By
+    /// default, these references are reported as warnings.
+    #[arg(long, default_value = "false")]
+    pub fail_on_deprecated_ref: bool,
+
+    /// Maximum number of seconds of the resolution of the registry. The check
+    /// fails if the resolution takes longer. Unlimited by default.
+    #[arg(long)]
+    pub timeout: Option<u64>,
+
+    /// Maximum number of `extends` clauses followed from a group (e.g. 2 for
+    /// `a` extending `b` extending `c`). Unlimited by default.
+    #[arg(long)]
+    pub max_extends_depth: Option<usize>,
+
+    /// Maximum number of iterations of each iterative resolution step
+    /// (`extends` clauses, attribute references, and `include` constraints).
+    /// Unlimited by default.
+    #[arg(long)]
+    pub max_iterations: Option<usize>,
+
+    /// Only report the findings owned by this owner, as declared in the
+    /// `OWNERS` file of the registry. Can be repeated.
+    #[arg(long)]
+    pub owner: Vec<String>,
+
+    /// Re-run the check each time a file of the registry or a policy file
+    /// changes (the registry must be a local directory).
+    #[arg(long, default_value = "false")]
+    pub watch: bool,
+
+    /// Path to a resolved registry written by `weaver registry resolve --format binary`.
+    /// The registry is not loaded nor resolved again, so only the policies
+    /// applying to the resolved registry are checked.
+    #[arg(long, conflicts_with_all = ["watch", "dead_definitions", "lint_text"])]
+    pub resolved_registry: Option<PathBuf>,
+
     /// Parameters to specify the diagnostic format.
     #[command(flatten)]
     pub diagnostic: DiagnosticArgs,
 }
 
-/// Check a semantic convention registry.
+/// Check a semantic convention registry, once or each time its files change.
 #[cfg(not(tarpaulin_include))]
 pub(crate) fn command(
     logger: impl Logger + Sync + Clone,
     cache: &Cache,
     args: &RegistryCheckArgs,
+) -> Result<ExitDirectives, DiagnosticMessages> {
+    if args.watch {
+        let mut watched = WatchedPaths::registries(&args.registry.registry)?;
+        for policy in &args.policies {
+            watched = watched.watch(policy);
+        }
+        return watch(logger.clone(), &watched, &args.diagnostic, || {
+            let owners = load_owners(&args.registry)?;
+            route_findings(check(logger.clone(), cache, args), &owners, &args.owner)
+        });
+    }
+    let owners = load_owners(&args.registry)?;
+    let result = if let Some(path) = &args.resolved_registry {
+        check_resolved_registry(logger, cache, args, path)
+    } else {
+        check(logger, cache, args)
+    };
+    // Every finding is attributed to the owners of the attribute or the group
+    // it relates to.
+    route_findings(result, &owners, &args.owner)
+}
+
+/// Check the policies against a resolved registry loaded from a binary file.
+#[cfg(not(tarpaulin_include))]
+fn check_resolved_registry(
+    logger: impl Logger + Sync + Clone,
+    cache: &Cache,
+    args: &RegistryCheckArgs,
+    path: &Path,
+) -> Result<ExitDirectives, DiagnosticMessages> {
+    logger.loading(&format!("Checking resolved registry `{}`", path.display()));
+    let resolved_registry = load_resolved_registry(path)?;
+    if !args.skip_policies {
+        let mut policy_engine =
+            init_policy_engine(&[], cache, &args.policies, args.display_policy_coverage)?;
+        _ = policy_engine.add_builtin_policies(&args.disabled_builtin_policies)?;
+        check_resolved_policies(&mut policy_engine, &[], &resolved_registry, logger.clone())?;
+    }
+    if args.check_links {
+        check_links(&resolved_registry, &cache.link_check_cache_file(), logger)?;
+    }
+    Ok(ExitDirectives {
+        exit_code: 0,
+        quiet_mode: false,
+    })
+}
+
+/// Check a semantic convention registry.
+#[cfg(not(tarpaulin_include))]
+fn check(
+    logger: impl Logger + Sync + Clone,
+    cache: &Cache,
+    args: &RegistryCheckArgs,
 ) -> Result<ExitDirectives, DiagnosticMessages> {
     let mut diag_msgs = DiagnosticMessages::empty();
-    logger.loading(&format!("Checking registry `{}`", args.registry.registry));
+    logger.loading(&format!(
+        "Checking registry `{}`",
+        args.registry.registries()
+    ));
 
     let registry_id = "default";
-    let registry_path =
-        semconv_registry_path_from(&args.registry.registry, &args.registry.registry_git_sub_dir);
+    let registry_paths = args.registry.registry_paths();
 
     // Load the semantic convention registry into a local cache.
     // No parsing errors should be observed.
-    let semconv_specs = load_semconv_specs(&registry_path, cache, logger.clone())?;
+    let semconv_specs = load_semconv_specs(
+        &registry_paths,
+        &args.registry.file_filter()?,
+        cache,
+        logger.clone(),
+    )?;
+    if args.dead_definitions {
+        // Dead definitions are warnings, they don't prevent the next stages.
+        _ = check_dead_definitions(&semconv_specs).capture_diag_msgs_into(&mut diag_msgs);
+    }
+    if args.lint_text {
+        let dictionary = match &args.dictionary {
+            Some(path) => Dictionary::from_file(path)?,
+            None => Dictionary::default(),
+        };
+        // The text findings are warnings as well.
+        _ = check_text(&semconv_specs, &dictionary).capture_diag_msgs_into(&mut diag_msgs);
+    }
     let mut policy_engine = if !args.skip_policies {
-        Some(init_policy_engine(
-            &registry_path,
+        let mut policy_engine = init_policy_engine(
+            &registry_paths,
             cache,
             &args.policies,
             args.display_policy_coverage,
-        )?)
+        )?;
+        _ = policy_engine.add_builtin_policies(&args.disabled_builtin_policies)?;
+        Some(policy_engine)
     } else {
         None
     };
@@ -92,10 +240,23 @@ pub(crate) fn command(
     // If there are any resolution errors, they should be captured into the ongoing list of
     // diagnostic messages and returned immediately because there is no point in continuing
     // as the resolution is a prerequisite for the next stages.
-    let resolved_schema =
-        resolve_semconv_specs(&mut registry, logger.clone()).combine_diag_msgs_with(&diag_msgs)?;
+    let options = ResolverOptions {
+        fail_on_deprecated_ref: args.fail_on_deprecated_ref,
+        max_extends_depth: args.max_extends_depth,
+        max_iterations: args.max_iterations,
+        ..ResolverOptions::default()
+    };
+    let cancellation = match args.timeout {
+        Some(timeout) => CancellationToken::new().with_timeout(Duration::from_secs(timeout)),
+        None => CancellationToken::new(),
+    };
+    let (resolved_schema, warnings) =
+        resolve_semconv_specs_with_options(&mut registry, &options, &cancellation, logger.clone())
+            .combine_diag_msgs_with(&diag_msgs)?;
+    // The references to deprecated items are reported as warnings.
+    diag_msgs.extend(warnings);
 
-    if let Some(policy_engine) = policy_engine.as_mut() {
+    if policy_engine.is_some() || args.check_links {
         // Convert the resolved schemas into a resolved registry.
         // If there are any policy violations, they should be captured into the ongoing list of
         // diagnostic messages and returned immediately because there is no point in continuing
@@ -108,23 +269,30 @@ pub(crate) fn command(
         )
         .combine_diag_msgs_with(&diag_msgs)?;
 
-        // Check the policies against the resolved registry (`PolicyState::AfterResolution`).
-        let errs = check_policy_stage(
-            policy_engine,
-            PolicyStage::AfterResolution,
-            &registry_path.to_string(),
-            &resolved_registry,
-        );
-
-        // Append the policy errors to the ongoing list of diagnostic messages and if there are
-        // any errors, return them immediately.
-        if let Err(err) = handle_errors(errs) {
-            diag_msgs.extend(err.into());
+        if let Some(policy_engine) = policy_engine.as_mut() {
+            // Check the policies against the resolved registry (`PolicyState::AfterResolution`).
+            // Any policy violations are appended to the ongoing list of diagnostic messages.
+            _ = check_resolved_policies(
+                policy_engine,
+                &registry_paths,
+                &resolved_registry,
+                logger.clone(),
+            )
+            .capture_diag_msgs_into(&mut diag_msgs);
         }
-        if !diag_msgs.is_empty() {
-            return Err(diag_msgs);
+        if args.check_links {
+            // The dead links are appended to the ongoing list of diagnostic messages.
+            _ = check_links(
+                &resolved_registry,
+                &cache.link_check_cache_file(),
+                logger.clone(),
+            )
+            .capture_diag_msgs_into(&mut diag_msgs);
         }
     }
+    if !diag_msgs.is_empty() {
+        return Err(diag_msgs);
+    }
 
     Ok(ExitDirectives {
         exit_code: 0,
@@ -149,17 +317,32 @@ mod tests {
         let cli = Cli {
             debug: 0,
             quiet: false,
+            cache: Default::default(),
             command: Some(Commands::Registry(RegistryCommand {
                 command: RegistrySubCommand::Check(RegistryCheckArgs {
                     registry: RegistryArgs {
-                        registry: RegistryPath::Local(
+                        registry: vec![RegistryPath::Local(
                             "crates/weaver_codegen_test/semconv_registry/".to_owned(),
-                        ),
+                        )],
                         registry_git_sub_dir: None,
+                        include: vec![],
+                        exclude: vec![],
                     },
                     policies: vec![],
                     skip_policies: true,
+                    disabled_builtin_policies: vec![],
                     display_policy_coverage: false,
+                    dead_definitions: false,
+                    lint_text: false,
+                    dictionary: None,
+                    check_links: false,
+                    fail_on_deprecated_ref: false,
+                    timeout: None,
+                    max_extends_depth: None,
+                    max_iterations: None,
+                    owner: vec![],
+                    watch: false,
+                    resolved_registry: None,
                     diagnostic: Default::default(),
                 }),
             })),
@@ -173,25 +356,91 @@ mod tests {
         let cli = Cli {
             debug: 0,
             quiet: false,
+            cache: Default::default(),
             command: Some(Commands::Registry(RegistryCommand {
                 command: RegistrySubCommand::Check(RegistryCheckArgs {
                     registry: RegistryArgs {
-                        registry: RegistryPath::Local(
+                        registry: vec![RegistryPath::Local(
                             "crates/weaver_codegen_test/semconv_registry/".to_owned(),
-                        ),
+                        )],
                         registry_git_sub_dir: None,
+                        include: vec![],
+                        exclude: vec![],
                     },
                     policies: vec![],
                     skip_policies: false,
+                    disabled_builtin_policies: vec![],
                     display_policy_coverage: false,
+                    dead_definitions: false,
+                    lint_text: false,
+                    dictionary: None,
+                    check_links: false,
+                    fail_on_deprecated_ref: false,
+                    timeout: None,
+                    max_extends_depth: None,
+                    max_iterations: None,
+                    owner: vec![],
+                    watch: false,
+                    resolved_registry: None,
                     diagnostic: Default::default(),
                 }),
             })),
         };
 
-        let exit_directive = run_command(&cli, logger);
+        let exit_directive = run_command(&cli, logger.clone());
         // The command should exit with an error code.
         assert_eq!(exit_directive.exit_code, 1);
+
+        // The dead definitions are reported as warnings.
+        let cli = Cli {
+            debug: 0,
+            quiet: false,
+            cache: Default::default(),
+            command: Some(Commands::Registry(RegistryCommand {
+                command: RegistrySubCommand::Check(RegistryCheckArgs {
+                    registry: RegistryArgs {
+                        registry: vec![RegistryPath::Local(
+                            "crates/weaver_codegen_test/semconv_registry/".to_owned(),
+                        )],
+                        registry_git_sub_dir: None,
+                        include: vec![],
+                        exclude: vec![],
+                    },
+                    policies: vec![],
+                    skip_policies: true,
+                    disabled_builtin_policies: vec![],
+                    display_policy_coverage: false,
+                    dead_definitions: true,
+                    lint_text: false,
+                    dictionary: None,
+                    check_links: false,
+                    fail_on_deprecated_ref: false,
+                    timeout: None,
+                    max_extends_depth: None,
+                    max_iterations: None,
+                    owner: vec![],
+                    watch: false,
+                    resolved_registry: None,
+                    diagnostic: Default::default(),
+                }),
+            })),
+        };
+
+        let exit_directive = run_command(&cli, logger.clone());
+        // The command should succeed.
+        assert_eq!(exit_directive.exit_code, 0);
+
+        // A resolution exceeding the timeout fails the check.
+        let mut cli = cli;
+        if let Some(Commands::Registry(RegistryCommand {
+            command: RegistrySubCommand::Check(args),
+        })) = &mut cli.command
+        {
+            args.dead_definitions = false;
+            args.timeout = Some(0);
+        }
+        let exit_directive = run_command(&cli, logger);
+        assert_eq!(exit_directive.exit_code, 1);
     }
 
     #[test]
@@ -201,19 +450,34 @@ mod tests {
         let registry_cmd = RegistryCommand {
             command: RegistrySubCommand::Check(RegistryCheckArgs {
                 registry: RegistryArgs {
-                    registry: RegistryPath::Local(
+                    registry: vec![RegistryPath::Local(
                         "crates/weaver_codegen_test/semconv_registry/".to_owned(),
-                    ),
+                    )],
                     registry_git_sub_dir: None,
+                    include: vec![],
+                    exclude: vec![],
                 },
                 policies: vec![],
                 skip_policies: false,
+                disabled_builtin_policies: vec![],
                 display_policy_coverage: false,
+                dead_definitions: false,
+                lint_text: false,
+                dictionary: None,
+                check_links: false,
+                fail_on_deprecated_ref: false,
+                timeout: None,
+                max_extends_depth: None,
+                max_iterations: None,
+                owner: vec![],
+                watch: false,
+                resolved_registry: None,
                 diagnostic: Default::default(),
             }),
         };
 
-        let cmd_result = semconv_registry(logger.clone(), &registry_cmd);
+        let cmd_result =
+            semconv_registry(logger.clone(), &registry_cmd, &Default::default(), false);
         // Violations should be observed.
         assert!(cmd_result.command_result.is_err());
         if let Err(diag_msgs) = cmd_result.command_result {
@@ -0,0 +1,408 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Generate an OpenTelemetry Collector configuration enforcing the attributes of
+//! a semantic convention registry at ingestion.
+
+use std::collections::BTreeSet;
+use std::path::PathBuf;
+
+use clap::Args;
+use serde::Serialize;
+
+use weaver_cache::Cache;
+use weaver_common::diagnostic::DiagnosticMessages;
+use weaver_common::Logger;
+use weaver_resolved_schema::ResolvedTelemetrySchema;
+use weaver_schema::ottl::{ContextStatements, TransformProcessor};
+use weaver_semconv::attribute::AttributeType;
+use weaver_semconv::group::GroupType;
+use weaver_semconv::registry::SemConvRegistry;
+
+use crate::registry::{Error, RegistryArgs};
+use crate::util::{load_semconv_specs, resolve_semconv_specs};
+use crate::{DiagnosticArgs, ExitDirectives};
+
+/// Parameters for the `registry collector-config` sub-command
+#[derive(Debug, Args)]
+pub struct RegistryCollectorConfigArgs {
+    /// Parameters to specify the semantic convention registry
+    #[command(flatten)]
+    registry: RegistryArgs,
+
+    /// Also generate a `transform` processor keeping only the attributes of
+    /// the registry used by each signal type, all the other attributes are
+    /// deleted.
+    #[arg(long)]
+    allowlist: bool,
+
+    /// Output file to write the collector configuration to.
+    /// If not specified, the configuration is printed to stdout
+    #[arg(short, long)]
+    output: Option<PathBuf>,
+
+    /// Parameters to specify the diagnostic format.
+    #[command(flatten)]
+    pub diagnostic: DiagnosticArgs,
+}
+
+/// An action of an `attributes` or `resource` processor.
+#[derive(Serialize, Debug, PartialEq)]
+struct Action {
+    key: String,
+    action: &'static str,
+}
+
+/// The `processors` section of the collector configuration.
+#[derive(Serialize, Debug, Default)]
+struct Processors {
+    /// Actions of the `attributes` processor (span, log, and data point
+    /// attributes).
+    #[serde(rename = "attributes/semconv", skip_serializing_if = "Option::is_none")]
+    attributes: Option<AttributesProcessor>,
+    /// Actions of the `resource` processor.
+    #[serde(rename = "resource/semconv", skip_serializing_if = "Option::is_none")]
+    resource: Option<ResourceProcessor>,
+    /// Allowlist of the attributes per signal type.
+    #[serde(rename = "transform/semconv", skip_serializing_if = "Option::is_none")]
+    transform: Option<TransformProcessor>,
+}
+
+/// The configuration of an `attributes` processor.
+#[derive(Serialize, Debug)]
+struct AttributesProcessor {
+    actions: Vec<Action>,
+}
+
+/// The configuration of a `resource` processor.
+#[derive(Serialize, Debug)]
+struct ResourceProcessor {
+    attributes: Vec<Action>,
+}
+
+/// Generate the collector configuration enforcing the attributes of the
+/// registry.
+#[cfg(not(tarpaulin_include))]
+pub(crate) fn command(
+    logger: impl Logger + Sync + Clone,
+    cache: &Cache,
+    args: &RegistryCollectorConfigArgs,
+) -> Result<ExitDirectives, DiagnosticMessages> {
+    if args.output.is_none() {
+        logger.mute();
+    }
+    logger.loading(&format!(
+        "Generating the collector configuration of the registry `{}`",
+        args.registry.registries()
+    ));
+
+    let registry_id = "default";
+    let semconv_specs = load_semconv_specs(
+        &args.registry.registry_paths(),
+        &args.registry.file_filter()?,
+        cache,
+        logger.clone(),
+    )?;
+    let mut registry = SemConvRegistry::from_semconv_specs(registry_id, semconv_specs);
+    let schema = resolve_semconv_specs(&mut registry, logger.clone())?;
+
+    let processors = processors(&schema, args.allowlist);
+    if processors.attributes.is_none()
+        && processors.resource.is_none()
+        && processors.transform.is_none()
+    {
+        logger.warn("No deprecated attribute to delete, use `--allowlist` to enforce the registry");
+    }
+    let yaml = to_yaml(&processors);
+    if let Some(path) = &args.output {
+        std::fs::write(path, yaml).map_err(|e| Error::CollectorConfigWriteFailed {
+            output: path.clone(),
+            error: e.to_string(),
+        })?;
+        logger.success(&format!(
+            "Collector configuration written to `{}`",
+            path.display()
+        ));
+    } else {
+        print!("{}", yaml);
+    }
+
+    Ok(ExitDirectives {
+        exit_code: 0,
+        quiet_mode: args.output.is_none(),
+    })
+}
+
+/// The attributes used by the groups of each signal type, by OTTL context.
+#[derive(Default)]
+struct SignalAttributes<'a> {
+    resource: Option<BTreeSet<Key<'a>>>,
+    scope: Option<BTreeSet<Key<'a>>>,
+    span: Option<BTreeSet<Key<'a>>>,
+    event: Option<BTreeSet<Key<'a>>>,
+    metric: Option<BTreeSet<Key<'a>>>,
+}
+
+/// An attribute key, or the prefix of the keys of a template attribute.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+struct Key<'a> {
+    name: &'a str,
+    template: bool,
+}
+
+/// Returns the processors deleting the deprecated attributes, and optionally
+/// the `transform` processor keeping only the attributes of the registry.
+///
+/// The deprecated attributes of the registry used by resource groups are
+/// deleted by the `resource` processor, the other ones by the `attributes`
+/// processor. The
+/// allowlist of a signal type is only generated if the registry defines groups
+/// of this type, so the attributes of the signals not covered by the registry
+/// are kept.
+fn processors(schema: &ResolvedTelemetrySchema, allowlist: bool) -> Processors {
+    let mut signals = SignalAttributes::default();
+    let mut deprecated_resource_attributes = BTreeSet::new();
+    let mut deprecated_attributes = BTreeSet::new();
+    for group in schema
+        .registries
+        .values()
+        .flat_map(|registry| &registry.groups)
+    {
+        let keys = match group.r#type {
+            GroupType::Resource => &mut signals.resource,
+            GroupType::Scope => &mut signals.scope,
+            GroupType::Span => &mut signals.span,
+            GroupType::Event => &mut signals.event,
+            GroupType::Metric => &mut signals.metric,
+            GroupType::AttributeGroup | GroupType::MetricGroup => continue,
+        };
+        let keys = keys.get_or_insert_with(BTreeSet::new);
+        for attr in group
+            .attributes
+            .iter()
+            .filter_map(|attr_ref| schema.catalog().attribute(attr_ref))
+        {
+            _ = keys.insert(Key {
+                name: &attr.name,
+                template: matches!(attr.r#type, AttributeType::Template(_)),
+            });
+        }
+    }
+    for group in schema
+        .registries
+        .values()
+        .flat_map(|registry| &registry.groups)
+    {
+        for attr in group
+            .attributes
+            .iter()
+            .filter_map(|attr_ref| schema.catalog().attribute(attr_ref))
+            .filter(|attr| attr.deprecated.is_some())
+        {
+            if group.r#type == GroupType::Resource {
+                _ = deprecated_resource_attributes.insert(attr.name.as_str());
+            } else {
+                _ = deprecated_attributes.insert(attr.name.as_str());
+            }
+        }
+    }
+    // The deprecated resource attributes are only deleted from the resources.
+    deprecated_attributes.retain(|name| !deprecated_resource_attributes.contains(name));
+
+    let delete = |names: BTreeSet<&str>| -> Vec<Action> {
+        names
+            .into_iter()
+            .map(|name| Action {
+                key: name.to_owned(),
+                action: "delete",
+            })
+            .collect()
+    };
+    let attributes = delete(deprecated_attributes);
+    let resource = delete(deprecated_resource_attributes);
+    Processors {
+        attributes: (!attributes.is_empty()).then_some(AttributesProcessor {
+            actions: attributes,
+        }),
+        resource: (!resource.is_empty()).then_some(ResourceProcessor {
+            attributes: resource,
+        }),
+        transform: allowlist.then(|| transform_processor(&signals)),
+    }
+}
+
+/// Returns the `transform` processor keeping only the attributes of the
+/// registry in each OTTL context.
+fn transform_processor(signals: &SignalAttributes<'_>) -> TransformProcessor {
+    let contexts = |contexts: Vec<(&str, &Option<BTreeSet<Key<'_>>>)>| {
+        contexts
+            .into_iter()
+            .filter_map(|(context, keys)| {
+                keys.as_ref().map(|keys| ContextStatements {
+                    context: context.to_owned(),
+                    statements: vec![keep_matching_keys(keys)],
+                })
+            })
+            .collect()
+    };
+    TransformProcessor {
+        trace_statements: contexts(vec![
+            ("resource", &signals.resource),
+            ("scope", &signals.scope),
+            ("span", &signals.span),
+            ("spanevent", &signals.event),
+        ]),
+        metric_statements: contexts(vec![
+            ("resource", &signals.resource),
+            ("scope", &signals.scope),
+            ("datapoint", &signals.metric),
+        ]),
+        log_statements: contexts(vec![
+            ("resource", &signals.resource),
+            ("scope", &signals.scope),
+            ("log", &signals.event),
+        ]),
+    }
+}
+
+/// Returns the OTTL statement keeping only the given attribute keys.
+fn keep_matching_keys(keys: &BTreeSet<Key<'_>>) -> String {
+    let escape = |name: &str| name.replace('.', "\\.");
+    let patterns: Vec<String> = keys
+        .iter()
+        .map(|key| {
+            if key.template {
+                format!("{}\\..+", escape(key.name))
+            } else {
+                escape(key.name)
+            }
+        })
+        .collect();
+    let regex = format!("^(?:{})$", patterns.join("|"));
+    format!(
+        "keep_matching_keys(attributes, \"{}\")",
+        regex.replace('\\', "\\\\")
+    )
+}
+
+/// Serializes the processors in YAML, as the `processors` section of a
+/// collector configuration.
+fn to_yaml(processors: &Processors) -> String {
+    #[derive(Serialize)]
+    struct Config<'a> {
+        processors: &'a Processors,
+    }
+    serde_yaml::to_string(&Config { processors })
+        .expect("The configuration is serializable to YAML")
+}
+
+#[cfg(test)]
+mod tests {
+    use weaver_resolver::SchemaResolver;
+    use weaver_semconv::registry::SemConvRegistry;
+
+    use crate::registry::collector::{processors, to_yaml};
+
+    #[test]
+    fn test_collector_config() {
+        let mut registry = SemConvRegistry::new("default");
+        registry
+            .add_semconv_spec_from_string(
+                "registry.yaml",
+                r#"groups:
+  - id: registry.http
+    type: attribute_group
+    brief: HTTP attributes.
+    prefix: http
+    attributes:
+      - id: request.method
+        type: string
+        brief: HTTP request method.
+        examples: [GET]
+      - id: method
+        type: string
+        brief: HTTP request method.
+        examples: [GET]
+        deprecated: Replaced by `http.request.method`.
+      - id: request.header
+        type: template[string[]]
+        brief: HTTP request headers.
+        examples: ['http.request.header.content-type=["application/json"]']
+  - id: registry.service
+    type: attribute_group
+    brief: Service attributes.
+    prefix: service
+    attributes:
+      - id: name
+        type: string
+        brief: Service name.
+        examples: [shop]
+      - id: old_name
+        type: string
+        brief: Old service name.
+        examples: [shop]
+        deprecated: Replaced by `service.name`.
+  - id: span.http.client
+    type: span
+    span_kind: client
+    brief: HTTP client span.
+    attributes:
+      - ref: http.request.method
+      - ref: http.method
+      - ref: http.request.header
+  - id: resource.service
+    type: resource
+    brief: Service resource.
+    attributes:
+      - ref: service.name
+      - ref: service.old_name
+"#,
+            )
+            .expect("Invalid registry");
+        let schema = SchemaResolver::resolve_semantic_convention_registry(&mut registry)
+            .expect("Failed to resolve the registry");
+
+        let yaml = to_yaml(&processors(&schema, false));
+        assert_eq!(
+            yaml,
+            r#"processors:
+  attributes/semconv:
+    actions:
+    - key: http.method
+      action: delete
+  resource/semconv:
+    attributes:
+    - key: service.old_name
+      action: delete
+"#
+        );
+
+        let yaml = to_yaml(&processors(&schema, true));
+        let config: serde_yaml::Value = serde_yaml::from_str(&yaml).expect("Invalid YAML");
+        let transform = &config["processors"]["transform/semconv"];
+        assert_eq!(
+            transform["trace_statements"],
+            serde_yaml::from_str::<serde_yaml::Value>(
+                r#"
+- context: resource
+  statements:
+  - keep_matching_keys(attributes, "^(?:service\\.name|service\\.old_name)$")
+- context: span
+  statements:
+  - keep_matching_keys(attributes, "^(?:http\\.method|http\\.request\\.header\\..+|http\\.request\\.method)$")
+"#
+            )
+            .expect("Invalid YAML")
+        );
+        // No metric or event groups, so only the resources are filtered.
+        assert_eq!(
+            transform["metric_statements"][0]["context"],
+            serde_yaml::Value::from("resource")
+        );
+        assert_eq!(
+            transform["metric_statements"]
+                .as_sequence()
+                .map(|s| s.len()),
+            Some(1)
+        );
+    }
+}
@@ -0,0 +1,234 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Detection of the dead definitions of a semantic convention registry, i.e.
+//! the attributes never referenced by a signal and the orphan groups.
+
+use std::collections::{HashMap, HashSet};
+
+use weaver_common::diagnostic::DiagnosticMessages;
+use weaver_semconv::attribute::AttributeSpec;
+use weaver_semconv::group::{GroupSpec, GroupType};
+use weaver_semconv::semconv::SemConvSpec;
+
+use crate::registry::Error;
+
+/// Returns true if the group defines a signal (span, event, metric, resource,
+/// or scope).
+fn is_signal(group: &GroupSpec) -> bool {
+    matches!(
+        group.r#type,
+        GroupType::Span
+            | GroupType::Event
+            | GroupType::Metric
+            | GroupType::Resource
+            | GroupType::Scope
+    )
+}
+
+/// Returns the fully qualified id of an attribute defined in a group.
+pub(crate) fn qualified_id(group: &GroupSpec, id: &str) -> String {
+    if group.prefix.is_empty() {
+        id.to_owned()
+    } else {
+        format!("{}.{}", group.prefix, id)
+    }
+}
+
+/// Reports, as warnings, the attributes defined in attribute groups (or metric
+/// groups) that are never used by a signal group, and the groups that are
+/// neither signals, nor extended or included by another group, nor used by a
+/// signal group.
+///
+/// A group is used by a signal if it is the signal group itself or a group
+/// reached from it through `extends` and `include` constraints. An attribute is
+/// used if it is referenced (or defined) by a group used by a signal.
+/// Deprecated attributes and groups are not reported, as they are kept for
+/// backward compatibility.
+pub(crate) fn check_dead_definitions(
+    semconv_specs: &[(String, SemConvSpec)],
+) -> Result<(), DiagnosticMessages> {
+    let warnings = dead_definitions(semconv_specs);
+    if warnings.is_empty() {
+        Ok(())
+    } else {
+        Err(DiagnosticMessages::from_errors(warnings))
+    }
+}
+
+/// Returns the unused attributes and the orphan groups of the registry.
+fn dead_definitions(semconv_specs: &[(String, SemConvSpec)]) -> Vec<Error> {
+    let groups: HashMap<&str, &GroupSpec> = semconv_specs
+        .iter()
+        .flat_map(|(_, spec)| spec.groups())
+        .map(|group| (group.id.as_str(), group))
+        .collect();
+    let parents = |group: &'_ GroupSpec| -> Vec<String> {
+        group
+            .extends
+            .iter()
+            .chain(group.constraints.iter().filter_map(|c| c.include.as_ref()))
+            .cloned()
+            .collect()
+    };
+
+    // Groups extended or included by another group.
+    let referenced_groups: HashSet<String> = groups.values().flat_map(|g| parents(g)).collect();
+
+    // Groups reachable from the signal groups.
+    let mut used_groups = HashSet::new();
+    let mut pending: Vec<&str> = groups
+        .values()
+        .filter(|group| is_signal(group))
+        .map(|group| group.id.as_str())
+        .collect();
+    while let Some(id) = pending.pop() {
+        let Some(group) = groups.get(id) else {
+            continue;
+        };
+        if used_groups.insert(id) {
+            for parent in parents(group) {
+                if let Some((parent, _)) = groups.get_key_value(parent.as_str()) {
+                    pending.push(parent);
+                }
+            }
+        }
+    }
+
+    // Attributes used by the signal groups.
+    let used_attributes: HashSet<String> = used_groups
+        .iter()
+        .filter_map(|id| groups.get(id))
+        .flat_map(|group| {
+            group.attributes.iter().map(|attr| match attr {
+                AttributeSpec::Ref { r#ref, .. } => r#ref.clone(),
+                AttributeSpec::Id { id, .. } => qualified_id(group, id),
+            })
+        })
+        .collect();
+
+    let mut warnings = vec![];
+    for (provenance, spec) in semconv_specs {
+        for group in spec.groups() {
+            if is_signal(group) || group.deprecated.is_some() {
+                continue;
+            }
+            let defined: Vec<String> = group
+                .attributes
+                .iter()
+                .filter_map(|attr| match attr {
+                    AttributeSpec::Id { id, deprecated, .. } if deprecated.is_none() => {
+                        Some(qualified_id(group, id))
+                    }
+                    _ => None,
+                })
+                .collect();
+            let unused: Vec<&String> = defined
+                .iter()
+                .filter(|id| !used_attributes.contains(*id))
+                .collect();
+            let orphan = !referenced_groups.contains(&group.id)
+                && !used_groups.contains(group.id.as_str())
+                && unused.len() == defined.len();
+            if orphan {
+                warnings.push(Error::OrphanGroup {
+                    group: group.id.clone(),
+                    provenance: provenance.clone(),
+                });
+            } else {
+                warnings.extend(unused.into_iter().map(|attr| Error::UnusedAttribute {
+                    attr: attr.clone(),
+                    group: group.id.clone(),
+                    provenance: provenance.clone(),
+                }));
+            }
+        }
+    }
+    warnings
+}
+
+#[cfg(test)]
+mod tests {
+    use weaver_semconv::semconv::SemConvSpec;
+
+    use crate::registry::dead_definitions::{check_dead_definitions, dead_definitions};
+
+    #[test]
+    fn test_check_dead_definitions() {
+        let registry = SemConvSpec::from_string(
+            r#"groups:
+  - id: registry.http
+    type: attribute_group
+    brief: HTTP attributes.
+    prefix: http
+    attributes:
+      - id: request.method
+        type: string
+        brief: HTTP request method.
+        examples: [GET]
+      - id: route
+        type: string
+        brief: The matched route.
+        examples: ['/users/:id']
+      - id: method
+        type: string
+        brief: Deprecated.
+        examples: [GET]
+        deprecated: Replaced by `http.request.method`.
+  - id: registry.unused
+    type: attribute_group
+    brief: Attributes never used.
+    attributes:
+      - id: unused.flag
+        type: boolean
+        brief: A flag.
+  - id: attributes.http.common
+    type: attribute_group
+    brief: HTTP common attributes.
+    attributes:
+      - ref: http.request.method
+  - id: attributes.http.orphan
+    type: attribute_group
+    brief: HTTP attributes never extended.
+    attributes:
+      - ref: http.route
+"#,
+        )
+        .expect("Invalid registry");
+        let spans = SemConvSpec::from_string(
+            r#"groups:
+  - id: span.http.client
+    type: span
+    span_kind: client
+    brief: HTTP client span.
+    extends: attributes.http.common
+"#,
+        )
+        .expect("Invalid spans");
+
+        let specs = vec![
+            ("registry.yaml".to_owned(), registry),
+            ("spans.yaml".to_owned(), spans.clone()),
+        ];
+        let mut messages: Vec<String> = dead_definitions(&specs)
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+        messages.sort();
+        assert_eq!(
+            messages,
+            vec![
+                "The attribute `http.route` of the group `registry.http` (defined in `registry.yaml`) is not used by any span, event, metric, resource, or scope group.",
+                "The group `attributes.http.orphan` (defined in `registry.yaml`) is neither extended nor used by any span, event, metric, resource, or scope group.",
+                "The group `registry.unused` (defined in `registry.yaml`) is neither extended nor used by any span, event, metric, resource, or scope group.",
+            ]
+        );
+
+        // Dead definitions are reported as warnings.
+        let diag_msgs = check_dead_definitions(&specs).expect_err("Dead definitions expected");
+        assert_eq!(diag_msgs.len(), 3);
+        assert!(!diag_msgs.has_error());
+
+        // A registry without dead definitions.
+        assert!(check_dead_definitions(&[("spans.yaml".to_owned(), spans)]).is_ok());
+    }
+}
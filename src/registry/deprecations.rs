@@ -0,0 +1,536 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Report of the deprecated attributes and groups of a semantic convention
+//! registry: the replacement of each deprecated item, the version it has been
+//! deprecated in, and the non-deprecated groups still referencing it.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fmt::Write;
+use std::path::PathBuf;
+
+use clap::{Args, ValueEnum};
+use serde::Serialize;
+
+use weaver_cache::Cache;
+use weaver_common::diagnostic::DiagnosticMessages;
+use weaver_common::Logger;
+use weaver_forge::registry::{ResolvedGroup, ResolvedRegistry};
+use weaver_resolver::file_filter::RegistryFileFilter;
+use weaver_resolver::registry::deprecation_replacement;
+use weaver_semconv::attribute::AttributeSpec;
+use weaver_semconv::registry::SemConvRegistry;
+use weaver_semconv::semconv::SemConvSpec;
+
+use crate::registry::dead_definitions::qualified_id;
+use crate::registry::schema_file::RegistryVersion;
+use crate::registry::search::group_type_name;
+use crate::registry::{Error, RegistryArgs};
+use crate::util::{load_semconv_specs, resolve_semconv_specs, semconv_registry_path_from};
+use crate::{DiagnosticArgs, ExitDirectives};
+
+/// Supported output formats for the deprecation report
+#[derive(Debug, Clone, ValueEnum)]
+pub enum DeprecationsFormat {
+    /// JSON format
+    Json,
+    /// Markdown format
+    Markdown,
+}
+
+/// Parameters for the `registry deprecations` sub-command
+#[derive(Debug, Args)]
+pub struct RegistryDeprecationsArgs {
+    /// Parameters to specify the semantic convention registry
+    #[command(flatten)]
+    registry: RegistryArgs,
+
+    /// Released version of the registry and local path or Git URL of the
+    /// registry for this version, e.g. `1.26.0=semconv-1.26.0/model`. Repeat
+    /// the option for each version. The versions are used to find the version
+    /// each item has been deprecated in.
+    #[arg(long = "registry-version", value_name = "VERSION=REGISTRY")]
+    pub registry_versions: Vec<RegistryVersion>,
+
+    /// Output format of the report.
+    #[arg(short, long, default_value = "markdown")]
+    format: DeprecationsFormat,
+
+    /// Output file to write the report to.
+    /// If not specified, the report is printed to stdout
+    #[arg(short, long)]
+    output: Option<PathBuf>,
+
+    /// Parameters to specify the diagnostic format.
+    #[command(flatten)]
+    pub diagnostic: DiagnosticArgs,
+}
+
+/// The deprecated items of a registry.
+#[derive(Debug, Serialize)]
+struct DeprecationReport {
+    /// The released versions of the registry the items have been looked up
+    /// in, the oldest first.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    versions: Vec<String>,
+    /// The deprecated attributes and groups, sorted by id.
+    items: Vec<DeprecatedItemReport>,
+}
+
+/// A deprecated attribute or group.
+#[derive(Debug, Serialize)]
+struct DeprecatedItemReport {
+    /// The id of the attribute or the group.
+    id: String,
+    /// `attribute` or the type of the group.
+    kind: &'static str,
+    /// The deprecation note.
+    note: String,
+    /// The replacement declared by the deprecation note, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    replacement: Option<String>,
+    /// The oldest version of the latest run of released versions deprecating
+    /// the item. Not set if no released version deprecates the item.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    since: Option<String>,
+    /// The file defining the item.
+    provenance: String,
+    /// The non-deprecated groups still referencing the item (attribute
+    /// reference, `extends` clause, or `include` constraint), sorted by id.
+    #[serde(skip_serializing_if = "BTreeSet::is_empty")]
+    referenced_by: BTreeSet<String>,
+}
+
+/// Report the deprecated items of a semantic convention registry.
+#[cfg(not(tarpaulin_include))]
+pub(crate) fn command(
+    logger: impl Logger + Sync + Clone,
+    cache: &Cache,
+    args: &RegistryDeprecationsArgs,
+) -> Result<ExitDirectives, DiagnosticMessages> {
+    if args.output.is_none() {
+        logger.mute();
+    }
+    logger.loading(&format!(
+        "Reporting the deprecations of the registry `{}`",
+        args.registry.registries()
+    ));
+
+    let semconv_specs = load_semconv_specs(
+        &args.registry.registry_paths(),
+        &args.registry.file_filter()?,
+        cache,
+        logger.clone(),
+    )?;
+    let mut registry_versions = args.registry_versions.clone();
+    registry_versions.sort_by(|a, b| a.version.cmp(&b.version));
+    let mut versions = vec![];
+    for registry_version in &registry_versions {
+        let registry_path = semconv_registry_path_from(
+            &registry_version.registry,
+            &args.registry.registry_git_sub_dir,
+        );
+        let specs = load_semconv_specs(
+            &[registry_path],
+            &RegistryFileFilter::default(),
+            cache,
+            logger.clone(),
+        )?;
+        versions.push((registry_version.version.to_string(), specs));
+    }
+    let mut registry = SemConvRegistry::from_semconv_specs("default", semconv_specs.clone());
+    let schema = resolve_semconv_specs(&mut registry, logger.clone())?;
+    let registry = ResolvedRegistry::try_from_resolved_registry(
+        schema
+            .registry("default")
+            .expect("Failed to get the registry from the resolved schema"),
+        schema.catalog(),
+    )?;
+    let report = DeprecationReport::new(&semconv_specs, &registry, &versions);
+
+    let output = match args.format {
+        DeprecationsFormat::Json => {
+            let mut json = serde_json::to_string_pretty(&report)
+                .expect("Failed to serialize the deprecation report");
+            json.push('\n');
+            json
+        }
+        DeprecationsFormat::Markdown => report.to_markdown(),
+    };
+    if let Some(path) = &args.output {
+        std::fs::write(path, output).map_err(|e| Error::DeprecationsWriteFailed {
+            output: path.clone(),
+            error: e.to_string(),
+        })?;
+        logger.success(&format!(
+            "{} deprecated items reported in `{}`",
+            report.items.len(),
+            path.display()
+        ));
+    } else {
+        print!("{}", output);
+    }
+
+    Ok(ExitDirectives {
+        exit_code: 0,
+        quiet_mode: args.output.is_none(),
+    })
+}
+
+/// A deprecated item of a registry, identified by its kind and its id.
+type ItemKey = (&'static str, String);
+
+/// Returns the deprecated attributes and groups of a registry with their
+/// deprecation note and provenance.
+fn deprecated_items(semconv_specs: &[(String, SemConvSpec)]) -> BTreeMap<ItemKey, (&str, &str)> {
+    let mut items = BTreeMap::new();
+    for (provenance, spec) in semconv_specs {
+        for group in spec.groups() {
+            if let Some(deprecated) = &group.deprecated {
+                _ = items.insert(
+                    (group_type_name(&group.r#type), group.id.clone()),
+                    (deprecated.as_str(), provenance.as_str()),
+                );
+            }
+            for attr in &group.attributes {
+                if let AttributeSpec::Id {
+                    id,
+                    deprecated: Some(deprecated),
+                    ..
+                } = attr
+                {
+                    _ = items.insert(
+                        ("attribute", qualified_id(group, id)),
+                        (deprecated.as_str(), provenance.as_str()),
+                    );
+                }
+            }
+        }
+    }
+    items
+}
+
+impl DeprecationReport {
+    /// Builds the deprecation report of a registry from its semantic
+    /// convention specifications, its resolved registry (for the groups using
+    /// each attribute), and the specifications of its released versions (the
+    /// oldest first).
+    fn new(
+        semconv_specs: &[(String, SemConvSpec)],
+        registry: &ResolvedRegistry,
+        versions: &[(String, Vec<(String, SemConvSpec)>)],
+    ) -> Self {
+        let mut items: BTreeMap<ItemKey, DeprecatedItemReport> = deprecated_items(semconv_specs)
+            .into_iter()
+            .map(|((kind, id), (note, provenance))| {
+                let item = DeprecatedItemReport {
+                    id: id.clone(),
+                    kind,
+                    note: note.trim().to_owned(),
+                    replacement: deprecation_replacement(note),
+                    since: None,
+                    provenance: provenance.to_owned(),
+                    referenced_by: BTreeSet::new(),
+                };
+                ((kind, id), item)
+            })
+            .collect();
+
+        // The version an item has been deprecated in is the first version of
+        // the latest run of versions deprecating it.
+        for (version, specs) in versions {
+            let deprecated = deprecated_items(specs);
+            for (key, item) in items.iter_mut() {
+                if !deprecated.contains_key(key) {
+                    item.since = None;
+                } else if item.since.is_none() {
+                    item.since = Some(version.clone());
+                }
+            }
+        }
+
+        // The groups referencing the deprecated items, the references made by
+        // deprecated groups and the references deprecating the attribute
+        // themselves are ignored. The attribute references are looked up in
+        // the attribute index of the resolved registry, the group defining an
+        // attribute has no lineage for it.
+        let groups: HashMap<&str, &ResolvedGroup> = registry
+            .groups
+            .iter()
+            .map(|group| (group.id.as_str(), group))
+            .collect();
+        let mut references = vec![];
+        for (kind, name) in items.keys().filter(|(kind, _)| *kind == "attribute") {
+            let usages = registry.attribute_index.get(name).into_iter().flatten();
+            for group in usages.filter_map(|usage| groups.get(usage.group_id.as_str())) {
+                let Some(lineage) = group
+                    .lineage
+                    .as_ref()
+                    .and_then(|lineage| lineage.attribute(name))
+                else {
+                    continue;
+                };
+                if group.deprecated.is_none()
+                    && !lineage.locally_overridden_fields.contains("deprecated")
+                    && lineage.overriding_group("deprecated").is_none()
+                {
+                    references.push(((*kind, name.clone()), group.id.clone()));
+                }
+            }
+        }
+        let group_kinds: BTreeMap<&str, &'static str> = items
+            .keys()
+            .filter(|(kind, _)| *kind != "attribute")
+            .map(|(kind, id)| (id.as_str(), *kind))
+            .collect();
+        for group in semconv_specs.iter().flat_map(|(_, spec)| spec.groups()) {
+            if group.deprecated.is_some() {
+                continue;
+            }
+            let referenced_groups = group.extends.iter().chain(
+                group
+                    .constraints
+                    .iter()
+                    .filter_map(|constraint| constraint.include.as_ref()),
+            );
+            for group_id in referenced_groups {
+                if let Some(kind) = group_kinds.get(group_id.as_str()) {
+                    references.push(((*kind, group_id.clone()), group.id.clone()));
+                }
+            }
+        }
+        for (key, group_id) in references {
+            if let Some(item) = items.get_mut(&key) {
+                _ = item.referenced_by.insert(group_id);
+            }
+        }
+
+        let mut items: Vec<_> = items.into_values().collect();
+        items.sort_by(|a, b| a.id.cmp(&b.id).then(a.kind.cmp(b.kind)));
+        Self {
+            versions: versions
+                .iter()
+                .map(|(version, _)| version.clone())
+                .collect(),
+            items,
+        }
+    }
+
+    /// Renders the report in Markdown, with a table of the deprecated items.
+    fn to_markdown(&self) -> String {
+        let mut markdown = String::from("# Deprecations\n");
+        if !self.versions.is_empty() {
+            _ = write!(
+                markdown,
+                "\nReleased versions: {}\n",
+                self.versions
+                    .iter()
+                    .map(|version| format!("`{}`", version))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            );
+        }
+        if self.items.is_empty() {
+            markdown.push_str("\nNo deprecated attribute or group.\n");
+            return markdown;
+        }
+        markdown.push_str(
+            "\n| Item | Kind | Since | Replacement | Still referenced by | Source file |\n",
+        );
+        markdown.push_str("|---|---|---|---|---|---|\n");
+        for item in &self.items {
+            let since = match &item.since {
+                Some(version) => version.clone(),
+                None if self.versions.is_empty() => "-".to_owned(),
+                None => "unreleased".to_owned(),
+            };
+            let replacement = match &item.replacement {
+                Some(replacement) => format!("`{}`", replacement),
+                None => item.note.replace('\n', " ").replace('|', "\\|"),
+            };
+            _ = writeln!(
+                markdown,
+                "| `{}` | {} | {} | {} | {} | `{}` |",
+                item.id,
+                item.kind,
+                since,
+                replacement,
+                item.referenced_by
+                    .iter()
+                    .map(|group_id| format!("`{}`", group_id))
+                    .collect::<Vec<_>>()
+                    .join(", "),
+                item.provenance
+            );
+        }
+        markdown
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use weaver_forge::registry::ResolvedRegistry;
+    use weaver_resolver::SchemaResolver;
+    use weaver_semconv::registry::SemConvRegistry;
+    use weaver_semconv::semconv::SemConvSpec;
+
+    use crate::registry::deprecations::DeprecationReport;
+
+    const V1: &str = r#"groups:
+  - id: registry.http
+    type: attribute_group
+    brief: HTTP attributes.
+    prefix: http
+    attributes:
+      - id: method
+        type: string
+        brief: HTTP request method.
+        examples: [GET]
+      - id: flavor
+        type: string
+        brief: HTTP flavor.
+        examples: ['1.1']
+        deprecated: Replaced by `network.protocol.version`.
+"#;
+
+    const V2: &str = r#"groups:
+  - id: registry.http
+    type: attribute_group
+    brief: HTTP attributes.
+    prefix: http
+    attributes:
+      - id: method
+        type: string
+        brief: HTTP request method.
+        examples: [GET]
+        deprecated: Replaced by `http.request.method`.
+      - id: request.method
+        type: string
+        brief: HTTP request method.
+        examples: [GET]
+      - id: flavor
+        type: string
+        brief: HTTP flavor.
+        examples: ['1.1']
+        deprecated: Replaced by `network.protocol.version`.
+"#;
+
+    const CURRENT: &str = r#"groups:
+  - id: registry.http
+    type: attribute_group
+    brief: HTTP attributes.
+    prefix: http
+    attributes:
+      - id: method
+        type: string
+        brief: HTTP request method.
+        examples: [GET]
+        deprecated: Replaced by `http.request.method`.
+      - id: request.method
+        type: string
+        brief: HTTP request method.
+        examples: [GET]
+      - id: flavor
+        type: string
+        brief: HTTP flavor.
+        examples: ['1.1']
+        deprecated: Replaced by `network.protocol.version`.
+      - id: user_agent
+        type: string
+        brief: User agent.
+        examples: ['curl']
+        deprecated: Removed.
+  - id: attributes.http.legacy
+    type: attribute_group
+    brief: Legacy HTTP attributes.
+    deprecated: Use the registry attributes.
+    attributes:
+      - ref: http.method
+  - id: metric.http.duration
+    type: metric
+    metric_name: http.duration
+    instrument: histogram
+    unit: s
+    brief: Duration of the HTTP requests.
+    deprecated: Replaced by `metric.http.server.request.duration`.
+  - id: span.http.client
+    type: span
+    span_kind: client
+    brief: HTTP client span.
+    extends: attributes.http.legacy
+    attributes:
+      - ref: http.method
+      - ref: http.flavor
+        deprecated: Still deprecated.
+"#;
+
+    fn specs(content: &str) -> Vec<(String, SemConvSpec)> {
+        vec![(
+            "http.yaml".to_owned(),
+            SemConvSpec::from_string(content).expect("Failed to parse the registry"),
+        )]
+    }
+
+    #[test]
+    fn test_deprecation_report() {
+        let versions = vec![
+            ("1.0.0".to_owned(), specs(V1)),
+            ("1.1.0".to_owned(), specs(V2)),
+        ];
+        let mut registry = SemConvRegistry::from_semconv_specs("default", specs(CURRENT));
+        let schema = SchemaResolver::resolve_semantic_convention_registry(&mut registry)
+            .expect("Failed to resolve the registry");
+        let registry = ResolvedRegistry::try_from_resolved_registry(
+            schema.registry("default").expect("Registry not found"),
+            schema.catalog(),
+        )
+        .expect("Failed to build the registry");
+        let report = DeprecationReport::new(&specs(CURRENT), &registry, &versions);
+
+        let items: Vec<_> = report
+            .items
+            .iter()
+            .map(|item| (item.id.as_str(), item.kind, item.since.as_deref()))
+            .collect();
+        assert_eq!(
+            items,
+            vec![
+                ("attributes.http.legacy", "attribute_group", None),
+                ("http.flavor", "attribute", Some("1.0.0")),
+                ("http.method", "attribute", Some("1.1.0")),
+                ("http.user_agent", "attribute", None),
+                ("metric.http.duration", "metric", None),
+            ]
+        );
+
+        let method = &report.items[2];
+        assert_eq!(method.replacement.as_deref(), Some("http.request.method"));
+        assert_eq!(method.provenance, "http.yaml");
+        // The references of the deprecated group are ignored.
+        assert_eq!(
+            method.referenced_by.iter().collect::<Vec<_>>(),
+            vec!["span.http.client"]
+        );
+        // The reference deprecating the attribute itself is ignored.
+        assert!(report.items[1].referenced_by.is_empty());
+        assert_eq!(
+            report.items[0].referenced_by.iter().collect::<Vec<_>>(),
+            vec!["span.http.client"]
+        );
+        assert_eq!(report.items[3].replacement, None);
+
+        let markdown = report.to_markdown();
+        assert!(markdown.contains("Released versions: `1.0.0`, `1.1.0`"));
+        assert!(markdown.contains(
+            "| `http.method` | attribute | 1.1.0 | `http.request.method` | `span.http.client` | `http.yaml` |"
+        ));
+        assert!(markdown.contains(
+            "| `http.user_agent` | attribute | unreleased | Removed. |  | `http.yaml` |"
+        ));
+        let json = serde_json::to_value(&report).expect("Failed to serialize the report");
+        assert_eq!(json["items"][4]["kind"], "metric");
+        assert_eq!(
+            json["items"][4]["replacement"],
+            "metric.http.server.request.duration"
+        );
+    }
+}
@@ -0,0 +1,209 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Render a semantic convention registry into a static HTML documentation site.
+
+use std::path::PathBuf;
+
+use clap::Args;
+use serde_yaml::Value;
+
+use weaver_cache::Cache;
+use weaver_common::diagnostic::DiagnosticMessages;
+use weaver_common::Logger;
+use weaver_forge::config::Params;
+use weaver_forge::registry::ResolvedRegistry;
+use weaver_forge::OutputDirective;
+use weaver_semconv::registry::SemConvRegistry;
+
+use crate::registry::generate::template_engine;
+use crate::registry::RegistryArgs;
+use crate::util::{
+    check_policies, check_resolved_policies, init_policy_engine, load_semconv_specs,
+    resolve_semconv_specs,
+};
+use crate::{DiagnosticArgs, ExitDirectives};
+
+/// Name of the built-in target used to render the documentation site.
+const DOCS_TARGET: &str = "html";
+
+/// Parameters for the `registry docs` sub-command
+#[derive(Debug, Args)]
+pub struct RegistryDocsArgs {
+    /// Path to the directory where the documentation site will be saved.
+    /// Default is the `site` directory.
+    #[arg(default_value = "site")]
+    pub output: PathBuf,
+
+    /// Path to the directory where the templates are located.
+    /// A `registry/html` directory in this directory overrides the built-in templates.
+    #[arg(short = 't', long, default_value = "templates")]
+    pub templates: PathBuf,
+
+    /// Title of the documentation site.
+    #[arg(long)]
+    pub title: Option<String>,
+
+    /// Parameters to specify the semantic convention registry
+    #[command(flatten)]
+    registry: RegistryArgs,
+
+    /// Optional list of policy files to check against the files of the semantic
+    /// convention registry.
+    #[arg(short = 'p', long = "policy")]
+    pub policies: Vec<PathBuf>,
+
+    /// Skip the policy checks.
+    #[arg(long, default_value = "false")]
+    pub skip_policies: bool,
+
+    /// Parameters to specify the diagnostic format.
+    #[command(flatten)]
+    pub diagnostic: DiagnosticArgs,
+}
+
+/// Render a semantic convention registry into a static HTML documentation site.
+#[cfg(not(tarpaulin_include))]
+pub(crate) fn command(
+    logger: impl Logger + Sync + Clone,
+    cache: &Cache,
+    args: &RegistryDocsArgs,
+    debug: bool,
+) -> Result<ExitDirectives, DiagnosticMessages> {
+    logger.loading(&format!(
+        "Generating the documentation site for the registry `{}`",
+        args.registry.registries()
+    ));
+
+    let registry_id = "default";
+    let registry_paths = args.registry.registry_paths();
+
+    // Load the semantic convention registry into a local cache.
+    let semconv_specs = load_semconv_specs(
+        &registry_paths,
+        &args.registry.file_filter()?,
+        cache,
+        logger.clone(),
+    )?;
+
+    let mut policy_engine = if !args.skip_policies {
+        let policy_engine = init_policy_engine(&registry_paths, cache, &args.policies, false)?;
+        check_policies(&policy_engine, &semconv_specs, logger.clone())?;
+        Some(policy_engine)
+    } else {
+        None
+    };
+
+    let mut registry = SemConvRegistry::from_semconv_specs(registry_id, semconv_specs);
+    let schema = resolve_semconv_specs(&mut registry, logger.clone())?;
+
+    let mut params = Params::default();
+    if let Some(title) = &args.title {
+        _ = params
+            .params
+            .insert("title".to_owned(), Value::String(title.clone()));
+    }
+    let engine = template_engine(&args.templates, DOCS_TARGET, params, debug)?;
+
+    let template_registry = ResolvedRegistry::try_from_resolved_registry(
+        schema
+            .registry(registry_id)
+            .expect("Failed to get the registry from the resolved schema"),
+        schema.catalog(),
+    )?;
+
+    if let Some(policy_engine) = policy_engine.as_mut() {
+        check_resolved_policies(
+            policy_engine,
+            &registry_paths,
+            &template_registry,
+            logger.clone(),
+        )?;
+    }
+
+    engine.generate(
+        logger.clone(),
+        &template_registry,
+        args.output.as_path(),
+        &OutputDirective::File,
+    )?;
+
+    logger.success(&format!(
+        "Documentation site generated in {:?}",
+        args.output.join("index.html")
+    ));
+    Ok(ExitDirectives {
+        exit_code: 0,
+        quiet_mode: false,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use tempdir::TempDir;
+
+    use weaver_common::TestLogger;
+
+    use crate::cli::{Cli, Commands};
+    use crate::registry::docs::RegistryDocsArgs;
+    use crate::registry::{RegistryArgs, RegistryCommand, RegistryPath, RegistrySubCommand};
+    use crate::run_command;
+
+    #[test]
+    fn test_registry_docs() {
+        let logger = TestLogger::new();
+        let temp_output = TempDir::new("site")
+            .expect("Failed to create temporary directory")
+            .into_path();
+        let cli = Cli {
+            debug: 0,
+            quiet: false,
+            cache: Default::default(),
+            command: Some(Commands::Registry(RegistryCommand {
+                command: RegistrySubCommand::Docs(RegistryDocsArgs {
+                    output: temp_output.clone(),
+                    templates: PathBuf::from("does-not-exist/"),
+                    title: Some("Test Registry".to_owned()),
+                    registry: RegistryArgs {
+                        registry: vec![RegistryPath::Local(
+                            "crates/weaver_codegen_test/semconv_registry/".to_owned(),
+                        )],
+                        registry_git_sub_dir: None,
+                        include: vec![],
+                        exclude: vec![],
+                    },
+                    policies: vec![],
+                    skip_policies: true,
+                    diagnostic: Default::default(),
+                }),
+            })),
+        };
+
+        let exit_directive = run_command(&cli, logger.clone());
+        // The command should succeed.
+        assert_eq!(exit_directive.exit_code, 0);
+
+        for file in ["index.html", "style.css", "search.js", "search-index.js"] {
+            assert!(temp_output.join(file).exists(), "{} is missing", file);
+        }
+
+        let index = std::fs::read_to_string(temp_output.join("index.html"))
+            .expect("Failed to read index.html");
+        assert!(index.contains("<title>Test Registry</title>"));
+        assert!(index.contains(r#"<a href="http.html">http</a>"#));
+
+        // References to registry attributes are linked to their defining group.
+        let http = std::fs::read_to_string(temp_output.join("http.html"))
+            .expect("Failed to read http.html");
+        assert!(http.contains(r#"<tr id="registry.http--http.request.method">"#));
+        assert!(http.contains(
+            r#"<a href="error.html#registry.error--error.type"><code>error.type</code></a>"#
+        ));
+        assert!(http.contains(r#"<span class="badge stable">stable</span>"#));
+
+        let search_index = std::fs::read_to_string(temp_output.join("search-index.js"))
+            .expect("Failed to read search-index.js");
+        assert!(search_index.contains(r#""href":"http.html#registry.http--http.request.method""#));
+    }
+}
@@ -0,0 +1,709 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Export the attribute catalog of a semantic convention registry in formats
+//! consumed by data tools (CSV, Parquet) and data catalogs (JSON-LD).
+
+use std::collections::HashMap;
+use std::io::Write;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use clap::{Args, ValueEnum};
+use parquet::data_type::{ByteArray, ByteArrayType, Int64Type};
+use parquet::file::properties::WriterProperties;
+use parquet::file::writer::SerializedFileWriter;
+use parquet::schema::parser::parse_message_type;
+use serde_json::{json, Map, Value};
+
+use weaver_cache::Cache;
+use weaver_common::diagnostic::DiagnosticMessages;
+use weaver_common::Logger;
+use weaver_resolved_schema::registry::Group;
+use weaver_resolved_schema::ResolvedTelemetrySchema;
+use weaver_semconv::attribute::{AttributeSpec, BasicRequirementLevelSpec, RequirementLevel};
+use weaver_semconv::group::GroupType;
+use weaver_semconv::registry::SemConvRegistry;
+use weaver_semconv::semconv::SemConvSpec;
+
+use crate::registry::{Error, RegistryArgs, RegistryPath};
+use crate::util::{load_semconv_specs, resolve_semconv_specs};
+use crate::{DiagnosticArgs, ExitDirectives};
+
+/// Supported export formats
+#[derive(Debug, Clone, ValueEnum)]
+pub enum ExportFormat {
+    /// CSV file with a header row
+    Csv,
+    /// Apache Parquet file
+    Parquet,
+    /// JSON-LD document using the schema.org vocabulary
+    Jsonld,
+}
+
+/// Parameters for the `registry export` sub-command
+#[derive(Debug, Args)]
+pub struct RegistryExportArgs {
+    /// Parameters to specify the semantic convention registry
+    #[command(flatten)]
+    registry: RegistryArgs,
+
+    /// Export format.
+    #[arg(short, long, default_value = "csv")]
+    format: ExportFormat,
+
+    /// Output file to write the export to.
+    /// If not specified, the export is printed to stdout (text formats only)
+    #[arg(short, long)]
+    output: Option<PathBuf>,
+
+    /// Base IRI of the groups and attributes of the JSON-LD export (e.g.
+    /// `https://example.com/semconv/`). Defaults to the Git URL of the
+    /// registry, or to `urn:semconv:` for a local registry.
+    #[arg(long)]
+    base_iri: Option<String>,
+
+    /// Parameters to specify the diagnostic format.
+    #[command(flatten)]
+    pub diagnostic: DiagnosticArgs,
+}
+
+/// An attribute of the catalog with its usage in the registry.
+#[derive(Debug, Default, PartialEq)]
+struct CatalogEntry {
+    name: String,
+    r#type: String,
+    stability: Option<String>,
+    deprecated: Option<String>,
+    brief: String,
+    /// Group defining the attribute.
+    defining_group: Option<String>,
+    /// Signal groups (spans, events, metrics, resources, scopes) using the
+    /// attribute.
+    signal_groups: Vec<String>,
+    /// Number of signal groups using the attribute by requirement level.
+    required_count: usize,
+    conditionally_required_count: usize,
+    recommended_count: usize,
+    opt_in_count: usize,
+}
+
+/// Names of the columns of the export, in order.
+const COLUMNS: [&str; 11] = [
+    "name",
+    "type",
+    "stability",
+    "deprecated",
+    "brief",
+    "defining_group",
+    "signal_groups",
+    "required_count",
+    "conditionally_required_count",
+    "recommended_count",
+    "opt_in_count",
+];
+
+/// Export the attribute catalog of a semantic convention registry.
+#[cfg(not(tarpaulin_include))]
+pub(crate) fn command(
+    logger: impl Logger + Sync + Clone,
+    cache: &Cache,
+    args: &RegistryExportArgs,
+) -> Result<ExitDirectives, DiagnosticMessages> {
+    if args.output.is_none() {
+        if let ExportFormat::Parquet = args.format {
+            return Err(Error::CatalogExportFailed {
+                error: "The Parquet format requires an output file (`--output`).".to_owned(),
+            }
+            .into());
+        }
+        logger.mute();
+    }
+    logger.loading(&format!(
+        "Exporting the attribute catalog of the registry `{}`",
+        args.registry.registries()
+    ));
+
+    let registry_id = "default";
+    let semconv_specs = load_semconv_specs(
+        &args.registry.registry_paths(),
+        &args.registry.file_filter()?,
+        cache,
+        logger.clone(),
+    )?;
+    let definitions = attribute_definitions(&semconv_specs);
+    let mut registry = SemConvRegistry::from_semconv_specs(registry_id, semconv_specs);
+    let schema = resolve_semconv_specs(&mut registry, logger.clone())?;
+    let entries = catalog_entries(&schema, &definitions);
+
+    let export_failed = |e: &dyn ToString| Error::CatalogExportFailed {
+        error: e.to_string(),
+    };
+    match (&args.format, &args.output) {
+        (ExportFormat::Csv, None) => {
+            write_csv(std::io::stdout().lock(), &entries).map_err(|e| export_failed(&e))?;
+        }
+        (ExportFormat::Csv, Some(path)) => {
+            let file = std::fs::File::create(path).map_err(|e| export_failed(&e))?;
+            write_csv(file, &entries).map_err(|e| export_failed(&e))?;
+        }
+        (ExportFormat::Parquet, Some(path)) => {
+            let file = std::fs::File::create(path).map_err(|e| export_failed(&e))?;
+            write_parquet(file, &entries).map_err(|e| export_failed(&e))?;
+        }
+        (ExportFormat::Parquet, None) => unreachable!("checked above"),
+        (ExportFormat::Jsonld, output) => {
+            let base_iri = base_iri(&args.registry, args.base_iri.as_deref());
+            let document = to_jsonld(&schema, &entries, &args.registry, &base_iri);
+            let document = serde_json::to_string_pretty(&document)
+                .expect("Failed to serialize the JSON-LD document");
+            match output {
+                Some(path) => std::fs::write(path, document).map_err(|e| export_failed(&e))?,
+                None => println!("{}", document),
+            }
+        }
+    }
+    if let Some(path) = &args.output {
+        logger.success(&format!(
+            "{} attributes exported to `{}`",
+            entries.len(),
+            path.display()
+        ));
+    }
+
+    Ok(ExitDirectives {
+        exit_code: 0,
+        quiet_mode: args.output.is_none(),
+    })
+}
+
+/// Returns the group defining each attribute, by attribute name.
+fn attribute_definitions(semconv_specs: &[(String, SemConvSpec)]) -> HashMap<String, String> {
+    let mut definitions = HashMap::new();
+    for group in semconv_specs.iter().flat_map(|(_, spec)| spec.groups()) {
+        for attr in &group.attributes {
+            if let AttributeSpec::Id { id, .. } = attr {
+                let name = if group.prefix.is_empty() {
+                    id.clone()
+                } else {
+                    format!("{}.{}", group.prefix, id)
+                };
+                _ = definitions.insert(name, group.id.clone());
+            }
+        }
+    }
+    definitions
+}
+
+/// Returns the attributes of the catalog, sorted by name, with their usage in
+/// the groups of the registries.
+fn catalog_entries(
+    schema: &ResolvedTelemetrySchema,
+    definitions: &HashMap<String, String>,
+) -> Vec<CatalogEntry> {
+    let groups: HashMap<&str, &Group> = schema
+        .registries
+        .values()
+        .flat_map(|registry| &registry.groups)
+        .map(|group| (group.id.as_str(), group))
+        .collect();
+    schema
+        .attribute_index
+        .iter()
+        .filter_map(|(name, usages)| {
+            let defining_group = definitions.get(name);
+            // The attribute of the defining group is the reference, the other
+            // groups may override some fields.
+            let usage = usages
+                .iter()
+                .find(|usage| Some(&usage.group_id) == defining_group)
+                .or_else(|| usages.first())?;
+            let attr = groups
+                .get(usage.group_id.as_str())?
+                .attributes
+                .iter()
+                .filter_map(|attr_ref| schema.catalog().attribute(attr_ref))
+                .find(|attr| attr.name.as_str() == name)?;
+            let mut entry = CatalogEntry {
+                name: name.clone(),
+                r#type: attr.r#type.to_string(),
+                stability: attr.stability.as_ref().map(ToString::to_string),
+                deprecated: attr.deprecated.clone(),
+                brief: attr.brief.trim().to_owned(),
+                defining_group: defining_group.cloned(),
+                ..CatalogEntry::default()
+            };
+            for usage in usages {
+                let is_signal = matches!(
+                    usage.group_type,
+                    GroupType::Span
+                        | GroupType::Event
+                        | GroupType::Metric
+                        | GroupType::Resource
+                        | GroupType::Scope
+                );
+                if !is_signal || entry.signal_groups.contains(&usage.group_id) {
+                    continue;
+                }
+                // The usages are sorted by group id.
+                entry.signal_groups.push(usage.group_id.clone());
+                match &usage.requirement_level {
+                    RequirementLevel::Basic(BasicRequirementLevelSpec::Required) => {
+                        entry.required_count += 1;
+                    }
+                    RequirementLevel::ConditionallyRequired { .. } => {
+                        entry.conditionally_required_count += 1;
+                    }
+                    RequirementLevel::Basic(BasicRequirementLevelSpec::Recommended)
+                    | RequirementLevel::Recommended { .. } => entry.recommended_count += 1,
+                    RequirementLevel::Basic(
+                        BasicRequirementLevelSpec::OptIn | BasicRequirementLevelSpec::Optional,
+                    ) => entry.opt_in_count += 1,
+                }
+            }
+            Some(entry)
+        })
+        .collect()
+}
+
+impl CatalogEntry {
+    /// Returns the values of the text columns of the entry, in order.
+    fn text_columns(&self) -> [String; 7] {
+        [
+            self.name.clone(),
+            self.r#type.clone(),
+            self.stability.clone().unwrap_or_default(),
+            self.deprecated.clone().unwrap_or_default(),
+            self.brief.clone(),
+            self.defining_group.clone().unwrap_or_default(),
+            self.signal_groups.join(";"),
+        ]
+    }
+
+    /// Returns the values of the count columns of the entry, in order.
+    fn count_columns(&self) -> [usize; 4] {
+        [
+            self.required_count,
+            self.conditionally_required_count,
+            self.recommended_count,
+            self.opt_in_count,
+        ]
+    }
+}
+
+/// Writes the entries in CSV format, with a header row. The signal groups are
+/// separated by `;`.
+fn write_csv(writer: impl Write, entries: &[CatalogEntry]) -> Result<(), csv::Error> {
+    let mut writer = csv::Writer::from_writer(writer);
+    writer.write_record(COLUMNS)?;
+    for entry in entries {
+        let counts = entry.count_columns().map(|count| count.to_string());
+        writer.write_record(entry.text_columns().iter().chain(counts.iter()))?;
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+/// Writes the entries in Parquet format, in a single row group. The
+/// `stability`, `deprecated`, and `defining_group` columns are optional (null
+/// when not defined), the signal groups are separated by `;`.
+fn write_parquet(
+    writer: impl Write + Send,
+    entries: &[CatalogEntry],
+) -> Result<(), parquet::errors::ParquetError> {
+    const OPTIONAL_COLUMNS: [&str; 3] = ["stability", "deprecated", "defining_group"];
+    let mut message_type = String::from("message attribute_catalog {\n");
+    for column in &COLUMNS[..7] {
+        let repetition = if OPTIONAL_COLUMNS.contains(column) {
+            "OPTIONAL"
+        } else {
+            "REQUIRED"
+        };
+        message_type.push_str(&format!("  {} BYTE_ARRAY {} (UTF8);\n", repetition, column));
+    }
+    for column in &COLUMNS[7..] {
+        message_type.push_str(&format!("  REQUIRED INT64 {};\n", column));
+    }
+    message_type.push('}');
+    let schema = Arc::new(parse_message_type(&message_type)?);
+
+    let properties = Arc::new(WriterProperties::builder().build());
+    let mut writer = SerializedFileWriter::new(writer, schema, properties)?;
+    let mut row_group = writer.next_row_group()?;
+    let rows: Vec<([String; 7], [usize; 4])> = entries
+        .iter()
+        .map(|entry| (entry.text_columns(), entry.count_columns()))
+        .collect();
+    let mut index = 0;
+    while let Some(mut column) = row_group.next_column()? {
+        if index < 7 {
+            let optional = OPTIONAL_COLUMNS.contains(&COLUMNS[index]);
+            let values: Vec<ByteArray> = rows
+                .iter()
+                .map(|(texts, _)| &texts[index])
+                .filter(|value| !optional || !value.is_empty())
+                .map(|value| ByteArray::from(value.as_str()))
+                .collect();
+            let def_levels: Option<Vec<i16>> = optional.then(|| {
+                rows.iter()
+                    .map(|(texts, _)| i16::from(!texts[index].is_empty()))
+                    .collect()
+            });
+            _ = column.typed::<ByteArrayType>().write_batch(
+                &values,
+                def_levels.as_deref(),
+                None,
+            )?;
+        } else {
+            let values: Vec<i64> = rows
+                .iter()
+                .map(|(_, counts)| counts[index - 7] as i64)
+                .collect();
+            _ = column
+                .typed::<Int64Type>()
+                .write_batch(&values, None, None)?;
+        }
+        column.close()?;
+        index += 1;
+    }
+    _ = row_group.close()?;
+    _ = writer.close()?;
+    Ok(())
+}
+
+/// Returns the base IRI of the JSON-LD export, ending with `/` or `:`.
+fn base_iri(registry: &RegistryArgs, base_iri: Option<&str>) -> String {
+    match (base_iri, registry.registry.first()) {
+        (Some(base_iri), _) if base_iri.ends_with(['/', ':', '#']) => base_iri.to_owned(),
+        (Some(base_iri), _) => format!("{base_iri}/"),
+        (None, Some(RegistryPath::Url(url))) => {
+            let url = url.trim_end_matches('/');
+            format!("{}/", url.strip_suffix(".git").unwrap_or(url))
+        }
+        (None, _) => "urn:semconv:".to_owned(),
+    }
+}
+
+/// Returns the IRI of a group.
+fn group_iri(base_iri: &str, id: &str) -> String {
+    format!("{base_iri}group/{id}")
+}
+
+/// Returns the IRI of an attribute.
+fn attribute_iri(base_iri: &str, name: &str) -> String {
+    format!("{base_iri}attribute/{name}")
+}
+
+/// Serializes the registry as a JSON-LD document. The registry and its groups
+/// are `DefinedTermSet`s and the attributes `DefinedTerm`s of the schema.org
+/// vocabulary. The properties specific to the semantic conventions (group
+/// type, attribute type, stability, ...) use the `semconv` vocabulary, under
+/// `<base IRI>vocab#`.
+fn to_jsonld(
+    schema: &ResolvedTelemetrySchema,
+    entries: &[CatalogEntry],
+    registry: &RegistryArgs,
+    base_iri: &str,
+) -> Value {
+    let mut groups: Vec<_> = schema
+        .registries
+        .values()
+        .flat_map(|registry| &registry.groups)
+        .collect();
+    groups.sort_by(|a, b| a.id.cmp(&b.id));
+
+    let mut root = json!({
+        "@id": base_iri,
+        "@type": "DefinedTermSet",
+        "name": "Semantic convention registry",
+        "hasPart": groups
+            .iter()
+            .map(|group| group_iri(base_iri, &group.id))
+            .collect::<Vec<_>>(),
+    });
+    let urls: Vec<&str> = registry
+        .registry
+        .iter()
+        .filter_map(|path| match path {
+            RegistryPath::Url(url) => Some(url.as_str()),
+            RegistryPath::Local(_) => None,
+        })
+        .collect();
+    if !urls.is_empty() {
+        root["url"] = json!(urls);
+    }
+    let mut graph = vec![root];
+    for group in &groups {
+        let mut node = Map::new();
+        _ = node.insert("@id".to_owned(), json!(group_iri(base_iri, &group.id)));
+        _ = node.insert("@type".to_owned(), json!("DefinedTermSet"));
+        _ = node.insert("identifier".to_owned(), json!(group.id));
+        _ = node.insert(
+            "name".to_owned(),
+            json!(group.display_name.as_deref().unwrap_or(&group.id)),
+        );
+        _ = node.insert("description".to_owned(), json!(group.brief.trim()));
+        _ = node.insert("isPartOf".to_owned(), json!(base_iri));
+        _ = node.insert(
+            "semconv:groupType".to_owned(),
+            serde_json::to_value(&group.r#type).expect("Failed to serialize the group type"),
+        );
+        if let Some(stability) = &group.stability {
+            _ = node.insert("semconv:stability".to_owned(), json!(stability.to_string()));
+        }
+        if let Some(deprecated) = &group.deprecated {
+            _ = node.insert("semconv:deprecated".to_owned(), json!(deprecated));
+        }
+        if let Some(metric_name) = &group.metric_name {
+            _ = node.insert("semconv:metricName".to_owned(), json!(metric_name));
+        }
+        if let Some(instrument) = &group.instrument {
+            _ = node.insert(
+                "semconv:instrument".to_owned(),
+                json!(instrument.to_string()),
+            );
+        }
+        if let Some(unit) = &group.unit {
+            _ = node.insert("semconv:unit".to_owned(), json!(unit));
+        }
+        let attributes: Vec<&str> = group
+            .attributes
+            .iter()
+            .filter_map(|attr_ref| schema.catalog().attribute(attr_ref))
+            .map(|attr| attr.name.as_str())
+            .collect();
+        let defined: Vec<String> = entries
+            .iter()
+            .filter(|entry| entry.defining_group.as_deref() == Some(group.id.as_str()))
+            .map(|entry| attribute_iri(base_iri, &entry.name))
+            .collect();
+        if !defined.is_empty() {
+            _ = node.insert("hasDefinedTerm".to_owned(), json!(defined));
+        }
+        if !attributes.is_empty() {
+            _ = node.insert(
+                "semconv:attribute".to_owned(),
+                json!(attributes
+                    .iter()
+                    .map(|name| attribute_iri(base_iri, name))
+                    .collect::<Vec<_>>()),
+            );
+        }
+        graph.push(Value::Object(node));
+    }
+    for entry in entries {
+        let mut node = Map::new();
+        _ = node.insert(
+            "@id".to_owned(),
+            json!(attribute_iri(base_iri, &entry.name)),
+        );
+        _ = node.insert("@type".to_owned(), json!("DefinedTerm"));
+        _ = node.insert("identifier".to_owned(), json!(entry.name));
+        _ = node.insert("name".to_owned(), json!(entry.name));
+        _ = node.insert("description".to_owned(), json!(entry.brief));
+        if let Some(defining_group) = &entry.defining_group {
+            _ = node.insert(
+                "inDefinedTermSet".to_owned(),
+                json!(group_iri(base_iri, defining_group)),
+            );
+        }
+        _ = node.insert("semconv:type".to_owned(), json!(entry.r#type));
+        if let Some(stability) = &entry.stability {
+            _ = node.insert("semconv:stability".to_owned(), json!(stability));
+        }
+        if let Some(deprecated) = &entry.deprecated {
+            _ = node.insert("semconv:deprecated".to_owned(), json!(deprecated));
+        }
+        graph.push(Value::Object(node));
+    }
+
+    let iri = json!({ "@type": "@id" });
+    json!({
+        "@context": {
+            "@vocab": "https://schema.org/",
+            "semconv": format!("{base_iri}vocab#"),
+            "hasPart": iri,
+            "isPartOf": iri,
+            "hasDefinedTerm": iri,
+            "inDefinedTermSet": iri,
+            "semconv:attribute": iri,
+        },
+        "@graph": graph,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::File;
+
+    use parquet::file::reader::{FileReader, SerializedFileReader};
+    use tempdir::TempDir;
+
+    use weaver_resolved_schema::ResolvedTelemetrySchema;
+    use weaver_resolver::SchemaResolver;
+    use weaver_semconv::registry::SemConvRegistry;
+
+    use crate::registry::export::{
+        attribute_definitions, base_iri, catalog_entries, to_jsonld, write_csv, write_parquet,
+        CatalogEntry,
+    };
+    use crate::registry::{RegistryArgs, RegistryPath};
+
+    fn entries() -> Vec<CatalogEntry> {
+        resolved().1
+    }
+
+    fn resolved() -> (ResolvedTelemetrySchema, Vec<CatalogEntry>) {
+        let semconv_specs = vec![SemConvRegistry::semconv_spec_from_file(
+            "crates/weaver_schema/data/v1.1.0/registry.yaml",
+        )
+        .expect("Failed to load the registry")];
+        let definitions = attribute_definitions(&semconv_specs);
+        let mut registry = SemConvRegistry::from_semconv_specs("default", semconv_specs);
+        let schema = SchemaResolver::resolve_semantic_convention_registry(&mut registry)
+            .expect("Failed to resolve the registry");
+        let entries = catalog_entries(&schema, &definitions);
+        (schema, entries)
+    }
+
+    #[test]
+    fn test_catalog_entries() {
+        let entries = entries();
+        assert_eq!(
+            entries.iter().map(|e| e.name.as_str()).collect::<Vec<_>>(),
+            vec![
+                "http.flavor",
+                "http.method",
+                "http.request.method",
+                "http.url",
+                "url.full"
+            ]
+        );
+        assert_eq!(
+            entries[1],
+            CatalogEntry {
+                name: "http.method".to_owned(),
+                r#type: "string".to_owned(),
+                stability: Some("experimental".to_owned()),
+                deprecated: Some("Replaced by `http.request.method`.".to_owned()),
+                brief: "HTTP request method.".to_owned(),
+                defining_group: Some("registry.http".to_owned()),
+                signal_groups: vec!["metric.http.server.duration".to_owned()],
+                recommended_count: 1,
+                ..Default::default()
+            }
+        );
+        assert!(entries[4].signal_groups.is_empty());
+    }
+
+    #[test]
+    fn test_write_csv() {
+        let mut csv = vec![];
+        write_csv(&mut csv, &entries()).expect("Failed to write the CSV");
+        let csv = String::from_utf8(csv).expect("Invalid UTF-8");
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines.len(), 6);
+        assert_eq!(
+            lines[0],
+            "name,type,stability,deprecated,brief,defining_group,signal_groups,required_count,conditionally_required_count,recommended_count,opt_in_count"
+        );
+        assert_eq!(
+            lines[3],
+            "http.request.method,string,stable,,HTTP request method.,registry.http,metric.http.server.request.duration,0,0,1,0"
+        );
+    }
+
+    #[test]
+    fn test_write_parquet() {
+        let dir = TempDir::new("weaver-export").expect("Failed to create a temp dir");
+        let path = dir.path().join("catalog.parquet");
+        let file = File::create(&path).expect("Failed to create the file");
+        write_parquet(file, &entries()).expect("Failed to write the Parquet file");
+
+        let reader = SerializedFileReader::new(File::open(&path).expect("Failed to open the file"))
+            .expect("Invalid Parquet file");
+        let metadata = reader.metadata();
+        assert_eq!(metadata.file_metadata().num_rows(), 5);
+        assert_eq!(metadata.file_metadata().schema_descr().num_columns(), 11);
+        let rows: Vec<String> = reader
+            .get_row_iter(None)
+            .expect("Failed to read the rows")
+            .map(|row| row.expect("Invalid row").to_string())
+            .collect();
+        assert!(rows[4].starts_with(
+            "{name: \"url.full\", type: \"string\", stability: \"stable\", deprecated: null,"
+        ));
+    }
+
+    #[test]
+    fn test_to_jsonld() {
+        let mut registry = RegistryArgs {
+            registry: vec![RegistryPath::Url(
+                "https://github.com/open-telemetry/semantic-conventions.git".to_owned(),
+            )],
+            registry_git_sub_dir: None,
+            include: vec![],
+            exclude: vec![],
+        };
+        let base = base_iri(&registry, None);
+        assert_eq!(
+            base,
+            "https://github.com/open-telemetry/semantic-conventions/"
+        );
+        assert_eq!(
+            base_iri(&registry, Some("https://example.com/semconv")),
+            "https://example.com/semconv/"
+        );
+
+        let (schema, entries) = resolved();
+        let document = to_jsonld(&schema, &entries, &registry, &base);
+        assert_eq!(document["@context"]["@vocab"], "https://schema.org/");
+        assert_eq!(
+            document["@context"]["semconv"],
+            "https://github.com/open-telemetry/semantic-conventions/vocab#"
+        );
+        let graph = document["@graph"].as_array().expect("Missing @graph");
+        let node = |id: &str| {
+            graph
+                .iter()
+                .find(|node| node["@id"] == id)
+                .unwrap_or_else(|| panic!("Node `{id}` not found"))
+        };
+
+        let registry_http =
+            node("https://github.com/open-telemetry/semantic-conventions/group/registry.http");
+        assert_eq!(registry_http["@type"], "DefinedTermSet");
+        assert_eq!(
+            node(&base)["url"][0],
+            "https://github.com/open-telemetry/semantic-conventions.git"
+        );
+        assert_eq!(registry_http["semconv:groupType"], "attribute_group");
+        assert!(registry_http["hasDefinedTerm"]
+            .as_array()
+            .expect("Missing hasDefinedTerm")
+            .contains(
+                &"https://github.com/open-telemetry/semantic-conventions/attribute/http.method"
+                    .into()
+            ));
+
+        let http_method =
+            node("https://github.com/open-telemetry/semantic-conventions/attribute/http.method");
+        assert_eq!(http_method["@type"], "DefinedTerm");
+        assert_eq!(http_method["description"], "HTTP request method.");
+        assert_eq!(
+            http_method["inDefinedTermSet"],
+            "https://github.com/open-telemetry/semantic-conventions/group/registry.http"
+        );
+        assert_eq!(
+            http_method["semconv:deprecated"],
+            "Replaced by `http.request.method`."
+        );
+        // One node for the registry, the groups, and the attributes.
+        assert_eq!(
+            graph.len(),
+            1 + node(&base)["hasPart"].as_array().map_or(0, Vec::len) + entries.len()
+        );
+
+        registry.registry = vec![RegistryPath::Local("model".to_owned())];
+        assert_eq!(base_iri(&registry, None), "urn:semconv:");
+    }
+}
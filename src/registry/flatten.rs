@@ -0,0 +1,263 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Flatten a resolved semantic convention registry: every group carries its
+//! full attribute objects inline, for consumers that want human-inspectable
+//! resolved files rather than the indexed format of the resolved schema.
+
+use std::path::PathBuf;
+
+use clap::{Args, ValueEnum};
+use serde::Serialize;
+
+use weaver_cache::Cache;
+use weaver_common::diagnostic::DiagnosticMessages;
+use weaver_common::Logger;
+use weaver_forge::registry::{ResolvedGroup, ResolvedRegistry};
+use weaver_semconv::registry::SemConvRegistry;
+
+use crate::registry::{Error, RegistryArgs};
+use crate::util::{load_semconv_specs, resolve_semconv_specs};
+use crate::{DiagnosticArgs, ExitDirectives};
+
+/// Supported output formats for the flattened registry
+#[derive(Debug, Clone, ValueEnum)]
+pub enum FlattenFormat {
+    /// YAML format (a stream of documents with `--per-group`)
+    Yaml,
+    /// JSON format (JSON lines with `--per-group`)
+    Json,
+}
+
+/// Parameters for the `registry flatten` sub-command
+#[derive(Debug, Args)]
+pub struct RegistryFlattenArgs {
+    /// Parameters to specify the semantic convention registry
+    #[command(flatten)]
+    registry: RegistryArgs,
+
+    /// Output format of the flattened registry.
+    #[arg(short, long, default_value = "yaml")]
+    format: FlattenFormat,
+
+    /// Write one document per group (a YAML stream, or JSON lines) instead of
+    /// a single document listing all the groups.
+    #[arg(long)]
+    per_group: bool,
+
+    /// Keep the lineage of the groups (source file and provenance of the
+    /// attributes).
+    #[arg(long)]
+    lineage: bool,
+
+    /// Output file to write the flattened registry to.
+    /// If not specified, the flattened registry is printed to stdout
+    #[arg(short, long)]
+    output: Option<PathBuf>,
+
+    /// Parameters to specify the diagnostic format.
+    #[command(flatten)]
+    pub diagnostic: DiagnosticArgs,
+}
+
+/// A flattened registry.
+#[derive(Debug, Serialize)]
+struct FlattenedRegistry<'a> {
+    /// The groups sorted by id, each one with its attributes sorted by name.
+    groups: &'a [ResolvedGroup],
+}
+
+/// Flatten a semantic convention registry and write it to a file or print it
+/// to stdout.
+#[cfg(not(tarpaulin_include))]
+pub(crate) fn command(
+    logger: impl Logger + Sync + Clone,
+    cache: &Cache,
+    args: &RegistryFlattenArgs,
+) -> Result<ExitDirectives, DiagnosticMessages> {
+    if args.output.is_none() {
+        logger.mute();
+    }
+    logger.loading(&format!(
+        "Flattening registry `{}`",
+        args.registry.registries()
+    ));
+
+    let registry_id = "default";
+    let semconv_specs = load_semconv_specs(
+        &args.registry.registry_paths(),
+        &args.registry.file_filter()?,
+        cache,
+        logger.clone(),
+    )?;
+    let mut registry = SemConvRegistry::from_semconv_specs(registry_id, semconv_specs);
+    let schema = resolve_semconv_specs(&mut registry, logger.clone())?;
+    let registry = ResolvedRegistry::try_from_resolved_registry(
+        schema
+            .registry(registry_id)
+            .expect("Failed to get the registry from the resolved schema"),
+        schema.catalog(),
+    )
+    .unwrap_or_else(|e| panic!("Failed to create the registry without catalog: {e:?}"));
+
+    let groups = flatten_groups(registry, args.lineage);
+    let output = render(&groups, &args.format, args.per_group);
+    if let Some(path) = &args.output {
+        std::fs::write(path, output).map_err(|e| Error::FlattenWriteFailed {
+            output: path.clone(),
+            error: e.to_string(),
+        })?;
+        logger.success(&format!(
+            "Flattened registry written to `{}`",
+            path.display()
+        ));
+    } else {
+        print!("{}", output);
+    }
+
+    Ok(ExitDirectives {
+        exit_code: 0,
+        quiet_mode: args.output.is_none(),
+    })
+}
+
+/// Returns the groups of a resolved registry sorted by id, with their
+/// attributes sorted by name, and without lineage unless `lineage` is set.
+fn flatten_groups(registry: ResolvedRegistry, lineage: bool) -> Vec<ResolvedGroup> {
+    let mut groups = registry.groups;
+    groups.sort_by(|a, b| a.id.cmp(&b.id));
+    for group in &mut groups {
+        group.attributes.sort_by(|a, b| a.name.cmp(&b.name));
+        if !lineage {
+            group.lineage = None;
+        }
+    }
+    groups
+}
+
+/// Renders the flattened groups in the given format, as a single document or
+/// as one document per group.
+fn render(groups: &[ResolvedGroup], format: &FlattenFormat, per_group: bool) -> String {
+    match (format, per_group) {
+        (FlattenFormat::Yaml, false) => serde_yaml::to_string(&FlattenedRegistry { groups })
+            .expect("Failed to serialize the flattened registry"),
+        (FlattenFormat::Yaml, true) => groups
+            .iter()
+            .map(|group| {
+                format!(
+                    "---\n{}",
+                    serde_yaml::to_string(group).expect("Failed to serialize the group")
+                )
+            })
+            .collect(),
+        (FlattenFormat::Json, false) => {
+            let mut json = serde_json::to_string_pretty(&FlattenedRegistry { groups })
+                .expect("Failed to serialize the flattened registry");
+            json.push('\n');
+            json
+        }
+        (FlattenFormat::Json, true) => groups
+            .iter()
+            .map(|group| {
+                let mut json = serde_json::to_string(group).expect("Failed to serialize the group");
+                json.push('\n');
+                json
+            })
+            .collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::Deserialize;
+    use weaver_forge::registry::{ResolvedGroup, ResolvedRegistry};
+    use weaver_resolver::SchemaResolver;
+    use weaver_semconv::registry::SemConvRegistry;
+
+    use crate::registry::flatten::{flatten_groups, render, FlattenFormat};
+
+    fn flattened_groups(lineage: bool) -> Vec<ResolvedGroup> {
+        let mut registry = SemConvRegistry::try_from_path_pattern(
+            "default",
+            "crates/weaver_resolver/data/registry-test-3-extends/registry/*.yaml",
+        )
+        .expect("Failed to load the registry");
+        let schema = SchemaResolver::resolve_semantic_convention_registry(&mut registry)
+            .expect("Failed to resolve the registry");
+        let registry = ResolvedRegistry::try_from_resolved_registry(
+            schema.registry("default").expect("Registry not found"),
+            schema.catalog(),
+        )
+        .expect("Failed to create the registry without catalog");
+        flatten_groups(registry, lineage)
+    }
+
+    #[test]
+    fn test_flatten_groups() {
+        let groups = flattened_groups(false);
+        assert!(groups.windows(2).all(|w| w[0].id < w[1].id));
+        assert!(groups.iter().all(|group| group.lineage.is_none()
+            && group.attributes.windows(2).all(|w| w[0].name <= w[1].name)));
+
+        // The inherited attributes are inlined.
+        let duration = groups
+            .iter()
+            .find(|group| group.id == "metric.messaging.publish.duration")
+            .expect("Group not found");
+        let names: Vec<_> = duration
+            .attributes
+            .iter()
+            .map(|attr| attr.name.as_str())
+            .collect();
+        assert_eq!(
+            names,
+            vec![
+                "error.type",
+                "messaging.destination.name",
+                "messaging.destination.template",
+                "messaging.system",
+                "network.protocol.name",
+                "network.protocol.version",
+                "server.address",
+                "server.port",
+            ]
+        );
+        assert!(duration
+            .attributes
+            .iter()
+            .all(|attr| !attr.brief.is_empty()));
+
+        assert!(flattened_groups(true)
+            .iter()
+            .all(|group| group.lineage.is_some()));
+    }
+
+    #[test]
+    fn test_render_per_group() {
+        let groups = flattened_groups(false);
+
+        let yaml = render(&groups, &FlattenFormat::Yaml, true);
+        let documents: Vec<ResolvedGroup> = serde_yaml::Deserializer::from_str(&yaml)
+            .map(|document| ResolvedGroup::deserialize(document).expect("Invalid YAML document"))
+            .collect();
+        assert_eq!(documents.len(), groups.len());
+        assert_eq!(documents[0].id, groups[0].id);
+
+        let json = render(&groups, &FlattenFormat::Json, true);
+        let lines: Vec<ResolvedGroup> = json
+            .lines()
+            .map(|line| serde_json::from_str(line).expect("Invalid JSON line"))
+            .collect();
+        assert_eq!(lines.len(), groups.len());
+        assert_eq!(lines[1].id, groups[1].id);
+
+        let yaml = render(&groups, &FlattenFormat::Yaml, false);
+        let registry: serde_yaml::Value = serde_yaml::from_str(&yaml).expect("Invalid YAML");
+        assert_eq!(
+            registry["groups"]
+                .as_sequence()
+                .expect("groups is not a sequence")
+                .len(),
+            groups.len()
+        );
+    }
+}
@@ -0,0 +1,707 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Format the semantic convention files of a local registry.
+
+use std::path::{Path, PathBuf};
+
+use clap::Args;
+use serde_yaml::{Mapping, Value};
+use walkdir::WalkDir;
+
+use weaver_common::diagnostic::{DiagnosticMessage, DiagnosticMessages};
+use weaver_common::Logger;
+use weaver_diff::diff_output;
+use weaver_resolver::file_filter::RegistryFileFilter;
+use weaver_semconv::manifest::REGISTRY_MANIFEST;
+use weaver_semconv::registry::SemConvRegistry;
+
+use crate::registry::Error;
+use crate::{DiagnosticArgs, ExitDirectives};
+
+/// Canonical order of the keys of a group.
+const GROUP_KEYS: &[&str] = &[
+    "id",
+    "type",
+    "span_kind",
+    "metric_name",
+    "instrument",
+    "unit",
+    "name",
+    "scope_version",
+    "display_name",
+    "doc_url",
+    "since_version",
+    "prefix",
+    "extends",
+    "stability",
+    "deprecated",
+    "brief",
+    "note",
+    "events",
+    "constraints",
+    "attributes",
+];
+
+/// Canonical order of the keys of an attribute (definition or reference).
+const ATTRIBUTE_KEYS: &[&str] = &[
+    "id",
+    "ref",
+    "type",
+    "stability",
+    "deprecated",
+    "requirement_level",
+    "sampling_relevant",
+    "tag",
+    "prefix",
+    "brief",
+    "note",
+    "examples",
+];
+
+/// Canonical order of the keys of an enum type.
+const ENUM_TYPE_KEYS: &[&str] = &["allow_custom_values", "members"];
+
+/// Canonical order of the keys of an enum member.
+const MEMBER_KEYS: &[&str] = &["id", "value", "stability", "deprecated", "brief", "note"];
+
+/// Canonical order of the keys of a constraint.
+const CONSTRAINT_KEYS: &[&str] = &["any_of", "include"];
+
+/// The keys whose long single-line values are wrapped.
+const WRAPPED_KEYS: &[&str] = &["brief", "note"];
+
+/// Parameters for the `registry fmt` sub-command
+#[derive(Debug, Args)]
+pub struct RegistryFmtArgs {
+    /// Local path of the semantic convention registry to format.
+    #[arg(short = 'r', long)]
+    pub registry: PathBuf,
+
+    /// Check that the files are formatted, without modifying them. The
+    /// differences are printed and the process exits with a non-zero code if a
+    /// file is not formatted.
+    #[arg(long, default_value = "false")]
+    pub check: bool,
+
+    /// Maximum width of the lines, beyond which the briefs and notes are
+    /// wrapped.
+    #[arg(long, default_value = "100")]
+    pub max_width: usize,
+
+    /// Parameters to specify the diagnostic format.
+    #[command(flatten)]
+    pub diagnostic: DiagnosticArgs,
+}
+
+/// Context of a YAML node in a semantic convention file, defining the
+/// canonical order of its keys.
+#[derive(Clone, Copy, PartialEq)]
+enum Context {
+    Root,
+    Group,
+    Attribute,
+    EnumType,
+    Member,
+    Constraint,
+    Other,
+}
+
+impl Context {
+    /// Returns the canonical order of the keys of a mapping in this context.
+    fn keys(self) -> &'static [&'static str] {
+        match self {
+            Context::Root => &["groups"],
+            Context::Group => GROUP_KEYS,
+            Context::Attribute => ATTRIBUTE_KEYS,
+            Context::EnumType => ENUM_TYPE_KEYS,
+            Context::Member => MEMBER_KEYS,
+            Context::Constraint => CONSTRAINT_KEYS,
+            Context::Other => &[],
+        }
+    }
+
+    /// Returns the context of the value of the given key.
+    fn child(self, key: &str) -> Context {
+        match (self, key) {
+            (Context::Root, "groups") => Context::Group,
+            (Context::Group, "attributes") => Context::Attribute,
+            (Context::Group, "constraints") => Context::Constraint,
+            (Context::Attribute, "type") => Context::EnumType,
+            (Context::EnumType, "members") => Context::Member,
+            _ => Context::Other,
+        }
+    }
+}
+
+/// Format the semantic convention files of a local registry.
+#[cfg(not(tarpaulin_include))]
+pub(crate) fn command(
+    logger: impl Logger + Sync + Clone,
+    args: &RegistryFmtArgs,
+) -> Result<ExitDirectives, DiagnosticMessages> {
+    logger.loading(&format!(
+        "Formatting registry `{}`",
+        args.registry.display()
+    ));
+
+    let mut diag_msgs = DiagnosticMessages::empty();
+    let mut unformatted = 0;
+    for path in semconv_files(&args.registry)? {
+        let format_failed = |error: String| Error::FormatFailed {
+            path: path.clone(),
+            error,
+        };
+        // Only the valid semantic convention files are formatted.
+        if let Err(e) = SemConvRegistry::semconv_spec_from_file(&path) {
+            diag_msgs.extend(DiagnosticMessages::new(vec![DiagnosticMessage::new(e)]));
+            continue;
+        }
+        let content = match std::fs::read_to_string(&path) {
+            Ok(content) => content,
+            Err(e) => {
+                diag_msgs.extend(format_failed(e.to_string()).into());
+                continue;
+            }
+        };
+        let formatted = match format_semconv_file(&content, args.max_width) {
+            Ok(Formatted::Content(formatted)) => formatted,
+            Ok(Formatted::Skipped(constructs)) => {
+                logger.warn(&format!(
+                    "`{}` skipped, its {} would be lost",
+                    path.display(),
+                    constructs
+                ));
+                continue;
+            }
+            Err(error) => {
+                diag_msgs.extend(format_failed(error).into());
+                continue;
+            }
+        };
+        if formatted == content {
+            continue;
+        }
+        unformatted += 1;
+        if args.check {
+            logger.log(&format!("Diff in `{}`:", path.display()));
+            print!("{}", diff_output(&content, &formatted));
+        } else if let Err(e) = std::fs::write(&path, formatted) {
+            diag_msgs.extend(format_failed(e.to_string()).into());
+        } else {
+            logger.success(&format!("Formatted `{}`", path.display()));
+        }
+    }
+
+    if !diag_msgs.is_empty() {
+        return Err(diag_msgs);
+    }
+    if args.check && unformatted > 0 {
+        return Err(Error::UnformattedFiles { count: unformatted }.into());
+    }
+    if unformatted == 0 {
+        logger.success("All the semantic convention files are formatted");
+    }
+
+    Ok(ExitDirectives {
+        exit_code: 0,
+        quiet_mode: false,
+    })
+}
+
+/// Returns the semantic convention files of a local registry, in a
+/// deterministic order. Hidden files and the files excluded by the
+/// `.weaverignore` file of the registry are skipped.
+pub(crate) fn semconv_files(registry: &Path) -> Result<Vec<PathBuf>, DiagnosticMessages> {
+    let file_filter = RegistryFileFilter::default().with_ignore_file(registry)?;
+    let mut files = vec![];
+    for entry in WalkDir::new(registry)
+        .sort_by_file_name()
+        .into_iter()
+        .filter_entry(|entry| {
+            entry.depth() == 0 || !entry.file_name().to_string_lossy().starts_with('.')
+        })
+    {
+        let entry = entry.map_err(|e| Error::FormatFailed {
+            path: registry.to_path_buf(),
+            error: e.to_string(),
+        })?;
+        let path = entry.path();
+        let is_yaml = path
+            .extension()
+            .is_some_and(|ext| ext == "yaml" || ext == "yml");
+        let relative_path = path.strip_prefix(registry).unwrap_or(path);
+        if entry.file_type().is_file()
+            && is_yaml
+            && entry.file_name() != "schema-next.yaml"
+            && entry.file_name() != REGISTRY_MANIFEST
+            && file_filter.is_included(relative_path)
+        {
+            files.push(path.to_path_buf());
+        }
+    }
+    Ok(files)
+}
+
+/// The outcome of the formatting of a semantic convention file.
+#[derive(Debug, PartialEq)]
+enum Formatted {
+    /// The formatted content of the file.
+    Content(String),
+    /// The file is not formatted as the given YAML constructs (e.g.
+    /// `comments`) would be lost.
+    Skipped(&'static str),
+}
+
+/// Formats the content of a semantic convention file: the keys are sorted in
+/// a canonical order, the attributes of each group are sorted by id, and the
+/// long briefs and notes are wrapped.
+///
+/// The comment lines at the top of the file (e.g. a license header) are
+/// preserved. The files containing other comments, anchors and aliases, or
+/// merge keys are skipped: the comments would be lost, and the aliases and
+/// merge keys replaced by copies of their content.
+fn format_semconv_file(content: &str, max_width: usize) -> Result<Formatted, String> {
+    let header_len = content
+        .lines()
+        .take_while(|line| line.trim().is_empty() || line.trim_start().starts_with('#'))
+        .map(|line| line.len() + 1)
+        .sum::<usize>()
+        .min(content.len());
+    let (header, body) = content.split_at(header_len);
+
+    let value: Value = serde_yaml::from_str(body).map_err(|e| e.to_string())?;
+    if has_comments(body, &value) {
+        return Ok(Formatted::Skipped("comments"));
+    }
+    if has_anchors(body, &value) || has_merge_keys(&value) {
+        return Ok(Formatted::Skipped("anchors, aliases, and merge keys"));
+    }
+    let value = normalize(value, Context::Root);
+
+    let mut formatted = header.to_owned();
+    match &value {
+        Value::Mapping(mapping) => write_mapping(&mut formatted, mapping, 0, false, max_width),
+        _ => return Err("The root of the file is not a mapping.".to_owned()),
+    }
+
+    let reparsed: Value = serde_yaml::from_str(&formatted[header.len()..])
+        .map_err(|e| format!("The formatted file is invalid. {}", e))?;
+    if reparsed != value {
+        return Err("The formatted file is not equivalent to the original file.".to_owned());
+    }
+    Ok(Formatted::Content(formatted))
+}
+
+/// Returns true if the YAML document contains comments, i.e. if removing a
+/// comment-like part of a line leaves the document unchanged.
+fn has_comments(body: &str, value: &Value) -> bool {
+    let lines: Vec<_> = body.lines().collect();
+    lines.iter().enumerate().any(|(index, line)| {
+        let uncommented = if line.trim_start().starts_with('#') {
+            ""
+        } else if let Some(position) = line.find(" #") {
+            &line[..position]
+        } else {
+            return false;
+        };
+        let mut candidate = lines.clone();
+        candidate[index] = uncommented;
+        serde_yaml::from_str::<Value>(&candidate.join("\n")).is_ok_and(|v| &v == value)
+    })
+}
+
+/// Returns true if the YAML document defines anchors, which aliases can refer
+/// to, i.e. if removing an anchor-like part of a line (`&name` at the start of
+/// a node) leaves the document unchanged or makes an alias refer to an unknown
+/// anchor.
+fn has_anchors(body: &str, value: &Value) -> bool {
+    let lines: Vec<_> = body.lines().collect();
+    lines.iter().enumerate().any(|(index, line)| {
+        line.char_indices()
+            .filter(|&(position, c)| {
+                c == '&'
+                    && line[..position]
+                        .chars()
+                        .last()
+                        .map_or(true, |previous| " -[{,".contains(previous))
+                    && line[position + 1..]
+                        .chars()
+                        .next()
+                        .is_some_and(|next| !next.is_whitespace() && !",[]{}".contains(next))
+            })
+            .any(|(position, _)| {
+                let end = line[position..]
+                    .find(|c: char| c.is_whitespace() || ",[]{}".contains(c))
+                    .map_or(line.len(), |end| position + end);
+                // The anchor is replaced by spaces to keep the indentation of
+                // the following content.
+                let unanchored = format!(
+                    "{}{}{}",
+                    &line[..position],
+                    " ".repeat(end - position),
+                    &line[end..]
+                );
+                let mut candidate = lines.clone();
+                candidate[index] = &unanchored;
+                match serde_yaml::from_str::<Value>(&candidate.join("\n")) {
+                    Ok(candidate) => &candidate == value,
+                    Err(error) => error.to_string().contains("unknown anchor"),
+                }
+            })
+    })
+}
+
+/// Returns true if a mapping of the YAML document has a merge key (`<<`),
+/// which is not expanded when the document is parsed.
+fn has_merge_keys(value: &Value) -> bool {
+    match value {
+        Value::Mapping(mapping) => mapping
+            .iter()
+            .any(|(key, value)| key.as_str() == Some("<<") || has_merge_keys(value)),
+        Value::Sequence(items) => items.iter().any(has_merge_keys),
+        _ => false,
+    }
+}
+
+/// Sorts the keys of the mappings in their canonical order and the attributes
+/// of the groups by id.
+fn normalize(value: Value, context: Context) -> Value {
+    match value {
+        Value::Mapping(mapping) => {
+            let keys = context.keys();
+            let mut entries: Vec<_> = mapping
+                .into_iter()
+                .map(|(key, value)| {
+                    let name = key.as_str().unwrap_or_default().to_owned();
+                    let value = normalize(value, context.child(&name));
+                    (key, value)
+                })
+                .collect();
+            // Stable sort, the unknown keys keep their order after the known ones.
+            entries.sort_by_key(|(key, _)| {
+                key.as_str()
+                    .and_then(|name| keys.iter().position(|known| *known == name))
+                    .unwrap_or(keys.len())
+            });
+            Value::Mapping(entries.into_iter().collect())
+        }
+        Value::Sequence(items) => {
+            let mut items: Vec<_> = items
+                .into_iter()
+                .map(|item| normalize(item, context))
+                .collect();
+            if context == Context::Attribute {
+                items.sort_by_key(|item| {
+                    item.get("id")
+                        .or_else(|| item.get("ref"))
+                        .and_then(Value::as_str)
+                        .map(str::to_owned)
+                });
+            }
+            Value::Sequence(items)
+        }
+        value => value,
+    }
+}
+
+/// Writes a block mapping. If `inline_first` is true, the first entry follows
+/// a sequence indicator already written on the current line.
+fn write_mapping(
+    out: &mut String,
+    mapping: &Mapping,
+    indent: usize,
+    inline_first: bool,
+    max_width: usize,
+) {
+    for (index, (key, value)) in mapping.iter().enumerate() {
+        if index > 0 || !inline_first {
+            out.push_str(&" ".repeat(indent));
+        }
+        let key_name = key.as_str().unwrap_or_default();
+        let key = scalar(key);
+        out.push_str(&key);
+        out.push(':');
+        match value {
+            Value::Mapping(mapping) if mapping.is_empty() => out.push_str(" {}\n"),
+            Value::Mapping(mapping) => {
+                out.push('\n');
+                write_mapping(out, mapping, indent + 2, false, max_width);
+            }
+            Value::Sequence(items) if items.is_empty() => out.push_str(" []\n"),
+            Value::Sequence(items) => match flow_sequence(items) {
+                Some(flow) if indent + key.len() + 2 + flow.len() <= max_width => {
+                    out.push(' ');
+                    out.push_str(&flow);
+                    out.push('\n');
+                }
+                _ => {
+                    out.push('\n');
+                    write_sequence(out, items, indent + 2, max_width);
+                }
+            },
+            Value::String(string) => {
+                let width = max_width.saturating_sub(indent + 2);
+                let wrap = WRAPPED_KEYS.contains(&key_name)
+                    && indent + key.len() + 2 + scalar(value).len() > max_width;
+                out.push(' ');
+                out.push_str(&string_scalar(string, indent + 2, wrap, width));
+                out.push('\n');
+            }
+            value => {
+                out.push(' ');
+                out.push_str(&scalar(value));
+                out.push('\n');
+            }
+        }
+    }
+}
+
+/// Writes a block sequence.
+fn write_sequence(out: &mut String, items: &[Value], indent: usize, max_width: usize) {
+    for item in items {
+        out.push_str(&" ".repeat(indent));
+        out.push('-');
+        match item {
+            Value::Mapping(mapping) if !mapping.is_empty() => {
+                out.push(' ');
+                write_mapping(out, mapping, indent + 2, true, max_width);
+            }
+            Value::Sequence(items) if !items.is_empty() => {
+                out.push('\n');
+                write_sequence(out, items, indent + 2, max_width);
+            }
+            Value::String(string) => {
+                out.push(' ');
+                out.push_str(&string_scalar(string, indent + 2, false, max_width));
+                out.push('\n');
+            }
+            item => {
+                out.push(' ');
+                out.push_str(&scalar(item));
+                out.push('\n');
+            }
+        }
+    }
+}
+
+/// Returns the flow representation of a sequence of scalars (e.g. examples),
+/// or `None` if the sequence contains collections or multi-line strings.
+fn flow_sequence(items: &[Value]) -> Option<String> {
+    let items = items
+        .iter()
+        .map(|item| match item {
+            Value::String(string) if string.contains('\n') => None,
+            // The flow indicators must be quoted.
+            Value::String(string) if string.contains([',', '[', ']', '{', '}', '#', ':']) => {
+                Some(double_quoted(string))
+            }
+            Value::Mapping(_) | Value::Sequence(_) | Value::Tagged(_) => None,
+            item => Some(scalar(item)),
+        })
+        .collect::<Option<Vec<_>>>()?;
+    Some(format!("[{}]", items.join(", ")))
+}
+
+/// Returns the single-line representation of a scalar, quoted only if needed.
+fn scalar(value: &Value) -> String {
+    match serde_yaml::to_string(value) {
+        Ok(yaml) if !yaml.trim_end_matches('\n').contains('\n') => {
+            yaml.trim_end_matches('\n').to_owned()
+        }
+        _ => match value {
+            Value::String(string) => double_quoted(string),
+            _ => "null".to_owned(),
+        },
+    }
+}
+
+/// Returns the double-quoted representation of a string (a JSON string is a
+/// valid YAML double-quoted scalar).
+fn double_quoted(string: &str) -> String {
+    serde_json::to_string(string).unwrap_or_else(|_| format!("{:?}", string))
+}
+
+/// Returns the representation of a string: a literal block for multi-line
+/// strings, a folded block for the long strings to wrap, and a single-line
+/// scalar otherwise. Each candidate representation is only used if it parses
+/// back to the same string.
+fn string_scalar(string: &str, indent: usize, wrap: bool, width: usize) -> String {
+    let candidates = [
+        if string.contains('\n') {
+            literal_block(string)
+        } else {
+            None
+        },
+        if wrap {
+            folded_block(string, width)
+        } else {
+            None
+        },
+    ];
+    candidates
+        .into_iter()
+        .flatten()
+        .find(|lines| {
+            let block = lines.join("\n  ");
+            serde_yaml::from_str::<Value>(&format!("k: {}\n", block))
+                .is_ok_and(|value| value.get("k").and_then(Value::as_str) == Some(string))
+        })
+        .map(|lines| {
+            lines
+                .iter()
+                .enumerate()
+                .map(|(index, line)| {
+                    if index == 0 || line.is_empty() {
+                        line.clone()
+                    } else {
+                        format!("{}{}", " ".repeat(indent), line)
+                    }
+                })
+                .collect::<Vec<_>>()
+                .join("\n")
+        })
+        .unwrap_or_else(|| scalar(&Value::String(string.to_owned())))
+}
+
+/// Returns the lines of a literal block representing a multi-line string
+/// (the indicator, then the unindented content lines).
+fn literal_block(string: &str) -> Option<Vec<String>> {
+    if string.starts_with([' ', '\t', '\n']) || string.chars().any(|c| c.is_control() && c != '\n')
+    {
+        return None;
+    }
+    let content = string.trim_end_matches('\n');
+    let indicator = match string.len() - content.len() {
+        0 => "|-",
+        1 => "|",
+        _ => "|+",
+    };
+    let mut lines = vec![indicator.to_owned()];
+    lines.extend(content.split('\n').map(str::to_owned));
+    // The trailing line breaks kept by the `|+` indicator.
+    lines.extend((1..string.len() - content.len()).map(|_| String::new()));
+    Some(lines)
+}
+
+/// Returns the lines of a folded block wrapping a long single-line string at
+/// the given width.
+fn folded_block(string: &str, width: usize) -> Option<Vec<String>> {
+    if string != string.trim() || string.contains("  ") || string.chars().any(char::is_control) {
+        return None;
+    }
+    let mut lines = vec![">-".to_owned()];
+    let mut line = String::new();
+    for word in string.split(' ') {
+        if !line.is_empty() && line.len() + 1 + word.len() > width {
+            lines.push(std::mem::take(&mut line));
+        }
+        if !line.is_empty() {
+            line.push(' ');
+        }
+        line.push_str(word);
+    }
+    lines.push(line);
+    Some(lines)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::registry::fmt::{format_semconv_file, Formatted};
+
+    #[test]
+    fn test_format_semconv_file() {
+        let content = r#"# Copyright header
+
+groups:
+    - type: attribute_group
+      id: registry.http
+      prefix: http
+      brief: 'This document defines semantic convention attributes in the HTTP namespace, including the request and response attributes.'
+      attributes:
+        - id: route
+          type: string
+          note: |
+            MUST NOT be populated when this is not supported by the HTTP server framework.
+
+            SHOULD include the application root if there is one.
+          examples: ['/users/:userID?', "{controller}/{action}/{id?}"]
+          stability: stable
+          requirement_level: recommended
+        - id: request.method
+          brief: "HTTP request method."
+          stability: stable
+          type:
+            members:
+              - id: get
+                value: GET
+                stability: stable
+            allow_custom_values: true
+          examples: [GET, POST, HEAD]
+"#;
+        let expected = r#"# Copyright header
+
+groups:
+  - id: registry.http
+    type: attribute_group
+    prefix: http
+    brief: >-
+      This document defines semantic convention attributes in the HTTP namespace,
+      including the request and response attributes.
+    attributes:
+      - id: request.method
+        type:
+          allow_custom_values: true
+          members:
+            - id: get
+              value: GET
+              stability: stable
+        stability: stable
+        brief: HTTP request method.
+        examples: [GET, POST, HEAD]
+      - id: route
+        type: string
+        stability: stable
+        requirement_level: recommended
+        note: |
+          MUST NOT be populated when this is not supported by the HTTP server framework.
+
+          SHOULD include the application root if there is one.
+        examples: ["/users/:userID?", "{controller}/{action}/{id?}"]
+"#;
+        let formatted = format_semconv_file(content, 90).expect("Formatting failed");
+        assert_eq!(formatted, Formatted::Content(expected.to_owned()));
+
+        // The formatting is idempotent.
+        let reformatted = format_semconv_file(expected, 90).expect("Formatting failed");
+        assert_eq!(reformatted, formatted);
+
+        // The comments (other than the header) can't be preserved.
+        let commented = "groups:\n  # The HTTP attributes\n  - id: registry.http\n    type: attribute_group\n    brief: HTTP attributes. # Short\n";
+        assert_eq!(
+            format_semconv_file(commented, 90).expect("Formatting failed"),
+            Formatted::Skipped("comments")
+        );
+        // A `#` in a string is not a comment.
+        let hash = "groups:\n  - id: registry.http\n    type: attribute_group\n    brief: 'HTTP #1'\n    note: |\n      # Title\n";
+        assert!(matches!(
+            format_semconv_file(hash, 90).expect("Formatting failed"),
+            Formatted::Content(_)
+        ));
+
+        // The anchors, aliases, and merge keys can't be preserved.
+        let aliased = "groups:\n  - id: registry.http\n    type: attribute_group\n    brief: &b HTTP attributes.\n  - id: registry.url\n    type: attribute_group\n    brief: *b\n";
+        let merged = "groups:\n  - &base\n    id: registry.http\n    type: attribute_group\n    brief: HTTP attributes.\n  - <<: *base\n    id: registry.url\n";
+        for content in [aliased, merged] {
+            assert_eq!(
+                format_semconv_file(content, 90).expect("Formatting failed"),
+                Formatted::Skipped("anchors, aliases, and merge keys")
+            );
+        }
+        // A `&` or a `*` in a string is not an anchor or an alias.
+        let ampersand = "groups:\n  - id: registry.http\n    type: attribute_group\n    brief: R&D &more\n    note: |\n      &not an anchor\n      * a list item\n";
+        assert!(matches!(
+            format_semconv_file(ampersand, 90).expect("Formatting failed"),
+            Formatted::Content(_)
+        ));
+    }
+}
@@ -2,31 +2,41 @@
 
 //! Generate artifacts for a semantic convention registry.
 
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use clap::Args;
+use include_dir::{include_dir, Dir};
 use serde_yaml::Value;
+use tempdir::TempDir;
 
 use weaver_cache::Cache;
 use weaver_common::diagnostic::DiagnosticMessages;
 use weaver_common::Logger;
+use weaver_diff::{diff_dir, unified_diff_dir};
 use weaver_forge::config::Params;
-use weaver_forge::file_loader::FileSystemFileLoader;
+use weaver_forge::file_loader::{EmbeddedFileLoader, FileSystemFileLoader};
 use weaver_forge::registry::ResolvedRegistry;
 use weaver_forge::{OutputDirective, TemplateEngine};
 use weaver_semconv::registry::SemConvRegistry;
 
+use crate::registry::binary::load_resolved_registry;
+use crate::registry::watch::{watch, WatchedPaths};
 use crate::registry::{Error, RegistryArgs};
 use crate::util::{
-    check_policies, init_policy_engine, load_semconv_specs, resolve_semconv_specs,
-    semconv_registry_path_from,
+    check_policies, check_resolved_policies, init_policy_engine, load_semconv_specs,
+    resolve_semconv_specs,
 };
 use crate::{DiagnosticArgs, ExitDirectives};
 
+/// Embedded built-in registry targets (e.g. `java`).
+pub(crate) static DEFAULT_REGISTRY_TEMPLATES: Dir<'_> = include_dir!("default_templates/registry");
+
 /// Parameters for the `registry generate` sub-command
 #[derive(Debug, Args)]
 pub struct RegistryGenerateArgs {
     /// Target to generate the artifacts for.
+    /// The built-in targets (the directories of `default_templates/registry`) are used unless the
+    /// templates directory contains a directory with the same name.
     pub target: String,
 
     /// Path to the directory where the generated artifacts will be saved.
@@ -50,7 +60,7 @@ pub struct RegistryGenerateArgs {
 
     /// Parameters to specify the semantic convention registry
     #[command(flatten)]
-    registry: RegistryArgs,
+    pub registry: RegistryArgs,
 
     /// Optional list of policy files to check against the files of the semantic
     /// convention registry.
@@ -61,13 +71,36 @@ pub struct RegistryGenerateArgs {
     #[arg(long, default_value = "false")]
     pub skip_policies: bool,
 
+    /// Regenerate the artifacts into a temporary directory and compare them with the
+    /// content of the output directory, without modifying it.
+    /// The command fails if the generated artifacts are stale.
+    #[arg(long, default_value = "false")]
+    pub verify: bool,
+
+    /// Render the artifacts without writing them and print a unified diff of the
+    /// changes they would make to the content of the output directory.
+    #[arg(long, default_value = "false", conflicts_with = "verify")]
+    pub dry_run: bool,
+
+    /// Regenerate the artifacts each time a file of the registry, a template,
+    /// a policy, or the params file changes (the registry must be a local
+    /// directory). Only the artifacts whose content changed are rewritten.
+    #[arg(long, default_value = "false", conflicts_with_all = ["verify", "dry_run"])]
+    pub watch: bool,
+
+    /// Path to a resolved registry written by `weaver registry resolve --format binary`.
+    /// The registry is not loaded nor resolved again, and only the policies
+    /// applying to the resolved registry are checked.
+    #[arg(long, conflicts_with = "watch")]
+    pub resolved_registry: Option<PathBuf>,
+
     /// Parameters to specify the diagnostic format.
     #[command(flatten)]
     pub diagnostic: DiagnosticArgs,
 }
 
 /// Utility function to parse key-value pairs from the command line.
-fn parse_key_val(s: &str) -> Result<(String, Value), Error> {
+pub(crate) fn parse_key_val(s: &str) -> Result<(String, Value), Error> {
     let pos = s.find('=').ok_or_else(|| Error::InvalidParam {
         param: s.to_owned(),
         error: "A valid parameter definition is `--param <name>=<yaml-value>`".to_owned(),
@@ -82,41 +115,121 @@ fn parse_key_val(s: &str) -> Result<(String, Value), Error> {
     Ok((s[..pos].to_string(), value))
 }
 
-/// Generate artifacts from a semantic convention registry.
+/// Generate artifacts from a semantic convention registry, once or each time
+/// the registry or the templates change.
 pub(crate) fn command(
     logger: impl Logger + Sync + Clone,
     cache: &Cache,
     args: &RegistryGenerateArgs,
+    debug: bool,
 ) -> Result<ExitDirectives, DiagnosticMessages> {
-    logger.loading(&format!(
-        "Generating artifacts for the registry `{}`",
-        args.registry.registry
-    ));
-
-    let params = generate_params(args)?;
-    let registry_id = "default";
-    let registry_path =
-        semconv_registry_path_from(&args.registry.registry, &args.registry.registry_git_sub_dir);
+    if args.watch {
+        let mut watched = WatchedPaths::registries(&args.registry.registry)?
+            .watch(&args.templates)
+            .ignore(&args.output);
+        if let Some(params) = &args.params {
+            watched = watched.watch(params);
+        }
+        for policy in &args.policies {
+            watched = watched.watch(policy);
+        }
+        return watch(logger.clone(), &watched, &args.diagnostic, || {
+            generate(logger.clone(), cache, args, debug)
+        });
+    }
+    generate(logger, cache, args, debug)
+}
 
-    // Load the semantic convention registry into a local cache.
-    let semconv_specs = load_semconv_specs(&registry_path, cache, logger.clone())?;
+/// Generate artifacts from a semantic convention registry.
+fn generate(
+    logger: impl Logger + Sync + Clone,
+    cache: &Cache,
+    args: &RegistryGenerateArgs,
+    debug: bool,
+) -> Result<ExitDirectives, DiagnosticMessages> {
+    let params = generate_params(args.params.as_deref(), args.param.as_deref())?;
+    let template_registry = if let Some(path) = &args.resolved_registry {
+        logger.loading(&format!(
+            "Generating artifacts for the resolved registry `{}`",
+            path.display()
+        ));
+        let template_registry = load_resolved_registry(path)?;
+        if !args.skip_policies {
+            let mut policy_engine = init_policy_engine(&[], cache, &args.policies, false)?;
+            check_resolved_policies(&mut policy_engine, &[], &template_registry, logger.clone())?;
+        }
+        template_registry
+    } else {
+        logger.loading(&format!(
+            "Generating artifacts for the registry `{}`",
+            args.registry.registries()
+        ));
+        resolve_registry(logger.clone(), cache, args)?
+    };
+    let mut engine = template_engine(&args.templates, &args.target, params, debug)?;
 
-    if !args.skip_policies {
-        let policy_engine = init_policy_engine(&registry_path, cache, &args.policies, false)?;
-        check_policies(&policy_engine, &semconv_specs, logger.clone())?;
+    if args.verify {
+        let verify_dir = TempDir::new("weaver-verify").map_err(|e| Error::VerificationFailed {
+            output: args.output.clone(),
+            error: e.to_string(),
+        })?;
+        engine.generate(
+            logger.clone(),
+            &template_registry,
+            verify_dir.path(),
+            &OutputDirective::File,
+        )?;
+        let up_to_date = diff_dir(args.output.as_path(), verify_dir.path()).map_err(|e| {
+            Error::VerificationFailed {
+                output: args.output.clone(),
+                error: e.to_string(),
+            }
+        })?;
+        if !up_to_date {
+            return Err(Error::StaleGeneratedArtifacts {
+                output: args.output.clone(),
+            }
+            .into());
+        }
+        logger.success("Generated artifacts are up to date");
+        return Ok(ExitDirectives {
+            exit_code: 0,
+            quiet_mode: false,
+        });
     }
 
-    let mut registry = SemConvRegistry::from_semconv_specs(registry_id, semconv_specs);
-    let schema = resolve_semconv_specs(&mut registry, logger.clone())?;
-    let loader = FileSystemFileLoader::try_new(args.templates.join("registry"), &args.target)?;
-    let engine = TemplateEngine::try_new(loader, params)?;
+    if args.dry_run {
+        let dry_run_dir = TempDir::new("weaver-dry-run").map_err(|e| Error::DryRunFailed {
+            output: args.output.clone(),
+            error: e.to_string(),
+        })?;
+        engine.generate(
+            logger.clone(),
+            &template_registry,
+            dry_run_dir.path(),
+            &OutputDirective::File,
+        )?;
+        let diffs = unified_diff_dir(args.output.as_path(), dry_run_dir.path()).map_err(|e| {
+            Error::DryRunFailed {
+                output: args.output.clone(),
+                error: e.to_string(),
+            }
+        })?;
+        for (_, diff) in &diffs {
+            print!("{}", diff);
+        }
+        logger.success(&format!(
+            "{} file(s) would be changed in {:?} (dry run)",
+            diffs.len(),
+            args.output
+        ));
+        return Ok(ExitDirectives {
+            exit_code: 0,
+            quiet_mode: false,
+        });
+    }
 
-    let template_registry = ResolvedRegistry::try_from_resolved_registry(
-        schema
-            .registry(registry_id)
-            .expect("Failed to get the registry from the resolved schema"),
-        schema.catalog(),
-    )?;
+    engine.set_generation_cache(cache.generation_cache_file(&args.output));
 
     engine.generate(
         logger.clone(),
@@ -132,19 +245,94 @@ pub(crate) fn command(
     })
 }
 
+/// Load, check, and resolve the semantic convention registry.
+fn resolve_registry(
+    logger: impl Logger + Sync + Clone,
+    cache: &Cache,
+    args: &RegistryGenerateArgs,
+) -> Result<ResolvedRegistry, DiagnosticMessages> {
+    let registry_id = "default";
+    let registry_paths = args.registry.registry_paths();
+
+    // Load the semantic convention registry into a local cache.
+    let semconv_specs = load_semconv_specs(
+        &registry_paths,
+        &args.registry.file_filter()?,
+        cache,
+        logger.clone(),
+    )?;
+
+    let mut policy_engine = if !args.skip_policies {
+        let policy_engine = init_policy_engine(&registry_paths, cache, &args.policies, false)?;
+        check_policies(&policy_engine, &semconv_specs, logger.clone())?;
+        Some(policy_engine)
+    } else {
+        None
+    };
+
+    let mut registry = SemConvRegistry::from_semconv_specs(registry_id, semconv_specs);
+    let schema = resolve_semconv_specs(&mut registry, logger.clone())?;
+
+    let template_registry = ResolvedRegistry::try_from_resolved_registry(
+        schema
+            .registry(registry_id)
+            .expect("Failed to get the registry from the resolved schema"),
+        schema.catalog(),
+    )?;
+
+    if let Some(policy_engine) = policy_engine.as_mut() {
+        check_resolved_policies(policy_engine, &registry_paths, &template_registry, logger)?;
+    }
+    Ok(template_registry)
+}
+
+/// Returns the names of the built-in targets.
+pub(crate) fn builtin_targets() -> Vec<&'static str> {
+    DEFAULT_REGISTRY_TEMPLATES
+        .dirs()
+        .filter_map(|dir| dir.path().file_name()?.to_str())
+        .collect()
+}
+
+/// Create the template engine for the given target.
+/// Built-in targets are loaded from the binary unless the `<templates>/registry/<target>`
+/// directory exists, in which case the local templates are used.
+/// The debug mode of the engine is enabled with the `--debug` flag.
+pub(crate) fn template_engine(
+    templates: &Path,
+    target: &str,
+    params: Params,
+    debug: bool,
+) -> Result<TemplateEngine, weaver_forge::error::Error> {
+    let templates_dir = templates.join("registry");
+    let mut engine = if DEFAULT_REGISTRY_TEMPLATES.get_dir(target).is_some() {
+        let loader =
+            EmbeddedFileLoader::try_new(&DEFAULT_REGISTRY_TEMPLATES, templates_dir, target)?;
+        TemplateEngine::try_new(loader, params)?
+    } else {
+        let loader = FileSystemFileLoader::try_new(templates_dir, target)?;
+        TemplateEngine::try_new(loader, params)?
+    };
+    engine.set_debug(debug);
+    Ok(engine)
+}
+
 /// Generate the parameters to pass to the templates.
 /// The `--params` argument (if provided) is used to load the parameters from a YAML file.
 /// Then the key-value pairs from the `--param` arguments are added to the parameters.
 /// So `--param key=value` will override the value of `key` if it exists in the YAML file.
-fn generate_params(args: &RegistryGenerateArgs) -> Result<Params, Error> {
+pub(crate) fn generate_params(
+    params_file: Option<&Path>,
+    param: Option<&[(String, Value)]>,
+) -> Result<Params, Error> {
     // Load the parameters from the YAML file or if not provided, use the default parameters.
-    let mut params = if let Some(params_file) = &args.params {
+    let mut params = if let Some(params_file) = params_file {
         let file = std::fs::File::open(params_file).map_err(|e| Error::InvalidParams {
-            params_file: params_file.clone(),
+            params_file: params_file.to_path_buf(),
             error: e.to_string(),
         })?;
         serde_yaml::from_reader(file).map_err(|e| Error::InvalidParams {
-            params_file: params_file.clone(),
+            params_file: params_file.to_path_buf(),
             error: e.to_string(),
         })?
     } else {
@@ -152,7 +340,7 @@ fn generate_params(args: &RegistryGenerateArgs) -> Result<Params, Error> {
     };
 
     // Override the parameters with the key-value pairs from the command line.
-    if let Some(param) = &args.param {
+    if let Some(param) = param {
         for (name, value) in param {
             _ = params.params.insert(name.clone(), value.clone());
         }
@@ -163,8 +351,9 @@ fn generate_params(args: &RegistryGenerateArgs) -> Result<Params, Error> {
 
 #[cfg(test)]
 mod tests {
-    use std::path::PathBuf;
+    use std::path::{Path, PathBuf};
 
+    use serde::Deserialize;
     use tempdir::TempDir;
 
     use weaver_common::TestLogger;
@@ -174,6 +363,50 @@ mod tests {
     use crate::registry::{RegistryArgs, RegistryCommand, RegistryPath, RegistrySubCommand};
     use crate::run_command;
 
+    /// Builds the command line generating the artifacts of a built-in target
+    /// for the test registry, with the given template parameters.
+    fn generate_cli(target: &str, output: &Path, params: &[(&str, &str)]) -> Cli {
+        Cli {
+            debug: 0,
+            quiet: false,
+            cache: Default::default(),
+            command: Some(Commands::Registry(RegistryCommand {
+                command: RegistrySubCommand::Generate(RegistryGenerateArgs {
+                    target: target.to_owned(),
+                    output: output.to_path_buf(),
+                    templates: PathBuf::from("does-not-exist/"),
+                    param: (!params.is_empty()).then(|| {
+                        params
+                            .iter()
+                            .map(|(name, value)| {
+                                (
+                                    (*name).to_owned(),
+                                    serde_yaml::Value::String((*value).to_owned()),
+                                )
+                            })
+                            .collect()
+                    }),
+                    params: None,
+                    registry: RegistryArgs {
+                        registry: vec![RegistryPath::Local(
+                            "crates/weaver_codegen_test/semconv_registry/".to_owned(),
+                        )],
+                        registry_git_sub_dir: None,
+                        include: vec![],
+                        exclude: vec![],
+                    },
+                    policies: vec![],
+                    skip_policies: true,
+                    verify: false,
+                    dry_run: false,
+                    watch: false,
+                    resolved_registry: None,
+                    diagnostic: Default::default(),
+                }),
+            })),
+        }
+    }
+
     #[test]
     fn test_registry_generate() {
         let logger = TestLogger::new();
@@ -183,6 +416,7 @@ mod tests {
         let cli = Cli {
             debug: 0,
             quiet: false,
+            cache: Default::default(),
             command: Some(Commands::Registry(RegistryCommand {
                 command: RegistrySubCommand::Generate(RegistryGenerateArgs {
                     target: "rust".to_owned(),
@@ -191,13 +425,19 @@ mod tests {
                     param: None,
                     params: None,
                     registry: RegistryArgs {
-                        registry: RegistryPath::Local(
+                        registry: vec![RegistryPath::Local(
                             "crates/weaver_codegen_test/semconv_registry/".to_owned(),
-                        ),
+                        )],
                         registry_git_sub_dir: None,
+                        include: vec![],
+                        exclude: vec![],
                     },
                     policies: vec![],
                     skip_policies: true,
+                    verify: false,
+                    dry_run: false,
+                    watch: false,
+                    resolved_registry: None,
                     diagnostic: Default::default(),
                 }),
             })),
@@ -212,7 +452,7 @@ mod tests {
         let rust_files: std::collections::HashSet<_> = walkdir::WalkDir::new(&temp_output)
             .into_iter()
             .filter_map(|e| e.ok())
-            .filter(|e| e.path().extension().map_or(false, |ext| ext == "rs"))
+            .filter(|e| e.path().extension().is_some_and(|ext| ext == "rs"))
             .map(|e| {
                 e.path()
                     .strip_prefix(&temp_output)
@@ -248,10 +488,63 @@ mod tests {
 
         assert_eq!(rust_files, expected_rust_files);
 
+        // The output directory is up to date, so the verification should succeed.
+        let check_cli = |output: PathBuf, verify: bool| Cli {
+            debug: 0,
+            quiet: false,
+            cache: Default::default(),
+            command: Some(Commands::Registry(RegistryCommand {
+                command: RegistrySubCommand::Generate(RegistryGenerateArgs {
+                    target: "rust".to_owned(),
+                    output,
+                    templates: PathBuf::from("crates/weaver_codegen_test/templates/"),
+                    param: None,
+                    params: None,
+                    registry: RegistryArgs {
+                        registry: vec![RegistryPath::Local(
+                            "crates/weaver_codegen_test/semconv_registry/".to_owned(),
+                        )],
+                        registry_git_sub_dir: None,
+                        include: vec![],
+                        exclude: vec![],
+                    },
+                    policies: vec![],
+                    skip_policies: true,
+                    verify,
+                    dry_run: !verify,
+                    watch: false,
+                    resolved_registry: None,
+                    diagnostic: Default::default(),
+                }),
+            })),
+        };
+        let exit_directive = run_command(&check_cli(temp_output.clone(), true), logger.clone());
+        assert_eq!(exit_directive.exit_code, 0);
+
+        // A stale generated file should make the verification fail without
+        // modifying the output directory.
+        let stale_file = temp_output.join("attributes").join("client.rs");
+        std::fs::write(&stale_file, "// stale").expect("Failed to write stale file");
+        let exit_directive = run_command(&check_cli(temp_output.clone(), true), logger.clone());
+        assert_eq!(exit_directive.exit_code, 1);
+        assert_eq!(
+            std::fs::read_to_string(&stale_file).expect("Failed to read stale file"),
+            "// stale"
+        );
+
+        // A dry run should succeed without modifying the output directory.
+        let exit_directive = run_command(&check_cli(temp_output.clone(), false), logger.clone());
+        assert_eq!(exit_directive.exit_code, 0);
+        assert_eq!(
+            std::fs::read_to_string(&stale_file).expect("Failed to read stale file"),
+            "// stale"
+        );
+
         // Now, let's run the command again with the policy checks enabled.
         let cli = Cli {
             debug: 0,
             quiet: false,
+            cache: Default::default(),
             command: Some(Commands::Registry(RegistryCommand {
                 command: RegistrySubCommand::Generate(RegistryGenerateArgs {
                     target: "rust".to_owned(),
@@ -260,13 +553,19 @@ mod tests {
                     param: None,
                     params: None,
                     registry: RegistryArgs {
-                        registry: RegistryPath::Local(
+                        registry: vec![RegistryPath::Local(
                             "crates/weaver_codegen_test/semconv_registry/".to_owned(),
-                        ),
+                        )],
                         registry_git_sub_dir: None,
+                        include: vec![],
+                        exclude: vec![],
                     },
                     policies: vec![],
                     skip_policies: false,
+                    verify: false,
+                    dry_run: false,
+                    watch: false,
+                    resolved_registry: None,
                     diagnostic: Default::default(),
                 }),
             })),
@@ -276,4 +575,271 @@ mod tests {
         // The command should exit with an error code.
         assert_eq!(exit_directive.exit_code, 1);
     }
+
+    #[test]
+    fn test_registry_generate_builtin_java() {
+        let logger = TestLogger::new();
+        let temp_output = TempDir::new("output")
+            .expect("Failed to create temporary directory")
+            .into_path();
+        let cli = generate_cli("java", &temp_output, &[("package", "com.example.semconv")]);
+
+        let exit_directive = run_command(&cli, logger.clone());
+        // The command should succeed.
+        assert_eq!(exit_directive.exit_code, 0);
+
+        let package_dir = temp_output.join("com/example/semconv");
+        let http_attributes = std::fs::read_to_string(package_dir.join("HttpAttributes.java"))
+            .expect("Failed to read HttpAttributes.java");
+        assert!(http_attributes.contains("package com.example.semconv;"));
+        assert!(http_attributes.contains("public final class HttpAttributes {"));
+        assert!(http_attributes.contains(
+            "public static final AttributeKey<String> HTTP_REQUEST_METHOD =\n      stringKey(\"http.request.method\");"
+        ));
+        assert!(http_attributes.contains("public static final class HttpRequestMethodValues {"));
+
+        let http_metrics = std::fs::read_to_string(package_dir.join("HttpMetrics.java"))
+            .expect("Failed to read HttpMetrics.java");
+        assert!(http_metrics.contains(
+            "public static final String HTTP_SERVER_REQUEST_DURATION = \"http.server.request.duration\";"
+        ));
+    }
+
+    #[test]
+    fn test_registry_generate_builtin_markdown() {
+        let logger = TestLogger::new();
+        let temp_output = TempDir::new("output")
+            .expect("Failed to create temporary directory")
+            .into_path();
+        let cli = generate_cli("markdown", &temp_output, &[]);
+
+        let exit_directive = run_command(&cli, logger.clone());
+        // The command should succeed.
+        assert_eq!(exit_directive.exit_code, 0);
+
+        let http_registry =
+            std::fs::read_to_string(temp_output.join("attributes-registry/http.md"))
+                .expect("Failed to read attributes-registry/http.md");
+        assert!(http_registry.contains("| Attribute  | Type | Description  | Examples  | [Requirement Level](https://opentelemetry.io/docs/specs/semconv/general/attribute-requirement-level/) | Stability |"));
+        assert!(http_registry.contains("| [`http.request.method`](/docs/attributes-registry/http.md) | string | HTTP request method. [2] | `GET`; `POST`; `HEAD` | `Recommended` | ![Stable](https://img.shields.io/badge/-stable-lightgreen) |"));
+
+        let metric = std::fs::read_to_string(
+            temp_output.join("metric/metric-http-server-request-duration.md"),
+        )
+        .expect("Failed to read metric/metric-http-server-request-duration.md");
+        assert!(metric.contains("| `http.server.request.duration` | Histogram | `s` |"));
+        assert!(metric.contains("`Conditionally Required` If request has ended with an error."));
+    }
+
+    #[test]
+    fn test_registry_generate_builtin_sql() {
+        let logger = TestLogger::new();
+        let temp_output = TempDir::new("output")
+            .expect("Failed to create temporary directory")
+            .into_path();
+        let cli = generate_cli("sql", &temp_output, &[("dialect", "clickhouse")]);
+
+        let exit_directive = run_command(&cli, logger.clone());
+        // The command should succeed.
+        assert_eq!(exit_directive.exit_code, 0);
+
+        let tables = std::fs::read_to_string(temp_output.join("tables.sql"))
+            .expect("Failed to read tables.sql");
+        assert!(tables.contains("CREATE TABLE IF NOT EXISTS metric_http (\n  timestamp DateTime64(9) NOT NULL,\n  error_type Nullable(String) COMMENT 'Describes a class of error the operation ended with.',"));
+        assert!(tables.contains("  server_port Nullable(Int64) COMMENT"));
+        assert!(tables.contains("A system\\'s CPU SHOULD be characterized"));
+        assert!(tables.contains(")\nENGINE = MergeTree\nORDER BY timestamp;"));
+    }
+
+    #[test]
+    fn test_registry_generate_builtin_grafana() {
+        let logger = TestLogger::new();
+        let temp_output = TempDir::new("output")
+            .expect("Failed to create temporary directory")
+            .into_path();
+        let cli = generate_cli("grafana", &temp_output, &[]);
+
+        let exit_directive = run_command(&cli, logger.clone());
+        // The command should succeed.
+        assert_eq!(exit_directive.exit_code, 0);
+
+        let dashboard: serde_json::Value = serde_json::from_str(
+            &std::fs::read_to_string(temp_output.join("http.json"))
+                .expect("Failed to read http.json"),
+        )
+        .expect("Invalid dashboard");
+        assert_eq!(dashboard["uid"], "semconv-http");
+        let variables: Vec<&str> = dashboard["templating"]["list"]
+            .as_array()
+            .expect("Missing variables")
+            .iter()
+            .filter_map(|v| v["name"].as_str())
+            .collect();
+        assert_eq!(
+            variables,
+            vec![
+                "datasource",
+                "http_request_method",
+                "server_address",
+                "server_port",
+                "url_scheme"
+            ]
+        );
+        let panel = &dashboard["panels"][2];
+        assert_eq!(panel["title"], "http.server.request.duration");
+        assert_eq!(panel["description"], "Duration of HTTP server requests.");
+        assert_eq!(panel["fieldConfig"]["defaults"]["unit"], "s");
+        assert_eq!(
+            panel["targets"][0]["expr"],
+            "histogram_quantile(0.95, sum by (le) (rate(http_server_request_duration_seconds_bucket{http_request_method=~\"$http_request_method\", url_scheme=~\"$url_scheme\"}[$__rate_interval])))"
+        );
+
+        let dashboard: serde_json::Value = serde_json::from_str(
+            &std::fs::read_to_string(temp_output.join("system.json"))
+                .expect("Failed to read system.json"),
+        )
+        .expect("Invalid dashboard");
+        assert_eq!(
+            dashboard["panels"][0]["targets"][0]["expr"],
+            "sum(rate(system_cpu_time_seconds_total[$__rate_interval]))"
+        );
+        assert_eq!(
+            dashboard["panels"][1]["fieldConfig"]["defaults"]["unit"],
+            "percentunit"
+        );
+    }
+
+    #[test]
+    fn test_registry_generate_builtin_backstage() {
+        let logger = TestLogger::new();
+        let temp_output = TempDir::new("output")
+            .expect("Failed to create temporary directory")
+            .into_path();
+        let cli = generate_cli("backstage", &temp_output, &[("system", "telemetry")]);
+
+        let exit_directive = run_command(&cli, logger.clone());
+        // The command should succeed.
+        assert_eq!(exit_directive.exit_code, 0);
+
+        let catalog = std::fs::read_to_string(temp_output.join("catalog-info.yaml"))
+            .expect("Failed to read catalog-info.yaml");
+        let entities: Vec<serde_yaml::Value> = serde_yaml::Deserializer::from_str(&catalog)
+            .map(|document| serde_yaml::Value::deserialize(document).expect("Invalid entity"))
+            .collect();
+        let entity = |kind: &str, name: &str| {
+            entities
+                .iter()
+                .find(|e| e["kind"] == kind && e["metadata"]["name"] == name)
+                .unwrap_or_else(|| panic!("{kind} `{name}` not found"))
+        };
+
+        let api = entity("API", "http");
+        assert_eq!(api["spec"]["type"], "opentelemetry-semconv");
+        assert_eq!(api["spec"]["owner"], "observability");
+        assert_eq!(api["spec"]["system"], "telemetry");
+        let definition: serde_yaml::Value = serde_yaml::from_str(
+            api["spec"]["definition"]
+                .as_str()
+                .expect("Missing definition"),
+        )
+        .expect("Invalid definition");
+        let method = definition
+            .as_sequence()
+            .expect("Invalid definition")
+            .iter()
+            .find(|a| a["name"] == "http.request.method")
+            .expect("Attribute `http.request.method` not found");
+        assert_eq!(method["type"], "string");
+        assert_eq!(entity("API", "client")["spec"]["lifecycle"], "production");
+
+        let resource = entity("Resource", "metric.http.server.request.duration");
+        assert_eq!(
+            resource["metadata"]["title"],
+            "http.server.request.duration"
+        );
+        assert_eq!(resource["spec"]["type"], "semconv-metric");
+        assert_eq!(resource["spec"]["dependencyOf"][0], "api:http");
+    }
+
+    #[test]
+    fn test_registry_generate_builtin_span_builders() {
+        let logger = TestLogger::new();
+        let temp_output = TempDir::new("output")
+            .expect("Failed to create temporary directory")
+            .into_path();
+        let generate = |language: &str| {
+            let mut cli = generate_cli("span_builders", &temp_output, &[("language", language)]);
+            if let Some(Commands::Registry(RegistryCommand {
+                command: RegistrySubCommand::Generate(args),
+            })) = &mut cli.command
+            {
+                args.registry.registry = vec![RegistryPath::Local(
+                    "crates/weaver_resolver/data/registry-test-7-spans/registry/".to_owned(),
+                )];
+            }
+            cli
+        };
+
+        // The required attributes are the parameters of the constructors.
+        let exit_directive = run_command(&generate("rust"), logger.clone());
+        assert_eq!(exit_directive.exit_code, 0);
+        let rust = std::fs::read_to_string(temp_output.join("span_builders.rs"))
+            .expect("Failed to read span_builders.rs");
+        assert!(rust.contains("pub struct DbSpanBuilder {"));
+        assert!(rust.contains("    pub const SPAN_KIND: SpanKind = SpanKind::Client;"));
+        assert!(rust.contains("    pub fn new(db_system: String) -> Self {"));
+        assert!(rust.contains("    pub fn network_peer_port(mut self, value: i64) -> Self {"));
+        // The attributes listed several times by a group have a single setter.
+        assert!(rust
+            .split("pub struct ")
+            .all(|builder| builder.matches("    pub fn db_name(").count() <= 1));
+        assert!(!temp_output.join("span_builders.go").exists());
+
+        let exit_directive = run_command(&generate("go"), logger.clone());
+        assert_eq!(exit_directive.exit_code, 0);
+        let go = std::fs::read_to_string(temp_output.join("span_builders.go"))
+            .expect("Failed to read span_builders.go");
+        assert!(go.contains("func NewDbSpanBuilder(dbSystem string) *DbSpanBuilder {"));
+        assert!(go
+            .contains("func (b *DbSpanBuilder) WithNetworkPeerPort(value int64) *DbSpanBuilder {"));
+        assert!(go.contains("trace.WithSpanKind(trace.SpanKindClient)"));
+    }
+
+    #[test]
+    fn test_registry_generate_builtin_metric_factories() {
+        let logger = TestLogger::new();
+        let temp_output = TempDir::new("output")
+            .expect("Failed to create temporary directory")
+            .into_path();
+        let generate = |language: &str| {
+            generate_cli("metric_factories", &temp_output, &[("language", language)])
+        };
+
+        // The instruments are created with the kind, the unit, and the
+        // description of their metric.
+        let exit_directive = run_command(&generate("rust"), logger.clone());
+        assert_eq!(exit_directive.exit_code, 0);
+        let rust = std::fs::read_to_string(temp_output.join("metric_factories.rs"))
+            .expect("Failed to read metric_factories.rs");
+        assert!(rust.contains("pub struct HttpServerRequestDuration {"));
+        assert!(rust.contains("    instrument: opentelemetry::metrics::Histogram<f64>,"));
+        assert!(rust.contains("    pub const UNIT: &'static str = \"s\";"));
+        assert!(rust.contains("                .f64_histogram(Self::NAME)"));
+        assert!(rust.contains("                .i64_up_down_counter(Self::NAME)"));
+        // The required attributes are the parameters of the constructors.
+        assert!(rust
+            .contains("    pub fn new(http_request_method: String, url_scheme: String) -> Self {"));
+        assert!(!temp_output.join("metric_factories.go").exists());
+
+        let exit_directive = run_command(&generate("go"), logger.clone());
+        assert_eq!(exit_directive.exit_code, 0);
+        let go = std::fs::read_to_string(temp_output.join("metric_factories.go"))
+            .expect("Failed to read metric_factories.go");
+        assert!(go.contains(
+            "func NewHttpServerRequestDuration(meter metric.Meter) (*HttpServerRequestDuration, error) {"
+        ));
+        assert!(go.contains("\tinstrument, err := meter.Float64Histogram("));
+        assert!(go.contains("\t\tmetric.WithUnit(\"s\"),"));
+        assert!(go.contains("func NewHttpServerRequestDurationAttributes(httpRequestMethod string, urlScheme string) *HttpServerRequestDurationAttributes {"));
+    }
 }
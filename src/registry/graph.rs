@@ -0,0 +1,437 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Export the graph of the dependencies between the groups of a semantic
+//! convention registry (`extends`, `include` constraints, and attribute
+//! references) in Graphviz DOT or Mermaid format.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fmt::Write;
+use std::path::PathBuf;
+
+use clap::{Args, ValueEnum};
+
+use weaver_cache::Cache;
+use weaver_common::diagnostic::DiagnosticMessages;
+use weaver_common::Logger;
+use weaver_semconv::attribute::AttributeSpec;
+use weaver_semconv::group::{GroupSpec, GroupType};
+use weaver_semconv::semconv::SemConvSpec;
+
+use crate::registry::{Error, RegistryArgs};
+use crate::util::load_semconv_specs;
+use crate::{DiagnosticArgs, ExitDirectives};
+
+/// Supported output formats for the graph
+#[derive(Debug, Clone, ValueEnum)]
+pub enum GraphFormat {
+    /// Graphviz DOT format
+    Dot,
+    /// Mermaid flowchart format
+    Mermaid,
+}
+
+/// Parameters for the `registry graph` sub-command
+#[derive(Debug, Args)]
+pub struct RegistryGraphArgs {
+    /// Parameters to specify the semantic convention registry
+    #[command(flatten)]
+    registry: RegistryArgs,
+
+    /// Output format of the graph.
+    #[arg(short, long, default_value = "dot")]
+    format: GraphFormat,
+
+    /// Only keep the dependencies from or to the groups of these namespaces
+    /// (e.g. `http` for `registry.http` or `span.http.client`). Can be
+    /// repeated.
+    #[arg(long = "namespace")]
+    namespaces: Vec<String>,
+
+    /// Output file to write the graph to.
+    /// If not specified, the graph is printed to stdout
+    #[arg(short, long)]
+    output: Option<PathBuf>,
+
+    /// Parameters to specify the diagnostic format.
+    #[command(flatten)]
+    pub diagnostic: DiagnosticArgs,
+}
+
+/// Kind of dependency between two groups.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum EdgeKind {
+    /// The group extends the target group.
+    Extends,
+    /// The group includes the target group with an `include` constraint.
+    Include,
+    /// The group references attributes defined by the target group.
+    Ref,
+}
+
+/// Dependencies between the groups of a registry.
+#[derive(Debug, Default)]
+struct RegistryGraph {
+    /// Type of each group of the graph, by group id.
+    nodes: BTreeMap<String, GroupType>,
+    /// Dependencies (source group, kind, target group) with the number of
+    /// attributes referenced for the `Ref` dependencies.
+    edges: BTreeMap<(String, EdgeKind, String), usize>,
+}
+
+/// Export the dependency graph of the groups of a semantic convention registry.
+#[cfg(not(tarpaulin_include))]
+pub(crate) fn command(
+    logger: impl Logger + Sync + Clone,
+    cache: &Cache,
+    args: &RegistryGraphArgs,
+) -> Result<ExitDirectives, DiagnosticMessages> {
+    if args.output.is_none() {
+        logger.mute();
+    }
+    logger.loading(&format!(
+        "Building the graph of the registry `{}`",
+        args.registry.registries()
+    ));
+
+    let semconv_specs = load_semconv_specs(
+        &args.registry.registry_paths(),
+        &args.registry.file_filter()?,
+        cache,
+        logger.clone(),
+    )?;
+    let graph = RegistryGraph::from_semconv_specs(&semconv_specs, &args.namespaces);
+    let chain = graph.longest_extends_chain();
+    if chain.len() > 1 {
+        logger.log(&format!(
+            "Longest extends chain ({} groups): {}",
+            chain.len(),
+            chain.join(" -> ")
+        ));
+    }
+
+    let output = match args.format {
+        GraphFormat::Dot => graph.to_dot(),
+        GraphFormat::Mermaid => graph.to_mermaid(),
+    };
+    if let Some(path) = &args.output {
+        std::fs::write(path, output).map_err(|e| Error::GraphWriteFailed {
+            output: path.clone(),
+            error: e.to_string(),
+        })?;
+        logger.success(&format!("Graph written to `{}`", path.display()));
+    } else {
+        print!("{}", output);
+    }
+
+    Ok(ExitDirectives {
+        exit_code: 0,
+        quiet_mode: args.output.is_none(),
+    })
+}
+
+/// Returns the namespace of a group, i.e. the component following the kind of
+/// the group in its id (e.g. `http` for `span.http.client`).
+fn group_namespace(group_id: &str) -> &str {
+    let mut components = group_id.split('.');
+    let kind = components.next().unwrap_or_default();
+    components.next().unwrap_or(kind)
+}
+
+/// Returns the name of a group type, as written in the semantic convention files.
+fn group_type_name(group_type: &GroupType) -> &'static str {
+    match group_type {
+        GroupType::AttributeGroup => "attribute_group",
+        GroupType::Span => "span",
+        GroupType::Event => "event",
+        GroupType::Metric => "metric",
+        GroupType::MetricGroup => "metric_group",
+        GroupType::Resource => "resource",
+        GroupType::Scope => "scope",
+    }
+}
+
+impl RegistryGraph {
+    /// Builds the graph of the dependencies between the groups. If namespaces
+    /// are specified, only the dependencies from or to the groups of these
+    /// namespaces are kept.
+    fn from_semconv_specs(semconv_specs: &[(String, SemConvSpec)], namespaces: &[String]) -> Self {
+        let groups: Vec<&GroupSpec> = semconv_specs
+            .iter()
+            .flat_map(|(_, spec)| spec.groups())
+            .collect();
+        let group_types: HashMap<&str, &GroupType> = groups
+            .iter()
+            .map(|group| (group.id.as_str(), &group.r#type))
+            .collect();
+
+        // Group defining each attribute.
+        let mut definitions = HashMap::new();
+        for group in &groups {
+            for attr in &group.attributes {
+                if let AttributeSpec::Id { id, .. } = attr {
+                    let name = if group.prefix.is_empty() {
+                        id.clone()
+                    } else {
+                        format!("{}.{}", group.prefix, id)
+                    };
+                    _ = definitions.insert(name, group.id.as_str());
+                }
+            }
+        }
+
+        let selected = |group_id: &str| {
+            namespaces.is_empty()
+                || namespaces
+                    .iter()
+                    .any(|namespace| namespace == group_namespace(group_id))
+        };
+        let mut graph = RegistryGraph::default();
+        let mut add_edge = |source: &str, kind: EdgeKind, target: &str| {
+            if !group_types.contains_key(target) || !(selected(source) || selected(target)) {
+                return;
+            }
+            *graph
+                .edges
+                .entry((source.to_owned(), kind, target.to_owned()))
+                .or_insert(0) += 1;
+        };
+        for group in &groups {
+            if let Some(parent) = &group.extends {
+                add_edge(&group.id, EdgeKind::Extends, parent);
+            }
+            for include in group.constraints.iter().filter_map(|c| c.include.as_ref()) {
+                add_edge(&group.id, EdgeKind::Include, include);
+            }
+            for attr in &group.attributes {
+                if let AttributeSpec::Ref { r#ref, .. } = attr {
+                    if let Some(target) = definitions.get(r#ref) {
+                        add_edge(&group.id, EdgeKind::Ref, target);
+                    }
+                }
+            }
+        }
+
+        let mut node_ids: BTreeSet<&str> = groups
+            .iter()
+            .map(|group| group.id.as_str())
+            .filter(|id| selected(id))
+            .collect();
+        node_ids.extend(
+            graph
+                .edges
+                .keys()
+                .flat_map(|(source, _, target)| [source.as_str(), target.as_str()]),
+        );
+        graph.nodes = node_ids
+            .into_iter()
+            .filter_map(|id| group_types.get(id).map(|t| (id.to_owned(), (*t).clone())))
+            .collect();
+        graph
+    }
+
+    /// Returns the longest chain of groups linked by `extends` (the first one
+    /// being the most derived group).
+    fn longest_extends_chain(&self) -> Vec<&str> {
+        let parents: HashMap<&str, &str> = self
+            .edges
+            .keys()
+            .filter(|(_, kind, _)| *kind == EdgeKind::Extends)
+            .map(|(source, _, target)| (source.as_str(), target.as_str()))
+            .collect();
+        let mut longest = vec![];
+        for id in self.nodes.keys() {
+            let mut chain = vec![id.as_str()];
+            while let Some(parent) = parents.get(chain[chain.len() - 1]) {
+                // Guard against (invalid) cyclic extends.
+                if chain.contains(parent) {
+                    break;
+                }
+                chain.push(parent);
+            }
+            if chain.len() > longest.len() {
+                longest = chain;
+            }
+        }
+        longest
+    }
+
+    /// Returns the label of a dependency.
+    fn edge_label(kind: EdgeKind, count: usize) -> String {
+        match kind {
+            EdgeKind::Extends => "extends".to_owned(),
+            EdgeKind::Include => "include".to_owned(),
+            EdgeKind::Ref => format!("ref ({})", count),
+        }
+    }
+
+    /// Renders the graph in Graphviz DOT format.
+    fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph registry {\n  rankdir=LR;\n  node [shape=box];\n");
+        for (id, group_type) in &self.nodes {
+            _ = writeln!(
+                dot,
+                "  \"{}\" [label=\"{}\\n({})\"];",
+                id,
+                id,
+                group_type_name(group_type)
+            );
+        }
+        for ((source, kind, target), count) in &self.edges {
+            let style = match kind {
+                EdgeKind::Extends => "solid",
+                EdgeKind::Include => "dashed",
+                EdgeKind::Ref => "dotted",
+            };
+            _ = writeln!(
+                dot,
+                "  \"{}\" -> \"{}\" [label=\"{}\", style={}];",
+                source,
+                target,
+                Self::edge_label(*kind, *count),
+                style
+            );
+        }
+        dot.push_str("}\n");
+        dot
+    }
+
+    /// Renders the graph as a Mermaid flowchart.
+    fn to_mermaid(&self) -> String {
+        // Mermaid node ids can't contain dots, the nodes are numbered.
+        let node_ids: HashMap<&str, usize> = self
+            .nodes
+            .keys()
+            .enumerate()
+            .map(|(index, id)| (id.as_str(), index))
+            .collect();
+        let mut mermaid = String::from("flowchart LR\n");
+        for (index, (id, group_type)) in self.nodes.iter().enumerate() {
+            _ = writeln!(
+                mermaid,
+                "  n{}[\"{}<br/>({})\"]",
+                index,
+                id,
+                group_type_name(group_type)
+            );
+        }
+        for ((source, kind, target), count) in &self.edges {
+            let arrow = match kind {
+                EdgeKind::Extends => "-->",
+                EdgeKind::Include | EdgeKind::Ref => "-.->",
+            };
+            _ = writeln!(
+                mermaid,
+                "  n{} {}|\"{}\"| n{}",
+                node_ids[source.as_str()],
+                arrow,
+                Self::edge_label(*kind, *count),
+                node_ids[target.as_str()]
+            );
+        }
+        mermaid
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use weaver_semconv::semconv::SemConvSpec;
+
+    use crate::registry::graph::RegistryGraph;
+
+    const REGISTRY: &str = r#"groups:
+  - id: registry.http
+    type: attribute_group
+    brief: HTTP attributes.
+    prefix: http
+    attributes:
+      - id: request.method
+        type: string
+        brief: HTTP request method.
+        examples: [GET]
+      - id: route
+        type: string
+        brief: The matched route.
+        examples: ['/users/:id']
+  - id: registry.url
+    type: attribute_group
+    brief: URL attributes.
+    attributes:
+      - id: url.full
+        type: string
+        brief: Full URL.
+        examples: ['https://example.com']
+  - id: attributes.http.common
+    type: attribute_group
+    brief: HTTP common attributes.
+    attributes:
+      - ref: http.request.method
+      - ref: http.route
+  - id: span.http.client
+    type: span
+    span_kind: client
+    brief: HTTP client span.
+    extends: attributes.http.common
+    attributes:
+      - ref: url.full
+  - id: span.url.fetch
+    type: span
+    span_kind: client
+    brief: Fetch span.
+    attributes:
+      - ref: url.full
+    constraints:
+      - include: span.http.client
+"#;
+
+    fn graph(namespaces: &[&str]) -> RegistryGraph {
+        let spec = SemConvSpec::from_string(REGISTRY).expect("Invalid registry");
+        let namespaces: Vec<String> = namespaces.iter().map(|ns| (*ns).to_owned()).collect();
+        RegistryGraph::from_semconv_specs(&[("registry.yaml".to_owned(), spec)], &namespaces)
+    }
+
+    #[test]
+    fn test_graph_to_dot() {
+        assert_eq!(
+            graph(&[]).to_dot(),
+            r#"digraph registry {
+  rankdir=LR;
+  node [shape=box];
+  "attributes.http.common" [label="attributes.http.common\n(attribute_group)"];
+  "registry.http" [label="registry.http\n(attribute_group)"];
+  "registry.url" [label="registry.url\n(attribute_group)"];
+  "span.http.client" [label="span.http.client\n(span)"];
+  "span.url.fetch" [label="span.url.fetch\n(span)"];
+  "attributes.http.common" -> "registry.http" [label="ref (2)", style=dotted];
+  "span.http.client" -> "attributes.http.common" [label="extends", style=solid];
+  "span.http.client" -> "registry.url" [label="ref (1)", style=dotted];
+  "span.url.fetch" -> "span.http.client" [label="include", style=dashed];
+  "span.url.fetch" -> "registry.url" [label="ref (1)", style=dotted];
+}
+"#
+        );
+    }
+
+    #[test]
+    fn test_graph_to_mermaid_with_namespace() {
+        // Only the dependencies from or to the `url` namespace are kept.
+        assert_eq!(
+            graph(&["url"]).to_mermaid(),
+            r#"flowchart LR
+  n0["registry.url<br/>(attribute_group)"]
+  n1["span.http.client<br/>(span)"]
+  n2["span.url.fetch<br/>(span)"]
+  n1 -.->|"ref (1)"| n0
+  n2 -.->|"include"| n1
+  n2 -.->|"ref (1)"| n0
+"#
+        );
+    }
+
+    #[test]
+    fn test_longest_extends_chain() {
+        assert_eq!(
+            graph(&[]).longest_extends_chain(),
+            vec!["span.http.client", "attributes.http.common"]
+        );
+    }
+}
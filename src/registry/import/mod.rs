@@ -0,0 +1,43 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Commands to import semantic conventions from other specification formats.
+
+mod openapi;
+
+use clap::{Args, Subcommand};
+
+use weaver_common::Logger;
+
+use crate::registry::import::openapi::RegistryImportOpenapiArgs;
+use crate::CmdResult;
+
+/// Parameters for the `registry import` sub-command
+#[derive(Debug, Args)]
+pub struct RegistryImportArgs {
+    /// Define the sub-commands for the `registry import` command
+    #[clap(subcommand)]
+    pub command: RegistryImportSubCommand,
+}
+
+/// Sub-commands to import semantic conventions from other specification formats.
+#[derive(Debug, Subcommand)]
+#[clap(verbatim_doc_comment)]
+pub enum RegistryImportSubCommand {
+    /// Derives draft attribute groups from the operations of an OpenAPI document.
+    ///
+    /// The path, query, header, and cookie parameters of the operations are converted into attributes of a `registry.<namespace>` attribute group, with types inferred from their schemas (string enums become enum attributes) and briefs taken from their descriptions. An attribute group referencing the attributes of its parameters is generated per operation. Local `$ref` references are followed; OpenAPI 3.x and Swagger 2.0 documents in YAML or JSON are supported.
+    ///
+    /// The generated file is a starting point: review the attribute ids, types, and briefs before adding it to a registry.
+    #[clap(verbatim_doc_comment)]
+    Openapi(RegistryImportOpenapiArgs),
+}
+
+/// Import semantic conventions from other specification formats.
+#[cfg(not(tarpaulin_include))]
+pub(crate) fn import(log: impl Logger + Sync + Clone, args: &RegistryImportArgs) -> CmdResult {
+    match &args.command {
+        RegistryImportSubCommand::Openapi(args) => {
+            CmdResult::new(openapi::command(log, args), Some(args.diagnostic.clone()))
+        }
+    }
+}
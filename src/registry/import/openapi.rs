@@ -0,0 +1,839 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Import the parameters of the operations of an OpenAPI document as draft
+//! attribute groups.
+
+use std::collections::{BTreeMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use clap::Args;
+use convert_case::{Case, Casing};
+use serde::Serialize;
+use serde_json::Value;
+
+use weaver_common::diagnostic::DiagnosticMessages;
+use weaver_common::Logger;
+
+use crate::registry::Error;
+use crate::{DiagnosticArgs, ExitDirectives};
+
+/// HTTP methods of the operations of a path item.
+const METHODS: [&str; 8] = [
+    "get", "put", "post", "delete", "options", "head", "patch", "trace",
+];
+
+/// Maximum number of `$ref` references followed to resolve a value (guards
+/// against reference cycles).
+const MAX_REF_DEPTH: usize = 32;
+
+/// Stability of the generated groups and attributes.
+const DRAFT_STABILITY: &str = "experimental";
+
+/// Parameters for the `registry import openapi` sub-command
+#[derive(Debug, Args)]
+pub struct RegistryImportOpenapiArgs {
+    /// Path to the OpenAPI document (YAML or JSON).
+    spec: PathBuf,
+
+    /// Namespace of the generated attributes (e.g. `petstore` for
+    /// `petstore.pet_id`). Defaults to the title of the API in snake case.
+    #[arg(long)]
+    namespace: Option<String>,
+
+    /// Output file to write the attribute groups to.
+    /// If not specified, the attribute groups are printed to stdout
+    #[arg(short, long)]
+    output: Option<PathBuf>,
+
+    /// Parameters to specify the diagnostic format.
+    #[command(flatten)]
+    pub diagnostic: DiagnosticArgs,
+}
+
+/// A semantic convention file containing draft attribute groups.
+#[derive(Debug, Serialize)]
+struct DraftFile {
+    groups: Vec<DraftGroup>,
+}
+
+/// A draft attribute group.
+#[derive(Debug, Serialize)]
+struct DraftGroup {
+    id: String,
+    r#type: &'static str,
+    brief: String,
+    stability: &'static str,
+    attributes: Vec<DraftAttribute>,
+}
+
+/// An attribute definition or an attribute reference of a draft group.
+#[derive(Debug, Serialize)]
+#[serde(untagged)]
+enum DraftAttribute {
+    /// An attribute defined by the registry group.
+    Definition(AttributeDefinition),
+    /// A reference from an operation group to an attribute of the registry
+    /// group.
+    Ref {
+        r#ref: String,
+        requirement_level: &'static str,
+    },
+}
+
+/// An attribute derived from an operation parameter.
+#[derive(Debug, Serialize)]
+struct AttributeDefinition {
+    id: String,
+    r#type: DraftType,
+    brief: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    note: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    examples: Option<Value>,
+    stability: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    deprecated: Option<String>,
+}
+
+/// The type of a draft attribute.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(untagged)]
+enum DraftType {
+    /// A primitive or array type (e.g. `int`, `string[]`).
+    Primitive(&'static str),
+    /// An enum type derived from a string enum schema.
+    Enum { members: Vec<DraftMember> },
+}
+
+/// A member of a draft enum type.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+struct DraftMember {
+    id: String,
+    value: String,
+    stability: &'static str,
+}
+
+/// A parameter of an operation, after resolution of the `$ref` references.
+#[derive(Debug, Clone)]
+struct Parameter<'a> {
+    name: String,
+    /// Location of the parameter (`path`, `query`, `header`, or `cookie`).
+    location: String,
+    required: bool,
+    deprecated: bool,
+    description: Option<String>,
+    /// Schema of the parameter (the parameter itself for Swagger 2.0).
+    schema: Option<&'a Value>,
+    example: Option<&'a Value>,
+}
+
+/// An OpenAPI document.
+struct OpenApi<'a> {
+    root: &'a Value,
+}
+
+/// Import the operation parameters of an OpenAPI document as draft attribute
+/// groups.
+#[cfg(not(tarpaulin_include))]
+pub(crate) fn command(
+    logger: impl Logger + Sync + Clone,
+    args: &RegistryImportOpenapiArgs,
+) -> Result<ExitDirectives, DiagnosticMessages> {
+    if args.output.is_none() {
+        logger.mute();
+    }
+    logger.loading(&format!(
+        "Importing the OpenAPI document `{}`",
+        args.spec.display()
+    ));
+
+    let import_failed = |error: String| Error::OpenApiImportFailed {
+        spec: args.spec.clone(),
+        error,
+    };
+    let content = std::fs::read_to_string(&args.spec).map_err(|e| import_failed(e.to_string()))?;
+    let document: Value =
+        serde_yaml::from_str(&content).map_err(|e| import_failed(e.to_string()))?;
+    let (draft, warnings) = OpenApi { root: &document }
+        .import(args.namespace.as_deref())
+        .map_err(import_failed)?;
+    let output = draft.to_yaml(&args.spec);
+
+    if let Some(path) = &args.output {
+        std::fs::write(path, output).map_err(|e| Error::DraftWriteFailed {
+            output: path.clone(),
+            error: e.to_string(),
+        })?;
+        logger.success(&format!(
+            "{} attribute(s) and {} operation group(s) written to `{}`",
+            draft.attribute_count(),
+            draft.groups.len() - 1,
+            path.display()
+        ));
+    } else {
+        print!("{}", output);
+    }
+
+    if !warnings.is_empty() {
+        return Err(DiagnosticMessages::from_errors(warnings));
+    }
+    Ok(ExitDirectives {
+        exit_code: 0,
+        quiet_mode: args.output.is_none(),
+    })
+}
+
+impl DraftFile {
+    /// Serializes the draft groups in YAML with a header comment.
+    fn to_yaml(&self, spec: &Path) -> String {
+        let groups = serde_yaml::to_string(self).expect("Failed to serialize the draft groups");
+        let spec = spec.file_name().map_or(spec, Path::new).display();
+        format!(
+            "# Draft attribute groups imported from the OpenAPI document `{spec}`.\n\
+             # Review the attribute ids, types, and briefs before adding them to a registry.\n\
+             {groups}"
+        )
+    }
+
+    /// Number of attributes defined by the registry group.
+    fn attribute_count(&self) -> usize {
+        self.groups
+            .first()
+            .map_or(0, |group| group.attributes.len())
+    }
+}
+
+impl<'a> OpenApi<'a> {
+    /// Converts the parameters of the operations of the document into a
+    /// registry group defining one attribute per parameter and one group per
+    /// operation referencing these attributes. Returns the draft file and the
+    /// warnings about the parameters that couldn't be fully converted.
+    fn import(&self, namespace: Option<&str>) -> Result<(DraftFile, Vec<Error>), String> {
+        if self.root.get("openapi").is_none() && self.root.get("swagger").is_none() {
+            return Err(
+                "The document is not an OpenAPI document (no `openapi` or `swagger` field)."
+                    .to_owned(),
+            );
+        }
+        let title = self
+            .root
+            .pointer("/info/title")
+            .and_then(Value::as_str)
+            .unwrap_or("API");
+        let namespace = match namespace {
+            Some(namespace) => namespace.to_owned(),
+            None => Some(snake_case(title))
+                .filter(|namespace| !namespace.is_empty())
+                .unwrap_or_else(|| "api".to_owned()),
+        };
+
+        let mut warnings = vec![];
+        let mut group_ids = HashSet::new();
+        let mut attributes: BTreeMap<String, AttributeDefinition> = BTreeMap::new();
+        let mut operation_groups = vec![];
+        let paths = self
+            .root
+            .get("paths")
+            .and_then(Value::as_object)
+            .into_iter()
+            .flatten();
+        for (path, item) in paths {
+            let item = match self.resolve(item) {
+                Ok(item) => item,
+                Err(reason) => {
+                    warnings.push(Error::UnsupportedOpenApiParameter {
+                        location: path.clone(),
+                        reason,
+                    });
+                    continue;
+                }
+            };
+            let path_parameters = self.parameters(item, path, &mut warnings);
+            for method in METHODS {
+                let Some(operation) = item.get(method) else {
+                    continue;
+                };
+                let operation_name = format!("{} {}", method.to_uppercase(), path);
+
+                // Operation parameters override the path parameters with the
+                // same name and location.
+                let mut parameters = path_parameters.clone();
+                for parameter in self.parameters(operation, &operation_name, &mut warnings) {
+                    parameters
+                        .retain(|p| p.name != parameter.name || p.location != parameter.location);
+                    parameters.push(parameter);
+                }
+                if parameters.is_empty() {
+                    continue;
+                }
+
+                let mut refs: Vec<(String, bool)> = vec![];
+                for parameter in &parameters {
+                    let id = attribute_id(&namespace, parameter);
+                    if !attributes.contains_key(&id) {
+                        let definition =
+                            self.attribute(&id, parameter, &operation_name, &mut warnings);
+                        _ = attributes.insert(id.clone(), definition);
+                    }
+                    match refs.iter_mut().find(|(ref_id, _)| *ref_id == id) {
+                        Some((_, required)) => *required |= parameter.required,
+                        None => refs.push((id, parameter.required)),
+                    }
+                }
+
+                let operation_id = operation
+                    .get("operationId")
+                    .and_then(Value::as_str)
+                    .map(snake_case)
+                    .unwrap_or_else(|| snake_case(&format!("{method} {path}")));
+                let mut group_id = format!("{namespace}.{operation_id}");
+                let mut suffix = 1;
+                while !group_ids.insert(group_id.clone()) {
+                    suffix += 1;
+                    group_id = format!("{namespace}.{operation_id}_{suffix}");
+                }
+                let brief = operation
+                    .get("summary")
+                    .and_then(Value::as_str)
+                    .or_else(|| operation.get("description").and_then(Value::as_str))
+                    .map(|text| split_description(text).0)
+                    .unwrap_or_else(|| format!("Parameters of the `{operation_name}` operation."));
+                operation_groups.push(DraftGroup {
+                    id: group_id,
+                    r#type: "attribute_group",
+                    brief,
+                    stability: DRAFT_STABILITY,
+                    attributes: refs
+                        .into_iter()
+                        .map(|(id, required)| DraftAttribute::Ref {
+                            r#ref: id,
+                            requirement_level: if required { "required" } else { "recommended" },
+                        })
+                        .collect(),
+                });
+            }
+        }
+
+        if attributes.is_empty() {
+            return Err("The document doesn't define any operation parameter.".to_owned());
+        }
+        let mut groups = vec![DraftGroup {
+            id: format!("registry.{namespace}"),
+            r#type: "attribute_group",
+            brief: format!("Attributes derived from the parameters of the {title} operations."),
+            stability: DRAFT_STABILITY,
+            attributes: attributes
+                .into_values()
+                .map(DraftAttribute::Definition)
+                .collect(),
+        }];
+        groups.extend(operation_groups);
+        Ok((DraftFile { groups }, warnings))
+    }
+
+    /// Follows the local `$ref` references of a value.
+    fn resolve(&self, mut value: &'a Value) -> Result<&'a Value, String> {
+        for _ in 0..MAX_REF_DEPTH {
+            let Some(reference) = value.get("$ref").and_then(Value::as_str) else {
+                return Ok(value);
+            };
+            let Some(pointer) = reference.strip_prefix('#') else {
+                return Err(format!(
+                    "The external reference `{reference}` is not supported."
+                ));
+            };
+            value = self
+                .root
+                .pointer(pointer)
+                .ok_or_else(|| format!("The reference `{reference}` can't be resolved."))?;
+        }
+        Err("Too many nested references (reference cycle?).".to_owned())
+    }
+
+    /// Returns the path, query, header, and cookie parameters of a path item
+    /// or an operation. Request bodies (`body` and `formData` parameters in
+    /// Swagger 2.0) are ignored.
+    fn parameters(
+        &self,
+        object: &'a Value,
+        location: &str,
+        warnings: &mut Vec<Error>,
+    ) -> Vec<Parameter<'a>> {
+        let mut parameters = vec![];
+        for parameter in object
+            .get("parameters")
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+        {
+            let parameter = match self.resolve(parameter) {
+                Ok(parameter) => parameter,
+                Err(reason) => {
+                    warnings.push(Error::UnsupportedOpenApiParameter {
+                        location: location.to_owned(),
+                        reason,
+                    });
+                    continue;
+                }
+            };
+            let (Some(name), Some(param_location)) = (
+                parameter.get("name").and_then(Value::as_str),
+                parameter.get("in").and_then(Value::as_str),
+            ) else {
+                continue;
+            };
+            if !matches!(param_location, "path" | "query" | "header" | "cookie") {
+                continue;
+            }
+            // OpenAPI 3.x parameters are described by a schema or by the
+            // schema of a media type, Swagger 2.0 parameters by their own
+            // `type` and `items` fields.
+            let schema = parameter
+                .get("schema")
+                .or_else(|| {
+                    parameter
+                        .get("content")
+                        .and_then(Value::as_object)
+                        .and_then(|content| content.values().next())
+                        .and_then(|media_type| media_type.get("schema"))
+                })
+                .or_else(|| parameter.get("type").map(|_| parameter));
+            let example = parameter.get("example").or_else(|| {
+                parameter
+                    .get("examples")
+                    .and_then(Value::as_object)
+                    .and_then(|examples| examples.values().next())
+                    .and_then(|example| example.get("value"))
+            });
+            parameters.push(Parameter {
+                name: name.to_owned(),
+                location: param_location.to_owned(),
+                required: param_location == "path"
+                    || parameter
+                        .get("required")
+                        .and_then(Value::as_bool)
+                        .unwrap_or(false),
+                deprecated: parameter
+                    .get("deprecated")
+                    .and_then(Value::as_bool)
+                    .unwrap_or(false),
+                description: parameter
+                    .get("description")
+                    .and_then(Value::as_str)
+                    .map(str::to_owned),
+                schema,
+                example,
+            });
+        }
+        parameters
+    }
+
+    /// Converts a parameter into an attribute definition.
+    fn attribute(
+        &self,
+        id: &str,
+        parameter: &Parameter<'a>,
+        operation: &str,
+        warnings: &mut Vec<Error>,
+    ) -> AttributeDefinition {
+        let schema = parameter
+            .schema
+            .map(|schema| self.resolve(schema))
+            .transpose();
+        let r#type = match &schema {
+            Ok(Some(schema)) => self.attribute_type(schema),
+            Ok(None) => Err("The parameter has no schema.".to_owned()),
+            Err(reason) => Err(reason.clone()),
+        }
+        .unwrap_or_else(|reason| {
+            warnings.push(Error::UnsupportedOpenApiParameter {
+                location: format!("{operation} ({} `{}`)", parameter.location, parameter.name),
+                reason: format!("{reason} The `string` type is used."),
+            });
+            DraftType::Primitive("string")
+        });
+
+        let schema = schema.ok().flatten();
+        let description = parameter.description.clone().or_else(|| {
+            schema
+                .and_then(|schema| schema.get("description"))
+                .and_then(Value::as_str)
+                .map(str::to_owned)
+        });
+        let (brief, note) = match description {
+            Some(description) => split_description(&description),
+            None if parameter.location == "header" => {
+                (format!("The `{}` request header.", parameter.name), None)
+            }
+            None => (
+                format!("The `{}` {} parameter.", parameter.name, parameter.location),
+                None,
+            ),
+        };
+
+        // String attributes require examples, a placeholder is used when the
+        // document doesn't provide any.
+        let example = parameter
+            .example
+            .or_else(|| schema.and_then(|schema| schema.get("example")))
+            .or_else(|| schema.and_then(|schema| schema.get("default")));
+        let examples =
+            match (example, &r#type) {
+                (Some(example @ Value::Array(_)), DraftType::Primitive(t)) if t.ends_with("[]") => {
+                    Some(Value::Array(vec![example.clone()]))
+                }
+                (Some(example @ (Value::Bool(_) | Value::Number(_) | Value::String(_))), _) => {
+                    Some(example.clone())
+                }
+                (_, DraftType::Primitive("string")) => Some(Value::String(
+                    schema
+                        .and_then(|schema| schema.get("format"))
+                        .and_then(Value::as_str)
+                        .and_then(format_example)
+                        .map_or_else(|| format!("<{}>", parameter.name), str::to_owned),
+                )),
+                (_, DraftType::Primitive("string[]")) => Some(Value::Array(vec![Value::Array(
+                    vec![Value::String(format!("<{}>", parameter.name))],
+                )])),
+                _ => None,
+            };
+
+        AttributeDefinition {
+            id: id.to_owned(),
+            r#type,
+            brief,
+            note,
+            examples,
+            stability: DRAFT_STABILITY,
+            deprecated: parameter
+                .deprecated
+                .then(|| "Deprecated in the OpenAPI document.".to_owned()),
+        }
+    }
+
+    /// Infers the type of an attribute from a (resolved) parameter schema.
+    fn attribute_type(&self, schema: &'a Value) -> Result<DraftType, String> {
+        if let Some(members) = string_enum(schema) {
+            return Ok(DraftType::Enum { members });
+        }
+        match schema_type(schema) {
+            Some("array") => {
+                let items = schema
+                    .get("items")
+                    .ok_or_else(|| "The array schema has no `items`.".to_owned())?;
+                let items = self.resolve(items)?;
+                let item_type = if string_enum(items).is_some() {
+                    Some("string")
+                } else {
+                    schema_type(items)
+                };
+                match item_type {
+                    Some("string") => Ok(DraftType::Primitive("string[]")),
+                    Some("integer") => Ok(DraftType::Primitive("int[]")),
+                    Some("number") => Ok(DraftType::Primitive("double[]")),
+                    Some("boolean") => Ok(DraftType::Primitive("boolean[]")),
+                    Some(other) => Err(format!("Arrays of `{other}` are not supported.")),
+                    None => Err("The type of the array items is not specified.".to_owned()),
+                }
+            }
+            Some("string") => Ok(DraftType::Primitive("string")),
+            Some("integer") => Ok(DraftType::Primitive("int")),
+            Some("number") => Ok(DraftType::Primitive("double")),
+            Some("boolean") => Ok(DraftType::Primitive("boolean")),
+            Some(other) => Err(format!("The `{other}` type is not supported.")),
+            None => Err("The type of the schema is not specified.".to_owned()),
+        }
+    }
+}
+
+/// Returns the type of a schema, ignoring `null` in the OpenAPI 3.1 type
+/// lists (e.g. `[string, "null"]`).
+fn schema_type(schema: &Value) -> Option<&str> {
+    match schema.get("type")? {
+        Value::String(t) => Some(t.as_str()),
+        Value::Array(types) => types
+            .iter()
+            .filter_map(Value::as_str)
+            .find(|t| *t != "null"),
+        _ => None,
+    }
+}
+
+/// Returns an example value for the well-known formats of the string schemas.
+fn format_example(format: &str) -> Option<&'static str> {
+    match format {
+        "uuid" => Some("123e4567-e89b-12d3-a456-426614174000"),
+        "date" => Some("2024-01-01"),
+        "date-time" => Some("2024-01-01T00:00:00Z"),
+        "email" => Some("user@example.com"),
+        "uri" | "url" => Some("https://example.com"),
+        "ipv4" => Some("192.0.2.1"),
+        "ipv6" => Some("2001:db8::1"),
+        _ => None,
+    }
+}
+
+/// Returns the members of the enum type derived from a schema enumerating
+/// string values, or `None` if the schema doesn't enumerate string values.
+fn string_enum(schema: &Value) -> Option<Vec<DraftMember>> {
+    let values: Vec<&str> = schema
+        .get("enum")?
+        .as_array()?
+        .iter()
+        .filter(|value| !value.is_null())
+        .map(Value::as_str)
+        .collect::<Option<_>>()?;
+    let mut ids = HashSet::new();
+    let members: Vec<DraftMember> = values
+        .into_iter()
+        .enumerate()
+        .filter_map(|(i, value)| {
+            let id = Some(snake_case(value))
+                .filter(|id| id.starts_with(|c: char| c.is_ascii_lowercase()))
+                .unwrap_or_else(|| format!("value_{i}"));
+            ids.insert(id.clone()).then(|| DraftMember {
+                id,
+                value: value.to_owned(),
+                stability: DRAFT_STABILITY,
+            })
+        })
+        .collect();
+    (!members.is_empty()).then_some(members)
+}
+
+/// Returns the id of the attribute of a parameter. Headers are namespaced
+/// under `<namespace>.request.header`, the other parameters directly under the
+/// namespace so a path and a query parameter with the same name share the
+/// same attribute.
+fn attribute_id(namespace: &str, parameter: &Parameter<'_>) -> String {
+    let name = snake_case(&parameter.name);
+    if parameter.location == "header" {
+        format!("{namespace}.request.header.{name}")
+    } else {
+        format!("{namespace}.{name}")
+    }
+}
+
+/// Splits a description into a brief (the first paragraph on a single line)
+/// and a note (the following paragraphs, if any).
+fn split_description(description: &str) -> (String, Option<String>) {
+    let description = description.trim();
+    let (brief, note) = description
+        .split_once("\n\n")
+        .map_or((description, None), |(brief, note)| {
+            (brief, Some(note.trim()))
+        });
+    (
+        brief.split_whitespace().collect::<Vec<_>>().join(" "),
+        note.filter(|note| !note.is_empty()).map(str::to_owned),
+    )
+}
+
+/// Converts a name into a snake case identifier only made of lowercase ASCII
+/// letters, digits, and underscores (e.g. `X-Request-ID` -> `x_request_id`).
+fn snake_case(name: &str) -> String {
+    let name: String = name
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { ' ' })
+        .collect();
+    name.to_case(Case::Snake)
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::Value;
+    use weaver_resolver::SchemaResolver;
+    use weaver_semconv::registry::SemConvRegistry;
+
+    use super::*;
+
+    const PETSTORE: &str = r##"
+openapi: 3.0.3
+info:
+  title: Swagger Petstore
+  version: 1.0.0
+paths:
+  /pets:
+    get:
+      operationId: listPets
+      summary: List all pets
+      parameters:
+        - name: limit
+          in: query
+          description: |
+            How many items to return at one time.
+
+            The maximum is 100.
+          schema:
+            type: integer
+            format: int32
+          example: 20
+        - name: status
+          in: query
+          schema:
+            type: array
+            items:
+              type: string
+              enum: [available, pending, sold]
+        - $ref: "#/components/parameters/RequestId"
+    post:
+      summary: Create a pet
+      parameters:
+        - $ref: "#/components/parameters/RequestId"
+  /pets/{petId}:
+    parameters:
+      - name: petId
+        in: path
+        description: The id of the pet.
+        schema:
+          type: string
+    get:
+      operationId: showPetById
+      parameters:
+        - name: X-Filter
+          in: header
+          schema:
+            type: object
+        - name: kind
+          in: query
+          deprecated: true
+          schema:
+            $ref: "#/components/schemas/Kind"
+components:
+  parameters:
+    RequestId:
+      name: X-Request-ID
+      in: header
+      required: true
+      schema:
+        type: string
+        format: uuid
+  schemas:
+    Kind:
+      type: string
+      description: The kind of pet.
+      enum: [dog, cat, "Guinea Pig"]
+"##;
+
+    #[test]
+    fn test_import_openapi() {
+        let document: Value = serde_yaml::from_str(PETSTORE).expect("Invalid OpenAPI document");
+        let (draft, warnings) = OpenApi { root: &document }
+            .import(None)
+            .expect("Failed to import the OpenAPI document");
+
+        // The `X-Filter` header has an object schema.
+        assert_eq!(warnings.len(), 1);
+        assert!(
+            matches!(&warnings[0], Error::UnsupportedOpenApiParameter { location, .. } if location.contains("X-Filter"))
+        );
+
+        let ids: Vec<&str> = draft.groups.iter().map(|g| g.id.as_str()).collect();
+        assert_eq!(
+            ids,
+            vec![
+                "registry.swagger_petstore",
+                "swagger_petstore.list_pets",
+                "swagger_petstore.post_pets",
+                "swagger_petstore.show_pet_by_id",
+            ]
+        );
+        assert_eq!(draft.attribute_count(), 6);
+
+        // The generated file is a valid semantic convention file.
+        let yaml = draft.to_yaml(Path::new("petstore.yaml"));
+        let mut registry = SemConvRegistry::new("default");
+        registry
+            .add_semconv_spec_from_string("petstore.yaml", &yaml)
+            .expect("Invalid semantic convention file");
+        let schema = SchemaResolver::resolve_semantic_convention_registry(&mut registry)
+            .expect("Failed to resolve the imported registry");
+        let catalog = schema.catalog();
+        let attribute = |name: &str| {
+            catalog
+                .attributes
+                .iter()
+                .find(|attribute| attribute.name == name)
+                .unwrap_or_else(|| panic!("Attribute `{name}` not found"))
+        };
+
+        let limit = attribute("swagger_petstore.limit");
+        assert_eq!(limit.r#type.to_string(), "int");
+        assert_eq!(limit.brief, "How many items to return at one time.");
+        assert_eq!(limit.note, "The maximum is 100.");
+        assert_eq!(
+            attribute("swagger_petstore.status").r#type.to_string(),
+            "string[]"
+        );
+        assert_eq!(
+            attribute("swagger_petstore.pet_id").brief,
+            "The id of the pet."
+        );
+        assert_eq!(
+            attribute("swagger_petstore.request.header.x_request_id")
+                .r#type
+                .to_string(),
+            "string"
+        );
+        assert_eq!(
+            attribute("swagger_petstore.request.header.x_filter")
+                .r#type
+                .to_string(),
+            "string"
+        );
+        let kind = attribute("swagger_petstore.kind");
+        assert_eq!(kind.r#type.to_string(), "enum {dog, cat, guinea_pig}");
+        assert_eq!(kind.brief, "The kind of pet.");
+        assert!(kind.deprecated.is_some());
+
+        // The path parameters are required in the operation groups.
+        assert!(yaml.contains("- ref: swagger_petstore.pet_id\n    requirement_level: required"));
+        assert!(yaml.contains(
+            "- ref: swagger_petstore.request.header.x_request_id\n    requirement_level: required"
+        ));
+    }
+
+    #[test]
+    fn test_import_swagger() {
+        let document: Value = serde_json::json!({
+            "swagger": "2.0",
+            "info": { "title": "Store", "version": "1.0" },
+            "paths": {
+                "/orders": {
+                    "get": {
+                        "parameters": [
+                            { "name": "page", "in": "query", "type": "integer" },
+                            { "name": "tags", "in": "query", "type": "array", "items": { "type": "string" } },
+                            { "name": "body", "in": "body", "schema": { "type": "object" } }
+                        ]
+                    }
+                }
+            }
+        });
+        let (draft, warnings) = OpenApi { root: &document }
+            .import(Some("store"))
+            .expect("Failed to import the Swagger document");
+        assert!(warnings.is_empty());
+        assert_eq!(draft.groups[1].id, "store.get_orders");
+        let types: Vec<(&str, DraftType)> = draft.groups[0]
+            .attributes
+            .iter()
+            .filter_map(|attribute| match attribute {
+                DraftAttribute::Definition(definition) => {
+                    Some((definition.id.as_str(), definition.r#type.clone()))
+                }
+                DraftAttribute::Ref { .. } => None,
+            })
+            .collect();
+        assert_eq!(
+            types,
+            vec![
+                ("store.page", DraftType::Primitive("int")),
+                ("store.tags", DraftType::Primitive("string[]")),
+            ]
+        );
+
+        let not_openapi = serde_json::json!({ "paths": {} });
+        assert!(OpenApi { root: &not_openapi }.import(None).is_err());
+    }
+}
@@ -102,6 +102,7 @@ mod tests {
         let cli = Cli {
             debug: 0,
             quiet: false,
+            cache: Default::default(),
             command: Some(Commands::Registry(RegistryCommand {
                 command: RegistrySubCommand::JsonSchema(RegistryJsonSchemaArgs {
                     output: None,
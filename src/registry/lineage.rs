@@ -0,0 +1,505 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Report of the lineage of a resolved semantic convention registry: the source
+//! file of each group and, for each resolved attribute of a group, the group it
+//! originates from, how it was resolved (definition, reference, `extends`, or
+//! `include`), and which of its fields are inherited or overridden locally.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::fmt::Write;
+use std::path::PathBuf;
+
+use clap::{Args, ValueEnum};
+use serde::Serialize;
+
+use weaver_cache::Cache;
+use weaver_common::diagnostic::DiagnosticMessages;
+use weaver_common::Logger;
+use weaver_resolved_schema::catalog::Catalog;
+use weaver_resolved_schema::registry::Registry;
+use weaver_semconv::attribute::AttributeSpec;
+use weaver_semconv::group::GroupSpec;
+use weaver_semconv::registry::SemConvRegistry;
+use weaver_semconv::semconv::SemConvSpec;
+
+use crate::registry::dead_definitions::qualified_id;
+use crate::registry::search::group_type_name;
+use crate::registry::{Error, RegistryArgs};
+use crate::util::{load_semconv_specs, resolve_semconv_specs};
+use crate::{DiagnosticArgs, ExitDirectives};
+
+/// Supported output formats for the lineage report
+#[derive(Debug, Clone, ValueEnum)]
+pub enum LineageFormat {
+    /// JSON format
+    Json,
+    /// Markdown format (one table per group)
+    Markdown,
+}
+
+/// Parameters for the `registry lineage` sub-command
+#[derive(Debug, Args)]
+pub struct RegistryLineageArgs {
+    /// Parameters to specify the semantic convention registry
+    #[command(flatten)]
+    registry: RegistryArgs,
+
+    /// Output format of the report.
+    #[arg(short, long, default_value = "json")]
+    format: LineageFormat,
+
+    /// Output file to write the report to.
+    /// If not specified, the report is printed to stdout
+    #[arg(short, long)]
+    output: Option<PathBuf>,
+
+    /// Parameters to specify the diagnostic format.
+    #[command(flatten)]
+    pub diagnostic: DiagnosticArgs,
+}
+
+/// How a resolved attribute ended up in a group.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+enum ResolutionMode {
+    /// The attribute is defined by the group.
+    Definition,
+    /// The attribute is referenced by the group (`ref`).
+    Reference,
+    /// The attribute is inherited from an extended group (`extends`).
+    Extends,
+    /// The attribute is imported from an included group (`include` constraint).
+    Include,
+}
+
+impl ResolutionMode {
+    /// Returns the name of the resolution mode, as serialized.
+    fn name(self) -> &'static str {
+        match self {
+            ResolutionMode::Definition => "definition",
+            ResolutionMode::Reference => "reference",
+            ResolutionMode::Extends => "extends",
+            ResolutionMode::Include => "include",
+        }
+    }
+}
+
+/// Lineage of a resolved registry.
+#[derive(Debug, Serialize)]
+struct LineageReport {
+    /// The lineage of each group, sorted by group id.
+    groups: Vec<GroupLineageReport>,
+}
+
+/// Lineage of a resolved group.
+#[derive(Debug, Serialize)]
+struct GroupLineageReport {
+    /// The id of the group.
+    id: String,
+    /// The type of the group.
+    r#type: &'static str,
+    /// The file defining the group (and all its fields).
+    source_file: String,
+    /// The lineage of each resolved attribute of the group, sorted by name.
+    attributes: Vec<AttributeLineageReport>,
+}
+
+/// Lineage of a resolved attribute of a group.
+#[derive(Debug, Serialize)]
+struct AttributeLineageReport {
+    /// The name of the attribute.
+    name: String,
+    /// How the attribute ended up in the group.
+    mode: ResolutionMode,
+    /// The group the attribute originates from: the group defining it for a
+    /// definition or a reference, the extended or included group otherwise.
+    source_group: String,
+    /// The file defining the source group.
+    source_file: String,
+    /// The fields inherited from the source group.
+    #[serde(skip_serializing_if = "BTreeSet::is_empty")]
+    inherited_fields: BTreeSet<String>,
+    /// The fields overridden by the group or by one of its extended groups,
+    /// with the overriding group.
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    overridden_fields: BTreeMap<String, String>,
+}
+
+/// Report the lineage of the resolved attributes of the groups of a semantic
+/// convention registry.
+#[cfg(not(tarpaulin_include))]
+pub(crate) fn command(
+    logger: impl Logger + Sync + Clone,
+    cache: &Cache,
+    args: &RegistryLineageArgs,
+) -> Result<ExitDirectives, DiagnosticMessages> {
+    if args.output.is_none() {
+        logger.mute();
+    }
+    logger.loading(&format!(
+        "Reporting the lineage of the registry `{}`",
+        args.registry.registries()
+    ));
+
+    let registry_id = "default";
+    let semconv_specs = load_semconv_specs(
+        &args.registry.registry_paths(),
+        &args.registry.file_filter()?,
+        cache,
+        logger.clone(),
+    )?;
+    let mut registry = SemConvRegistry::from_semconv_specs(registry_id, semconv_specs.clone());
+    let schema = resolve_semconv_specs(&mut registry, logger.clone())?;
+    let report = LineageReport::new(
+        &semconv_specs,
+        schema
+            .registry(registry_id)
+            .expect("Failed to get the registry from the resolved schema"),
+        schema.catalog(),
+    );
+
+    let output = match args.format {
+        LineageFormat::Json => {
+            let mut json = serde_json::to_string_pretty(&report)
+                .expect("Failed to serialize the lineage report");
+            json.push('\n');
+            json
+        }
+        LineageFormat::Markdown => report.to_markdown(),
+    };
+    if let Some(path) = &args.output {
+        std::fs::write(path, output).map_err(|e| Error::LineageWriteFailed {
+            output: path.clone(),
+            error: e.to_string(),
+        })?;
+        logger.success(&format!("Lineage report written to `{}`", path.display()));
+    } else {
+        print!("{}", output);
+    }
+
+    Ok(ExitDirectives {
+        exit_code: 0,
+        quiet_mode: args.output.is_none(),
+    })
+}
+
+/// Returns how an attribute is declared by a group spec, if it is.
+fn declaration(group: &GroupSpec, name: &str) -> Option<ResolutionMode> {
+    group.attributes.iter().find_map(|attr| match attr {
+        AttributeSpec::Id { id, .. } if qualified_id(group, id) == name => {
+            Some(ResolutionMode::Definition)
+        }
+        AttributeSpec::Ref { r#ref, .. } if r#ref == name => Some(ResolutionMode::Reference),
+        _ => None,
+    })
+}
+
+impl LineageReport {
+    /// Builds the lineage report of a resolved registry from the semantic
+    /// convention specifications it has been resolved from.
+    fn new(
+        semconv_specs: &[(String, SemConvSpec)],
+        registry: &Registry,
+        catalog: &Catalog,
+    ) -> Self {
+        // Group specs and source files, by group id.
+        let group_specs: HashMap<&str, (&GroupSpec, &str)> = semconv_specs
+            .iter()
+            .flat_map(|(provenance, spec)| {
+                spec.groups()
+                    .iter()
+                    .map(move |group| (group.id.as_str(), (group, provenance.as_str())))
+            })
+            .collect();
+        // Group defining each attribute.
+        let definitions: HashMap<String, &str> = group_specs
+            .values()
+            .flat_map(|(group, _)| {
+                group.attributes.iter().filter_map(move |attr| match attr {
+                    AttributeSpec::Id { id, .. } => {
+                        Some((qualified_id(group, id), group.id.as_str()))
+                    }
+                    AttributeSpec::Ref { .. } => None,
+                })
+            })
+            .collect();
+        // Names of the resolved attributes of each group.
+        let resolved_attributes: HashMap<&str, HashSet<&str>> = registry
+            .groups
+            .iter()
+            .map(|group| {
+                let names = group
+                    .attributes
+                    .iter()
+                    .filter_map(|attr_ref| catalog.attribute_name(attr_ref))
+                    .collect();
+                (group.id.as_str(), names)
+            })
+            .collect();
+        let source_file = |group_id: &str| {
+            group_specs
+                .get(group_id)
+                .map_or("unknown", |(_, provenance)| provenance)
+                .to_owned()
+        };
+
+        // Returns the resolution mode and the source group of an attribute of
+        // a group. Inherited attributes are traced back to the nearest extended
+        // group declaring them, the attributes imported by the `extends` chain
+        // from an included group are attributed to the extended group.
+        let origin = |group: &GroupSpec, name: &str| -> (ResolutionMode, String) {
+            let defining_group = |default: &str| {
+                definitions
+                    .get(name)
+                    .map_or(default, |group_id| *group_id)
+                    .to_owned()
+            };
+            match declaration(group, name) {
+                Some(ResolutionMode::Definition) => {
+                    return (ResolutionMode::Definition, group.id.clone())
+                }
+                Some(mode) => return (mode, defining_group(&group.id)),
+                None => {}
+            }
+            let mut visited = HashSet::new();
+            let mut parent = group.extends.as_deref();
+            while let Some(parent_id) = parent {
+                if !visited.insert(parent_id) {
+                    break;
+                }
+                let Some((parent_spec, _)) = group_specs.get(parent_id) else {
+                    break;
+                };
+                let inherited = declaration(parent_spec, name).is_some()
+                    || resolved_attributes
+                        .get(parent_id)
+                        .is_some_and(|names| names.contains(name))
+                        && parent_spec.extends.is_none();
+                if inherited {
+                    return (ResolutionMode::Extends, parent_id.to_owned());
+                }
+                parent = parent_spec.extends.as_deref();
+            }
+            for include in group.constraints.iter().filter_map(|c| c.include.as_ref()) {
+                if resolved_attributes
+                    .get(include.as_str())
+                    .is_some_and(|names| names.contains(name))
+                {
+                    return (ResolutionMode::Include, include.clone());
+                }
+            }
+            (ResolutionMode::Reference, defining_group("unknown"))
+        };
+
+        let mut groups: Vec<_> = registry
+            .groups
+            .iter()
+            .map(|group| {
+                let group_spec = group_specs.get(group.id.as_str()).map(|(spec, _)| *spec);
+                let mut attributes: Vec<_> = group
+                    .attributes
+                    .iter()
+                    .filter_map(|attr_ref| catalog.attribute_name(attr_ref))
+                    .map(|name| {
+                        let (mode, source_group) = match group_spec {
+                            Some(group_spec) => origin(group_spec, name),
+                            None => (ResolutionMode::Reference, "unknown".to_owned()),
+                        };
+                        let attr_lineage = group
+                            .lineage
+                            .as_ref()
+                            .and_then(|lineage| lineage.attribute(name));
+                        AttributeLineageReport {
+                            name: name.to_owned(),
+                            mode,
+                            source_file: source_file(&source_group),
+                            source_group,
+                            inherited_fields: attr_lineage
+                                .map(|lineage| lineage.inherited_fields.clone())
+                                .unwrap_or_default(),
+                            overridden_fields: attr_lineage
+                                .map(|lineage| {
+                                    lineage
+                                        .locally_overridden_fields
+                                        .iter()
+                                        .map(|field| {
+                                            let overriding_group = lineage
+                                                .overriding_group(field)
+                                                .unwrap_or(&group.id);
+                                            (field.clone(), overriding_group.to_owned())
+                                        })
+                                        .collect()
+                                })
+                                .unwrap_or_default(),
+                        }
+                    })
+                    .collect();
+                attributes.sort_by(|a, b| a.name.cmp(&b.name));
+                GroupLineageReport {
+                    id: group.id.clone(),
+                    r#type: group_type_name(&group.r#type),
+                    source_file: source_file(&group.id),
+                    attributes,
+                }
+            })
+            .collect();
+        groups.sort_by(|a, b| a.id.cmp(&b.id));
+        Self { groups }
+    }
+
+    /// Renders the report in Markdown, with a table of the attributes of each
+    /// group.
+    fn to_markdown(&self) -> String {
+        let fields = |fields: &BTreeSet<String>| {
+            fields
+                .iter()
+                .map(|field| format!("`{}`", field))
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        let overridden_fields = |fields: &BTreeMap<String, String>| {
+            fields
+                .iter()
+                .map(|(field, group_id)| format!("`{}` (`{}`)", field, group_id))
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        let mut markdown = String::from("# Registry lineage\n");
+        for group in &self.groups {
+            _ = write!(
+                markdown,
+                "\n## `{}` ({})\n\nSource file: `{}`\n",
+                group.id, group.r#type, group.source_file
+            );
+            if group.attributes.is_empty() {
+                continue;
+            }
+            markdown.push_str(
+                "\n| Attribute | Resolution | Source group | Source file | Inherited fields | Overridden fields |\n",
+            );
+            markdown.push_str("|---|---|---|---|---|---|\n");
+            for attr in &group.attributes {
+                _ = writeln!(
+                    markdown,
+                    "| `{}` | {} | `{}` | `{}` | {} | {} |",
+                    attr.name,
+                    attr.mode.name(),
+                    attr.source_group,
+                    attr.source_file,
+                    fields(&attr.inherited_fields),
+                    overridden_fields(&attr.overridden_fields)
+                );
+            }
+        }
+        markdown
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use weaver_resolver::SchemaResolver;
+    use weaver_semconv::registry::SemConvRegistry;
+    use weaver_semconv::semconv::SemConvSpec;
+
+    use crate::registry::lineage::{LineageReport, ResolutionMode};
+
+    const REGISTRY: &str = r#"groups:
+  - id: registry.http
+    type: attribute_group
+    brief: HTTP attributes.
+    prefix: http
+    attributes:
+      - id: request.method
+        type: string
+        brief: HTTP request method.
+        examples: [GET]
+      - id: route
+        type: string
+        brief: The matched route.
+        examples: ['/users/:id']
+  - id: attributes.http.common
+    type: attribute_group
+    brief: HTTP common attributes.
+    attributes:
+      - ref: http.request.method
+        requirement_level: required
+  - id: attributes.http.route
+    type: attribute_group
+    brief: HTTP route attributes.
+    attributes:
+      - ref: http.route
+  - id: span.http.server
+    type: span
+    span_kind: server
+    brief: HTTP server span.
+    extends: attributes.http.common
+    attributes:
+      - ref: http.request.method
+        brief: The method of the received request.
+    constraints:
+      - include: attributes.http.route
+"#;
+
+    #[test]
+    fn test_lineage_report() {
+        let spec = SemConvSpec::from_string(REGISTRY).expect("Failed to parse the registry");
+        let semconv_specs = vec![("http.yaml".to_owned(), spec)];
+        let mut registry = SemConvRegistry::from_semconv_specs("default", semconv_specs.clone());
+        let schema = SchemaResolver::resolve_semantic_convention_registry(&mut registry)
+            .expect("Failed to resolve the registry");
+        let report = LineageReport::new(
+            &semconv_specs,
+            schema.registry("default").expect("Registry not found"),
+            schema.catalog(),
+        );
+
+        let ids: Vec<_> = report.groups.iter().map(|g| g.id.as_str()).collect();
+        assert_eq!(
+            ids,
+            vec![
+                "attributes.http.common",
+                "attributes.http.route",
+                "registry.http",
+                "span.http.server"
+            ]
+        );
+
+        let registry_group = &report.groups[2];
+        assert_eq!(registry_group.r#type, "attribute_group");
+        assert_eq!(registry_group.source_file, "http.yaml");
+        assert!(registry_group
+            .attributes
+            .iter()
+            .all(|attr| attr.mode == ResolutionMode::Definition
+                && attr.source_group == "registry.http"));
+
+        let common = &report.groups[0];
+        assert_eq!(common.attributes[0].mode, ResolutionMode::Reference);
+        assert_eq!(common.attributes[0].source_group, "registry.http");
+        assert!(common.attributes[0]
+            .overridden_fields
+            .contains_key("requirement_level"));
+
+        let span = &report.groups[3];
+        let method = &span.attributes[0];
+        assert_eq!(method.name, "http.request.method");
+        assert_eq!(method.mode, ResolutionMode::Reference);
+        assert_eq!(method.overridden_fields["brief"], "span.http.server");
+        assert_eq!(
+            method.overridden_fields["requirement_level"],
+            "attributes.http.common"
+        );
+        let route = &span.attributes[1];
+        assert_eq!(route.name, "http.route");
+        assert_eq!(route.mode, ResolutionMode::Include);
+        assert_eq!(route.source_group, "attributes.http.route");
+        assert_eq!(route.source_file, "http.yaml");
+
+        let markdown = report.to_markdown();
+        assert!(markdown.contains("## `span.http.server` (span)"));
+        assert!(markdown
+            .contains("| `http.route` | include | `attributes.http.route` | `http.yaml` |  |  |"));
+        let json = serde_json::to_value(&report).expect("Failed to serialize the report");
+        assert_eq!(json["groups"][3]["attributes"][1]["mode"], "include");
+    }
+}
@@ -0,0 +1,378 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Link checker of a resolved semantic convention registry: the HTTP(S) links
+//! found in the `brief` and `note` of the groups and attributes are checked
+//! with HEAD requests, so the published documentation doesn't contain dead
+//! links.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::path::Path;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use rayon::prelude::{IntoParallelRefIterator, ParallelIterator};
+
+use weaver_common::diagnostic::DiagnosticMessages;
+use weaver_common::Logger;
+use weaver_forge::registry::ResolvedRegistry;
+
+use crate::registry::text_lint::TextField;
+use crate::registry::Error;
+
+/// The number of links checked concurrently.
+const CONCURRENCY: usize = 16;
+
+/// The timeout of the request checking a link.
+const TIMEOUT: Duration = Duration::from_secs(10);
+
+/// The duration during which a link found alive is not checked again.
+const CACHE_TTL: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// The location of a link in the text of a group or an attribute.
+#[derive(Debug, Clone, PartialEq)]
+struct LinkLocation {
+    /// The text field containing the link.
+    field: TextField,
+    /// The id of the group.
+    group: String,
+    /// The name of the attribute, empty for a link in the text of the group.
+    attr: String,
+    /// The file defining the group.
+    provenance: String,
+}
+
+/// The status of a checked link.
+#[derive(Debug, Clone, PartialEq)]
+enum LinkStatus {
+    /// The link resolves.
+    Alive,
+    /// The link doesn't resolve, with the reason.
+    Dead(String),
+    /// The link could not be checked, e.g. because of rate limiting. It is
+    /// neither reported nor cached.
+    Unknown,
+}
+
+/// Checks the HTTP(S) links found in the `brief` and `note` of the groups and
+/// attributes of the resolved registry, and reports the dead links as errors.
+///
+/// The links found alive are cached in `cache_file` (by URL, with the time of
+/// the check) and are not checked again for a day.
+pub(crate) fn check_links(
+    registry: &ResolvedRegistry,
+    cache_file: &Path,
+    logger: impl Logger,
+) -> Result<(), DiagnosticMessages> {
+    let links = extract_links(registry);
+    let mut cache = load_cache(cache_file);
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+    let to_check: Vec<&String> = links
+        .keys()
+        .filter(|url| {
+            cache.get(*url).map_or(true, |checked_at| {
+                now.saturating_sub(*checked_at) > CACHE_TTL.as_secs()
+            })
+        })
+        .collect();
+    logger.loading(&format!(
+        "Checking {} link(s) ({} cached)",
+        to_check.len(),
+        links.len() - to_check.len()
+    ));
+
+    let statuses = check_urls(&to_check);
+    let mut errors = vec![];
+    for (url, status) in to_check.into_iter().zip(statuses) {
+        match status {
+            LinkStatus::Alive => _ = cache.insert(url.clone(), now),
+            LinkStatus::Dead(reason) => {
+                _ = cache.remove(url);
+                errors.extend(links[url].iter().map(|location| Error::DeadLink {
+                    url: url.clone(),
+                    reason: reason.clone(),
+                    field: location.field,
+                    group: location.group.clone(),
+                    attr: location.attr.clone(),
+                    provenance: location.provenance.clone(),
+                }));
+            }
+            LinkStatus::Unknown => {}
+        }
+    }
+    // The cache is an optimization, failing to write it doesn't fail the check.
+    if let Err(e) = save_cache(cache_file, &cache) {
+        logger.warn(&format!(
+            "Failed to write the link cache `{}`: {}",
+            cache_file.display(),
+            e
+        ));
+    }
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(DiagnosticMessages::from_errors(errors))
+    }
+}
+
+/// Returns the locations of the HTTP(S) links of the resolved registry, by URL
+/// (without fragment). The links of an attribute are reported once, in the
+/// first group containing the attribute.
+fn extract_links(registry: &ResolvedRegistry) -> BTreeMap<String, Vec<LinkLocation>> {
+    let mut links: BTreeMap<String, Vec<LinkLocation>> = BTreeMap::new();
+    let mut visited_attrs = BTreeSet::new();
+    for group in &registry.groups {
+        let provenance = group
+            .lineage
+            .as_ref()
+            .map(|lineage| lineage.source_file().to_owned())
+            .unwrap_or_default();
+        let group_texts = [
+            (TextField::Brief, group.brief.as_str()),
+            (TextField::Note, group.note.as_str()),
+        ];
+        let attr_texts = group
+            .attributes
+            .iter()
+            .filter(|attr| visited_attrs.insert(attr.name.as_str()))
+            .flat_map(|attr| {
+                [
+                    (attr.name.as_str(), TextField::Brief, attr.brief.as_str()),
+                    (attr.name.as_str(), TextField::Note, attr.note.as_str()),
+                ]
+            });
+        let texts = group_texts
+            .into_iter()
+            .map(|(field, text)| ("", field, text))
+            .chain(attr_texts);
+        for (attr, field, text) in texts {
+            for url in find_urls(text) {
+                let locations = links.entry(url).or_default();
+                let location = LinkLocation {
+                    field,
+                    group: group.id.clone(),
+                    attr: attr.to_owned(),
+                    provenance: provenance.clone(),
+                };
+                if !locations.contains(&location) {
+                    locations.push(location);
+                }
+            }
+        }
+    }
+    links
+}
+
+/// Returns the HTTP(S) URLs of a Markdown text, in markdown links
+/// (`[text](url)`), autolinks (`<url>`), or bare URLs. The fragments are
+/// removed.
+fn find_urls(text: &str) -> Vec<String> {
+    let mut urls = vec![];
+    let mut rest = text;
+    while let Some(start) = ["http://", "https://"]
+        .iter()
+        .filter_map(|scheme| rest.find(scheme))
+        .min()
+    {
+        let candidate = &rest[start..];
+        // The URL ends with a whitespace, a Markdown or HTML delimiter, or a
+        // closing parenthesis not opened in the URL.
+        let mut depth = 0;
+        let end = candidate
+            .char_indices()
+            .find(|(_, c)| match c {
+                '(' => {
+                    depth += 1;
+                    false
+                }
+                ')' if depth > 0 => {
+                    depth -= 1;
+                    false
+                }
+                ')' | '>' | ']' | '"' | '\'' | '`' => true,
+                c => c.is_whitespace(),
+            })
+            .map_or(candidate.len(), |(i, _)| i);
+        let url = candidate[..end].trim_end_matches(['.', ',', ';', ':', '!', '?']);
+        let url = url.split('#').next().unwrap_or(url);
+        if url.len() > "https://".len() && !urls.iter().any(|u| u == url) {
+            urls.push(url.to_owned());
+        }
+        rest = &candidate[end.max(1)..];
+    }
+    urls
+}
+
+/// Checks the URLs concurrently and returns their status, in the same order.
+fn check_urls(urls: &[&String]) -> Vec<LinkStatus> {
+    let agent = ureq::AgentBuilder::new()
+        .timeout(TIMEOUT)
+        .user_agent(concat!("weaver/", env!("CARGO_PKG_VERSION")))
+        .build();
+    let check = || urls.par_iter().map(|url| check_url(&agent, url)).collect();
+    match rayon::ThreadPoolBuilder::new()
+        .num_threads(CONCURRENCY)
+        .build()
+    {
+        Ok(pool) => pool.install(check),
+        Err(_) => check(),
+    }
+}
+
+/// Checks a URL with a HEAD request. Some servers don't support HEAD
+/// requests, in which case a GET request is sent.
+fn check_url(agent: &ureq::Agent, url: &str) -> LinkStatus {
+    let status = match agent.head(url).call() {
+        Err(ureq::Error::Status(403 | 405 | 501, _)) => agent.get(url).call(),
+        result => result,
+    };
+    match status {
+        Ok(_) => LinkStatus::Alive,
+        Err(ureq::Error::Status(429, _)) => LinkStatus::Unknown,
+        Err(ureq::Error::Status(code, response)) => {
+            LinkStatus::Dead(format!("HTTP {} {}", code, response.status_text()))
+        }
+        Err(ureq::Error::Transport(e)) => LinkStatus::Dead(e.to_string()),
+    }
+}
+
+/// Loads the cache of the links found alive. A missing or invalid cache file
+/// is an empty cache.
+fn load_cache(cache_file: &Path) -> HashMap<String, u64> {
+    std::fs::read_to_string(cache_file)
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+/// Saves the cache of the links found alive.
+fn save_cache(cache_file: &Path, cache: &HashMap<String, u64>) -> std::io::Result<()> {
+    if let Some(dir) = cache_file.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    let cache: BTreeMap<_, _> = cache.iter().collect();
+    std::fs::write(cache_file, serde_json::to_string_pretty(&cache)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::TcpListener;
+
+    use tempdir::TempDir;
+    use weaver_common::TestLogger;
+    use weaver_forge::registry::ResolvedRegistry;
+    use weaver_resolver::SchemaResolver;
+    use weaver_semconv::registry::SemConvRegistry;
+
+    use super::*;
+
+    /// Starts an HTTP server answering `200 OK` to the requests of `/ok` and
+    /// `404 Not Found` otherwise, and returns its URL.
+    fn start_server() -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("Failed to bind the server");
+        let url = format!("http://{}", listener.local_addr().expect("No address"));
+        _ = std::thread::spawn(move || {
+            for mut stream in listener.incoming().flatten() {
+                let mut request_line = String::new();
+                _ = BufReader::new(&stream).read_line(&mut request_line);
+                let status = if request_line.contains(" /ok ") {
+                    "200 OK"
+                } else {
+                    "404 Not Found"
+                };
+                _ = write!(
+                    stream,
+                    "HTTP/1.1 {status}\r\nContent-Length: 0\r\nConnection: close\r\n\r\n"
+                );
+            }
+        });
+        url
+    }
+
+    fn resolved_registry(content: &str) -> ResolvedRegistry {
+        let mut registry = SemConvRegistry::new("default");
+        registry
+            .add_semconv_spec_from_string("http.yaml", content)
+            .expect("Failed to load the test registry");
+        let schema = SchemaResolver::resolve_semantic_convention_registry(&mut registry)
+            .expect("Failed to resolve the registry");
+        ResolvedRegistry::try_from_resolved_registry(
+            schema
+                .registry("default")
+                .expect("Failed to get the registry from the resolved schema"),
+            schema.catalog(),
+        )
+        .expect("Failed to create the resolved registry")
+    }
+
+    #[test]
+    fn test_find_urls() {
+        assert_eq!(
+            find_urls(
+                "See [the spec](https://example.com/spec#section), <http://example.com/a>, \
+                 https://en.wikipedia.org/wiki/Header_(computing). Or `https://example.com/spec`."
+            ),
+            vec![
+                "https://example.com/spec",
+                "http://example.com/a",
+                "https://en.wikipedia.org/wiki/Header_(computing)",
+            ]
+        );
+        assert!(find_urls("No link, only a relative one: [doc](../doc.md).").is_empty());
+    }
+
+    #[test]
+    fn test_check_links() {
+        let server = start_server();
+        let registry = resolved_registry(&format!(
+            r#"groups:
+  - id: registry.http
+    type: attribute_group
+    brief: HTTP attributes, see [the spec]({server}/ok).
+    prefix: http
+    attributes:
+      - id: request.method
+        type: string
+        brief: HTTP request method.
+        note: See <{server}/missing> and {server}/ok#method.
+        examples: [GET]
+  - id: span.http.client
+    type: span
+    span_kind: client
+    brief: HTTP client span.
+    attributes:
+      - ref: http.request.method
+"#
+        ));
+
+        let links = extract_links(&registry);
+        assert_eq!(
+            links.keys().collect::<Vec<_>>(),
+            [&format!("{server}/missing"), &format!("{server}/ok")]
+        );
+        // The links of an attribute are reported once.
+        assert_eq!(links[&format!("{server}/missing")].len(), 1);
+        assert_eq!(links[&format!("{server}/ok")].len(), 2);
+
+        let cache_dir = TempDir::new("weaver-links").expect("Failed to create a temp dir");
+        let cache_file = cache_dir.path().join("links.json");
+        let diag_msgs = check_links(&registry, &cache_file, TestLogger::new())
+            .expect_err("A dead link is expected");
+        assert_eq!(diag_msgs.len(), 1);
+        let json = serde_json::to_value(&diag_msgs).expect("Failed to serialize the findings");
+        let error = &json[0]["error"]["DeadLink"];
+        assert_eq!(error["url"], format!("{server}/missing"));
+        assert_eq!(error["reason"], "HTTP 404 Not Found");
+        assert_eq!(error["field"], "note");
+        assert_eq!(error["group"], "registry.http");
+        assert_eq!(error["attr"], "http.request.method");
+        assert_eq!(error["provenance"], "http.yaml");
+
+        // Only the links found alive are cached.
+        let cache = load_cache(&cache_file);
+        assert_eq!(cache.keys().collect::<Vec<_>>(), [&format!("{server}/ok")]);
+    }
+}
@@ -0,0 +1,399 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Merge several semantic convention registries into a single resolved
+//! registry.
+
+use std::collections::HashSet;
+use std::path::PathBuf;
+
+use clap::{Args, ValueEnum};
+
+use weaver_cache::Cache;
+use weaver_common::diagnostic::{DiagnosticMessage, DiagnosticMessages};
+use weaver_common::Logger;
+use weaver_forge::registry::ResolvedRegistry;
+use weaver_semconv::attribute::AttributeSpec;
+use weaver_semconv::group::GroupSpec;
+use weaver_semconv::registry::SemConvRegistry;
+use weaver_semconv::semconv::SemConvSpec;
+
+use crate::format::Format;
+use crate::registry::resolve::write_resolved_registry;
+use crate::registry::{Error, RegistryArgs};
+use crate::util::{load_registries, registry_conflicts, resolve_semconv_specs, RegistryConflict};
+use crate::{DiagnosticArgs, ExitDirectives};
+
+/// Policy applied to the groups and attributes defined by several registries.
+#[derive(Debug, Clone, Copy, PartialEq, ValueEnum)]
+pub enum ConflictPolicy {
+    /// Report the conflicting definitions as errors.
+    Error,
+    /// Keep the definition of the first registry and drop the other ones.
+    FirstWins,
+    /// Prefix the conflicting definitions with the namespace of their registry.
+    Prefix,
+}
+
+/// Parameters for the `registry merge` sub-command
+#[derive(Debug, Args)]
+pub struct RegistryMergeArgs {
+    /// Parameters to specify the semantic convention registries to merge
+    #[command(flatten)]
+    registry: RegistryArgs,
+
+    /// Policy applied to the groups and attributes defined by several
+    /// registries
+    #[arg(long, default_value = "error")]
+    on_conflict: ConflictPolicy,
+
+    /// Namespace of each registry, in the order of the `--registry` options.
+    /// Required by the `prefix` conflict policy, e.g. with `--namespace otel
+    /// --namespace acme`, an attribute `http.method` of the second registry
+    /// conflicting with the first registry is renamed `acme.http.method`
+    #[arg(long)]
+    namespace: Vec<String>,
+
+    /// Output file to write the merged registry to
+    /// If not specified, the merged registry is printed to stdout
+    #[arg(short, long)]
+    output: Option<PathBuf>,
+
+    /// Output format for the merged registry
+    /// Supported formats: yaml, json, protobuf, binary
+    /// Default format: yaml
+    #[arg(short, long, default_value = "yaml")]
+    format: Format,
+
+    /// Parameters to specify the diagnostic format.
+    #[command(flatten)]
+    pub diagnostic: DiagnosticArgs,
+}
+
+/// Merge several semantic convention registries and write the resolved
+/// registry to a file or print it to stdout.
+#[cfg(not(tarpaulin_include))]
+pub(crate) fn command(
+    logger: impl Logger + Sync + Clone,
+    cache: &Cache,
+    args: &RegistryMergeArgs,
+) -> Result<ExitDirectives, DiagnosticMessages> {
+    if args.output.is_none() {
+        logger.mute();
+    }
+    logger.loading(&format!(
+        "Merging registries `{}`",
+        args.registry.registries()
+    ));
+
+    let registry_id = "default";
+    let registry_paths = args.registry.registry_paths();
+    let registries = load_registries(
+        &registry_paths,
+        &args.registry.file_filter()?,
+        cache,
+        logger.clone(),
+    )?;
+    let semconv_specs = merge_registries(
+        registries,
+        args.on_conflict,
+        &args.namespace,
+        logger.clone(),
+    )?;
+
+    let mut registry = SemConvRegistry::from_semconv_specs(registry_id, semconv_specs);
+    let schema = resolve_semconv_specs(&mut registry, logger.clone())?;
+    let registry = ResolvedRegistry::try_from_resolved_registry(
+        schema
+            .registry(registry_id)
+            .expect("Failed to get the registry from the resolved schema"),
+        schema.catalog(),
+    )
+    .unwrap_or_else(|e| panic!("Failed to create the registry without catalog: {e:?}"));
+
+    write_resolved_registry(&registry, &args.format, args.output.as_deref())
+        .map_err(|error| Error::MergeWriteFailed { error })?;
+    logger.success("Registries merged");
+
+    Ok(ExitDirectives {
+        exit_code: 0,
+        quiet_mode: args.output.is_none(),
+    })
+}
+
+/// Merges the specifications of several registries, applying the conflict
+/// policy to the groups and attributes defined by several registries.
+fn merge_registries(
+    mut registries: Vec<Vec<(String, SemConvSpec)>>,
+    policy: ConflictPolicy,
+    namespaces: &[String],
+    logger: impl Logger,
+) -> Result<Vec<(String, SemConvSpec)>, DiagnosticMessages> {
+    if policy == ConflictPolicy::Prefix && namespaces.len() != registries.len() {
+        return Err(DiagnosticMessages::from_error(Error::InvalidNamespaces {
+            expected: registries.len(),
+            found: namespaces.len(),
+        }));
+    }
+
+    let conflicts = registry_conflicts(&registries);
+    if policy == ConflictPolicy::Error && !conflicts.is_empty() {
+        return Err(DiagnosticMessages::new(
+            conflicts
+                .into_iter()
+                .map(|conflict| {
+                    DiagnosticMessage::new(Error::ConflictingDefinition {
+                        kind: conflict.kind.to_owned(),
+                        id: conflict.id,
+                        first: conflict.first,
+                        second: conflict.second,
+                    })
+                })
+                .collect(),
+        ));
+    }
+
+    for (index, semconv_specs) in registries.iter_mut().enumerate() {
+        let conflicts: Vec<&RegistryConflict> = conflicts
+            .iter()
+            .filter(|conflict| conflict.registry == index)
+            .collect();
+        if conflicts.is_empty() {
+            continue;
+        }
+        for conflict in &conflicts {
+            let resolution = match policy {
+                ConflictPolicy::Prefix => {
+                    format!("renamed `{}.{}`", namespaces[index], conflict.id)
+                }
+                _ => format!("dropped in favor of `{}`", conflict.first),
+            };
+            logger.warn(&format!(
+                "The {} `{}` defined in `{}` is {}",
+                conflict.kind, conflict.id, conflict.second, resolution
+            ));
+        }
+        let ids = |kind: &str| -> HashSet<String> {
+            conflicts
+                .iter()
+                .filter(|conflict| conflict.kind == kind)
+                .map(|conflict| conflict.id.clone())
+                .collect()
+        };
+        let (groups, attributes) = (ids("group"), ids("attribute"));
+        for (_, spec) in semconv_specs.iter_mut() {
+            if policy == ConflictPolicy::Prefix {
+                for group in spec.groups_mut() {
+                    prefix_definitions(group, &namespaces[index], &groups, &attributes);
+                }
+            } else {
+                drop_definitions(spec, &groups, &attributes);
+            }
+        }
+    }
+
+    Ok(registries.into_iter().flatten().collect())
+}
+
+/// Returns the fully qualified id of an attribute defined by a group.
+fn qualified_id(group: &GroupSpec, id: &str) -> String {
+    if group.prefix.is_empty() {
+        id.to_owned()
+    } else {
+        format!("{}.{}", group.prefix, id)
+    }
+}
+
+/// Drops the given group and attribute definitions from a specification.
+fn drop_definitions(
+    spec: &mut SemConvSpec,
+    groups: &HashSet<String>,
+    attributes: &HashSet<String>,
+) {
+    spec.groups_mut()
+        .retain(|group| !groups.contains(&group.id));
+    for group in spec.groups_mut() {
+        let prefix = group.prefix.clone();
+        group.attributes.retain(|attr| match attr {
+            AttributeSpec::Id { id, .. } if prefix.is_empty() => !attributes.contains(id),
+            AttributeSpec::Id { id, .. } => !attributes.contains(&format!("{prefix}.{id}")),
+            AttributeSpec::Ref { .. } => true,
+        });
+    }
+}
+
+/// Prefixes the given group and attribute ids with a namespace, in the
+/// definitions and in the references of a group.
+///
+/// When one of the renamed attributes is defined by a group with a prefix, the
+/// prefix of the group is removed and the ids of its attributes are fully
+/// qualified.
+fn prefix_definitions(
+    group: &mut GroupSpec,
+    namespace: &str,
+    groups: &HashSet<String>,
+    attributes: &HashSet<String>,
+) {
+    let rename = |id: &mut String, ids: &HashSet<String>| {
+        if ids.contains(id.as_str()) {
+            *id = format!("{namespace}.{id}");
+        }
+    };
+
+    rename(&mut group.id, groups);
+    if let Some(extends) = group.extends.as_mut() {
+        rename(extends, groups);
+    }
+    for constraint in &mut group.constraints {
+        if let Some(include) = constraint.include.as_mut() {
+            rename(include, groups);
+        }
+        for id in &mut constraint.any_of {
+            rename(id, attributes);
+        }
+    }
+
+    let renames_attribute = group.attributes.iter().any(|attr| match attr {
+        AttributeSpec::Id { id, .. } => attributes.contains(&qualified_id(group, id)),
+        AttributeSpec::Ref { .. } => false,
+    });
+    if renames_attribute && !group.prefix.is_empty() {
+        let prefix = std::mem::take(&mut group.prefix);
+        for attr in &mut group.attributes {
+            if let AttributeSpec::Id { id, .. } = attr {
+                *id = format!("{prefix}.{id}");
+            }
+        }
+    }
+    for attr in &mut group.attributes {
+        match attr {
+            AttributeSpec::Id { id, .. } => rename(id, attributes),
+            AttributeSpec::Ref { r#ref, .. } => rename(r#ref, attributes),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use weaver_common::TestLogger;
+    use weaver_semconv::attribute::AttributeSpec;
+    use weaver_semconv::semconv::SemConvSpec;
+
+    use crate::registry::merge::{merge_registries, ConflictPolicy};
+
+    const UPSTREAM: &str = r#"groups:
+  - id: registry.http
+    type: attribute_group
+    brief: HTTP attributes.
+    prefix: http
+    attributes:
+      - id: request.method
+        type: string
+        brief: HTTP request method.
+        examples: [GET]
+"#;
+
+    const VENDOR: &str = r#"groups:
+  - id: registry.http
+    type: attribute_group
+    brief: Vendor HTTP attributes.
+    prefix: http
+    attributes:
+      - id: request.method
+        type: string
+        brief: Vendor HTTP request method.
+        examples: [GET]
+      - id: request.tenant
+        type: string
+        brief: Tenant of the request.
+        examples: [acme]
+  - id: span.acme.http
+    type: span
+    span_kind: client
+    brief: Vendor HTTP span.
+    extends: registry.http
+    attributes:
+      - ref: http.request.method
+"#;
+
+    fn registries() -> Vec<Vec<(String, SemConvSpec)>> {
+        vec![
+            vec![(
+                "upstream.yaml".to_owned(),
+                SemConvSpec::from_string(UPSTREAM).expect("Invalid registry"),
+            )],
+            vec![(
+                "vendor.yaml".to_owned(),
+                SemConvSpec::from_string(VENDOR).expect("Invalid registry"),
+            )],
+        ]
+    }
+
+    /// Returns the ids of the groups and the defined or referenced attributes.
+    fn ids(specs: &[(String, SemConvSpec)]) -> Vec<String> {
+        specs
+            .iter()
+            .flat_map(|(_, spec)| spec.groups())
+            .flat_map(|group| {
+                let attributes = group.attributes.iter().map(|attr| match attr {
+                    AttributeSpec::Id { id, .. } if group.prefix.is_empty() => id.clone(),
+                    AttributeSpec::Id { id, .. } => format!("{}.{}", group.prefix, id),
+                    AttributeSpec::Ref { r#ref, .. } => format!("ref {}", r#ref),
+                });
+                std::iter::once(format!(
+                    "{} extends {}",
+                    group.id,
+                    group.extends.as_deref().unwrap_or("-")
+                ))
+                .chain(attributes)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_merge_registries() {
+        let logger = TestLogger::new();
+
+        let diag_msgs = merge_registries(registries(), ConflictPolicy::Error, &[], logger.clone())
+            .expect_err("Conflicts expected");
+        assert_eq!(diag_msgs.len(), 2);
+
+        let specs = merge_registries(registries(), ConflictPolicy::FirstWins, &[], logger.clone())
+            .expect("Failed to merge the registries");
+        assert_eq!(
+            ids(&specs),
+            vec![
+                "registry.http extends -",
+                "http.request.method",
+                "span.acme.http extends registry.http",
+                "ref http.request.method",
+            ]
+        );
+        assert_eq!(logger.warn_count(), 2);
+
+        let namespaces = vec!["otel".to_owned(), "acme".to_owned()];
+        let specs = merge_registries(
+            registries(),
+            ConflictPolicy::Prefix,
+            &namespaces,
+            logger.clone(),
+        )
+        .expect("Failed to merge the registries");
+        assert_eq!(
+            ids(&specs),
+            vec![
+                "registry.http extends -",
+                "http.request.method",
+                "acme.registry.http extends -",
+                "acme.http.request.method",
+                "http.request.tenant",
+                "span.acme.http extends acme.registry.http",
+                "ref acme.http.request.method",
+            ]
+        );
+
+        // The prefix policy requires a namespace per registry.
+        let diag_msgs = merge_registries(registries(), ConflictPolicy::Prefix, &[], logger)
+            .expect_err("Missing namespaces expected");
+        assert_eq!(diag_msgs.len(), 1);
+    }
+}
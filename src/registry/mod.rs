@@ -10,25 +10,73 @@ use clap::{Args, Subcommand};
 use miette::Diagnostic;
 use serde::Serialize;
 
+use crate::registry::arrow_schema::RegistryArrowSchemaArgs;
+use crate::registry::collector::RegistryCollectorConfigArgs;
+use crate::registry::deprecations::RegistryDeprecationsArgs;
+use crate::registry::docs::RegistryDocsArgs;
+use crate::registry::export::RegistryExportArgs;
+use crate::registry::flatten::RegistryFlattenArgs;
+use crate::registry::fmt::RegistryFmtArgs;
 use crate::registry::generate::RegistryGenerateArgs;
+use crate::registry::graph::RegistryGraphArgs;
+use crate::registry::import::RegistryImportArgs;
 use crate::registry::json_schema::RegistryJsonSchemaArgs;
+use crate::registry::lineage::RegistryLineageArgs;
+use crate::registry::merge::RegistryMergeArgs;
+use crate::registry::ottl::RegistryOttlArgs;
+use crate::registry::package::RegistryPackageArgs;
+use crate::registry::prometheus::RegistryPrometheusArgs;
+use crate::registry::query::RegistryQueryArgs;
+use crate::registry::rename_attribute::RegistryRenameAttributeArgs;
+use crate::registry::report::RegistryReportArgs;
 use crate::registry::resolve::RegistryResolveArgs;
+use crate::registry::resolve_app::RegistryResolveAppArgs;
+use crate::registry::schema_file::RegistrySchemaFileArgs;
 use crate::registry::search::RegistrySearchArgs;
 use crate::registry::stats::RegistryStatsArgs;
+use crate::registry::text_lint::TextField;
 use crate::registry::update_markdown::RegistryUpdateMarkdownArgs;
+use crate::util::semconv_registry_path_from;
 use crate::CmdResult;
 use check::RegistryCheckArgs;
-use weaver_cache::Cache;
+use weaver_cache::{Cache, CachePolicy};
 use weaver_common::diagnostic::{DiagnosticMessage, DiagnosticMessages};
 use weaver_common::Logger;
+use weaver_resolver::file_filter::RegistryFileFilter;
 
+mod arrow_schema;
+mod binary;
 mod check;
-mod generate;
+mod collector;
+mod dead_definitions;
+mod deprecations;
+mod docs;
+mod export;
+mod flatten;
+mod fmt;
+pub(crate) mod generate;
+mod graph;
+mod import;
 mod json_schema;
+mod lineage;
+mod links;
+mod merge;
+mod ottl;
+mod owners;
+mod package;
+mod prometheus;
+mod protobuf;
+mod query;
+mod rename_attribute;
+mod report;
 mod resolve;
-mod search;
+mod resolve_app;
+mod schema_file;
+pub(crate) mod search;
 mod stats;
+mod text_lint;
 mod update_markdown;
+mod watch;
 
 /// Errors emitted by the `registry` sub-commands
 #[derive(thiserror::Error, Debug, Serialize, Diagnostic)]
@@ -38,9 +86,237 @@ pub enum Error {
     #[error("The parameter `--param {param}` is invalid. {error}")]
     InvalidParam { param: String, error: String },
 
+    /// A baseline ref was given for a baseline registry that is not a Git URL.
+    #[error("The baseline `{baseline}` is not a Git URL, `--baseline-ref` only applies to a Git baseline.")]
+    BaselineRefWithoutGitUrl { baseline: String },
+
     /// Invalid params file passed to the command line
     #[error("The params file `{params_file}` is invalid. {error}")]
     InvalidParams { params_file: PathBuf, error: String },
+
+    /// The generated artifacts are not up to date with the registry and templates.
+    #[error("The generated artifacts in `{output}` are stale, run `weaver registry generate` to update them.")]
+    StaleGeneratedArtifacts { output: PathBuf },
+
+    /// The verification of the generated artifacts failed.
+    #[error("Failed to verify the generated artifacts in `{output}`. {error}")]
+    VerificationFailed { output: PathBuf, error: String },
+
+    /// The dry run of the generation failed.
+    #[error("Failed to compare the generated artifacts with the content of `{output}`. {error}")]
+    DryRunFailed { output: PathBuf, error: String },
+
+    /// The generated artifacts don't match the expected artifacts.
+    #[error("{mismatches} generated artifact(s) don't match the expected artifacts in `{expected}`, run with `--update` to update them.")]
+    UnexpectedTemplateOutput {
+        expected: PathBuf,
+        mismatches: usize,
+    },
+
+    /// Some policy tests failed.
+    #[error("{failed} of {total} policy test(s) failed.")]
+    PolicyTestsFailed { failed: usize, total: usize },
+
+    /// The telemetry schema file could not be written.
+    #[error("Failed to write the schema file `{output}`. {error}")]
+    SchemaFileWriteFailed { output: PathBuf, error: String },
+
+    /// The resolved application schema could not be written.
+    #[error("Failed to write the resolved application schema `{app_schema}`. {error}")]
+    AppSchemaWriteFailed { app_schema: PathBuf, error: String },
+
+    /// The transform processor configuration could not be written.
+    #[error("Failed to write the transform processor configuration `{output}`. {error}")]
+    OttlConfigWriteFailed { output: PathBuf, error: String },
+
+    /// The collector configuration could not be written.
+    #[error("Failed to write the collector configuration `{output}`. {error}")]
+    CollectorConfigWriteFailed { output: PathBuf, error: String },
+
+    /// The graph of the registry could not be written.
+    #[error("Failed to write the graph `{output}`. {error}")]
+    GraphWriteFailed { output: PathBuf, error: String },
+
+    /// The lineage report of the registry could not be written.
+    #[error("Failed to write the lineage report `{output}`. {error}")]
+    LineageWriteFailed { output: PathBuf, error: String },
+
+    /// A report on the registry could not be written.
+    #[error("Failed to write the report `{output}`. {error}")]
+    ReportWriteFailed { output: PathBuf, error: String },
+
+    /// The deprecation report of the registry could not be written.
+    #[error("Failed to write the deprecation report `{output}`. {error}")]
+    DeprecationsWriteFailed { output: PathBuf, error: String },
+
+    /// The attribute catalog could not be exported.
+    #[error("Failed to export the attribute catalog. {error}")]
+    CatalogExportFailed { error: String },
+
+    /// The Arrow or Parquet schema could not be written.
+    #[error("Failed to write the schema `{output}`. {error}")]
+    SchemaWriteFailed { output: PathBuf, error: String },
+
+    /// The flattened registry could not be written.
+    #[error("Failed to write the flattened registry `{output}`. {error}")]
+    FlattenWriteFailed { output: PathBuf, error: String },
+
+    /// The Prometheus metadata could not be written.
+    #[error("Failed to write the Prometheus metadata `{output}`. {error}")]
+    PrometheusWriteFailed { output: PathBuf, error: String },
+
+    /// The conversion of a metric to the Prometheus conventions is lossy.
+    #[error("The metric `{metric}` is exported as `{prometheus_name}`, which is lossy. {reason}")]
+    #[diagnostic(severity(Warning))]
+    LossyPrometheusNormalization {
+        metric: String,
+        prometheus_name: String,
+        reason: String,
+    },
+
+    /// The interactive terminal UI failed.
+    #[error("The interactive search failed. {error}")]
+    InteractiveSearchFailed { error: String },
+
+    /// The query of the resolved registry failed.
+    #[error("The query `{query}` failed. {error}")]
+    QueryFailed { query: String, error: String },
+
+    /// A group or an attribute is defined by several of the merged registries.
+    #[error("The {kind} `{id}` is defined in both `{first}` and `{second}`, which belong to different registries.")]
+    ConflictingDefinition {
+        kind: String,
+        id: String,
+        first: String,
+        second: String,
+    },
+
+    /// An attribute is defined outside of the namespaces declared by the
+    /// manifest of its registry.
+    #[error("The attribute `{attr}` (defined in `{provenance}`) is not under the namespaces declared by the registry manifest ({namespaces}).")]
+    UndeclaredNamespace {
+        attr: String,
+        namespaces: String,
+        provenance: String,
+    },
+
+    /// An attribute is defined under a namespace reserved by another registry.
+    #[error("The attribute `{attr}` (defined in `{provenance}`) is under the namespace `{namespace}` reserved by the registry `{registry}`.")]
+    ReservedNamespace {
+        attr: String,
+        namespace: String,
+        registry: String,
+        provenance: String,
+    },
+
+    /// The `prefix` conflict policy of the `registry merge` command requires a
+    /// namespace per registry.
+    #[error("The `prefix` conflict policy requires one `--namespace` per registry ({expected} expected, {found} found).")]
+    InvalidNamespaces { expected: usize, found: usize },
+
+    /// The merged registry could not be written.
+    #[error("Failed to write the merged registry. {error}")]
+    MergeWriteFailed { error: String },
+
+    /// The registry could not be packaged.
+    #[error("Failed to package the registry `{registry}`. {error}")]
+    PackageFailed { registry: PathBuf, error: String },
+
+    /// The registry archive could not be published.
+    #[error("Failed to publish the registry archive to `{url}`. {error}")]
+    PublishFailed { url: String, error: String },
+
+    /// A semantic convention file could not be formatted.
+    #[error("Failed to format `{path}`. {error}")]
+    FormatFailed { path: PathBuf, error: String },
+
+    /// Some semantic convention files are not formatted.
+    #[error("{count} semantic convention file(s) are not formatted, run `weaver registry fmt` to format them.")]
+    UnformattedFiles { count: usize },
+
+    /// An attribute could not be renamed.
+    #[error("Failed to rename the attribute `{old}` to `{new}`. {error}")]
+    AttributeRenameFailed {
+        old: String,
+        new: String,
+        error: String,
+    },
+
+    /// An attribute is not used by any signal group.
+    #[error("The attribute `{attr}` of the group `{group}` (defined in `{provenance}`) is not used by any span, event, metric, resource, or scope group.")]
+    #[diagnostic(severity(Warning))]
+    UnusedAttribute {
+        attr: String,
+        group: String,
+        provenance: String,
+    },
+
+    /// A group is neither extended nor used by any signal group.
+    #[error("The group `{group}` (defined in `{provenance}`) is neither extended nor used by any span, event, metric, resource, or scope group.")]
+    #[diagnostic(severity(Warning))]
+    OrphanGroup { group: String, provenance: String },
+
+    /// A word of the text of a group or an attribute is misspelled or
+    /// discouraged by the dictionary.
+    #[error("The word `{word}` in the {field} of the {} (defined in `{provenance}`) is misspelled or discouraged, use `{replacement}` instead.", text_lint::item(.group, .attr))]
+    #[diagnostic(severity(Warning))]
+    Misspelling {
+        word: String,
+        replacement: String,
+        field: TextField,
+        group: String,
+        attr: String,
+        provenance: String,
+    },
+
+    /// An RFC 2119 keyword of the text of a group or an attribute is not
+    /// capitalized.
+    #[error("The keyword `{keyword}` in the {field} of the {} (defined in `{provenance}`) is not capitalized, use `{}` as defined by RFC 2119 or rephrase the sentence.", text_lint::item(.group, .attr), .keyword.to_uppercase())]
+    #[diagnostic(severity(Warning))]
+    LowercaseNormativeKeyword {
+        keyword: String,
+        field: TextField,
+        group: String,
+        attr: String,
+        provenance: String,
+    },
+
+    /// A link of the text of a group or an attribute doesn't resolve.
+    #[error("The link `{url}` in the {field} of the {} (defined in `{provenance}`) is dead. {reason}", text_lint::item(.group, .attr))]
+    DeadLink {
+        url: String,
+        reason: String,
+        field: TextField,
+        group: String,
+        attr: String,
+        provenance: String,
+    },
+
+    /// The dictionary of the text lint could not be loaded.
+    #[error("Failed to load the dictionary `{path}`. {error}")]
+    InvalidDictionary { path: PathBuf, error: String },
+
+    /// The OpenAPI document could not be imported.
+    #[error("Failed to import the OpenAPI document `{spec}`. {error}")]
+    OpenApiImportFailed { spec: PathBuf, error: String },
+
+    /// A parameter of an OpenAPI document could not be fully converted into an
+    /// attribute.
+    #[error("The parameter {location} of the OpenAPI document can't be fully converted. {reason}")]
+    #[diagnostic(severity(Warning))]
+    UnsupportedOpenApiParameter { location: String, reason: String },
+
+    /// The draft attribute groups could not be written.
+    #[error("Failed to write the draft attribute groups `{output}`. {error}")]
+    DraftWriteFailed { output: PathBuf, error: String },
+
+    /// The files of the registry could not be watched.
+    #[error("Failed to watch the registry files. {error}")]
+    WatchFailed { error: String },
+
+    /// The resolved registry passed with `--resolved-registry` could not be loaded.
+    #[error("Failed to load the resolved registry `{path}`, regenerate it with `weaver registry resolve --format binary`. {error}")]
+    InvalidResolvedRegistry { path: PathBuf, error: String },
 }
 
 impl From<Error> for DiagnosticMessages {
@@ -92,8 +368,22 @@ pub enum RegistrySubCommand {
     /// The process exits with a code of 0 if the resolution is successful.
     #[clap(verbatim_doc_comment)]
     Resolve(RegistryResolveArgs),
-    /// Searches a registry (not yet implemented).
+    /// Searches the attributes, metrics, and groups of a semantic convention registry.
+    ///
+    /// The query is fuzzy matched against the ids (attribute names, metric names, and group ids) and substring matched against the briefs and notes of the resolved registry. Matches are printed with their type, stability, defining group, and source file, the best matches first.
+    ///
+    /// Use `--interactive` to browse the registry in a terminal UI with incremental search, a details pane showing the lineage of the selected item, and keybindings to copy ids to the clipboard.
+    ///
+    /// Note: The `-d` and `--registry-git-sub-dir` options are only used when the registry is a Git URL otherwise these options are ignored.
+    #[clap(verbatim_doc_comment)]
     Search(RegistrySearchArgs),
+    /// Evaluates a jq expression against the resolved registry and prints the results as JSON lines.
+    ///
+    /// The expression is evaluated against the JSON representation of the resolved registry (as produced by `registry resolve --format json`), e.g. `weaver registry query '.groups[] | select(.type == "metric") | .metric_name'`. Each result is printed on its own line, which makes the output easy to consume from scripts.
+    ///
+    /// Note: The `-d` and `--registry-git-sub-dir` options are only used when the registry is a Git URL otherwise these options are ignored.
+    #[clap(verbatim_doc_comment)]
+    Query(RegistryQueryArgs),
     /// Calculate a set of general statistics on a semantic convention registry.
     Stats(RegistryStatsArgs),
     /// Update markdown files that contain markers indicating the templates used to update the specified sections.
@@ -103,10 +393,138 @@ pub enum RegistrySubCommand {
     /// The produced JSON Schema can be used to generate documentation of the resolved registry format or to generate code in your language of choice if you need to interact with the resolved registry format for any reason.
     #[clap(verbatim_doc_comment)]
     JsonSchema(RegistryJsonSchemaArgs),
+    /// Renders a semantic convention registry into a self-contained HTML documentation site.
+    ///
+    /// The site contains one page per namespace, links from attribute references to the registry groups defining them, a client-side search index, and stability/deprecation badges.
+    ///
+    /// Note: The `-d` and `--registry-git-sub-dir` options are only used when the registry is a Git URL otherwise these options are ignored.
+    #[clap(verbatim_doc_comment)]
+    Docs(RegistryDocsArgs),
+    /// Generates the telemetry schema file published at the schema URL of a registry.
+    ///
+    /// The transformations of each version (attribute and metric renames) are computed by diffing the resolved registry of this version with the resolved registry of the previous version. An attribute or a metric is renamed when its deprecation note references its replacement, e.g. "Replaced by `http.request.method`.".
+    ///
+    /// Note: The `-d` and `--registry-git-sub-dir` options are only used when the registries are Git URLs otherwise these options are ignored.
+    #[clap(verbatim_doc_comment)]
+    SchemaFile(RegistrySchemaFileArgs),
+    /// Resolves an application telemetry schema against a semantic convention registry.
+    ///
+    /// The application schema declares the resources, instrumentation scopes, spans, metrics, and events emitted by an application as references to the groups of the registry. The resolved application schema is self-contained (all the group references are replaced by the resolved groups) and can be used to generate client SDK code.
+    ///
+    /// Note: The `-d` and `--registry-git-sub-dir` options are only used when the registry is a Git URL otherwise these options are ignored.
+    #[clap(verbatim_doc_comment)]
+    ResolveApp(RegistryResolveAppArgs),
+    /// Merges several semantic convention registries into a single resolved registry.
+    ///
+    /// The groups and attributes defined by several registries are handled according to `--on-conflict`: `error` reports them, `first-wins` keeps the definition of the first registry and drops the other ones (dropping a group drops the attributes it defines), and `prefix` renames the conflicting definitions of each registry with its `--namespace` and updates the references of the registry accordingly. The merged registry is written in the same formats as `registry resolve`, e.g. to ship a combined registry of the OpenTelemetry and vendor-specific semantic conventions.
+    ///
+    /// Note: The `-d` and `--registry-git-sub-dir` options are only used when the registries are Git URLs otherwise these options are ignored.
+    #[clap(verbatim_doc_comment)]
+    Merge(RegistryMergeArgs),
+    /// Packages a local semantic convention registry into a distributable archive.
+    ///
+    /// The reproducible `.tar.gz` archive contains the semantic convention files of the registry, its manifest completed with the name and the version of the package, the resolved registry in JSON format (`resolved.json`), and the SHA-256 checksums of these files (`SHA256SUMS`). Once extracted, the archive can be used as a local registry.
+    ///
+    /// Use `--publish` to upload the archive to an HTTP(S) endpoint or to push it as an artifact to an OCI registry.
+    #[clap(verbatim_doc_comment)]
+    Package(RegistryPackageArgs),
+    /// Generates an OpenTelemetry Collector `transform` processor configuration bridging a baseline registry to the current registry.
+    ///
+    /// The OTTL statements rename the attributes and metrics of the baseline registry to their names in the current registry, so telemetry emitted with the previous version of the semantic conventions can be converted at the collector. Renames are computed from the deprecation notes of the current registry, as for the `schema-file` command.
+    ///
+    /// Note: The `-d` and `--registry-git-sub-dir` options are only used when the registries are Git URLs otherwise these options are ignored.
+    #[clap(verbatim_doc_comment)]
+    Ottl(RegistryOttlArgs),
+    /// Formats the semantic convention files of a local registry.
+    ///
+    /// The keys are sorted in a canonical order, the attributes of each group are sorted by id, the indentation and quoting are normalized, and the long briefs and notes are wrapped. Files containing comments (other than a header at the top of the file), anchors and aliases, or merge keys are skipped, as these can't be preserved.
+    ///
+    /// Use `--check` in CI to verify that the files are formatted without modifying them. The process exits with a code of 0 if all the files are formatted.
+    #[clap(verbatim_doc_comment)]
+    Fmt(RegistryFmtArgs),
+    /// Renames an attribute across the semantic convention files of a local registry.
+    ///
+    /// The definition of the attribute is renamed, its previous id is added to its `renamed_from` field, a deprecated attribute with the old id (keeping the type, examples, stability, and requirement level) is added after it, and the references, `any_of` constraints, and deprecation notes using the old id are updated. If the new id doesn't start with the prefix of the group defining the attribute, the prefix of the group is removed and the ids of its attributes are fully qualified. The comments and the layout of the files are preserved.
+    ///
+    /// Use `--dry-run` to print the changes without modifying the files.
+    #[clap(verbatim_doc_comment)]
+    RenameAttribute(RegistryRenameAttributeArgs),
+    /// Exports the dependency graph of the groups of a registry in Graphviz DOT or Mermaid format.
+    ///
+    /// The nodes are the groups of the registry and the edges are the `extends` relations, the `include` constraints, and the attribute references (from a group to the group defining the attribute). Use `--namespace` to only keep the dependencies from or to the groups of some namespaces. The longest `extends` chain is reported to help spotting overly deep inheritance.
+    ///
+    /// Note: The `-d` and `--registry-git-sub-dir` options are only used when the registry is a Git URL otherwise these options are ignored.
+    #[clap(verbatim_doc_comment)]
+    Graph(RegistryGraphArgs),
+    /// Reports the lineage of the resolved attributes of each group of a registry in JSON or Markdown format.
+    ///
+    /// For each group, the report gives the file defining it and, for each of its resolved attributes, the group the attribute originates from, the file defining that group, how the attribute was resolved (`definition`, `reference`, `extends`, or `include`), and the fields inherited from the source or overridden along with the overriding group.
+    ///
+    /// Note: The `-d` and `--registry-git-sub-dir` options are only used when the registry is a Git URL otherwise these options are ignored.
+    #[clap(verbatim_doc_comment)]
+    Lineage(RegistryLineageArgs),
+    /// Generates reports on a semantic convention registry for reviews, in Markdown or JSON format.
+    ///
+    /// With `--sensitivity`, the report gives the number of attributes per sensitivity level and lists the attributes classified as `low`, `pii`, or `secret` (the most sensitive first) with the spans, events, metrics, resources, and scopes using them and their requirement level, for privacy reviews.
+    ///
+    /// Note: The `-d` and `--registry-git-sub-dir` options are only used when the registry is a Git URL otherwise these options are ignored.
+    #[clap(verbatim_doc_comment)]
+    Report(RegistryReportArgs),
+    /// Reports the deprecated attributes and groups of a registry in Markdown or JSON format.
+    ///
+    /// For each deprecated item, the report gives its deprecation note, the replacement declared by the note (`Replaced by ...`), the file defining it, and the non-deprecated groups still referencing it (attribute references, `extends` clauses, and `include` constraints). With `--registry-version`, the report also gives the released version the item has been deprecated in, or `unreleased` if no released version deprecates it.
+    ///
+    /// Note: The `-d` and `--registry-git-sub-dir` options are only used when the registry is a Git URL otherwise these options are ignored.
+    #[clap(verbatim_doc_comment)]
+    Deprecations(RegistryDeprecationsArgs),
+    /// Exports the attribute catalog of a registry in CSV, Parquet, or JSON-LD format.
+    ///
+    /// The export contains one row per attribute with its type, stability, deprecation note, brief, the group defining it, the signal groups (spans, events, metrics, resources, scopes) using it, and the number of signal groups using it per requirement level. The Parquet format requires an output file, the CSV export is printed to stdout if no output file is specified.
+    ///
+    /// The JSON-LD export describes the groups (as schema.org `DefinedTermSet`s) and the attributes (as `DefinedTerm`s) with stable IRIs derived from the Git URL of the registry or from `--base-iri`, for data catalogs and knowledge graphs.
+    ///
+    /// Note: The `-d` and `--registry-git-sub-dir` options are only used when the registry is a Git URL otherwise these options are ignored.
+    #[clap(verbatim_doc_comment)]
+    Export(RegistryExportArgs),
+    /// Generates the Apache Arrow schemas of the signal types (span, event, metric, resource, scope) of a registry.
+    ///
+    /// An Arrow IPC file without any record batch is written per signal type in the output directory (e.g. `span.arrow`). The columns are the attributes used by the groups of the signal type, sorted by name, and are all nullable. Arrays are mapped to lists, template attributes to maps, and string enums to dictionary encoded strings. The brief, stability, and deprecation note of the attributes are kept in the metadata of the fields. Use `--parquet` to also write an empty Parquet file with the same schema per signal type.
+    ///
+    /// Note: The `-d` and `--registry-git-sub-dir` options are only used when the registry is a Git URL otherwise these options are ignored.
+    #[clap(verbatim_doc_comment)]
+    ArrowSchema(RegistryArrowSchemaArgs),
+    /// Exports the metrics of a registry as Prometheus metadata (OpenMetrics text format or Prometheus metadata API JSON format).
+    ///
+    /// The metric names are normalized following the OpenTelemetry to Prometheus compatibility rules: the characters not allowed by Prometheus are replaced by `_`, the unit is appended to the name (e.g. `_seconds`), the gauges with a `1` unit get a `_ratio` suffix, and the counters a `_total` suffix. The instruments are mapped to the `counter`, `gauge` (gauges and up-down counters), and `histogram` types, and the brief of the metrics is used as help text.
+    ///
+    /// A warning is reported when the normalization is lossy: characters other than `.` replaced, units without Prometheus equivalent, or several metrics exported with the same name.
+    ///
+    /// Note: The `-d` and `--registry-git-sub-dir` options are only used when the registry is a Git URL otherwise these options are ignored.
+    #[clap(verbatim_doc_comment)]
+    Prometheus(RegistryPrometheusArgs),
+    /// Generates an OpenTelemetry Collector configuration enforcing the attributes of a registry at ingestion.
+    ///
+    /// The generated `attributes/semconv` and `resource/semconv` processors delete the deprecated attributes of the registry (the deprecated attributes used by resource groups are deleted from the resources, the other ones from the spans, logs, and data points). With `--allowlist`, a `transform/semconv` processor keeping only the attributes used by the groups of each signal type is generated as well. The signal types without any group in the registry are not filtered.
+    ///
+    /// Note: The `-d` and `--registry-git-sub-dir` options are only used when the registry is a Git URL otherwise these options are ignored.
+    #[clap(verbatim_doc_comment)]
+    CollectorConfig(RegistryCollectorConfigArgs),
+    /// Flattens a semantic convention registry: every resolved group carries its full attribute objects inline.
+    ///
+    /// The groups are sorted by id, their attributes (including the attributes inherited through `extends` or imported through `include` constraints) are sorted by name, and the lineage is dropped unless `--lineage` is set. Unlike the resolved schema, there are no catalog indices to follow, which makes the output easy to inspect or diff. Use `--per-group` to write one document per group (a YAML stream, or JSON lines).
+    ///
+    /// Note: The `-d` and `--registry-git-sub-dir` options are only used when the registry is a Git URL otherwise these options are ignored.
+    #[clap(verbatim_doc_comment)]
+    Flatten(RegistryFlattenArgs),
+    /// Imports semantic conventions from other specification formats (e.g. OpenAPI).
+    ///
+    /// The imported definitions are written as draft semantic convention files to review before adding them to a registry.
+    #[clap(verbatim_doc_comment)]
+    Import(RegistryImportArgs),
 }
 
 /// Path to a semantic convention registry.
-/// The path can be a local directory or a Git URL.
+/// The path can be a local directory, a Git URL, or an OCI reference.
 #[derive(Debug, Clone)]
 pub enum RegistryPath {
     Local(String),
@@ -120,7 +538,7 @@ impl FromStr for RegistryPath {
 
     /// Parse a string into a `RegistryPath`.
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        if s.starts_with("http://") || s.starts_with("https://") {
+        if s.starts_with("http://") || s.starts_with("https://") || s.starts_with("oci://") {
             Ok(Self::Url(s.to_owned()))
         } else {
             Ok(Self::Local(s.to_owned()))
@@ -143,23 +561,68 @@ impl Display for RegistryPath {
 /// Set of parameters used to specify a semantic convention registry.
 #[derive(Args, Debug)]
 pub struct RegistryArgs {
-    /// Local path or Git URL of the semantic convention registry.
+    /// Local path, Git URL, or OCI reference (`oci://...`) of the semantic
+    /// convention registry. Repeat the option to merge several registries into
+    /// a single registry (e.g. the OpenTelemetry registry and a vendor-specific
+    /// registry).
     #[arg(
         short = 'r',
         long,
         default_value = "https://github.com/open-telemetry/semantic-conventions.git"
     )]
-    pub registry: RegistryPath,
+    pub registry: Vec<RegistryPath>,
 
     /// Optional path in the Git repository where the semantic convention
     /// registry is located
     #[arg(short = 'd', long, default_value = "model")]
     pub registry_git_sub_dir: Option<String>,
+
+    /// Glob pattern of the files to load from a local registry, relative to
+    /// the registry directory (e.g. `**/*.yaml`). Can be repeated. By default,
+    /// all the YAML files are loaded.
+    #[arg(long)]
+    pub include: Vec<String>,
+
+    /// Glob pattern of the files or directories to skip in a local registry,
+    /// relative to the registry directory (e.g. `deprecated/**`). Can be
+    /// repeated. The patterns listed in the `.weaverignore` file at the root of
+    /// the registry are skipped as well.
+    #[arg(long)]
+    pub exclude: Vec<String>,
+}
+
+impl RegistryArgs {
+    /// Returns the paths of the registries to load.
+    pub(crate) fn registry_paths(&self) -> Vec<weaver_semconv::path::RegistryPath> {
+        self.registry
+            .iter()
+            .map(|registry| semconv_registry_path_from(registry, &self.registry_git_sub_dir))
+            .collect()
+    }
+
+    /// Returns the filter selecting the files of the local registries.
+    pub(crate) fn file_filter(&self) -> Result<RegistryFileFilter, weaver_resolver::Error> {
+        RegistryFileFilter::try_new(&self.include, &self.exclude)
+    }
+
+    /// Returns the comma-separated list of the registries, for display.
+    pub(crate) fn registries(&self) -> String {
+        self.registry
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
 }
 
 /// Manage a semantic convention registry and return the exit code.
-pub fn semconv_registry(log: impl Logger + Sync + Clone, command: &RegistryCommand) -> CmdResult {
-    let cache = match Cache::try_new() {
+pub fn semconv_registry(
+    log: impl Logger + Sync + Clone,
+    command: &RegistryCommand,
+    cache_policy: &CachePolicy,
+    debug: bool,
+) -> CmdResult {
+    let cache = match Cache::try_new_with_policy(*cache_policy) {
         Ok(cache) => cache,
         Err(e) => return CmdResult::new(Err(e.into()), None),
     };
@@ -170,7 +633,7 @@ pub fn semconv_registry(log: impl Logger + Sync + Clone, command: &RegistryComma
             Some(args.diagnostic.clone()),
         ),
         RegistrySubCommand::Generate(args) => CmdResult::new(
-            generate::command(log.clone(), &cache, args),
+            generate::command(log.clone(), &cache, args, debug),
             Some(args.diagnostic.clone()),
         ),
         RegistrySubCommand::Stats(args) => CmdResult::new(
@@ -181,7 +644,14 @@ pub fn semconv_registry(log: impl Logger + Sync + Clone, command: &RegistryComma
             resolve::command(log.clone(), &cache, args),
             Some(args.diagnostic.clone()),
         ),
-        RegistrySubCommand::Search(_) => unimplemented!(),
+        RegistrySubCommand::Search(args) => CmdResult::new(
+            search::command(log.clone(), &cache, args),
+            Some(args.diagnostic.clone()),
+        ),
+        RegistrySubCommand::Query(args) => CmdResult::new(
+            query::command(log.clone(), &cache, args),
+            Some(args.diagnostic.clone()),
+        ),
         RegistrySubCommand::UpdateMarkdown(args) => CmdResult::new(
             update_markdown::command(log.clone(), &cache, args),
             Some(args.diagnostic.clone()),
@@ -190,5 +660,74 @@ pub fn semconv_registry(log: impl Logger + Sync + Clone, command: &RegistryComma
             json_schema::command(log.clone(), &cache, args),
             Some(args.diagnostic.clone()),
         ),
+        RegistrySubCommand::Docs(args) => CmdResult::new(
+            docs::command(log.clone(), &cache, args, debug),
+            Some(args.diagnostic.clone()),
+        ),
+        RegistrySubCommand::SchemaFile(args) => CmdResult::new(
+            schema_file::command(log.clone(), &cache, args),
+            Some(args.diagnostic.clone()),
+        ),
+        RegistrySubCommand::ResolveApp(args) => CmdResult::new(
+            resolve_app::command(log.clone(), &cache, args),
+            Some(args.diagnostic.clone()),
+        ),
+        RegistrySubCommand::Merge(args) => CmdResult::new(
+            merge::command(log.clone(), &cache, args),
+            Some(args.diagnostic.clone()),
+        ),
+        RegistrySubCommand::Package(args) => CmdResult::new(
+            package::command(log.clone(), &cache, args),
+            Some(args.diagnostic.clone()),
+        ),
+        RegistrySubCommand::Ottl(args) => CmdResult::new(
+            ottl::command(log.clone(), &cache, args),
+            Some(args.diagnostic.clone()),
+        ),
+        RegistrySubCommand::Fmt(args) => CmdResult::new(
+            fmt::command(log.clone(), args),
+            Some(args.diagnostic.clone()),
+        ),
+        RegistrySubCommand::Graph(args) => CmdResult::new(
+            graph::command(log.clone(), &cache, args),
+            Some(args.diagnostic.clone()),
+        ),
+        RegistrySubCommand::Lineage(args) => CmdResult::new(
+            lineage::command(log.clone(), &cache, args),
+            Some(args.diagnostic.clone()),
+        ),
+        RegistrySubCommand::Report(args) => CmdResult::new(
+            report::command(log.clone(), &cache, args),
+            Some(args.diagnostic.clone()),
+        ),
+        RegistrySubCommand::Deprecations(args) => CmdResult::new(
+            deprecations::command(log.clone(), &cache, args),
+            Some(args.diagnostic.clone()),
+        ),
+        RegistrySubCommand::RenameAttribute(args) => CmdResult::new(
+            rename_attribute::command(log.clone(), args),
+            Some(args.diagnostic.clone()),
+        ),
+        RegistrySubCommand::Export(args) => CmdResult::new(
+            export::command(log.clone(), &cache, args),
+            Some(args.diagnostic.clone()),
+        ),
+        RegistrySubCommand::ArrowSchema(args) => CmdResult::new(
+            arrow_schema::command(log.clone(), &cache, args),
+            Some(args.diagnostic.clone()),
+        ),
+        RegistrySubCommand::Prometheus(args) => CmdResult::new(
+            prometheus::command(log.clone(), &cache, args),
+            Some(args.diagnostic.clone()),
+        ),
+        RegistrySubCommand::CollectorConfig(args) => CmdResult::new(
+            collector::command(log.clone(), &cache, args),
+            Some(args.diagnostic.clone()),
+        ),
+        RegistrySubCommand::Flatten(args) => CmdResult::new(
+            flatten::command(log.clone(), &cache, args),
+            Some(args.diagnostic.clone()),
+        ),
+        RegistrySubCommand::Import(args) => import::import(log.clone(), args),
     }
 }
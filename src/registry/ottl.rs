@@ -0,0 +1,167 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Generate an OpenTelemetry Collector `transform` processor configuration
+//! bridging two versions of a semantic convention registry.
+
+use std::path::PathBuf;
+
+use clap::Args;
+
+use weaver_cache::Cache;
+use weaver_common::diagnostic::DiagnosticMessages;
+use weaver_common::Logger;
+use weaver_forge::registry::ResolvedRegistry;
+use weaver_resolver::file_filter::RegistryFileFilter;
+use weaver_schema::diff::diff_registries;
+use weaver_schema::ottl::TransformProcessor;
+
+use crate::registry::{Error, RegistryArgs, RegistryPath};
+use crate::util::{resolve_registries, semconv_registry_path_from};
+use crate::{DiagnosticArgs, ExitDirectives};
+
+/// Parameters for the `registry ottl` sub-command
+#[derive(Debug, Args)]
+pub struct RegistryOttlArgs {
+    /// Parameters to specify the current semantic convention registry
+    #[command(flatten)]
+    registry: RegistryArgs,
+
+    /// Local path or Git URL of the baseline semantic convention registry,
+    /// i.e. the previous version of the registry.
+    #[arg(long)]
+    pub baseline_registry: RegistryPath,
+
+    /// Output file to write the collector configuration to.
+    /// If not specified, the configuration is printed to stdout
+    #[arg(short, long)]
+    pub output: Option<PathBuf>,
+
+    /// Parameters to specify the diagnostic format.
+    #[command(flatten)]
+    pub diagnostic: DiagnosticArgs,
+}
+
+/// Generate the `transform` processor configuration renaming the attributes
+/// and metrics of the baseline registry to their names in the current registry.
+#[cfg(not(tarpaulin_include))]
+pub(crate) fn command(
+    logger: impl Logger + Sync + Clone,
+    cache: &Cache,
+    args: &RegistryOttlArgs,
+) -> Result<ExitDirectives, DiagnosticMessages> {
+    if args.output.is_none() {
+        logger.mute();
+    }
+
+    logger.loading(&format!(
+        "Resolving registries `{}` (baseline) and `{}`",
+        args.baseline_registry,
+        args.registry.registries()
+    ));
+    let baseline_paths = [semconv_registry_path_from(
+        &args.baseline_registry,
+        &args.registry.registry_git_sub_dir,
+    )];
+    let current_paths = args.registry.registry_paths();
+    // The baseline and the current registries are independent and are
+    // resolved concurrently.
+    let resolved = resolve_registries(
+        &[
+            (&baseline_paths, &RegistryFileFilter::default()),
+            (&current_paths, &args.registry.file_filter()?),
+        ],
+        cache,
+        logger.clone(),
+    )?;
+    let [baseline, current] = [&resolved[0].1, &resolved[1].1].map(|schema| {
+        ResolvedRegistry::try_from_resolved_registry(
+            schema
+                .registry("default")
+                .expect("Failed to get the registry from the resolved schema"),
+            schema.catalog(),
+        )
+    });
+    let (baseline, current) = (baseline?, current?);
+
+    let processor = TransformProcessor::from_spec(&diff_registries(&baseline, &current));
+    if processor.is_empty() {
+        logger.warn("No attribute or metric renamed between the two registries");
+    }
+    let yaml = processor.to_yaml()?;
+    if let Some(path) = &args.output {
+        std::fs::write(path, yaml).map_err(|e| Error::OttlConfigWriteFailed {
+            output: path.clone(),
+            error: e.to_string(),
+        })?;
+        logger.success(&format!(
+            "Transform processor configuration written to `{}`",
+            path.display()
+        ));
+    } else {
+        print!("{}", yaml);
+    }
+
+    Ok(ExitDirectives {
+        exit_code: 0,
+        quiet_mode: args.output.is_none(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use tempdir::TempDir;
+    use weaver_common::TestLogger;
+
+    use crate::cli::{Cli, Commands};
+    use crate::registry::ottl::RegistryOttlArgs;
+    use crate::registry::{RegistryArgs, RegistryCommand, RegistryPath, RegistrySubCommand};
+    use crate::run_command;
+
+    #[test]
+    fn test_registry_ottl() {
+        let logger = TestLogger::new();
+        let output_dir = TempDir::new("weaver-ottl").expect("Failed to create a temp dir");
+        let temp_output = output_dir.path().join("transform.yaml");
+        let cli = Cli {
+            debug: 0,
+            quiet: false,
+            cache: Default::default(),
+            command: Some(Commands::Registry(RegistryCommand {
+                command: RegistrySubCommand::Ottl(RegistryOttlArgs {
+                    registry: RegistryArgs {
+                        registry: vec![RegistryPath::Local(
+                            "crates/weaver_schema/data/v1.1.0".to_owned(),
+                        )],
+                        registry_git_sub_dir: None,
+                        include: vec![],
+                        exclude: vec![],
+                    },
+                    baseline_registry: RegistryPath::Local(
+                        "crates/weaver_schema/data/v1.0.0".to_owned(),
+                    ),
+                    output: Some(temp_output.clone()),
+                    diagnostic: Default::default(),
+                }),
+            })),
+        };
+
+        let exit_directive = run_command(&cli, logger.clone());
+        assert_eq!(exit_directive.exit_code, 0);
+        assert_eq!(logger.error_count(), 0);
+
+        let config: serde_yaml::Value = serde_yaml::from_str(
+            &std::fs::read_to_string(&temp_output).expect("Failed to read the configuration"),
+        )
+        .expect("Invalid configuration");
+        let metric_statements = &config["processors"]["transform"]["metric_statements"];
+        let metric = metric_statements
+            .as_sequence()
+            .and_then(|statements| statements.last())
+            .expect("No metric statements");
+        assert_eq!(metric["context"], "metric");
+        assert_eq!(
+            metric["statements"][0],
+            "set(name, \"http.server.request.duration\") where name == \"http.server.duration\""
+        );
+    }
+}
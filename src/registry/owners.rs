@@ -0,0 +1,175 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Routing of the findings and changes of a registry to the owners of its
+//! namespaces (see the `OWNERS` file of a local registry).
+
+use std::path::Path;
+
+use serde_json::Value;
+
+use weaver_common::diagnostic::DiagnosticMessages;
+use weaver_resolver::owners::Owners;
+
+use crate::registry::{RegistryArgs, RegistryPath};
+use crate::ExitDirectives;
+
+/// The fields of the serialized errors naming the attribute a finding relates
+/// to, by order of precedence.
+const ATTRIBUTE_FIELDS: [&str; 4] = ["attr", "attribute", "attribute_id", "attribute_ref"];
+
+/// The fields of the serialized errors naming the group a finding relates to,
+/// by order of precedence.
+const GROUP_FIELDS: [&str; 2] = ["group_id", "group"];
+
+/// Loads and merges the `OWNERS` files of the local registries. The Git
+/// registries have no owners.
+pub(crate) fn load_owners(registry: &RegistryArgs) -> Result<Owners, DiagnosticMessages> {
+    let mut owners = Owners::default();
+    for path in &registry.registry {
+        if let RegistryPath::Local(path) = path {
+            let path = Path::new(path);
+            if path.is_dir() {
+                owners.merge(Owners::from_registry_dir(path)?);
+            }
+        }
+    }
+    Ok(owners)
+}
+
+/// Returns the owners of the attribute or, failing that, of the group a
+/// finding relates to, from the fields of its serialized error.
+pub(crate) fn finding_owners(owners: &Owners, error: &Value) -> Vec<String> {
+    if let Some(attr) = find_field(error, &ATTRIBUTE_FIELDS) {
+        return owners.owners_of(attr).to_vec();
+    }
+    if let Some(group_id) = find_field(error, &GROUP_FIELDS) {
+        return owners.owners_of_group(group_id).to_vec();
+    }
+    vec![]
+}
+
+/// Returns the first non-empty string value of the given fields, looked up in
+/// the error and then in its nested errors (e.g. the violation of a policy
+/// violation).
+fn find_field<'a>(error: &'a Value, fields: &[&str]) -> Option<&'a str> {
+    let Value::Object(object) = error else {
+        return None;
+    };
+    fields
+        .iter()
+        .find_map(|field| object.get(*field)?.as_str().filter(|s| !s.is_empty()))
+        .or_else(|| {
+            object
+                .values()
+                .filter(|value| value.is_object())
+                .find_map(|value| find_field(value, fields))
+        })
+}
+
+/// Attributes the findings of a command to the owners of the items they relate
+/// to and, if `owner_filter` is not empty, only keeps the findings of these
+/// owners. The command succeeds if no finding is left.
+pub(crate) fn route_findings(
+    result: Result<ExitDirectives, DiagnosticMessages>,
+    owners: &Owners,
+    owner_filter: &[String],
+) -> Result<ExitDirectives, DiagnosticMessages> {
+    let Err(mut diag_msgs) = result else {
+        return result;
+    };
+    diag_msgs.assign_owners(|error| finding_owners(owners, error));
+    if !owner_filter.is_empty() {
+        diag_msgs.retain(|msg| {
+            msg.owners()
+                .iter()
+                .any(|owner| owner_filter.contains(owner))
+        });
+        if diag_msgs.is_empty() {
+            return Ok(ExitDirectives {
+                exit_code: 0,
+                quiet_mode: false,
+            });
+        }
+    }
+    Err(diag_msgs)
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use weaver_common::diagnostic::DiagnosticMessages;
+    use weaver_resolver::owners::Owners;
+
+    use crate::registry::owners::{finding_owners, route_findings};
+
+    #[test]
+    fn test_finding_owners() {
+        let owners = Owners::parse("* @org/approvers\nhttp @org/http-team\ndb @org/db-team");
+
+        // Policy violations relate to an attribute, or to a group if the
+        // attribute is empty.
+        let violation = json!({
+            "type": "policy_violation",
+            "provenance": "http.yaml",
+            "violation": {"type": "semconv_attribute", "id": "attr_name", "category": "naming", "group": "registry.db", "attr": "http.Method"}
+        });
+        assert_eq!(finding_owners(&owners, &violation), ["@org/http-team"]);
+        let violation = json!({
+            "type": "policy_violation",
+            "violation": {"type": "semconv_attribute", "group": "registry.db", "attr": ""}
+        });
+        assert_eq!(finding_owners(&owners, &violation), ["@org/db-team"]);
+
+        // Resolution errors relate to a group.
+        let error = json!({"type": "unresolved_include_ref", "group_id": "span.rpc.client", "include_ref": "attributes.rpc"});
+        assert_eq!(finding_owners(&owners, &error), ["@org/approvers"]);
+        let error = json!({"type": "unresolved_extends_ref", "group_id": "span.db.client"});
+        assert_eq!(finding_owners(&owners, &error), ["@org/db-team"]);
+
+        assert!(finding_owners(&owners, &json!({"type": "cancelled"})).is_empty());
+    }
+
+    #[test]
+    fn test_route_findings() {
+        let owners = Owners::parse("http @org/http-team\ndb @org/db-team");
+        let findings = || {
+            Err(DiagnosticMessages::from_errors(
+                ["span.http.client", "span.db.client", "span.rpc.client"]
+                    .map(|group_id| weaver_resolver::Error::UnresolvedExtendsRef {
+                        group_id: group_id.to_owned(),
+                        extends_ref: "unknown".to_owned(),
+                        provenance: "registry.yaml".to_owned(),
+                    })
+                    .to_vec(),
+            ))
+        };
+
+        // All the findings are reported, with their owners.
+        let Err(diag_msgs) = route_findings(findings(), &owners, &[]) else {
+            panic!("Expected findings");
+        };
+        let owners_of: Vec<_> = diag_msgs
+            .into_inner()
+            .iter()
+            .map(|msg| msg.owners().to_vec())
+            .collect();
+        assert_eq!(
+            owners_of,
+            vec![
+                vec!["@org/http-team".to_owned()],
+                vec!["@org/db-team".to_owned()],
+                vec![]
+            ]
+        );
+
+        // Only the findings of the given owners are reported.
+        let Err(diag_msgs) = route_findings(findings(), &owners, &["@org/db-team".to_owned()])
+        else {
+            panic!("Expected findings");
+        };
+        assert_eq!(diag_msgs.len(), 1);
+        let result = route_findings(findings(), &owners, &["@org/rpc-team".to_owned()]);
+        assert_eq!(result.expect("No finding expected").exit_code, 0);
+    }
+}
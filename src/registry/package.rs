@@ -0,0 +1,356 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Package a local semantic convention registry into a distributable archive
+//! and optionally publish it.
+//!
+//! The archive is a reproducible `.tar.gz` file containing the semantic
+//! convention files of the registry, its manifest (completed with the name and
+//! the version of the package), the resolved registry in JSON format
+//! (`resolved.json`), and the SHA-256 checksums of these files (`SHA256SUMS`,
+//! in the format of `sha256sum`). Once extracted, the archive can be used as
+//! a local registry.
+
+use std::collections::BTreeMap;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use clap::Args;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use sha2::{Digest, Sha256};
+
+use weaver_cache::oci::{sha256_digest, OciClient, OciReference, OCI_SCHEME};
+use weaver_cache::oci::{PASSWORD_ENV, USERNAME_ENV};
+use weaver_cache::verification::SIGNATURE_EXTENSION;
+use weaver_cache::Cache;
+use weaver_common::diagnostic::DiagnosticMessages;
+use weaver_common::Logger;
+use weaver_forge::registry::ResolvedRegistry;
+use weaver_resolver::file_filter::RegistryFileFilter;
+use weaver_semconv::manifest::{RegistryManifest, REGISTRY_MANIFEST};
+use weaver_semconv::registry::SemConvRegistry;
+
+use crate::registry::fmt::semconv_files;
+use crate::registry::Error;
+use crate::util::{load_semconv_specs, resolve_semconv_specs};
+use crate::{DiagnosticArgs, ExitDirectives};
+
+/// The name of the file containing the resolved registry in the archive.
+const RESOLVED_REGISTRY_FILE: &str = "resolved.json";
+
+/// The name of the file containing the checksums of the files of the archive.
+const CHECKSUMS_FILE: &str = "SHA256SUMS";
+
+/// Parameters for the `registry package` sub-command
+#[derive(Debug, Args)]
+pub struct RegistryPackageArgs {
+    /// Local path of the semantic convention registry to package.
+    #[arg(short = 'r', long)]
+    pub registry: PathBuf,
+
+    /// Version of the package. Defaults to the version declared by the
+    /// registry manifest.
+    #[arg(long)]
+    pub version: Option<String>,
+
+    /// Output file of the archive. Defaults to `<name>-<version>.tar.gz`,
+    /// where the name is the name declared by the registry manifest or the
+    /// name of the registry directory.
+    #[arg(short, long)]
+    pub output: Option<PathBuf>,
+
+    /// Publish the archive to an HTTP(S) URL (with a `PUT` request) or to an
+    /// OCI registry (e.g. `oci://ghcr.io/acme/semconv`, tagged with the
+    /// version of the package if no tag is specified). The credentials are
+    /// read from the `WEAVER_REGISTRY_USERNAME` and `WEAVER_REGISTRY_PASSWORD`
+    /// environment variables.
+    #[arg(long)]
+    pub publish: Option<String>,
+
+    /// Parameters to specify the diagnostic format.
+    #[command(flatten)]
+    pub diagnostic: DiagnosticArgs,
+}
+
+/// Package a local semantic convention registry.
+#[cfg(not(tarpaulin_include))]
+pub(crate) fn command(
+    logger: impl Logger + Sync + Clone,
+    cache: &Cache,
+    args: &RegistryPackageArgs,
+) -> Result<ExitDirectives, DiagnosticMessages> {
+    logger.loading(&format!("Packaging registry `{}`", args.registry.display()));
+    let package_error = |error: String| Error::PackageFailed {
+        registry: args.registry.clone(),
+        error,
+    };
+
+    let mut manifest =
+        RegistryManifest::try_from_registry_root(&args.registry)?.unwrap_or_default();
+    if manifest.name.is_none() {
+        manifest.name = args.registry.canonicalize().ok().and_then(|path| {
+            path.file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+        });
+    }
+    if let Some(version) = &args.version {
+        manifest.version = Some(version.clone());
+    }
+    let (Some(name), Some(version)) = (manifest.name.clone(), manifest.version.clone()) else {
+        return Err(package_error(
+            "The version of the package is missing, use `--version` or declare it in the registry manifest.".to_owned(),
+        )
+        .into());
+    };
+
+    // Resolve the registry.
+    let registry_path = weaver_semconv::path::RegistryPath::Local {
+        path_pattern: args.registry.display().to_string(),
+    };
+    let semconv_specs = load_semconv_specs(
+        &[registry_path],
+        &RegistryFileFilter::default(),
+        cache,
+        logger.clone(),
+    )?;
+    let mut registry = SemConvRegistry::from_semconv_specs("default", semconv_specs);
+    let schema = resolve_semconv_specs(&mut registry, logger.clone())?;
+    let resolved_registry = ResolvedRegistry::try_from_resolved_registry(
+        schema
+            .registry("default")
+            .expect("Failed to get the registry from the resolved schema"),
+        schema.catalog(),
+    )
+    .unwrap_or_else(|e| panic!("Failed to create the registry without catalog: {e:?}"));
+
+    let mut files = vec![];
+    for path in semconv_files(&args.registry)? {
+        let content = std::fs::read(&path).map_err(|e| package_error(e.to_string()))?;
+        let relative_path = path
+            .strip_prefix(&args.registry)
+            .unwrap_or(&path)
+            .to_string_lossy()
+            .replace('\\', "/");
+        files.push((relative_path, content));
+    }
+    files.push((
+        REGISTRY_MANIFEST.to_owned(),
+        serde_yaml::to_string(&manifest)
+            .map_err(|e| package_error(e.to_string()))?
+            .into_bytes(),
+    ));
+    files.push((
+        RESOLVED_REGISTRY_FILE.to_owned(),
+        serde_json::to_vec_pretty(&resolved_registry).map_err(|e| package_error(e.to_string()))?,
+    ));
+    let archive = archive(files).map_err(|e| package_error(e.to_string()))?;
+
+    let file_name = format!("{name}-{version}.tar.gz");
+    let output = args
+        .output
+        .clone()
+        .unwrap_or_else(|| PathBuf::from(&file_name));
+    std::fs::write(&output, &archive).map_err(|e| package_error(e.to_string()))?;
+    logger.success(&format!(
+        "Registry packaged into `{}` ({})",
+        output.display(),
+        sha256_digest(&archive)
+    ));
+
+    // A minisign signature of the archive (e.g. created with `minisign -Sm
+    // <archive>`) is published along with the archive.
+    let signature_path = PathBuf::from(format!("{}.{SIGNATURE_EXTENSION}", output.display()));
+    let signature = std::fs::read(&signature_path).ok();
+
+    if let Some(url) = &args.publish {
+        if signature.is_some() {
+            logger.log(&format!(
+                "Publishing the signature `{}`",
+                signature_path.display()
+            ));
+        }
+        let publish_error = |error: String| Error::PublishFailed {
+            url: url.clone(),
+            error,
+        };
+        if url.starts_with(OCI_SCHEME) {
+            let mut reference: OciReference = url.parse()?;
+            if reference.tag.is_none() && reference.digest.is_none() {
+                reference.tag = Some(version.clone());
+            }
+            let annotations = BTreeMap::from([
+                ("org.opencontainers.image.title".to_owned(), name),
+                ("org.opencontainers.image.version".to_owned(), version),
+            ]);
+            let digest = OciClient::new(reference.clone())?.push_registry_archive(
+                &file_name,
+                &archive,
+                signature.as_deref(),
+                &annotations,
+            )?;
+            logger.success(&format!("Registry published to `{reference}@{digest}`"));
+        } else {
+            publish_http(url, &archive, "application/gzip").map_err(publish_error)?;
+            if let Some(signature) = &signature {
+                publish_http(
+                    &format!("{url}.{SIGNATURE_EXTENSION}"),
+                    signature,
+                    "text/plain",
+                )
+                .map_err(publish_error)?;
+            }
+            logger.success(&format!("Registry published to `{url}`"));
+        }
+    }
+
+    Ok(ExitDirectives {
+        exit_code: 0,
+        quiet_mode: false,
+    })
+}
+
+/// Builds a reproducible `.tar.gz` archive of the given files (relative path
+/// and content), completed with the checksums of the files. The entries are
+/// sorted by path and their metadata (time, owner, mode) are normalized.
+fn archive(mut files: Vec<(String, Vec<u8>)>) -> std::io::Result<Vec<u8>> {
+    files.sort_by(|a, b| a.0.cmp(&b.0));
+    let checksums: String = files
+        .iter()
+        .map(|(path, content)| {
+            let hash: String = Sha256::digest(content)
+                .iter()
+                .map(|byte| format!("{:02x}", byte))
+                .collect();
+            format!("{hash}  {path}\n")
+        })
+        .collect();
+    files.push((CHECKSUMS_FILE.to_owned(), checksums.into_bytes()));
+
+    let mut builder = tar::Builder::new(GzEncoder::new(Vec::new(), Compression::default()));
+    for (path, content) in &files {
+        let mut header = tar::Header::new_gnu();
+        header.set_size(content.len() as u64);
+        header.set_mode(0o644);
+        header.set_mtime(0);
+        header.set_uid(0);
+        header.set_gid(0);
+        header.set_cksum();
+        builder.append_data(&mut header, Path::new(path), content.as_slice())?;
+    }
+    let mut encoder = builder.into_inner()?;
+    encoder.flush()?;
+    encoder.finish()
+}
+
+/// Uploads a file to an HTTP(S) URL with a `PUT` request, with basic
+/// authentication if credentials are set in the environment.
+fn publish_http(url: &str, content: &[u8], content_type: &str) -> Result<(), String> {
+    let mut request = weaver_common::http::agent(url)?
+        .put(url)
+        .set("Content-Type", content_type);
+    if let (Ok(username), Ok(password)) = (std::env::var(USERNAME_ENV), std::env::var(PASSWORD_ENV))
+    {
+        use base64::Engine;
+        let credentials =
+            base64::engine::general_purpose::STANDARD.encode(format!("{username}:{password}"));
+        request = request.set("Authorization", &format!("Basic {credentials}"));
+    }
+    _ = request.send_bytes(content).map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Read;
+
+    use flate2::read::GzDecoder;
+    use tempdir::TempDir;
+    use weaver_cache::oci::sha256_digest;
+    use weaver_common::TestLogger;
+    use weaver_resolver::file_filter::RegistryFileFilter;
+
+    use crate::cli::{Cli, Commands};
+    use crate::registry::package::RegistryPackageArgs;
+    use crate::registry::{RegistryCommand, RegistryPath, RegistrySubCommand};
+    use crate::run_command;
+    use crate::util::{load_semconv_specs, semconv_registry_path_from};
+
+    #[test]
+    fn test_registry_package() {
+        let logger = TestLogger::new();
+        let temp_dir = TempDir::new("weaver-package").expect("Failed to create a temp dir");
+        let output = temp_dir.path().join("registry.tar.gz");
+        let package = |version: Option<&str>| Cli {
+            debug: 0,
+            quiet: false,
+            cache: Default::default(),
+            command: Some(Commands::Registry(RegistryCommand {
+                command: RegistrySubCommand::Package(RegistryPackageArgs {
+                    registry: "crates/weaver_codegen_test/semconv_registry/".into(),
+                    version: version.map(ToOwned::to_owned),
+                    output: Some(output.clone()),
+                    publish: None,
+                    diagnostic: Default::default(),
+                }),
+            })),
+        };
+
+        // The registry doesn't declare its version.
+        let exit_directive = run_command(&package(None), logger.clone());
+        assert_eq!(exit_directive.exit_code, 1);
+
+        let exit_directive = run_command(&package(Some("1.0.0")), logger.clone());
+        assert_eq!(exit_directive.exit_code, 0);
+        let first = std::fs::read(&output).expect("Failed to read the archive");
+
+        let mut archive = tar::Archive::new(GzDecoder::new(first.as_slice()));
+        let mut entries = std::collections::BTreeMap::new();
+        for entry in archive.entries().expect("Invalid archive") {
+            let mut entry = entry.expect("Invalid entry");
+            let mut content = String::new();
+            _ = entry
+                .read_to_string(&mut content)
+                .expect("Failed to read the entry");
+            let path = entry.path().expect("Invalid path").display().to_string();
+            _ = entries.insert(path, content);
+        }
+        assert!(entries.contains_key("http-common.yaml"));
+        assert!(entries["registry_manifest.yaml"].contains("version: 1.0.0"));
+        assert!(entries["registry_manifest.yaml"].contains("name: semconv_registry"));
+        assert!(entries["resolved.json"].contains("\"groups\""));
+        // Every file except the checksums file itself has a checksum.
+        let checksums = &entries["SHA256SUMS"];
+        assert_eq!(checksums.lines().count(), entries.len() - 1);
+        assert!(checksums
+            .lines()
+            .any(|line| line.ends_with("  resolved.json")));
+
+        // The archive can be used as a registry, once its checksum verified.
+        let cache = weaver_cache::Cache::try_new().expect("Failed to create the cache");
+        let load = |checksum: &str| {
+            let registry_path = semconv_registry_path_from(
+                &RegistryPath::Local(format!("{}#sha256={checksum}", output.display())),
+                &None,
+            );
+            load_semconv_specs(
+                &[registry_path],
+                &RegistryFileFilter::default(),
+                &cache,
+                logger.clone(),
+            )
+        };
+        let checksum = sha256_digest(&first).replace("sha256:", "");
+        assert!(!load(&checksum)
+            .expect("Failed to load the archive")
+            .is_empty());
+        assert!(load(&"0".repeat(64)).is_err());
+
+        // The archive is reproducible.
+        let exit_directive = run_command(&package(Some("1.0.0")), logger);
+        assert_eq!(exit_directive.exit_code, 0);
+        assert_eq!(
+            first,
+            std::fs::read(&output).expect("Failed to read the archive")
+        );
+    }
+}
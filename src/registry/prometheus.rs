@@ -0,0 +1,508 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Export the metrics of a semantic convention registry as Prometheus metadata
+//! (OpenMetrics text format or Prometheus metadata API JSON format).
+
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use clap::{Args, ValueEnum};
+use serde::Serialize;
+
+use weaver_cache::Cache;
+use weaver_common::diagnostic::DiagnosticMessages;
+use weaver_common::Logger;
+use weaver_resolved_schema::registry::Group;
+use weaver_resolved_schema::ResolvedTelemetrySchema;
+use weaver_semconv::group::{GroupType, InstrumentSpec};
+use weaver_semconv::registry::SemConvRegistry;
+
+use crate::registry::{Error, RegistryArgs};
+use crate::util::{load_semconv_specs, resolve_semconv_specs};
+use crate::{DiagnosticArgs, ExitDirectives};
+
+/// Supported formats of the Prometheus metadata
+#[derive(Debug, Clone, ValueEnum)]
+pub enum PrometheusFormat {
+    /// OpenMetrics text format (`# TYPE`, `# UNIT`, and `# HELP` lines)
+    Openmetrics,
+    /// JSON format of the Prometheus metadata API (`/api/v1/metadata`)
+    Json,
+}
+
+/// Parameters for the `registry prometheus` sub-command
+#[derive(Debug, Args)]
+pub struct RegistryPrometheusArgs {
+    /// Parameters to specify the semantic convention registry
+    #[command(flatten)]
+    registry: RegistryArgs,
+
+    /// Format of the metadata.
+    #[arg(short, long, default_value = "openmetrics")]
+    format: PrometheusFormat,
+
+    /// Output file to write the metadata to.
+    #[arg(short, long)]
+    output: PathBuf,
+
+    /// Parameters to specify the diagnostic format.
+    #[command(flatten)]
+    pub diagnostic: DiagnosticArgs,
+}
+
+/// The Prometheus metadata of a metric.
+#[derive(Debug, PartialEq, Serialize)]
+struct PrometheusMetric {
+    /// Name of the metric in the semantic conventions.
+    #[serde(skip)]
+    metric_name: String,
+    /// Name of the metric family (without the `_total` suffix of the counters).
+    #[serde(skip)]
+    family: String,
+    /// Name of the Prometheus metric.
+    #[serde(skip)]
+    name: String,
+    /// Prometheus metric type.
+    r#type: &'static str,
+    /// Help text.
+    help: String,
+    /// Prometheus unit (e.g. `seconds`), empty if the metric has no unit.
+    unit: String,
+}
+
+/// Export the metrics of a semantic convention registry as Prometheus metadata.
+#[cfg(not(tarpaulin_include))]
+pub(crate) fn command(
+    logger: impl Logger + Sync + Clone,
+    cache: &Cache,
+    args: &RegistryPrometheusArgs,
+) -> Result<ExitDirectives, DiagnosticMessages> {
+    logger.loading(&format!(
+        "Exporting the Prometheus metadata of the registry `{}`",
+        args.registry.registries()
+    ));
+
+    let registry_id = "default";
+    let semconv_specs = load_semconv_specs(
+        &args.registry.registry_paths(),
+        &args.registry.file_filter()?,
+        cache,
+        logger.clone(),
+    )?;
+    let mut registry = SemConvRegistry::from_semconv_specs(registry_id, semconv_specs);
+    let schema = resolve_semconv_specs(&mut registry, logger.clone())?;
+
+    let (metrics, warnings) = prometheus_metrics(&schema);
+    let content = match args.format {
+        PrometheusFormat::Openmetrics => to_openmetrics(&metrics),
+        PrometheusFormat::Json => to_json(&metrics),
+    };
+    std::fs::write(&args.output, content).map_err(|e| Error::PrometheusWriteFailed {
+        output: args.output.clone(),
+        error: e.to_string(),
+    })?;
+    logger.success(&format!(
+        "Prometheus metadata of {} metrics written to `{}`",
+        metrics.len(),
+        args.output.display()
+    ));
+
+    if !warnings.is_empty() {
+        return Err(DiagnosticMessages::from_errors(warnings));
+    }
+    Ok(ExitDirectives {
+        exit_code: 0,
+        quiet_mode: false,
+    })
+}
+
+/// Returns the Prometheus metadata of the metrics of the registry, sorted by
+/// Prometheus name, and the warnings about the lossy normalizations.
+fn prometheus_metrics(schema: &ResolvedTelemetrySchema) -> (Vec<PrometheusMetric>, Vec<Error>) {
+    let mut metrics = vec![];
+    let mut warnings = vec![];
+    for group in schema
+        .registries
+        .values()
+        .flat_map(|registry| &registry.groups)
+        .filter(|group| group.r#type == GroupType::Metric)
+    {
+        if let Some(metric) = prometheus_metric(group, &mut warnings) {
+            metrics.push(metric);
+        }
+    }
+    metrics.sort_by(|a, b| a.name.cmp(&b.name).then(a.metric_name.cmp(&b.metric_name)));
+
+    // Several metrics with the same Prometheus name can't be distinguished.
+    let mut by_name: BTreeMap<&str, Vec<&str>> = BTreeMap::new();
+    for metric in &metrics {
+        by_name
+            .entry(&metric.name)
+            .or_default()
+            .push(&metric.metric_name);
+    }
+    for (name, metric_names) in by_name {
+        if metric_names.len() > 1 {
+            warnings.push(Error::LossyPrometheusNormalization {
+                metric: metric_names.join("`, `"),
+                prometheus_name: name.to_owned(),
+                reason: "Several metrics are exported with the same name.".to_owned(),
+            });
+        }
+    }
+    metrics.dedup_by(|a, b| a.name == b.name);
+    (metrics, warnings)
+}
+
+/// Returns the Prometheus metadata of a metric group, or None if the group
+/// doesn't define a metric name or an instrument.
+///
+/// The name and the unit are normalized following the OpenTelemetry to
+/// Prometheus compatibility rules: the characters not allowed by Prometheus are
+/// replaced by `_`, the unit is appended to the name (e.g. `_seconds`), the
+/// gauges with a `1` unit get a `_ratio` suffix, and the counters a `_total`
+/// suffix.
+fn prometheus_metric(group: &Group, warnings: &mut Vec<Error>) -> Option<PrometheusMetric> {
+    let metric_name = group.metric_name.as_ref()?;
+    let instrument = group.instrument.as_ref()?;
+    let mut lossy = |reason: String, prometheus_name: &str| {
+        warnings.push(Error::LossyPrometheusNormalization {
+            metric: metric_name.clone(),
+            prometheus_name: prometheus_name.to_owned(),
+            reason,
+        });
+    };
+
+    let mut name = sanitize(metric_name);
+    let replaced: Vec<char> = metric_name
+        .chars()
+        .filter(|c| *c != '.' && !is_valid_char(*c))
+        .collect();
+    let unit = group.unit.as_deref().unwrap_or_default();
+    let (unit, unknown_unit) = prometheus_unit(unit);
+    let unit = match instrument {
+        InstrumentSpec::Gauge if group.unit.as_deref() == Some("1") => "ratio".to_owned(),
+        _ => unit,
+    };
+    if !unit.is_empty() && !name.ends_with(&unit) {
+        name = format!("{}_{}", name, unit);
+    }
+    let family = name.clone();
+    let r#type = match instrument {
+        InstrumentSpec::Counter => {
+            name = format!("{}_total", name);
+            "counter"
+        }
+        InstrumentSpec::UpDownCounter | InstrumentSpec::Gauge => "gauge",
+        InstrumentSpec::Histogram => "histogram",
+    };
+
+    if !replaced.is_empty() {
+        lossy(
+            format!(
+                "The characters {} are replaced by `_`.",
+                replaced
+                    .iter()
+                    .map(|c| format!("`{}`", c))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            &name,
+        );
+    }
+    if let Some(unknown_unit) = unknown_unit {
+        lossy(
+            format!(
+                "The unit `{}` has no Prometheus equivalent and is used as is.",
+                unknown_unit
+            ),
+            &name,
+        );
+    }
+
+    Some(PrometheusMetric {
+        metric_name: metric_name.clone(),
+        family,
+        name,
+        r#type,
+        help: group.brief.split_whitespace().collect::<Vec<_>>().join(" "),
+        unit,
+    })
+}
+
+/// Returns true if the character is allowed in a Prometheus metric name.
+fn is_valid_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '_' || c == ':'
+}
+
+/// Replaces the characters not allowed in a Prometheus metric name by `_`,
+/// collapses the consecutive `_`, trims the leading and trailing `_`, and
+/// prefixes the names starting with a digit with `_`.
+fn sanitize(name: &str) -> String {
+    let mut sanitized = String::with_capacity(name.len());
+    for c in name.chars() {
+        let c = if is_valid_char(c) { c } else { '_' };
+        if c == '_' && sanitized.ends_with('_') {
+            continue;
+        }
+        sanitized.push(c);
+    }
+    let sanitized = sanitized.trim_matches('_');
+    if sanitized.starts_with(|c: char| c.is_ascii_digit()) {
+        format!("_{}", sanitized)
+    } else {
+        sanitized.to_owned()
+    }
+}
+
+/// Returns the Prometheus unit of a UCUM unit (e.g. `seconds` for `s`,
+/// `bytes_per_second` for `By/s`), and the unit if it has no Prometheus
+/// equivalent. The annotations (e.g. `{request}`) are dropped.
+fn prometheus_unit(unit: &str) -> (String, Option<String>) {
+    let mut stripped = String::new();
+    let mut depth = 0;
+    for c in unit.chars() {
+        match c {
+            '{' => depth += 1,
+            '}' if depth > 0 => depth -= 1,
+            _ if depth == 0 => stripped.push(c),
+            _ => {}
+        }
+    }
+    let (main, per) = match stripped.split_once('/') {
+        Some((main, per)) => (main.trim(), per.trim()),
+        None => (stripped.trim(), ""),
+    };
+
+    let mut unknown = None;
+    let main = match main {
+        "" | "1" => String::new(),
+        main => main_unit(main).map(str::to_owned).unwrap_or_else(|| {
+            unknown = Some(unit.to_owned());
+            sanitize(main)
+        }),
+    };
+    let per = match per {
+        "" => String::new(),
+        per => per_unit(per).map(str::to_owned).unwrap_or_else(|| {
+            unknown = Some(unit.to_owned());
+            sanitize(per)
+        }),
+    };
+    let unit = match (main.is_empty(), per.is_empty()) {
+        (_, true) => main,
+        (true, false) => format!("per_{}", per),
+        (false, false) => format!("{}_per_{}", main, per),
+    };
+    (unit, unknown)
+}
+
+/// Returns the Prometheus name of a UCUM unit.
+fn main_unit(unit: &str) -> Option<&'static str> {
+    Some(match unit {
+        // Time
+        "d" => "days",
+        "h" => "hours",
+        "min" => "minutes",
+        "s" => "seconds",
+        "ms" => "milliseconds",
+        "us" => "microseconds",
+        "ns" => "nanoseconds",
+        // Bytes
+        "By" => "bytes",
+        "KiBy" => "kibibytes",
+        "MiBy" => "mebibytes",
+        "GiBy" => "gibibytes",
+        "TiBy" => "tibibytes",
+        "KBy" => "kilobytes",
+        "MBy" => "megabytes",
+        "GBy" => "gigabytes",
+        "TBy" => "terabytes",
+        // SI
+        "m" => "meters",
+        "V" => "volts",
+        "A" => "amperes",
+        "J" => "joules",
+        "W" => "watts",
+        "g" => "grams",
+        // Misc
+        "Cel" => "celsius",
+        "Hz" => "hertz",
+        "%" => "percent",
+        _ => return None,
+    })
+}
+
+/// Returns the Prometheus name of the UCUM unit used as a denominator.
+fn per_unit(unit: &str) -> Option<&'static str> {
+    Some(match unit {
+        "s" => "second",
+        "m" => "minute",
+        "h" => "hour",
+        "d" => "day",
+        "w" => "week",
+        "mo" => "month",
+        "y" => "year",
+        _ => return None,
+    })
+}
+
+/// Renders the metadata in the OpenMetrics text format, without any sample.
+fn to_openmetrics(metrics: &[PrometheusMetric]) -> String {
+    let mut content = String::new();
+    for metric in metrics {
+        content.push_str(&format!("# TYPE {} {}\n", metric.family, metric.r#type));
+        if !metric.unit.is_empty() {
+            content.push_str(&format!("# UNIT {} {}\n", metric.family, metric.unit));
+        }
+        let help = metric.help.replace('\\', "\\\\").replace('\n', "\\n");
+        content.push_str(&format!("# HELP {} {}\n", metric.family, help));
+    }
+    content.push_str("# EOF\n");
+    content
+}
+
+/// Renders the metadata in the JSON format of the Prometheus metadata API.
+fn to_json(metrics: &[PrometheusMetric]) -> String {
+    #[derive(Serialize)]
+    struct Response<'a> {
+        status: &'static str,
+        data: BTreeMap<&'a str, [&'a PrometheusMetric; 1]>,
+    }
+    let response = Response {
+        status: "success",
+        data: metrics
+            .iter()
+            .map(|metric| (metric.name.as_str(), [metric]))
+            .collect(),
+    };
+    let mut json =
+        serde_json::to_string_pretty(&response).expect("The metadata is serializable to JSON");
+    json.push('\n');
+    json
+}
+
+#[cfg(test)]
+mod tests {
+    use weaver_resolver::SchemaResolver;
+    use weaver_semconv::registry::SemConvRegistry;
+
+    use crate::registry::prometheus::{
+        prometheus_metrics, prometheus_unit, sanitize, to_json, to_openmetrics,
+    };
+
+    #[test]
+    fn test_prometheus_unit() {
+        assert_eq!(prometheus_unit("s"), ("seconds".to_owned(), None));
+        assert_eq!(
+            prometheus_unit("By/s"),
+            ("bytes_per_second".to_owned(), None)
+        );
+        assert_eq!(prometheus_unit("{request}"), (String::new(), None));
+        assert_eq!(
+            prometheus_unit("{packet}/s"),
+            ("per_second".to_owned(), None)
+        );
+        assert_eq!(prometheus_unit("1"), (String::new(), None));
+        assert_eq!(
+            prometheus_unit("[degF]"),
+            ("degF".to_owned(), Some("[degF]".to_owned()))
+        );
+        assert_eq!(
+            sanitize("http.server.request-size"),
+            "http_server_request_size"
+        );
+        assert_eq!(sanitize("3d..render"), "_3d_render");
+    }
+
+    #[test]
+    fn test_prometheus_metrics() {
+        let mut registry = SemConvRegistry::new("default");
+        registry
+            .add_semconv_spec_from_string(
+                "metrics.yaml",
+                r#"groups:
+  - id: metric.http.server.request.duration
+    type: metric
+    metric_name: http.server.request.duration
+    brief: "Duration of HTTP server requests."
+    instrument: histogram
+    unit: s
+  - id: metric.http.server.requests
+    type: metric
+    metric_name: http.server.requests
+    brief: Number of HTTP server requests.
+    instrument: counter
+    unit: "{request}"
+  - id: metric.system.cpu.utilization
+    type: metric
+    metric_name: system.cpu.utilization
+    brief: |
+      Difference in system.cpu.time since the last measurement,
+      divided by the elapsed time and number of logical CPUs.
+    instrument: gauge
+    unit: "1"
+  - id: metric.db.client.connections.usage
+    type: metric
+    metric_name: db.client.connections.usage
+    brief: The number of connections.
+    instrument: updowncounter
+    unit: "{connection}"
+  - id: metric.db.client.connections-usage
+    type: metric
+    metric_name: db.client.connections-usage
+    brief: The number of connections (duplicate).
+    instrument: updowncounter
+    unit: "{connection}"
+  - id: metric.room.temperature
+    type: metric
+    metric_name: room.temperature
+    brief: Room temperature.
+    instrument: gauge
+    unit: "[degF]"
+"#,
+            )
+            .expect("Invalid registry");
+        let schema = SchemaResolver::resolve_semantic_convention_registry(&mut registry)
+            .expect("Failed to resolve the registry");
+
+        let (metrics, warnings) = prometheus_metrics(&schema);
+        let mut warnings: Vec<String> = warnings.iter().map(ToString::to_string).collect();
+        warnings.sort();
+        assert_eq!(
+            warnings,
+            vec![
+                "The metric `db.client.connections-usage` is exported as `db_client_connections_usage`, which is lossy. The characters `-` are replaced by `_`.",
+                "The metric `db.client.connections-usage`, `db.client.connections.usage` is exported as `db_client_connections_usage`, which is lossy. Several metrics are exported with the same name.",
+                "The metric `room.temperature` is exported as `room_temperature_degF`, which is lossy. The unit `[degF]` has no Prometheus equivalent and is used as is.",
+            ]
+        );
+
+        assert_eq!(
+            to_openmetrics(&metrics),
+            r#"# TYPE db_client_connections_usage gauge
+# HELP db_client_connections_usage The number of connections (duplicate).
+# TYPE http_server_request_duration_seconds histogram
+# UNIT http_server_request_duration_seconds seconds
+# HELP http_server_request_duration_seconds Duration of HTTP server requests.
+# TYPE http_server_requests counter
+# HELP http_server_requests Number of HTTP server requests.
+# TYPE room_temperature_degF gauge
+# UNIT room_temperature_degF degF
+# HELP room_temperature_degF Room temperature.
+# TYPE system_cpu_utilization_ratio gauge
+# UNIT system_cpu_utilization_ratio ratio
+# HELP system_cpu_utilization_ratio Difference in system.cpu.time since the last measurement, divided by the elapsed time and number of logical CPUs.
+# EOF
+"#
+        );
+
+        let json: serde_json::Value =
+            serde_json::from_str(&to_json(&metrics)).expect("Invalid JSON");
+        assert_eq!(json["status"], "success");
+        assert_eq!(
+            json["data"]["http_server_requests_total"][0],
+            serde_json::json!({"type": "counter", "help": "Number of HTTP server requests.", "unit": ""})
+        );
+    }
+}
@@ -0,0 +1,590 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Protobuf encoding of the resolved registry.
+//!
+//! The messages below mirror the schema defined in
+//! `schemas/resolved-registry.proto`. They are maintained by hand (no build
+//! script) so any change to one side must be reflected on the other.
+
+use std::io::Write;
+use std::path::Path;
+
+use prost::Message;
+use serde::Serialize;
+
+use weaver_forge::registry::{ResolvedGroup, ResolvedRegistry as ForgeResolvedRegistry};
+use weaver_resolved_schema::attribute::Attribute as ResolvedAttribute;
+use weaver_semconv::annotation::Annotations;
+use weaver_semconv::attribute::{
+    AttributeType as SemconvAttributeType, EnumEntriesSpec, Examples,
+    RequirementLevel as SemconvRequirementLevel, ValueSpec,
+};
+
+/// The protobuf schema of the resolved registry.
+pub(crate) const RESOLVED_REGISTRY_PROTO: &str =
+    include_str!("../../schemas/resolved-registry.proto");
+
+/// A resolved semantic convention registry.
+#[derive(Clone, PartialEq, Message)]
+pub(crate) struct ResolvedRegistry {
+    #[prost(string, tag = "1")]
+    pub registry_url: String,
+    #[prost(message, repeated, tag = "2")]
+    pub groups: Vec<Group>,
+}
+
+/// A resolved semantic convention group.
+#[derive(Clone, PartialEq, Message)]
+pub(crate) struct Group {
+    #[prost(string, tag = "1")]
+    pub id: String,
+    #[prost(string, tag = "2")]
+    pub r#type: String,
+    #[prost(string, tag = "3")]
+    pub brief: String,
+    #[prost(string, tag = "4")]
+    pub note: String,
+    #[prost(string, tag = "5")]
+    pub prefix: String,
+    #[prost(string, optional, tag = "6")]
+    pub extends: Option<String>,
+    #[prost(string, optional, tag = "7")]
+    pub stability: Option<String>,
+    #[prost(string, optional, tag = "8")]
+    pub deprecated: Option<String>,
+    #[prost(message, repeated, tag = "9")]
+    pub constraints: Vec<Constraint>,
+    #[prost(message, repeated, tag = "10")]
+    pub attributes: Vec<Attribute>,
+    #[prost(string, optional, tag = "11")]
+    pub span_kind: Option<String>,
+    #[prost(string, repeated, tag = "12")]
+    pub events: Vec<String>,
+    #[prost(string, optional, tag = "13")]
+    pub metric_name: Option<String>,
+    #[prost(string, optional, tag = "14")]
+    pub instrument: Option<String>,
+    #[prost(string, optional, tag = "15")]
+    pub unit: Option<String>,
+    #[prost(string, optional, tag = "16")]
+    pub name: Option<String>,
+    #[prost(string, optional, tag = "17")]
+    pub display_name: Option<String>,
+    #[prost(string, optional, tag = "18")]
+    pub doc_url: Option<String>,
+    #[prost(string, optional, tag = "19")]
+    pub since_version: Option<String>,
+    #[prost(message, optional, tag = "20")]
+    pub scope_version: Option<RequirementLevel>,
+    #[prost(string, optional, tag = "21")]
+    pub annotations: Option<String>,
+}
+
+/// A constraint of a group.
+#[derive(Clone, PartialEq, Message)]
+pub(crate) struct Constraint {
+    #[prost(string, repeated, tag = "1")]
+    pub any_of: Vec<String>,
+    #[prost(string, optional, tag = "2")]
+    pub include: Option<String>,
+}
+
+/// A resolved attribute.
+#[derive(Clone, PartialEq, Message)]
+pub(crate) struct Attribute {
+    #[prost(string, tag = "1")]
+    pub name: String,
+    #[prost(message, optional, tag = "2")]
+    pub r#type: Option<AttributeType>,
+    #[prost(string, tag = "3")]
+    pub brief: String,
+    #[prost(message, repeated, tag = "4")]
+    pub examples: Vec<Value>,
+    #[prost(string, optional, tag = "5")]
+    pub tag: Option<String>,
+    #[prost(message, optional, tag = "6")]
+    pub requirement_level: Option<RequirementLevel>,
+    #[prost(bool, optional, tag = "7")]
+    pub sampling_relevant: Option<bool>,
+    #[prost(string, tag = "8")]
+    pub note: String,
+    #[prost(string, optional, tag = "9")]
+    pub stability: Option<String>,
+    #[prost(string, optional, tag = "10")]
+    pub deprecated: Option<String>,
+    #[prost(string, tag = "11")]
+    pub short_name: String,
+    #[prost(string, repeated, tag = "12")]
+    pub renamed_from: Vec<String>,
+    #[prost(string, optional, tag = "13")]
+    pub sensitivity: Option<String>,
+    #[prost(string, optional, tag = "14")]
+    pub annotations: Option<String>,
+}
+
+/// The type of an attribute.
+#[derive(Clone, PartialEq, Message)]
+pub(crate) struct AttributeType {
+    #[prost(oneof = "AttributeTypeKind", tags = "1, 2, 3")]
+    pub kind: Option<AttributeTypeKind>,
+}
+
+/// The different kinds of attribute types.
+#[derive(Clone, PartialEq, prost::Oneof)]
+pub(crate) enum AttributeTypeKind {
+    /// A primitive or array type.
+    #[prost(string, tag = "1")]
+    Primitive(String),
+    /// A template type.
+    #[prost(string, tag = "2")]
+    Template(String),
+    /// An enum type.
+    #[prost(message, tag = "3")]
+    Enum(Enum),
+}
+
+/// An enum type.
+#[derive(Clone, PartialEq, Message)]
+pub(crate) struct Enum {
+    #[prost(bool, tag = "1")]
+    pub allow_custom_values: bool,
+    #[prost(message, repeated, tag = "2")]
+    pub members: Vec<EnumMember>,
+}
+
+/// An enum entry.
+#[derive(Clone, PartialEq, Message)]
+pub(crate) struct EnumMember {
+    #[prost(string, tag = "1")]
+    pub id: String,
+    #[prost(message, optional, tag = "2")]
+    pub value: Option<Value>,
+    #[prost(string, optional, tag = "3")]
+    pub brief: Option<String>,
+    #[prost(string, optional, tag = "4")]
+    pub note: Option<String>,
+    #[prost(string, optional, tag = "5")]
+    pub stability: Option<String>,
+    #[prost(string, optional, tag = "6")]
+    pub deprecated: Option<String>,
+}
+
+/// The requirement level of an attribute.
+#[derive(Clone, PartialEq, Message)]
+pub(crate) struct RequirementLevel {
+    #[prost(string, tag = "1")]
+    pub level: String,
+    #[prost(string, optional, tag = "2")]
+    pub text: Option<String>,
+}
+
+/// A value (example or enum value).
+#[derive(Clone, PartialEq, Message)]
+pub(crate) struct Value {
+    #[prost(oneof = "ValueKind", tags = "1, 2, 3, 4, 5")]
+    pub kind: Option<ValueKind>,
+}
+
+/// The different kinds of values.
+#[derive(Clone, PartialEq, prost::Oneof)]
+pub(crate) enum ValueKind {
+    /// A boolean value.
+    #[prost(bool, tag = "1")]
+    Bool(bool),
+    /// An integer value.
+    #[prost(int64, tag = "2")]
+    Int(i64),
+    /// A double value.
+    #[prost(double, tag = "3")]
+    Double(f64),
+    /// A string value.
+    #[prost(string, tag = "4")]
+    String(String),
+    /// An array value.
+    #[prost(message, tag = "5")]
+    Array(Values),
+}
+
+/// A list of values.
+#[derive(Clone, PartialEq, Message)]
+pub(crate) struct Values {
+    #[prost(message, repeated, tag = "1")]
+    pub values: Vec<Value>,
+}
+
+impl From<&ForgeResolvedRegistry> for ResolvedRegistry {
+    fn from(registry: &ForgeResolvedRegistry) -> Self {
+        ResolvedRegistry {
+            registry_url: registry.registry_url.clone(),
+            groups: registry.groups.iter().map(Group::from).collect(),
+        }
+    }
+}
+
+impl From<&ResolvedGroup> for Group {
+    fn from(group: &ResolvedGroup) -> Self {
+        Group {
+            id: group.id.clone(),
+            r#type: serde_name(&group.r#type),
+            brief: group.brief.clone(),
+            note: group.note.clone(),
+            prefix: group.prefix.to_string(),
+            extends: group.extends.clone(),
+            stability: group.stability.as_ref().map(serde_name),
+            deprecated: group.deprecated.clone(),
+            constraints: group
+                .constraints
+                .iter()
+                .map(|constraint| Constraint {
+                    any_of: constraint.any_of.clone(),
+                    include: constraint.include.clone(),
+                })
+                .collect(),
+            attributes: group.attributes.iter().map(Attribute::from).collect(),
+            span_kind: group.span_kind.as_ref().map(serde_name),
+            events: group.events.clone(),
+            metric_name: group.metric_name.clone(),
+            instrument: group.instrument.as_ref().map(serde_name),
+            unit: group.unit.clone(),
+            name: group.name.clone(),
+            display_name: group.display_name.clone(),
+            doc_url: group.doc_url.clone(),
+            since_version: group.since_version.clone(),
+            scope_version: group.scope_version.as_ref().map(RequirementLevel::from),
+            annotations: annotations(&group.annotations),
+        }
+    }
+}
+
+impl From<&ResolvedAttribute> for Attribute {
+    fn from(attribute: &ResolvedAttribute) -> Self {
+        Attribute {
+            name: attribute.name.to_string(),
+            r#type: Some(AttributeType::from(&attribute.r#type)),
+            brief: attribute.brief.to_string(),
+            examples: attribute
+                .examples
+                .as_ref()
+                .map(examples)
+                .unwrap_or_default(),
+            tag: attribute.tag.clone(),
+            requirement_level: Some(RequirementLevel::from(&attribute.requirement_level)),
+            sampling_relevant: attribute.sampling_relevant,
+            note: attribute.note.to_string(),
+            stability: attribute.stability.as_ref().map(serde_name),
+            deprecated: attribute.deprecated.clone(),
+            short_name: attribute.short_name.to_string(),
+            renamed_from: attribute.renamed_from.clone(),
+            sensitivity: attribute.sensitivity.as_ref().map(serde_name),
+            annotations: annotations(&attribute.annotations),
+        }
+    }
+}
+
+impl From<&SemconvAttributeType> for AttributeType {
+    fn from(attribute_type: &SemconvAttributeType) -> Self {
+        let kind = match attribute_type {
+            SemconvAttributeType::PrimitiveOrArray(t) => {
+                AttributeTypeKind::Primitive(serde_name(t))
+            }
+            SemconvAttributeType::Template(t) => AttributeTypeKind::Template(serde_name(t)),
+            SemconvAttributeType::Enum {
+                allow_custom_values,
+                members,
+            } => AttributeTypeKind::Enum(Enum {
+                allow_custom_values: *allow_custom_values,
+                members: members.iter().map(EnumMember::from).collect(),
+            }),
+        };
+        AttributeType { kind: Some(kind) }
+    }
+}
+
+impl From<&EnumEntriesSpec> for EnumMember {
+    fn from(member: &EnumEntriesSpec) -> Self {
+        let value = match &member.value {
+            ValueSpec::Int(v) => ValueKind::Int(*v),
+            ValueSpec::Double(v) => ValueKind::Double(v.into_inner()),
+            ValueSpec::String(v) => ValueKind::String(v.clone()),
+        };
+        EnumMember {
+            id: member.id.clone(),
+            value: Some(Value { kind: Some(value) }),
+            brief: member.brief.clone(),
+            note: member.note.clone(),
+            stability: member.stability.as_ref().map(serde_name),
+            deprecated: member.deprecated.clone(),
+        }
+    }
+}
+
+impl From<&SemconvRequirementLevel> for RequirementLevel {
+    fn from(requirement_level: &SemconvRequirementLevel) -> Self {
+        let (level, text) = match requirement_level {
+            SemconvRequirementLevel::Basic(level) => (serde_name(level), None),
+            SemconvRequirementLevel::ConditionallyRequired { text } => {
+                ("conditionally_required".to_owned(), Some(text.clone()))
+            }
+            SemconvRequirementLevel::Recommended { text } => {
+                ("recommended".to_owned(), Some(text.clone()))
+            }
+        };
+        RequirementLevel { level, text }
+    }
+}
+
+impl Value {
+    fn new(kind: ValueKind) -> Self {
+        Value { kind: Some(kind) }
+    }
+
+    fn array(values: impl IntoIterator<Item = ValueKind>) -> Self {
+        Value::new(ValueKind::Array(Values {
+            values: values.into_iter().map(Value::new).collect(),
+        }))
+    }
+}
+
+/// Converts the examples of an attribute into a list of values. A list of
+/// arrays is encoded as a list of array values.
+fn examples(examples: &Examples) -> Vec<Value> {
+    match examples {
+        Examples::Bool(v) => vec![Value::new(ValueKind::Bool(*v))],
+        Examples::Int(v) => vec![Value::new(ValueKind::Int(*v))],
+        Examples::Double(v) => vec![Value::new(ValueKind::Double(v.into_inner()))],
+        Examples::String(v) => vec![Value::new(ValueKind::String(v.clone()))],
+        Examples::Ints(v) => v.iter().map(|v| Value::new(ValueKind::Int(*v))).collect(),
+        Examples::Doubles(v) => v
+            .iter()
+            .map(|v| Value::new(ValueKind::Double(v.into_inner())))
+            .collect(),
+        Examples::Bools(v) => v.iter().map(|v| Value::new(ValueKind::Bool(*v))).collect(),
+        Examples::Strings(v) => v
+            .iter()
+            .map(|v| Value::new(ValueKind::String(v.clone())))
+            .collect(),
+        Examples::ListOfInts(v) => v
+            .iter()
+            .map(|v| Value::array(v.iter().map(|v| ValueKind::Int(*v))))
+            .collect(),
+        Examples::ListOfDoubles(v) => v
+            .iter()
+            .map(|v| Value::array(v.iter().map(|v| ValueKind::Double(v.into_inner()))))
+            .collect(),
+        Examples::ListOfBools(v) => v
+            .iter()
+            .map(|v| Value::array(v.iter().map(|v| ValueKind::Bool(*v))))
+            .collect(),
+        Examples::ListOfStrings(v) => v
+            .iter()
+            .map(|v| Value::array(v.iter().map(|v| ValueKind::String(v.clone()))))
+            .collect(),
+    }
+}
+
+/// Encodes non-empty annotations as a JSON object. The annotations that can't
+/// be represented in JSON (e.g. with a list as a mapping key) are omitted.
+fn annotations(annotations: &Annotations) -> Option<String> {
+    if annotations.is_empty() {
+        return None;
+    }
+    serde_json::to_string(annotations).ok()
+}
+
+/// Returns the name used in the semantic convention files for a unit-like
+/// enum value (e.g. `attribute_group`, `stable`, `histogram`).
+fn serde_name<T: Serialize>(value: &T) -> String {
+    match serde_json::to_value(value) {
+        Ok(serde_json::Value::String(name)) => name,
+        Ok(other) => other.to_string(),
+        Err(e) => panic!("Failed to serialize an enum value: {e}"),
+    }
+}
+
+/// Encodes the resolved registry as a protobuf message and writes it to the
+/// given file along with its `.proto` schema (same file stem, `.proto`
+/// extension). Without an output file, the encoded message is written to
+/// stdout.
+pub(crate) fn write_protobuf(
+    registry: &ForgeResolvedRegistry,
+    output: Option<&Path>,
+) -> Result<(), String> {
+    let bytes = ResolvedRegistry::from(registry).encode_to_vec();
+    let Some(path) = output else {
+        let mut stdout = std::io::stdout().lock();
+        return stdout
+            .write_all(&bytes)
+            .and_then(|_| stdout.flush())
+            .map_err(|e| format!("Failed to write the resolved registry to stdout: {e:?}"));
+    };
+
+    std::fs::write(path, bytes)
+        .map_err(|e| format!("Failed to write the resolved registry to file: {e:?}"))?;
+    let schema_path = path.with_extension("proto");
+    if schema_path != path {
+        std::fs::write(&schema_path, RESOLVED_REGISTRY_PROTO)
+            .map_err(|e| format!("Failed to write the protobuf schema to file: {e:?}"))?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use prost::Message;
+
+    use weaver_forge::registry::ResolvedRegistry as ForgeResolvedRegistry;
+    use weaver_resolver::SchemaResolver;
+    use weaver_semconv::registry::SemConvRegistry;
+
+    use super::*;
+
+    #[test]
+    fn test_protobuf_round_trip() {
+        let mut registry = SemConvRegistry::new("default");
+        registry
+            .add_semconv_spec_from_string(
+                "test.yaml",
+                r#"
+groups:
+  - id: registry.http
+    type: attribute_group
+    brief: HTTP attributes.
+    prefix: http
+    attributes:
+      - id: request.method
+        type:
+          allow_custom_values: true
+          members:
+            - id: get
+              value: GET
+              brief: GET method.
+        brief: HTTP request method.
+        examples: ["GET", "POST"]
+        requirement_level:
+          conditionally_required: If available.
+        stability: stable
+        renamed_from: [http.method]
+        annotations:
+          owner: http-team
+          issues: [42]
+      - id: request.body
+        type: string
+        brief: HTTP request body.
+        examples: ["{}"]
+        stability: experimental
+        sensitivity: pii
+      - id: request.header
+        type: template[string[]]
+        brief: HTTP request headers.
+        examples: [["application/json"], ["1.2.3.4", "1.2.3.5"]]
+        requirement_level: opt_in
+        stability: experimental
+  - id: metric.http.server.request.duration
+    type: metric
+    metric_name: http.server.request.duration
+    brief: Duration of HTTP server requests.
+    instrument: histogram
+    unit: s
+    stability: stable
+    attributes:
+      - ref: http.request.method
+"#,
+            )
+            .expect("Failed to load the test registry");
+        let schema = SchemaResolver::resolve_semantic_convention_registry(&mut registry)
+            .expect("Failed to resolve the registry");
+        let registry = ForgeResolvedRegistry::try_from_resolved_registry(
+            schema
+                .registry("default")
+                .expect("Failed to get the registry from the resolved schema"),
+            schema.catalog(),
+        )
+        .expect("Failed to create the resolved registry");
+
+        let message = ResolvedRegistry::from(&registry);
+        let decoded = ResolvedRegistry::decode(message.encode_to_vec().as_slice())
+            .expect("Failed to decode the resolved registry");
+        assert_eq!(decoded, message);
+        assert_eq!(decoded.groups.len(), 2);
+
+        let group = decoded
+            .groups
+            .iter()
+            .find(|g| g.id == "registry.http")
+            .expect("Group `registry.http` not found");
+        assert_eq!(group.r#type, "attribute_group");
+        assert_eq!(group.prefix, "http");
+
+        let method = group
+            .attributes
+            .iter()
+            .find(|a| a.name == "http.request.method")
+            .expect("Attribute `http.request.method` not found");
+        assert_eq!(method.stability.as_deref(), Some("stable"));
+        assert_eq!(method.short_name, "request.method");
+        assert_eq!(method.renamed_from, vec!["http.method"]);
+        assert_eq!(method.sensitivity, None);
+        let method_annotations: serde_json::Value =
+            serde_json::from_str(method.annotations.as_deref().expect("Annotations expected"))
+                .expect("Invalid annotations");
+        assert_eq!(
+            method_annotations,
+            serde_json::json!({"owner": "http-team", "issues": [42]})
+        );
+        let body = group
+            .attributes
+            .iter()
+            .find(|a| a.name == "http.request.body")
+            .expect("Attribute `http.request.body` not found");
+        assert_eq!(body.sensitivity.as_deref(), Some("pii"));
+        assert_eq!(body.annotations, None);
+        assert_eq!(
+            method.requirement_level,
+            Some(RequirementLevel {
+                level: "conditionally_required".to_owned(),
+                text: Some("If available.".to_owned()),
+            })
+        );
+        let Some(AttributeTypeKind::Enum(method_enum)) =
+            method.r#type.as_ref().and_then(|t| t.kind.clone())
+        else {
+            panic!("Expected an enum type for `http.request.method`");
+        };
+        assert!(method_enum.allow_custom_values);
+        assert_eq!(method_enum.members[0].id, "get");
+        assert_eq!(
+            method_enum.members[0].value,
+            Some(Value::new(ValueKind::String("GET".to_owned())))
+        );
+        assert_eq!(method.examples.len(), 2);
+
+        let header = group
+            .attributes
+            .iter()
+            .find(|a| a.name == "http.request.header")
+            .expect("Attribute `http.request.header` not found");
+        assert_eq!(
+            header.r#type,
+            Some(AttributeType {
+                kind: Some(AttributeTypeKind::Template("template[string[]]".to_owned()))
+            })
+        );
+        assert_eq!(
+            header.examples[1],
+            Value::array([
+                ValueKind::String("1.2.3.4".to_owned()),
+                ValueKind::String("1.2.3.5".to_owned()),
+            ])
+        );
+
+        let metric = decoded
+            .groups
+            .iter()
+            .find(|g| g.id == "metric.http.server.request.duration")
+            .expect("Group `metric.http.server.request.duration` not found");
+        assert_eq!(metric.r#type, "metric");
+        assert_eq!(metric.instrument.as_deref(), Some("histogram"));
+        assert_eq!(metric.unit.as_deref(), Some("s"));
+        assert_eq!(metric.attributes.len(), 1);
+    }
+}
@@ -0,0 +1,189 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Query a resolved semantic convention registry with a jq expression.
+
+use std::io::Write;
+
+use clap::Args;
+
+use weaver_cache::Cache;
+use weaver_common::diagnostic::DiagnosticMessages;
+use weaver_common::Logger;
+use weaver_forge::filter::Filter;
+use weaver_forge::registry::ResolvedRegistry;
+use weaver_semconv::registry::SemConvRegistry;
+
+use crate::registry::{Error, RegistryArgs};
+use crate::util::{load_semconv_specs, resolve_semconv_specs};
+use crate::{DiagnosticArgs, ExitDirectives};
+
+/// Parameters for the `registry query` sub-command
+#[derive(Debug, Args)]
+pub struct RegistryQueryArgs {
+    /// Parameters to specify the semantic convention registry
+    #[command(flatten)]
+    registry: RegistryArgs,
+
+    /// The jq expression evaluated against the resolved registry, e.g.
+    /// `.groups[] | select(.type == "metric") | .metric_name`.
+    pub query: String,
+
+    /// Print the string results without quotes (the other results are still
+    /// printed as JSON).
+    #[arg(long)]
+    pub raw_output: bool,
+
+    /// Parameters to specify the diagnostic format.
+    #[command(flatten)]
+    pub diagnostic: DiagnosticArgs,
+}
+
+/// Evaluates a jq expression against the JSON representation of a resolved
+/// registry and returns the results as JSON lines.
+fn query_registry(
+    registry: &ResolvedRegistry,
+    query: &str,
+    raw_output: bool,
+) -> Result<Vec<String>, DiagnosticMessages> {
+    let filter = Filter::try_new(query, Vec::new())?;
+    let registry = serde_json::to_value(registry).map_err(|e| Error::QueryFailed {
+        query: query.to_owned(),
+        error: e.to_string(),
+    })?;
+    Ok(filter
+        .apply_all(registry, Vec::new())?
+        .into_iter()
+        .map(|value| match value {
+            serde_json::Value::String(value) if raw_output => value,
+            value => value.to_string(),
+        })
+        .collect())
+}
+
+/// Query a resolved semantic convention registry and print the results as JSON
+/// lines.
+#[cfg(not(tarpaulin_include))]
+pub(crate) fn command(
+    logger: impl Logger + Sync + Clone,
+    cache: &Cache,
+    args: &RegistryQueryArgs,
+) -> Result<ExitDirectives, DiagnosticMessages> {
+    logger.mute();
+
+    let registry_id = "default";
+    let registry_paths = args.registry.registry_paths();
+    let semconv_specs = load_semconv_specs(
+        &registry_paths,
+        &args.registry.file_filter()?,
+        cache,
+        logger.clone(),
+    )?;
+    let mut registry = SemConvRegistry::from_semconv_specs(registry_id, semconv_specs);
+    let schema = resolve_semconv_specs(&mut registry, logger.clone())?;
+    let registry = ResolvedRegistry::try_from_resolved_registry(
+        schema
+            .registry(registry_id)
+            .expect("Failed to get the registry from the resolved schema"),
+        schema.catalog(),
+    )?;
+
+    let mut stdout = std::io::stdout().lock();
+    for line in query_registry(&registry, &args.query, args.raw_output)? {
+        // Stop when the output is closed, e.g. when piped into `head`.
+        if writeln!(stdout, "{}", line).is_err() {
+            break;
+        }
+    }
+
+    Ok(ExitDirectives {
+        exit_code: 0,
+        quiet_mode: true,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use weaver_common::TestLogger;
+    use weaver_forge::registry::ResolvedRegistry;
+    use weaver_resolver::SchemaResolver;
+    use weaver_semconv::registry::SemConvRegistry;
+
+    use crate::cli::{Cli, Commands};
+    use crate::registry::query::{query_registry, RegistryQueryArgs};
+    use crate::registry::{RegistryArgs, RegistryCommand, RegistryPath, RegistrySubCommand};
+    use crate::run_command;
+
+    #[test]
+    fn test_query_registry() {
+        let registry_id = "default";
+        let mut registry = SemConvRegistry::try_from_path_pattern(
+            registry_id,
+            "crates/weaver_codegen_test/semconv_registry/**/*.yaml",
+        )
+        .expect("Failed to load the registry");
+        let schema = SchemaResolver::resolve_semantic_convention_registry(&mut registry)
+            .expect("Failed to resolve the registry");
+        let registry = ResolvedRegistry::try_from_resolved_registry(
+            schema
+                .registry(registry_id)
+                .expect("Failed to get the registry from the resolved schema"),
+            schema.catalog(),
+        )
+        .expect("Failed to create the resolved registry");
+
+        let query = r#".groups[] | select(.type == "metric") | .metric_name"#;
+        let mut metrics = query_registry(&registry, query, false).expect("Query failed");
+        metrics.sort();
+        assert_eq!(
+            metrics,
+            vec![
+                "\"http.client.active_requests\"",
+                "\"http.client.request.duration\"",
+                "\"http.server.request.duration\"",
+                "\"system.cpu.time\"",
+                "\"system.cpu.utilization\"",
+                "\"system.memory.usage\"",
+            ]
+        );
+        let metrics = query_registry(&registry, query, true).expect("Query failed");
+        assert!(metrics.contains(&"system.cpu.time".to_owned()));
+
+        // Objects are printed on a single line.
+        let groups =
+            query_registry(&registry, ".groups[] | {id, type}", false).expect("Query failed");
+        assert!(groups.contains(&r#"{"id":"registry.http","type":"attribute_group"}"#.to_owned()));
+
+        // Invalid and failing expressions are reported.
+        assert!(query_registry(&registry, ".groups[", false).is_err());
+        assert!(query_registry(&registry, ".groups + 1", false).is_err());
+    }
+
+    #[test]
+    fn test_registry_query() {
+        let logger = TestLogger::new();
+        let cli = Cli {
+            debug: 0,
+            quiet: false,
+            cache: Default::default(),
+            command: Some(Commands::Registry(RegistryCommand {
+                command: RegistrySubCommand::Query(RegistryQueryArgs {
+                    registry: RegistryArgs {
+                        registry: vec![RegistryPath::Local(
+                            "crates/weaver_codegen_test/semconv_registry/".to_owned(),
+                        )],
+                        registry_git_sub_dir: None,
+                        include: vec![],
+                        exclude: vec![],
+                    },
+                    query: ".groups | length".to_owned(),
+                    raw_output: false,
+                    diagnostic: Default::default(),
+                }),
+            })),
+        };
+
+        let exit_directive = run_command(&cli, logger.clone());
+        assert_eq!(exit_directive.exit_code, 0);
+        assert_eq!(logger.error_count(), 0);
+    }
+}
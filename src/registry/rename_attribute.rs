@@ -0,0 +1,668 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Rename an attribute across the semantic convention files of a local
+//! registry.
+//!
+//! The YAML sources are edited line by line, so the comments and the layout of
+//! the files are preserved.
+
+use std::ops::Range;
+use std::path::PathBuf;
+
+use clap::Args;
+
+use weaver_common::diagnostic::DiagnosticMessages;
+use weaver_common::Logger;
+use weaver_diff::diff_output;
+use weaver_resolver::SchemaResolver;
+use weaver_semconv::attribute::AttributeSpec;
+use weaver_semconv::registry::SemConvRegistry;
+use weaver_semconv::semconv::SemConvSpec;
+
+use crate::registry::fmt::semconv_files;
+use crate::registry::Error;
+use crate::{DiagnosticArgs, ExitDirectives};
+
+/// Parameters for the `registry rename-attribute` sub-command
+#[derive(Debug, Args)]
+pub struct RegistryRenameAttributeArgs {
+    /// Current id of the attribute.
+    pub old: String,
+
+    /// New id of the attribute.
+    pub new: String,
+
+    /// Local path of the semantic convention registry.
+    #[arg(short = 'r', long)]
+    pub registry: PathBuf,
+
+    /// Print the changes without modifying the files.
+    #[arg(long, default_value = "false")]
+    pub dry_run: bool,
+
+    /// Parameters to specify the diagnostic format.
+    #[command(flatten)]
+    pub diagnostic: DiagnosticArgs,
+}
+
+/// A semantic convention file being edited.
+struct SourceFile {
+    path: PathBuf,
+    original: String,
+    lines: Vec<String>,
+    spec: SemConvSpec,
+}
+
+impl SourceFile {
+    /// Returns the edited content of the file.
+    fn content(&self) -> String {
+        let mut content = self.lines.join("\n");
+        if self.original.ends_with('\n') {
+            content.push('\n');
+        }
+        content
+    }
+}
+
+/// A `key: value` entry of a line of a YAML document.
+#[derive(Debug, PartialEq)]
+struct Entry<'a> {
+    /// The column of the key (after the `- ` sequence indicator, if any).
+    column: usize,
+    key: &'a str,
+    /// The value, without quotes.
+    value: &'a str,
+    /// The byte range of the value (without quotes) in the line.
+    range: Range<usize>,
+}
+
+/// Rename an attribute across the semantic convention files of a local registry.
+#[cfg(not(tarpaulin_include))]
+pub(crate) fn command(
+    logger: impl Logger + Sync + Clone,
+    args: &RegistryRenameAttributeArgs,
+) -> Result<ExitDirectives, DiagnosticMessages> {
+    logger.loading(&format!(
+        "Renaming the attribute `{}` to `{}` in the registry `{}`",
+        args.old,
+        args.new,
+        args.registry.display()
+    ));
+
+    let mut files = vec![];
+    for path in semconv_files(&args.registry)? {
+        let (_, spec) = SemConvRegistry::semconv_spec_from_file(&path)
+            .map_err(DiagnosticMessages::from_error)?;
+        let original = std::fs::read_to_string(&path).map_err(|e| rename_failed(args, e))?;
+        files.push(SourceFile {
+            lines: original.lines().map(str::to_owned).collect(),
+            path,
+            original,
+            spec,
+        });
+    }
+
+    rename_attribute(&mut files, &args.old, &args.new).map_err(|e| rename_failed(args, e))?;
+    check_registry(&files).map_err(|e| rename_failed(args, e))?;
+
+    for file in &files {
+        let content = file.content();
+        if content == file.original {
+            continue;
+        }
+        if args.dry_run {
+            logger.log(&format!("Diff in `{}`:", file.path.display()));
+            print!("{}", diff_output(&file.original, &content));
+        } else {
+            std::fs::write(&file.path, content).map_err(|e| rename_failed(args, e))?;
+            logger.success(&format!("Updated `{}`", file.path.display()));
+        }
+    }
+
+    Ok(ExitDirectives {
+        exit_code: 0,
+        quiet_mode: false,
+    })
+}
+
+fn rename_failed(args: &RegistryRenameAttributeArgs, error: impl ToString) -> Error {
+    Error::AttributeRenameFailed {
+        old: args.old.clone(),
+        new: args.new.clone(),
+        error: error.to_string(),
+    }
+}
+
+/// Renames the definition of the attribute, inserts a deprecated attribute
+/// with the old id after it, and updates the references to the attribute.
+fn rename_attribute(files: &mut [SourceFile], old: &str, new: &str) -> Result<(), String> {
+    if old == new {
+        return Err("The old and new ids are identical.".to_owned());
+    }
+    let mut definition = None;
+    for (file_index, file) in files.iter().enumerate() {
+        for group in file.spec.groups() {
+            for attribute in &group.attributes {
+                let AttributeSpec::Id { id, .. } = attribute else {
+                    continue;
+                };
+                let attribute_id = qualified_id(&group.prefix, id);
+                if attribute_id == new {
+                    return Err(format!(
+                        "The attribute `{}` is already defined in `{}`.",
+                        new,
+                        file.path.display()
+                    ));
+                }
+                if attribute_id == old {
+                    definition = Some((file_index, group.id.clone(), group.prefix.clone()));
+                }
+            }
+        }
+    }
+    let Some((file_index, group_id, prefix)) = definition else {
+        return Err(format!("The attribute `{}` is not defined.", old));
+    };
+
+    rename_definition(&mut files[file_index], &group_id, &prefix, old, new)?;
+    for file in files.iter_mut() {
+        update_references(&mut file.lines, old, new);
+    }
+    Ok(())
+}
+
+/// Renames the definition of the attribute in its group and inserts the
+/// deprecated attribute. If the new id doesn't start with the prefix of the
+/// group, the prefix is removed and the ids of the attributes of the group are
+/// qualified.
+fn rename_definition(
+    file: &mut SourceFile,
+    group_id: &str,
+    prefix: &str,
+    old: &str,
+    new: &str,
+) -> Result<(), String> {
+    let not_found = || {
+        format!(
+            "The definition of the attribute in `{}` can't be located.",
+            file.path.display()
+        )
+    };
+    let lines = &mut file.lines;
+    let group = sequence_items(lines, 0..lines.len(), 0, "groups")
+        .into_iter()
+        .find(|item| {
+            find_entry(lines, item.clone(), "id").is_some_and(|(_, entry)| entry.value == group_id)
+        })
+        .ok_or_else(not_found)?;
+    let group_column = column(&lines[group.start]) + 2;
+    let attributes = sequence_items(lines, group.clone(), group_column, "attributes");
+
+    let keep_prefix = prefix.is_empty() || new.starts_with(&format!("{}.", prefix));
+    let local_id = |id: &str| {
+        if prefix.is_empty() || !keep_prefix {
+            id.to_owned()
+        } else {
+            id[prefix.len() + 1..].to_owned()
+        }
+    };
+
+    let mut definition = None;
+    for item in attributes.iter().rev() {
+        if !keep_prefix
+            && find_entry(lines, item.clone(), "prefix")
+                .is_some_and(|(_, entry)| entry.value == "true")
+        {
+            return Err(format!(
+                "The prefix `{}` of the group `{}` can't be removed, it is used by a reference.",
+                prefix, group_id
+            ));
+        }
+        let Some((line, entry)) = find_entry(lines, item.clone(), "id") else {
+            continue;
+        };
+        let id = qualified_id(prefix, entry.value);
+        if id == old {
+            definition = Some(item.clone());
+        }
+        let range = entry.range.clone();
+        if id == old {
+            lines[line].replace_range(range, &local_id(new));
+        } else if !keep_prefix {
+            lines[line].replace_range(range, &id);
+        }
+    }
+    let definition = definition.ok_or_else(not_found)?;
+    if !keep_prefix {
+        if let Some((line, _)) = find_entry(lines, group.clone(), "prefix") {
+            _ = lines.remove(line);
+        }
+    }
+    // The line numbers after the removed prefix line are shifted.
+    let shift = usize::from(!keep_prefix && definition.start > group.start);
+    let definition = definition.start - shift..definition.end - shift;
+
+    // The deprecated attribute has the same type, examples, stability, and
+    // requirement level as the renamed attribute.
+    let dash_column = column(&lines[definition.start]);
+    let indent = " ".repeat(dash_column + 2);
+    let entry_lines = |key: &str| {
+        let Some((start, _)) = find_entry(lines, definition.clone(), key) else {
+            return vec![];
+        };
+        let end = (start + 1..definition.end)
+            .find(|&line| !is_blank(&lines[line]) && column(&lines[line]) <= dash_column + 2)
+            .unwrap_or(definition.end);
+        let end = (start + 1..end)
+            .rev()
+            .find(|&line| !is_blank(&lines[line]))
+            .map_or(start + 1, |line| line + 1);
+        lines[start..end]
+            .iter()
+            .map(|line| {
+                line.strip_prefix(&format!("{}- ", " ".repeat(dash_column)))
+                    .map_or_else(|| line.clone(), |line| format!("{}{}", indent, line))
+            })
+            .collect::<Vec<_>>()
+    };
+    let type_lines = entry_lines("type");
+    if type_lines.is_empty() {
+        return Err(not_found());
+    }
+    let mut stub = vec![format!(
+        "{}- id: {}",
+        " ".repeat(dash_column),
+        if keep_prefix {
+            local_id(old)
+        } else {
+            old.to_owned()
+        }
+    )];
+    stub.extend(type_lines);
+    stub.extend(entry_lines("examples"));
+    stub.extend(entry_lines("stability"));
+    stub.extend(entry_lines("requirement_level"));
+    stub.push(format!(
+        "{}brief: \"Deprecated, use `{}` instead.\"",
+        indent, new
+    ));
+    stub.push(format!("{}deprecated: \"Replaced by `{}`.\"", indent, new));
+    // Insert after the last non-blank line of the definition.
+    let mut insert_at = (definition.start..definition.end)
+        .rev()
+        .find(|&line| !is_blank(&lines[line]))
+        .map_or(definition.end, |line| line + 1);
+
+    // The renamed attribute records its previous name.
+    match find_entry(lines, definition.clone(), "renamed_from") {
+        Some((line, entry)) if entry.value.is_empty() => {
+            let items = sequence_items(lines, definition.clone(), entry.column, "renamed_from");
+            let (after, item_column) = match items.last() {
+                Some(item) => (item.end, column(&lines[item.start])),
+                None => (line + 1, entry.column + 2),
+            };
+            lines.insert(after, format!("{}- {}", " ".repeat(item_column), old));
+            insert_at += 1;
+        }
+        Some((line, entry)) => {
+            let range = entry.range.clone();
+            let previous = entry
+                .value
+                .trim_start_matches('[')
+                .trim_end_matches(']')
+                .trim();
+            let value = if previous.is_empty() {
+                format!("[{}]", old)
+            } else {
+                format!("[{}, {}]", previous, old)
+            };
+            lines[line].replace_range(range, &value);
+        }
+        None => {
+            lines.insert(insert_at, format!("{}renamed_from: [{}]", indent, old));
+            insert_at += 1;
+        }
+    }
+    _ = lines.splice(insert_at..insert_at, stub);
+    Ok(())
+}
+
+/// Updates the references (`ref` entries, `any_of` constraints, and
+/// deprecation notes) to the attribute.
+fn update_references(lines: &mut [String], old: &str, new: &str) {
+    let mut any_of_column = None;
+    for line in lines.iter_mut() {
+        if let Some(column) = any_of_column {
+            let in_any_of = is_blank(line)
+                || self::column(line) > column
+                || (line.trim_start().starts_with('-') && self::column(line) == column);
+            if !in_any_of {
+                any_of_column = None;
+            }
+        }
+        let Some(entry) = parse_entry(line) else {
+            if any_of_column.is_some() {
+                if let Some(range) =
+                    sequence_scalar(line).filter(|range| &line[range.clone()] == old)
+                {
+                    line.replace_range(range, new);
+                }
+            }
+            continue;
+        };
+        let range = entry.range.clone();
+        match entry.key {
+            "ref" if entry.value == old => line.replace_range(range, new),
+            "any_of" if entry.value.is_empty() => any_of_column = Some(entry.column),
+            "any_of" => {
+                let value = replace_token(entry.value, old, new);
+                line.replace_range(range, &value);
+            }
+            "deprecated" => {
+                let value = entry
+                    .value
+                    .replace(&format!("`{}`", old), &format!("`{}`", new));
+                line.replace_range(range, &value);
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Checks that the edited files are valid and that the registry can still be
+/// resolved.
+fn check_registry(files: &[SourceFile]) -> Result<(), String> {
+    let mut semconv_specs = vec![];
+    for file in files {
+        let spec = SemConvSpec::from_string(&file.content()).map_err(|e| {
+            format!(
+                "The updated file `{}` is invalid. {}",
+                file.path.display(),
+                e
+            )
+        })?;
+        semconv_specs.push((file.path.display().to_string(), spec));
+    }
+    let mut registry = SemConvRegistry::from_semconv_specs("default", semconv_specs);
+    _ = SchemaResolver::resolve_semantic_convention_registry(&mut registry)
+        .map_err(|e| format!("The updated registry can't be resolved. {}", e))?;
+    Ok(())
+}
+
+/// Returns the fully qualified id of an attribute defined in a group.
+fn qualified_id(prefix: &str, id: &str) -> String {
+    if prefix.is_empty() {
+        id.to_owned()
+    } else {
+        format!("{}.{}", prefix, id)
+    }
+}
+
+/// Returns the column of the first character of a line.
+fn column(line: &str) -> usize {
+    line.len() - line.trim_start().len()
+}
+
+/// Returns true if the line is empty or a comment.
+fn is_blank(line: &str) -> bool {
+    let line = line.trim_start();
+    line.is_empty() || line.starts_with('#')
+}
+
+/// Parses a line of the form `[- ]key: value`.
+fn parse_entry(line: &str) -> Option<Entry<'_>> {
+    let content = line.trim_start();
+    let content = content.strip_prefix("- ").map_or(content, str::trim_start);
+    let column = line.len() - content.len();
+    let (key, rest) = content.split_once(':')?;
+    if key.is_empty() || !key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+        return None;
+    }
+    let rest = rest.find(" #").map_or(rest, |comment| &rest[..comment]);
+    let mut start = column + key.len() + 1 + rest.len() - rest.trim_start().len();
+    let mut value = rest.trim();
+    for quote in ['"', '\''] {
+        if value.len() >= 2 && value.starts_with(quote) && value.ends_with(quote) {
+            value = &value[1..value.len() - 1];
+            start += 1;
+        }
+    }
+    Some(Entry {
+        column,
+        key,
+        value,
+        range: start..start + value.len(),
+    })
+}
+
+/// Returns the byte range of the (unquoted) scalar of a `- value` line.
+fn sequence_scalar(line: &str) -> Option<Range<usize>> {
+    let content = line.trim_start().strip_prefix('-')?;
+    let content = content
+        .find(" #")
+        .map_or(content, |comment| &content[..comment]);
+    let mut start = line.len() - line.trim_start().len() + 1;
+    start += content.len() - content.trim_start().len();
+    let mut value = content.trim();
+    for quote in ['"', '\''] {
+        if value.len() >= 2 && value.starts_with(quote) && value.ends_with(quote) {
+            value = &value[1..value.len() - 1];
+            start += 1;
+        }
+    }
+    Some(start..start + value.len())
+}
+
+/// Replaces the occurrences of an id in a flow sequence, e.g. `[a, b]`.
+fn replace_token(value: &str, old: &str, new: &str) -> String {
+    let is_id_char = |c: char| c.is_ascii_alphanumeric() || c == '_' || c == '.';
+    let mut result = String::new();
+    let mut token = String::new();
+    for c in value.chars().chain(std::iter::once(' ')) {
+        if is_id_char(c) {
+            token.push(c);
+            continue;
+        }
+        result.push_str(if token == old { new } else { &token });
+        token.clear();
+        result.push(c);
+    }
+    _ = result.pop();
+    result
+}
+
+/// Finds the entry with the given key in the lines of a mapping (the column
+/// of its keys is the column of its first line).
+fn find_entry<'a>(
+    lines: &'a [String],
+    range: Range<usize>,
+    key: &str,
+) -> Option<(usize, Entry<'a>)> {
+    let column = parse_entry(lines.get(range.start)?)?.column;
+    range.into_iter().find_map(|line| {
+        parse_entry(&lines[line])
+            .filter(|entry| entry.column == column && entry.key == key)
+            .map(|entry| (line, entry))
+    })
+}
+
+/// Returns the line ranges of the items of the block sequence defined by the
+/// given key (at the given column) in the given lines.
+fn sequence_items(
+    lines: &[String],
+    range: Range<usize>,
+    key_column: usize,
+    key: &str,
+) -> Vec<Range<usize>> {
+    let Some(key_line) = range.clone().find(|&line| {
+        parse_entry(&lines[line]).is_some_and(|entry| {
+            entry.column == key_column && entry.key == key && entry.value.is_empty()
+        })
+    }) else {
+        return vec![];
+    };
+    let mut items: Vec<Range<usize>> = vec![];
+    let mut dash_column = None;
+    for (line, text) in lines.iter().enumerate().take(range.end).skip(key_line + 1) {
+        if is_blank(text) {
+            continue;
+        }
+        let is_item = text.trim_start().starts_with('-');
+        let dash = *dash_column.get_or_insert(column(text));
+        if is_item && column(text) == dash {
+            items.push(line..line + 1);
+        } else if column(text) > dash && !items.is_empty() {
+            if let Some(item) = items.last_mut() {
+                item.end = line + 1;
+            }
+        } else {
+            break;
+        }
+    }
+    items
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use weaver_semconv::semconv::SemConvSpec;
+
+    use crate::registry::rename_attribute::{check_registry, rename_attribute, SourceFile};
+
+    fn source_file(path: &str, content: &str) -> SourceFile {
+        SourceFile {
+            path: PathBuf::from(path),
+            original: content.to_owned(),
+            lines: content.lines().map(str::to_owned).collect(),
+            spec: SemConvSpec::from_string(content).expect("Invalid spec"),
+        }
+    }
+
+    const REGISTRY: &str = r#"groups:
+  - id: registry.http
+    type: attribute_group
+    brief: HTTP attributes.
+    prefix: http
+    attributes:
+      # The method of the request.
+      - id: method
+        type:
+          allow_custom_values: true
+          members:
+            - id: get
+              value: GET
+        stability: stable
+        brief: HTTP request method.
+        examples: [GET]
+
+      - id: route
+        type: string
+        brief: The matched route.
+        examples: ['/users/:id']
+"#;
+
+    const SPANS: &str = r#"groups:
+  - id: span.http.server
+    type: span
+    span_kind: server
+    brief: HTTP server span.
+    attributes:
+      - ref: http.method
+        requirement_level: required
+      - ref: http.route
+    constraints:
+      - any_of: [http.method, http.route]
+      - any_of:
+          - http.method
+"#;
+
+    #[test]
+    fn test_rename_attribute() {
+        let mut files = vec![
+            source_file("registry.yaml", REGISTRY),
+            source_file("spans.yaml", SPANS),
+        ];
+        rename_attribute(&mut files, "http.method", "http.request.method").expect("Rename failed");
+        check_registry(&files).expect("Invalid registry");
+        assert_eq!(
+            files[0].content(),
+            r#"groups:
+  - id: registry.http
+    type: attribute_group
+    brief: HTTP attributes.
+    prefix: http
+    attributes:
+      # The method of the request.
+      - id: request.method
+        type:
+          allow_custom_values: true
+          members:
+            - id: get
+              value: GET
+        stability: stable
+        brief: HTTP request method.
+        examples: [GET]
+        renamed_from: [http.method]
+      - id: method
+        type:
+          allow_custom_values: true
+          members:
+            - id: get
+              value: GET
+        examples: [GET]
+        stability: stable
+        brief: "Deprecated, use `http.request.method` instead."
+        deprecated: "Replaced by `http.request.method`."
+
+      - id: route
+        type: string
+        brief: The matched route.
+        examples: ['/users/:id']
+"#
+        );
+        assert_eq!(
+            files[1].content(),
+            SPANS
+                .replace("ref: http.method", "ref: http.request.method")
+                .replace("[http.method,", "[http.request.method,")
+                .replace("- http.method\n", "- http.request.method\n")
+        );
+
+        // The previous names are accumulated.
+        let mut files = vec![
+            source_file("registry.yaml", &files[0].content()),
+            source_file("spans.yaml", &files[1].content()),
+        ];
+        rename_attribute(&mut files, "http.request.method", "http.request.verb")
+            .expect("Rename failed");
+        check_registry(&files).expect("Invalid registry");
+        assert!(files[0]
+            .content()
+            .contains("        renamed_from: [http.method, http.request.method]\n"));
+    }
+
+    #[test]
+    fn test_rename_attribute_with_prefix_update() {
+        let mut files = vec![
+            source_file("registry.yaml", REGISTRY),
+            source_file("spans.yaml", SPANS),
+        ];
+        rename_attribute(&mut files, "http.route", "url.template").expect("Rename failed");
+        check_registry(&files).expect("Invalid registry");
+        let content = files[0].content();
+        assert!(!content.contains("prefix: http"));
+        assert!(content.contains("      - id: http.method\n"));
+        assert!(content.contains("      - id: url.template\n        type: string\n"));
+        assert!(content.contains("        renamed_from: [http.route]\n"));
+        assert!(content.contains(
+            "      - id: http.route\n        type: string\n        examples: ['/users/:id']\n        brief: \"Deprecated, use `url.template` instead.\"\n"
+        ));
+        assert!(files[1].content().contains("- ref: url.template\n"));
+        assert!(files[1].content().contains("[http.method, url.template]"));
+
+        // The old attribute must exist and the new one must not.
+        assert!(rename_attribute(&mut files, "http.unknown", "http.other").is_err());
+        assert!(rename_attribute(&mut files, "http.method", "url.template").is_err());
+    }
+}
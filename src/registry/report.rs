@@ -0,0 +1,377 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Reports on a resolved semantic convention registry, for reviews of the
+//! registry. The sensitivity report lists the attributes classified by
+//! sensitivity (see [`Sensitivity`]) with the telemetry groups using them, for
+//! privacy reviews.
+
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Write;
+use std::path::PathBuf;
+
+use clap::{Args, ValueEnum};
+use serde::Serialize;
+
+use weaver_cache::Cache;
+use weaver_common::diagnostic::DiagnosticMessages;
+use weaver_common::Logger;
+use weaver_forge::registry::{ResolvedGroup, ResolvedRegistry};
+use weaver_semconv::group::GroupType;
+use weaver_semconv::registry::SemConvRegistry;
+use weaver_semconv::sensitivity::Sensitivity;
+
+use crate::registry::search::group_type_name;
+use crate::registry::{Error, RegistryArgs};
+use crate::util::{load_semconv_specs, resolve_semconv_specs};
+use crate::{DiagnosticArgs, ExitDirectives};
+
+/// Supported output formats for the reports
+#[derive(Debug, Clone, ValueEnum)]
+pub enum ReportFormat {
+    /// JSON format
+    Json,
+    /// Markdown format
+    Markdown,
+}
+
+/// The reports to generate (at least one).
+#[derive(Debug, Args)]
+#[group(required = true, multiple = true)]
+pub struct ReportSelection {
+    /// Report the attributes classified by sensitivity (low, pii, secret) with
+    /// the telemetry groups using them.
+    #[arg(long)]
+    sensitivity: bool,
+}
+
+/// Parameters for the `registry report` sub-command
+#[derive(Debug, Args)]
+pub struct RegistryReportArgs {
+    /// Parameters to specify the semantic convention registry
+    #[command(flatten)]
+    registry: RegistryArgs,
+
+    /// The reports to generate.
+    #[command(flatten)]
+    reports: ReportSelection,
+
+    /// Output format of the report.
+    #[arg(short, long, default_value = "markdown")]
+    format: ReportFormat,
+
+    /// Output file to write the report to.
+    /// If not specified, the report is printed to stdout
+    #[arg(short, long)]
+    output: Option<PathBuf>,
+
+    /// Parameters to specify the diagnostic format.
+    #[command(flatten)]
+    pub diagnostic: DiagnosticArgs,
+}
+
+/// Reports on a resolved registry.
+#[derive(Debug, Default, Serialize)]
+struct Report {
+    /// The sensitivity report, if selected.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    sensitivity: Option<SensitivityReport>,
+}
+
+/// The attributes of a registry classified by sensitivity.
+#[derive(Debug, Serialize)]
+struct SensitivityReport {
+    /// The number of attributes per sensitivity level. The attributes without
+    /// classification are counted as `unclassified`.
+    summary: BTreeMap<String, usize>,
+    /// The attributes classified as `low`, `pii`, or `secret`, the most
+    /// sensitive first, then sorted by name.
+    attributes: Vec<SensitiveAttribute>,
+}
+
+/// An attribute classified as sensitive.
+#[derive(Debug, Serialize)]
+struct SensitiveAttribute {
+    /// The name of the attribute.
+    name: String,
+    /// The sensitivity of the attribute.
+    sensitivity: Sensitivity,
+    /// The brief of the attribute.
+    brief: String,
+    /// The telemetry groups (spans, events, metrics, resources, scopes) using
+    /// the attribute, sorted by id.
+    used_by: Vec<AttributeUsage>,
+}
+
+/// A telemetry group using a sensitive attribute.
+#[derive(Debug, Serialize)]
+struct AttributeUsage {
+    /// The id of the group.
+    group_id: String,
+    /// The type of the group.
+    r#type: &'static str,
+    /// The requirement level of the attribute in the group.
+    requirement_level: String,
+}
+
+/// Generate reports on a semantic convention registry.
+#[cfg(not(tarpaulin_include))]
+pub(crate) fn command(
+    logger: impl Logger + Sync + Clone,
+    cache: &Cache,
+    args: &RegistryReportArgs,
+) -> Result<ExitDirectives, DiagnosticMessages> {
+    if args.output.is_none() {
+        logger.mute();
+    }
+    logger.loading(&format!(
+        "Reporting on the registry `{}`",
+        args.registry.registries()
+    ));
+
+    let registry_id = "default";
+    let semconv_specs = load_semconv_specs(
+        &args.registry.registry_paths(),
+        &args.registry.file_filter()?,
+        cache,
+        logger.clone(),
+    )?;
+    let mut registry = SemConvRegistry::from_semconv_specs(registry_id, semconv_specs);
+    let schema = resolve_semconv_specs(&mut registry, logger.clone())?;
+    let registry = ResolvedRegistry::try_from_resolved_registry(
+        schema
+            .registry(registry_id)
+            .expect("Failed to get the registry from the resolved schema"),
+        schema.catalog(),
+    )?;
+
+    let mut report = Report::default();
+    if args.reports.sensitivity {
+        report.sensitivity = Some(SensitivityReport::new(&registry));
+    }
+
+    let output = match args.format {
+        ReportFormat::Json => {
+            let mut json =
+                serde_json::to_string_pretty(&report).expect("Failed to serialize the report");
+            json.push('\n');
+            json
+        }
+        ReportFormat::Markdown => report.to_markdown(),
+    };
+    if let Some(path) = &args.output {
+        std::fs::write(path, output).map_err(|e| Error::ReportWriteFailed {
+            output: path.clone(),
+            error: e.to_string(),
+        })?;
+        logger.success(&format!("Report written to `{}`", path.display()));
+    } else {
+        print!("{}", output);
+    }
+
+    Ok(ExitDirectives {
+        exit_code: 0,
+        quiet_mode: args.output.is_none(),
+    })
+}
+
+impl Report {
+    /// Renders the selected reports in Markdown.
+    fn to_markdown(&self) -> String {
+        let mut markdown = String::new();
+        if let Some(sensitivity) = &self.sensitivity {
+            markdown.push_str(&sensitivity.to_markdown());
+        }
+        markdown
+    }
+}
+
+impl SensitivityReport {
+    /// Builds the sensitivity report of a resolved registry from its index of
+    /// the groups using each attribute.
+    fn new(registry: &ResolvedRegistry) -> Self {
+        let groups: HashMap<&str, &ResolvedGroup> = registry
+            .groups
+            .iter()
+            .map(|group| (group.id.as_str(), group))
+            .collect();
+        let mut summary = BTreeMap::new();
+        let mut attributes = vec![];
+        for (name, usages) in &registry.attribute_index {
+            let Some(attr) = usages
+                .first()
+                .and_then(|usage| groups.get(usage.group_id.as_str()))
+                .and_then(|group| {
+                    group
+                        .attributes
+                        .iter()
+                        .find(|attr| attr.name.as_str() == name)
+                })
+            else {
+                continue;
+            };
+            let level = attr
+                .sensitivity
+                .map_or("unclassified".to_owned(), |s| s.to_string());
+            *summary.entry(level).or_insert(0) += 1;
+            let Some(sensitivity) = attr.sensitivity.filter(|s| *s > Sensitivity::None) else {
+                continue;
+            };
+            attributes.push(SensitiveAttribute {
+                name: name.clone(),
+                sensitivity,
+                brief: attr.brief.trim().to_owned(),
+                // The usages of the index are sorted by group id.
+                used_by: usages
+                    .iter()
+                    .filter(|usage| usage.group_type != GroupType::AttributeGroup)
+                    .map(|usage| AttributeUsage {
+                        group_id: usage.group_id.clone(),
+                        r#type: group_type_name(&usage.group_type),
+                        requirement_level: usage.requirement_level.to_string(),
+                    })
+                    .collect(),
+            });
+        }
+        attributes.sort_by(|a, b| b.sensitivity.cmp(&a.sensitivity).then(a.name.cmp(&b.name)));
+        Self {
+            summary,
+            attributes,
+        }
+    }
+
+    /// Renders the report in Markdown, with a summary table and a table of the
+    /// attributes of each sensitivity level.
+    fn to_markdown(&self) -> String {
+        let mut markdown =
+            String::from("# Sensitivity report\n\n| Sensitivity | Attributes |\n|---|---|\n");
+        for (level, count) in &self.summary {
+            _ = writeln!(markdown, "| {} | {} |", level, count);
+        }
+        let mut current_level = None;
+        for attr in &self.attributes {
+            if current_level != Some(attr.sensitivity) {
+                current_level = Some(attr.sensitivity);
+                _ = write!(
+                    markdown,
+                    "\n## {}\n\n| Attribute | Brief | Used by |\n|---|---|---|\n",
+                    attr.sensitivity
+                );
+            }
+            let used_by = attr
+                .used_by
+                .iter()
+                .map(|usage| format!("`{}` ({})", usage.group_id, usage.requirement_level))
+                .collect::<Vec<_>>()
+                .join(", ");
+            _ = writeln!(
+                markdown,
+                "| `{}` | {} | {} |",
+                attr.name,
+                attr.brief.replace('\n', " ").replace('|', "\\|"),
+                used_by
+            );
+        }
+        markdown
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use weaver_resolver::SchemaResolver;
+    use weaver_semconv::registry::SemConvRegistry;
+    use weaver_semconv::semconv::SemConvSpec;
+    use weaver_semconv::sensitivity::Sensitivity;
+
+    use weaver_forge::registry::ResolvedRegistry;
+
+    use crate::registry::report::SensitivityReport;
+
+    const REGISTRY: &str = r#"groups:
+  - id: registry.user
+    type: attribute_group
+    brief: User attributes.
+    prefix: user
+    attributes:
+      - id: email
+        type: string
+        brief: User email.
+        examples: [a@b.c]
+        sensitivity: pii
+      - id: password
+        type: string
+        brief: User password.
+        examples: [secret]
+        sensitivity: secret
+      - id: id
+        type: string
+        brief: User id.
+        examples: ['42']
+        sensitivity: none
+      - id: roles
+        type: string[]
+        brief: User roles.
+        examples: [[admin]]
+  - id: span.login
+    type: span
+    span_kind: server
+    brief: Login span.
+    attributes:
+      - ref: user.email
+        requirement_level: required
+      - ref: user.password
+        requirement_level: opt_in
+      - ref: user.roles
+  - id: event.logout
+    type: event
+    name: logout
+    brief: Logout event.
+    attributes:
+      - ref: user.email
+"#;
+
+    #[test]
+    fn test_sensitivity_report() {
+        let spec = SemConvSpec::from_string(REGISTRY).expect("Failed to parse the registry");
+        let mut registry =
+            SemConvRegistry::from_semconv_specs("default", vec![("user.yaml".to_owned(), spec)]);
+        let schema = SchemaResolver::resolve_semantic_convention_registry(&mut registry)
+            .expect("Failed to resolve the registry");
+        let registry = ResolvedRegistry::try_from_resolved_registry(
+            schema.registry("default").expect("Registry not found"),
+            schema.catalog(),
+        )
+        .expect("Failed to build the registry");
+        let report = SensitivityReport::new(&registry);
+
+        assert_eq!(report.summary["none"], 1);
+        assert_eq!(report.summary["pii"], 1);
+        assert_eq!(report.summary["secret"], 1);
+        assert_eq!(report.summary["unclassified"], 1);
+
+        let names: Vec<_> = report.attributes.iter().map(|a| a.name.as_str()).collect();
+        assert_eq!(names, vec!["user.password", "user.email"]);
+        assert_eq!(report.attributes[0].sensitivity, Sensitivity::Secret);
+        let email = &report.attributes[1];
+        let used_by: Vec<_> = email
+            .used_by
+            .iter()
+            .map(|u| (u.group_id.as_str(), u.r#type, u.requirement_level.as_str()))
+            .collect();
+        assert_eq!(
+            used_by,
+            vec![
+                ("event.logout", "event", "recommended"),
+                ("span.login", "span", "required")
+            ]
+        );
+
+        let markdown = report.to_markdown();
+        assert!(markdown.contains("| pii | 1 |"));
+        assert!(markdown.contains("## secret"));
+        assert!(markdown.contains(
+            "| `user.email` | User email. | `event.logout` (recommended), `span.login` (required) |"
+        ));
+        let json = serde_json::to_value(&report).expect("Failed to serialize the report");
+        assert_eq!(json["attributes"][0]["sensitivity"], "secret");
+    }
+}
@@ -2,7 +2,7 @@
 
 //! Resolve a semantic convention registry.
 
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use clap::Args;
 
@@ -13,10 +13,12 @@ use weaver_forge::registry::ResolvedRegistry;
 use weaver_semconv::registry::SemConvRegistry;
 
 use crate::format::{apply_format, Format};
+use crate::registry::binary::write_binary;
+use crate::registry::protobuf::write_protobuf;
 use crate::registry::RegistryArgs;
 use crate::util::{
-    check_policies, init_policy_engine, load_semconv_specs, resolve_semconv_specs,
-    semconv_registry_path_from,
+    check_policies, check_resolved_policies, init_policy_engine, load_semconv_specs,
+    resolve_semconv_specs,
 };
 use crate::{DiagnosticArgs, ExitDirectives};
 
@@ -32,6 +34,12 @@ pub struct RegistryResolveArgs {
     #[arg(long, default_value = "false")]
     lineage: bool,
 
+    /// Include the index of the groups using each attribute (with the
+    /// requirement level of each usage) in the resolved registry, under
+    /// `attribute_index`. Not supported by the protobuf format
+    #[arg(long, default_value = "false")]
+    attribute_index: bool,
+
     /// Output file to write the resolved schema to
     /// If not specified, the resolved schema is printed to stdout
     #[arg(short, long)]
@@ -39,7 +47,11 @@ pub struct RegistryResolveArgs {
 
     /// Output format for the resolved schema
     /// If not specified, the resolved schema is printed in YAML format
-    /// Supported formats: yaml, json
+    /// Supported formats: yaml, json, protobuf, binary
+    /// With protobuf, the `.proto` schema is written next to the output file
+    /// (same file stem, `.proto` extension)
+    /// With binary, the output can be loaded back by `weaver registry generate`
+    /// and `weaver registry check` with `--resolved-registry`
     /// Default format: yaml
     /// Example: `--format json`
     #[arg(short, long, default_value = "yaml")]
@@ -70,63 +82,100 @@ pub(crate) fn command(
     if args.output.is_none() {
         logger.mute();
     }
-    logger.loading(&format!("Resolving registry `{}`", args.registry.registry));
+    logger.loading(&format!(
+        "Resolving registry `{}`",
+        args.registry.registries()
+    ));
 
     let registry_id = "default";
-    let registry_path =
-        semconv_registry_path_from(&args.registry.registry, &args.registry.registry_git_sub_dir);
+    let registry_paths = args.registry.registry_paths();
 
     // Load the semantic convention registry into a local cache.
-    let semconv_specs = load_semconv_specs(&registry_path, cache, logger.clone())?;
+    let semconv_specs = load_semconv_specs(
+        &registry_paths,
+        &args.registry.file_filter()?,
+        cache,
+        logger.clone(),
+    )?;
 
-    if !args.skip_policies {
-        let policy_engine = init_policy_engine(&registry_path, cache, &args.policies, false)?;
+    let mut policy_engine = if !args.skip_policies {
+        let policy_engine = init_policy_engine(&registry_paths, cache, &args.policies, false)?;
         check_policies(&policy_engine, &semconv_specs, logger.clone())?;
-    }
+        Some(policy_engine)
+    } else {
+        None
+    };
 
     let mut registry = SemConvRegistry::from_semconv_specs(registry_id, semconv_specs);
     let schema = resolve_semconv_specs(&mut registry, logger.clone())?;
 
     // Serialize the resolved schema and write it
     // to a file or print it to stdout.
-    let registry = ResolvedRegistry::try_from_resolved_registry(
+    let mut registry = ResolvedRegistry::try_from_resolved_registry(
         schema
             .registry(registry_id)
             .expect("Failed to get the registry from the resolved schema"),
         schema.catalog(),
     )
     .unwrap_or_else(|e| panic!("Failed to create the registry without catalog: {e:?}"));
+    if !args.attribute_index {
+        registry.attribute_index.clear();
+    }
+
+    if let Some(policy_engine) = policy_engine.as_mut() {
+        check_resolved_policies(policy_engine, &registry_paths, &registry, logger.clone())?;
+    }
+
+    write_resolved_registry(&registry, &args.format, args.output.as_deref()).unwrap_or_else(|e| {
+        // Capture all the errors
+        panic!("{}", e);
+    });
 
-    apply_format(&args.format, &registry)
-        .map_err(|e| format!("Failed to serialize the registry: {e:?}"))
-        .and_then(|s| {
-            if let Some(ref path) = args.output {
+    Ok(ExitDirectives {
+        exit_code: 0,
+        quiet_mode: args.output.is_none(),
+    })
+}
+
+/// Writes a resolved registry in the given format to a file, or prints it to
+/// stdout if no output file is specified.
+pub(crate) fn write_resolved_registry(
+    registry: &ResolvedRegistry,
+    format: &Format,
+    output: Option<&Path>,
+) -> Result<(), String> {
+    match format {
+        Format::Protobuf => write_protobuf(registry, output),
+        Format::Binary => write_binary(registry, output),
+        _ => {
+            let content = apply_format(format, registry)
+                .map_err(|e| format!("Failed to serialize the registry: {e:?}"))?;
+            if let Some(path) = output {
                 // Write the resolved registry to a file.
-                std::fs::write(path, s)
+                std::fs::write(path, content)
                     .map_err(|e| format!("Failed to write the resolved registry to file: {e:?}"))
             } else {
                 // Print the resolved registry to stdout.
-                println!("{}", s);
+                println!("{}", content);
                 Ok(())
             }
-        })
-        .unwrap_or_else(|e| {
-            // Capture all the errors
-            panic!("{}", e);
-        });
-
-    Ok(ExitDirectives {
-        exit_code: 0,
-        quiet_mode: args.output.is_none(),
-    })
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use std::path::PathBuf;
+
+    use prost::Message;
+    use tempdir::TempDir;
     use weaver_common::TestLogger;
+    use weaver_diff::diff_dir;
 
     use crate::cli::{Cli, Commands};
     use crate::format::Format;
+    use crate::registry::generate::RegistryGenerateArgs;
+    use crate::registry::protobuf::{ResolvedRegistry, RESOLVED_REGISTRY_PROTO};
     use crate::registry::resolve::RegistryResolveArgs;
     use crate::registry::{RegistryArgs, RegistryCommand, RegistryPath, RegistrySubCommand};
     use crate::run_command;
@@ -137,15 +186,19 @@ mod tests {
         let cli = Cli {
             debug: 0,
             quiet: false,
+            cache: Default::default(),
             command: Some(Commands::Registry(RegistryCommand {
                 command: RegistrySubCommand::Resolve(RegistryResolveArgs {
                     registry: RegistryArgs {
-                        registry: RegistryPath::Local(
+                        registry: vec![RegistryPath::Local(
                             "crates/weaver_codegen_test/semconv_registry/".to_owned(),
-                        ),
+                        )],
                         registry_git_sub_dir: None,
+                        include: vec![],
+                        exclude: vec![],
                     },
                     lineage: true,
+                    attribute_index: true,
                     output: None,
                     format: Format::Yaml,
                     policies: vec![],
@@ -163,15 +216,19 @@ mod tests {
         let cli = Cli {
             debug: 0,
             quiet: false,
+            cache: Default::default(),
             command: Some(Commands::Registry(RegistryCommand {
                 command: RegistrySubCommand::Resolve(RegistryResolveArgs {
                     registry: RegistryArgs {
-                        registry: RegistryPath::Local(
+                        registry: vec![RegistryPath::Local(
                             "crates/weaver_codegen_test/semconv_registry/".to_owned(),
-                        ),
+                        )],
                         registry_git_sub_dir: None,
+                        include: vec![],
+                        exclude: vec![],
                     },
                     lineage: true,
+                    attribute_index: false,
                     output: None,
                     format: Format::Json,
                     policies: vec![],
@@ -185,4 +242,131 @@ mod tests {
         // The command should exit with an error code.
         assert_eq!(exit_directive.exit_code, 1);
     }
+
+    #[test]
+    fn test_registry_resolve_protobuf() {
+        let logger = TestLogger::new();
+        let output = TempDir::new("weaver-resolve").expect("Failed to create a temp dir");
+        let output_file = output.path().join("registry.binpb");
+        let cli = Cli {
+            debug: 0,
+            quiet: false,
+            cache: Default::default(),
+            command: Some(Commands::Registry(RegistryCommand {
+                command: RegistrySubCommand::Resolve(RegistryResolveArgs {
+                    registry: RegistryArgs {
+                        registry: vec![RegistryPath::Local(
+                            "crates/weaver_codegen_test/semconv_registry/".to_owned(),
+                        )],
+                        registry_git_sub_dir: None,
+                        include: vec![],
+                        exclude: vec![],
+                    },
+                    lineage: false,
+                    attribute_index: false,
+                    output: Some(output_file.clone()),
+                    format: Format::Protobuf,
+                    policies: vec![],
+                    skip_policies: true,
+                    diagnostic: Default::default(),
+                }),
+            })),
+        };
+
+        let exit_directive = run_command(&cli, logger);
+        assert_eq!(exit_directive.exit_code, 0);
+
+        let bytes = std::fs::read(&output_file).expect("Failed to read the protobuf output");
+        let registry =
+            ResolvedRegistry::decode(bytes.as_slice()).expect("Failed to decode the registry");
+        assert!(registry
+            .groups
+            .iter()
+            .any(|g| g.r#type == "metric" && g.metric_name.is_some()));
+
+        let schema = std::fs::read_to_string(output.path().join("registry.proto"))
+            .expect("Failed to read the protobuf schema");
+        assert_eq!(schema, RESOLVED_REGISTRY_PROTO);
+    }
+
+    #[test]
+    fn test_registry_resolve_binary() {
+        let logger = TestLogger::new();
+        let temp_dir = TempDir::new("weaver-resolve").expect("Failed to create a temp dir");
+        let resolved_registry = temp_dir.path().join("registry.bin");
+        let registry = || RegistryArgs {
+            registry: vec![RegistryPath::Local(
+                "crates/weaver_codegen_test/semconv_registry/".to_owned(),
+            )],
+            registry_git_sub_dir: None,
+            include: vec![],
+            exclude: vec![],
+        };
+        let cli = Cli {
+            debug: 0,
+            quiet: false,
+            cache: Default::default(),
+            command: Some(Commands::Registry(RegistryCommand {
+                command: RegistrySubCommand::Resolve(RegistryResolveArgs {
+                    registry: registry(),
+                    lineage: false,
+                    attribute_index: false,
+                    output: Some(resolved_registry.clone()),
+                    format: Format::Binary,
+                    policies: vec![],
+                    skip_policies: true,
+                    diagnostic: Default::default(),
+                }),
+            })),
+        };
+        let exit_directive = run_command(&cli, logger.clone());
+        assert_eq!(exit_directive.exit_code, 0);
+
+        // The artifacts generated from the binary resolved registry are the same
+        // as the artifacts generated from the registry files.
+        let generate = |resolved_registry: Option<PathBuf>, output: PathBuf| Cli {
+            debug: 0,
+            quiet: false,
+            cache: Default::default(),
+            command: Some(Commands::Registry(RegistryCommand {
+                command: RegistrySubCommand::Generate(RegistryGenerateArgs {
+                    target: "rust".to_owned(),
+                    output,
+                    templates: PathBuf::from("crates/weaver_codegen_test/templates/"),
+                    param: None,
+                    params: None,
+                    registry: registry(),
+                    policies: vec![],
+                    skip_policies: true,
+                    verify: false,
+                    dry_run: false,
+                    watch: false,
+                    resolved_registry,
+                    diagnostic: Default::default(),
+                }),
+            })),
+        };
+        let expected = temp_dir.path().join("expected");
+        let observed = temp_dir.path().join("observed");
+        let exit_directive = run_command(&generate(None, expected.clone()), logger.clone());
+        assert_eq!(exit_directive.exit_code, 0);
+        let exit_directive = run_command(
+            &generate(Some(resolved_registry), observed.clone()),
+            logger.clone(),
+        );
+        assert_eq!(exit_directive.exit_code, 0);
+        assert!(diff_dir(&expected, &observed).expect("Failed to compare the outputs"));
+
+        // A file which is not a binary resolved registry is rejected.
+        let exit_directive = run_command(
+            &generate(
+                Some(PathBuf::from(
+                    "crates/weaver_codegen_test/semconv_registry/http-common.yaml",
+                )),
+                observed,
+            ),
+            logger,
+        );
+        assert_eq!(exit_directive.exit_code, 1);
+    }
 }
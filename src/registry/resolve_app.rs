@@ -0,0 +1,176 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Resolve an application telemetry schema against a semantic convention
+//! registry.
+
+use std::path::PathBuf;
+
+use clap::Args;
+
+use weaver_cache::Cache;
+use weaver_common::diagnostic::DiagnosticMessages;
+use weaver_common::Logger;
+use weaver_forge::registry::ResolvedRegistry;
+use weaver_schema::app::AppSchema;
+use weaver_semconv::registry::SemConvRegistry;
+
+use crate::format::{apply_format, Format};
+use crate::registry::{Error, RegistryArgs};
+use crate::util::{load_semconv_specs, resolve_semconv_specs};
+use crate::{DiagnosticArgs, ExitDirectives};
+
+/// Parameters for the `registry resolve-app` sub-command
+#[derive(Debug, Args)]
+pub struct RegistryResolveAppArgs {
+    /// Path to the application schema declaring the resources, instrumentation
+    /// scopes, spans, metrics, and events emitted by the application.
+    pub app_schema: PathBuf,
+
+    /// Parameters to specify the semantic convention registry
+    #[command(flatten)]
+    registry: RegistryArgs,
+
+    /// Output file to write the resolved application schema to
+    /// If not specified, the resolved application schema is printed to stdout
+    #[arg(short, long)]
+    output: Option<PathBuf>,
+
+    /// Output format for the resolved application schema
+    /// Supported formats: yaml, json
+    #[arg(short, long, default_value = "yaml")]
+    format: Format,
+
+    /// Parameters to specify the diagnostic format.
+    #[command(flatten)]
+    pub diagnostic: DiagnosticArgs,
+}
+
+/// Resolve an application schema against a semantic convention registry and
+/// write the resolved application schema to a file or print it to stdout.
+#[cfg(not(tarpaulin_include))]
+pub(crate) fn command(
+    logger: impl Logger + Sync + Clone,
+    cache: &Cache,
+    args: &RegistryResolveAppArgs,
+) -> Result<ExitDirectives, DiagnosticMessages> {
+    if args.output.is_none() {
+        logger.mute();
+    }
+    logger.loading(&format!(
+        "Resolving application schema `{}` against registry `{}`",
+        args.app_schema.display(),
+        args.registry.registries()
+    ));
+
+    let app_schema = AppSchema::load_from_file(&args.app_schema)?;
+
+    let registry_id = "default";
+    let registry_paths = args.registry.registry_paths();
+    let semconv_specs = load_semconv_specs(
+        &registry_paths,
+        &args.registry.file_filter()?,
+        cache,
+        logger.clone(),
+    )?;
+    let mut registry = SemConvRegistry::from_semconv_specs(registry_id, semconv_specs);
+    let schema = resolve_semconv_specs(&mut registry, logger.clone())?;
+    let resolved_registry = ResolvedRegistry::try_from_resolved_registry(
+        schema
+            .registry(registry_id)
+            .expect("Failed to get the registry from the resolved schema"),
+        schema.catalog(),
+    )?;
+
+    let resolved_app_schema = app_schema.resolve(&resolved_registry)?;
+    logger.success("Application schema resolved");
+
+    let content = apply_format(&args.format, &resolved_app_schema).map_err(|error| {
+        Error::AppSchemaWriteFailed {
+            app_schema: args.app_schema.clone(),
+            error,
+        }
+    })?;
+    if let Some(path) = &args.output {
+        std::fs::write(path, content).map_err(|e| Error::AppSchemaWriteFailed {
+            app_schema: args.app_schema.clone(),
+            error: e.to_string(),
+        })?;
+    } else {
+        println!("{}", content);
+    }
+
+    Ok(ExitDirectives {
+        exit_code: 0,
+        quiet_mode: args.output.is_none(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use tempdir::TempDir;
+    use weaver_common::TestLogger;
+
+    use crate::cli::{Cli, Commands};
+    use crate::format::Format;
+    use crate::registry::resolve_app::RegistryResolveAppArgs;
+    use crate::registry::{RegistryArgs, RegistryCommand, RegistryPath, RegistrySubCommand};
+    use crate::run_command;
+
+    fn cli(app_schema: &str, output: Option<PathBuf>) -> Cli {
+        Cli {
+            debug: 0,
+            quiet: false,
+            cache: Default::default(),
+            command: Some(Commands::Registry(RegistryCommand {
+                command: RegistrySubCommand::ResolveApp(RegistryResolveAppArgs {
+                    app_schema: PathBuf::from(app_schema),
+                    registry: RegistryArgs {
+                        registry: vec![RegistryPath::Local(
+                            "crates/weaver_schema/data/app/registry".to_owned(),
+                        )],
+                        registry_git_sub_dir: None,
+                        include: vec![],
+                        exclude: vec![],
+                    },
+                    output,
+                    format: Format::Json,
+                    diagnostic: Default::default(),
+                }),
+            })),
+        }
+    }
+
+    #[test]
+    fn test_registry_resolve_app() {
+        let logger = TestLogger::new();
+        let output_dir = TempDir::new("weaver-resolve-app").expect("Failed to create a temp dir");
+        let output = output_dir.path().join("app.json");
+        let exit_directive = run_command(
+            &cli(
+                "crates/weaver_schema/data/app/app.yaml",
+                Some(output.clone()),
+            ),
+            logger.clone(),
+        );
+        assert_eq!(exit_directive.exit_code, 0);
+        assert_eq!(logger.error_count(), 0);
+
+        let resolved: serde_json::Value = serde_json::from_str(
+            &std::fs::read_to_string(&output).expect("Failed to read the output"),
+        )
+        .expect("Invalid JSON");
+        assert_eq!(
+            resolved["instrumentation_scopes"][0]["spans"][0]["id"],
+            "span.http.server"
+        );
+
+        // The application schema doesn't exist.
+        let exit_directive = run_command(
+            &cli("crates/weaver_schema/data/app/unknown.yaml", None),
+            logger,
+        );
+        assert_eq!(exit_directive.exit_code, 1);
+    }
+}
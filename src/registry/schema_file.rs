@@ -0,0 +1,208 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Generate the telemetry schema file of a semantic convention registry.
+
+use std::path::PathBuf;
+use std::str::FromStr;
+
+use clap::Args;
+
+use weaver_cache::Cache;
+use weaver_common::diagnostic::DiagnosticMessages;
+use weaver_common::Logger;
+use weaver_forge::registry::ResolvedRegistry;
+use weaver_resolver::file_filter::RegistryFileFilter;
+use weaver_schema::SchemaFile;
+
+use crate::registry::{Error, RegistryPath};
+use crate::util::{resolve_registries, semconv_registry_path_from};
+use crate::{DiagnosticArgs, ExitDirectives};
+
+/// A version of a semantic convention registry, specified on the command line
+/// as `<VERSION>=<REGISTRY>`.
+#[derive(Debug, Clone)]
+pub struct RegistryVersion {
+    /// The version of the registry.
+    pub version: semver::Version,
+    /// Local path or Git URL of the registry for this version.
+    pub registry: RegistryPath,
+}
+
+impl FromStr for RegistryVersion {
+    type Err = String;
+
+    /// Parse a string of the form `<VERSION>=<REGISTRY>`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (version, registry) = s
+            .split_once('=')
+            .ok_or_else(|| format!("`{s}` is not of the form `<VERSION>=<REGISTRY>`"))?;
+        let version = semver::Version::parse(version)
+            .map_err(|e| format!("`{version}` is not a valid version: {e}"))?;
+        Ok(Self {
+            version,
+            registry: RegistryPath::from_str(registry)?,
+        })
+    }
+}
+
+/// Parameters for the `registry schema-file` sub-command
+#[derive(Debug, Args)]
+pub struct RegistrySchemaFileArgs {
+    /// Version of the registry and local path or Git URL of the registry for
+    /// this version, e.g. `1.26.0=semconv-1.26.0/model`.
+    /// Repeat the option for each version of the schema file.
+    #[arg(
+        long = "registry-version",
+        value_name = "VERSION=REGISTRY",
+        required = true
+    )]
+    pub registry_versions: Vec<RegistryVersion>,
+
+    /// Optional path in the Git repository where the semantic convention
+    /// registry is located
+    #[arg(short = 'd', long, default_value = "model")]
+    pub registry_git_sub_dir: Option<String>,
+
+    /// URL at which the schema file is published, the last segment of which is
+    /// usually the latest version, e.g. `https://opentelemetry.io/schemas/1.26.0`.
+    #[arg(long)]
+    pub schema_url: String,
+
+    /// Output file to write the schema file to.
+    /// If not specified, the schema file is printed to stdout
+    #[arg(short, long)]
+    pub output: Option<PathBuf>,
+
+    /// Parameters to specify the diagnostic format.
+    #[command(flatten)]
+    pub diagnostic: DiagnosticArgs,
+}
+
+/// Generate the telemetry schema file of the versions of a semantic convention
+/// registry and write it to a file or print it to stdout.
+#[cfg(not(tarpaulin_include))]
+pub(crate) fn command(
+    logger: impl Logger + Sync + Clone,
+    cache: &Cache,
+    args: &RegistrySchemaFileArgs,
+) -> Result<ExitDirectives, DiagnosticMessages> {
+    if args.output.is_none() {
+        logger.mute();
+    }
+
+    logger.loading(&format!(
+        "Resolving registries {}",
+        args.registry_versions
+            .iter()
+            .map(|registry_version| format!(
+                "`{}` (version {})",
+                registry_version.registry, registry_version.version
+            ))
+            .collect::<Vec<_>>()
+            .join(", ")
+    ));
+    let registry_paths: Vec<_> = args
+        .registry_versions
+        .iter()
+        .map(|registry_version| {
+            [semconv_registry_path_from(
+                &registry_version.registry,
+                &args.registry_git_sub_dir,
+            )]
+        })
+        .collect();
+    let file_filter = RegistryFileFilter::default();
+    // The versions of the registry are independent and are resolved
+    // concurrently.
+    let resolved = resolve_registries(
+        &registry_paths
+            .iter()
+            .map(|paths| (paths.as_slice(), &file_filter))
+            .collect::<Vec<_>>(),
+        cache,
+        logger.clone(),
+    )?;
+    let mut registries = vec![];
+    for (registry_version, (_, schema)) in args.registry_versions.iter().zip(resolved) {
+        let resolved_registry = ResolvedRegistry::try_from_resolved_registry(
+            schema
+                .registry("default")
+                .expect("Failed to get the registry from the resolved schema"),
+            schema.catalog(),
+        )?;
+        registries.push((registry_version.version.clone(), resolved_registry));
+    }
+
+    let schema_file = SchemaFile::from_registries(&args.schema_url, registries)?;
+    let yaml = schema_file.to_yaml()?;
+    if let Some(path) = &args.output {
+        std::fs::write(path, yaml).map_err(|e| Error::SchemaFileWriteFailed {
+            output: path.clone(),
+            error: e.to_string(),
+        })?;
+        logger.success(&format!("Schema file written to `{}`", path.display()));
+    } else {
+        print!("{}", yaml);
+    }
+
+    Ok(ExitDirectives {
+        exit_code: 0,
+        quiet_mode: args.output.is_none(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use tempdir::TempDir;
+    use weaver_common::TestLogger;
+    use weaver_version::Versions;
+
+    use crate::cli::{Cli, Commands};
+    use crate::registry::schema_file::{RegistrySchemaFileArgs, RegistryVersion};
+    use crate::registry::{RegistryCommand, RegistrySubCommand};
+    use crate::run_command;
+
+    #[test]
+    fn test_registry_schema_file() {
+        let logger = TestLogger::new();
+        let output_dir = TempDir::new("weaver-schema-file").expect("Failed to create a temp dir");
+        let temp_output = output_dir.path().join("1.1.0");
+        let cli = Cli {
+            debug: 0,
+            quiet: false,
+            cache: Default::default(),
+            command: Some(Commands::Registry(RegistryCommand {
+                command: RegistrySubCommand::SchemaFile(RegistrySchemaFileArgs {
+                    registry_versions: vec![
+                        RegistryVersion::from_str("1.0.0=crates/weaver_schema/data/v1.0.0")
+                            .expect("Invalid registry version"),
+                        RegistryVersion::from_str("1.1.0=crates/weaver_schema/data/v1.1.0")
+                            .expect("Invalid registry version"),
+                    ],
+                    registry_git_sub_dir: None,
+                    schema_url: "https://example.com/schemas/1.1.0".to_owned(),
+                    output: Some(temp_output.clone()),
+                    diagnostic: Default::default(),
+                }),
+            })),
+        };
+
+        let exit_directive = run_command(&cli, logger.clone());
+        assert_eq!(exit_directive.exit_code, 0);
+        assert_eq!(logger.error_count(), 0);
+
+        // The generated schema file is a valid `versions` file.
+        let versions = Versions::load_from_file(&temp_output).expect("Invalid schema file");
+        let changes =
+            versions.version_changes_for(versions.latest_version().as_ref().expect("No versions"));
+        assert_eq!(
+            changes.get_resource_attribute_name("http.method"),
+            "http.request.method"
+        );
+
+        assert!(RegistryVersion::from_str("crates/weaver_schema/data/v1.0.0").is_err());
+        assert!(RegistryVersion::from_str("1.0=crates/weaver_schema/data/v1.0.0").is_err());
+    }
+}
@@ -0,0 +1,433 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Search a semantic convention registry.
+
+mod tui;
+
+use std::collections::BTreeMap;
+use std::fmt::Display;
+
+use clap::{Args, ValueEnum};
+use fuzzy_matcher::skim::SkimMatcherV2;
+use fuzzy_matcher::FuzzyMatcher;
+use serde::{Deserialize, Serialize};
+
+use weaver_cache::Cache;
+use weaver_common::cancellation::CancellationToken;
+use weaver_common::diagnostic::DiagnosticMessages;
+use weaver_common::Logger;
+use weaver_forge::registry::{ResolvedGroup, ResolvedRegistry};
+use weaver_resolver::{ResolutionMode, ResolverOptions};
+use weaver_semconv::group::GroupType;
+use weaver_semconv::registry::SemConvRegistry;
+use weaver_semconv::stability::Stability;
+
+use crate::registry::{Error, RegistryArgs};
+use crate::util::{load_semconv_specs_with_mode, resolve_semconv_specs_with_options};
+use crate::{DiagnosticArgs, ExitDirectives};
+
+/// Parameters for the `registry search` sub-command
+#[derive(Debug, Args)]
+pub struct RegistrySearchArgs {
+    /// Parameters to specify the semantic convention registry
+    #[command(flatten)]
+    registry: RegistryArgs,
+
+    /// The search query, fuzzy matched against the ids and substring matched
+    /// against the briefs and notes of the registry items.
+    #[arg(required_unless_present = "interactive")]
+    pub query: Option<String>,
+
+    /// Browse the registry in an interactive terminal UI with incremental
+    /// search (the query, if any, is the initial query).
+    #[arg(short, long)]
+    pub interactive: bool,
+
+    /// Kinds of registry items to search (all kinds by default).
+    #[arg(short, long, value_enum)]
+    pub kind: Vec<SearchKind>,
+
+    /// Maximum number of matches to print (ignored in interactive mode).
+    #[arg(short, long, default_value = "20")]
+    pub limit: usize,
+
+    /// Load and resolve the registry in lenient mode: the files that fail to
+    /// load are skipped, the unresolved attribute references are replaced with
+    /// placeholder attributes, the unresolved `extends` and `include`
+    /// references are ignored, and the corresponding errors are reported as
+    /// warnings.
+    #[arg(long)]
+    pub lenient: bool,
+
+    /// Parameters to specify the diagnostic format.
+    #[command(flatten)]
+    pub diagnostic: DiagnosticArgs,
+}
+
+/// The kind of a registry item.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SearchKind {
+    /// An attribute.
+    Attribute,
+    /// A metric.
+    Metric,
+    /// A group that is not a metric.
+    Group,
+}
+
+impl Display for SearchKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SearchKind::Attribute => write!(f, "attribute"),
+            SearchKind::Metric => write!(f, "metric"),
+            SearchKind::Group => write!(f, "group"),
+        }
+    }
+}
+
+/// An item of a resolved registry that can be searched.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RegistryItem {
+    /// The kind of the item.
+    pub kind: SearchKind,
+    /// The attribute name, the metric name, or the group id.
+    pub id: String,
+    /// The id of the group defining the item.
+    pub group_id: String,
+    /// The attribute type, the metric instrument and unit, or the group type.
+    pub r#type: String,
+    /// The stability of the item.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stability: Option<Stability>,
+    /// The brief of the item.
+    pub brief: String,
+    /// The note of the item.
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub note: String,
+    /// The source file of the group defining the item.
+    pub provenance: String,
+}
+
+/// Returns all the attributes, metrics, and groups of a resolved registry.
+/// Each attribute is reported once, with the group defining it.
+pub(crate) fn registry_items(registry: &ResolvedRegistry) -> Vec<RegistryItem> {
+    let mut items = vec![];
+    // Attribute name -> (item, whether the group is the one defining the attribute).
+    let mut attributes: BTreeMap<String, (RegistryItem, bool)> = BTreeMap::new();
+
+    for group in &registry.groups {
+        let provenance = group
+            .lineage
+            .as_ref()
+            .map(|lineage| lineage.source_file().to_owned())
+            .unwrap_or_default();
+
+        for attr in &group.attributes {
+            let defined = group
+                .lineage
+                .as_ref()
+                .map_or(true, |lineage| lineage.attribute(&attr.name).is_none());
+            if attributes
+                .get(attr.name.as_str())
+                .is_some_and(|(_, found_defined)| *found_defined || !defined)
+            {
+                continue;
+            }
+            _ = attributes.insert(
+                attr.name.to_string(),
+                (
+                    RegistryItem {
+                        kind: SearchKind::Attribute,
+                        id: attr.name.to_string(),
+                        group_id: group.id.clone(),
+                        r#type: attr.r#type.to_string(),
+                        stability: attr.stability.clone(),
+                        brief: attr.brief.to_string(),
+                        note: attr.note.to_string(),
+                        provenance: provenance.clone(),
+                    },
+                    defined,
+                ),
+            );
+        }
+
+        items.push(group_item(group, provenance));
+    }
+
+    items.extend(attributes.into_values().map(|(item, _)| item));
+    items
+}
+
+/// Returns the item describing a group (or the metric it defines).
+fn group_item(group: &ResolvedGroup, provenance: String) -> RegistryItem {
+    let (kind, id, r#type) = match (&group.r#type, &group.metric_name) {
+        (GroupType::Metric, Some(metric_name)) => {
+            let instrument = group
+                .instrument
+                .as_ref()
+                .map(|instrument| instrument.to_string())
+                .unwrap_or_default();
+            let r#type = match &group.unit {
+                Some(unit) => format!("{instrument}, unit: {unit}"),
+                None => instrument,
+            };
+            (SearchKind::Metric, metric_name.clone(), r#type)
+        }
+        (group_type, _) => (
+            SearchKind::Group,
+            group.id.clone(),
+            group_type_name(group_type).to_owned(),
+        ),
+    };
+    RegistryItem {
+        kind,
+        id,
+        group_id: group.id.clone(),
+        r#type,
+        stability: group.stability.clone(),
+        brief: group.brief.clone(),
+        note: group.note.clone(),
+        provenance,
+    }
+}
+
+/// Returns the name of a group type as used in the semantic convention files.
+pub(crate) fn group_type_name(group_type: &GroupType) -> &'static str {
+    match group_type {
+        GroupType::AttributeGroup => "attribute_group",
+        GroupType::Span => "span",
+        GroupType::Event => "event",
+        GroupType::Metric => "metric",
+        GroupType::MetricGroup => "metric_group",
+        GroupType::Resource => "resource",
+        GroupType::Scope => "scope",
+    }
+}
+
+/// Returns the items matching the query, the best matches first, with their
+/// score. The ids are fuzzy matched and the briefs and notes are substring
+/// matched (case insensitive). Matches on the ids rank higher than matches on
+/// the briefs and notes.
+pub(crate) fn search<'a>(
+    items: &'a [RegistryItem],
+    query: &str,
+    kinds: &[SearchKind],
+) -> Vec<(i64, &'a RegistryItem)> {
+    let matcher = SkimMatcherV2::default().ignore_case();
+    let lowercase_query = query.to_lowercase();
+    let text_score = |text: &str| {
+        if text.to_lowercase().contains(&lowercase_query) {
+            matcher.fuzzy_match(text, query)
+        } else {
+            None
+        }
+    };
+
+    let mut matches: Vec<_> = items
+        .iter()
+        .filter(|item| kinds.is_empty() || kinds.contains(&item.kind))
+        .filter_map(|item| {
+            let id_score = matcher.fuzzy_match(&item.id, query).map(|score| score * 2);
+            [id_score, text_score(&item.brief), text_score(&item.note)]
+                .into_iter()
+                .flatten()
+                .max()
+                .map(|score| (score, item))
+        })
+        .collect();
+    matches.sort_by(|(score1, item1), (score2, item2)| {
+        score2
+            .cmp(score1)
+            .then_with(|| item1.kind.cmp(&item2.kind))
+            .then_with(|| item1.id.cmp(&item2.id))
+    });
+    matches
+}
+
+/// Search a semantic convention registry and print the matching items.
+#[cfg(not(tarpaulin_include))]
+pub(crate) fn command(
+    logger: impl Logger + Sync + Clone,
+    cache: &Cache,
+    args: &RegistrySearchArgs,
+) -> Result<ExitDirectives, DiagnosticMessages> {
+    logger.mute();
+
+    let registry_id = "default";
+    let registry_paths = args.registry.registry_paths();
+    let options = ResolverOptions {
+        mode: if args.lenient {
+            ResolutionMode::Lenient
+        } else {
+            ResolutionMode::Strict
+        },
+        ..ResolverOptions::default()
+    };
+    let (semconv_specs, mut warnings) = load_semconv_specs_with_mode(
+        &registry_paths,
+        &args.registry.file_filter()?,
+        options.mode,
+        cache,
+        logger.clone(),
+    )?;
+    let mut registry = SemConvRegistry::from_semconv_specs(registry_id, semconv_specs);
+    let (schema, resolution_warnings) = resolve_semconv_specs_with_options(
+        &mut registry,
+        &options,
+        &CancellationToken::default(),
+        logger.clone(),
+    )?;
+    warnings.extend(resolution_warnings);
+    let registry = ResolvedRegistry::try_from_resolved_registry(
+        schema
+            .registry(registry_id)
+            .expect("Failed to get the registry from the resolved schema"),
+        schema.catalog(),
+    )?;
+
+    let items = registry_items(&registry);
+    if args.interactive {
+        tui::run(&registry, &items, args.query.clone().unwrap_or_default()).map_err(|e| {
+            Error::InteractiveSearchFailed {
+                error: e.to_string(),
+            }
+        })?;
+        if !warnings.is_empty() {
+            return Err(warnings);
+        }
+        return Ok(ExitDirectives {
+            exit_code: 0,
+            quiet_mode: true,
+        });
+    }
+
+    let query = args.query.as_deref().unwrap_or_default();
+    let matches = search(&items, query, &args.kind);
+    for (_, item) in matches.iter().take(args.limit) {
+        let stability = item
+            .stability
+            .as_ref()
+            .map(|stability| format!(", {stability}"))
+            .unwrap_or_default();
+        println!("{} {} ({}{})", item.kind, item.id, item.r#type, stability);
+        if item.kind != SearchKind::Group {
+            println!("  defined in {}", item.group_id);
+        }
+        if !item.provenance.is_empty() {
+            println!("  source: {}", item.provenance);
+        }
+        if !item.brief.is_empty() {
+            println!("  {}", item.brief.trim());
+        }
+    }
+    println!(
+        "{} of {} match(es) for `{}`",
+        matches.len().min(args.limit),
+        matches.len(),
+        query
+    );
+
+    // The errors ignored by the lenient resolution are reported as warnings.
+    if !warnings.is_empty() {
+        return Err(warnings);
+    }
+    Ok(ExitDirectives {
+        exit_code: 0,
+        quiet_mode: true,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use weaver_common::TestLogger;
+    use weaver_forge::registry::ResolvedRegistry;
+    use weaver_resolver::SchemaResolver;
+    use weaver_semconv::registry::SemConvRegistry;
+
+    use crate::cli::{Cli, Commands};
+    use crate::registry::search::{registry_items, search, RegistrySearchArgs, SearchKind};
+    use crate::registry::{RegistryArgs, RegistryCommand, RegistryPath, RegistrySubCommand};
+    use crate::run_command;
+
+    #[test]
+    fn test_search() {
+        let registry_id = "default";
+        let mut registry = SemConvRegistry::try_from_path_pattern(
+            registry_id,
+            "crates/weaver_codegen_test/semconv_registry/**/*.yaml",
+        )
+        .expect("Failed to load the registry");
+        let schema = SchemaResolver::resolve_semantic_convention_registry(&mut registry)
+            .expect("Failed to resolve the registry");
+        let registry = ResolvedRegistry::try_from_resolved_registry(
+            schema
+                .registry(registry_id)
+                .expect("Failed to get the registry from the resolved schema"),
+            schema.catalog(),
+        )
+        .expect("Failed to create the resolved registry");
+        let items = registry_items(&registry);
+
+        // Attributes are reported once, with the group defining them.
+        let method: Vec<_> = items
+            .iter()
+            .filter(|item| item.id == "http.request.method")
+            .collect();
+        assert_eq!(method.len(), 1);
+        assert_eq!(method[0].kind, SearchKind::Attribute);
+        assert_eq!(method[0].group_id, "registry.http");
+        assert!(method[0].provenance.ends_with("registry/http.yaml"));
+
+        // Fuzzy match on the ids.
+        let matches = search(&items, "httpreqmethod", &[]);
+        assert_eq!(matches[0].1.id, "http.request.method");
+
+        // Filter on the kinds.
+        let matches = search(&items, "http.server", &[SearchKind::Metric]);
+        assert!(!matches.is_empty());
+        assert!(matches
+            .iter()
+            .all(|(_, item)| item.kind == SearchKind::Metric));
+        assert_eq!(matches[0].1.id, "http.server.request.duration");
+        assert_eq!(matches[0].1.group_id, "metric.http.server.request.duration");
+
+        // Substring match on the briefs.
+        let matches = search(&items, "logical cpu", &[]);
+        assert!(matches.iter().any(|(_, item)| item.id == "system.cpu.time"));
+
+        assert!(search(&items, "no such item", &[]).is_empty());
+    }
+
+    #[test]
+    fn test_registry_search() {
+        let logger = TestLogger::new();
+        let cli = Cli {
+            debug: 0,
+            quiet: false,
+            cache: Default::default(),
+            command: Some(Commands::Registry(RegistryCommand {
+                command: RegistrySubCommand::Search(RegistrySearchArgs {
+                    registry: RegistryArgs {
+                        registry: vec![RegistryPath::Local(
+                            "crates/weaver_codegen_test/semconv_registry/".to_owned(),
+                        )],
+                        registry_git_sub_dir: None,
+                        include: vec![],
+                        exclude: vec![],
+                    },
+                    query: Some("request.method".to_owned()),
+                    interactive: false,
+                    kind: vec![],
+                    limit: 5,
+                    lenient: false,
+                    diagnostic: Default::default(),
+                }),
+            })),
+        };
+
+        let exit_directive = run_command(&cli, logger.clone());
+        assert_eq!(exit_directive.exit_code, 0);
+        assert_eq!(logger.error_count(), 0);
+    }
+}
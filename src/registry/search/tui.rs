@@ -0,0 +1,441 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Interactive terminal UI to search and browse a semantic convention registry.
+
+use std::io::{self, Write};
+
+use base64::prelude::BASE64_STANDARD;
+use base64::Engine;
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use ratatui::layout::{Constraint, Layout, Position};
+use ratatui::style::{Color, Modifier, Style, Stylize};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, List, ListItem, ListState, Paragraph, Wrap};
+use ratatui::Frame;
+
+use weaver_forge::registry::ResolvedRegistry;
+
+use crate::registry::search::{search, RegistryItem, SearchKind};
+
+/// Number of matches skipped by the page up and page down keys.
+const PAGE_SIZE: usize = 10;
+
+/// The help line displayed at the bottom of the screen.
+const HELP: &str =
+    "↑/↓ select · Tab kind · Enter copy id · Ctrl+G copy group id · Ctrl+U clear · Esc quit";
+
+/// The state of the interactive search.
+pub(crate) struct App<'a> {
+    /// The resolved registry, used to display the lineage of the items.
+    registry: &'a ResolvedRegistry,
+    /// All the items of the registry.
+    items: &'a [RegistryItem],
+    /// The current query.
+    query: String,
+    /// The kind of items searched (all kinds if `None`).
+    kind: Option<SearchKind>,
+    /// The items matching the query, the best matches first.
+    matches: Vec<&'a RegistryItem>,
+    /// The state of the list of matches (i.e. the selected match).
+    list_state: ListState,
+    /// The message displayed in place of the help line, if any.
+    status: Option<String>,
+    /// Whether the user asked to quit.
+    should_quit: bool,
+}
+
+impl<'a> App<'a> {
+    /// Creates the state of the interactive search with an initial query.
+    pub(crate) fn new(
+        registry: &'a ResolvedRegistry,
+        items: &'a [RegistryItem],
+        query: String,
+    ) -> Self {
+        let mut app = Self {
+            registry,
+            items,
+            query,
+            kind: None,
+            matches: vec![],
+            list_state: ListState::default(),
+            status: None,
+            should_quit: false,
+        };
+        app.update_matches();
+        app
+    }
+
+    /// Runs the search with the current query and kind, and selects the best
+    /// match.
+    fn update_matches(&mut self) {
+        let kinds: Vec<_> = self.kind.into_iter().collect();
+        self.matches = search(self.items, &self.query, &kinds)
+            .into_iter()
+            .map(|(_, item)| item)
+            .collect();
+        self.list_state
+            .select((!self.matches.is_empty()).then_some(0));
+    }
+
+    /// Returns the selected match, if any.
+    fn selected(&self) -> Option<&'a RegistryItem> {
+        self.list_state
+            .selected()
+            .and_then(|index| self.matches.get(index).copied())
+    }
+
+    /// Moves the selection by `offset` matches, staying within the list.
+    fn move_selection(&mut self, offset: isize) {
+        if let Some(selected) = self.list_state.selected() {
+            let last = self.matches.len().saturating_sub(1);
+            self.list_state
+                .select(Some(selected.saturating_add_signed(offset).min(last)));
+        }
+    }
+
+    /// Handles a key press and returns the text to copy to the clipboard, if
+    /// any.
+    pub(crate) fn handle_key(&mut self, key: KeyEvent) -> Option<String> {
+        self.status = None;
+        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+        match key.code {
+            KeyCode::Esc => self.should_quit = true,
+            KeyCode::Char('c') if ctrl => self.should_quit = true,
+            KeyCode::Up => self.move_selection(-1),
+            KeyCode::Char('p') if ctrl => self.move_selection(-1),
+            KeyCode::Down => self.move_selection(1),
+            KeyCode::Char('n') if ctrl => self.move_selection(1),
+            KeyCode::PageUp => self.move_selection(-(PAGE_SIZE as isize)),
+            KeyCode::PageDown => self.move_selection(PAGE_SIZE as isize),
+            KeyCode::Tab => {
+                self.kind = match self.kind {
+                    None => Some(SearchKind::Attribute),
+                    Some(SearchKind::Attribute) => Some(SearchKind::Metric),
+                    Some(SearchKind::Metric) => Some(SearchKind::Group),
+                    Some(SearchKind::Group) => None,
+                };
+                self.update_matches();
+            }
+            KeyCode::Enter => {
+                let id = self.selected()?.id.clone();
+                self.status = Some(format!("Copied `{id}` to the clipboard"));
+                return Some(id);
+            }
+            KeyCode::Char('g') if ctrl => {
+                let group_id = self.selected()?.group_id.clone();
+                self.status = Some(format!("Copied `{group_id}` to the clipboard"));
+                return Some(group_id);
+            }
+            KeyCode::Char('u') if ctrl => {
+                self.query.clear();
+                self.update_matches();
+            }
+            KeyCode::Backspace => {
+                _ = self.query.pop();
+                self.update_matches();
+            }
+            KeyCode::Char(c) if !ctrl => {
+                self.query.push(c);
+                self.update_matches();
+            }
+            _ => {}
+        }
+        None
+    }
+
+    /// Renders the search input, the list of matches, the details of the
+    /// selected match, and the help line.
+    pub(crate) fn render(&mut self, frame: &mut Frame<'_>) {
+        let [input_area, main_area, help_area] = Layout::vertical([
+            Constraint::Length(3),
+            Constraint::Min(0),
+            Constraint::Length(1),
+        ])
+        .areas(frame.area());
+        let [list_area, details_area] =
+            Layout::horizontal([Constraint::Percentage(40), Constraint::Percentage(60)])
+                .areas(main_area);
+
+        let kind = self
+            .kind
+            .map_or("all".to_owned(), |kind| format!("{kind}s"));
+        frame.render_widget(
+            Paragraph::new(self.query.as_str())
+                .block(Block::bordered().title(format!(" Search {kind} "))),
+            input_area,
+        );
+        frame.set_cursor_position(Position::new(
+            input_area.x + 1 + self.query.chars().count() as u16,
+            input_area.y + 1,
+        ));
+
+        let list = List::new(self.matches.iter().map(|item| {
+            ListItem::new(Line::from(vec![
+                Span::styled(format!("{:<10}", item.kind), kind_style(item.kind)),
+                Span::raw(item.id.as_str()),
+            ]))
+        }))
+        .block(Block::bordered().title(format!(" Matches ({}) ", self.matches.len())))
+        .highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+        frame.render_stateful_widget(list, list_area, &mut self.list_state);
+
+        let details = self
+            .selected()
+            .map(|item| self.details(item))
+            .unwrap_or_default();
+        frame.render_widget(
+            Paragraph::new(details)
+                .wrap(Wrap { trim: false })
+                .block(Block::bordered().title(" Details ")),
+            details_area,
+        );
+
+        let help = match &self.status {
+            Some(status) => Line::from(status.as_str()).green(),
+            None => Line::from(HELP).dark_gray(),
+        };
+        frame.render_widget(help, help_area);
+    }
+
+    /// Returns the details of an item, including its lineage.
+    fn details(&self, item: &RegistryItem) -> Vec<Line<'static>> {
+        let field = |name: &str, value: String| {
+            Line::from(vec![
+                Span::raw(format!("{name}: ")).bold(),
+                Span::raw(value),
+            ])
+        };
+        let mut lines = vec![
+            Line::from(item.id.clone())
+                .bold()
+                .style(kind_style(item.kind)),
+            Line::default(),
+            field("Kind", item.kind.to_string()),
+            field("Type", item.r#type.clone()),
+        ];
+        if let Some(stability) = &item.stability {
+            lines.push(field("Stability", stability.to_string()));
+        }
+        if item.kind != SearchKind::Group {
+            lines.push(field("Defined in", item.group_id.clone()));
+        }
+        if !item.provenance.is_empty() {
+            lines.push(field("Source", item.provenance.clone()));
+        }
+        for text in [&item.brief, &item.note] {
+            if !text.trim().is_empty() {
+                lines.push(Line::default());
+                lines.extend(text.trim().lines().map(|line| Line::from(line.to_owned())));
+            }
+        }
+
+        lines.push(Line::default());
+        if item.kind == SearchKind::Attribute {
+            lines.push(Line::from("Referenced by:").bold());
+            let usages = self.registry.attribute_index.get(&item.id);
+            for usage in usages.into_iter().flatten() {
+                // The defining group has no lineage for the attribute.
+                let Some(lineage) = self
+                    .registry
+                    .groups
+                    .iter()
+                    .find(|group| group.id == usage.group_id)
+                    .and_then(|group| group.lineage.as_ref())
+                    .and_then(|lineage| lineage.attribute(&item.id))
+                else {
+                    continue;
+                };
+                let mut line = format!("  - {}", usage.group_id);
+                if !lineage.inherited_fields.is_empty() {
+                    let fields: Vec<_> = lineage.inherited_fields.iter().cloned().collect();
+                    line.push_str(&format!(" (inherited: {})", fields.join(", ")));
+                }
+                if !lineage.locally_overridden_fields.is_empty() {
+                    let fields: Vec<_> =
+                        lineage.locally_overridden_fields.iter().cloned().collect();
+                    line.push_str(&format!(" (overridden: {})", fields.join(", ")));
+                }
+                lines.push(Line::from(line));
+            }
+        } else if let Some(group) = self
+            .registry
+            .groups
+            .iter()
+            .find(|group| group.id == item.group_id)
+        {
+            if let Some(extends) = &group.extends {
+                lines.push(field("Extends", extends.clone()));
+            }
+            lines.push(Line::from("Attributes:").bold());
+            for attr in &group.attributes {
+                let source_group = group
+                    .lineage
+                    .as_ref()
+                    .and_then(|lineage| lineage.attribute(&attr.name))
+                    .map(|lineage| format!(" (from {})", lineage.source_group))
+                    .unwrap_or_default();
+                lines.push(Line::from(format!(
+                    "  - {}: {}{}",
+                    attr.name, attr.r#type, source_group
+                )));
+            }
+        }
+        lines
+    }
+}
+
+/// Returns the style used to display the kind of an item.
+fn kind_style(kind: SearchKind) -> Style {
+    match kind {
+        SearchKind::Attribute => Style::default().fg(Color::Cyan),
+        SearchKind::Metric => Style::default().fg(Color::Magenta),
+        SearchKind::Group => Style::default().fg(Color::Yellow),
+    }
+}
+
+/// Copies a text to the clipboard of the terminal with the OSC 52 escape
+/// sequence (supported by most terminals, including over SSH).
+fn copy_to_clipboard(text: &str) -> io::Result<()> {
+    let mut stdout = io::stdout();
+    write!(stdout, "\x1b]52;c;{}\x07", BASE64_STANDARD.encode(text))?;
+    stdout.flush()
+}
+
+/// Runs the interactive search until the user quits.
+#[cfg(not(tarpaulin_include))]
+pub(crate) fn run(
+    registry: &ResolvedRegistry,
+    items: &[RegistryItem],
+    query: String,
+) -> io::Result<()> {
+    let mut app = App::new(registry, items, query);
+    let mut terminal = ratatui::try_init()?;
+    let result = (|| loop {
+        _ = terminal.draw(|frame| app.render(frame))?;
+        if let Event::Key(key) = event::read()? {
+            if key.kind != KeyEventKind::Press {
+                continue;
+            }
+            if let Some(text) = app.handle_key(key) {
+                copy_to_clipboard(&text)?;
+            }
+            if app.should_quit {
+                return Ok(());
+            }
+        }
+    })();
+    ratatui::restore();
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use ratatui::backend::TestBackend;
+    use ratatui::crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+    use ratatui::Terminal;
+
+    use weaver_forge::registry::ResolvedRegistry;
+    use weaver_resolver::SchemaResolver;
+    use weaver_semconv::registry::SemConvRegistry;
+
+    use crate::registry::search::registry_items;
+    use crate::registry::search::tui::App;
+    use crate::registry::search::SearchKind;
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    #[test]
+    fn test_interactive_search() {
+        let registry_id = "default";
+        let mut registry = SemConvRegistry::try_from_path_pattern(
+            registry_id,
+            "crates/weaver_codegen_test/semconv_registry/**/*.yaml",
+        )
+        .expect("Failed to load the registry");
+        let schema = SchemaResolver::resolve_semantic_convention_registry(&mut registry)
+            .expect("Failed to resolve the registry");
+        let registry = ResolvedRegistry::try_from_resolved_registry(
+            schema
+                .registry(registry_id)
+                .expect("Failed to get the registry from the resolved schema"),
+            schema.catalog(),
+        )
+        .expect("Failed to create the resolved registry");
+        let items = registry_items(&registry);
+
+        // An empty query matches all the items.
+        let mut app = App::new(&registry, &items, String::new());
+        assert_eq!(app.matches.len(), items.len());
+
+        // Incremental search.
+        for c in "httpreqmethod".chars() {
+            assert_eq!(app.handle_key(key(KeyCode::Char(c))), None);
+        }
+        assert_eq!(
+            app.selected().map(|item| item.id.as_str()),
+            Some("http.request.method")
+        );
+
+        // Copy the ids.
+        assert_eq!(
+            app.handle_key(key(KeyCode::Enter)).as_deref(),
+            Some("http.request.method")
+        );
+        assert_eq!(
+            app.handle_key(KeyEvent::new(KeyCode::Char('g'), KeyModifiers::CONTROL))
+                .as_deref(),
+            Some("registry.http")
+        );
+
+        // The details pane shows the lineage of the attribute.
+        let details: Vec<String> = app
+            .details(app.selected().expect("No selected match"))
+            .iter()
+            .map(|line| line.to_string())
+            .collect();
+        let referenced_by = details
+            .iter()
+            .position(|line| line == "Referenced by:")
+            .expect("No lineage");
+        assert!(details[referenced_by..]
+            .iter()
+            .any(|line| line.starts_with("  - attributes.http.common")));
+
+        let mut terminal =
+            Terminal::new(TestBackend::new(120, 30)).expect("Failed to create the terminal");
+        _ = terminal
+            .draw(|frame| app.render(frame))
+            .expect("Failed to render");
+        let screen: String = terminal
+            .backend()
+            .buffer()
+            .content()
+            .iter()
+            .map(|cell| cell.symbol())
+            .collect();
+        assert!(screen.contains("Search all"));
+        assert!(screen.contains("Copied `registry.http` to the clipboard"));
+
+        // Filter on the kind of items.
+        _ = app.handle_key(KeyEvent::new(KeyCode::Char('u'), KeyModifiers::CONTROL));
+        for _ in 0..2 {
+            assert_eq!(app.handle_key(key(KeyCode::Tab)), None);
+        }
+        assert_eq!(app.kind, Some(SearchKind::Metric));
+        assert!(app
+            .matches
+            .iter()
+            .all(|item| item.kind == SearchKind::Metric));
+
+        // Navigation stays within the list of matches.
+        _ = app.handle_key(key(KeyCode::PageDown));
+        assert_eq!(app.list_state.selected(), Some(app.matches.len() - 1));
+        _ = app.handle_key(key(KeyCode::PageUp));
+        assert_eq!(app.list_state.selected(), Some(0));
+
+        _ = app.handle_key(key(KeyCode::Esc));
+        assert!(app.should_quit);
+    }
+}
@@ -2,17 +2,24 @@
 
 //! Compute stats on a semantic convention registry.
 
-use crate::registry::RegistryArgs;
-use crate::util::{load_semconv_specs, resolve_semconv_specs, semconv_registry_path_from};
+use crate::registry::owners::load_owners;
+use crate::registry::{Error, RegistryArgs, RegistryPath};
+use crate::util::{resolve_registries_with_options, semconv_registry_path_from};
 use crate::{DiagnosticArgs, ExitDirectives};
 use clap::Args;
+use std::collections::{BTreeMap, HashMap, HashSet};
 use weaver_cache::Cache;
 use weaver_common::diagnostic::DiagnosticMessages;
 use weaver_common::Logger;
+use weaver_resolved_schema::catalog::EnumStats;
 use weaver_resolved_schema::registry::{CommonGroupStats, GroupStats};
-use weaver_resolved_schema::ResolvedTelemetrySchema;
-use weaver_semconv::group::GroupType;
+use weaver_resolved_schema::{NamespaceStats, ResolvedTelemetrySchema};
+use weaver_resolver::file_filter::RegistryFileFilter;
+use weaver_resolver::owners::Owners;
+use weaver_resolver::{ResolutionMode, ResolverOptions};
+use weaver_semconv::group::{GroupSpec, GroupType};
 use weaver_semconv::registry::SemConvRegistry;
+use weaver_semconv::stability::Stability;
 
 /// Parameters for the `registry stats` sub-command
 #[derive(Debug, Args)]
@@ -21,6 +28,37 @@ pub struct RegistryStatsArgs {
     #[command(flatten)]
     registry: RegistryArgs,
 
+    /// Number of most reused attributes, of largest enums, and of most complex
+    /// groups to display.
+    #[arg(long, default_value = "10")]
+    pub top: usize,
+
+    /// Local path or Git URL of a baseline semantic convention registry (e.g.
+    /// the previous release). The new, newly stabilized, newly deprecated, and
+    /// removed attributes since the baseline are reported per namespace.
+    #[arg(long)]
+    pub baseline: Option<RegistryPath>,
+
+    /// Tag, branch, or full ref name (e.g. `v1.26.0`) of the Git baseline
+    /// registry to compare with. By default, the default branch of the
+    /// baseline repo is used.
+    #[arg(long, requires = "baseline")]
+    pub baseline_ref: Option<String>,
+
+    /// Only report the trends since the baseline of the namespaces owned by
+    /// this owner, as declared in the `OWNERS` file of the registry. Can be
+    /// repeated.
+    #[arg(long, requires = "baseline")]
+    pub owner: Vec<String>,
+
+    /// Load and resolve the registry (and the baseline) in lenient mode: the
+    /// files that fail to load are skipped, the unresolved attribute
+    /// references are replaced with placeholder attributes, the unresolved
+    /// `extends` and `include` references are ignored, and the corresponding
+    /// errors are reported as warnings.
+    #[arg(long)]
+    pub lenient: bool,
+
     /// Parameters to specify the diagnostic format.
     #[command(flatten)]
     pub diagnostic: DiagnosticArgs,
@@ -35,23 +73,65 @@ pub(crate) fn command(
 ) -> Result<ExitDirectives, DiagnosticMessages> {
     logger.loading(&format!(
         "Compute statistics on the registry `{}`",
-        args.registry.registry
+        args.registry.registries()
     ));
 
-    let registry_id = "default";
-    let registry_path =
-        semconv_registry_path_from(&args.registry.registry, &args.registry.registry_git_sub_dir);
+    let registry_paths = args.registry.registry_paths();
+    let file_filter = args.registry.file_filter()?;
+    let owners = load_owners(&args.registry)?;
+    let baseline_paths = args
+        .baseline
+        .iter()
+        .map(|baseline| {
+            baseline_registry_path(
+                baseline,
+                &args.baseline_ref,
+                &args.registry.registry_git_sub_dir,
+            )
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    let baseline_filter = RegistryFileFilter::default();
 
-    // Load the semantic convention registry into a local cache.
-    let semconv_specs = load_semconv_specs(&registry_path, cache, logger.clone())?;
-    let mut registry = SemConvRegistry::from_semconv_specs(registry_id, semconv_specs);
+    // Load and resolve the semantic convention registry, and the baseline
+    // registry if any, concurrently.
+    let mut registries = vec![(registry_paths.as_slice(), &file_filter)];
+    if !baseline_paths.is_empty() {
+        registries.push((baseline_paths.as_slice(), &baseline_filter));
+    }
+    let options = ResolverOptions {
+        mode: if args.lenient {
+            ResolutionMode::Lenient
+        } else {
+            ResolutionMode::Strict
+        },
+        ..ResolverOptions::default()
+    };
+    let (resolved, warnings) =
+        resolve_registries_with_options(&registries, &options, cache, logger.clone())?;
+    let mut resolved = resolved.into_iter();
+    let (registry, resolved_schema) = resolved.next().expect("The registry has been resolved");
 
     display_semconv_registry_stats(&registry);
+    display_schema_stats(&resolved_schema, args.top);
+    display_group_complexity(&group_complexity(&registry, &resolved_schema), args.top);
 
-    // Resolve the semantic convention registry.
-    let resolved_schema = resolve_semconv_specs(&mut registry, logger)?;
+    if let (Some(baseline), Some((_, baseline_schema))) = (&args.baseline, resolved.next()) {
+        let mut trends = namespace_trends(&baseline_schema, &resolved_schema);
+        if !args.owner.is_empty() {
+            trends.retain(|namespace, _| {
+                owners
+                    .owners_of(namespace)
+                    .iter()
+                    .any(|owner| args.owner.contains(owner))
+            });
+        }
+        display_namespace_trends(baseline, &trends, &owners);
+    }
 
-    display_schema_stats(&resolved_schema);
+    // The errors ignored by the lenient resolution are reported as warnings.
+    if !warnings.is_empty() {
+        return Err(warnings);
+    }
     Ok(ExitDirectives {
         exit_code: 0,
         quiet_mode: false,
@@ -66,11 +146,17 @@ fn display_semconv_registry_stats(semconv_registry: &SemConvRegistry) {
 }
 
 #[cfg(not(tarpaulin_include))]
-fn display_schema_stats(schema: &ResolvedTelemetrySchema) {
+fn display_schema_stats(schema: &ResolvedTelemetrySchema, top: usize) {
     let stats = schema.stats();
     println!("Resolved Telemetry Schema Stats:");
     let mut total_number_of_attributes = 0;
+    let mut group_size_breakdown = BTreeMap::new();
     for registry_stats in stats.registry_stats.iter() {
+        for group_stats in registry_stats.group_breakdown.values() {
+            for (card, count) in common_stats(group_stats).attribute_card_breakdown.iter() {
+                *group_size_breakdown.entry(*card).or_insert(0) += *count;
+            }
+        }
         println!("Registry");
         println!("  - {} groups", registry_stats.group_count);
         for (group_type, group_stats) in registry_stats.group_breakdown.iter() {
@@ -133,11 +219,12 @@ fn display_schema_stats(schema: &ResolvedTelemetrySchema) {
 
     let catalog_stats = &stats.catalog_stats;
     println!("Shared Catalog (after resolution and deduplication):");
-    if total_number_of_attributes > 0 {
+    if let Some(percent) =
+        (catalog_stats.attribute_count * 100).checked_div(total_number_of_attributes)
+    {
         println!(
             "  - Number of deduplicated attributes: {} ({}%)",
-            catalog_stats.attribute_count,
-            catalog_stats.attribute_count * 100 / total_number_of_attributes
+            catalog_stats.attribute_count, percent
         );
     }
     println!("    - Attribute types breakdown:");
@@ -165,6 +252,517 @@ fn display_schema_stats(schema: &ResolvedTelemetrySchema) {
             catalog_stats.deprecated_count * 100 / catalog_stats.attribute_count
         );
     }
+
+    display_enum_stats(&catalog_stats.enum_stats, top);
+    display_namespace_stats(&stats.namespace_stats);
+    display_attribute_reuse(&stats.attribute_reuse, top);
+    println!("Group Size Histogram (number of attributes: number of groups):");
+    display_histogram(&group_size_breakdown);
+}
+
+/// Returns the statistics common to all the types of groups.
+fn common_stats(group_stats: &GroupStats) -> &CommonGroupStats {
+    match group_stats {
+        GroupStats::AttributeGroup { common_stats }
+        | GroupStats::Metric { common_stats, .. }
+        | GroupStats::MetricGroup { common_stats }
+        | GroupStats::Event { common_stats }
+        | GroupStats::Resource { common_stats }
+        | GroupStats::Scope { common_stats }
+        | GroupStats::Span { common_stats, .. } => common_stats,
+    }
+}
+
+/// Returns the rows of the per-namespace table, with aligned columns.
+fn namespace_table(namespace_stats: &BTreeMap<String, NamespaceStats>) -> Vec<String> {
+    let percent = |count: usize, total: usize| {
+        (count * 100)
+            .checked_div(total)
+            .map_or_else(|| "-".to_owned(), |percent| format!("{}%", percent))
+    };
+    let mut rows = vec![[
+        "Namespace".to_owned(),
+        "Groups".to_owned(),
+        "Attributes".to_owned(),
+        "Stable".to_owned(),
+        "Deprecated".to_owned(),
+        "Avg brief".to_owned(),
+    ]];
+    for (namespace, stats) in namespace_stats {
+        let mut groups: Vec<String> = stats
+            .group_breakdown
+            .iter()
+            .map(|(group_type, count)| format!("{:?}: {}", group_type, count))
+            .collect();
+        groups.sort();
+        rows.push([
+            namespace.clone(),
+            groups.join(", "),
+            stats.attribute_count.to_string(),
+            percent(stats.stable_attribute_count, stats.attribute_count),
+            percent(stats.deprecated_attribute_count, stats.attribute_count),
+            stats.average_brief_length().to_string(),
+        ]);
+    }
+
+    aligned_rows(&rows)
+}
+
+/// Pads the cells of the rows so the columns are aligned.
+fn aligned_rows<const N: usize>(rows: &[[String; N]]) -> Vec<String> {
+    let mut widths = [0; N];
+    for row in rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+    rows.iter()
+        .map(|row| {
+            row.iter()
+                .zip(widths)
+                .map(|(cell, width)| format!("{:<width$}", cell, width = width))
+                .collect::<Vec<_>>()
+                .join("  ")
+                .trim_end()
+                .to_owned()
+        })
+        .collect()
+}
+
+#[cfg(not(tarpaulin_include))]
+fn display_namespace_stats(namespace_stats: &BTreeMap<String, NamespaceStats>) {
+    println!("Namespace Breakdown:");
+    for row in namespace_table(namespace_stats) {
+        println!("  {}", row);
+    }
+}
+
+/// Converts the baseline registry path, checking out the given ref of a Git
+/// baseline.
+fn baseline_registry_path(
+    baseline: &RegistryPath,
+    baseline_ref: &Option<String>,
+    registry_git_sub_dir: &Option<String>,
+) -> Result<weaver_semconv::path::RegistryPath, Error> {
+    let mut path = semconv_registry_path_from(baseline, registry_git_sub_dir);
+    if let Some(baseline_ref) = baseline_ref {
+        match &mut path {
+            weaver_semconv::path::RegistryPath::GitUrl { git_ref, .. } => {
+                *git_ref = Some(baseline_ref.clone());
+            }
+            _ => {
+                return Err(Error::BaselineRefWithoutGitUrl {
+                    baseline: baseline.to_string(),
+                })
+            }
+        }
+    }
+    Ok(path)
+}
+
+/// Changes of the attributes of a namespace since the baseline registry.
+#[derive(Debug, Default, PartialEq)]
+struct NamespaceTrend {
+    /// Number of attributes in the baseline registry.
+    baseline_count: usize,
+    /// Number of attributes in the current registry.
+    current_count: usize,
+    /// Attributes absent from the baseline registry.
+    added: Vec<String>,
+    /// Attributes not stable in the baseline registry and stable now.
+    stabilized: Vec<String>,
+    /// Attributes not deprecated in the baseline registry and deprecated now.
+    deprecated: Vec<String>,
+    /// Attributes absent from the current registry.
+    removed: Vec<String>,
+}
+
+/// Returns the stability and the deprecation status of the attributes of a
+/// resolved schema, by name.
+fn attribute_statuses(schema: &ResolvedTelemetrySchema) -> BTreeMap<&str, (bool, bool)> {
+    let mut statuses = BTreeMap::new();
+    for attr in &schema.catalog().attributes {
+        // Only the first catalog entry of an attribute is kept, the other ones
+        // are references overriding some fields of the attribute.
+        _ = statuses.entry(attr.name.as_str()).or_insert((
+            attr.stability == Some(Stability::Stable),
+            attr.deprecated.is_some(),
+        ));
+    }
+    statuses
+}
+
+/// Computes the changes of the attributes since the baseline, by root
+/// namespace.
+fn namespace_trends(
+    baseline: &ResolvedTelemetrySchema,
+    current: &ResolvedTelemetrySchema,
+) -> BTreeMap<String, NamespaceTrend> {
+    let baseline = attribute_statuses(baseline);
+    let current = attribute_statuses(current);
+    let mut trends: BTreeMap<String, NamespaceTrend> = BTreeMap::new();
+    let namespace = |name: &str| name.split('.').next().unwrap_or_default().to_owned();
+    for (name, (stable, deprecated)) in &current {
+        let trend = trends.entry(namespace(name)).or_default();
+        trend.current_count += 1;
+        match baseline.get(name) {
+            None => trend.added.push((*name).to_owned()),
+            Some((was_stable, was_deprecated)) => {
+                if *stable && !was_stable {
+                    trend.stabilized.push((*name).to_owned());
+                }
+                if *deprecated && !was_deprecated {
+                    trend.deprecated.push((*name).to_owned());
+                }
+            }
+        }
+    }
+    for name in baseline.keys() {
+        let trend = trends.entry(namespace(name)).or_default();
+        trend.baseline_count += 1;
+        if !current.contains_key(name) {
+            trend.removed.push((*name).to_owned());
+        }
+    }
+    trends
+}
+
+/// Returns the rows of the per-namespace trend table, with aligned columns.
+/// The owners of the namespaces are only displayed if the registry declares
+/// owners.
+fn namespace_trend_table(
+    trends: &BTreeMap<String, NamespaceTrend>,
+    owners: &Owners,
+) -> Vec<String> {
+    let mut rows = vec![[
+        "Namespace".to_owned(),
+        "Attributes".to_owned(),
+        "New".to_owned(),
+        "Stabilized".to_owned(),
+        "Deprecated".to_owned(),
+        "Removed".to_owned(),
+        if owners.is_empty() { "" } else { "Owners" }.to_owned(),
+    ]];
+    for (namespace, trend) in trends {
+        let delta = trend.current_count as i64 - trend.baseline_count as i64;
+        rows.push([
+            namespace.clone(),
+            format!(
+                "{} -> {} ({:+})",
+                trend.baseline_count, trend.current_count, delta
+            ),
+            trend.added.len().to_string(),
+            trend.stabilized.len().to_string(),
+            trend.deprecated.len().to_string(),
+            trend.removed.len().to_string(),
+            owners.owners_of(namespace).join(" "),
+        ]);
+    }
+    aligned_rows(&rows)
+}
+
+#[cfg(not(tarpaulin_include))]
+fn display_namespace_trends(
+    baseline: &RegistryPath,
+    trends: &BTreeMap<String, NamespaceTrend>,
+    owners: &Owners,
+) {
+    println!("Trend Since Baseline `{}`:", baseline);
+    for row in namespace_trend_table(trends, owners) {
+        println!("  {}", row);
+    }
+    for (namespace, trend) in trends {
+        let changes = [
+            ("New", &trend.added),
+            ("Stabilized", &trend.stabilized),
+            ("Deprecated", &trend.deprecated),
+            ("Removed", &trend.removed),
+        ];
+        if changes.iter().all(|(_, names)| names.is_empty()) {
+            continue;
+        }
+        println!();
+        println!("### {}", namespace);
+        for (label, names) in changes {
+            if !names.is_empty() {
+                println!(
+                    "- {}: {}",
+                    label,
+                    names
+                        .iter()
+                        .map(|name| format!("`{}`", name))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                );
+            }
+        }
+    }
+}
+
+/// Returns the `top` most reused attributes (by decreasing number of
+/// referencing groups, then by name) and the attributes never reused.
+fn attribute_reuse_ranking(
+    attribute_reuse: &BTreeMap<String, usize>,
+    top: usize,
+) -> (Vec<(&str, usize)>, Vec<&str>) {
+    let mut most_reused: Vec<(&str, usize)> = attribute_reuse
+        .iter()
+        .filter(|(_, count)| **count > 0)
+        .map(|(name, count)| (name.as_str(), *count))
+        .collect();
+    most_reused.sort_by(|(name1, count1), (name2, count2)| {
+        count2.cmp(count1).then_with(|| name1.cmp(name2))
+    });
+    most_reused.truncate(top);
+    let never_reused = attribute_reuse
+        .iter()
+        .filter(|(_, count)| **count == 0)
+        .map(|(name, _)| name.as_str())
+        .collect();
+    (most_reused, never_reused)
+}
+
+#[cfg(not(tarpaulin_include))]
+fn display_attribute_reuse(attribute_reuse: &BTreeMap<String, usize>, top: usize) {
+    let (most_reused, never_reused) = attribute_reuse_ranking(attribute_reuse, top);
+    println!("Attribute Reuse (number of groups referencing the attribute):");
+    println!("  - Top {} most reused attributes:", most_reused.len());
+    for (name, count) in most_reused {
+        println!("    - {}: {}", name, count);
+    }
+    if let Some(percent) = (never_reused.len() * 100).checked_div(attribute_reuse.len()) {
+        println!(
+            "  - Number of never reused attributes: {} ({}%)",
+            never_reused.len(),
+            percent
+        );
+    }
+    for name in never_reused {
+        println!("    - {}", name);
+    }
+}
+
+/// Returns the number of enums per number of members.
+fn enum_cardinality_breakdown(enum_stats: &BTreeMap<String, EnumStats>) -> BTreeMap<usize, usize> {
+    let mut breakdown = BTreeMap::new();
+    for stats in enum_stats.values() {
+        *breakdown.entry(stats.member_count).or_insert(0) += 1;
+    }
+    breakdown
+}
+
+/// Returns the `top` largest enums (by decreasing number of members, then by
+/// name).
+fn largest_enums(enum_stats: &BTreeMap<String, EnumStats>, top: usize) -> Vec<(&str, &EnumStats)> {
+    let mut largest: Vec<_> = enum_stats
+        .iter()
+        .map(|(name, stats)| (name.as_str(), stats))
+        .collect();
+    largest.sort_by(|(name1, stats1), (name2, stats2)| {
+        stats2
+            .member_count
+            .cmp(&stats1.member_count)
+            .then_with(|| name1.cmp(name2))
+    });
+    largest.truncate(top);
+    largest
+}
+
+#[cfg(not(tarpaulin_include))]
+fn display_enum_stats(enum_stats: &BTreeMap<String, EnumStats>, top: usize) {
+    if enum_stats.is_empty() {
+        return;
+    }
+    let closed_count = enum_stats
+        .values()
+        .filter(|stats| !stats.allow_custom_values)
+        .count();
+    println!(
+        "Enums: {} ({} closed, i.e. not accepting custom values)",
+        enum_stats.len(),
+        closed_count
+    );
+    println!("  - Top {} largest enums:", top.min(enum_stats.len()));
+    for (name, stats) in largest_enums(enum_stats, top) {
+        println!(
+            "    - {}: {} members{}",
+            name,
+            stats.member_count,
+            if stats.allow_custom_values {
+                ""
+            } else {
+                " (closed)"
+            }
+        );
+    }
+    println!("Enum Size Histogram (number of members: number of enums):");
+    display_histogram(&enum_cardinality_breakdown(enum_stats));
+}
+
+/// Complexity metrics of a group.
+#[derive(Debug, Default, PartialEq)]
+struct GroupComplexity {
+    /// Ids of the groups extended directly or transitively by the group, the
+    /// closest one first. Its length is the `extends` depth of the group.
+    extends_chain: Vec<String>,
+    /// Number of attributes declared by the group (defined or referenced).
+    own_attribute_count: usize,
+    /// Number of attributes inherited through `extends` or imported through
+    /// `include` constraints, and not declared by the group.
+    inherited_attribute_count: usize,
+    /// Number of constraints declared by the group.
+    constraint_count: usize,
+    /// Number of groups extending or including the group.
+    fan_out: usize,
+}
+
+/// Computes the complexity metrics of each group, by group id.
+///
+/// The `extends` clauses are not kept by the resolution, so the chains and the
+/// fan-out are computed from the group specs of the semantic convention
+/// registry, and the inherited attributes from the resolved groups.
+fn group_complexity(
+    registry: &SemConvRegistry,
+    schema: &ResolvedTelemetrySchema,
+) -> BTreeMap<String, GroupComplexity> {
+    let specs: HashMap<&str, &GroupSpec> = registry
+        .group_with_provenance_iter()
+        .map(|(group, _)| (group.id.as_str(), group))
+        .collect();
+    let resolved_attribute_counts: HashMap<&str, usize> = schema
+        .registries
+        .values()
+        .flat_map(|registry| &registry.groups)
+        .map(|group| (group.id.as_str(), group.attributes.len()))
+        .collect();
+
+    let mut fan_out: HashMap<&str, usize> = HashMap::new();
+    for group in specs.values() {
+        let referenced_groups: HashSet<&str> = group
+            .extends
+            .iter()
+            .chain(group.constraints.iter().filter_map(|c| c.include.as_ref()))
+            .map(String::as_str)
+            .collect();
+        for referenced_group in referenced_groups {
+            *fan_out.entry(referenced_group).or_insert(0) += 1;
+        }
+    }
+
+    specs
+        .iter()
+        .map(|(id, group)| {
+            // Cycles are only left in registries resolved in lenient mode, the
+            // chain stops before looping.
+            let mut extends_chain: Vec<String> = vec![];
+            let mut parent = group.extends.as_deref();
+            while let Some(parent_id) = parent {
+                if parent_id == *id || extends_chain.iter().any(|g| g == parent_id) {
+                    break;
+                }
+                extends_chain.push(parent_id.to_owned());
+                parent = specs
+                    .get(parent_id)
+                    .and_then(|parent_group| parent_group.extends.as_deref());
+            }
+            let own_attribute_count = group.attributes.len();
+            let complexity = GroupComplexity {
+                extends_chain,
+                own_attribute_count,
+                inherited_attribute_count: resolved_attribute_counts
+                    .get(id)
+                    .map_or(0, |count| count.saturating_sub(own_attribute_count)),
+                constraint_count: group.constraints.len(),
+                fan_out: fan_out.get(id).copied().unwrap_or_default(),
+            };
+            ((*id).to_owned(), complexity)
+        })
+        .collect()
+}
+
+/// Returns the maximum and the average of a metric over all the groups.
+fn max_and_average(
+    complexity: &BTreeMap<String, GroupComplexity>,
+    metric: impl Fn(&GroupComplexity) -> usize,
+) -> (usize, f64) {
+    let values: Vec<usize> = complexity.values().map(metric).collect();
+    let max = values.iter().copied().max().unwrap_or_default();
+    if values.is_empty() {
+        return (max, 0.0);
+    }
+    (
+        max,
+        values.iter().sum::<usize>() as f64 / values.len() as f64,
+    )
+}
+
+/// Returns the `top` groups with the highest non-zero value of a metric (by
+/// decreasing value, then by id).
+fn highest_groups(
+    complexity: &BTreeMap<String, GroupComplexity>,
+    top: usize,
+    metric: impl Fn(&GroupComplexity) -> usize,
+) -> Vec<(&str, &GroupComplexity)> {
+    let mut highest: Vec<_> = complexity
+        .iter()
+        .filter(|(_, group)| metric(group) > 0)
+        .map(|(id, group)| (id.as_str(), group))
+        .collect();
+    highest.sort_by(|(id1, group1), (id2, group2)| {
+        metric(group2)
+            .cmp(&metric(group1))
+            .then_with(|| id1.cmp(id2))
+    });
+    highest.truncate(top);
+    highest
+}
+
+#[cfg(not(tarpaulin_include))]
+fn display_group_complexity(complexity: &BTreeMap<String, GroupComplexity>, top: usize) {
+    let display_metric = |label: &str, metric: &dyn Fn(&GroupComplexity) -> usize| {
+        let (max, average) = max_and_average(complexity, metric);
+        println!("  - {}: max {}, average {:.2}", label, max, average);
+    };
+    println!("Group Complexity:");
+    display_metric("`extends` depth", &|group| group.extends_chain.len());
+    display_metric("Own attributes", &|group| group.own_attribute_count);
+    display_metric("Inherited attributes", &|group| {
+        group.inherited_attribute_count
+    });
+    display_metric("Constraints", &|group| group.constraint_count);
+    display_metric("Fan-out (extending or including groups)", &|group| {
+        group.fan_out
+    });
+
+    let deepest = highest_groups(complexity, top, |group| group.extends_chain.len());
+    if !deepest.is_empty() {
+        println!("  - Top {} deepest `extends` chains:", deepest.len());
+        for (id, group) in deepest {
+            println!(
+                "    - {} -> {} (depth {})",
+                id,
+                group.extends_chain.join(" -> "),
+                group.extends_chain.len()
+            );
+        }
+    }
+    let widest = highest_groups(complexity, top, |group| group.fan_out);
+    if !widest.is_empty() {
+        println!("  - Top {} groups by fan-out:", widest.len());
+        for (id, group) in widest {
+            println!("    - {}: {}", id, group.fan_out);
+        }
+    }
+}
+
+#[cfg(not(tarpaulin_include))]
+fn display_histogram(breakdown: &BTreeMap<usize, usize>) {
+    const MAX_BAR_WIDTH: usize = 50;
+    let max_count = breakdown.values().copied().max().unwrap_or_default();
+    for (size, count) in breakdown {
+        let width = (count * MAX_BAR_WIDTH).div_ceil(max_count);
+        println!("  {:>4}: {} {}", size, "#".repeat(width), count);
+    }
 }
 
 #[cfg(not(tarpaulin_include))]
@@ -210,3 +808,246 @@ fn display_common_group_stats(group_type: &GroupType, common_stats: &CommonGroup
         );
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use weaver_resolved_schema::catalog::EnumStats;
+    use weaver_resolved_schema::NamespaceStats;
+    use weaver_semconv::group::GroupType;
+
+    use weaver_resolved_schema::ResolvedTelemetrySchema;
+    use weaver_resolver::owners::Owners;
+    use weaver_resolver::SchemaResolver;
+    use weaver_semconv::registry::SemConvRegistry;
+
+    use crate::registry::stats::{
+        attribute_reuse_ranking, baseline_registry_path, enum_cardinality_breakdown,
+        group_complexity, highest_groups, largest_enums, max_and_average, namespace_table,
+        namespace_trend_table, namespace_trends, NamespaceTrend,
+    };
+    use crate::registry::{Error, RegistryPath};
+
+    fn resolve(path_pattern: &str) -> ResolvedTelemetrySchema {
+        let mut registry = SemConvRegistry::try_from_path_pattern("default", path_pattern)
+            .expect("Failed to load the registry");
+        SchemaResolver::resolve_semantic_convention_registry(&mut registry)
+            .expect("Failed to resolve the registry")
+    }
+
+    #[test]
+    fn test_baseline_registry_path() {
+        let git_baseline: RegistryPath =
+            "https://github.com/open-telemetry/semantic-conventions.git"
+                .parse()
+                .unwrap();
+        let sub_dir = Some("model".to_owned());
+        let path =
+            baseline_registry_path(&git_baseline, &Some("v1.26.0".to_owned()), &sub_dir).unwrap();
+        assert!(matches!(
+            path,
+            weaver_semconv::path::RegistryPath::GitUrl { git_ref: Some(git_ref), .. } if git_ref == "v1.26.0"
+        ));
+        let path = baseline_registry_path(&git_baseline, &None, &sub_dir).unwrap();
+        assert!(matches!(
+            path,
+            weaver_semconv::path::RegistryPath::GitUrl { git_ref: None, .. }
+        ));
+
+        // A ref only applies to a Git baseline.
+        let local_baseline: RegistryPath = "data/registry".parse().unwrap();
+        assert!(matches!(
+            baseline_registry_path(&local_baseline, &Some("v1.26.0".to_owned()), &sub_dir),
+            Err(Error::BaselineRefWithoutGitUrl { .. })
+        ));
+    }
+
+    #[test]
+    fn test_attribute_reuse_ranking() {
+        let attribute_reuse: BTreeMap<String, usize> = [
+            ("http.request.method", 5),
+            ("http.route", 0),
+            ("server.address", 5),
+            ("server.port", 2),
+            ("url.full", 0),
+        ]
+        .into_iter()
+        .map(|(name, count)| (name.to_owned(), count))
+        .collect();
+
+        let (most_reused, never_reused) = attribute_reuse_ranking(&attribute_reuse, 2);
+        assert_eq!(
+            most_reused,
+            vec![("http.request.method", 5), ("server.address", 5)]
+        );
+        assert_eq!(never_reused, vec!["http.route", "url.full"]);
+
+        let (most_reused, _) = attribute_reuse_ranking(&attribute_reuse, 10);
+        assert_eq!(most_reused.len(), 3);
+    }
+
+    #[test]
+    fn test_enum_stats_report() {
+        let enum_stats: BTreeMap<String, EnumStats> = [
+            ("db.system", 40, true),
+            ("http.request.method", 10, true),
+            ("network.transport", 5, false),
+            ("otel.status_code", 2, false),
+            ("server.type", 10, false),
+        ]
+        .into_iter()
+        .map(|(name, member_count, allow_custom_values)| {
+            (
+                name.to_owned(),
+                EnumStats {
+                    member_count,
+                    allow_custom_values,
+                },
+            )
+        })
+        .collect();
+
+        let breakdown = enum_cardinality_breakdown(&enum_stats);
+        assert_eq!(
+            breakdown.into_iter().collect::<Vec<_>>(),
+            vec![(2, 1), (5, 1), (10, 2), (40, 1)]
+        );
+
+        let largest: Vec<_> = largest_enums(&enum_stats, 3)
+            .into_iter()
+            .map(|(name, stats)| (name, stats.member_count))
+            .collect();
+        assert_eq!(
+            largest,
+            vec![
+                ("db.system", 40),
+                ("http.request.method", 10),
+                ("server.type", 10)
+            ]
+        );
+        assert_eq!(largest_enums(&enum_stats, 10).len(), 5);
+    }
+
+    #[test]
+    fn test_group_complexity() {
+        let mut registry = SemConvRegistry::try_from_path_pattern(
+            "default",
+            "crates/weaver_resolver/data/registry-test-3-extends/registry/*.yaml",
+        )
+        .expect("Failed to load the registry");
+        let schema = SchemaResolver::resolve_semantic_convention_registry(&mut registry)
+            .expect("Failed to resolve the registry");
+        let complexity = group_complexity(&registry, &schema);
+
+        let common = &complexity["messaging.attributes.common"];
+        assert!(common.extends_chain.is_empty());
+        assert_eq!(common.own_attribute_count, 6);
+        assert_eq!(common.inherited_attribute_count, 0);
+        assert_eq!(common.fan_out, 1);
+
+        let attributes = &complexity["metric.messaging.attributes"];
+        assert_eq!(
+            attributes.extends_chain,
+            vec!["messaging.attributes.common"]
+        );
+        assert_eq!(attributes.own_attribute_count, 2);
+        assert_eq!(attributes.inherited_attribute_count, 6);
+        assert_eq!(attributes.fan_out, 6);
+
+        let duration = &complexity["metric.messaging.publish.duration"];
+        assert_eq!(
+            duration.extends_chain,
+            vec!["metric.messaging.attributes", "messaging.attributes.common"]
+        );
+        assert_eq!(duration.own_attribute_count, 0);
+        assert_eq!(duration.inherited_attribute_count, 8);
+        assert_eq!(duration.constraint_count, 0);
+        assert_eq!(duration.fan_out, 0);
+
+        let (max, _) = max_and_average(&complexity, |group| group.extends_chain.len());
+        assert_eq!(max, 2);
+        let widest: Vec<_> = highest_groups(&complexity, 2, |group| group.fan_out)
+            .into_iter()
+            .map(|(id, group)| (id, group.fan_out))
+            .collect();
+        assert_eq!(
+            widest,
+            vec![
+                ("metric.messaging.attributes", 6),
+                ("attributes.http.common", 2)
+            ]
+        );
+        assert!(
+            highest_groups(&complexity, 100, |group| group.extends_chain.len())
+                .iter()
+                .all(|(_, group)| !group.extends_chain.is_empty())
+        );
+    }
+
+    #[test]
+    fn test_namespace_table() {
+        let mut http = NamespaceStats {
+            attribute_count: 4,
+            stable_attribute_count: 3,
+            deprecated_attribute_count: 1,
+            total_brief_length: 100,
+            ..Default::default()
+        };
+        _ = http.group_breakdown.insert(GroupType::Span, 2);
+        _ = http.group_breakdown.insert(GroupType::AttributeGroup, 1);
+        let mut jvm = NamespaceStats::default();
+        _ = jvm.group_breakdown.insert(GroupType::Metric, 12);
+        let namespace_stats = [("http".to_owned(), http), ("jvm".to_owned(), jvm)]
+            .into_iter()
+            .collect();
+
+        assert_eq!(
+            namespace_table(&namespace_stats),
+            vec![
+                "Namespace  Groups                      Attributes  Stable  Deprecated  Avg brief",
+                "http       AttributeGroup: 1, Span: 2  4           75%     25%         25",
+                "jvm        Metric: 12                  0           -       -           0",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_namespace_trends() {
+        let baseline = resolve("crates/weaver_schema/data/v1.0.0/*.yaml");
+        let current = resolve("crates/weaver_schema/data/v1.1.0/*.yaml");
+        let trends = namespace_trends(&baseline, &current);
+        assert_eq!(
+            trends["http"],
+            NamespaceTrend {
+                baseline_count: 3,
+                current_count: 4,
+                added: vec!["http.request.method".to_owned()],
+                stabilized: vec![],
+                deprecated: vec!["http.flavor".to_owned(), "http.method".to_owned()],
+                removed: vec![],
+            }
+        );
+        assert_eq!(
+            namespace_trend_table(&trends, &Owners::default()),
+            vec![
+                "Namespace  Attributes   New  Stabilized  Deprecated  Removed",
+                "http       3 -> 4 (+1)  1    0           2           0",
+                "url        1 -> 1 (+0)  0    0           0           0",
+            ]
+        );
+        assert_eq!(
+            namespace_trend_table(&trends, &Owners::parse("http @org/http-team")),
+            vec![
+                "Namespace  Attributes   New  Stabilized  Deprecated  Removed  Owners",
+                "http       3 -> 4 (+1)  1    0           2           0        @org/http-team",
+                "url        1 -> 1 (+0)  0    0           0           0",
+            ]
+        );
+
+        // The baseline and the current registries are swapped.
+        let trends = namespace_trends(&current, &baseline);
+        assert_eq!(trends["http"].removed, vec!["http.request.method"]);
+        assert!(trends["http"].deprecated.is_empty());
+    }
+}
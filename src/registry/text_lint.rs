@@ -0,0 +1,385 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Lint of the text of a semantic convention registry, i.e. the `brief` and
+//! `note` of its groups and attributes: misspelled words, discouraged terms,
+//! and RFC 2119 keywords that are not capitalized.
+
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::fmt::{Display, Formatter};
+use std::path::Path;
+
+use serde::Serialize;
+use weaver_common::diagnostic::DiagnosticMessages;
+use weaver_semconv::attribute::AttributeSpec;
+use weaver_semconv::semconv::SemConvSpec;
+
+use crate::registry::dead_definitions::qualified_id;
+use crate::registry::Error;
+
+/// Common misspellings and their correction, checked in addition to the rules
+/// of the project dictionary.
+const MISSPELLINGS: &[(&str, &str)] = &[
+    ("accross", "across"),
+    ("acheive", "achieve"),
+    ("adress", "address"),
+    ("agressive", "aggressive"),
+    ("alot", "a lot"),
+    ("apparant", "apparent"),
+    ("appearence", "appearance"),
+    ("arguement", "argument"),
+    ("asynchonous", "asynchronous"),
+    ("attribtue", "attribute"),
+    ("authentification", "authentication"),
+    ("availible", "available"),
+    ("begining", "beginning"),
+    ("beleive", "believe"),
+    ("calender", "calendar"),
+    ("cancelation", "cancellation"),
+    ("commited", "committed"),
+    ("concatination", "concatenation"),
+    ("connexion", "connection"),
+    ("containg", "containing"),
+    ("defualt", "default"),
+    ("definately", "definitely"),
+    ("dependant", "dependent"),
+    ("descripton", "description"),
+    ("enviroment", "environment"),
+    ("exeption", "exception"),
+    ("existant", "existent"),
+    ("familar", "familiar"),
+    ("guarentee", "guarantee"),
+    ("heirarchy", "hierarchy"),
+    ("identifer", "identifier"),
+    ("independant", "independent"),
+    ("informations", "information"),
+    ("intial", "initial"),
+    ("lenght", "length"),
+    ("maintainance", "maintenance"),
+    ("mesage", "message"),
+    ("milisecond", "millisecond"),
+    ("miliseconds", "milliseconds"),
+    ("neccessary", "necessary"),
+    ("occured", "occurred"),
+    ("occurence", "occurrence"),
+    ("paramter", "parameter"),
+    ("persistant", "persistent"),
+    ("posible", "possible"),
+    ("prefered", "preferred"),
+    ("proccess", "process"),
+    ("recieve", "receive"),
+    ("recieved", "received"),
+    ("refered", "referred"),
+    ("reponse", "response"),
+    ("requets", "request"),
+    ("seperate", "separate"),
+    ("seperated", "separated"),
+    ("sucess", "success"),
+    ("succesful", "successful"),
+    ("successfull", "successful"),
+    ("teh", "the"),
+    ("threshhold", "threshold"),
+    ("transfered", "transferred"),
+    ("untill", "until"),
+    ("usefull", "useful"),
+    ("wich", "which"),
+];
+
+/// The RFC 2119 keywords that must be capitalized. `may` is not part of the
+/// list as it is commonly used in a non-normative sense.
+const NORMATIVE_KEYWORDS: &[&str] = &["must", "shall", "should"];
+
+/// The text field of a group or an attribute.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TextField {
+    /// The `brief` field.
+    Brief,
+    /// The `note` field.
+    Note,
+}
+
+impl Display for TextField {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TextField::Brief => write!(f, "brief"),
+            TextField::Note => write!(f, "note"),
+        }
+    }
+}
+
+/// Returns the description of the item of a text finding: the attribute of the
+/// group, or the group itself when `attr` is empty.
+pub(crate) fn item(group: &str, attr: &str) -> String {
+    if attr.is_empty() {
+        format!("group `{group}`")
+    } else {
+        format!("attribute `{attr}` of the group `{group}`")
+    }
+}
+
+/// The words accepted in the text of a registry and the misspellings or
+/// discouraged terms to replace.
+///
+/// Each line of a dictionary file is either an accepted word (e.g. a product
+/// name), or a rule `word -> replacement`. Empty lines and lines starting with
+/// `#` are ignored. The words are case-insensitive. An accepted word disables
+/// every rule for this word, including the capitalization of the RFC 2119
+/// keywords.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Dictionary {
+    /// The accepted words, in lowercase.
+    accepted: HashSet<String>,
+    /// The replacement of the misspellings and discouraged terms, by word in
+    /// lowercase.
+    replacements: HashMap<String, String>,
+}
+
+impl Default for Dictionary {
+    /// The dictionary of the common misspellings.
+    fn default() -> Self {
+        Self {
+            accepted: HashSet::new(),
+            replacements: MISSPELLINGS
+                .iter()
+                .map(|(word, replacement)| ((*word).to_owned(), (*replacement).to_owned()))
+                .collect(),
+        }
+    }
+}
+
+impl Dictionary {
+    /// Adds the words and rules of a dictionary file to the common
+    /// misspellings.
+    pub(crate) fn parse(content: &str) -> Self {
+        let mut dictionary = Self::default();
+        for line in content
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        {
+            if let Some((word, replacement)) = line.split_once("->") {
+                _ = dictionary
+                    .replacements
+                    .insert(word.trim().to_lowercase(), replacement.trim().to_owned());
+            } else {
+                _ = dictionary.accepted.insert(line.to_lowercase());
+            }
+        }
+        dictionary
+    }
+
+    /// Loads a dictionary file.
+    pub(crate) fn from_file(path: &Path) -> Result<Self, Error> {
+        let content = std::fs::read_to_string(path).map_err(|e| Error::InvalidDictionary {
+            path: path.to_path_buf(),
+            error: e.to_string(),
+        })?;
+        Ok(Self::parse(&content))
+    }
+}
+
+/// Reports, as warnings, the misspelled words, the discouraged terms, and the
+/// RFC 2119 keywords not capitalized found in the `brief` and `note` of the
+/// groups and attributes of the registry. The code spans (between backquotes)
+/// and the URLs are not checked.
+pub(crate) fn check_text(
+    semconv_specs: &[(String, SemConvSpec)],
+    dictionary: &Dictionary,
+) -> Result<(), DiagnosticMessages> {
+    let warnings = text_findings(semconv_specs, dictionary);
+    if warnings.is_empty() {
+        Ok(())
+    } else {
+        Err(DiagnosticMessages::from_errors(warnings))
+    }
+}
+
+/// Returns the findings of the text lint of the registry.
+fn text_findings(semconv_specs: &[(String, SemConvSpec)], dictionary: &Dictionary) -> Vec<Error> {
+    let mut findings = vec![];
+    for (provenance, spec) in semconv_specs {
+        for group in spec.groups() {
+            let texts = [
+                (TextField::Brief, group.brief.as_str()),
+                (TextField::Note, group.note.as_str()),
+            ];
+            for (field, text) in texts {
+                lint_text(text, dictionary, |word, finding| {
+                    findings.push(finding.into_error(word, field, &group.id, "", provenance));
+                });
+            }
+            for attr in &group.attributes {
+                let name = match attr {
+                    AttributeSpec::Ref { r#ref, .. } => r#ref.clone(),
+                    AttributeSpec::Id { id, .. } => qualified_id(group, id),
+                };
+                for (field, text) in [
+                    (TextField::Brief, attr.brief()),
+                    (TextField::Note, attr.note()),
+                ] {
+                    lint_text(&text, dictionary, |word, finding| {
+                        findings
+                            .push(finding.into_error(word, field, &group.id, &name, provenance));
+                    });
+                }
+            }
+        }
+    }
+    findings
+}
+
+/// A finding of the text lint.
+enum Finding {
+    /// A misspelled word or a discouraged term, with its replacement.
+    Replace(String),
+    /// An RFC 2119 keyword not capitalized.
+    Capitalize,
+}
+
+impl Finding {
+    /// Converts the finding on a word of the text of an item into an error.
+    fn into_error(
+        self,
+        word: &str,
+        field: TextField,
+        group: &str,
+        attr: &str,
+        provenance: &str,
+    ) -> Error {
+        match self {
+            Finding::Replace(replacement) => Error::Misspelling {
+                word: word.to_owned(),
+                replacement,
+                field,
+                group: group.to_owned(),
+                attr: attr.to_owned(),
+                provenance: provenance.to_owned(),
+            },
+            Finding::Capitalize => Error::LowercaseNormativeKeyword {
+                keyword: word.to_owned(),
+                field,
+                group: group.to_owned(),
+                attr: attr.to_owned(),
+                provenance: provenance.to_owned(),
+            },
+        }
+    }
+}
+
+/// Calls `report` for each word of the text (outside code spans and URLs)
+/// breaking a rule of the dictionary. A word is reported once per text.
+fn lint_text(text: &str, dictionary: &Dictionary, mut report: impl FnMut(&str, Finding)) {
+    let mut reported = BTreeSet::new();
+    // The code spans are the odd segments between backquotes.
+    let prose = text.split('`').step_by(2);
+    let words = prose
+        .flat_map(str::split_whitespace)
+        .filter(|token| !token.contains("://"))
+        .flat_map(|token| token.split(|c: char| !c.is_ascii_alphanumeric()))
+        .filter(|word| !word.is_empty());
+    for word in words {
+        let lowercase = word.to_lowercase();
+        if dictionary.accepted.contains(&lowercase) || !reported.insert(word) {
+            continue;
+        }
+        if let Some(replacement) = dictionary.replacements.get(&lowercase) {
+            report(word, Finding::Replace(match_case(word, replacement)));
+        } else if NORMATIVE_KEYWORDS.contains(&lowercase.as_str()) && word != word.to_uppercase() {
+            report(word, Finding::Capitalize);
+        }
+    }
+}
+
+/// Returns the replacement of a word, capitalized if the word is.
+fn match_case(word: &str, replacement: &str) -> String {
+    let mut chars = replacement.chars();
+    match chars.next() {
+        Some(first) if word.starts_with(|c: char| c.is_ascii_uppercase()) => {
+            first.to_uppercase().chain(chars).collect()
+        }
+        _ => replacement.to_owned(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use weaver_semconv::semconv::SemConvSpec;
+
+    use crate::registry::text_lint::{check_text, text_findings, Dictionary};
+
+    #[test]
+    fn test_check_text() {
+        let registry = SemConvSpec::from_string(
+            r#"groups:
+  - id: registry.http
+    type: attribute_group
+    brief: HTTP attributes recieved by the server.
+    note: The attributes should be set on the spans, see https://exemple.com/specs.
+    prefix: http
+    attributes:
+      - id: request.method
+        type: string
+        brief: HTTP request method.
+        note: The method MUST be known, otherwise `_OTHER` Must be used. Use `recieve` or `occured`.
+        examples: [GET]
+      - id: route
+        type: string
+        brief: Occured route of the k8s cluster.
+        examples: ['/users/:id']
+  - id: span.http.client
+    type: span
+    span_kind: client
+    brief: HTTP client span.
+    attributes:
+      - ref: http.request.method
+        note: The method colour should be checked.
+"#,
+        )
+        .expect("Invalid registry");
+        let specs = vec![("registry.yaml".to_owned(), registry)];
+
+        let messages: Vec<String> = text_findings(&specs, &Dictionary::default())
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+        assert_eq!(
+            messages,
+            vec![
+                "The word `recieved` in the brief of the group `registry.http` (defined in `registry.yaml`) is misspelled or discouraged, use `received` instead.",
+                "The keyword `should` in the note of the group `registry.http` (defined in `registry.yaml`) is not capitalized, use `SHOULD` as defined by RFC 2119 or rephrase the sentence.",
+                "The keyword `Must` in the note of the attribute `http.request.method` of the group `registry.http` (defined in `registry.yaml`) is not capitalized, use `MUST` as defined by RFC 2119 or rephrase the sentence.",
+                "The word `Occured` in the brief of the attribute `http.route` of the group `registry.http` (defined in `registry.yaml`) is misspelled or discouraged, use `Occurred` instead.",
+                "The keyword `should` in the note of the attribute `http.request.method` of the group `span.http.client` (defined in `registry.yaml`) is not capitalized, use `SHOULD` as defined by RFC 2119 or rephrase the sentence.",
+            ]
+        );
+
+        // The project dictionary accepts words and adds terminology rules.
+        let dictionary = Dictionary::parse(
+            "# Project dictionary
+should
+colour -> color
+k8s -> Kubernetes
+",
+        );
+        let messages: Vec<String> = text_findings(&specs, &dictionary)
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+        assert_eq!(messages.len(), 5);
+        assert!(
+            messages[3].starts_with("The word `k8s` in the brief of the attribute `http.route`")
+        );
+        assert!(messages[4].contains("use `color` instead"));
+
+        // The findings are warnings, attributed to the attribute or the group.
+        let diag_msgs = check_text(&specs, &dictionary).expect_err("Findings expected");
+        assert!(!diag_msgs.has_error());
+        let json = serde_json::to_value(&diag_msgs).expect("Failed to serialize the findings");
+        assert_eq!(json[0]["error"]["Misspelling"]["group"], "registry.http");
+        assert_eq!(json[0]["error"]["Misspelling"]["attr"], "");
+        assert_eq!(
+            json[1]["error"]["LowercaseNormativeKeyword"]["attr"],
+            "http.request.method"
+        );
+    }
+}
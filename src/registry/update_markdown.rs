@@ -4,7 +4,7 @@
 //! update the specified sections.
 
 use crate::registry::RegistryArgs;
-use crate::util::semconv_registry_path_from;
+use crate::util::load_semconv_specs;
 use crate::{DiagnosticArgs, ExitDirectives};
 use clap::Args;
 use weaver_cache::Cache;
@@ -75,11 +75,13 @@ pub(crate) fn command(
         }
     };
 
-    let generator = SnippetGenerator::try_from_url(
-        semconv_registry_path_from(&args.registry.registry, &args.registry.registry_git_sub_dir),
+    let semconv_specs = load_semconv_specs(
+        &args.registry.registry_paths(),
+        &args.registry.file_filter()?,
         cache,
-        generator,
+        log.clone(),
     )?;
+    let generator = SnippetGenerator::try_from_semconv_specs(semconv_specs, generator)?;
     log.success("Registry resolved successfully");
     let operation = if args.dry_run {
         "Validating"
@@ -130,12 +132,17 @@ mod tests {
         let cli = Cli {
             debug: 0,
             quiet: false,
+            cache: Default::default(),
             command: Some(Commands::Registry(RegistryCommand {
                 command: RegistrySubCommand::UpdateMarkdown(RegistryUpdateMarkdownArgs {
                     markdown_dir: "data/update_markdown/markdown".to_owned(),
                     registry: RegistryArgs {
-                        registry: RegistryPath::Local("data/update_markdown/registry".to_owned()),
+                        registry: vec![RegistryPath::Local(
+                            "data/update_markdown/registry".to_owned(),
+                        )],
                         registry_git_sub_dir: None,
+                        include: vec![],
+                        exclude: vec![],
                     },
                     dry_run: true,
                     attribute_registry_base_url: Some("/docs/attributes-registry".to_owned()),
@@ -0,0 +1,177 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Re-run a registry command each time the files it depends on change.
+
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::channel;
+use std::time::Duration;
+
+use notify_debouncer_mini::new_debouncer;
+use notify_debouncer_mini::notify::RecursiveMode;
+
+use weaver_common::diagnostic::DiagnosticMessages;
+use weaver_common::Logger;
+
+use crate::registry::{Error, RegistryPath};
+use crate::{process_diagnostics, CmdResult, DiagnosticArgs, ExitDirectives};
+
+/// Delay during which the file changes are grouped into a single re-run
+/// (editors usually write a file in several steps).
+const DEBOUNCE_DELAY: Duration = Duration::from_millis(300);
+
+/// The files watched by a command.
+#[derive(Debug, Default)]
+pub(crate) struct WatchedPaths {
+    /// Files and directories (watched recursively) triggering a re-run.
+    paths: Vec<PathBuf>,
+    /// Directories whose changes are ignored (e.g. the output directory of
+    /// the command).
+    ignored: Vec<PathBuf>,
+}
+
+impl WatchedPaths {
+    /// Watches the files of local registries.
+    pub(crate) fn registries(registries: &[RegistryPath]) -> Result<Self, Error> {
+        let paths = registries
+            .iter()
+            .map(|registry| match registry {
+                RegistryPath::Local(path) => Ok(PathBuf::from(path)),
+                RegistryPath::Url(url) => Err(Error::WatchFailed {
+                    error: format!("The registry `{url}` is not a local directory."),
+                }),
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Self {
+            paths,
+            ignored: vec![],
+        })
+    }
+
+    /// Watches an additional file or directory, if it exists.
+    pub(crate) fn watch(mut self, path: &Path) -> Self {
+        if path.exists() {
+            self.paths.push(path.to_path_buf());
+        }
+        self
+    }
+
+    /// Ignores the changes of the given directory.
+    pub(crate) fn ignore(mut self, path: &Path) -> Self {
+        self.ignored.push(path.to_path_buf());
+        self
+    }
+
+    /// Returns true if the change of the given file must trigger a re-run.
+    /// Hidden files and backup files (e.g. `.file.yaml.swp` or `file.yaml~`)
+    /// are ignored.
+    fn is_relevant(&self, path: &Path) -> bool {
+        let hidden_or_backup = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .map_or(true, |name| name.starts_with('.') || name.ends_with('~'));
+        !hidden_or_backup
+            && !self.ignored.iter().any(|ignored| {
+                path.starts_with(ignored)
+                    || ignored
+                        .canonicalize()
+                        .is_ok_and(|ignored| path.starts_with(ignored))
+            })
+    }
+}
+
+/// Runs the command, then re-runs it each time a watched file changes, until
+/// the process is interrupted. The diagnostic messages of each run are
+/// rendered immediately.
+#[cfg(not(tarpaulin_include))]
+pub(crate) fn watch(
+    logger: impl Logger + Sync + Clone,
+    watched: &WatchedPaths,
+    diagnostic_args: &DiagnosticArgs,
+    mut run: impl FnMut() -> Result<ExitDirectives, DiagnosticMessages>,
+) -> Result<ExitDirectives, DiagnosticMessages> {
+    let (sender, receiver) = channel();
+    let mut debouncer = new_debouncer(DEBOUNCE_DELAY, sender).map_err(|e| Error::WatchFailed {
+        error: e.to_string(),
+    })?;
+    for path in &watched.paths {
+        debouncer
+            .watcher()
+            .watch(path, RecursiveMode::Recursive)
+            .map_err(|e| Error::WatchFailed {
+                error: format!("Failed to watch `{}`. {}", path.display(), e),
+            })?;
+    }
+
+    _ = process_diagnostics(
+        CmdResult::new(run(), Some(diagnostic_args.clone())),
+        logger.clone(),
+    );
+    logger.log(&format!(
+        "Watching {} for changes (press Ctrl-C to stop)",
+        watched
+            .paths
+            .iter()
+            .map(|path| format!("`{}`", path.display()))
+            .collect::<Vec<_>>()
+            .join(", ")
+    ));
+
+    for events in receiver {
+        let events = events.map_err(|e| Error::WatchFailed {
+            error: e.to_string(),
+        })?;
+        let mut changed: Vec<_> = events
+            .into_iter()
+            .map(|event| event.path)
+            .filter(|path| watched.is_relevant(path))
+            .collect();
+        if changed.is_empty() {
+            continue;
+        }
+        changed.sort();
+        changed.dedup();
+        for path in &changed {
+            logger.log(&format!("Changed: {}", path.display()));
+        }
+        _ = process_diagnostics(
+            CmdResult::new(run(), Some(diagnostic_args.clone())),
+            logger.clone(),
+        );
+    }
+
+    Ok(ExitDirectives {
+        exit_code: 0,
+        quiet_mode: false,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use crate::registry::watch::WatchedPaths;
+    use crate::registry::RegistryPath;
+
+    #[test]
+    fn test_watched_paths() {
+        let watched = WatchedPaths::registries(&[RegistryPath::Local("model".to_owned())])
+            .expect("Local registries can be watched")
+            .watch(Path::new("does-not-exist"))
+            .ignore(Path::new("output"));
+        assert_eq!(watched.paths, vec![Path::new("model")]);
+
+        assert!(watched.is_relevant(Path::new("model/http.yaml")));
+        assert!(watched.is_relevant(Path::new("templates/registry/go/attributes.j2")));
+        assert!(!watched.is_relevant(Path::new("model/.http.yaml.swp")));
+        assert!(!watched.is_relevant(Path::new("model/http.yaml~")));
+        assert!(!watched.is_relevant(Path::new("output/attributes.go")));
+
+        assert!(WatchedPaths::registries(&[
+            RegistryPath::Local("model".to_owned()),
+            RegistryPath::Url(
+                "https://github.com/open-telemetry/semantic-conventions.git".to_owned()
+            )
+        ])
+        .is_err());
+    }
+}
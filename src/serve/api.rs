@@ -0,0 +1,454 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! REST API exposing resolved semantic convention registries.
+
+use std::future::Future;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::Duration;
+
+use axum::extract::{Path, Query, State};
+use axum::http::{header, StatusCode};
+use axum::response::{IntoResponse, Response};
+use axum::routing::get;
+use axum::{Json, Router};
+use serde::{Deserialize, Serialize};
+use tokio::net::TcpListener;
+use tokio::runtime::Runtime;
+
+use weaver_forge::registry::{ResolvedGroup, ResolvedRegistry};
+use weaver_resolved_schema::attribute::Attribute;
+
+use crate::registry::search::{group_type_name, registry_items, search, RegistryItem, SearchKind};
+use crate::serve::Error;
+
+/// The OpenAPI description of the REST API.
+const OPENAPI: &str = include_str!("openapi.json");
+
+/// Default number of matches returned by the `/search` endpoint.
+const DEFAULT_SEARCH_LIMIT: usize = 20;
+
+/// A resolved registry served by the REST API.
+pub(crate) struct ServedRegistry {
+    /// The name of the registry, used to select it in the requests.
+    name: String,
+    /// The resolved registry.
+    registry: ResolvedRegistry,
+    /// The searchable items of the registry.
+    items: Vec<RegistryItem>,
+}
+
+impl ServedRegistry {
+    /// Creates a served registry and indexes its items.
+    pub(crate) fn new(name: String, registry: ResolvedRegistry) -> Self {
+        let items = registry_items(&registry);
+        Self {
+            name,
+            registry,
+            items,
+        }
+    }
+}
+
+/// The registries served, the first one being the default registry.
+type Registries = Arc<Vec<ServedRegistry>>;
+
+/// HTTP server exposing the REST API.
+pub(crate) struct RegistryServer {
+    /// The runtime driving the server.
+    runtime: Runtime,
+    /// The listener accepting the connections.
+    listener: TcpListener,
+}
+
+impl RegistryServer {
+    /// Binds the server to the given address.
+    pub(crate) fn bind(addr: SocketAddr) -> Result<Self, Error> {
+        let runtime = tokio::runtime::Builder::new_multi_thread()
+            .enable_all()
+            .build()
+            .map_err(|e| server_error(&e))?;
+        let listener = runtime
+            .block_on(TcpListener::bind(addr))
+            .map_err(|e| server_error(&format!("{}: {}", addr, e)))?;
+        Ok(Self { runtime, listener })
+    }
+
+    /// Returns the address the server listens on.
+    pub(crate) fn local_addr(&self) -> Result<SocketAddr, Error> {
+        self.listener.local_addr().map_err(|e| server_error(&e))
+    }
+
+    /// Serves the registries until the `shutdown` future completes.
+    pub(crate) fn run<F>(self, registries: Vec<ServedRegistry>, shutdown: F) -> Result<(), Error>
+    where
+        F: Future<Output = ()> + Send + 'static,
+    {
+        let Self { runtime, listener } = self;
+        let result = runtime.block_on(async move {
+            axum::serve(listener, router(registries))
+                .with_graceful_shutdown(shutdown)
+                .await
+        });
+        runtime.shutdown_timeout(Duration::from_secs(1));
+        result.map_err(|e| server_error(&e))
+    }
+}
+
+/// Builds the router of the REST API.
+fn router(registries: Vec<ServedRegistry>) -> Router {
+    Router::new()
+        .route("/registries", get(list_registries))
+        .route("/groups", get(list_groups))
+        .route("/groups/:id", get(get_group))
+        .route("/attributes/:name", get(get_attribute))
+        .route("/search", get(search_registry))
+        .route("/resolved", get(get_resolved))
+        .route("/openapi.json", get(get_openapi))
+        .with_state(Arc::new(registries))
+}
+
+/// An error returned by the REST API as `{"error": "<message>"}`.
+struct ApiError {
+    /// The HTTP status of the response.
+    status: StatusCode,
+    /// The error message.
+    message: String,
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        let body = Json(serde_json::json!({ "error": self.message }));
+        (self.status, body).into_response()
+    }
+}
+
+/// Returns a `404 Not Found` error.
+fn not_found(message: String) -> ApiError {
+    ApiError {
+        status: StatusCode::NOT_FOUND,
+        message,
+    }
+}
+
+/// Query parameter selecting a registry.
+#[derive(Deserialize)]
+struct RegistryParam {
+    /// The name of the registry (the default registry if not specified).
+    registry: Option<String>,
+}
+
+/// Returns the registry selected by the request.
+fn served_registry<'a>(
+    registries: &'a [ServedRegistry],
+    name: &Option<String>,
+) -> Result<&'a ServedRegistry, ApiError> {
+    match name {
+        None => registries.first(),
+        Some(name) => registries.iter().find(|served| &served.name == name),
+    }
+    .ok_or_else(|| {
+        not_found(format!(
+            "Unknown registry `{}`",
+            name.as_deref().unwrap_or_default()
+        ))
+    })
+}
+
+/// A summary of a served registry.
+#[derive(Serialize)]
+struct RegistrySummary<'a> {
+    /// The name of the registry.
+    name: &'a str,
+    /// The URL of the registry.
+    registry_url: &'a str,
+    /// The number of groups of the registry.
+    group_count: usize,
+    /// The number of attributes of the registry.
+    attribute_count: usize,
+}
+
+/// `GET /registries`: lists the served registries.
+async fn list_registries(State(registries): State<Registries>) -> Response {
+    let summaries: Vec<_> = registries
+        .iter()
+        .map(|served| RegistrySummary {
+            name: &served.name,
+            registry_url: &served.registry.registry_url,
+            group_count: served.registry.groups.len(),
+            attribute_count: served
+                .items
+                .iter()
+                .filter(|item| item.kind == SearchKind::Attribute)
+                .count(),
+        })
+        .collect();
+    Json(summaries).into_response()
+}
+
+/// Query parameters of the `/groups` endpoint.
+#[derive(Deserialize)]
+struct GroupsParams {
+    /// The name of the registry (the default registry if not specified).
+    registry: Option<String>,
+    /// The type of the groups to return (all types if not specified).
+    r#type: Option<String>,
+}
+
+/// `GET /groups`: lists the groups of a registry, optionally filtered by type.
+async fn list_groups(
+    State(registries): State<Registries>,
+    Query(params): Query<GroupsParams>,
+) -> Result<Response, ApiError> {
+    let served = served_registry(&registries, &params.registry)?;
+    let groups: Vec<&ResolvedGroup> = served
+        .registry
+        .groups
+        .iter()
+        .filter(|group| {
+            params
+                .r#type
+                .as_ref()
+                .map_or(true, |r#type| group_type_name(&group.r#type) == r#type)
+        })
+        .collect();
+    Ok(Json(groups).into_response())
+}
+
+/// `GET /groups/{id}`: returns a group of a registry.
+async fn get_group(
+    State(registries): State<Registries>,
+    Path(id): Path<String>,
+    Query(params): Query<RegistryParam>,
+) -> Result<Response, ApiError> {
+    let served = served_registry(&registries, &params.registry)?;
+    let group = served
+        .registry
+        .groups
+        .iter()
+        .find(|group| group.id == id)
+        .ok_or_else(|| not_found(format!("Unknown group `{id}`")))?;
+    Ok(Json(group).into_response())
+}
+
+/// The response of the `/attributes/{name}` endpoint.
+#[derive(Serialize)]
+struct AttributeResponse<'a> {
+    /// The resolved attribute, as defined in its group.
+    attribute: &'a Attribute,
+    /// The id of the group defining the attribute.
+    group_id: &'a str,
+    /// The source file of the group defining the attribute.
+    provenance: &'a str,
+    /// The ids of the groups referencing the attribute.
+    referenced_by: Vec<&'a str>,
+}
+
+/// `GET /attributes/{name}`: returns an attribute of a registry with its
+/// defining group and the groups referencing it.
+async fn get_attribute(
+    State(registries): State<Registries>,
+    Path(name): Path<String>,
+    Query(params): Query<RegistryParam>,
+) -> Result<Response, ApiError> {
+    let served = served_registry(&registries, &params.registry)?;
+    let unknown_attribute = || not_found(format!("Unknown attribute `{name}`"));
+    let item = served
+        .items
+        .iter()
+        .find(|item| item.kind == SearchKind::Attribute && item.id == name)
+        .ok_or_else(unknown_attribute)?;
+    let attribute = served
+        .registry
+        .groups
+        .iter()
+        .filter(|group| group.id == item.group_id)
+        .flat_map(|group| group.attributes.iter())
+        .find(|attr| attr.name == name)
+        .ok_or_else(unknown_attribute)?;
+    // The groups using the attribute, except the group defining it.
+    let referenced_by = served
+        .registry
+        .attribute_index
+        .get(&name)
+        .into_iter()
+        .flatten()
+        .map(|usage| usage.group_id.as_str())
+        .filter(|group_id| *group_id != item.group_id)
+        .collect();
+    Ok(Json(AttributeResponse {
+        attribute,
+        group_id: &item.group_id,
+        provenance: &item.provenance,
+        referenced_by,
+    })
+    .into_response())
+}
+
+/// Query parameters of the `/search` endpoint.
+#[derive(Deserialize)]
+struct SearchParams {
+    /// The name of the registry (the default registry if not specified).
+    registry: Option<String>,
+    /// The search query.
+    q: String,
+    /// The kind of items to search (all kinds if not specified).
+    kind: Option<SearchKind>,
+    /// The maximum number of matches to return.
+    limit: Option<usize>,
+}
+
+/// A match of the `/search` endpoint.
+#[derive(Serialize)]
+struct SearchMatch<'a> {
+    /// The score of the match (the higher the better).
+    score: i64,
+    /// The matching item.
+    #[serde(flatten)]
+    item: &'a RegistryItem,
+}
+
+/// `GET /search?q=`: searches the attributes, metrics, and groups of a
+/// registry, the best matches first.
+async fn search_registry(
+    State(registries): State<Registries>,
+    Query(params): Query<SearchParams>,
+) -> Result<Response, ApiError> {
+    let served = served_registry(&registries, &params.registry)?;
+    let kinds: Vec<_> = params.kind.into_iter().collect();
+    let matches: Vec<_> = search(&served.items, &params.q, &kinds)
+        .into_iter()
+        .take(params.limit.unwrap_or(DEFAULT_SEARCH_LIMIT))
+        .map(|(score, item)| SearchMatch { score, item })
+        .collect();
+    Ok(Json(matches).into_response())
+}
+
+/// `GET /resolved`: returns the whole resolved registry.
+async fn get_resolved(
+    State(registries): State<Registries>,
+    Query(params): Query<RegistryParam>,
+) -> Result<Response, ApiError> {
+    let served = served_registry(&registries, &params.registry)?;
+    Ok(Json(&served.registry).into_response())
+}
+
+/// `GET /openapi.json`: returns the OpenAPI description of the REST API.
+async fn get_openapi() -> Response {
+    ([(header::CONTENT_TYPE, "application/json")], OPENAPI).into_response()
+}
+
+/// Creates a server error.
+fn server_error(error: &impl ToString) -> Error {
+    Error::ServerFailed {
+        error: error.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::sync::oneshot;
+
+    use weaver_forge::registry::ResolvedRegistry;
+    use weaver_resolver::SchemaResolver;
+    use weaver_semconv::registry::SemConvRegistry;
+
+    use crate::serve::api::{RegistryServer, ServedRegistry, OPENAPI};
+
+    fn get(url: &str) -> (u16, serde_json::Value) {
+        let response = match ureq::get(url).call() {
+            Ok(response) => response,
+            Err(ureq::Error::Status(_, response)) => response,
+            Err(e) => panic!("Request to {url} failed: {e}"),
+        };
+        let status = response.status();
+        let body = response.into_string().expect("Failed to read the response");
+        let body = serde_json::from_str(&body).expect("Invalid JSON response");
+        (status, body)
+    }
+
+    #[test]
+    fn test_registry_server() {
+        let registry_id = "default";
+        let mut registry = SemConvRegistry::try_from_path_pattern(
+            registry_id,
+            "crates/weaver_codegen_test/semconv_registry/**/*.yaml",
+        )
+        .expect("Failed to load the registry");
+        let schema = SchemaResolver::resolve_semantic_convention_registry(&mut registry)
+            .expect("Failed to resolve the registry");
+        let registry = ResolvedRegistry::try_from_resolved_registry(
+            schema
+                .registry(registry_id)
+                .expect("Failed to get the registry from the resolved schema"),
+            schema.catalog(),
+        )
+        .expect("Failed to create the resolved registry");
+
+        let server = RegistryServer::bind("127.0.0.1:0".parse().expect("Invalid address"))
+            .expect("Failed to bind the server");
+        let base_url = format!("http://{}", server.local_addr().expect("No address"));
+        let (shutdown, shutdown_received) = oneshot::channel::<()>();
+        let server_thread = std::thread::spawn(move || {
+            server.run(
+                vec![ServedRegistry::new("otel".to_owned(), registry)],
+                async {
+                    _ = shutdown_received.await;
+                },
+            )
+        });
+
+        let (status, registries) = get(&format!("{base_url}/registries"));
+        assert_eq!(status, 200);
+        assert_eq!(registries[0]["name"], "otel");
+
+        let (status, groups) = get(&format!("{base_url}/groups?type=metric"));
+        assert_eq!(status, 200);
+        let groups = groups.as_array().expect("Expected an array of groups");
+        assert_eq!(groups.len(), 6);
+        assert!(groups.iter().all(|group| group["type"] == "metric"));
+
+        let (status, group) = get(&format!("{base_url}/groups/registry.http?registry=otel"));
+        assert_eq!(status, 200);
+        assert_eq!(group["id"], "registry.http");
+
+        let (status, attribute) = get(&format!("{base_url}/attributes/http.request.method"));
+        assert_eq!(status, 200);
+        assert_eq!(attribute["attribute"]["name"], "http.request.method");
+        assert_eq!(attribute["group_id"], "registry.http");
+        assert!(attribute["referenced_by"]
+            .as_array()
+            .expect("Expected an array of group ids")
+            .contains(&serde_json::json!("attributes.http.common")));
+
+        let (status, matches) = get(&format!("{base_url}/search?q=httpreqmethod&limit=1"));
+        assert_eq!(status, 200);
+        assert_eq!(matches.as_array().map(Vec::len), Some(1));
+        assert_eq!(matches[0]["id"], "http.request.method");
+        assert_eq!(matches[0]["kind"], "attribute");
+
+        let (status, resolved) = get(&format!("{base_url}/resolved"));
+        assert_eq!(status, 200);
+        assert!(resolved["groups"].is_array());
+
+        let (status, openapi) = get(&format!("{base_url}/openapi.json"));
+        assert_eq!(status, 200);
+        assert_eq!(
+            openapi,
+            serde_json::from_str::<serde_json::Value>(OPENAPI).expect("Invalid OpenAPI")
+        );
+
+        // Unknown registries, groups, and attributes.
+        let (status, error) = get(&format!("{base_url}/groups?registry=unknown"));
+        assert_eq!(status, 404);
+        assert_eq!(error["error"], "Unknown registry `unknown`");
+        assert_eq!(get(&format!("{base_url}/groups/unknown")).0, 404);
+        assert_eq!(get(&format!("{base_url}/attributes/unknown")).0, 404);
+
+        shutdown.send(()).expect("Failed to stop the server");
+        server_thread
+            .join()
+            .expect("The server thread panicked")
+            .expect("The server failed");
+    }
+}
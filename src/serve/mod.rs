@@ -0,0 +1,242 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Serve resolved semantic convention registries over HTTP.
+
+use std::collections::HashSet;
+use std::fmt::Display;
+use std::net::{IpAddr, SocketAddr};
+use std::str::FromStr;
+
+use clap::Args;
+use miette::Diagnostic;
+use serde::Serialize;
+
+use weaver_cache::{Cache, CachePolicy};
+use weaver_common::cancellation::CancellationToken;
+use weaver_common::diagnostic::{DiagnosticMessage, DiagnosticMessages};
+use weaver_common::Logger;
+use weaver_forge::registry::ResolvedRegistry;
+use weaver_resolver::file_filter::RegistryFileFilter;
+use weaver_resolver::ResolverOptions;
+use weaver_semconv::registry::SemConvRegistry;
+
+use crate::registry::RegistryPath;
+use crate::serve::api::{RegistryServer, ServedRegistry};
+use crate::util::{
+    load_semconv_specs, resolve_semconv_specs_with_options, semconv_registry_path_from,
+};
+use crate::{CmdResult, DiagnosticArgs, ExitDirectives};
+
+mod api;
+
+/// Errors emitted by the `serve` command
+#[derive(thiserror::Error, Debug, Serialize, Diagnostic)]
+#[non_exhaustive]
+pub enum Error {
+    /// Two registries have the same name.
+    #[error(
+        "Several registries are named `{name}`. Use `--registry <NAME>=<REGISTRY>` to name them."
+    )]
+    DuplicateRegistryName { name: String },
+
+    /// The HTTP server failed.
+    #[error("The HTTP server failed. {error}")]
+    ServerFailed { error: String },
+}
+
+impl From<Error> for DiagnosticMessages {
+    fn from(error: Error) -> Self {
+        DiagnosticMessages::new(vec![DiagnosticMessage::new(error)])
+    }
+}
+
+/// A registry to serve, specified on the command line as `[<NAME>=]<REGISTRY>`.
+#[derive(Debug, Clone)]
+pub struct NamedRegistry {
+    /// The name used to select the registry in the requests.
+    pub name: String,
+    /// Local path or Git URL of the registry.
+    pub registry: RegistryPath,
+}
+
+impl FromStr for NamedRegistry {
+    type Err = String;
+
+    /// Parse a string of the form `[<NAME>=]<REGISTRY>`. The name defaults to
+    /// `default`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let is_name = |name: &str| {
+            !name.is_empty()
+                && name
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.'))
+        };
+        let (name, registry) = match s.split_once('=') {
+            Some((name, registry)) if is_name(name) => (name.to_owned(), registry),
+            _ => ("default".to_owned(), s),
+        };
+        Ok(Self {
+            name,
+            registry: RegistryPath::from_str(registry)?,
+        })
+    }
+}
+
+impl Display for NamedRegistry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}={}", self.name, self.registry)
+    }
+}
+
+/// Parameters for the `serve` command
+#[derive(Debug, Args)]
+pub struct ServeArgs {
+    /// Registry to serve, as `[<NAME>=]<REGISTRY>` where `<REGISTRY>` is a
+    /// local path or Git URL. Repeat the option to serve several registries;
+    /// the first one is the default registry of the endpoints.
+    #[arg(
+        short = 'r',
+        long = "registry",
+        value_name = "[NAME=]REGISTRY",
+        default_value = "https://github.com/open-telemetry/semantic-conventions.git"
+    )]
+    pub registries: Vec<NamedRegistry>,
+
+    /// Optional path in the Git repository where the semantic convention
+    /// registries are located
+    #[arg(short = 'd', long, default_value = "model")]
+    pub registry_git_sub_dir: Option<String>,
+
+    /// Address the HTTP server listens on.
+    #[arg(long, default_value = "127.0.0.1")]
+    pub address: IpAddr,
+
+    /// Port the HTTP server listens on.
+    #[arg(long, default_value = "8080")]
+    pub port: u16,
+
+    /// Maximum number of `extends` clauses followed from a group (e.g. 2 for
+    /// `a` extending `b` extending `c`). Bounded to fail fast on a
+    /// pathological registry instead of keeping the server from starting.
+    #[arg(long, default_value = "32")]
+    pub max_extends_depth: usize,
+
+    /// Maximum number of iterations of each iterative resolution step
+    /// (`extends` clauses, attribute references, and `include` constraints).
+    #[arg(long, default_value = "1000")]
+    pub max_iterations: usize,
+
+    /// Parameters to specify the diagnostic format.
+    #[command(flatten)]
+    pub diagnostic: DiagnosticArgs,
+}
+
+/// Serve the registries and return the exit code.
+#[cfg(not(tarpaulin_include))]
+pub fn serve(
+    log: impl Logger + Sync + Clone,
+    args: &ServeArgs,
+    cache_policy: &CachePolicy,
+) -> CmdResult {
+    let cache = match Cache::try_new_with_policy(*cache_policy) {
+        Ok(cache) => cache,
+        Err(e) => return CmdResult::new(Err(e.into()), None),
+    };
+    CmdResult::new(command(log, &cache, args), Some(args.diagnostic.clone()))
+}
+
+/// Resolve the registries and serve them over HTTP until the process receives
+/// a Ctrl-C signal.
+#[cfg(not(tarpaulin_include))]
+pub(crate) fn command(
+    logger: impl Logger + Sync + Clone,
+    cache: &Cache,
+    args: &ServeArgs,
+) -> Result<ExitDirectives, DiagnosticMessages> {
+    let mut names = HashSet::new();
+    for named_registry in &args.registries {
+        if !names.insert(named_registry.name.as_str()) {
+            return Err(Error::DuplicateRegistryName {
+                name: named_registry.name.clone(),
+            }
+            .into());
+        }
+    }
+
+    let options = ResolverOptions::default()
+        .max_extends_depth(args.max_extends_depth)
+        .max_iterations(args.max_iterations);
+    let mut registries = vec![];
+    for named_registry in &args.registries {
+        logger.loading(&format!("Resolving registry `{}`", named_registry));
+        let registry_id = "default";
+        let registry_path =
+            semconv_registry_path_from(&named_registry.registry, &args.registry_git_sub_dir);
+        let semconv_specs = load_semconv_specs(
+            std::slice::from_ref(&registry_path),
+            &RegistryFileFilter::default(),
+            cache,
+            logger.clone(),
+        )?;
+        let mut registry = SemConvRegistry::from_semconv_specs(registry_id, semconv_specs);
+        let (schema, _) = resolve_semconv_specs_with_options(
+            &mut registry,
+            &options,
+            &CancellationToken::default(),
+            logger.clone(),
+        )?;
+        let resolved_registry = ResolvedRegistry::try_from_resolved_registry(
+            schema
+                .registry(registry_id)
+                .expect("Failed to get the registry from the resolved schema"),
+            schema.catalog(),
+        )?;
+        registries.push(ServedRegistry::new(
+            named_registry.name.clone(),
+            resolved_registry,
+        ));
+    }
+
+    let server = RegistryServer::bind(SocketAddr::new(args.address, args.port))?;
+    logger.log(&format!(
+        "Serving {} registry(ies) on http://{} (OpenAPI description at /openapi.json, stops on Ctrl-C)",
+        registries.len(),
+        server.local_addr()?
+    ));
+    server.run(registries, async {
+        _ = tokio::signal::ctrl_c().await;
+    })?;
+    logger.success("HTTP server stopped");
+
+    Ok(ExitDirectives {
+        exit_code: 0,
+        quiet_mode: false,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use crate::serve::NamedRegistry;
+
+    #[test]
+    fn test_named_registry() {
+        let named = NamedRegistry::from_str("model").expect("Invalid registry");
+        assert_eq!(named.name, "default");
+        assert_eq!(named.registry.to_string(), "model");
+
+        let named = NamedRegistry::from_str("v1.26=semconv/model").expect("Invalid registry");
+        assert_eq!(named.name, "v1.26");
+        assert_eq!(named.registry.to_string(), "semconv/model");
+
+        // URLs containing `=` are not mistaken for names.
+        let named = NamedRegistry::from_str("https://example.com/semconv.git?ref=main")
+            .expect("Invalid registry");
+        assert_eq!(named.name, "default");
+        assert_eq!(
+            named.registry.to_string(),
+            "https://example.com/semconv.git?ref=main"
+        );
+    }
+}
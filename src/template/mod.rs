@@ -0,0 +1,55 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Commands to manage the templates of a target.
+
+mod test;
+
+use clap::{Args, Subcommand};
+
+use weaver_cache::{Cache, CachePolicy};
+use weaver_common::Logger;
+
+use crate::template::test::TemplateTestArgs;
+use crate::CmdResult;
+
+/// Parameters for the `template` command
+#[derive(Debug, Args)]
+pub struct TemplateCommand {
+    /// Define the sub-commands for the `template` command
+    #[clap(subcommand)]
+    pub command: TemplateSubCommand,
+}
+
+/// Sub-commands to manage the templates of a target.
+#[derive(Debug, Subcommand)]
+#[clap(verbatim_doc_comment)]
+pub enum TemplateSubCommand {
+    /// Tests the templates of a target against a fixture registry.
+    ///
+    /// The artifacts generated from the fixture registry are compared with the expected artifacts, and a unified diff is printed for each mismatch. Use `--update` to replace the expected artifacts with the generated ones.
+    ///
+    /// The process exits with a code of 0 if the generated artifacts match the expected artifacts.
+    #[clap(verbatim_doc_comment)]
+    Test(TemplateTestArgs),
+}
+
+/// Manage the templates of a target.
+#[cfg(not(tarpaulin_include))]
+pub fn template(
+    log: impl Logger + Sync + Clone,
+    command: &TemplateCommand,
+    cache_policy: &CachePolicy,
+    debug: bool,
+) -> CmdResult {
+    let cache = match Cache::try_new_with_policy(*cache_policy) {
+        Ok(cache) => cache,
+        Err(e) => return CmdResult::new(Err(e.into()), None),
+    };
+
+    match &command.command {
+        TemplateSubCommand::Test(args) => CmdResult::new(
+            test::command(log.clone(), &cache, args, debug),
+            Some(args.diagnostic.clone()),
+        ),
+    }
+}
@@ -0,0 +1,189 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Test the templates of a target against a fixture registry and expected files.
+
+use std::path::PathBuf;
+
+use clap::Args;
+use serde_yaml::Value;
+
+use weaver_cache::Cache;
+use weaver_common::diagnostic::DiagnosticMessages;
+use weaver_common::Logger;
+use weaver_forge::registry::ResolvedRegistry;
+use weaver_semconv::registry::SemConvRegistry;
+
+use crate::registry::generate::{generate_params, parse_key_val, template_engine};
+use crate::registry::{Error, RegistryArgs};
+use crate::util::{load_semconv_specs, resolve_semconv_specs};
+use crate::{DiagnosticArgs, ExitDirectives};
+
+/// Parameters for the `template test` sub-command
+#[derive(Debug, Args)]
+pub struct TemplateTestArgs {
+    /// Target whose templates are tested.
+    pub target: String,
+
+    /// Path to the directory containing the expected artifacts.
+    /// Default is the `expected_output` directory.
+    #[arg(default_value = "expected_output")]
+    pub expected: PathBuf,
+
+    /// Path to the directory where the templates are located.
+    /// Default is the `templates` directory.
+    #[arg(short = 't', long, default_value = "templates")]
+    pub templates: PathBuf,
+
+    /// Parameters key=value, defined in the command line, to pass to the templates.
+    /// The value must be a valid YAML value.
+    #[arg(short= 'D', long, value_parser = parse_key_val)]
+    pub param: Option<Vec<(String, Value)>>,
+
+    /// Parameters, defined in a YAML file, to pass to the templates.
+    #[arg(long)]
+    pub params: Option<PathBuf>,
+
+    /// Parameters to specify the fixture semantic convention registry.
+    #[command(flatten)]
+    registry: RegistryArgs,
+
+    /// Replace the expected artifacts with the generated artifacts.
+    #[arg(long, default_value = "false")]
+    pub update: bool,
+
+    /// Parameters to specify the diagnostic format.
+    #[command(flatten)]
+    pub diagnostic: DiagnosticArgs,
+}
+
+/// Render the templates of a target against a fixture registry and compare the
+/// generated artifacts with the expected artifacts.
+#[cfg(not(tarpaulin_include))]
+pub(crate) fn command(
+    logger: impl Logger + Sync + Clone,
+    cache: &Cache,
+    args: &TemplateTestArgs,
+    debug: bool,
+) -> Result<ExitDirectives, DiagnosticMessages> {
+    logger.loading(&format!(
+        "Testing the templates of the target `{}` against the registry `{}`",
+        args.target,
+        args.registry.registries()
+    ));
+
+    let params = generate_params(args.params.as_deref(), args.param.as_deref())?;
+    let registry_id = "default";
+    let registry_paths = args.registry.registry_paths();
+    let semconv_specs = load_semconv_specs(
+        &registry_paths,
+        &args.registry.file_filter()?,
+        cache,
+        logger.clone(),
+    )?;
+    let mut registry = SemConvRegistry::from_semconv_specs(registry_id, semconv_specs);
+    let schema = resolve_semconv_specs(&mut registry, logger.clone())?;
+    let engine = template_engine(&args.templates, &args.target, params, debug)?;
+
+    let template_registry = ResolvedRegistry::try_from_resolved_registry(
+        schema
+            .registry(registry_id)
+            .expect("Failed to get the registry from the resolved schema"),
+        schema.catalog(),
+    )?;
+
+    let report = engine.check_expected_output(
+        logger.clone(),
+        &template_registry,
+        args.expected.as_path(),
+        args.update,
+    )?;
+
+    for mismatch in &report.mismatches {
+        print!("{}", mismatch.diff);
+    }
+    if report.updated {
+        logger.success(&format!(
+            "{} expected file(s) updated in {:?}",
+            report.mismatches.len(),
+            args.expected
+        ));
+    } else if report.passed() {
+        logger.success("Generated artifacts match the expected artifacts");
+    } else {
+        return Err(Error::UnexpectedTemplateOutput {
+            expected: args.expected.clone(),
+            mismatches: report.mismatches.len(),
+        }
+        .into());
+    }
+
+    Ok(ExitDirectives {
+        exit_code: 0,
+        quiet_mode: false,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::path::PathBuf;
+
+    use tempdir::TempDir;
+
+    use weaver_common::TestLogger;
+
+    use crate::cli::{Cli, Commands};
+    use crate::registry::{RegistryArgs, RegistryPath};
+    use crate::run_command;
+    use crate::template::test::TemplateTestArgs;
+    use crate::template::{TemplateCommand, TemplateSubCommand};
+
+    #[test]
+    fn test_template_test() {
+        let logger = TestLogger::new();
+        let expected = TempDir::new("expected_output")
+            .expect("Failed to create temporary directory")
+            .into_path();
+        let cli = |update: bool| Cli {
+            debug: 0,
+            quiet: false,
+            cache: Default::default(),
+            command: Some(Commands::Template(TemplateCommand {
+                command: TemplateSubCommand::Test(TemplateTestArgs {
+                    target: "rust".to_owned(),
+                    expected: expected.clone(),
+                    templates: PathBuf::from("crates/weaver_codegen_test/templates/"),
+                    param: None,
+                    params: None,
+                    registry: RegistryArgs {
+                        registry: vec![RegistryPath::Local(
+                            "crates/weaver_codegen_test/semconv_registry/".to_owned(),
+                        )],
+                        registry_git_sub_dir: None,
+                        include: vec![],
+                        exclude: vec![],
+                    },
+                    update,
+                    diagnostic: Default::default(),
+                }),
+            })),
+        };
+
+        // No expected files yet, so the test fails.
+        let exit_directive = run_command(&cli(false), logger.clone());
+        assert_eq!(exit_directive.exit_code, 1);
+
+        // Generate the expected files, then the test passes.
+        let exit_directive = run_command(&cli(true), logger.clone());
+        assert_eq!(exit_directive.exit_code, 0);
+        assert!(expected.join("attributes").join("client.rs").exists());
+        let exit_directive = run_command(&cli(false), logger.clone());
+        assert_eq!(exit_directive.exit_code, 0);
+
+        // A modified expected file makes the test fail.
+        fs::write(expected.join("attributes").join("client.rs"), "// modified")
+            .expect("Failed to write file");
+        let exit_directive = run_command(&cli(false), logger.clone());
+        assert_eq!(exit_directive.exit_code, 1);
+    }
+}
@@ -6,15 +6,24 @@
 use crate::registry::RegistryPath;
 use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
 use serde::Serialize;
+use std::collections::hash_map::Entry;
+use std::collections::{HashMap, HashSet};
 use std::path::PathBuf;
+use std::time::Instant;
+use weaver_cache::oci::OCI_SCHEME;
 use weaver_cache::Cache;
 use weaver_checker::Error::{InvalidPolicyFile, PolicyViolation};
 use weaver_checker::{Engine, Error, PolicyStage};
-use weaver_common::diagnostic::DiagnosticMessages;
+use weaver_common::cancellation::CancellationToken;
+use weaver_common::diagnostic::{DiagnosticMessage, DiagnosticMessages, ResultExt};
 use weaver_common::error::handle_errors;
 use weaver_common::Logger;
+use weaver_forge::registry::ResolvedRegistry;
 use weaver_resolved_schema::ResolvedTelemetrySchema;
-use weaver_resolver::SchemaResolver;
+use weaver_resolver::file_filter::RegistryFileFilter;
+use weaver_resolver::{ResolutionMode, ResolverOptions, SchemaResolver};
+use weaver_semconv::attribute::AttributeSpec;
+use weaver_semconv::manifest::{in_namespace, RegistryManifest};
 use weaver_semconv::registry::SemConvRegistry;
 use weaver_semconv::semconv::SemConvSpec;
 
@@ -29,48 +38,367 @@ pub(crate) fn semconv_registry_path_from(
     path: &Option<String>,
 ) -> weaver_semconv::path::RegistryPath {
     match registry {
+        RegistryPath::Local(path) if is_registry_archive(path) => {
+            weaver_semconv::path::RegistryPath::Archive {
+                archive_path: path.clone(),
+            }
+        }
         RegistryPath::Local(path) => weaver_semconv::path::RegistryPath::Local {
             path_pattern: path.clone(),
         },
+        RegistryPath::Url(url) if url.starts_with(OCI_SCHEME) => {
+            weaver_semconv::path::RegistryPath::Oci {
+                oci_reference: url.clone(),
+            }
+        }
         RegistryPath::Url(url) => weaver_semconv::path::RegistryPath::GitUrl {
             git_url: url.clone(),
             path: path.clone(),
+            git_ref: None,
         },
     }
 }
 
-/// Loads the semantic convention specifications from a registry path.
+/// Returns true if the local path (optionally followed by a verification
+/// fragment) is a registry archive built by `weaver registry package`.
+fn is_registry_archive(path: &str) -> bool {
+    let path = path.split_once('#').map_or(path, |(path, _)| path);
+    path.ends_with(".tar.gz") || path.ends_with(".tgz")
+}
+
+/// Loads the semantic convention specifications from one or several registry
+/// paths. The registries are loaded concurrently, then the specifications of
+/// all the registries are merged, each one keeping its provenance.
 ///
 /// # Arguments
 ///
-/// * `registry_path` - The path to the semantic convention registry.
-/// * `cache` - The cache for loading the registry.
+/// * `registry_paths` - The paths to the semantic convention registries.
+/// * `file_filter` - The filter selecting the files of the local registries.
+/// * `cache` - The cache for loading the registries.
 /// * `log` - The logger for logging messages.
 ///
 /// # Returns
 ///
 /// A `Result` containing a vector of tuples with file names and `SemConvSpec` on success,
-/// or a `weaver_resolver::Error` on failure.
+/// or `DiagnosticMessages` on failure (including the groups and attributes defined by
+/// several registries).
 pub(crate) fn load_semconv_specs(
-    registry_path: &weaver_semconv::path::RegistryPath,
+    registry_paths: &[weaver_semconv::path::RegistryPath],
+    file_filter: &RegistryFileFilter,
     cache: &Cache,
     log: impl Logger + Sync + Clone,
-) -> Result<Vec<(String, SemConvSpec)>, weaver_resolver::Error> {
-    let semconv_specs = SchemaResolver::load_semconv_specs(registry_path, cache)?;
-    log.success(&format!(
-        "SemConv registry loaded ({} files)",
-        semconv_specs.len()
-    ));
+) -> Result<Vec<(String, SemConvSpec)>, DiagnosticMessages> {
+    let (semconv_specs, _) = load_semconv_specs_with_mode(
+        registry_paths,
+        file_filter,
+        ResolutionMode::Strict,
+        cache,
+        log,
+    )?;
     Ok(semconv_specs)
 }
 
+/// Same as [`load_semconv_specs`], with the given resolution mode. In lenient
+/// mode, the files that fail to load are skipped and the registries are made
+/// of their valid files.
+///
+/// # Returns
+///
+/// A `Result` containing the specifications of the valid files and the errors
+/// of the skipped files (reported as warnings) on success, or
+/// `DiagnosticMessages` on failure.
+pub(crate) fn load_semconv_specs_with_mode(
+    registry_paths: &[weaver_semconv::path::RegistryPath],
+    file_filter: &RegistryFileFilter,
+    mode: ResolutionMode,
+    cache: &Cache,
+    log: impl Logger + Sync + Clone,
+) -> Result<(Vec<(String, SemConvSpec)>, DiagnosticMessages), DiagnosticMessages> {
+    let (registries, warnings) =
+        load_registries_with_mode(registry_paths, file_filter, mode, cache, log)?;
+    check_registry_conflicts(&registries)?;
+    let manifests = registry_paths
+        .iter()
+        .map(|registry_path| {
+            let (registry_root, _) = SchemaResolver::path_to_registry(registry_path, cache)?;
+            Ok(RegistryManifest::try_from_registry_root(registry_root)?)
+        })
+        .collect::<Result<Vec<_>, DiagnosticMessages>>()?;
+    check_namespaces(registry_paths, &registries, &manifests)?;
+    Ok((registries.into_iter().flatten().collect(), warnings))
+}
+
+/// Loads the semantic convention specifications of each registry path
+/// concurrently, without checking the conflicts between the registries.
+///
+/// # Returns
+///
+/// A `Result` containing the specifications of each registry (in the order of
+/// the registry paths) on success, or `DiagnosticMessages` on failure.
+pub(crate) fn load_registries(
+    registry_paths: &[weaver_semconv::path::RegistryPath],
+    file_filter: &RegistryFileFilter,
+    cache: &Cache,
+    log: impl Logger + Sync + Clone,
+) -> Result<Vec<Vec<(String, SemConvSpec)>>, DiagnosticMessages> {
+    let (registries, _) = load_registries_with_mode(
+        registry_paths,
+        file_filter,
+        ResolutionMode::Strict,
+        cache,
+        log,
+    )?;
+    Ok(registries)
+}
+
+/// Same as [`load_registries`], with the given resolution mode. The invalid
+/// files of all the registries are reported together: in strict mode as
+/// errors, and in lenient mode as warnings, the invalid files being skipped.
+fn load_registries_with_mode(
+    registry_paths: &[weaver_semconv::path::RegistryPath],
+    file_filter: &RegistryFileFilter,
+    mode: ResolutionMode,
+    cache: &Cache,
+    log: impl Logger + Sync + Clone,
+) -> Result<(Vec<Vec<(String, SemConvSpec)>>, DiagnosticMessages), DiagnosticMessages> {
+    let results: Vec<_> = registry_paths
+        .par_iter()
+        .map(|registry_path| {
+            // Each registry reports its own progress as the fetches are
+            // interleaved.
+            let start = Instant::now();
+            if !matches!(
+                registry_path,
+                weaver_semconv::path::RegistryPath::Local { .. }
+            ) {
+                log.info(&format!("Fetching registry `{registry_path}`"));
+            }
+            let (semconv_specs, errors) = if mode == ResolutionMode::Lenient {
+                SchemaResolver::load_valid_semconv_specs(registry_path, cache, file_filter)?
+            } else {
+                let semconv_specs = SchemaResolver::load_semconv_specs_with_filter(
+                    registry_path,
+                    cache,
+                    file_filter,
+                )?;
+                (semconv_specs, vec![])
+            };
+            if errors.is_empty() {
+                log.success(&format!(
+                    "SemConv registry `{registry_path}` loaded ({} files, {:.1}s)",
+                    semconv_specs.len(),
+                    start.elapsed().as_secs_f32()
+                ));
+            } else {
+                log.warn(&format!(
+                    "SemConv registry `{registry_path}` partially loaded ({} files, {} invalid file(s) skipped, {:.1}s)",
+                    semconv_specs.len(),
+                    errors.len(),
+                    start.elapsed().as_secs_f32()
+                ));
+            }
+            Ok::<_, weaver_resolver::Error>((semconv_specs, errors))
+        })
+        .collect();
+    let mut diag_msgs = DiagnosticMessages::empty();
+    let mut skipped = vec![];
+    let registries: Vec<_> = results
+        .into_iter()
+        .filter_map(|result| result.capture_diag_msgs_into(&mut diag_msgs))
+        .map(|(semconv_specs, errors)| {
+            skipped.extend(errors);
+            semconv_specs
+        })
+        .collect();
+    if !diag_msgs.is_empty() {
+        return Err(diag_msgs);
+    }
+    let warnings = skipped
+        .into_iter()
+        .map(|error| weaver_resolver::Error::IgnoredByLenientMode {
+            error: Box::new(error),
+        })
+        .collect();
+    Ok((registries, DiagnosticMessages::from_errors(warnings)))
+}
+
+/// A group or an attribute defined by several registries.
+#[derive(Debug)]
+pub(crate) struct RegistryConflict {
+    /// The kind of the definition (`group` or `attribute`).
+    pub(crate) kind: &'static str,
+    /// The id of the group or the attribute.
+    pub(crate) id: String,
+    /// The provenance of the first definition.
+    pub(crate) first: String,
+    /// The provenance of the conflicting definition.
+    pub(crate) second: String,
+    /// The index of the registry of the conflicting definition.
+    pub(crate) registry: usize,
+}
+
+/// Returns the groups and the attributes of each registry already defined by
+/// one of the previous registries.
+pub(crate) fn registry_conflicts(
+    registries: &[Vec<(String, SemConvSpec)>],
+) -> Vec<RegistryConflict> {
+    // Maps the (kind, id) of each definition to its registry index and provenance.
+    let mut definitions: HashMap<(&str, String), (usize, &str)> = HashMap::new();
+    let mut conflicts = vec![];
+    for (index, semconv_specs) in registries.iter().enumerate() {
+        for (provenance, spec) in semconv_specs {
+            for group in spec.groups() {
+                let attribute_ids = group.attributes.iter().filter_map(|attr| match attr {
+                    AttributeSpec::Id { id, .. } if group.prefix.is_empty() => Some(id.clone()),
+                    AttributeSpec::Id { id, .. } => Some(format!("{}.{}", group.prefix, id)),
+                    AttributeSpec::Ref { .. } => None,
+                });
+                let ids = std::iter::once(("group", group.id.clone()))
+                    .chain(attribute_ids.map(|id| ("attribute", id)));
+                for (kind, id) in ids {
+                    match definitions.entry((kind, id)) {
+                        Entry::Occupied(entry) if entry.get().0 != index => {
+                            conflicts.push(RegistryConflict {
+                                kind,
+                                id: entry.key().1.clone(),
+                                first: entry.get().1.to_owned(),
+                                second: provenance.clone(),
+                                registry: index,
+                            });
+                        }
+                        Entry::Occupied(_) => {}
+                        Entry::Vacant(entry) => {
+                            _ = entry.insert((index, provenance));
+                        }
+                    }
+                }
+            }
+        }
+    }
+    conflicts
+}
+
+/// Checks that no group and no attribute is defined by more than one of the
+/// merged registries.
+fn check_registry_conflicts(
+    registries: &[Vec<(String, SemConvSpec)>],
+) -> Result<(), DiagnosticMessages> {
+    let errors: Vec<_> = registry_conflicts(registries)
+        .into_iter()
+        .map(|conflict| {
+            DiagnosticMessage::new(crate::registry::Error::ConflictingDefinition {
+                kind: conflict.kind.to_owned(),
+                id: conflict.id,
+                first: conflict.first,
+                second: conflict.second,
+            })
+        })
+        .collect();
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(DiagnosticMessages::new(errors))
+    }
+}
+
+/// Returns the ids of the attributes defined by a registry with the
+/// provenance of their definition.
+fn attribute_definitions(
+    semconv_specs: &[(String, SemConvSpec)],
+) -> impl Iterator<Item = (String, &str)> {
+    semconv_specs.iter().flat_map(|(provenance, spec)| {
+        spec.groups().iter().flat_map(move |group| {
+            group.attributes.iter().filter_map(move |attr| match attr {
+                AttributeSpec::Id { id, .. } if group.prefix.is_empty() => {
+                    Some((id.clone(), provenance.as_str()))
+                }
+                AttributeSpec::Id { id, .. } => {
+                    Some((format!("{}.{}", group.prefix, id), provenance.as_str()))
+                }
+                AttributeSpec::Ref { .. } => None,
+            })
+        })
+    })
+}
+
+/// Checks that the registries declaring namespaces in their manifest only
+/// define attributes under these namespaces, and not under the namespaces
+/// reserved by the other registries. The namespaces reserved by a registry
+/// are the namespaces declared by its manifest or, without manifest, the root
+/// namespaces of its attributes (e.g. `http` for `http.request.method`).
+fn check_namespaces(
+    registry_paths: &[weaver_semconv::path::RegistryPath],
+    registries: &[Vec<(String, SemConvSpec)>],
+    manifests: &[Option<RegistryManifest>],
+) -> Result<(), DiagnosticMessages> {
+    let reserved: Vec<HashSet<String>> = registries
+        .iter()
+        .zip(manifests)
+        .map(|(semconv_specs, manifest)| match manifest {
+            Some(manifest) if !manifest.namespaces.is_empty() => {
+                manifest.namespaces.iter().cloned().collect()
+            }
+            _ => attribute_definitions(semconv_specs)
+                .map(|(id, _)| id.split('.').next().unwrap_or_default().to_owned())
+                .collect(),
+        })
+        .collect();
+
+    let mut errors = vec![];
+    for (index, (semconv_specs, manifest)) in registries.iter().zip(manifests).enumerate() {
+        let Some(manifest) = manifest.as_ref().filter(|m| !m.namespaces.is_empty()) else {
+            continue;
+        };
+        for (attr, provenance) in attribute_definitions(semconv_specs) {
+            if manifest.namespace_of(&attr).is_none() {
+                errors.push(DiagnosticMessage::new(
+                    crate::registry::Error::UndeclaredNamespace {
+                        attr,
+                        namespaces: manifest.namespaces.join(", "),
+                        provenance: provenance.to_owned(),
+                    },
+                ));
+                continue;
+            }
+            let reserved_by = reserved.iter().enumerate().find_map(|(other, namespaces)| {
+                if other == index {
+                    return None;
+                }
+                let mut namespaces: Vec<&String> = namespaces
+                    .iter()
+                    .filter(|namespace| in_namespace(&attr, namespace))
+                    .collect();
+                namespaces.sort();
+                namespaces
+                    .first()
+                    .map(|namespace| (other, (*namespace).clone()))
+            });
+            if let Some((other, namespace)) = reserved_by {
+                errors.push(DiagnosticMessage::new(
+                    crate::registry::Error::ReservedNamespace {
+                        attr,
+                        namespace,
+                        registry: registry_paths[other].to_string(),
+                        provenance: provenance.to_owned(),
+                    },
+                ));
+            }
+        }
+    }
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(DiagnosticMessages::new(errors))
+    }
+}
+
 /// Initializes the policy engine with policies from the registry and command line.
 ///
 /// # Arguments
 ///
-/// * `registry_path` - The path to the semantic convention registry.
-/// * `cache` - The cache for loading the registry.
-/// * `policies` - A list of paths to policy files.
+/// * `registry_paths` - The paths to the semantic convention registries.
+/// * `cache` - The cache for loading the registries.
+/// * `policies` - A list of paths to policy files (Rego files or WASM modules).
 /// * `policy_coverage` - A flag to enable policy coverage.
 ///
 /// # Returns
@@ -78,7 +406,7 @@ pub(crate) fn load_semconv_specs(
 /// A `Result` containing the initialized `Engine` on success, or `DiagnosticMessages`
 /// on failure.
 pub(crate) fn init_policy_engine(
-    registry_path: &weaver_semconv::path::RegistryPath,
+    registry_paths: &[weaver_semconv::path::RegistryPath],
     cache: &Cache,
     policies: &[PathBuf],
     policy_coverage: bool,
@@ -89,13 +417,22 @@ pub(crate) fn init_policy_engine(
         engine.enable_coverage();
     }
 
-    // Add policies from the registry
-    let (registry_path, _) = SchemaResolver::path_to_registry(registry_path, cache)?;
-    _ = engine.add_policies(registry_path.as_path(), "*.rego")?;
+    // Add policies from the registries
+    for registry_path in registry_paths {
+        let (registry_path, _) = SchemaResolver::path_to_registry(registry_path, cache)?;
+        _ = engine.add_policies(registry_path.as_path(), "*.rego")?;
+    }
 
-    // Add policies from the command line
+    // Add policies from the command line (Rego files, WASM modules, or
+    // directories of Rego files)
     for policy in policies {
-        _ = engine.add_policy(policy)?;
+        if policy.is_dir() {
+            _ = engine.add_policies(policy, "*.rego")?;
+        } else if policy.extension().is_some_and(|ext| ext == "wasm") {
+            engine.add_wasm_policy(policy)?;
+        } else {
+            _ = engine.add_policy(policy)?;
+        }
     }
 
     Ok(engine)
@@ -212,6 +549,45 @@ pub(crate) fn check_policies(
     Ok(())
 }
 
+/// Checks the policies of the `after_resolution` stage against the resolved
+/// registry. These policies can detect violations spanning several groups or
+/// files of the registry.
+///
+/// # Arguments
+///
+/// * `policy_engine` - The policy engine.
+/// * `registry_paths` - The paths of the semantic convention registries (used as provenance).
+/// * `resolved_registry` - The resolved registry to check.
+/// * `logger` - The logger for logging messages.
+///
+/// # Returns
+///
+/// A `Result` which is `Ok` if all policies are checked successfully,
+/// or `DiagnosticMessages` if any policy violations occur.
+pub(crate) fn check_resolved_policies(
+    policy_engine: &mut Engine,
+    registry_paths: &[weaver_semconv::path::RegistryPath],
+    resolved_registry: &ResolvedRegistry,
+    logger: impl Logger + Sync + Clone,
+) -> Result<(), DiagnosticMessages> {
+    if !policy_engine.has_policies(PolicyStage::AfterResolution) {
+        return Ok(());
+    }
+    let errors = check_policy_stage(
+        policy_engine,
+        PolicyStage::AfterResolution,
+        &registry_paths
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join(", "),
+        resolved_registry,
+    );
+    handle_errors(errors).map_err(DiagnosticMessages::from)?;
+    logger.success("Resolved registry policies checked");
+    Ok(())
+}
+
 /// Resolves the semantic convention specifications and returns the resolved schema.
 ///
 /// # Arguments
@@ -227,8 +603,368 @@ pub(crate) fn resolve_semconv_specs(
     registry: &mut SemConvRegistry,
     logger: impl Logger + Sync + Clone,
 ) -> Result<ResolvedTelemetrySchema, DiagnosticMessages> {
-    let resolved_schema = SchemaResolver::resolve_semantic_convention_registry(registry)?;
-
-    logger.success("SemConv registry resolved");
+    let (resolved_schema, _) = resolve_semconv_specs_with_options(
+        registry,
+        &ResolverOptions::default(),
+        &CancellationToken::default(),
+        logger,
+    )?;
     Ok(resolved_schema)
 }
+
+/// Same as [`resolve_semconv_specs`], with the given resolution options. The
+/// resolution fails once the given cancellation token is cancelled or timed
+/// out.
+///
+/// # Returns
+///
+/// A `Result` containing the `ResolvedTelemetrySchema` and the warnings of the
+/// resolution (i.e. the references to deprecated items and the errors ignored
+/// by the lenient resolution mode) on
+/// success, or `DiagnosticMessages` on failure.
+pub(crate) fn resolve_semconv_specs_with_options(
+    registry: &mut SemConvRegistry,
+    options: &ResolverOptions,
+    cancellation: &CancellationToken,
+    logger: impl Logger + Sync + Clone,
+) -> Result<(ResolvedTelemetrySchema, DiagnosticMessages), DiagnosticMessages> {
+    let (resolved_schema, warnings) =
+        SchemaResolver::resolve_semantic_convention_registry_with_options(
+            registry,
+            options,
+            cancellation,
+        )?;
+
+    if warnings.is_empty() {
+        logger.success("SemConv registry resolved");
+    } else {
+        logger.warn(&format!(
+            "SemConv registry resolved with {} warning(s)",
+            warnings.len()
+        ));
+    }
+    Ok((resolved_schema, DiagnosticMessages::from_errors(warnings)))
+}
+
+/// Loads and resolves several independent registries (e.g. a baseline and the
+/// current version of a registry) concurrently. Each registry is made of the
+/// merged registry paths and is filtered by its file filter.
+///
+/// # Returns
+///
+/// A `Result` containing the semantic convention registries and their resolved
+/// schemas, in the order of the given registries, or the `DiagnosticMessages`
+/// of all the registries that failed to load or to resolve.
+pub(crate) fn resolve_registries(
+    registries: &[(&[weaver_semconv::path::RegistryPath], &RegistryFileFilter)],
+    cache: &Cache,
+    logger: impl Logger + Sync + Clone,
+) -> Result<Vec<(SemConvRegistry, ResolvedTelemetrySchema)>, DiagnosticMessages> {
+    let (resolved, _) =
+        resolve_registries_with_options(registries, &ResolverOptions::default(), cache, logger)?;
+    Ok(resolved)
+}
+
+/// Same as [`resolve_registries`], with the given resolution options. The
+/// warnings of the loading and the resolution of all the registries are
+/// returned along with the resolved registries.
+pub(crate) fn resolve_registries_with_options(
+    registries: &[(&[weaver_semconv::path::RegistryPath], &RegistryFileFilter)],
+    options: &ResolverOptions,
+    cache: &Cache,
+    logger: impl Logger + Sync + Clone,
+) -> Result<
+    (
+        Vec<(SemConvRegistry, ResolvedTelemetrySchema)>,
+        DiagnosticMessages,
+    ),
+    DiagnosticMessages,
+> {
+    let registry_id = "default";
+    let results: Vec<_> = registries
+        .par_iter()
+        .map(|(registry_paths, file_filter)| {
+            let (semconv_specs, mut warnings) = load_semconv_specs_with_mode(
+                registry_paths,
+                file_filter,
+                options.mode,
+                cache,
+                logger.clone(),
+            )?;
+            let mut registry = SemConvRegistry::from_semconv_specs(registry_id, semconv_specs);
+            let (schema, resolution_warnings) = resolve_semconv_specs_with_options(
+                &mut registry,
+                options,
+                &CancellationToken::default(),
+                logger.clone(),
+            )?;
+            warnings.extend(resolution_warnings);
+            Ok::<_, DiagnosticMessages>((registry, schema, warnings))
+        })
+        .collect();
+    let mut diag_msgs = DiagnosticMessages::empty();
+    let mut warnings = DiagnosticMessages::empty();
+    let resolved: Vec<_> = results
+        .into_iter()
+        .filter_map(|result| result.capture_diag_msgs_into(&mut diag_msgs))
+        .map(|(registry, schema, registry_warnings)| {
+            warnings.extend(registry_warnings);
+            (registry, schema)
+        })
+        .collect();
+    if diag_msgs.is_empty() {
+        Ok((resolved, warnings))
+    } else {
+        Err(diag_msgs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tempdir::TempDir;
+    use weaver_cache::Cache;
+    use weaver_common::TestLogger;
+    use weaver_resolver::file_filter::RegistryFileFilter;
+    use weaver_resolver::{ResolutionMode, SchemaResolver};
+    use weaver_semconv::path::RegistryPath;
+    use weaver_semconv::registry::SemConvRegistry;
+
+    use crate::util::{load_semconv_specs, load_semconv_specs_with_mode, resolve_registries};
+
+    #[test]
+    fn test_load_merged_registries() {
+        let cache = Cache::try_new().expect("Failed to create the cache");
+        let logger = TestLogger::new();
+        let core = RegistryPath::Local {
+            path_pattern: "crates/weaver_codegen_test/semconv_registry/".to_owned(),
+        };
+        let core_only = load_semconv_specs(
+            std::slice::from_ref(&core),
+            &RegistryFileFilter::default(),
+            &cache,
+            logger.clone(),
+        )
+        .expect("Failed to load the registry");
+
+        let vendor_dir = TempDir::new("vendor").expect("Failed to create the temp dir");
+        std::fs::write(
+            vendor_dir.path().join("vendor.yaml"),
+            r#"groups:
+  - id: registry.vendor
+    type: attribute_group
+    brief: Vendor attributes.
+    prefix: vendor
+    attributes:
+      - id: tenant
+        type: string
+        brief: The tenant.
+        examples: [acme]
+        stability: experimental
+"#,
+        )
+        .expect("Failed to write the vendor registry");
+        let vendor = RegistryPath::Local {
+            path_pattern: vendor_dir.path().display().to_string(),
+        };
+        let merged = load_semconv_specs(
+            &[core.clone(), vendor.clone()],
+            &RegistryFileFilter::default(),
+            &cache,
+            logger.clone(),
+        )
+        .expect("Failed to merge the registries");
+        assert_eq!(merged.len(), core_only.len() + 1);
+        // The provenance of each specification is preserved.
+        assert!(merged
+            .iter()
+            .any(|(provenance, _)| provenance.ends_with("vendor.yaml")));
+
+        // The groups and attributes of the core registry are redefined.
+        std::fs::write(
+            vendor_dir.path().join("conflict.yaml"),
+            std::fs::read_to_string("crates/weaver_codegen_test/semconv_registry/http-common.yaml")
+                .expect("Failed to read the core registry"),
+        )
+        .expect("Failed to write the vendor registry");
+        let diag_msgs = load_semconv_specs(
+            &[core, vendor],
+            &RegistryFileFilter::default(),
+            &cache,
+            logger,
+        )
+        .expect_err("The registries should conflict");
+        // One conflict per group of `http-common.yaml` (its attributes are references).
+        assert_eq!(diag_msgs.len(), 3);
+    }
+
+    #[test]
+    fn test_partial_load() {
+        let cache = Cache::try_new().expect("Failed to create the cache");
+        let logger = TestLogger::new();
+        let registry_dir = TempDir::new("partial").expect("Failed to create the temp dir");
+        std::fs::write(
+            registry_dir.path().join("valid.yaml"),
+            r#"groups:
+  - id: registry.acme
+    type: attribute_group
+    brief: Acme attributes.
+    prefix: acme
+    attributes:
+      - id: tenant
+        type: string
+        brief: The tenant.
+        examples: [acme]
+        stability: experimental
+"#,
+        )
+        .expect("Failed to write the valid file");
+        for file in ["invalid1.yaml", "invalid2.yaml"] {
+            std::fs::write(
+                registry_dir.path().join(file),
+                "groups:
+  - id: [broken
+",
+            )
+            .expect("Failed to write the invalid file");
+        }
+        let registry = [RegistryPath::Local {
+            path_pattern: registry_dir.path().display().to_string(),
+        }];
+        let file_filter = RegistryFileFilter::default();
+
+        // The errors of all the invalid files are reported together.
+        let diag_msgs = load_semconv_specs(&registry, &file_filter, &cache, logger.clone())
+            .expect_err("The registry should fail to load");
+        assert_eq!(diag_msgs.len(), 2);
+
+        // The lenient mode proceeds with the valid files.
+        let (specs, warnings) = load_semconv_specs_with_mode(
+            &registry,
+            &file_filter,
+            ResolutionMode::Lenient,
+            &cache,
+            logger,
+        )
+        .expect("The valid files should be loaded");
+        assert_eq!(specs.len(), 1);
+        assert!(specs[0].0.ends_with("valid.yaml"));
+        assert_eq!(warnings.len(), 2);
+    }
+
+    #[test]
+    fn test_namespace_reservation() {
+        let cache = Cache::try_new().expect("Failed to create the cache");
+        let logger = TestLogger::new();
+        let core = RegistryPath::Local {
+            path_pattern: "crates/weaver_codegen_test/semconv_registry/".to_owned(),
+        };
+        let vendor_dir = TempDir::new("vendor").expect("Failed to create the temp dir");
+        let vendor = RegistryPath::Local {
+            path_pattern: vendor_dir.path().display().to_string(),
+        };
+        std::fs::write(
+            vendor_dir.path().join("registry_manifest.yaml"),
+            "name: acme\nnamespaces: [acme, http]\n",
+        )
+        .expect("Failed to write the manifest");
+        let load = |attributes: &str| {
+            std::fs::write(
+                vendor_dir.path().join("vendor.yaml"),
+                format!(
+                    r#"groups:
+  - id: registry.acme
+    type: attribute_group
+    brief: Vendor attributes.
+    attributes:
+{attributes}"#
+                ),
+            )
+            .expect("Failed to write the vendor registry");
+            load_semconv_specs(
+                &[core.clone(), vendor.clone()],
+                &RegistryFileFilter::default(),
+                &cache,
+                logger.clone(),
+            )
+        };
+        let attribute = |id: &str| {
+            format!("      - id: {id}\n        type: string\n        brief: An attribute.\n        examples: [x]\n")
+        };
+
+        // The manifest is not loaded as a semantic convention file.
+        assert!(load(&attribute("acme.tenant")).is_ok());
+
+        let diag_msgs = load(&format!(
+            "{}{}{}",
+            attribute("acme.tenant"),
+            attribute("acmecorp.tenant"),
+            attribute("http.tenant")
+        ))
+        .expect_err("Namespace violations expected");
+        let errors: Vec<_> = diag_msgs
+            .into_inner()
+            .iter()
+            .map(|msg| msg.error().clone())
+            .collect();
+        assert_eq!(errors.len(), 2);
+        assert_eq!(errors[0]["UndeclaredNamespace"]["attr"], "acmecorp.tenant");
+        assert_eq!(errors[0]["UndeclaredNamespace"]["namespaces"], "acme, http");
+        assert_eq!(errors[1]["ReservedNamespace"]["attr"], "http.tenant");
+        assert_eq!(errors[1]["ReservedNamespace"]["namespace"], "http");
+    }
+
+    #[test]
+    fn test_resolve_registries() {
+        let cache = Cache::try_new().expect("Failed to create the cache");
+        let logger = TestLogger::new();
+        let file_filter = RegistryFileFilter::default();
+        let head = [RegistryPath::Local {
+            path_pattern: "crates/weaver_codegen_test/semconv_registry/".to_owned(),
+        }];
+        let baseline = [RegistryPath::Local {
+            path_pattern: "crates/weaver_resolver/data/registry-test-7-spans/registry/".to_owned(),
+        }];
+
+        // The resolved registries are returned in the order of the registries.
+        let resolved = resolve_registries(
+            &[(&baseline, &file_filter), (&head, &file_filter)],
+            &cache,
+            logger.clone(),
+        )
+        .expect("Failed to resolve the registries");
+        assert_eq!(resolved.len(), 2);
+        for ((_, schema), registry_paths) in resolved.iter().zip([&baseline, &head]) {
+            let specs = load_semconv_specs(registry_paths, &file_filter, &cache, logger.clone())
+                .expect("Failed to load the registry");
+            let mut registry = SemConvRegistry::from_semconv_specs("default", specs);
+            let expected = SchemaResolver::resolve_semantic_convention_registry(&mut registry)
+                .expect("Failed to resolve the registry");
+            assert!(schema.semantic_eq(&expected));
+        }
+
+        // The errors of all the failing registries are reported.
+        let missing = [RegistryPath::Local {
+            path_pattern: "does-not-exist/".to_owned(),
+        }];
+        let invalid = TempDir::new("invalid").expect("Failed to create the temp dir");
+        std::fs::write(
+            invalid.path().join("invalid.yaml"),
+            "groups:\n  - id: span.invalid\n    type: span\n    brief: Invalid.\n    extends: unknown\n",
+        )
+        .expect("Failed to write the invalid registry");
+        let invalid = [RegistryPath::Local {
+            path_pattern: invalid.path().display().to_string(),
+        }];
+        let diag_msgs = resolve_registries(
+            &[
+                (&missing, &file_filter),
+                (&head, &file_filter),
+                (&invalid, &file_filter),
+            ],
+            &cache,
+            logger,
+        )
+        .expect_err("The registries should fail to resolve");
+        assert_eq!(diag_msgs.len(), 2);
+    }
+}
@@ -49,3 +49,71 @@ fn test_cli_interface() {
     // - 9 violations for http after resolution
     assert_eq!(json_value.len(), 25);
 }
+
+/// This test checks the `gh-workflow` diagnostic format, printing a GitHub
+/// Actions workflow command per finding.
+/// This test doesn't count for the coverage report as it runs a separate process.
+#[test]
+fn test_gh_workflow_diagnostic_format() {
+    let mut cmd = Command::cargo_bin("weaver").unwrap();
+    let output = cmd
+        .arg("--quiet")
+        .arg("registry")
+        .arg("check")
+        .arg("-r")
+        .arg("crates/weaver_codegen_test/semconv_registry/")
+        .arg("--diagnostic-format")
+        .arg("gh-workflow")
+        .timeout(std::time::Duration::from_secs(60))
+        .output()
+        .expect("failed to execute process");
+
+    assert!(!output.status.success());
+
+    let stdout = String::from_utf8(output.stdout).expect("Invalid UTF-8");
+    let commands: Vec<&str> = stdout.lines().filter(|line| !line.is_empty()).collect();
+    assert_eq!(commands.len(), 25);
+    for command in commands {
+        assert!(
+            command.starts_with("::error file="),
+            "Unexpected workflow command: {command}"
+        );
+        assert!(command.contains(",title="));
+    }
+}
+
+/// This test checks the `ndjson` diagnostic format, printing a JSON object per
+/// finding in the Reviewdog Diagnostic Format.
+/// This test doesn't count for the coverage report as it runs a separate process.
+#[test]
+fn test_ndjson_diagnostic_format() {
+    let mut cmd = Command::cargo_bin("weaver").unwrap();
+    let output = cmd
+        .arg("--quiet")
+        .arg("registry")
+        .arg("check")
+        .arg("-r")
+        .arg("crates/weaver_codegen_test/semconv_registry/")
+        .arg("--diagnostic-format")
+        .arg("ndjson")
+        .timeout(std::time::Duration::from_secs(60))
+        .output()
+        .expect("failed to execute process");
+
+    assert!(!output.status.success());
+
+    let stdout = String::from_utf8(output.stdout).expect("Invalid UTF-8");
+    let diagnostics: Vec<serde_json::Value> = stdout
+        .lines()
+        .filter(|line| !line.is_empty())
+        .map(|line| serde_json::from_str(line).expect("Invalid JSON"))
+        .collect();
+    assert_eq!(diagnostics.len(), 25);
+    for diagnostic in diagnostics {
+        assert_eq!(diagnostic["severity"], "ERROR");
+        assert_eq!(diagnostic["source"]["name"], "weaver");
+        assert!(diagnostic["code"]["value"].is_string());
+        assert!(diagnostic["message"].is_string());
+        assert!(diagnostic["location"]["path"].is_string());
+    }
+}
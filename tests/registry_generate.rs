@@ -2,7 +2,13 @@
 
 //! Test the registry generate command.
 
+use std::fs;
+use std::path::Path;
+
 use assert_cmd::Command;
+use tempdir::TempDir;
+use walkdir::WalkDir;
+use weaver_diff::unified_diff_dir;
 
 /// This test checks the CLI interface for the registry generate command.
 /// This test doesn't count for the coverage report as it runs a separate process.
@@ -47,3 +53,64 @@ fn test_cli_interface() {
     // We expect 13 policy violations.
     assert_eq!(json_value.len(), 13);
 }
+
+/// This test checks that a dry run prints the diff of the artifacts that would be
+/// generated without writing anything to the output directory.
+/// This test doesn't count for the coverage report as it runs a separate process.
+#[test]
+fn test_dry_run() {
+    let dry_run_dir = TempDir::new("weaver-dry-run-output").expect("Failed to create temp dir");
+    let expected_dir = TempDir::new("weaver-dry-run-expected").expect("Failed to create temp dir");
+    let generate = |output: &Path, dry_run: bool| {
+        let mut cmd = Command::cargo_bin("weaver").unwrap();
+        _ = cmd
+            .arg("--quiet")
+            .arg("registry")
+            .arg("generate")
+            .arg("-r")
+            .arg("crates/weaver_codegen_test/semconv_registry/")
+            .arg("-t")
+            .arg("crates/weaver_codegen_test/templates/")
+            .arg("--skip-policies");
+        if dry_run {
+            _ = cmd.arg("--dry-run");
+        }
+        cmd.arg("rust")
+            .arg(output)
+            .timeout(std::time::Duration::from_secs(60))
+            .output()
+            .expect("failed to execute process")
+    };
+
+    // A stale file in the output directory must be left untouched.
+    let stale_file = dry_run_dir.path().join("attributes").join("client.rs");
+    fs::create_dir_all(stale_file.parent().unwrap()).expect("Failed to create directory");
+    fs::write(&stale_file, "// stale\n").expect("Failed to write stale file");
+
+    let output = generate(dry_run_dir.path(), true);
+    assert!(output.status.success());
+    let files: Vec<_> = WalkDir::new(dry_run_dir.path())
+        .into_iter()
+        .filter_map(Result::ok)
+        .filter(|entry| entry.file_type().is_file())
+        .map(|entry| entry.into_path())
+        .collect();
+    assert_eq!(files, vec![stale_file.clone()]);
+    assert_eq!(
+        fs::read_to_string(&stale_file).expect("Failed to read stale file"),
+        "// stale\n"
+    );
+
+    // The printed diff is the diff between the output directory and the artifacts
+    // that a real generation produces.
+    let output_expected = generate(expected_dir.path(), false);
+    assert!(output_expected.status.success());
+    let expected_diff: String = unified_diff_dir(dry_run_dir.path(), expected_dir.path())
+        .expect("Failed to diff the directories")
+        .into_iter()
+        .map(|(_, diff)| diff)
+        .collect();
+    assert!(expected_diff.contains("-// stale"));
+    let stdout = String::from_utf8(output.stdout).expect("Invalid UTF-8");
+    assert_eq!(stdout, expected_diff);
+}
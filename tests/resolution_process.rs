@@ -39,6 +39,7 @@ fn test_cli_interface() {
     let registry_path = RegistryPath::GitUrl {
         git_url: SEMCONV_REGISTRY_URL.to_owned(),
         path: Some(SEMCONV_REGISTRY_MODEL.to_owned()),
+        git_ref: None,
     };
     let semconv_specs =
         SchemaResolver::load_semconv_specs(&registry_path, &cache).unwrap_or_else(|e| {